@@ -1,10 +1,17 @@
 use std::env;
+use std::process::Command;
 
-use pyo3_build_config::pyo3_build_script_impl::{cargo_env_var, errors::Result};
+use pyo3_build_config::pyo3_build_script_impl::{
+    cargo_env_var,
+    errors::{Context, Result},
+};
 use pyo3_build_config::{bail, print_feature_cfgs, InterpreterConfig};
 
 fn ensure_auto_initialize_ok(interpreter_config: &InterpreterConfig) -> Result<()> {
-    if cargo_env_var("CARGO_FEATURE_AUTO_INITIALIZE").is_some() && !interpreter_config.shared {
+    let auto_initialize = cargo_env_var("CARGO_FEATURE_AUTO_INITIALIZE").is_some()
+        || (cargo_env_var("CARGO_FEATURE_AUTO_INITIALIZE_EMBEDDED").is_some()
+            && cargo_env_var("CARGO_FEATURE_EXTENSION_MODULE").is_none());
+    if auto_initialize && !interpreter_config.shared {
         bail!(
             "The `auto-initialize` feature is enabled, but your python installation only supports \
             embedding the Python interpreter statically. If you are attempting to run tests, or a \
@@ -23,6 +30,20 @@ fn ensure_auto_initialize_ok(interpreter_config: &InterpreterConfig) -> Result<(
     Ok(())
 }
 
+/// Returns the output of `rustc --version` for the compiler building PyO3.
+fn rustc_version() -> Result<String> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .context("failed to run rustc")?;
+    if !output.status.success() {
+        bail!("failed to query the version of rustc");
+    }
+    let version = String::from_utf8(output.stdout).context("rustc version is not UTF-8")?;
+    Ok(version.trim().to_owned())
+}
+
 /// Prepares the PyO3 crate for compilation.
 ///
 /// This loads the config from pyo3-build-config and then makes some additional checks to improve UX
@@ -42,6 +63,10 @@ fn configure_pyo3() -> Result<()> {
     // Emit cfgs like `thread_local_const_init`
     print_feature_cfgs();
 
+    // The layout of `#[pyclass(shared = ...)]` types is only guaranteed to match between
+    // modules built with the same compiler.
+    println!("cargo:rustc-env=PYO3_RUSTC_VERSION={}", rustc_version()?);
+
     Ok(())
 }
 
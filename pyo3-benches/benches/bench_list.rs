@@ -55,6 +55,14 @@ fn list_get_item_unchecked(b: &mut Bencher<'_>) {
     });
 }
 
+fn extract_vec_from_list(b: &mut Bencher<'_>) {
+    Python::with_gil(|py| {
+        const LEN: usize = 50_000;
+        let list = PyList::new_bound(py, 0..LEN);
+        b.iter(|| black_box(&list).extract::<Vec<usize>>().unwrap());
+    });
+}
+
 fn sequence_from_list(b: &mut Bencher<'_>) {
     Python::with_gil(|py| {
         const LEN: usize = 50_000;
@@ -69,6 +77,7 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("list_get_item", list_get_item);
     #[cfg(not(Py_LIMITED_API))]
     c.bench_function("list_get_item_unchecked", list_get_item_unchecked);
+    c.bench_function("extract_vec_from_list", extract_vec_from_list);
     c.bench_function("sequence_from_list", sequence_from_list);
 }
 
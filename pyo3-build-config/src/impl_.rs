@@ -22,7 +22,7 @@ use std::{
 
 pub use target_lexicon::Triple;
 
-use target_lexicon::{Environment, OperatingSystem};
+use target_lexicon::{Architecture, Environment, OperatingSystem};
 
 use crate::{
     bail, ensure,
@@ -737,6 +737,12 @@ fn is_linking_libpython_for_target(target: &Triple) -> bool {
         || !is_extension_module()
 }
 
+/// Checks if the target is `wasm32-unknown-emscripten`, as used by Pyodide.
+fn is_emscripten_target(target: &Triple) -> bool {
+    target.architecture == Architecture::Wasm32
+        && target.operating_system == OperatingSystem::Emscripten
+}
+
 /// Checks if we need to discover the Python library directory
 /// to link the extension module binary.
 ///
@@ -959,6 +965,7 @@ pub enum BuildFlag {
     Py_REF_DEBUG,
     Py_TRACE_REFS,
     COUNT_ALLOCS,
+    Py_GIL_DISABLED,
     Other(String),
 }
 
@@ -979,6 +986,7 @@ impl FromStr for BuildFlag {
             "Py_REF_DEBUG" => Ok(BuildFlag::Py_REF_DEBUG),
             "Py_TRACE_REFS" => Ok(BuildFlag::Py_TRACE_REFS),
             "COUNT_ALLOCS" => Ok(BuildFlag::COUNT_ALLOCS),
+            "Py_GIL_DISABLED" => Ok(BuildFlag::Py_GIL_DISABLED),
             other => Ok(BuildFlag::Other(other.to_owned())),
         }
     }
@@ -1001,11 +1009,12 @@ impl FromStr for BuildFlag {
 pub struct BuildFlags(pub HashSet<BuildFlag>);
 
 impl BuildFlags {
-    const ALL: [BuildFlag; 4] = [
+    const ALL: [BuildFlag; 5] = [
         BuildFlag::Py_DEBUG,
         BuildFlag::Py_REF_DEBUG,
         BuildFlag::Py_TRACE_REFS,
         BuildFlag::COUNT_ALLOCS,
+        BuildFlag::Py_GIL_DISABLED,
     ];
 
     pub fn new() -> Self {
@@ -1397,7 +1406,8 @@ fn default_cross_compile(cross_compile_config: &CrossCompileConfig) -> Result<In
     Ok(InterpreterConfig {
         implementation,
         version,
-        shared: true,
+        // CPython for Emscripten (e.g. Pyodide) only provides a static libpython
+        shared: !is_emscripten_target(&cross_compile_config.target),
         abi3,
         lib_name,
         lib_dir,
@@ -2210,6 +2220,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn emscripten_hardcoded_cross_compile() {
+        let env_vars = CrossCompileEnvVars {
+            pyo3_cross: None,
+            pyo3_cross_lib_dir: Some("/pyodide/lib".into()),
+            pyo3_cross_python_implementation: None,
+            pyo3_cross_python_version: Some("3.11".into()),
+        };
+
+        let host = triple!("x86_64-unknown-linux-gnu");
+        let target = triple!("wasm32-unknown-emscripten");
+        let cross_config =
+            CrossCompileConfig::try_from_env_vars_host_target(env_vars, &host, &target)
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(
+            default_cross_compile(&cross_config).unwrap(),
+            InterpreterConfig {
+                implementation: PythonImplementation::CPython,
+                version: PythonVersion {
+                    major: 3,
+                    minor: 11
+                },
+                shared: false,
+                abi3: false,
+                lib_name: Some("python3.11".into()),
+                lib_dir: Some("/pyodide/lib".into()),
+                executable: None,
+                pointer_width: None,
+                build_flags: BuildFlags::default(),
+                suppress_build_script_link_lines: false,
+                extra_build_script_lines: vec![],
+            }
+        );
+    }
+
     #[test]
     fn pypy_hardcoded_cross_compile() {
         let env_vars = CrossCompileEnvVars {
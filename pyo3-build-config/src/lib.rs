@@ -144,11 +144,21 @@ pub fn print_feature_cfgs() {
 
     let rustc_minor_version = rustc_minor_version().unwrap_or(0);
 
+    // Panicking in const fn was stabilized in Rust 1.57
+    if rustc_minor_version >= 57 {
+        println!("cargo:rustc-cfg=const_panic");
+    }
+
     // Enable use of const initializer for thread_local! on Rust 1.59 and greater
     if rustc_minor_version >= 59 {
         println!("cargo:rustc-cfg=thread_local_const_init");
     }
 
+    // std::backtrace was stabilized in Rust 1.65
+    if rustc_minor_version >= 65 {
+        println!("cargo:rustc-cfg=std_backtrace");
+    }
+
     // invalid_from_utf8 lint was added in Rust 1.74
     if rustc_minor_version >= 74 {
         println!("cargo:rustc-cfg=invalid_from_utf8_lint");
@@ -4,7 +4,7 @@ use pyo3_build_config::{
         cargo_env_var, env_var, errors::Result, is_linking_libpython, resolve_interpreter_config,
         InterpreterConfig, PythonVersion,
     },
-    PythonImplementation,
+    BuildFlag, PythonImplementation,
 };
 
 /// Minimum Python version PyO3 supports.
@@ -78,6 +78,26 @@ fn ensure_python_version(interpreter_config: &InterpreterConfig) -> Result<()> {
     Ok(())
 }
 
+fn is_free_threaded(interpreter_config: &InterpreterConfig) -> bool {
+    interpreter_config
+        .build_flags
+        .0
+        .contains(&BuildFlag::Py_GIL_DISABLED)
+}
+
+fn ensure_free_threaded_ok(interpreter_config: &InterpreterConfig) -> Result<()> {
+    ensure!(
+        !interpreter_config.abi3,
+        "the configured Python interpreter is a free-threaded build, which does not support the limited API (abi3)\n\
+         = help: disable the `abi3` feature, or build with a Python interpreter which has the GIL"
+    );
+    println!(
+        "cargo:warning=Support for the free-threaded build of Python ({}t) is experimental",
+        interpreter_config.version
+    );
+    Ok(())
+}
+
 fn ensure_target_pointer_width(interpreter_config: &InterpreterConfig) -> Result<()> {
     if let Some(pointer_width) = interpreter_config.pointer_width {
         // Try to check whether the target architecture matches the python library
@@ -141,7 +161,13 @@ fn configure_pyo3() -> Result<()> {
         print_config_and_exit(&interpreter_config);
     }
 
-    ensure_python_version(&interpreter_config)?;
+    // Free-threaded builds only exist for versions newer than PyO3's maximum supported version,
+    // so they are not subject to the version check while their support is experimental.
+    if is_free_threaded(&interpreter_config) {
+        ensure_free_threaded_ok(&interpreter_config)?;
+    } else {
+        ensure_python_version(&interpreter_config)?;
+    }
     ensure_target_pointer_width(&interpreter_config)?;
 
     // Serialize the whole interpreter config into DEP_PYTHON_PYO3_CONFIG env var.
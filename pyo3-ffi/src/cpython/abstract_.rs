@@ -48,8 +48,9 @@ const PY_VECTORCALL_ARGUMENTS_OFFSET: Py_ssize_t =
 #[cfg(Py_3_8)]
 #[inline(always)]
 pub unsafe fn PyVectorcall_NARGS(n: size_t) -> Py_ssize_t {
+    let n = n & !(PY_VECTORCALL_ARGUMENTS_OFFSET as size_t);
     assert!(n <= (PY_SSIZE_T_MAX as size_t));
-    (n as Py_ssize_t) & !PY_VECTORCALL_ARGUMENTS_OFFSET
+    n as Py_ssize_t
 }
 
 #[cfg(all(Py_3_8, not(PyPy)))]
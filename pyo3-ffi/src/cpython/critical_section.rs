@@ -0,0 +1,30 @@
+use crate::object::PyObject;
+use crate::PyMutex;
+use crate::Py_uintptr_t;
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct PyCriticalSection {
+    pub _cs_prev: Py_uintptr_t,
+    pub _cs_mutex: *mut PyMutex,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct PyCriticalSection2 {
+    pub _cs_base: PyCriticalSection,
+    pub _cs_mutex2: *mut PyMutex,
+}
+
+#[cfg_attr(windows, link(name = "pythonXY"))]
+extern "C" {
+    pub fn PyCriticalSection_Begin(c: *mut PyCriticalSection, op: *mut PyObject);
+    pub fn PyCriticalSection_End(c: *mut PyCriticalSection);
+    pub fn PyCriticalSection2_Begin(c: *mut PyCriticalSection2, a: *mut PyObject, b: *mut PyObject);
+    pub fn PyCriticalSection2_End(c: *mut PyCriticalSection2);
+}
+
+// skipped Py_BEGIN_CRITICAL_SECTION
+// skipped Py_END_CRITICAL_SECTION
+// skipped Py_BEGIN_CRITICAL_SECTION2
+// skipped Py_END_CRITICAL_SECTION2
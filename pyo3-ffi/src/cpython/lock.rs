@@ -0,0 +1,11 @@
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct PyMutex {
+    pub(crate) _bits: u8,
+}
+
+#[cfg_attr(windows, link(name = "pythonXY"))]
+extern "C" {
+    pub fn PyMutex_Lock(m: *mut PyMutex);
+    pub fn PyMutex_Unlock(m: *mut PyMutex);
+}
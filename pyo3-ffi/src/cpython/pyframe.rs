@@ -1,2 +1,24 @@
+#[cfg(all(Py_3_11, not(PyPy)))]
+use crate::object::PyObject;
+#[cfg(all(Py_3_9, not(PyPy)))]
+use crate::PyFrameObject;
+#[cfg(all(Py_3_11, not(PyPy)))]
+use std::os::raw::c_int;
+
 #[cfg(Py_3_11)]
 opaque_struct!(_PyInterpreterFrame);
+
+extern "C" {
+    #[cfg(all(Py_3_9, not(PyPy)))]
+    pub fn PyFrame_GetBack(frame: *mut PyFrameObject) -> *mut PyFrameObject;
+    #[cfg(all(Py_3_11, not(PyPy)))]
+    pub fn PyFrame_GetLocals(frame: *mut PyFrameObject) -> *mut PyObject;
+    #[cfg(all(Py_3_11, not(PyPy)))]
+    pub fn PyFrame_GetGlobals(frame: *mut PyFrameObject) -> *mut PyObject;
+    #[cfg(all(Py_3_11, not(PyPy)))]
+    pub fn PyFrame_GetBuiltins(frame: *mut PyFrameObject) -> *mut PyObject;
+    #[cfg(all(Py_3_11, not(PyPy)))]
+    pub fn PyFrame_GetGenerator(frame: *mut PyFrameObject) -> *mut PyObject;
+    #[cfg(all(Py_3_11, not(PyPy)))]
+    pub fn PyFrame_GetLasti(frame: *mut PyFrameObject) -> c_int;
+}
@@ -12,6 +12,11 @@ pub use crate::cpython::object::PyTypeObject;
 // _PyObject_HEAD_EXTRA: conditionally defined in PyObject_HEAD_INIT
 // _PyObject_EXTRA_INIT: conditionally defined in PyObject_HEAD_INIT
 
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+pub const _Py_IMMORTAL_REFCNT_LOCAL: u32 = u32::MAX;
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+pub const _Py_REF_SHARED_SHIFT: isize = 2;
+
 #[cfg(Py_3_12)]
 pub const _Py_IMMORTAL_REFCNT: Py_ssize_t = {
     if cfg!(target_pointer_width = "64") {
@@ -27,7 +32,19 @@ pub const PyObject_HEAD_INIT: PyObject = PyObject {
     _ob_next: std::ptr::null_mut(),
     #[cfg(py_sys_config = "Py_TRACE_REFS")]
     _ob_prev: std::ptr::null_mut(),
-    #[cfg(Py_3_12)]
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    ob_tid: 0,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    _padding: 0,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    ob_mutex: crate::PyMutex { _bits: 0 },
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    ob_gc_bits: 0,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    ob_ref_local: _Py_IMMORTAL_REFCNT_LOCAL,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    ob_ref_shared: 0,
+    #[cfg(all(Py_3_12, not(py_sys_config = "Py_GIL_DISABLED")))]
     ob_refcnt: PyObjectObRefcnt { ob_refcnt: 1 },
     #[cfg(not(Py_3_12))]
     ob_refcnt: 1,
@@ -67,6 +84,19 @@ pub struct PyObject {
     pub _ob_next: *mut PyObject,
     #[cfg(py_sys_config = "Py_TRACE_REFS")]
     pub _ob_prev: *mut PyObject,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    pub ob_tid: crate::Py_uintptr_t,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    pub _padding: u16,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    pub ob_mutex: crate::PyMutex,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    pub ob_gc_bits: u8,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    pub ob_ref_local: u32,
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    pub ob_ref_shared: Py_ssize_t,
+    #[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
     pub ob_refcnt: PyObjectObRefcnt,
     #[cfg(PyPy)]
     pub ob_pypy_link: Py_ssize_t,
@@ -90,11 +120,25 @@ pub unsafe fn Py_Is(x: *mut PyObject, y: *mut PyObject) -> c_int {
 }
 
 #[inline]
-#[cfg(Py_3_12)]
+#[cfg(all(Py_3_12, not(py_sys_config = "Py_GIL_DISABLED")))]
 pub unsafe fn Py_REFCNT(ob: *mut PyObject) -> Py_ssize_t {
     (*ob).ob_refcnt.ob_refcnt
 }
 
+#[inline]
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+pub unsafe fn Py_REFCNT(ob: *mut PyObject) -> Py_ssize_t {
+    use std::sync::atomic::{AtomicIsize, AtomicU32, Ordering::Relaxed};
+
+    // Other threads may update the reference counts concurrently.
+    let local = (*ptr::addr_of!((*ob).ob_ref_local).cast::<AtomicU32>()).load(Relaxed);
+    if local == _Py_IMMORTAL_REFCNT_LOCAL {
+        return _Py_IMMORTAL_REFCNT;
+    }
+    let shared = (*ptr::addr_of!((*ob).ob_ref_shared).cast::<AtomicIsize>()).load(Relaxed);
+    local as Py_ssize_t + (shared >> _Py_REF_SHARED_SHIFT)
+}
+
 #[inline]
 #[cfg(not(Py_3_12))]
 pub unsafe fn Py_REFCNT(ob: *mut PyObject) -> Py_ssize_t {
@@ -122,13 +166,30 @@ pub unsafe fn Py_IS_TYPE(ob: *mut PyObject, tp: *mut PyTypeObject) -> c_int {
 }
 
 #[inline(always)]
-#[cfg(all(Py_3_12, target_pointer_width = "64"))]
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+pub unsafe fn _Py_IsImmortal(op: *mut PyObject) -> c_int {
+    use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
+
+    let local = (*ptr::addr_of!((*op).ob_ref_local).cast::<AtomicU32>()).load(Relaxed);
+    (local == _Py_IMMORTAL_REFCNT_LOCAL) as c_int
+}
+
+#[inline(always)]
+#[cfg(all(
+    Py_3_12,
+    target_pointer_width = "64",
+    not(py_sys_config = "Py_GIL_DISABLED")
+))]
 pub unsafe fn _Py_IsImmortal(op: *mut PyObject) -> c_int {
     (((*op).ob_refcnt.ob_refcnt as crate::PY_INT32_T) < 0) as c_int
 }
 
 #[inline(always)]
-#[cfg(all(Py_3_12, target_pointer_width = "32"))]
+#[cfg(all(
+    Py_3_12,
+    target_pointer_width = "32",
+    not(py_sys_config = "Py_GIL_DISABLED")
+))]
 pub unsafe fn _Py_IsImmortal(op: *mut PyObject) -> c_int {
     ((*op).ob_refcnt.ob_refcnt == _Py_IMMORTAL_REFCNT) as c_int
 }
@@ -478,6 +539,12 @@ extern "C" {
 
 #[inline(always)]
 pub unsafe fn Py_INCREF(op: *mut PyObject) {
+    // The free-threaded build uses biased reference counting, which is left to the interpreter.
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    {
+        Py_IncRef(op);
+    }
+
     #[cfg(any(
         all(Py_LIMITED_API, Py_3_12),
         all(
@@ -495,15 +562,18 @@ pub unsafe fn Py_INCREF(op: *mut PyObject) {
         return Py_IncRef(op);
     }
 
-    #[cfg(any(
-        all(Py_LIMITED_API, not(Py_3_12)),
-        all(
-            not(Py_LIMITED_API),
-            any(
-                not(py_sys_config = "Py_REF_DEBUG"),
-                all(py_sys_config = "Py_REF_DEBUG", Py_3_12),
-            )
-        ),
+    #[cfg(all(
+        not(py_sys_config = "Py_GIL_DISABLED"),
+        any(
+            all(Py_LIMITED_API, not(Py_3_12)),
+            all(
+                not(Py_LIMITED_API),
+                any(
+                    not(py_sys_config = "Py_REF_DEBUG"),
+                    all(py_sys_config = "Py_REF_DEBUG", Py_3_12),
+                )
+            ),
+        )
     ))]
     {
         #[cfg(all(Py_3_12, target_pointer_width = "64"))]
@@ -543,6 +613,12 @@ pub unsafe fn Py_INCREF(op: *mut PyObject) {
     track_caller
 )]
 pub unsafe fn Py_DECREF(op: *mut PyObject) {
+    // The free-threaded build uses biased reference counting, which is left to the interpreter.
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    {
+        Py_DecRef(op);
+    }
+
     #[cfg(any(
         all(Py_LIMITED_API, Py_3_12),
         all(
@@ -560,15 +636,18 @@ pub unsafe fn Py_DECREF(op: *mut PyObject) {
         return Py_DecRef(op);
     }
 
-    #[cfg(any(
-        all(Py_LIMITED_API, not(Py_3_12)),
-        all(
-            not(Py_LIMITED_API),
-            any(
-                not(py_sys_config = "Py_REF_DEBUG"),
-                all(py_sys_config = "Py_REF_DEBUG", Py_3_12),
-            )
-        ),
+    #[cfg(all(
+        not(py_sys_config = "Py_GIL_DISABLED"),
+        any(
+            all(Py_LIMITED_API, not(Py_3_12)),
+            all(
+                not(Py_LIMITED_API),
+                any(
+                    not(py_sys_config = "Py_REF_DEBUG"),
+                    all(py_sys_config = "Py_REF_DEBUG", Py_3_12),
+                )
+            ),
+        )
     ))]
     {
         #[cfg(Py_3_12)]
@@ -1,39 +1,63 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
     token::Comma,
-    Attribute, Expr, ExprPath, Ident, LitStr, Path, Result, Token,
+    Attribute, Expr, ExprPath, Ident, LitBool, LitStr, Path, Result, Token,
 };
 
 pub mod kw {
+    syn::custom_keyword!(abc);
     syn::custom_keyword!(annotation);
+    syn::custom_keyword!(array);
     syn::custom_keyword!(attribute);
     syn::custom_keyword!(cancel_handle);
+    syn::custom_keyword!(copy);
+    syn::custom_keyword!(dataclass);
+    syn::custom_keyword!(default_factory);
+    syn::custom_keyword!(default_once);
+    syn::custom_keyword!(deny_unknown_fields);
     syn::custom_keyword!(dict);
+    syn::custom_keyword!(exception);
     syn::custom_keyword!(extends);
     syn::custom_keyword!(freelist);
     syn::custom_keyword!(from_py_with);
     syn::custom_keyword!(frozen);
+    syn::custom_keyword!(generic);
     syn::custom_keyword!(get);
     syn::custom_keyword!(get_all);
+    syn::custom_keyword!(gil_used);
+    syn::custom_keyword!(immortal);
+    syn::custom_keyword!(immutable_type);
+    syn::custom_keyword!(inject);
     syn::custom_keyword!(item);
+    syn::custom_keyword!(len);
     syn::custom_keyword!(from_item_all);
+    syn::custom_keyword!(from_trait);
+    syn::custom_keyword!(from_tuple);
     syn::custom_keyword!(mapping);
+    syn::custom_keyword!(match_args);
+    syn::custom_keyword!(metaclass);
     syn::custom_keyword!(module);
     syn::custom_keyword!(name);
+    syn::custom_keyword!(ord);
     syn::custom_keyword!(pass_module);
+    syn::custom_keyword!(pickle);
     syn::custom_keyword!(rename_all);
     syn::custom_keyword!(sequence);
     syn::custom_keyword!(set);
     syn::custom_keyword!(set_all);
+    syn::custom_keyword!(shared);
     syn::custom_keyword!(signature);
     syn::custom_keyword!(subclass);
     syn::custom_keyword!(text_signature);
     syn::custom_keyword!(transparent);
+    syn::custom_keyword!(unexported);
     syn::custom_keyword!(unsendable);
+    syn::custom_keyword!(vectorcall);
     syn::custom_keyword!(weakref);
 }
 
@@ -68,7 +92,8 @@ pub struct NameLitStr(pub Ident);
 impl Parse for NameLitStr {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let string_literal: LitStr = input.parse()?;
-        if let Ok(ident) = string_literal.parse() {
+        // Python names may be Rust keywords, e.g. `type` or `match`
+        if let Ok(ident) = string_literal.parse_with(Ident::parse_any) {
             Ok(NameLitStr(ident))
         } else {
             bail_spanned!(string_literal.span() => "expected a single identifier in double quotes")
@@ -131,6 +156,30 @@ impl ToTokens for RenamingRuleLitStr {
     }
 }
 
+/// A parenthesized list of string literals, e.g. `("x", "y")`
+#[derive(Clone, Debug)]
+pub struct MatchArgs {
+    pub paren_token: syn::token::Paren,
+    pub names: Punctuated<LitStr, Comma>,
+}
+
+impl Parse for MatchArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let content;
+        Ok(MatchArgs {
+            paren_token: syn::parenthesized!(content in input),
+            names: content.parse_terminated(<LitStr as Parse>::parse, Token![,])?,
+        })
+    }
+}
+
+impl ToTokens for MatchArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.paren_token
+            .surround(tokens, |tokens| self.names.to_tokens(tokens))
+    }
+}
+
 /// Text signatue can be either a literal string or opt-in/out
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TextSignatureAttributeValue {
@@ -166,8 +215,15 @@ impl ToTokens for TextSignatureAttributeValue {
     }
 }
 
+pub type AbcAttribute = KeywordAttribute<kw::abc, LitStr>;
+pub type ExceptionAttribute = KeywordAttribute<kw::exception, Path>;
 pub type ExtendsAttribute = KeywordAttribute<kw::extends, Path>;
 pub type FreelistAttribute = KeywordAttribute<kw::freelist, Box<Expr>>;
+pub type FromTraitAttribute = KeywordAttribute<kw::from_trait, Path>;
+pub type GILUsedAttribute = KeywordAttribute<kw::gil_used, LitBool>;
+pub type SharedAttribute = KeywordAttribute<kw::shared, LitStr>;
+pub type MatchArgsAttribute = KeywordAttribute<kw::match_args, MatchArgs>;
+pub type MetaclassAttribute = KeywordAttribute<kw::metaclass, Path>;
 pub type ModuleAttribute = KeywordAttribute<kw::module, LitStr>;
 pub type NameAttribute = KeywordAttribute<kw::name, NameLitStr>;
 pub type RenameAllAttribute = KeywordAttribute<kw::rename_all, RenamingRuleLitStr>;
@@ -192,6 +248,9 @@ impl<K: ToTokens, V: ToTokens> ToTokens for KeywordAttribute<K, V> {
 
 pub type FromPyWithAttribute = KeywordAttribute<kw::from_py_with, LitStrValue<ExprPath>>;
 
+pub type DefaultFactoryAttribute = KeywordAttribute<kw::default_factory, LitStrValue<ExprPath>>;
+pub type DefaultOnceAttribute = KeywordAttribute<kw::default_once, LitStrValue<ExprPath>>;
+
 /// For specifying the path to the pyo3 crate.
 pub type CrateAttribute = KeywordAttribute<Token![crate], LitStrValue<Path>>;
 
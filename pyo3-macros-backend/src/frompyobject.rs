@@ -1,18 +1,21 @@
 use crate::{
-    attributes::{self, get_pyo3_options, CrateAttribute, FromPyWithAttribute},
-    utils::get_pyo3_crate,
+    attributes::{self, get_pyo3_options, CrateAttribute, FromPyWithAttribute, RenameAllAttribute},
+    utils::{self, get_pyo3_crate},
 };
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
+    ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream},
     parse_quote,
-    punctuated::Punctuated,
     spanned::Spanned,
     Attribute, DataEnum, DeriveInput, Fields, Ident, LitStr, Result, Token,
 };
 
+/// The largest tuple which can be extracted with `FromPyObject`.
+pub(crate) const MAX_TUPLE_FIELDS: usize = 12;
+
 /// Describes derivation input of an enum.
 struct Enum<'a> {
     enum_ident: &'a Ident,
@@ -24,7 +27,11 @@ impl<'a> Enum<'a> {
     ///
     /// `data_enum` is the `syn` representation of the input enum, `ident` is the
     /// `Identifier` of the enum.
-    fn new(data_enum: &'a DataEnum, ident: &'a Ident) -> Result<Self> {
+    fn new(
+        data_enum: &'a DataEnum,
+        ident: &'a Ident,
+        rename_all: Option<&RenameAllAttribute>,
+    ) -> Result<Self> {
         ensure_spanned!(
             !data_enum.variants.is_empty(),
             ident.span() => "cannot derive FromPyObject for empty enum"
@@ -33,7 +40,10 @@ impl<'a> Enum<'a> {
             .variants
             .iter()
             .map(|variant| {
-                let attrs = ContainerOptions::from_attrs(&variant.attrs)?;
+                let mut attrs = ContainerOptions::from_attrs(&variant.attrs)?;
+                if attrs.rename_all.is_none() && !attrs.transparent && attrs.from_tuple.is_none() {
+                    attrs.rename_all = rename_all.cloned();
+                }
                 let var_ident = &variant.ident;
                 Container::new(&variant.fields, parse_quote!(#ident::#var_ident), attrs)
             })
@@ -89,6 +99,7 @@ struct NamedStructField<'a> {
     ident: &'a syn::Ident,
     getter: Option<FieldGetter>,
     from_py_with: Option<FromPyWithAttribute>,
+    default: Option<FieldDefault>,
 }
 
 struct TupleStructField {
@@ -103,6 +114,10 @@ enum ContainerType<'a> {
     ///
     /// Variant contains the list of field identifiers and the corresponding extraction call.
     Struct(Vec<NamedStructField<'a>>),
+    /// Struct container extracted positionally, e.g. `#[pyo3(from_tuple)] struct Foo { a: String }`
+    ///
+    /// The fields are extracted from the elements of a tuple, in declaration order.
+    StructFromTuple(Vec<NamedStructField<'a>>),
     /// Newtype struct container, e.g. `#[transparent] struct Foo { a: String }`
     ///
     /// The field specified by the identifier is extracted directly from the object.
@@ -125,6 +140,8 @@ struct Container<'a> {
     path: syn::Path,
     ty: ContainerType<'a>,
     err_name: String,
+    /// Keys accepted from the source mapping, if unknown keys are rejected.
+    known_keys: Option<Vec<String>>,
 }
 
 impl<'a> Container<'a> {
@@ -143,13 +160,33 @@ impl<'a> Container<'a> {
                             attrs.getter.is_none(),
                             field.span() => "`getter` is not permitted on tuple struct elements."
                         );
+                        if let Some(default) = &attrs.default {
+                            bail_spanned!(default.span => "`default` is only permitted on named struct fields");
+                        }
                         Ok(TupleStructField {
                             from_py_with: attrs.from_py_with,
                         })
                     })
                     .collect::<Result<Vec<_>>>()?;
 
-                if tuple_fields.len() == 1 {
+                if let Some(rename_all) = &options.rename_all {
+                    ensure_spanned!(
+                        !options.transparent && options.from_tuple.is_none(),
+                        rename_all.kw.span() => "`rename_all` cannot be used with `transparent` or `from_tuple`"
+                    );
+                }
+                if let Some(from_tuple) = &options.from_tuple {
+                    ensure_spanned!(
+                        !options.transparent,
+                        from_tuple.span() => "`from_tuple` cannot be used with `transparent`"
+                    );
+                    ensure_spanned!(
+                        tuple_fields.len() <= MAX_TUPLE_FIELDS,
+                        fields.span() => "`from_tuple` supports at most 12 fields"
+                    );
+                    // An explicit `from_tuple` extracts even a single field from a 1-tuple.
+                    ContainerType::Tuple(tuple_fields)
+                } else if tuple_fields.len() == 1 {
                     // Always treat a 1-length tuple struct as "transparent", even without the
                     // explicit annotation.
                     let field = tuple_fields.pop().unwrap();
@@ -192,10 +229,36 @@ impl<'a> Container<'a> {
                             ident,
                             getter: attrs.getter,
                             from_py_with: attrs.from_py_with,
+                            default: attrs.default,
                         })
                     })
                     .collect::<Result<Vec<_>>>()?;
-                if options.transparent {
+                if let Some(rename_all) = &options.rename_all {
+                    ensure_spanned!(
+                        !options.transparent && options.from_tuple.is_none(),
+                        rename_all.kw.span() => "`rename_all` cannot be used with `transparent` or `from_tuple`"
+                    );
+                }
+                if let Some(from_tuple) = &options.from_tuple {
+                    ensure_spanned!(
+                        !options.transparent && options.from_item_all.is_none(),
+                        from_tuple.span() => "`from_tuple` cannot be used with `transparent` or `from_item_all`"
+                    );
+                    ensure_spanned!(
+                        struct_fields.len() <= MAX_TUPLE_FIELDS,
+                        fields.span() => "`from_tuple` supports at most 12 fields"
+                    );
+                    for field in &struct_fields {
+                        ensure_spanned!(
+                            field.getter.is_none(),
+                            field.ident.span() => "`from_tuple` structs may not have a `getter` on their fields"
+                        );
+                        if let Some(default) = &field.default {
+                            bail_spanned!(default.span => "`default` is not permitted on the fields of `from_tuple` structs");
+                        }
+                    }
+                    ContainerType::StructFromTuple(struct_fields)
+                } else if options.transparent {
                     ensure_spanned!(
                         struct_fields.len() == 1,
                         fields.span() => "transparent structs and variants can only have 1 field"
@@ -205,8 +268,28 @@ impl<'a> Container<'a> {
                         field.getter.is_none(),
                         field.ident.span() => "`transparent` structs may not have a `getter` for the inner field"
                     );
+                    if let Some(default) = &field.default {
+                        bail_spanned!(default.span => "`default` is not permitted on the inner field of `transparent` structs");
+                    }
                     ContainerType::StructNewtype(field.ident, field.from_py_with)
                 } else {
+                    if let Some(rename_all) = &options.rename_all {
+                        for field in &mut struct_fields {
+                            let name = utils::apply_renaming_rule(
+                                rename_all.value.rule,
+                                &field.ident.unraw().to_string(),
+                            );
+                            field.getter = Some(match field.getter.take() {
+                                None | Some(FieldGetter::GetAttr(None)) => FieldGetter::GetAttr(
+                                    Some(LitStr::new(&name, field.ident.span())),
+                                ),
+                                Some(FieldGetter::GetItem(None)) => FieldGetter::GetItem(Some(
+                                    syn::Lit::Str(LitStr::new(&name, field.ident.span())),
+                                )),
+                                Some(getter) => getter,
+                            });
+                        }
+                    }
                     ContainerType::Struct(struct_fields)
                 }
             }
@@ -214,6 +297,24 @@ impl<'a> Container<'a> {
                 fields.span() => "cannot derive FromPyObject for empty structs and variants"
             ),
         };
+        let known_keys = match (&options.deny_unknown_fields, &style) {
+            (None, _) => None,
+            (Some(_), ContainerType::Struct(fields)) => Some(
+                fields
+                    .iter()
+                    .map(|field| match &field.getter {
+                        Some(FieldGetter::GetItem(None)) => Ok(field.ident.unraw().to_string()),
+                        Some(FieldGetter::GetItem(Some(syn::Lit::Str(key)))) => Ok(key.value()),
+                        _ => bail_spanned!(
+                            field.ident.span() => "`deny_unknown_fields` requires every field to be extracted with a string `item` key"
+                        ),
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            (Some(kw), _) => bail_spanned!(
+                kw.span() => "`deny_unknown_fields` is only supported for structs with named fields"
+            ),
+        };
         let err_name = options.annotation.map_or_else(
             || path.segments.last().unwrap().ident.to_string(),
             |lit_str| lit_str.value(),
@@ -223,6 +324,7 @@ impl<'a> Container<'a> {
             path,
             ty: style,
             err_name,
+            known_keys,
         };
         Ok(v)
     }
@@ -249,6 +351,7 @@ impl<'a> Container<'a> {
             }
             ContainerType::Tuple(tups) => self.build_tuple_struct(tups),
             ContainerType::Struct(tups) => self.build_struct(tups),
+            ContainerType::StructFromTuple(tups) => self.build_struct_from_tuple(tups),
         }
     }
 
@@ -309,7 +412,35 @@ impl<'a> Container<'a> {
         });
         quote!(
             match obj.extract() {
-                ::std::result::Result::Ok((#(#field_idents),*)) => ::std::result::Result::Ok(#self_ty(#(#fields),*)),
+                ::std::result::Result::Ok((#(#field_idents,)*)) => ::std::result::Result::Ok(#self_ty(#(#fields),*)),
+                ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+            }
+        )
+    }
+
+    fn build_struct_from_tuple(&self, struct_fields: &[NamedStructField<'_>]) -> TokenStream {
+        let self_ty = &self.path;
+        let struct_name = &self.name();
+        let arg_idents: Vec<_> = (0..struct_fields.len())
+            .map(|i| format_ident!("arg{}", i))
+            .collect();
+        let fields = struct_fields.iter().zip(&arg_idents).map(|(field, arg)| {
+            let ident = &field.ident;
+            let field_name = ident.to_string();
+            match &field.from_py_with {
+                None => quote!(
+                    #ident: _pyo3::impl_::frompyobject::extract_struct_field(&#arg, #struct_name, #field_name)?
+                ),
+                Some(FromPyWithAttribute {
+                    value: expr_path, ..
+                }) => quote! (
+                    #ident: _pyo3::impl_::frompyobject::extract_struct_field_with(#expr_path as fn(_) -> _, &#arg, #struct_name, #field_name)?
+                ),
+            }
+        });
+        quote!(
+            match obj.extract() {
+                ::std::result::Result::Ok((#(#arg_idents,)*)) => ::std::result::Result::Ok(#self_ty { #(#fields),* }),
                 ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
             }
         )
@@ -318,39 +449,91 @@ impl<'a> Container<'a> {
     fn build_struct(&self, struct_fields: &[NamedStructField<'_>]) -> TokenStream {
         let self_ty = &self.path;
         let struct_name = &self.name();
-        let mut fields: Punctuated<TokenStream, syn::Token![,]> = Punctuated::new();
-        for field in struct_fields {
+        let mut field_idents = Vec::new();
+        let mut field_values = Vec::new();
+        let mut extractions = Vec::new();
+        for (index, field) in struct_fields.iter().enumerate() {
             let ident = &field.ident;
+            let value = format_ident!("field{}", index);
             let field_name = ident.to_string();
-            let getter = match field.getter.as_ref().unwrap_or(&FieldGetter::GetAttr(None)) {
-                FieldGetter::GetAttr(Some(name)) => {
-                    quote!(getattr(_pyo3::intern!(obj.py(), #name)))
-                }
-                FieldGetter::GetAttr(None) => {
-                    quote!(getattr(_pyo3::intern!(obj.py(), #field_name)))
-                }
-                FieldGetter::GetItem(Some(syn::Lit::Str(key))) => {
-                    quote!(get_item(_pyo3::intern!(obj.py(), #key)))
-                }
-                FieldGetter::GetItem(Some(key)) => quote!(get_item(#key)),
-                FieldGetter::GetItem(None) => {
-                    quote!(get_item(_pyo3::intern!(obj.py(), #field_name)))
-                }
-            };
+            // `presence` checks for the attribute or item explicitly, so that a field with a
+            // default doesn't hide errors raised while looking it up.
+            let (getter, presence) =
+                match field.getter.as_ref().unwrap_or(&FieldGetter::GetAttr(None)) {
+                    FieldGetter::GetAttr(Some(name)) => (
+                        quote!(getattr(_pyo3::intern!(obj.py(), #name))),
+                        quote!(hasattr(_pyo3::intern!(obj.py(), #name))),
+                    ),
+                    FieldGetter::GetAttr(None) => (
+                        quote!(getattr(_pyo3::intern!(obj.py(), #field_name))),
+                        quote!(hasattr(_pyo3::intern!(obj.py(), #field_name))),
+                    ),
+                    FieldGetter::GetItem(Some(syn::Lit::Str(key))) => (
+                        quote!(get_item(_pyo3::intern!(obj.py(), #key))),
+                        quote!(contains(_pyo3::intern!(obj.py(), #key))),
+                    ),
+                    FieldGetter::GetItem(Some(key)) => {
+                        (quote!(get_item(#key)), quote!(contains(#key)))
+                    }
+                    FieldGetter::GetItem(None) => (
+                        quote!(get_item(_pyo3::intern!(obj.py(), #field_name))),
+                        quote!(contains(_pyo3::intern!(obj.py(), #field_name))),
+                    ),
+                };
             let extractor = match &field.from_py_with {
                 None => {
-                    quote!(_pyo3::impl_::frompyobject::extract_struct_field(&obj.#getter?, #struct_name, #field_name)?)
+                    quote!(_pyo3::impl_::frompyobject::extract_struct_field(&value, #struct_name, #field_name))
                 }
                 Some(FromPyWithAttribute {
                     value: expr_path, ..
                 }) => {
-                    quote! (_pyo3::impl_::frompyobject::extract_struct_field_with(#expr_path as fn(_) -> _, &obj.#getter?, #struct_name, #field_name)?)
+                    quote! (_pyo3::impl_::frompyobject::extract_struct_field_with(#expr_path as fn(_) -> _, &value, #struct_name, #field_name))
+                }
+            };
+            let extract = quote! {
+                match obj.#getter {
+                    ::std::result::Result::Ok(value) => errors.check(#extractor),
+                    ::std::result::Result::Err(err) => errors.check(::std::result::Result::Err(err)),
                 }
             };
+            let extraction = match &field.default {
+                Some(default) => {
+                    let expr = default
+                        .expr
+                        .clone()
+                        .unwrap_or_else(|| parse_quote!(::std::default::Default::default()));
+                    quote! {
+                        match obj.#presence {
+                            ::std::result::Result::Ok(true) => #extract,
+                            ::std::result::Result::Ok(false) => ::std::option::Option::Some(#expr),
+                            ::std::result::Result::Err(err) => errors.check(::std::result::Result::Err(err)),
+                        }
+                    }
+                }
+                None => extract,
+            };
 
-            fields.push(quote!(#ident: #extractor));
+            extractions.push(quote! {
+                let #value = #extraction;
+            });
+            field_idents.push(ident);
+            field_values.push(value);
+        }
+        let check_unknown_keys = self
+            .known_keys
+            .as_ref()
+            .map(|keys| quote!(errors.check_unknown_keys(obj, &[#(#keys),*]);));
+        quote! {
+            let mut errors = _pyo3::impl_::frompyobject::FieldErrors::new(#struct_name);
+            #(#extractions)*
+            #check_unknown_keys
+            match (#(#field_values,)*) {
+                (#(::std::option::Option::Some(#field_values),)*) if errors.is_empty() => {
+                    ::std::result::Result::Ok(#self_ty { #(#field_idents: #field_values),* })
+                }
+                _ => ::std::result::Result::Err(errors.into_err(obj.py())),
+            }
         }
-        quote!(::std::result::Result::Ok(#self_ty{#fields}))
     }
 }
 
@@ -364,6 +547,12 @@ struct ContainerOptions {
     annotation: Option<syn::LitStr>,
     /// Change the path for the pyo3 crate
     krate: Option<CrateAttribute>,
+    /// Reject source mappings which have keys not matching any field.
+    deny_unknown_fields: Option<attributes::kw::deny_unknown_fields>,
+    /// Extract the fields positionally from a tuple.
+    from_tuple: Option<attributes::kw::from_tuple>,
+    /// Rename the attributes or items the fields are extracted from.
+    rename_all: Option<RenameAllAttribute>,
 }
 
 /// Attributes for deriving FromPyObject scoped on containers.
@@ -376,6 +565,12 @@ enum ContainerPyO3Attribute {
     ErrorAnnotation(LitStr),
     /// Change the path for the pyo3 crate
     Crate(CrateAttribute),
+    /// Reject source mappings which have keys not matching any field.
+    DenyUnknownFields(attributes::kw::deny_unknown_fields),
+    /// Extract the fields positionally from a tuple.
+    FromTuple(attributes::kw::from_tuple),
+    /// Rename the attributes or items the fields are extracted from.
+    RenameAll(RenameAllAttribute),
 }
 
 impl Parse for ContainerPyO3Attribute {
@@ -393,6 +588,12 @@ impl Parse for ContainerPyO3Attribute {
             input.parse().map(ContainerPyO3Attribute::ErrorAnnotation)
         } else if lookahead.peek(Token![crate]) {
             input.parse().map(ContainerPyO3Attribute::Crate)
+        } else if lookahead.peek(attributes::kw::deny_unknown_fields) {
+            input.parse().map(ContainerPyO3Attribute::DenyUnknownFields)
+        } else if lookahead.peek(attributes::kw::from_tuple) {
+            input.parse().map(ContainerPyO3Attribute::FromTuple)
+        } else if lookahead.peek(attributes::kw::rename_all) {
+            input.parse().map(ContainerPyO3Attribute::RenameAll)
         } else {
             Err(lookahead.error())
         }
@@ -435,6 +636,27 @@ impl ContainerOptions {
                             );
                             options.krate = Some(path);
                         }
+                        ContainerPyO3Attribute::DenyUnknownFields(kw) => {
+                            ensure_spanned!(
+                                options.deny_unknown_fields.is_none(),
+                                kw.span() => "`deny_unknown_fields` may only be provided once"
+                            );
+                            options.deny_unknown_fields = Some(kw);
+                        }
+                        ContainerPyO3Attribute::FromTuple(kw) => {
+                            ensure_spanned!(
+                                options.from_tuple.is_none(),
+                                kw.span() => "`from_tuple` may only be provided once"
+                            );
+                            options.from_tuple = Some(kw);
+                        }
+                        ContainerPyO3Attribute::RenameAll(rename_all) => {
+                            ensure_spanned!(
+                                options.rename_all.is_none(),
+                                rename_all.kw.span() => "`rename_all` may only be provided once"
+                            );
+                            options.rename_all = Some(rename_all);
+                        }
                     }
                 }
             }
@@ -448,6 +670,14 @@ impl ContainerOptions {
 struct FieldPyO3Attributes {
     getter: Option<FieldGetter>,
     from_py_with: Option<FromPyWithAttribute>,
+    default: Option<FieldDefault>,
+}
+
+/// `#[pyo3(default)]` or `#[pyo3(default = expr)]`, used when the field is missing.
+#[derive(Clone, Debug)]
+struct FieldDefault {
+    span: proc_macro2::Span,
+    expr: Option<syn::Expr>,
 }
 
 #[derive(Clone, Debug)]
@@ -459,6 +689,7 @@ enum FieldGetter {
 enum FieldPyO3Attribute {
     Getter(FieldGetter),
     FromPyWith(FromPyWithAttribute),
+    Default(FieldDefault),
 }
 
 impl Parse for FieldPyO3Attribute {
@@ -502,6 +733,18 @@ impl Parse for FieldPyO3Attribute {
             }
         } else if lookahead.peek(attributes::kw::from_py_with) {
             input.parse().map(FieldPyO3Attribute::FromPyWith)
+        } else if lookahead.peek(Token![default]) {
+            let kw: Token![default] = input.parse()?;
+            let expr = if input.peek(Token![=]) {
+                let _: Token![=] = input.parse()?;
+                Some(input.parse()?)
+            } else {
+                None
+            };
+            Ok(FieldPyO3Attribute::Default(FieldDefault {
+                span: kw.span(),
+                expr,
+            }))
         } else {
             Err(lookahead.error())
         }
@@ -513,6 +756,7 @@ impl FieldPyO3Attributes {
     fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
         let mut getter = None;
         let mut from_py_with = None;
+        let mut default = None;
 
         for attr in attrs {
             if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
@@ -532,6 +776,13 @@ impl FieldPyO3Attributes {
                             );
                             from_py_with = Some(from_py_with_attr);
                         }
+                        FieldPyO3Attribute::Default(default_attr) => {
+                            ensure_spanned!(
+                                default.is_none(),
+                                attr.span() => "`default` may only be provided once"
+                            );
+                            default = Some(default_attr);
+                        }
                     }
                 }
             }
@@ -540,6 +791,7 @@ impl FieldPyO3Attributes {
         Ok(FieldPyO3Attributes {
             getter,
             from_py_with,
+            default,
         })
     }
 }
@@ -586,7 +838,15 @@ pub fn build_derive_from_pyobject(tokens: &DeriveInput) -> Result<TokenStream> {
                 bail_spanned!(tokens.span() => "`transparent` or `annotation` is not supported \
                                                 at top level for enums");
             }
-            let en = Enum::new(en, &tokens.ident)?;
+            if let Some(kw) = &options.deny_unknown_fields {
+                bail_spanned!(kw.span() => "`deny_unknown_fields` is not supported at top level \
+                                            for enums; apply it to the variants instead");
+            }
+            if let Some(kw) = &options.from_tuple {
+                bail_spanned!(kw.span() => "`from_tuple` is not supported at top level for enums; \
+                                            apply it to the variants instead");
+            }
+            let en = Enum::new(en, &tokens.ident, options.rename_all.as_ref())?;
             en.build()
         }
         syn::Data::Struct(st) => {
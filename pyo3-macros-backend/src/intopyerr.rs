@@ -0,0 +1,217 @@
+use crate::{
+    attributes::{self, CrateAttribute, ExceptionAttribute},
+    utils::get_pyo3_crate,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, DeriveInput, Fields, Path, Result, Token,
+};
+
+/// How a value (a struct, or a single enum variant) is converted into a `PyErr`.
+enum Conversion {
+    /// Raise the given exception type, with the `Display` output of the value as the message.
+    Exception(Path),
+    /// Forward to the `From<Field> for PyErr` implementation of the only field.
+    Transparent,
+}
+
+#[derive(Default)]
+struct PyErrOptions {
+    /// The exception type to raise.
+    exception: Option<ExceptionAttribute>,
+    /// Forward the conversion to the only field.
+    transparent: Option<attributes::kw::transparent>,
+    /// Change the path for the pyo3 crate
+    krate: Option<CrateAttribute>,
+}
+
+/// Attributes for deriving IntoPyErr, written as `#[pyerr(...)]`.
+enum PyErrAttribute {
+    Exception(ExceptionAttribute),
+    Transparent(attributes::kw::transparent),
+    Crate(CrateAttribute),
+}
+
+impl Parse for PyErrAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(attributes::kw::exception) {
+            input.parse().map(PyErrAttribute::Exception)
+        } else if lookahead.peek(attributes::kw::transparent) {
+            input.parse().map(PyErrAttribute::Transparent)
+        } else if lookahead.peek(Token![crate]) {
+            input.parse().map(PyErrAttribute::Crate)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl PyErrOptions {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = PyErrOptions::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("pyerr") {
+                continue;
+            }
+            let pyerr_attrs: Punctuated<PyErrAttribute, Token![,]> =
+                attr.parse_args_with(Punctuated::parse_terminated)?;
+            for pyerr_attr in pyerr_attrs {
+                match pyerr_attr {
+                    PyErrAttribute::Exception(exception) => {
+                        ensure_spanned!(
+                            options.exception.is_none(),
+                            exception.kw.span() => "`exception` may only be provided once"
+                        );
+                        options.exception = Some(exception);
+                    }
+                    PyErrAttribute::Transparent(kw) => {
+                        ensure_spanned!(
+                            options.transparent.is_none(),
+                            kw.span() => "`transparent` may only be provided once"
+                        );
+                        options.transparent = Some(kw);
+                    }
+                    PyErrAttribute::Crate(path) => {
+                        ensure_spanned!(
+                            options.krate.is_none(),
+                            path.span() => "`crate` may only be provided once"
+                        );
+                        options.krate = Some(path);
+                    }
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    /// Determines the conversion of a struct or variant with the given fields, falling back to
+    /// `default` (the exception given on the enum) if no conversion is specified.
+    fn conversion(
+        self,
+        fields: &Fields,
+        default: Option<&Path>,
+        span: proc_macro2::Span,
+    ) -> Result<Conversion> {
+        match (self.exception, self.transparent) {
+            (Some(exception), Some(_)) => bail_spanned!(
+                exception.kw.span() => "`exception` and `transparent` cannot be used together"
+            ),
+            (Some(exception), None) => Ok(Conversion::Exception(exception.value)),
+            (None, Some(kw)) => {
+                ensure_spanned!(
+                    fields.len() == 1,
+                    kw.span() => "`transparent` structs and variants must have exactly one field"
+                );
+                Ok(Conversion::Transparent)
+            }
+            (None, None) => match default {
+                Some(path) => Ok(Conversion::Exception(path.clone())),
+                None => bail_spanned!(
+                    span => "missing `#[pyerr(exception = ...)]` or `#[pyerr(transparent)]`"
+                ),
+            },
+        }
+    }
+}
+
+/// Builds the match arm (or, for structs, the body) converting `err` into a `PyErr`.
+fn build_conversion(conversion: &Conversion, fields: &Fields, path: TokenStream) -> TokenStream {
+    match conversion {
+        Conversion::Exception(exception) => {
+            let pattern = match fields {
+                Fields::Named(_) => quote!(#path { .. }),
+                Fields::Unnamed(_) => quote!(#path(..)),
+                Fields::Unit => quote!(#path),
+            };
+            quote! {
+                #pattern => _pyo3::PyErr::new::<#exception, _>(
+                    ::std::string::ToString::to_string(&err)
+                )
+            }
+        }
+        Conversion::Transparent => {
+            let pattern = match fields {
+                Fields::Named(named) => {
+                    let ident = &named.named[0].ident;
+                    quote!(#path { #ident: inner })
+                }
+                _ => quote!(#path(inner)),
+            };
+            quote! {
+                #pattern => ::std::convert::From::from(inner)
+            }
+        }
+    }
+}
+
+/// Derive `From<T> for PyErr` for enums and structs.
+///
+///   * Every variant must specify `#[pyerr(exception = ...)]` or `#[pyerr(transparent)]`, unless
+///     an exception is given on the enum itself.
+///   * Variants with an exception are converted using their `Display` implementation as the
+///     message; `transparent` variants forward to the conversion of their only field.
+pub fn build_derive_into_pyerr(tokens: &DeriveInput) -> Result<TokenStream> {
+    let options = PyErrOptions::from_attrs(&tokens.attrs)?;
+    let krate = get_pyo3_crate(&options.krate);
+    let ident = &tokens.ident;
+    let arms = match &tokens.data {
+        syn::Data::Enum(en) => {
+            ensure_spanned!(
+                !en.variants.is_empty(),
+                ident.span() => "cannot derive IntoPyErr for empty enum"
+            );
+            if let Some(kw) = &options.transparent {
+                bail_spanned!(kw.span() => "`transparent` is not supported at top level for \
+                                            enums; apply it to the variants instead");
+            }
+            let default = options.exception.as_ref().map(|exception| &exception.value);
+            en.variants
+                .iter()
+                .map(|variant| {
+                    let variant_options = PyErrOptions::from_attrs(&variant.attrs)?;
+                    if let Some(krate) = &variant_options.krate {
+                        bail_spanned!(krate.span() => "`crate` is only supported at top level");
+                    }
+                    let conversion =
+                        variant_options.conversion(&variant.fields, default, variant.span())?;
+                    let var_ident = &variant.ident;
+                    Ok(build_conversion(
+                        &conversion,
+                        &variant.fields,
+                        quote!(#ident::#var_ident),
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+        syn::Data::Struct(st) => {
+            let span = ident.span();
+            let conversion = options.conversion(&st.fields, None, span)?;
+            vec![build_conversion(&conversion, &st.fields, quote!(#ident))]
+        }
+        syn::Data::Union(_) => bail_spanned!(
+            tokens.span() => "#[derive(IntoPyErr)] is not supported for unions"
+        ),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = tokens.generics.split_for_impl();
+    Ok(quote!(
+        const _: () = {
+            use #krate as _pyo3;
+
+            #[automatically_derived]
+            impl #impl_generics ::std::convert::From<#ident #ty_generics> for _pyo3::PyErr #where_clause {
+                fn from(err: #ident #ty_generics) -> _pyo3::PyErr {
+                    match err {
+                        #(#arms,)*
+                    }
+                }
+            }
+        };
+    ))
+}
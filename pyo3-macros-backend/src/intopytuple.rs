@@ -0,0 +1,145 @@
+use crate::{
+    attributes::{self, get_pyo3_options, CrateAttribute},
+    frompyobject::MAX_TUPLE_FIELDS,
+    utils::get_pyo3_crate,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned,
+    Attribute, DeriveInput, Fields, Member, Result, Token,
+};
+
+#[derive(Default)]
+struct IntoPyTupleOptions {
+    /// Change the path for the pyo3 crate
+    krate: Option<CrateAttribute>,
+}
+
+/// Attributes for deriving IntoPyTuple scoped on the struct.
+enum IntoPyTupleAttribute {
+    /// Change the path for the pyo3 crate
+    Crate(CrateAttribute),
+    /// Accepted so that the struct can share its attributes with `#[derive(FromPyObject)]`.
+    FromTuple,
+}
+
+impl Parse for IntoPyTupleAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![crate]) {
+            input.parse().map(IntoPyTupleAttribute::Crate)
+        } else if lookahead.peek(attributes::kw::from_tuple) {
+            let _: attributes::kw::from_tuple = input.parse()?;
+            Ok(IntoPyTupleAttribute::FromTuple)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl IntoPyTupleOptions {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = IntoPyTupleOptions::default();
+
+        for attr in attrs {
+            if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
+                for pyo3_attr in pyo3_attrs {
+                    match pyo3_attr {
+                        IntoPyTupleAttribute::Crate(path) => {
+                            ensure_spanned!(
+                                options.krate.is_none(),
+                                path.span() => "`crate` may only be provided once"
+                            );
+                            options.krate = Some(path);
+                        }
+                        IntoPyTupleAttribute::FromTuple => {}
+                    }
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Derive `ToPyObject` and `IntoPy` for structs, converting them to a tuple of their fields.
+///
+///   * Fields are converted in declaration order, for both named and tuple structs.
+///   * The struct must have between 1 and 12 fields.
+///   * Derivation for structs with generic fields like `struct<T> Foo(T)`
+///     adds `T: ToPyObject` (respectively `T: IntoPy<PyObject>`, `T: IntoPyObject<'py>`) on the
+///     derived implementations.
+pub fn build_derive_into_pytuple(tokens: &DeriveInput) -> Result<TokenStream> {
+    let options = IntoPyTupleOptions::from_attrs(&tokens.attrs)?;
+    let krate = get_pyo3_crate(&options.krate);
+    let ident = &tokens.ident;
+    let fields = match &tokens.data {
+        syn::Data::Struct(st) => &st.fields,
+        syn::Data::Enum(_) | syn::Data::Union(_) => bail_spanned!(
+            tokens.span() => "#[derive(IntoPyTuple)] is only supported for structs"
+        ),
+    };
+    ensure_spanned!(
+        !matches!(fields, Fields::Unit) && !fields.is_empty(),
+        fields.span() => "cannot derive IntoPyTuple for empty structs"
+    );
+    ensure_spanned!(
+        fields.len() <= MAX_TUPLE_FIELDS,
+        fields.span() => "IntoPyTuple supports at most 12 fields"
+    );
+    let members: Vec<Member> = fields.members().collect();
+    let impl_params = &tokens.generics.params;
+
+    let (impl_generics, ty_generics, where_clause) = tokens.generics.split_for_impl();
+    let mut to_object_where = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    let mut into_py_where = to_object_where.clone();
+    let mut into_pyobject_where = to_object_where.clone();
+    for param in tokens.generics.type_params() {
+        let gen_ident = &param.ident;
+        to_object_where
+            .predicates
+            .push(parse_quote!(#gen_ident: _pyo3::ToPyObject));
+        into_py_where
+            .predicates
+            .push(parse_quote!(#gen_ident: _pyo3::IntoPy<_pyo3::PyObject>));
+        into_pyobject_where
+            .predicates
+            .push(parse_quote!(#gen_ident: _pyo3::IntoPyObject<'py>));
+    }
+
+    Ok(quote!(
+        const _: () = {
+            use #krate as _pyo3;
+
+            #[automatically_derived]
+            impl #impl_generics _pyo3::ToPyObject for #ident #ty_generics #to_object_where {
+                fn to_object(&self, py: _pyo3::Python<'_>) -> _pyo3::PyObject {
+                    _pyo3::ToPyObject::to_object(&(#(&self.#members,)*), py)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics _pyo3::IntoPy<_pyo3::PyObject> for #ident #ty_generics #into_py_where {
+                fn into_py(self, py: _pyo3::Python<'_>) -> _pyo3::PyObject {
+                    _pyo3::IntoPy::into_py((#(self.#members,)*), py)
+                }
+            }
+
+            #[automatically_derived]
+            impl<'py, #impl_params> _pyo3::IntoPyObject<'py> for #ident #ty_generics #into_pyobject_where {
+                fn into_pyobject(self, py: _pyo3::Python<'py>) -> _pyo3::PyResult<_pyo3::Bound<'py, _pyo3::PyAny>> {
+                    _pyo3::IntoPyObject::into_pyobject((#(self.#members,)*), py)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics _pyo3::IntoPy<_pyo3::Py<_pyo3::types::PyTuple>> for #ident #ty_generics #into_py_where {
+                fn into_py(self, py: _pyo3::Python<'_>) -> _pyo3::Py<_pyo3::types::PyTuple> {
+                    _pyo3::IntoPy::into_py((#(self.#members,)*), py)
+                }
+            }
+        };
+    ))
+}
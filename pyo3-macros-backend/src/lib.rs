@@ -11,6 +11,8 @@ mod utils;
 mod attributes;
 mod deprecations;
 mod frompyobject;
+mod intopyerr;
+mod intopytuple;
 mod konst;
 mod method;
 mod module;
@@ -19,11 +21,15 @@ mod pyclass;
 mod pyfunction;
 mod pyimpl;
 mod pymethod;
+mod pyproto;
 mod quotes;
 
 pub use frompyobject::build_derive_from_pyobject;
-pub use module::{process_functions_in_module, pymodule_impl, PyModuleOptions};
-pub use pyclass::{build_py_class, build_py_enum, PyClassArgs};
+pub use intopyerr::build_derive_into_pyerr;
+pub use intopytuple::build_derive_into_pytuple;
+pub use module::{check_module_doc, process_functions_in_module, pymodule_impl, PyModuleOptions};
+pub use pyclass::{build_py_class, build_py_class_alias, build_py_enum, PyClassArgs};
 pub use pyfunction::{build_py_function, PyFunctionOptions};
 pub use pyimpl::{build_py_methods, PyClassMethodsType};
+pub use pyproto::build_py_proto;
 pub use utils::get_doc;
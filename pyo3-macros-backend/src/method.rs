@@ -5,14 +5,14 @@ use quote::{quote, quote_spanned, ToTokens};
 use syn::{ext::IdentExt, spanned::Spanned, Ident, Result};
 
 use crate::{
-    attributes::{TextSignatureAttribute, TextSignatureAttributeValue},
+    attributes::{RenamingRule, TextSignatureAttribute, TextSignatureAttributeValue},
     deprecations::{Deprecation, Deprecations},
     params::impl_arg_params,
     pyfunction::{
         FunctionSignature, PyFunctionArgPyO3Attributes, PyFunctionOptions, SignatureAttribute,
     },
     quotes,
-    utils::{self, is_abi3, PythonDoc},
+    utils::{self, has_fastcall_with_keywords, PythonDoc},
 };
 
 #[derive(Clone, Debug)]
@@ -62,6 +62,47 @@ impl<'a> FnArg<'a> {
             }
         }
     }
+
+    /// Returns true if the argument is supplied by PyO3 rather than by the Python caller,
+    /// and so is not part of the Python signature.
+    pub fn is_injected(&self) -> bool {
+        self.py || self.is_cancel_handle || self.attrs.inject.is_some()
+    }
+
+    /// Returns true if the argument is created by an `InjectedArgument` implementation, which
+    /// needs the `__call_context` of the wrapper.
+    pub fn uses_call_context(&self) -> bool {
+        !self.py && !self.is_cancel_handle && self.attrs.inject.is_some()
+    }
+
+    /// The expression passed to the Rust function for an injected argument.
+    pub fn injected_value(&self) -> Option<TokenStream> {
+        if self.py {
+            Some(quote!(py))
+        } else if self.is_cancel_handle {
+            Some(quote!(__cancel_handle))
+        } else if self.attrs.inject.is_some() {
+            let ty = self.ty;
+            Some(quote_spanned! { ty.span() =>
+                <#ty as _pyo3::conversion::InjectedArgument<'_>>::inject(&__call_context)?
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Applies the `rename_all` rule of a `#[pymethods]` block to the name of a method, leaving
+/// Python special method names such as `__repr__` alone.
+fn rename_ident(name: &syn::Ident, renaming_rule: Option<RenamingRule>) -> syn::Ident {
+    let name = name.unraw();
+    let name_str = name.to_string();
+    match renaming_rule {
+        Some(rule) if !(name_str.starts_with("__") && name_str.ends_with("__")) => {
+            syn::Ident::new(&utils::apply_renaming_rule(rule, &name_str), name.span())
+        }
+        _ => name,
+    }
 }
 
 fn handle_argument_error(pat: &syn::Pat) -> syn::Error {
@@ -81,9 +122,11 @@ fn handle_argument_error(pat: &syn::Pat) -> syn::Error {
 pub enum FnType {
     Getter(SelfType),
     Setter(SelfType),
+    Deleter(SelfType),
     Fn(SelfType),
     FnNew,
     FnNewClass(Span),
+    FnInit(SelfType),
     FnClass(Span),
     FnStatic,
     FnModule(Span),
@@ -95,7 +138,9 @@ impl FnType {
         match self {
             FnType::Getter(_)
             | FnType::Setter(_)
+            | FnType::Deleter(_)
             | FnType::Fn(_)
+            | FnType::FnInit(_)
             | FnType::FnClass(_)
             | FnType::FnNewClass(_)
             | FnType::FnModule(_) => true,
@@ -110,7 +155,11 @@ impl FnType {
         holders: &mut Vec<TokenStream>,
     ) -> TokenStream {
         match self {
-            FnType::Getter(st) | FnType::Setter(st) | FnType::Fn(st) => {
+            FnType::Getter(st)
+            | FnType::Setter(st)
+            | FnType::Deleter(st)
+            | FnType::Fn(st)
+            | FnType::FnInit(st) => {
                 let mut receiver = st.receiver(
                     cls.expect("no class given for Fn with a \"self\" receiver"),
                     error_mode,
@@ -242,8 +291,7 @@ impl CallingConvention {
         } else if signature.python_signature.kwargs.is_some() {
             // for functions that accept **kwargs, always prefer varargs
             Self::Varargs
-        } else if !is_abi3() {
-            // FIXME: available in the stable ABI since 3.10
+        } else if has_fastcall_with_keywords() {
             Self::Fastcall
         } else {
             Self::Varargs
@@ -308,18 +356,28 @@ impl<'a> FnSpec<'a> {
             text_signature,
             name,
             signature,
+            renaming_rule,
             ..
         } = options;
 
         let mut python_name = name.map(|name| name.value.0);
         let mut deprecations = Deprecations::new();
 
-        let fn_type = Self::parse_fn_type(sig, meth_attrs, &mut python_name, &mut deprecations)?;
+        let fn_type = Self::parse_fn_type(
+            sig,
+            meth_attrs,
+            &mut python_name,
+            renaming_rule,
+            &mut deprecations,
+        )?;
         ensure_signatures_on_valid_method(&fn_type, signature.as_ref(), text_signature.as_ref())?;
 
         let name = &sig.ident;
         let ty = get_return_info(&sig.output);
-        let python_name = python_name.as_ref().unwrap_or(name).unraw();
+        let python_name = match python_name {
+            Some(python_name) => python_name.unraw(),
+            None => rename_ident(name, renaming_rule),
+        };
 
         let arguments: Vec<_> = sig
             .inputs
@@ -366,6 +424,7 @@ impl<'a> FnSpec<'a> {
         sig: &syn::Signature,
         meth_attrs: &mut Vec<syn::Attribute>,
         python_name: &mut Option<syn::Ident>,
+        renaming_rule: Option<RenamingRule>,
         deprecations: &mut Deprecations,
     ) -> Result<FnType> {
         let mut method_attributes = parse_method_attributes(meth_attrs, deprecations)?;
@@ -384,43 +443,57 @@ impl<'a> FnSpec<'a> {
             name.unraw()
                 .to_string()
                 .strip_prefix(prefix)
-                .map(|stripped| syn::Ident::new(stripped, name.span()))
+                .map(|stripped| {
+                    rename_ident(&syn::Ident::new(stripped, name.span()), renaming_rule)
+                })
+        };
+
+        // Like in Python, `__class_getitem__` is implicitly a classmethod
+        let is_class_getitem = match python_name {
+            Some(python_name) => python_name == "__class_getitem__",
+            None => name.unraw() == "__class_getitem__",
         };
 
-        let mut set_name_to_new = || {
+        let mut set_name_to_dunder = |attribute: &str, dunder: &str| {
             if let Some(name) = &python_name {
-                bail_spanned!(name.span() => "`name` not allowed with `#[new]`");
+                bail_spanned!(name.span() => format!("`name` not allowed with `{}`", attribute));
             }
-            *python_name = Some(syn::Ident::new("__new__", Span::call_site()));
+            *python_name = Some(syn::Ident::new(dunder, Span::call_site()));
             Ok(())
         };
 
+        let classmethod_span = || match sig.inputs.first() {
+            // Don't actually bother checking the type of the first argument, the compiler
+            // will error on incorrect type.
+            Some(syn::FnArg::Typed(first_arg)) => Ok(first_arg.ty.span()),
+            Some(syn::FnArg::Receiver(_)) | None => bail_spanned!(
+                sig.paren_token.span.join() => "Expected `&Bound<PyType>` or `Py<PyType>` as the first argument to `#[classmethod]`"
+            ),
+        };
+
         let fn_type = match method_attributes.as_mut_slice() {
+            [] if is_class_getitem => FnType::FnClass(classmethod_span()?),
             [] => FnType::Fn(parse_receiver(
                 "static method needs #[staticmethod] attribute",
             )?),
             [MethodTypeAttribute::StaticMethod(_)] => FnType::FnStatic,
             [MethodTypeAttribute::ClassAttribute(_)] => FnType::ClassAttribute,
             [MethodTypeAttribute::New(_)] => {
-                set_name_to_new()?;
+                set_name_to_dunder("#[new]", "__new__")?;
                 FnType::FnNew
             }
             [MethodTypeAttribute::New(_), MethodTypeAttribute::ClassMethod(span)]
             | [MethodTypeAttribute::ClassMethod(span), MethodTypeAttribute::New(_)] => {
-                set_name_to_new()?;
+                set_name_to_dunder("#[new]", "__new__")?;
                 FnType::FnNewClass(*span)
             }
+            [MethodTypeAttribute::Init(_)] => {
+                set_name_to_dunder("#[init]", "__init__")?;
+                FnType::FnInit(parse_receiver("expected receiver for `#[init]`")?)
+            }
             [MethodTypeAttribute::ClassMethod(_)] => {
                 // Add a helpful hint if the classmethod doesn't look like a classmethod
-                let span = match sig.inputs.first() {
-                    // Don't actually bother checking the type of the first argument, the compiler
-                    // will error on incorrect type.
-                    Some(syn::FnArg::Typed(first_arg)) => first_arg.ty.span(),
-                    Some(syn::FnArg::Receiver(_)) | None => bail_spanned!(
-                        sig.paren_token.span.join() => "Expected `&Bound<PyType>` or `Py<PyType>` as the first argument to `#[classmethod]`"
-                    ),
-                };
-                FnType::FnClass(span)
+                FnType::FnClass(classmethod_span()?)
             }
             [MethodTypeAttribute::Getter(_, name)] => {
                 if let Some(name) = name.take() {
@@ -448,6 +521,19 @@ impl<'a> FnSpec<'a> {
 
                 FnType::Setter(parse_receiver("expected receiver for `#[setter]`")?)
             }
+            [MethodTypeAttribute::Deleter(_, name)] => {
+                if let Some(name) = name.take() {
+                    ensure_spanned!(
+                        python_name.replace(name).is_none(),
+                        python_name.span() => "`name` may only be specified once"
+                    );
+                } else if python_name.is_none() {
+                    // Strip off "del_" prefix if needed
+                    *python_name = strip_fn_name("del_");
+                }
+
+                FnType::Deleter(parse_receiver("expected receiver for `#[deleter]`")?)
+            }
             [first, rest @ .., last] => {
                 // Join as many of the spans together as possible
                 let span = rest
@@ -475,6 +561,21 @@ impl<'a> FnSpec<'a> {
         Ok(fn_type)
     }
 
+    /// Creates the `__call_context` for `#[pyo3(inject)]` arguments with `constructor`, a
+    /// function of `_pyo3::conversion::CallContext` given its arguments, if any argument needs it.
+    pub fn call_context(&self, constructor: TokenStream) -> TokenStream {
+        if self
+            .signature
+            .arguments
+            .iter()
+            .any(FnArg::uses_call_context)
+        {
+            quote! { let __call_context = _pyo3::conversion::CallContext::#constructor; }
+        } else {
+            TokenStream::new()
+        }
+    }
+
     /// Return a C wrapper function for this signature.
     pub fn get_wrapper_function(
         &self,
@@ -527,13 +628,20 @@ impl<'a> FnSpec<'a> {
                     }
                     _ => quote! { function(#self_arg #(#args),*) },
                 };
+                // The output is converted inside the future, so that it can go through
+                // `IntoPyObject` or `IntoPy` like the output of a synchronous function.
+                let ok_wrapped_output = quotes::ok_wrap(quote! { future.await });
+                let converted_output = quotes::map_result_into_py(quote! { result });
                 let mut call = quote! {{
                     let future = #future;
                     _pyo3::impl_::coroutine::new_coroutine(
                         _pyo3::intern!(py, stringify!(#python_name)),
                         #qualname_prefix,
                         #throw_callback,
-                        async move { _pyo3::impl_::wrap::OkWrap::wrap(future.await) },
+                        async move {
+                            let result = #ok_wrapped_output;
+                            _pyo3::Python::with_gil(|py| #converted_output)
+                        },
                     )
                 }};
                 if cancel_handle.is_some() {
@@ -564,17 +672,10 @@ impl<'a> FnSpec<'a> {
                     .signature
                     .arguments
                     .iter()
-                    .map(|arg| {
-                        if arg.py {
-                            quote!(py)
-                        } else if arg.is_cancel_handle {
-                            quote!(__cancel_handle)
-                        } else {
-                            unreachable!()
-                        }
-                    })
+                    .map(|arg| arg.injected_value().unwrap())
                     .collect();
                 let call = rust_call(args, &mut holders);
+                let call_context = self.call_context(quote! { new(py, _slf) });
 
                 quote! {
                     unsafe fn #ident<'py>(
@@ -582,6 +683,7 @@ impl<'a> FnSpec<'a> {
                         _slf: *mut _pyo3::ffi::PyObject,
                     ) -> _pyo3::PyResult<*mut _pyo3::ffi::PyObject> {
                         let function = #rust_name; // Shadow the function name to avoid #3017
+                        #call_context
                         #( #holders )*
                         #call
                     }
@@ -591,6 +693,8 @@ impl<'a> FnSpec<'a> {
                 let mut holders = Vec::new();
                 let (arg_convert, args) = impl_arg_params(self, cls, true, &mut holders)?;
                 let call = rust_call(args, &mut holders);
+                let call_context =
+                    self.call_context(quote! { fastcall(py, _slf, _args, _nargs, _kwnames) });
                 quote! {
                     unsafe fn #ident<'py>(
                         py: _pyo3::Python<'py>,
@@ -600,6 +704,7 @@ impl<'a> FnSpec<'a> {
                         _kwnames: *mut _pyo3::ffi::PyObject
                     ) -> _pyo3::PyResult<*mut _pyo3::ffi::PyObject> {
                         let function = #rust_name; // Shadow the function name to avoid #3017
+                        #call_context
                         #arg_convert
                         #( #holders )*
                         #call
@@ -610,6 +715,7 @@ impl<'a> FnSpec<'a> {
                 let mut holders = Vec::new();
                 let (arg_convert, args) = impl_arg_params(self, cls, false, &mut holders)?;
                 let call = rust_call(args, &mut holders);
+                let call_context = self.call_context(quote! { varargs(py, _slf, _args, _kwargs) });
                 quote! {
                     unsafe fn #ident<'py>(
                         py: _pyo3::Python<'py>,
@@ -618,6 +724,7 @@ impl<'a> FnSpec<'a> {
                         _kwargs: *mut _pyo3::ffi::PyObject
                     ) -> _pyo3::PyResult<*mut _pyo3::ffi::PyObject> {
                         let function = #rust_name; // Shadow the function name to avoid #3017
+                        #call_context
                         #arg_convert
                         #( #holders )*
                         #call
@@ -629,6 +736,8 @@ impl<'a> FnSpec<'a> {
                 let (arg_convert, args) = impl_arg_params(self, cls, false, &mut holders)?;
                 let self_arg = self.tp.self_arg(cls, ExtractErrorMode::Raise, &mut holders);
                 let call = quote! { #rust_name(#self_arg #(#args),*) };
+                let call_context =
+                    self.call_context(quote! { varargs(py, _slf.cast(), _args, _kwargs) });
                 quote! {
                     unsafe fn #ident(
                         py: _pyo3::Python<'_>,
@@ -638,12 +747,13 @@ impl<'a> FnSpec<'a> {
                     ) -> _pyo3::PyResult<*mut _pyo3::ffi::PyObject> {
                         use _pyo3::callback::IntoPyCallbackOutput;
                         let function = #rust_name; // Shadow the function name to avoid #3017
+                        #call_context
                         #arg_convert
                         #( #holders )*
                         let result = #call;
                         let initializer: _pyo3::PyClassInitializer::<#cls> = result.convert(py)?;
-                        let cell = initializer.create_cell_from_subtype(py, _slf)?;
-                        ::std::result::Result::Ok(cell as *mut _pyo3::ffi::PyObject)
+                        let obj = initializer.create_class_object_of_type(py, _slf)?;
+                        ::std::result::Result::Ok(obj.into_ptr())
                     }
                 }
             }
@@ -654,11 +764,10 @@ impl<'a> FnSpec<'a> {
     /// calling convention.
     pub fn get_methoddef(&self, wrapper: impl ToTokens, doc: &PythonDoc) -> TokenStream {
         let python_name = self.null_terminated_python_name();
-        match self.convention {
-            CallingConvention::Noargs => quote! {
-                _pyo3::impl_::pymethods::PyMethodDef::noargs(
-                    #python_name,
-                    _pyo3::impl_::pymethods::PyCFunction({
+        let (meth, flags) = match self.convention {
+            CallingConvention::Noargs => (
+                quote! {
+                    PyCFunction: {
                         unsafe extern "C" fn trampoline(
                             _slf: *mut _pyo3::ffi::PyObject,
                             _args: *mut _pyo3::ffi::PyObject,
@@ -671,14 +780,13 @@ impl<'a> FnSpec<'a> {
                             )
                         }
                         trampoline
-                    }),
-                    #doc,
-                )
-            },
-            CallingConvention::Fastcall => quote! {
-                _pyo3::impl_::pymethods::PyMethodDef::fastcall_cfunction_with_keywords(
-                    #python_name,
-                    _pyo3::impl_::pymethods::PyCFunctionFastWithKeywords({
+                    }
+                },
+                quote!(_pyo3::ffi::METH_NOARGS),
+            ),
+            CallingConvention::Fastcall => (
+                quote! {
+                    _PyCFunctionFastWithKeywords: {
                         unsafe extern "C" fn trampoline(
                             _slf: *mut _pyo3::ffi::PyObject,
                             _args: *const *mut _pyo3::ffi::PyObject,
@@ -695,14 +803,13 @@ impl<'a> FnSpec<'a> {
                             )
                         }
                         trampoline
-                    }),
-                    #doc,
-                )
-            },
-            CallingConvention::Varargs => quote! {
-                _pyo3::impl_::pymethods::PyMethodDef::cfunction_with_keywords(
-                    #python_name,
-                    _pyo3::impl_::pymethods::PyCFunctionWithKeywords({
+                    }
+                },
+                quote!(_pyo3::ffi::METH_FASTCALL | _pyo3::ffi::METH_KEYWORDS),
+            ),
+            CallingConvention::Varargs => (
+                quote! {
+                    PyCFunctionWithKeywords: {
                         unsafe extern "C" fn trampoline(
                             _slf: *mut _pyo3::ffi::PyObject,
                             _args: *mut _pyo3::ffi::PyObject,
@@ -717,11 +824,22 @@ impl<'a> FnSpec<'a> {
                             )
                         }
                         trampoline
-                    }),
+                    }
+                },
+                quote!(_pyo3::ffi::METH_VARARGS | _pyo3::ffi::METH_KEYWORDS),
+            ),
+            CallingConvention::TpNew => unreachable!("tp_new cannot get a methoddef"),
+        };
+        // Safety: the name and doc are nul-terminated, and the flags match the trampoline
+        quote! {
+            unsafe {
+                _pyo3::impl_::pymethods::PyMethodDef::new(
+                    #python_name,
+                    _pyo3::ffi::PyMethodDefPointer { #meth },
+                    #flags,
                     #doc,
                 )
-            },
-            CallingConvention::TpNew => unreachable!("tp_new cannot get a methoddef"),
+            }
         }
     }
 
@@ -737,9 +855,11 @@ impl<'a> FnSpec<'a> {
     /// and/or attributes. Prepend the callable name to make a complete `__text_signature__`.
     pub fn text_signature_call_signature(&self) -> Option<String> {
         let self_argument = match &self.tp {
-            // Getters / Setters / ClassAttribute are not callables on the Python side
-            FnType::Getter(_) | FnType::Setter(_) | FnType::ClassAttribute => return None,
-            FnType::Fn(_) => Some("self"),
+            // Getters / Setters / Deleters / ClassAttribute are not callables on the Python side
+            FnType::Getter(_) | FnType::Setter(_) | FnType::Deleter(_) | FnType::ClassAttribute => {
+                return None
+            }
+            FnType::Fn(_) | FnType::FnInit(_) => Some("self"),
             FnType::FnModule(_) => Some("module"),
             FnType::FnClass(_) | FnType::FnNewClass(_) => Some("cls"),
             FnType::FnStatic | FnType::FnNew => None,
@@ -755,10 +875,12 @@ impl<'a> FnSpec<'a> {
 
 enum MethodTypeAttribute {
     New(Span),
+    Init(Span),
     ClassMethod(Span),
     StaticMethod(Span),
     Getter(Span, Option<Ident>),
     Setter(Span, Option<Ident>),
+    Deleter(Span, Option<Ident>),
     ClassAttribute(Span),
 }
 
@@ -766,10 +888,12 @@ impl MethodTypeAttribute {
     fn span(&self) -> Span {
         match self {
             MethodTypeAttribute::New(span)
+            | MethodTypeAttribute::Init(span)
             | MethodTypeAttribute::ClassMethod(span)
             | MethodTypeAttribute::StaticMethod(span)
             | MethodTypeAttribute::Getter(span, _)
             | MethodTypeAttribute::Setter(span, _)
+            | MethodTypeAttribute::Deleter(span, _)
             | MethodTypeAttribute::ClassAttribute(span) => *span,
         }
     }
@@ -831,6 +955,9 @@ impl MethodTypeAttribute {
             deprecations.push(Deprecation::PyMethodsNewDeprecatedForm, span);
             ensure_no_arguments(meta, "__new__")?;
             Ok(Some(MethodTypeAttribute::New(span)))
+        } else if path.is_ident("init") {
+            ensure_no_arguments(meta, "init")?;
+            Ok(Some(MethodTypeAttribute::Init(path.span())))
         } else if path.is_ident("classmethod") {
             ensure_no_arguments(meta, "classmethod")?;
             Ok(Some(MethodTypeAttribute::ClassMethod(path.span())))
@@ -846,6 +973,9 @@ impl MethodTypeAttribute {
         } else if path.is_ident("setter") {
             let name = extract_name(meta, "setter")?;
             Ok(Some(MethodTypeAttribute::Setter(path.span(), name)))
+        } else if path.is_ident("deleter") {
+            let name = extract_name(meta, "deleter")?;
+            Ok(Some(MethodTypeAttribute::Deleter(path.span(), name)))
         } else {
             Ok(None)
         }
@@ -856,10 +986,12 @@ impl Display for MethodTypeAttribute {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MethodTypeAttribute::New(_) => "#[new]".fmt(f),
+            MethodTypeAttribute::Init(_) => "#[init]".fmt(f),
             MethodTypeAttribute::ClassMethod(_) => "#[classmethod]".fmt(f),
             MethodTypeAttribute::StaticMethod(_) => "#[staticmethod]".fmt(f),
             MethodTypeAttribute::Getter(_, _) => "#[getter]".fmt(f),
             MethodTypeAttribute::Setter(_, _) => "#[setter]".fmt(f),
+            MethodTypeAttribute::Deleter(_, _) => "#[deleter]".fmt(f),
             MethodTypeAttribute::ClassAttribute(_) => "#[classattr]".fmt(f),
         }
     }
@@ -902,6 +1034,9 @@ fn ensure_signatures_on_valid_method(
             FnType::Setter(_) => {
                 bail_spanned!(signature.kw.span() => "`signature` not allowed with `setter`")
             }
+            FnType::Deleter(_) => {
+                bail_spanned!(signature.kw.span() => "`signature` not allowed with `deleter`")
+            }
             FnType::ClassAttribute => {
                 bail_spanned!(signature.kw.span() => "`signature` not allowed with `classattr`")
             }
@@ -916,9 +1051,15 @@ fn ensure_signatures_on_valid_method(
             FnType::Setter(_) => {
                 bail_spanned!(text_signature.kw.span() => "`text_signature` not allowed with `setter`")
             }
+            FnType::Deleter(_) => {
+                bail_spanned!(text_signature.kw.span() => "`text_signature` not allowed with `deleter`")
+            }
             FnType::ClassAttribute => {
                 bail_spanned!(text_signature.kw.span() => "`text_signature` not allowed with `classattr`")
             }
+            FnType::FnInit(_) => {
+                bail_spanned!(text_signature.kw.span() => "`text_signature` not allowed with `init`")
+            }
             _ => {}
         }
     }
@@ -1,7 +1,9 @@
 //! Code generation for the function that initializes a python module and adds classes and function.
 
 use crate::{
-    attributes::{self, take_attributes, take_pyo3_options, CrateAttribute, NameAttribute},
+    attributes::{
+        self, take_attributes, take_pyo3_options, CrateAttribute, GILUsedAttribute, NameAttribute,
+    },
     pyfunction::{impl_wrap_pyfunction, PyFunctionOptions},
     utils::{get_pyo3_crate, PythonDoc},
 };
@@ -19,6 +21,7 @@ use syn::{
 pub struct PyModuleOptions {
     krate: Option<CrateAttribute>,
     name: Option<syn::Ident>,
+    gil_used: Option<GILUsedAttribute>,
 }
 
 impl PyModuleOptions {
@@ -29,6 +32,7 @@ impl PyModuleOptions {
             match option {
                 PyModulePyO3Option::Name(name) => options.set_name(name.value.0)?,
                 PyModulePyO3Option::Crate(path) => options.set_crate(path)?,
+                PyModulePyO3Option::GILUsed(gil_used) => options.set_gil_used(gil_used)?,
             }
         }
 
@@ -54,6 +58,42 @@ impl PyModuleOptions {
         self.krate = Some(path);
         Ok(())
     }
+
+    fn set_gil_used(&mut self, gil_used: GILUsedAttribute) -> Result<()> {
+        ensure_spanned!(
+            self.gil_used.is_none(),
+            gil_used.span() => "`gil_used` may only be specified once"
+        );
+
+        self.gil_used = Some(gil_used);
+        Ok(())
+    }
+}
+
+/// Rejects nul bytes in the doc comments of a `#[pymodule]`, as the docstring is passed to Python
+/// as a nul-terminated string. Docs written with macros, e.g. `#[doc = include_str!(...)]`, can
+/// only be checked when the module definition is evaluated.
+pub fn check_module_doc(attrs: &[syn::Attribute]) -> Result<()> {
+    for attr in attrs {
+        if !attr.path().is_ident("doc") {
+            continue;
+        }
+        if let Ok(syn::MetaNameValue {
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }),
+            ..
+        }) = attr.meta.require_name_value()
+        {
+            ensure_spanned!(
+                !lit_str.value().contains('\0'),
+                lit_str.span() => "`#[pymodule]` doc comments cannot contain nul bytes"
+            );
+        }
+    }
+    Ok(())
 }
 
 /// Generates the function that is called by the python interpreter to initialize the native
@@ -67,6 +107,10 @@ pub fn pymodule_impl(
     let name = options.name.unwrap_or_else(|| fnname.unraw());
     let krate = get_pyo3_crate(&options.krate);
     let pyinit_symbol = format!("PyInit_{}", name);
+    let gil_used = match options.gil_used {
+        Some(gil_used) => gil_used.value.value,
+        None => true,
+    };
 
     quote! {
         // Create a module with the same name as the `#[pymodule]` - this way `use <the module>`
@@ -94,9 +138,7 @@ pub fn pymodule_impl(
             impl #fnname::MakeDef {
                 const fn make_def() -> impl_::ModuleDef {
                     const INITIALIZER: impl_::ModuleInitializer = impl_::ModuleInitializer(#fnname);
-                    unsafe {
-                        impl_::ModuleDef::new(#fnname::NAME, #doc, INITIALIZER)
-                    }
+                    impl_::ModuleDef::new(#fnname::NAME, #doc, INITIALIZER).gil_used(#gil_used)
                 }
             }
         };
@@ -115,11 +157,28 @@ pub fn process_functions_in_module(
         if let syn::Stmt::Item(syn::Item::Fn(func)) = &mut stmt {
             if let Some(pyfn_args) = get_pyfn_attr(&mut func.attrs)? {
                 let module_name = pyfn_args.modname;
+                let python_name = pyfn_args
+                    .options
+                    .name
+                    .as_ref()
+                    .map_or_else(|| func.sig.ident.unraw(), |name| name.value.0.clone())
+                    .to_string();
+                let unexported = pyfn_args.options.unexported.is_some();
                 let wrapped_function = impl_wrap_pyfunction(func, pyfn_args.options)?;
                 let name = &func.sig.ident;
-                let statements: Vec<syn::Stmt> = syn::parse_quote! {
-                    #wrapped_function
-                    #module_name.add_function(#krate::impl_::pyfunction::_wrap_pyfunction(&#name::DEF, #module_name)?)?;
+                let function = quote! {
+                    #krate::impl_::pyfunction::_wrap_pyfunction(&#name::DEF, #module_name)?
+                };
+                let statements: Vec<syn::Stmt> = if unexported {
+                    syn::parse_quote! {
+                        #wrapped_function
+                        #module_name.add_unexported(#python_name, #function)?;
+                    }
+                } else {
+                    syn::parse_quote! {
+                        #wrapped_function
+                        #module_name.add_function(#function)?;
+                    }
                 };
                 stmts.extend(statements);
             }
@@ -187,6 +246,7 @@ fn get_pyfn_attr(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Option<PyFnArgs
 enum PyModulePyO3Option {
     Crate(CrateAttribute),
     Name(NameAttribute),
+    GILUsed(GILUsedAttribute),
 }
 
 impl Parse for PyModulePyO3Option {
@@ -196,6 +256,8 @@ impl Parse for PyModulePyO3Option {
             input.parse().map(PyModulePyO3Option::Name)
         } else if lookahead.peek(syn::Token![crate]) {
             input.parse().map(PyModulePyO3Option::Crate)
+        } else if lookahead.peek(attributes::kw::gil_used) {
+            input.parse().map(PyModulePyO3Option::GILUsed)
         } else {
             Err(lookahead.error())
         }
@@ -98,6 +98,7 @@ pub fn impl_arg_params(
         quote! { ::std::option::Option::None }
     };
     let python_name = &spec.python_name;
+    let keyword_index = impl_keyword_index(&spec.signature);
 
     let extract_expression = if fastcall {
         quote! {
@@ -130,6 +131,7 @@ pub fn impl_arg_params(
                     positional_only_parameters: #positional_only_parameters,
                     required_positional_parameters: #required_positional_parameters,
                     keyword_only_parameters: &[#(#keyword_only_parameters),*],
+                    keyword_index: #keyword_index,
                 };
                 let mut #args_array = [::std::option::Option::None; #num_params];
                 let (_args, _kwargs) = #extract_expression;
@@ -138,6 +140,64 @@ pub fn impl_arg_params(
     ))
 }
 
+/// Generates a lookup from keyword name to index in the output array, specialized to this
+/// signature.
+///
+/// Small signatures use a `match` on the name; signatures with many parameters (such as
+/// config-style constructors) binary search a table sorted at compile time instead.
+fn impl_keyword_index(signature: &FunctionSignature<'_>) -> TokenStream {
+    /// Above this many parameters, the sorted table outperforms the `match`.
+    const MAX_MATCH_PARAMETERS: usize = 8;
+
+    let python_signature = &signature.python_signature;
+
+    // Positional-only parameters still need an entry so that passing them by keyword
+    // produces the correct error.
+    let mut keywords: Vec<(&str, usize)> = python_signature
+        .positional_parameters
+        .iter()
+        .chain(
+            python_signature
+                .keyword_only_parameters
+                .iter()
+                .map(|(name, _)| name),
+        )
+        .map(String::as_str)
+        .zip(0..)
+        .collect();
+
+    let lookup = if keywords.len() > MAX_MATCH_PARAMETERS {
+        // Sorting by bytes matches the runtime `str` ordering used by `binary_search_by`.
+        keywords.sort_unstable();
+        let num_keywords = keywords.len();
+        let (names, indices): (Vec<_>, Vec<_>) = keywords.into_iter().unzip();
+        quote! {
+            const KEYWORDS: [(&str, usize); #num_keywords] = [#((#names, #indices)),*];
+            KEYWORDS
+                .binary_search_by(|(keyword, _)| (*keyword).cmp(name))
+                .ok()
+                .map(|i| KEYWORDS[i].1)
+        }
+    } else {
+        let (names, indices): (Vec<_>, Vec<_>) = keywords.into_iter().unzip();
+        quote! {
+            match name {
+                #(#names => ::std::option::Option::Some(#indices),)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    };
+
+    quote! {
+        ::std::option::Option::Some({
+            fn keyword_index(name: &str) -> ::std::option::Option<usize> {
+                #lookup
+            }
+            keyword_index
+        })
+    }
+}
+
 /// Re option_pos: The option slice doesn't contain the py: Python argument, so the argument
 /// index and the index in option diverge when using py: Python
 fn impl_arg_param(
@@ -152,12 +212,8 @@ fn impl_arg_param(
         ($($tokens:tt)*) => { quote_spanned!(arg.ty.span() => $($tokens)*) }
     }
 
-    if arg.py {
-        return Ok(quote! { py });
-    }
-
-    if arg.is_cancel_handle {
-        return Ok(quote! { __cancel_handle });
+    if let Some(value) = arg.injected_value() {
+        return Ok(value);
     }
 
     let name = arg.name;
@@ -204,6 +260,35 @@ fn impl_arg_param(
     let arg_value = quote_arg_span!(#args_array[#option_pos]);
     *option_pos += 1;
 
+    if let Some(factory) = arg.attrs.default_factory.as_ref().map(|attr| &attr.value) {
+        let holder = push_holder();
+        return Ok(quote_arg_span! {
+            _pyo3::impl_::extract_argument::extract_argument_with_default_factory(
+                #arg_value,
+                &mut #holder,
+                #name_str,
+                py,
+                #factory as fn(_) -> _,
+            )?
+        });
+    } else if let Some(factory) = arg.attrs.default_once.as_ref().map(|attr| &attr.value) {
+        let holder = push_holder();
+        return Ok(quote_arg_span! {
+            {
+                static DEFAULT: _pyo3::sync::GILOnceCell<_pyo3::PyObject> =
+                    _pyo3::sync::GILOnceCell::new();
+                _pyo3::impl_::extract_argument::extract_argument_with_default_once(
+                    #arg_value,
+                    &mut #holder,
+                    #name_str,
+                    py,
+                    &DEFAULT,
+                    #factory as fn(_) -> _,
+                )?
+            }
+        });
+    }
+
     let mut default = arg.default.as_ref().map(|expr| quote!(#expr));
 
     // Option<T> arguments have special treatment: the default should be specified _without_ the
@@ -2,8 +2,9 @@ use std::borrow::Cow;
 
 use crate::attributes::kw::frozen;
 use crate::attributes::{
-    self, kw, take_pyo3_options, CrateAttribute, ExtendsAttribute, FreelistAttribute,
-    ModuleAttribute, NameAttribute, NameLitStr, RenameAllAttribute,
+    self, kw, take_pyo3_options, AbcAttribute, CrateAttribute, ExtendsAttribute, FreelistAttribute,
+    MatchArgsAttribute, MetaclassAttribute, ModuleAttribute, NameAttribute, NameLitStr,
+    RenameAllAttribute, SharedAttribute,
 };
 use crate::deprecations::Deprecations;
 use crate::konst::{ConstAttributes, ConstSpec};
@@ -11,12 +12,12 @@ use crate::method::{FnArg, FnSpec};
 use crate::pyimpl::{gen_py_const, PyClassMethodsType};
 use crate::pymethod::{
     impl_py_getter_def, impl_py_setter_def, MethodAndMethodDef, MethodAndSlotDef, PropertyType,
-    SlotDef, __INT__, __REPR__, __RICHCMP__,
+    SlotDef, __BOOL__, __INT__, __LEN__, __REPR__, __RICHCMP__,
 };
 use crate::utils::{self, apply_renaming_rule, get_pyo3_crate, PythonDoc};
 use crate::PyFunctionOptions;
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
@@ -55,46 +56,82 @@ impl PyClassArgs {
 
 #[derive(Clone, Default)]
 pub struct PyClassPyO3Options {
+    pub abc: Option<AbcAttribute>,
+    pub array: Option<kw::array>,
     pub krate: Option<CrateAttribute>,
+    pub copy: Option<kw::copy>,
+    pub dataclass: Option<kw::dataclass>,
     pub dict: Option<kw::dict>,
     pub extends: Option<ExtendsAttribute>,
     pub get_all: Option<kw::get_all>,
     pub freelist: Option<FreelistAttribute>,
     pub frozen: Option<kw::frozen>,
+    pub generic: Option<kw::generic>,
+    pub immortal: Option<kw::immortal>,
+    pub immutable_type: Option<kw::immutable_type>,
+    pub len: Option<kw::len>,
     pub mapping: Option<kw::mapping>,
+    pub match_args: Option<MatchArgsAttribute>,
+    pub metaclass: Option<MetaclassAttribute>,
     pub module: Option<ModuleAttribute>,
     pub name: Option<NameAttribute>,
+    pub ord: Option<kw::ord>,
+    pub pickle: Option<kw::pickle>,
     pub rename_all: Option<RenameAllAttribute>,
     pub sequence: Option<kw::sequence>,
     pub set_all: Option<kw::set_all>,
+    pub shared: Option<SharedAttribute>,
     pub subclass: Option<kw::subclass>,
     pub unsendable: Option<kw::unsendable>,
+    pub vectorcall: Option<kw::vectorcall>,
     pub weakref: Option<kw::weakref>,
 }
 
 enum PyClassPyO3Option {
+    Abc(AbcAttribute),
+    Array(kw::array),
     Crate(CrateAttribute),
+    Copy(kw::copy),
+    Dataclass(kw::dataclass),
     Dict(kw::dict),
     Extends(ExtendsAttribute),
     Freelist(FreelistAttribute),
     Frozen(kw::frozen),
+    Generic(kw::generic),
     GetAll(kw::get_all),
+    Immortal(kw::immortal),
+    ImmutableType(kw::immutable_type),
+    Len(kw::len),
     Mapping(kw::mapping),
+    MatchArgs(MatchArgsAttribute),
+    Metaclass(MetaclassAttribute),
     Module(ModuleAttribute),
     Name(NameAttribute),
+    Ord(kw::ord),
+    Pickle(kw::pickle),
     RenameAll(RenameAllAttribute),
     Sequence(kw::sequence),
     SetAll(kw::set_all),
+    Shared(SharedAttribute),
     Subclass(kw::subclass),
     Unsendable(kw::unsendable),
+    Vectorcall(kw::vectorcall),
     Weakref(kw::weakref),
 }
 
 impl Parse for PyClassPyO3Option {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let lookahead = input.lookahead1();
-        if lookahead.peek(Token![crate]) {
+        if lookahead.peek(attributes::kw::abc) {
+            input.parse().map(PyClassPyO3Option::Abc)
+        } else if lookahead.peek(attributes::kw::array) {
+            input.parse().map(PyClassPyO3Option::Array)
+        } else if lookahead.peek(Token![crate]) {
             input.parse().map(PyClassPyO3Option::Crate)
+        } else if lookahead.peek(attributes::kw::copy) {
+            input.parse().map(PyClassPyO3Option::Copy)
+        } else if lookahead.peek(attributes::kw::dataclass) {
+            input.parse().map(PyClassPyO3Option::Dataclass)
         } else if lookahead.peek(kw::dict) {
             input.parse().map(PyClassPyO3Option::Dict)
         } else if lookahead.peek(kw::extends) {
@@ -103,24 +140,44 @@ impl Parse for PyClassPyO3Option {
             input.parse().map(PyClassPyO3Option::Freelist)
         } else if lookahead.peek(attributes::kw::frozen) {
             input.parse().map(PyClassPyO3Option::Frozen)
+        } else if lookahead.peek(attributes::kw::generic) {
+            input.parse().map(PyClassPyO3Option::Generic)
         } else if lookahead.peek(attributes::kw::get_all) {
             input.parse().map(PyClassPyO3Option::GetAll)
+        } else if lookahead.peek(attributes::kw::immortal) {
+            input.parse().map(PyClassPyO3Option::Immortal)
+        } else if lookahead.peek(attributes::kw::immutable_type) {
+            input.parse().map(PyClassPyO3Option::ImmutableType)
+        } else if lookahead.peek(attributes::kw::len) {
+            input.parse().map(PyClassPyO3Option::Len)
         } else if lookahead.peek(attributes::kw::mapping) {
             input.parse().map(PyClassPyO3Option::Mapping)
+        } else if lookahead.peek(attributes::kw::match_args) {
+            input.parse().map(PyClassPyO3Option::MatchArgs)
+        } else if lookahead.peek(attributes::kw::metaclass) {
+            input.parse().map(PyClassPyO3Option::Metaclass)
         } else if lookahead.peek(attributes::kw::module) {
             input.parse().map(PyClassPyO3Option::Module)
         } else if lookahead.peek(kw::name) {
             input.parse().map(PyClassPyO3Option::Name)
+        } else if lookahead.peek(attributes::kw::ord) {
+            input.parse().map(PyClassPyO3Option::Ord)
+        } else if lookahead.peek(attributes::kw::pickle) {
+            input.parse().map(PyClassPyO3Option::Pickle)
         } else if lookahead.peek(kw::rename_all) {
             input.parse().map(PyClassPyO3Option::RenameAll)
         } else if lookahead.peek(attributes::kw::sequence) {
             input.parse().map(PyClassPyO3Option::Sequence)
+        } else if lookahead.peek(attributes::kw::shared) {
+            input.parse().map(PyClassPyO3Option::Shared)
         } else if lookahead.peek(attributes::kw::set_all) {
             input.parse().map(PyClassPyO3Option::SetAll)
         } else if lookahead.peek(attributes::kw::subclass) {
             input.parse().map(PyClassPyO3Option::Subclass)
         } else if lookahead.peek(attributes::kw::unsendable) {
             input.parse().map(PyClassPyO3Option::Unsendable)
+        } else if lookahead.peek(attributes::kw::vectorcall) {
+            input.parse().map(PyClassPyO3Option::Vectorcall)
         } else if lookahead.peek(attributes::kw::weakref) {
             input.parse().map(PyClassPyO3Option::Weakref)
         } else {
@@ -162,20 +219,52 @@ impl PyClassPyO3Options {
         }
 
         match option {
+            PyClassPyO3Option::Abc(abc) => {
+                ensure_spanned!(
+                    abc.value.value().contains('.'),
+                    abc.value.span() => "expected the qualified name of an ABC, e.g. `collections.abc.Mapping`"
+                );
+                set_option!(abc)
+            }
+            PyClassPyO3Option::Array(array) => set_option!(array),
             PyClassPyO3Option::Crate(krate) => set_option!(krate),
+            PyClassPyO3Option::Copy(copy) => set_option!(copy),
+            PyClassPyO3Option::Dataclass(dataclass) => set_option!(dataclass),
             PyClassPyO3Option::Dict(dict) => set_option!(dict),
             PyClassPyO3Option::Extends(extends) => set_option!(extends),
             PyClassPyO3Option::Freelist(freelist) => set_option!(freelist),
             PyClassPyO3Option::Frozen(frozen) => set_option!(frozen),
+            PyClassPyO3Option::Generic(generic) => set_option!(generic),
             PyClassPyO3Option::GetAll(get_all) => set_option!(get_all),
+            PyClassPyO3Option::Immortal(immortal) => set_option!(immortal),
+            PyClassPyO3Option::ImmutableType(immutable_type) => set_option!(immutable_type),
+            PyClassPyO3Option::Len(len) => set_option!(len),
             PyClassPyO3Option::Mapping(mapping) => set_option!(mapping),
-            PyClassPyO3Option::Module(module) => set_option!(module),
+            PyClassPyO3Option::MatchArgs(match_args) => set_option!(match_args),
+            PyClassPyO3Option::Metaclass(metaclass) => set_option!(metaclass),
+            PyClassPyO3Option::Module(module) => {
+                ensure_spanned!(
+                    is_valid_module_name(&module.value.value()),
+                    module.value.span() => "expected a module name, such as `\"my_package.my_module\"`"
+                );
+                set_option!(module)
+            }
             PyClassPyO3Option::Name(name) => set_option!(name),
+            PyClassPyO3Option::Ord(ord) => set_option!(ord),
+            PyClassPyO3Option::Pickle(pickle) => set_option!(pickle),
             PyClassPyO3Option::RenameAll(rename_all) => set_option!(rename_all),
             PyClassPyO3Option::Sequence(sequence) => set_option!(sequence),
             PyClassPyO3Option::SetAll(set_all) => set_option!(set_all),
+            PyClassPyO3Option::Shared(shared) => {
+                ensure_spanned!(
+                    !shared.value.value().is_empty() && !shared.value.value().contains('\0'),
+                    shared.value.span() => "expected a non-empty name, e.g. `\"my_package.MyClass\"`"
+                );
+                set_option!(shared)
+            }
             PyClassPyO3Option::Subclass(subclass) => set_option!(subclass),
             PyClassPyO3Option::Unsendable(unsendable) => set_option!(unsendable),
+            PyClassPyO3Option::Vectorcall(vectorcall) => set_option!(vectorcall),
             PyClassPyO3Option::Weakref(weakref) => set_option!(weakref),
         }
         Ok(())
@@ -202,7 +291,8 @@ pub fn build_py_class(
     ensure_spanned!(
         class.generics.params.is_empty(),
         class.generics.span() =>
-            "#[pyclass] cannot have generic parameters. \
+            "#[pyclass] cannot have generic parameters, instead put #[pyclass] on a type alias \
+            for each concrete instantiation. \
             For an explanation, see https://pyo3.rs/latest/class.html#no-generic-parameters"
     );
 
@@ -254,6 +344,37 @@ pub fn build_py_class(
     impl_class(&class.ident, &args, doc, field_options, methods_type, krate)
 }
 
+/// Builds a `#[pyclass]` for a concrete instantiation of a generic struct, declared as a type
+/// alias such as `type WrapperF64 = Wrapper<f64>;`.
+///
+/// The class has no field descriptors, as the fields are not visible to the macro; these can be
+/// exposed with `#[pymethods]` on the alias instead.
+pub fn build_py_class_alias(
+    alias: &mut syn::ItemType,
+    mut args: PyClassArgs,
+    methods_type: PyClassMethodsType,
+) -> syn::Result<TokenStream> {
+    args.options.take_pyo3_options(&mut alias.attrs)?;
+    let doc = utils::get_doc(&alias.attrs, None);
+    let krate = get_pyo3_crate(&args.options.krate);
+
+    ensure_spanned!(
+        alias.generics.params.is_empty(),
+        alias.generics.span() => "#[pyclass] type aliases cannot have generic parameters"
+    );
+    if let Some(get_all) = &args.options.get_all {
+        bail_spanned!(get_all.span() => "`get_all` is not supported on type aliases");
+    }
+    if let Some(set_all) = &args.options.set_all {
+        bail_spanned!(set_all.span() => "`set_all` is not supported on type aliases");
+    }
+    if let Some(pickle) = &args.options.pickle {
+        bail_spanned!(pickle.span() => "`pickle` is not supported on type aliases");
+    }
+
+    impl_class(&alias.ident, &args, doc, Vec::new(), methods_type, krate)
+}
+
 enum Annotated<X, Y> {
     Field(X),
     Struct(Y),
@@ -332,10 +453,18 @@ fn get_class_python_name<'a>(cls: &'a syn::Ident, args: &'a PyClassArgs) -> Cow<
     args.options
         .name
         .as_ref()
-        .map(|name_attr| Cow::Borrowed(&name_attr.value.0))
+        .map(|name_attr| Cow::Owned(name_attr.value.0.unraw()))
         .unwrap_or_else(|| Cow::Owned(cls.unraw()))
 }
 
+/// Checks that `module` is a dot-separated sequence of identifiers.
+fn is_valid_module_name(module: &str) -> bool {
+    use syn::parse::Parser;
+    module
+        .split('.')
+        .all(|part| syn::Ident::parse_any.parse_str(part).is_ok())
+}
+
 fn impl_class(
     cls: &syn::Ident,
     args: &PyClassArgs,
@@ -345,21 +474,147 @@ fn impl_class(
     krate: syn::Path,
 ) -> syn::Result<TokenStream> {
     let pytypeinfo_impl = impl_pytypeinfo(cls, args, None);
+    let ty: syn::Type = syn::parse_quote!(#cls);
 
-    let py_class_impl = PyClassImplsBuilder::new(
-        cls,
-        args,
-        methods_type,
-        descriptors_to_items(
-            cls,
+    let mut default_slots = vec![];
+    let default_richcmp = if args.options.ord.is_some() {
+        let mut richcmp_impl: syn::ImplItemFn = syn::parse_quote! {
+            fn __pyo3__richcmp__(
+                &self,
+                py: _pyo3::Python,
+                other: &_pyo3::PyAny,
+                op: _pyo3::basic::CompareOp
+            ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                use _pyo3::conversion::ToPyObject;
+                use ::core::result::Result::*;
+                match other.downcast::<_pyo3::PyCell<Self>>() {
+                    Ok(other) => {
+                        let other = other.try_borrow()?;
+                        Ok(op.matches(::core::cmp::Ord::cmp(self, &*other)).to_object(py))
+                    }
+                    Err(_) => Ok(py.NotImplemented()),
+                }
+            }
+        };
+        default_slots.push(generate_default_protocol_slot(
+            &ty,
+            &mut richcmp_impl,
+            &__RICHCMP__,
+        )?);
+        Some(richcmp_impl)
+    } else {
+        None
+    };
+
+    let (len_impls, len_methods) = if args.options.len.is_some() {
+        impl_len(&ty, &mut default_slots)?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let (array_impls, array_methods) = if args.options.array.is_some() {
+        impl_array(&ty)?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let (pickle_impls, pickle_methods) = if let Some(pickle) = &args.options.pickle {
+        ensure_spanned!(
+            args.options.extends.is_none(),
+            pickle.span() => "`pickle` cannot be used together with `extends`"
+        );
+        impl_pickle(&ty, &field_options, args.options.frozen.is_some())?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let (copy_impls, copy_methods) = if let Some(copy) = &args.options.copy {
+        ensure_spanned!(
+            args.options.extends.is_none(),
+            copy.span() => "`copy` cannot be used together with `extends`"
+        );
+        impl_copy(&ty, &field_options, args.options.frozen.is_some())?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let (dataclass_impls, dataclass_methods) = if args.options.dataclass.is_some() {
+        impl_dataclass(
+            &ty,
+            &field_options,
             args.options.rename_all.as_ref(),
-            args.options.frozen,
-            field_options,
-        )?,
-        vec![],
-    )
-    .doc(doc)
-    .impl_all()?;
+            args.options.match_args.is_none(),
+        )?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let (annotations_impls, annotations_methods) =
+        impl_annotations(&ty, &field_options, args.options.rename_all.as_ref())?;
+    let doc_attributes = class_doc_attributes(&field_options, args.options.rename_all.as_ref())?;
+
+    let (match_args_impls, match_args_methods) = if let Some(match_args) = &args.options.match_args
+    {
+        let names: Vec<TokenStream> = match_args
+            .value
+            .names
+            .iter()
+            .map(|name| quote!(#name))
+            .collect();
+        impl_match_args(&ty, &names)?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let (generic_impls, generic_methods) = if args.options.generic.is_some() {
+        impl_generic(&ty)?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let mut default_methods = descriptors_to_items(
+        cls,
+        args.options.rename_all.as_ref(),
+        args.options.frozen,
+        field_options,
+    )?;
+    default_methods.extend(len_methods);
+    default_methods.extend(array_methods);
+    default_methods.extend(pickle_methods);
+    default_methods.extend(copy_methods);
+    default_methods.extend(dataclass_methods);
+    default_methods.extend(match_args_methods);
+    default_methods.extend(annotations_methods);
+    default_methods.extend(generic_methods);
+
+    let py_class_impl =
+        PyClassImplsBuilder::new(cls, args, methods_type, default_methods, default_slots)
+            .doc(doc)
+            .doc_attributes(doc_attributes)
+            .impl_all()?;
+
+    let default_impls: Vec<syn::ImplItemFn> = default_richcmp
+        .into_iter()
+        .chain(len_impls)
+        .chain(array_impls)
+        .chain(pickle_impls)
+        .chain(copy_impls)
+        .chain(dataclass_impls)
+        .chain(match_args_impls)
+        .chain(annotations_impls)
+        .chain(generic_impls)
+        .collect();
+    let default_impls = if default_impls.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            impl #cls {
+                #(#default_impls)*
+            }
+        }
+    };
 
     Ok(quote! {
         const _: () = {
@@ -368,10 +623,547 @@ fn impl_class(
             #pytypeinfo_impl
 
             #py_class_impl
+
+            #default_impls
         };
     })
 }
 
+/// Generates `__getstate__`, `__setstate__`, `__reduce_ex__` and the reconstructor used by
+/// `#[pyclass(pickle)]`.
+///
+/// The pickled state is a dict of field name to value for structs with named fields, or a tuple
+/// of the field values for tuple structs. Instances reduce to the `__pyo3_unpickle__` classmethod
+/// of `type(self)`, so that subclasses unpickle as themselves, along with the instance `__dict__`
+/// if it is not empty; the class must be importable from its `module` for unpickling to succeed.
+/// Frozen classes get no `__setstate__`.
+fn impl_pickle(
+    cls: &syn::Type,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    frozen: bool,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    let fields: Vec<_> = field_options.iter().map(|(field, _)| *field).collect();
+    let cfg_attrs: Vec<Vec<&syn::Attribute>> = fields
+        .iter()
+        .map(|field| cfg_attributes(field).collect())
+        .collect();
+    let is_tuple_struct = matches!(fields.first(), Some(field) if field.ident.is_none());
+
+    let (getstate_body, unpickle_body) = if is_tuple_struct {
+        let getstate_stmts = tuple_struct_field_accesses(&fields, |index| {
+            quote! { state.push(_pyo3::ToPyObject::to_object(&self.#index, py)); }
+        })?;
+        (
+            quote! {
+                let mut state: ::std::vec::Vec<_pyo3::PyObject> = ::std::vec::Vec::new();
+                #(#getstate_stmts)*
+                ::std::result::Result::Ok(
+                    _pyo3::types::PyTuple::new_bound(py, state).into_any().unbind()
+                )
+            },
+            quote! {
+                let state = state.downcast::<_pyo3::types::PyTuple>()?;
+                let items = &mut _pyo3::prelude::PyTupleMethods::iter(state);
+                Self(
+                    #(#(#cfg_attrs)* _pyo3::prelude::PyAnyMethods::extract(
+                        &::std::iter::Iterator::next(items).ok_or_else(|| {
+                            _pyo3::exceptions::PyValueError::new_err(
+                                "pickled state has too few fields"
+                            )
+                        })?
+                    )?,)*
+                )
+            },
+        )
+    } else {
+        let idents: Vec<_> = fields
+            .iter()
+            .filter_map(|field| field.ident.as_ref())
+            .collect();
+        let names: Vec<_> = idents
+            .iter()
+            .map(|ident| ident.unraw().to_string())
+            .collect();
+        (
+            quote! {
+                let state = _pyo3::types::PyDict::new_bound(py);
+                #(#(#cfg_attrs)* _pyo3::prelude::PyDictMethods::set_item(&state, #names, &self.#idents)?;)*
+                ::std::result::Result::Ok(state.into_any().unbind())
+            },
+            quote! {
+                let state = state.downcast::<_pyo3::types::PyDict>()?;
+                Self {
+                    #(#(#cfg_attrs)* #idents: _pyo3::prelude::PyAnyMethods::extract(
+                        &_pyo3::prelude::PyDictMethods::get_item(state, #names)?.ok_or_else(|| {
+                            _pyo3::exceptions::PyKeyError::new_err(#names)
+                        })?
+                    )?,)*
+                }
+            },
+        )
+    };
+
+    let mut impls: Vec<syn::ImplItemFn> = vec![
+        parse_quote! {
+            #[pyo3(name = "__getstate__")]
+            fn __pyo3__getstate__(
+                &self,
+                py: _pyo3::Python<'_>,
+            ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                #getstate_body
+            }
+        },
+        parse_quote! {
+            #[pyo3(name = "__reduce_ex__")]
+            fn __pyo3__reduce_ex__<'py>(
+                slf: _pyo3::Py<Self>,
+                py: _pyo3::Python<'py>,
+                protocol: &_pyo3::Bound<'py, _pyo3::PyAny>,
+            ) -> _pyo3::PyResult<(_pyo3::Bound<'py, _pyo3::PyAny>, _pyo3::PyObject)> {
+                use _pyo3::prelude::PyAnyMethods;
+                // The state does not depend on the pickle protocol.
+                let _ = protocol;
+                let slf = slf.bind(py);
+                let unpickle = slf
+                    .as_any()
+                    .get_type()
+                    .as_borrowed()
+                    .as_any()
+                    .getattr(_pyo3::intern!(py, "__pyo3_unpickle__"))?;
+                let state = slf.borrow().__pyo3__getstate__(py)?;
+                let args = match _pyo3::impl_::pyclass::pickle_instance_dict(slf.as_any())? {
+                    ::std::option::Option::Some(dict) => _pyo3::IntoPy::into_py((state, dict), py),
+                    ::std::option::Option::None => _pyo3::IntoPy::into_py((state,), py),
+                };
+                ::std::result::Result::Ok((unpickle, args))
+            }
+        },
+        parse_quote! {
+            #[classmethod]
+            #[pyo3(name = "__pyo3_unpickle__", signature = (state, dict = None))]
+            fn __pyo3__unpickle__<'py>(
+                cls: &_pyo3::Bound<'py, _pyo3::types::PyType>,
+                state: &_pyo3::Bound<'py, _pyo3::PyAny>,
+                dict: ::std::option::Option<&_pyo3::Bound<'py, _pyo3::types::PyDict>>,
+            ) -> _pyo3::PyResult<_pyo3::Bound<'py, Self>> {
+                use _pyo3::prelude::PyAnyMethods;
+                let value = { #unpickle_body };
+                _pyo3::impl_::pyclass::unpickle_as(cls, value, dict)
+            }
+        },
+    ];
+    if !frozen {
+        impls.push(parse_quote! {
+            #[pyo3(name = "__setstate__")]
+            fn __pyo3__setstate__(
+                &mut self,
+                state: &_pyo3::Bound<'_, _pyo3::PyAny>,
+            ) -> _pyo3::PyResult<()> {
+                use _pyo3::prelude::PyAnyMethods;
+                *self = { #unpickle_body };
+                ::std::result::Result::Ok(())
+            }
+        });
+    }
+
+    let methods = gen_default_methods(cls, &mut impls)?;
+    Ok((impls, methods))
+}
+
+/// Generates `access(index)` for each field of a tuple struct, honouring `#[cfg]` on the fields.
+///
+/// The index of a field shifts when earlier fields are removed by `#[cfg]`, so the access is
+/// generated once for each combination of the `#[cfg]`s of the preceding fields, each guarded by
+/// the `#[cfg]` under which that index is correct.
+fn tuple_struct_field_accesses(
+    fields: &[&syn::Field],
+    access: impl Fn(syn::Index) -> TokenStream,
+) -> Result<Vec<TokenStream>> {
+    let predicates = fields
+        .iter()
+        .map(|field| {
+            cfg_attributes(field)
+                .map(|attr| Ok(attr.meta.require_list()?.tokens.clone()))
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut accesses = Vec::new();
+    for (i, own) in predicates.iter().enumerate() {
+        let preceding: Vec<_> = predicates[..i]
+            .iter()
+            .filter(|predicate| !predicate.is_empty())
+            .collect();
+        for enabled in 0..(1usize << preceding.len()) {
+            let mut conditions = own.clone();
+            let mut removed = 0;
+            for (bit, predicate) in preceding.iter().enumerate() {
+                if enabled & (1 << bit) != 0 {
+                    conditions.push(quote! { all(#(#predicate),*) });
+                } else {
+                    conditions.push(quote! { not(all(#(#predicate),*)) });
+                    removed += 1;
+                }
+            }
+            let access = access(syn::Index::from(i - removed));
+            accesses.push(if conditions.is_empty() {
+                access
+            } else {
+                quote! { #[cfg(all(#(#conditions),*))] { #access } }
+            });
+        }
+    }
+    Ok(accesses)
+}
+
+/// Generates `__len__`, `__bool__` and `__length_hint__` from the `PyLen` implementation, for
+/// `#[pyclass(len)]`.
+fn impl_len(
+    cls: &syn::Type,
+    default_slots: &mut Vec<MethodAndSlotDef>,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    let mut len_impl: syn::ImplItemFn = parse_quote! {
+        fn __pyo3__len__(&self) -> _pyo3::PyResult<usize> {
+            _pyo3::impl_::pyclass::py_len(self)
+        }
+    };
+    let mut bool_impl: syn::ImplItemFn = parse_quote! {
+        fn __pyo3__bool__(&self) -> bool {
+            !_pyo3::pyclass::PyLen::py_is_empty(self)
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        cls,
+        &mut len_impl,
+        &__LEN__,
+    )?);
+    default_slots.push(generate_default_protocol_slot(
+        cls,
+        &mut bool_impl,
+        &__BOOL__,
+    )?);
+
+    let mut impls: Vec<syn::ImplItemFn> = vec![parse_quote! {
+        #[pyo3(name = "__length_hint__")]
+        fn __pyo3__length_hint__(&self) -> usize {
+            _pyo3::impl_::pyclass::py_length_hint(self)
+        }
+    }];
+    let methods = gen_default_methods(cls, &mut impls)?;
+    impls.push(len_impl);
+    impls.push(bool_impl);
+    Ok((impls, methods))
+}
+
+/// Generates the `__array_interface__` property and the `__array__` method from the
+/// `PyArrayData` implementation, for `#[pyclass(array)]`.
+fn impl_array(cls: &syn::Type) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    let mut impls: Vec<syn::ImplItemFn> = vec![
+        parse_quote! {
+            #[getter(__array_interface__)]
+            fn __pyo3__array_interface__(
+                &self,
+                py: _pyo3::Python<'_>,
+            ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                _pyo3::impl_::pyclass::py_array_interface(py, self)
+            }
+        },
+        parse_quote! {
+            #[pyo3(name = "__array__", signature = (dtype = None, copy = None))]
+            fn __pyo3__array__(
+                slf: _pyo3::Py<Self>,
+                py: _pyo3::Python<'_>,
+                dtype: ::std::option::Option<&_pyo3::Bound<'_, _pyo3::PyAny>>,
+                copy: ::std::option::Option<bool>,
+            ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                _pyo3::impl_::pyclass::py_array(slf.bind(py).as_any(), dtype, copy)
+                    .map(_pyo3::Bound::unbind)
+            }
+        },
+    ];
+    let methods = gen_default_methods(cls, &mut impls)?;
+    Ok((impls, methods))
+}
+
+/// Generates `__copy__` and `__deepcopy__` for `#[pyclass(copy)]` from the `Clone` implementation.
+///
+/// `__deepcopy__` clones the struct and then replaces each field with `copy.deepcopy(field, memo)`,
+/// so every field must be convertible to and from a Python object. Unless the class is frozen, the
+/// copy is recorded in `memo` before recursing, so that reference cycles back to the original
+/// resolve to the copy. Both methods create the copy with the type of `self`.
+fn impl_copy(
+    cls: &syn::Type,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    frozen: bool,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    let deepcopy_fields: Vec<_> = field_options
+        .iter()
+        .enumerate()
+        .map(|(index, (field, _))| {
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(index.into()),
+            };
+            let cfg_attrs = cfg_attributes(field);
+            let ty = &field.ty;
+            // Spanned on the field type, to point at fields which cannot be deep-copied
+            let deepcopy = if frozen {
+                quote_spanned! { ty.span() =>
+                    let field = _pyo3::ToPyObject::to_object(&copy.#member, py);
+                    copy.#member =
+                        _pyo3::impl_::pyclass::deepcopy_field::<#ty>(py, field, memo)?;
+                }
+            } else {
+                quote_spanned! { ty.span() =>
+                    let field = _pyo3::ToPyObject::to_object(&slf.borrow().#member, py);
+                    let field = _pyo3::impl_::pyclass::deepcopy_field::<#ty>(py, field, memo)?;
+                    copy.borrow_mut().#member = field;
+                }
+            };
+            quote! {
+                #(#cfg_attrs)*
+                {
+                    #deepcopy
+                }
+            }
+        })
+        .collect();
+
+    let deepcopy_body = if frozen {
+        quote! {
+            let mut copy = ::std::clone::Clone::clone(&*slf.borrow());
+            #(#deepcopy_fields)*
+            _pyo3::impl_::pyclass::copy_as_type_of(slf, copy).map(_pyo3::Bound::unbind)
+        }
+    } else {
+        quote! {
+            let copy = _pyo3::impl_::pyclass::copy_as_type_of(
+                slf,
+                ::std::clone::Clone::clone(&*slf.borrow()),
+            )?;
+            _pyo3::prelude::PyDictMethods::set_item(memo, slf.as_ptr() as usize, &copy)?;
+            #(#deepcopy_fields)*
+            ::std::result::Result::Ok(copy.unbind())
+        }
+    };
+
+    let mut impls: Vec<syn::ImplItemFn> = vec![
+        parse_quote! {
+            #[pyo3(name = "__copy__")]
+            fn __pyo3__copy__(
+                slf: _pyo3::Py<Self>,
+                py: _pyo3::Python<'_>,
+            ) -> _pyo3::PyResult<_pyo3::Py<Self>> {
+                let slf = slf.bind(py);
+                let copy = ::std::clone::Clone::clone(&*slf.borrow());
+                _pyo3::impl_::pyclass::copy_as_type_of(slf, copy).map(_pyo3::Bound::unbind)
+            }
+        },
+        parse_quote! {
+            #[pyo3(name = "__deepcopy__", signature = (memo))]
+            fn __pyo3__deepcopy__(
+                slf: _pyo3::Py<Self>,
+                py: _pyo3::Python<'_>,
+                memo: &_pyo3::Bound<'_, _pyo3::types::PyDict>,
+            ) -> _pyo3::PyResult<_pyo3::Py<Self>> {
+                let slf = slf.bind(py);
+                #deepcopy_body
+            }
+        },
+    ];
+    let methods = gen_default_methods(cls, &mut impls)?;
+    Ok((impls, methods))
+}
+
+/// The `#[cfg]` attributes of `field`, to apply to the items generated for it.
+fn cfg_attributes(field: &syn::Field) -> impl Iterator<Item = &syn::Attribute> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+}
+
+/// Returns the fields which have a getter, along with their Python names.
+fn getter_fields<'a>(
+    field_options: &[(&'a syn::Field, FieldPyO3Options)],
+    rename_all: Option<&RenameAllAttribute>,
+) -> Result<Vec<(&'a syn::Field, String)>> {
+    let mut fields = Vec::new();
+    for (field, options) in field_options {
+        if options.get.is_none() {
+            continue;
+        }
+        let name = match (&options.name, &field.ident) {
+            (Some(name), _) => name.value.0.unraw().to_string(),
+            (None, Some(ident)) => {
+                let name = ident.unraw().to_string();
+                match rename_all {
+                    Some(rename_all) => utils::apply_renaming_rule(rename_all.value.rule, &name),
+                    None => name,
+                }
+            }
+            (None, None) => {
+                bail_spanned!(field.span() => "`get` and `set` with tuple struct fields require `name`")
+            }
+        };
+        fields.push((*field, name));
+    }
+    Ok(fields)
+}
+
+/// Generates `__annotations__` from the types of the fields which have a getter, with the
+/// `experimental-inspect` feature.
+fn impl_annotations(
+    cls: &syn::Type,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    rename_all: Option<&RenameAllAttribute>,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    if cfg!(not(feature = "experimental-inspect")) {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let fields = getter_fields(field_options, rename_all)?;
+    if fields.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let hints = fields.iter().map(|(field, name)| {
+        let cfg_attrs = cfg_attributes(field);
+        let type_hint = field_type_hint(field);
+        quote! {
+            #(#cfg_attrs)*
+            (#name, #type_hint)
+        }
+    });
+    let mut impls: Vec<syn::ImplItemFn> = vec![parse_quote! {
+        #[classattr]
+        #[pyo3(name = "__annotations__")]
+        fn __pyo3__annotations__(py: _pyo3::Python<'_>) -> _pyo3::PyResult<_pyo3::PyObject> {
+            _pyo3::impl_::pyclass::class_annotations(py, &[#(#hints),*])
+        }
+    }];
+    let methods = gen_default_methods(cls, &mut impls)?;
+    Ok((impls, methods))
+}
+
+/// Generates the entries of the `Attributes:` section which the `experimental-inspect` feature
+/// appends to the class doc, one for each field which has a getter.
+fn class_doc_attributes(
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    rename_all: Option<&RenameAllAttribute>,
+) -> Result<Vec<TokenStream>> {
+    if cfg!(not(feature = "experimental-inspect")) {
+        return Ok(Vec::new());
+    }
+    Ok(getter_fields(field_options, rename_all)?
+        .into_iter()
+        .map(|(field, name)| {
+            let cfg_attrs = cfg_attributes(field);
+            let type_hint = field_type_hint(field);
+            let doc = utils::get_doc(&field.attrs, None);
+            quote! {
+                #(#cfg_attrs)*
+                (#name, #type_hint, #doc)
+            }
+        })
+        .collect())
+}
+
+/// The `FieldTypeHint` of `field`, resolved from its type.
+fn field_type_hint(field: &syn::Field) -> TokenStream {
+    let ty = &field.ty;
+    quote! { _pyo3::impl_::wrap::type_converter::<#ty>().type_hint() }
+}
+
+/// Generates `__dataclass_fields__` (and `__match_args__`, unless given explicitly) for
+/// `#[pyclass(dataclass)]`.
+///
+/// Only fields with a getter are included, in declaration order.
+fn impl_dataclass(
+    cls: &syn::Type,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    rename_all: Option<&RenameAllAttribute>,
+    with_match_args: bool,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    // Fields removed by `#[cfg]` are left out, like their getters
+    let names: Vec<TokenStream> = getter_fields(field_options, rename_all)?
+        .into_iter()
+        .map(|(field, name)| {
+            let cfg_attrs = cfg_attributes(field);
+            quote!(#(#cfg_attrs)* #name)
+        })
+        .collect();
+
+    let mut impls: Vec<syn::ImplItemFn> = vec![parse_quote! {
+        #[classattr]
+        #[pyo3(name = "__dataclass_fields__")]
+        fn __pyo3__dataclass_fields__(
+            py: _pyo3::Python<'_>,
+        ) -> _pyo3::PyResult<_pyo3::PyObject> {
+            _pyo3::impl_::pyclass::dataclass_fields(
+                py,
+                <Self as _pyo3::PyTypeInfo>::NAME,
+                &[#(#names),*],
+            )
+        }
+    }];
+    let mut methods = gen_default_methods(cls, &mut impls)?;
+
+    if with_match_args {
+        let (match_args_impls, match_args_methods) = impl_match_args(cls, &names)?;
+        impls.extend(match_args_impls);
+        methods.extend(match_args_methods);
+    }
+
+    Ok((impls, methods))
+}
+
+/// Generates the `__match_args__` class attribute used for positional patterns in `match`.
+fn impl_match_args(
+    cls: &syn::Type,
+    names: &[TokenStream],
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    let mut impls: Vec<syn::ImplItemFn> = vec![parse_quote! {
+        #[classattr]
+        #[pyo3(name = "__match_args__")]
+        fn __pyo3__match_args__(py: _pyo3::Python<'_>) -> _pyo3::Py<_pyo3::types::PyTuple> {
+            let names: &[&'static str] = &[#(#names),*];
+            _pyo3::types::PyTuple::new_bound(py, names).unbind()
+        }
+    }];
+    let methods = gen_default_methods(cls, &mut impls)?;
+    Ok((impls, methods))
+}
+
+/// Generates the `__class_getitem__` classmethod used by `#[pyclass(generic)]`.
+fn impl_generic(cls: &syn::Type) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndMethodDef>)> {
+    let mut impls: Vec<syn::ImplItemFn> = vec![parse_quote! {
+        #[classmethod]
+        #[pyo3(name = "__class_getitem__")]
+        fn __pyo3__class_getitem__<'py>(
+            cls: &_pyo3::Bound<'py, _pyo3::types::PyType>,
+            key: &_pyo3::Bound<'py, _pyo3::PyAny>,
+        ) -> _pyo3::PyResult<_pyo3::Bound<'py, _pyo3::PyAny>> {
+            _pyo3::impl_::pyclass::generic_alias(cls, key)
+        }
+    }];
+    let methods = gen_default_methods(cls, &mut impls)?;
+    Ok((impls, methods))
+}
+
+/// Generates the method definitions for methods which a `#[pyclass]` option adds to the class.
+fn gen_default_methods(
+    cls: &syn::Type,
+    impls: &mut [syn::ImplItemFn],
+) -> Result<Vec<MethodAndMethodDef>> {
+    let mut methods = Vec::with_capacity(impls.len());
+    for method in impls {
+        let options = PyFunctionOptions::from_attrs(&mut method.attrs)?;
+        match crate::pymethod::gen_py_method(cls, &mut method.sig, &mut method.attrs, options)?.0 {
+            crate::pymethod::GeneratedPyMethod::Method(method) => methods.push(method),
+            _ => unreachable!("generated default methods are plain methods"),
+        }
+    }
+    Ok(methods)
+}
+
 enum PyClassEnum<'a> {
     Simple(PyClassSimpleEnum<'a>),
     Complex(PyClassComplexEnum<'a>),
@@ -405,6 +1197,16 @@ pub fn build_py_enum(
         bail_spanned!(extends.span() => "enums can't extend from other classes");
     } else if let Some(subclass) = &args.options.subclass {
         bail_spanned!(subclass.span() => "enums can't be inherited by other classes");
+    } else if let Some(copy) = &args.options.copy {
+        bail_spanned!(copy.span() => "`copy` is not supported on enums");
+    } else if let Some(len) = &args.options.len {
+        bail_spanned!(len.span() => "`len` is not supported on enums");
+    } else if let Some(generic) = &args.options.generic {
+        bail_spanned!(generic.span() => "`generic` is not supported on enums");
+    } else if let Some(dataclass) = &args.options.dataclass {
+        bail_spanned!(dataclass.span() => "`dataclass` is not supported on enums");
+    } else if let Some(match_args) = &args.options.match_args {
+        bail_spanned!(match_args.span() => "`match_args` is not supported on enums");
     } else if enum_.variants.is_empty() {
         bail_spanned!(enum_.brace_token.span.join() => "#[pyclass] can't be used on enums without any variants");
     }
@@ -724,6 +1526,14 @@ fn impl_simple_enum(
     };
 
     let (default_richcmp, default_richcmp_slot) = {
+        // With `ord`, the ordering comparisons between two variants follow the Rust `Ord` impl.
+        let ordering_cmp = args.options.ord.map(|_| {
+            quote! {
+                if let Ok(other) = other.extract::<_pyo3::PyRef<Self>>() {
+                    return Ok(op.matches(::core::cmp::Ord::cmp(self, &*other)).to_object(py));
+                }
+            }
+        });
         let mut richcmp_impl: syn::ImplItemFn = syn::parse_quote! {
             fn __pyo3__richcmp__(
                 &self,
@@ -756,7 +1566,10 @@ fn impl_simple_enum(
 
                         return Ok(py.NotImplemented());
                     }
-                    _ => Ok(py.NotImplemented()),
+                    _ => {
+                        #ordering_cmp
+                        Ok(py.NotImplemented())
+                    }
                 }
             }
         };
@@ -815,6 +1628,10 @@ fn impl_complex_enum(
         rigged_args
     };
 
+    if let Some(ord) = &args.options.ord {
+        bail_spanned!(ord.span() => "`ord` is not yet supported for complex enums");
+    }
+
     let krate = get_pyo3_crate(&args.options.krate);
     let cls = complex_enum.ident;
     let variants = complex_enum.variants;
@@ -846,21 +1663,26 @@ fn impl_complex_enum(
                 quote! {
                     #cls::#variant_ident { .. } => {
                         let pyclass_init = _pyo3::PyClassInitializer::from(self).add_subclass(#variant_cls);
-                        let variant_value = _pyo3::Py::new(py, pyclass_init).unwrap();
-                        _pyo3::IntoPy::into_py(variant_value, py)
+                        _pyo3::Bound::new(py, pyclass_init).map(_pyo3::Bound::into_any)
                     }
                 }
             })
             .collect();
 
         quote! {
-            impl _pyo3::IntoPy<_pyo3::PyObject> for #cls {
-                fn into_py(self, py: _pyo3::Python) -> _pyo3::PyObject {
+            impl<'py> _pyo3::IntoPyObject<'py> for #cls {
+                fn into_pyobject(self, py: _pyo3::Python<'py>) -> _pyo3::PyResult<_pyo3::Bound<'py, _pyo3::PyAny>> {
                     match self {
                         #(#match_arms)*
                     }
                 }
             }
+
+            impl _pyo3::IntoPy<_pyo3::PyObject> for #cls {
+                fn into_py(self, py: _pyo3::Python) -> _pyo3::PyObject {
+                    _pyo3::IntoPyObject::into_pyobject(self, py).unwrap().unbind()
+                }
+            }
         }
     };
 
@@ -1305,6 +2127,7 @@ struct PyClassImplsBuilder<'a> {
     default_methods: Vec<MethodAndMethodDef>,
     default_slots: Vec<MethodAndSlotDef>,
     doc: Option<PythonDoc>,
+    doc_attributes: Vec<TokenStream>,
 }
 
 impl<'a> PyClassImplsBuilder<'a> {
@@ -1322,6 +2145,7 @@ impl<'a> PyClassImplsBuilder<'a> {
             default_methods,
             default_slots,
             doc: None,
+            doc_attributes: Vec::new(),
         }
     }
 
@@ -1332,6 +2156,13 @@ impl<'a> PyClassImplsBuilder<'a> {
         }
     }
 
+    fn doc_attributes(self, doc_attributes: Vec<TokenStream>) -> Self {
+        Self {
+            doc_attributes,
+            ..self
+        }
+    }
+
     fn impl_all(&self) -> Result<TokenStream> {
         let tokens = vec![
             self.impl_pyclass(),
@@ -1410,6 +2241,12 @@ impl<'a> PyClassImplsBuilder<'a> {
                         _pyo3::IntoPy::into_py(_pyo3::Py::new(py, self).unwrap(), py)
                     }
                 }
+
+                impl<'py> _pyo3::IntoPyObject<'py> for #cls {
+                    fn into_pyobject(self, py: _pyo3::Python<'py>) -> _pyo3::PyResult<_pyo3::Bound<'py, _pyo3::PyAny>> {
+                        _pyo3::Bound::new(py, self).map(_pyo3::Bound::into_any)
+                    }
+                }
             }
         } else {
             quote! {}
@@ -1418,6 +2255,17 @@ impl<'a> PyClassImplsBuilder<'a> {
     fn impl_pyclassimpl(&self) -> Result<TokenStream> {
         let cls = self.cls;
         let doc = self.doc.as_ref().map_or(quote! {"\0"}, |doc| quote! {#doc});
+        let doc_attributes = &self.doc_attributes;
+        let build_doc = if doc_attributes.is_empty() {
+            quote! {
+                build_pyclass_doc(<#cls as _pyo3::PyTypeInfo>::NAME, #doc, collector.new_text_signature())
+            }
+        } else {
+            quote! {
+                build_pyclass_doc(<#cls as _pyo3::PyTypeInfo>::NAME, #doc, collector.new_text_signature())
+                    .and_then(|doc| class_doc_with_attributes(py, doc, &[#(#doc_attributes),*]))
+            }
+        };
         let is_basetype = self.attr.options.subclass.is_some();
         let base = match &self.attr.options.extends {
             Some(extends_attr) => extends_attr.value.clone(),
@@ -1426,11 +2274,25 @@ impl<'a> PyClassImplsBuilder<'a> {
         let is_subclass = self.attr.options.extends.is_some();
         let is_mapping: bool = self.attr.options.mapping.is_some();
         let is_sequence: bool = self.attr.options.sequence.is_some();
+        let is_immutable_type = self.attr.options.immutable_type.is_some();
+        let is_immortal = self.attr.options.immortal.is_some();
 
         ensure_spanned!(
             !(is_mapping && is_sequence),
             self.cls.span() => "a `#[pyclass]` cannot be both a `mapping` and a `sequence`"
         );
+        if let Some(immutable_type) = &self.attr.options.immutable_type {
+            ensure_spanned!(
+                utils::has_immutable_types(),
+                immutable_type.span() => "`immutable_type` requires Python 3.10 or newer, without the `abi3` feature"
+            );
+        }
+        if let Some(immortal) = &self.attr.options.immortal {
+            ensure_spanned!(
+                utils::has_immortal_types(),
+                immortal.span() => "`immortal` is only supported on CPython 3.12 and newer, without the `abi3` feature or the free-threaded build"
+            );
+        }
 
         let dict_offset = if self.attr.options.dict.is_some() {
             quote! {
@@ -1453,6 +2315,50 @@ impl<'a> PyClassImplsBuilder<'a> {
             TokenStream::new()
         };
 
+        // insert space for the vectorcall function pointer
+        let vectorcall = if self.attr.options.vectorcall.is_some() {
+            quote! {
+                fn vectorcall_offset() -> ::std::option::Option<_pyo3::ffi::Py_ssize_t> {
+                    ::std::option::Option::Some(_pyo3::impl_::pyclass::vectorcall_offset::<Self>())
+                }
+
+                fn vectorcall() -> ::std::option::Option<_pyo3::impl_::pyclass::VectorcallFunc> {
+                    use _pyo3::impl_::pyclass::*;
+                    let collector = PyClassImplCollector::<Self>::new();
+                    collector.vectorcall()
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        let abc = if let Some(abc) = &self.attr.options.abc {
+            let abc = &abc.value;
+            quote! { ::std::option::Option::Some(#abc) }
+        } else {
+            quote! { ::std::option::Option::None }
+        };
+
+        let shared = if let Some(shared) = &self.attr.options.shared {
+            let shared = &shared.value;
+            quote! { ::std::option::Option::Some(#shared) }
+        } else {
+            quote! { ::std::option::Option::None }
+        };
+
+        let metaclass = if let Some(metaclass) = &self.attr.options.metaclass {
+            let metaclass = &metaclass.value;
+            quote! {
+                fn metaclass_type_object(
+                    py: _pyo3::Python<'_>,
+                ) -> ::std::option::Option<*mut _pyo3::ffi::PyTypeObject> {
+                    ::std::option::Option::Some(<#metaclass as _pyo3::PyTypeInfo>::type_object_raw(py))
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
         let thread_checker = if self.attr.options.unsendable.is_some() {
             quote! { _pyo3::impl_::pyclass::ThreadCheckerImpl }
         } else {
@@ -1480,8 +2386,13 @@ impl<'a> PyClassImplsBuilder<'a> {
                     Some(define_inventory_class(&inventory_class_name)),
                 )
             }
+            PyClassMethodsType::PyProto { .. } => {
+                unreachable!("#[pyclass] is not collected like a protocol trait impl")
+            }
         };
 
+        let pyproto_items = crate::pyproto::pyproto_items();
+
         let default_methods = self
             .default_methods
             .iter()
@@ -1521,6 +2432,12 @@ impl<'a> PyClassImplsBuilder<'a> {
             quote! { _pyo3::impl_::pyclass::PyClassDummySlot }
         };
 
+        let vectorcall_slot = if attr.options.vectorcall.is_some() {
+            quote! { _pyo3::impl_::pyclass::PyClassVectorcallSlot }
+        } else {
+            quote! { _pyo3::impl_::pyclass::PyClassDummySlot }
+        };
+
         let base_nativetype = if attr.options.extends.is_some() {
             quote! { <Self::BaseType as _pyo3::impl_::pyclass::PyClassBaseType>::BaseNativeType }
         } else {
@@ -1533,6 +2450,10 @@ impl<'a> PyClassImplsBuilder<'a> {
                 const IS_SUBCLASS: bool = #is_subclass;
                 const IS_MAPPING: bool = #is_mapping;
                 const IS_SEQUENCE: bool = #is_sequence;
+                const IS_IMMUTABLE_TYPE: bool = #is_immutable_type;
+                const IS_IMMORTAL: bool = #is_immortal;
+                const ABC: ::std::option::Option<&'static str> = #abc;
+                const SHARED: ::std::option::Option<&'static str> = #shared;
 
                 type BaseType = #base;
                 type ThreadChecker = #thread_checker;
@@ -1540,6 +2461,7 @@ impl<'a> PyClassImplsBuilder<'a> {
                 type PyClassMutability = <<#base as _pyo3::impl_::pyclass::PyClassBaseType>::PyClassMutability as _pyo3::impl_::pycell::PyClassMutability>::#class_mutability;
                 type Dict = #dict;
                 type WeakRef = #weakref;
+                type Vectorcall = #vectorcall_slot;
                 type BaseNativeType = #base_nativetype;
 
                 fn items_iter() -> _pyo3::impl_::pyclass::PyClassItemsIter {
@@ -1550,6 +2472,7 @@ impl<'a> PyClassImplsBuilder<'a> {
                         slots: &[#(#default_slot_defs),* #(#freelist_slots),*],
                     };
                     PyClassItemsIter::new(&INTRINSIC_ITEMS, #pymethods_items)
+                        .with_pyproto_items([#(#pyproto_items),*])
                 }
 
                 fn doc(py: _pyo3::Python<'_>) -> _pyo3::PyResult<&'static ::std::ffi::CStr>  {
@@ -1557,7 +2480,7 @@ impl<'a> PyClassImplsBuilder<'a> {
                     static DOC: _pyo3::sync::GILOnceCell<::std::borrow::Cow<'static, ::std::ffi::CStr>> = _pyo3::sync::GILOnceCell::new();
                     DOC.get_or_try_init(py, || {
                         let collector = PyClassImplCollector::<Self>::new();
-                        build_pyclass_doc(<#cls as _pyo3::PyTypeInfo>::NAME, #doc, collector.new_text_signature())
+                        #build_doc
                     }).map(::std::ops::Deref::deref)
                 }
 
@@ -1565,6 +2488,10 @@ impl<'a> PyClassImplsBuilder<'a> {
 
                 #weaklist_offset
 
+                #vectorcall
+
+                #metaclass
+
                 fn lazy_type_object() -> &'static _pyo3::impl_::pyclass::LazyTypeObject<Self> {
                     use _pyo3::impl_::pyclass::LazyTypeObject;
                     static TYPE_OBJECT: LazyTypeObject<#cls> = LazyTypeObject::new();
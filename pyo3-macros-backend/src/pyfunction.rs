@@ -1,7 +1,8 @@
 use crate::{
     attributes::{
         self, get_pyo3_options, take_attributes, take_pyo3_options, CrateAttribute,
-        FromPyWithAttribute, NameAttribute, TextSignatureAttribute,
+        DefaultFactoryAttribute, DefaultOnceAttribute, FromPyWithAttribute, NameAttribute,
+        TextSignatureAttribute,
     },
     deprecations::Deprecations,
     method::{self, CallingConvention, FnArg},
@@ -24,11 +25,17 @@ pub use self::signature::{FunctionSignature, SignatureAttribute};
 pub struct PyFunctionArgPyO3Attributes {
     pub from_py_with: Option<FromPyWithAttribute>,
     pub cancel_handle: Option<attributes::kw::cancel_handle>,
+    pub inject: Option<attributes::kw::inject>,
+    pub default_factory: Option<DefaultFactoryAttribute>,
+    pub default_once: Option<DefaultOnceAttribute>,
 }
 
 enum PyFunctionArgPyO3Attribute {
     FromPyWith(FromPyWithAttribute),
     CancelHandle(attributes::kw::cancel_handle),
+    Inject(attributes::kw::inject),
+    DefaultFactory(DefaultFactoryAttribute),
+    DefaultOnce(DefaultOnceAttribute),
 }
 
 impl Parse for PyFunctionArgPyO3Attribute {
@@ -38,6 +45,14 @@ impl Parse for PyFunctionArgPyO3Attribute {
             input.parse().map(PyFunctionArgPyO3Attribute::CancelHandle)
         } else if lookahead.peek(attributes::kw::from_py_with) {
             input.parse().map(PyFunctionArgPyO3Attribute::FromPyWith)
+        } else if lookahead.peek(attributes::kw::inject) {
+            input.parse().map(PyFunctionArgPyO3Attribute::Inject)
+        } else if lookahead.peek(attributes::kw::default_factory) {
+            input
+                .parse()
+                .map(PyFunctionArgPyO3Attribute::DefaultFactory)
+        } else if lookahead.peek(attributes::kw::default_once) {
+            input.parse().map(PyFunctionArgPyO3Attribute::DefaultOnce)
         } else {
             Err(lookahead.error())
         }
@@ -50,6 +65,9 @@ impl PyFunctionArgPyO3Attributes {
         let mut attributes = PyFunctionArgPyO3Attributes {
             from_py_with: None,
             cancel_handle: None,
+            inject: None,
+            default_factory: None,
+            default_once: None,
         };
         take_attributes(attrs, |attr| {
             if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
@@ -69,11 +87,46 @@ impl PyFunctionArgPyO3Attributes {
                             );
                             attributes.cancel_handle = Some(cancel_handle);
                         }
+                        PyFunctionArgPyO3Attribute::Inject(inject) => {
+                            ensure_spanned!(
+                                attributes.inject.is_none(),
+                                inject.span() => "`inject` may only be specified once per argument"
+                            );
+                            attributes.inject = Some(inject);
+                        }
+                        PyFunctionArgPyO3Attribute::DefaultFactory(default_factory) => {
+                            ensure_spanned!(
+                                attributes.default_factory.is_none() && attributes.default_once.is_none(),
+                                default_factory.kw.span() => "only one of `default_factory` and `default_once` may be specified per argument"
+                            );
+                            attributes.default_factory = Some(default_factory);
+                        }
+                        PyFunctionArgPyO3Attribute::DefaultOnce(default_once) => {
+                            ensure_spanned!(
+                                attributes.default_factory.is_none() && attributes.default_once.is_none(),
+                                default_once.kw.span() => "only one of `default_factory` and `default_once` may be specified per argument"
+                            );
+                            attributes.default_once = Some(default_once);
+                        }
                     }
                     ensure_spanned!(
                         attributes.from_py_with.is_none() || attributes.cancel_handle.is_none(),
                         attributes.cancel_handle.unwrap().span() => "`from_py_with` and `cancel_handle` cannot be specified together"
                     );
+                    if let Some(inject) = &attributes.inject {
+                        ensure_spanned!(
+                            attributes.from_py_with.is_none() && attributes.cancel_handle.is_none(),
+                            inject.span() => "`inject` cannot be combined with `from_py_with` or `cancel_handle`"
+                        );
+                    }
+                    if let Some(span) = attributes.default_attribute_span() {
+                        ensure_spanned!(
+                            attributes.from_py_with.is_none()
+                                && attributes.cancel_handle.is_none()
+                                && attributes.inject.is_none(),
+                            span => "`default_factory` and `default_once` cannot be combined with `from_py_with`, `cancel_handle` or `inject`"
+                        );
+                    }
                 }
                 Ok(true)
             } else {
@@ -82,6 +135,19 @@ impl PyFunctionArgPyO3Attributes {
         })?;
         Ok(attributes)
     }
+
+    /// Whether the argument has its default value given by `default_factory` or `default_once`.
+    pub fn has_default_attribute(&self) -> bool {
+        self.default_factory.is_some() || self.default_once.is_some()
+    }
+
+    fn default_attribute_span(&self) -> Option<proc_macro2::Span> {
+        match (&self.default_factory, &self.default_once) {
+            (Some(default_factory), _) => Some(default_factory.kw.span()),
+            (_, Some(default_once)) => Some(default_once.kw.span()),
+            (None, None) => None,
+        }
+    }
 }
 
 #[derive(Default)]
@@ -91,6 +157,9 @@ pub struct PyFunctionOptions {
     pub signature: Option<SignatureAttribute>,
     pub text_signature: Option<TextSignatureAttribute>,
     pub krate: Option<CrateAttribute>,
+    pub unexported: Option<attributes::kw::unexported>,
+    /// The `rename_all` rule of the enclosing `#[pymethods]` block, if any.
+    pub renaming_rule: Option<attributes::RenamingRule>,
 }
 
 impl Parse for PyFunctionOptions {
@@ -103,6 +172,7 @@ impl Parse for PyFunctionOptions {
                 || lookahead.peek(attributes::kw::pass_module)
                 || lookahead.peek(attributes::kw::signature)
                 || lookahead.peek(attributes::kw::text_signature)
+                || lookahead.peek(attributes::kw::unexported)
             {
                 options.add_attributes(std::iter::once(input.parse()?))?;
                 if !input.is_empty() {
@@ -126,6 +196,7 @@ pub enum PyFunctionOption {
     Signature(SignatureAttribute),
     TextSignature(TextSignatureAttribute),
     Crate(CrateAttribute),
+    Unexported(attributes::kw::unexported),
 }
 
 impl Parse for PyFunctionOption {
@@ -141,6 +212,8 @@ impl Parse for PyFunctionOption {
             input.parse().map(PyFunctionOption::TextSignature)
         } else if lookahead.peek(syn::Token![crate]) {
             input.parse().map(PyFunctionOption::Crate)
+        } else if lookahead.peek(attributes::kw::unexported) {
+            input.parse().map(PyFunctionOption::Unexported)
         } else {
             Err(lookahead.error())
         }
@@ -176,6 +249,7 @@ impl PyFunctionOptions {
                 PyFunctionOption::Signature(signature) => set_option!(signature),
                 PyFunctionOption::TextSignature(text_signature) => set_option!(text_signature),
                 PyFunctionOption::Crate(krate) => set_option!(krate),
+                PyFunctionOption::Unexported(unexported) => set_option!(unexported),
             }
         }
         Ok(())
@@ -187,6 +261,9 @@ pub fn build_py_function(
     mut options: PyFunctionOptions,
 ) -> syn::Result<TokenStream> {
     options.add_attributes(take_pyo3_options(&mut ast.attrs)?)?;
+    if let Some(unexported) = &options.unexported {
+        bail_spanned!(unexported.span() => "`unexported` may only be used with `#[pyfn]`");
+    }
     impl_wrap_pyfunction(ast, options)
 }
 
@@ -203,6 +280,8 @@ pub fn impl_wrap_pyfunction(
         signature,
         text_signature,
         krate,
+        unexported: _,
+        renaming_rule: _,
     } = options;
 
     let python_name = name.map_or_else(|| func.sig.ident.unraw(), |name| name.value.0);
@@ -371,6 +371,13 @@ impl<'a> FunctionSignature<'a> {
                     // Otherwise try next argument.
                     continue;
                 }
+                if fn_arg.attrs.inject.is_some() {
+                    ensure_spanned!(
+                        name != fn_arg.name,
+                        name.span() => "`inject` argument must not be part of the signature"
+                    );
+                    continue;
+                }
 
                 ensure_spanned!(
                     name == fn_arg.name,
@@ -391,10 +398,14 @@ impl<'a> FunctionSignature<'a> {
             match item {
                 SignatureItem::Argument(arg) => {
                     let fn_arg = next_non_py_argument_checked(&arg.ident)?;
+                    ensure_spanned!(
+                        arg.eq_and_default.is_none() || !fn_arg.attrs.has_default_attribute(),
+                        arg.span() => "arguments with `default_factory` or `default_once` must not have a default value in the signature"
+                    );
                     parse_state.add_argument(
                         &mut python_signature,
                         arg.ident.unraw().to_string(),
-                        arg.eq_and_default.is_none(),
+                        arg.eq_and_default.is_none() && !fn_arg.attrs.has_default_attribute(),
                         arg.span(),
                     )?;
                     if let Some((_, default)) = &arg.eq_and_default {
@@ -421,7 +432,7 @@ impl<'a> FunctionSignature<'a> {
         }
 
         // Ensure no non-py arguments remain
-        if let Some(arg) = args_iter.find(|arg| !arg.py && !arg.is_cancel_handle) {
+        if let Some(arg) = args_iter.find(|arg| !arg.is_injected()) {
             bail_spanned!(
                 attribute.kw.span() => format!("missing signature entry for argument `{}`", arg.name)
             );
@@ -438,12 +449,12 @@ impl<'a> FunctionSignature<'a> {
     pub fn from_arguments(arguments: Vec<FnArg<'a>>) -> syn::Result<Self> {
         let mut python_signature = PythonSignature::default();
         for arg in &arguments {
-            // Python<'_> arguments don't show in Python signature
-            if arg.py || arg.is_cancel_handle {
+            // Python<'_> and other injected arguments don't show in Python signature
+            if arg.is_injected() {
                 continue;
             }
 
-            if arg.optional.is_none() {
+            if arg.optional.is_none() && !arg.attrs.has_default_attribute() {
                 // This argument is required, all previous arguments must also have been required
                 ensure_spanned!(
                     python_signature.required_positional_parameters == python_signature.positional_parameters.len(),
@@ -496,7 +507,7 @@ impl<'a> FunctionSignature<'a> {
                     // others, unsupported yet so defaults to `...`
                     _ => {}
                 }
-            } else if fn_arg.optional.is_some() {
+            } else if fn_arg.optional.is_some() && !fn_arg.attrs.has_default_attribute() {
                 // functions without a `#[pyo3(signature = (...))]` option
                 // will treat trailing `Option<T>` arguments as having a default of `None`
                 default = "None".to_string();
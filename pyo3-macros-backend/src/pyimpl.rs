@@ -1,13 +1,18 @@
 use std::collections::HashSet;
 
 use crate::{
-    attributes::{take_pyo3_options, CrateAttribute},
+    attributes::{
+        self, take_pyo3_options, CrateAttribute, FromTraitAttribute, RenameAllAttribute,
+        RenamingRule,
+    },
     konst::{ConstAttributes, ConstSpec},
     pyfunction::PyFunctionOptions,
-    pymethod::{self, is_proto_method, MethodAndMethodDef, MethodAndSlotDef},
+    pymethod::{
+        self, is_proto_method, MethodAndMethodDef, MethodAndSlotDef, SlotFragmentImpl, TypeSlot,
+    },
     utils::get_pyo3_crate,
 };
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use pymethod::GeneratedPyMethod;
 use quote::{format_ident, quote};
 use syn::{
@@ -21,10 +26,18 @@ use syn::{
 pub enum PyClassMethodsType {
     Specialization,
     Inventory,
+    /// A `#[pymethods]` impl of a legacy protocol trait, collected through the named trait and
+    /// method
+    PyProto {
+        items_trait: &'static str,
+        items_fn: &'static str,
+    },
 }
 
 enum PyImplPyO3Option {
     Crate(CrateAttribute),
+    FromTrait(FromTraitAttribute),
+    RenameAll(RenameAllAttribute),
 }
 
 impl Parse for PyImplPyO3Option {
@@ -32,6 +45,10 @@ impl Parse for PyImplPyO3Option {
         let lookahead = input.lookahead1();
         if lookahead.peek(syn::Token![crate]) {
             input.parse().map(PyImplPyO3Option::Crate)
+        } else if lookahead.peek(attributes::kw::from_trait) {
+            input.parse().map(PyImplPyO3Option::FromTrait)
+        } else if lookahead.peek(attributes::kw::rename_all) {
+            input.parse().map(PyImplPyO3Option::RenameAll)
         } else {
             Err(lookahead.error())
         }
@@ -40,7 +57,9 @@ impl Parse for PyImplPyO3Option {
 
 #[derive(Default)]
 pub struct PyImplOptions {
-    krate: Option<CrateAttribute>,
+    pub(crate) krate: Option<CrateAttribute>,
+    from_trait: Option<FromTraitAttribute>,
+    rename_all: Option<RenameAllAttribute>,
 }
 
 impl PyImplOptions {
@@ -50,6 +69,28 @@ impl PyImplOptions {
         for option in take_pyo3_options(attrs)? {
             match option {
                 PyImplPyO3Option::Crate(path) => options.set_crate(path)?,
+                PyImplPyO3Option::FromTrait(from_trait) => {
+                    ensure_spanned!(
+                        options.from_trait.is_none(),
+                        from_trait.kw.span() => "`from_trait` may only be specified once"
+                    );
+                    options.from_trait = Some(from_trait);
+                }
+                PyImplPyO3Option::RenameAll(rename_all) => {
+                    ensure_spanned!(
+                        options.rename_all.is_none(),
+                        rename_all.kw.span() => "`rename_all` may only be specified once"
+                    );
+                    // Method names must remain valid Python identifiers
+                    ensure_spanned!(
+                        !matches!(
+                            rename_all.value.rule,
+                            RenamingRule::KebabCase | RenamingRule::ScreamingKebabCase
+                        ),
+                        rename_all.value.lit.span() => "kebab-case renaming rules are not supported for `#[pymethods]`"
+                    );
+                    options.rename_all = Some(rename_all);
+                }
             }
         }
 
@@ -71,8 +112,8 @@ pub fn build_py_methods(
     ast: &mut syn::ItemImpl,
     methods_type: PyClassMethodsType,
 ) -> syn::Result<TokenStream> {
-    if let Some((_, path, _)) = &ast.trait_ {
-        bail_spanned!(path.span() => "#[pymethods] cannot be used on trait impl blocks");
+    if ast.trait_.is_some() {
+        crate::pyproto::build_protocol_methods(ast)
     } else if ast.generics != Default::default() {
         bail_spanned!(
             ast.generics.span() =>
@@ -80,10 +121,59 @@ pub fn build_py_methods(
         );
     } else {
         let options = PyImplOptions::from_attrs(&mut ast.attrs)?;
+        if let Some(from_trait) = &options.from_trait {
+            forward_to_trait(&mut ast.items, &from_trait.value)?;
+        }
         impl_methods(&ast.self_ty, &mut ast.items, methods_type, options)
     }
 }
 
+/// For `#[pymethods(from_trait = Trait)]`, gives every method declared without a body a body
+/// which calls the implementation of `Trait` for the class.
+///
+/// e.g. `fn area(&self) -> f64;` becomes `fn area(&self) -> f64 { <Self as Trait>::area(self) }`
+fn forward_to_trait(items: &mut [syn::ImplItem], trait_path: &syn::Path) -> Result<()> {
+    for item in items {
+        let decl = match item {
+            syn::ImplItem::Verbatim(tokens) => {
+                match syn::parse2::<syn::TraitItemFn>(tokens.clone()) {
+                    Ok(decl) => decl,
+                    Err(_) => continue,
+                }
+            }
+            _ => continue,
+        };
+        if let Some(default) = &decl.default {
+            bail_spanned!(default.span() => "expected a method declaration without a body");
+        }
+        let mut args = Vec::with_capacity(decl.sig.inputs.len());
+        for input in &decl.sig.inputs {
+            match input {
+                syn::FnArg::Receiver(_) => args.push(quote!(self)),
+                syn::FnArg::Typed(pat_type) => match &*pat_type.pat {
+                    syn::Pat::Ident(pat_ident) => {
+                        let ident = &pat_ident.ident;
+                        args.push(quote!(#ident));
+                    }
+                    other => bail_spanned!(
+                        other.span() => "arguments of methods forwarded to a trait must be identifiers"
+                    ),
+                },
+            }
+        }
+        let syn::TraitItemFn { attrs, sig, .. } = decl;
+        let name = &sig.ident;
+        let await_ = sig.asyncness.map(|_| quote!(.await));
+        *item = syn::ImplItem::Fn(syn::parse_quote! {
+            #(#attrs)*
+            #sig {
+                <Self as #trait_path>::#name(#(#args),*) #await_
+            }
+        });
+    }
+    Ok(())
+}
+
 pub fn impl_methods(
     ty: &syn::Type,
     impls: &mut [syn::ImplItem],
@@ -94,15 +184,36 @@ pub fn impl_methods(
     let mut proto_impls = Vec::new();
     let mut methods = Vec::new();
     let mut associated_methods = Vec::new();
+    let mut slot_markers = Vec::new();
 
     let mut implemented_proto_fragments = HashSet::new();
+    let mut implemented_slots: Vec<TypeSlot> = Vec::new();
 
     for iimpl in impls {
         match iimpl {
             syn::ImplItem::Fn(meth) => {
                 let mut fun_options = PyFunctionOptions::from_attrs(&mut meth.attrs)?;
                 fun_options.krate = fun_options.krate.or_else(|| options.krate.clone());
-                match pymethod::gen_py_method(ty, &mut meth.sig, &mut meth.attrs, fun_options)? {
+                fun_options.renaming_rule = options
+                    .rename_all
+                    .as_ref()
+                    .map(|rename_all| rename_all.value.rule);
+                let (generated, slot) =
+                    pymethod::gen_py_method(ty, &mut meth.sig, &mut meth.attrs, fun_options)?;
+                if let Some(slot) = slot {
+                    let attrs = get_cfg_attributes(&meth.attrs);
+                    let markers = slot.markers();
+                    slot_markers.push(quote!(#(#attrs)* #markers));
+                    // Methods under `#[cfg]` may be alternatives for each other, so conflicts
+                    // between them are only caught by the markers
+                    if attrs.is_empty() {
+                        for other in &implemented_slots {
+                            slot.ensure_compatible(other)?;
+                        }
+                        implemented_slots.push(slot);
+                    }
+                }
+                match generated {
                     GeneratedPyMethod::Method(MethodAndMethodDef {
                         associated_method,
                         method_def,
@@ -111,10 +222,11 @@ pub fn impl_methods(
                         associated_methods.push(quote!(#(#attrs)* #associated_method));
                         methods.push(quote!(#(#attrs)* #method_def));
                     }
-                    GeneratedPyMethod::SlotTraitImpl(method_name, token_stream) => {
+                    GeneratedPyMethod::SlotTraitImpl(method_name, fragment_impl) => {
                         implemented_proto_fragments.insert(method_name);
                         let attrs = get_cfg_attributes(&meth.attrs);
-                        trait_impls.push(quote!(#(#attrs)* #token_stream));
+                        let fragment_impl = impl_slot_fragment(ty, fragment_impl, methods_type);
+                        trait_impls.push(quote!(#(#attrs)* #fragment_impl));
                     }
                     GeneratedPyMethod::Proto(MethodAndSlotDef {
                         associated_method,
@@ -124,6 +236,18 @@ pub fn impl_methods(
                         proto_impls.push(quote!(#(#attrs)* #slot_def));
                         associated_methods.push(quote!(#(#attrs)* #associated_method));
                     }
+                    GeneratedPyMethod::ProtoWithTraitImpl(
+                        MethodAndSlotDef {
+                            associated_method,
+                            slot_def,
+                        },
+                        token_stream,
+                    ) => {
+                        let attrs = get_cfg_attributes(&meth.attrs);
+                        proto_impls.push(quote!(#(#attrs)* #slot_def));
+                        associated_methods.push(quote!(#(#attrs)* #associated_method));
+                        trait_impls.push(quote!(#(#attrs)* #token_stream));
+                    }
                 }
             }
             syn::ImplItem::Const(konst) => {
@@ -162,9 +286,17 @@ pub fn impl_methods(
 
     let krate = get_pyo3_crate(&options.krate);
 
+    let mut wrappers = TokenStream::new();
     let items = match methods_type {
         PyClassMethodsType::Specialization => impl_py_methods(ty, methods, proto_impls),
         PyClassMethodsType::Inventory => submit_methods_inventory(ty, methods, proto_impls),
+        PyClassMethodsType::PyProto {
+            items_trait,
+            items_fn,
+        } => {
+            wrappers = crate::pyproto::impl_wrappers(ty, std::mem::take(&mut associated_methods))?;
+            impl_py_proto_items(ty, items_trait, items_fn, methods, proto_impls)
+        }
     };
 
     Ok(quote! {
@@ -175,15 +307,44 @@ pub fn impl_methods(
 
             #items
 
+            #wrappers
+
             #[doc(hidden)]
             #[allow(non_snake_case)]
             impl #ty {
+                #(#slot_markers)*
                 #(#associated_methods)*
             }
         };
     })
 }
 
+/// Implements a slot fragment trait with the priority of the `#[pymethods]` block; see
+/// `PyClassImplCollector`.
+fn impl_slot_fragment(
+    ty: &syn::Type,
+    fragment_impl: SlotFragmentImpl,
+    methods_type: PyClassMethodsType,
+) -> TokenStream {
+    let SlotFragmentImpl {
+        fragment_trait,
+        method,
+    } = fragment_impl;
+    let collector = match methods_type {
+        PyClassMethodsType::Specialization | PyClassMethodsType::Inventory => {
+            quote!(&'_ _pyo3::impl_::pyclass::PyClassImplCollector<#ty>)
+        }
+        PyClassMethodsType::PyProto { .. } => {
+            quote!(&'_ &'_ _pyo3::impl_::pyclass::PyClassImplCollector<#ty>)
+        }
+    };
+    quote! {
+        impl _pyo3::impl_::pyclass::#fragment_trait<#ty> for #collector {
+            #method
+        }
+    }
+}
+
 pub fn gen_py_const(cls: &syn::Type, spec: &ConstSpec) -> MethodAndMethodDef {
     let member = &spec.rust_ident;
     let wrapper_ident = format_ident!("__pymethod_{}__", member);
@@ -232,6 +393,30 @@ fn impl_py_methods(
     }
 }
 
+fn impl_py_proto_items(
+    ty: &syn::Type,
+    items_trait: &str,
+    items_fn: &str,
+    methods: Vec<TokenStream>,
+    proto_impls: Vec<TokenStream>,
+) -> TokenStream {
+    let items_trait = syn::Ident::new(items_trait, Span::call_site());
+    let items_fn = syn::Ident::new(items_fn, Span::call_site());
+    quote! {
+        impl _pyo3::impl_::pyclass::#items_trait<#ty>
+            for _pyo3::impl_::pyclass::PyClassImplCollector<#ty>
+        {
+            fn #items_fn(self) -> &'static _pyo3::impl_::pyclass::PyClassItems {
+                static ITEMS: _pyo3::impl_::pyclass::PyClassItems = _pyo3::impl_::pyclass::PyClassItems {
+                    methods: &[#(#methods),*],
+                    slots: &[#(#proto_impls),*]
+                };
+                &ITEMS
+            }
+        }
+    }
+}
+
 fn add_shared_proto_slots(
     ty: &syn::Type,
     proto_impls: &mut Vec<TokenStream>,
@@ -9,7 +9,7 @@ use crate::{
 };
 use crate::{quotes, utils};
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{ext::IdentExt, spanned::Spanned, Result};
 
 /// Generated code for a single pymethod item.
@@ -31,7 +31,20 @@ pub struct MethodAndSlotDef {
 pub enum GeneratedPyMethod {
     Method(MethodAndMethodDef),
     Proto(MethodAndSlotDef),
-    SlotTraitImpl(String, TokenStream),
+    /// A slot which also specializes a `PyClassImplCollector` trait.
+    ProtoWithTraitImpl(MethodAndSlotDef, TokenStream),
+    SlotTraitImpl(String, SlotFragmentImpl),
+}
+
+/// Generated implementation of a slot fragment trait, e.g. `PyClass__add__SlotFragment`.
+///
+/// The type which implements the trait decides the priority of the implementation, so it is left
+/// to the caller; see `PyClassImplCollector`.
+pub struct SlotFragmentImpl {
+    /// The slot fragment trait
+    pub fragment_trait: syn::Ident,
+    /// The implementation of the trait method
+    pub method: TokenStream,
 }
 
 pub struct PyMethod<'a> {
@@ -94,6 +107,7 @@ impl PyMethodKind {
             "__getbuffer__" => PyMethodKind::Proto(PyMethodProtoKind::Slot(&__GETBUFFER__)),
             "__releasebuffer__" => PyMethodKind::Proto(PyMethodProtoKind::Slot(&__RELEASEBUFFER__)),
             "__clear__" => PyMethodKind::Proto(PyMethodProtoKind::Slot(&__CLEAR__)),
+            "__del__" => PyMethodKind::Proto(PyMethodProtoKind::Slot(&__DEL__)),
             // Protocols implemented through traits
             "__getattribute__" => {
                 PyMethodKind::Proto(PyMethodProtoKind::SlotFragment(&__GETATTRIBUTE__))
@@ -181,18 +195,139 @@ pub fn is_proto_method(name: &str) -> bool {
     }
 }
 
+/// Type slots which combine the implementations of several protocol methods ("slot fragments").
+const SHARED_SLOTS: &[(&str, &[&str])] = &[
+    ("Py_tp_getattro", &["__getattribute__", "__getattr__"]),
+    ("Py_tp_setattro", &["__setattr__", "__delattr__"]),
+    ("Py_tp_descr_set", &["__set__", "__delete__"]),
+    ("Py_mp_ass_subscript", &["__setitem__", "__delitem__"]),
+    ("Py_nb_add", &["__add__", "__radd__"]),
+    ("Py_nb_subtract", &["__sub__", "__rsub__"]),
+    ("Py_nb_multiply", &["__mul__", "__rmul__"]),
+    ("Py_nb_remainder", &["__mod__", "__rmod__"]),
+    ("Py_nb_divmod", &["__divmod__", "__rdivmod__"]),
+    ("Py_nb_lshift", &["__lshift__", "__rlshift__"]),
+    ("Py_nb_rshift", &["__rshift__", "__rrshift__"]),
+    ("Py_nb_and", &["__and__", "__rand__"]),
+    ("Py_nb_or", &["__or__", "__ror__"]),
+    ("Py_nb_xor", &["__xor__", "__rxor__"]),
+    ("Py_nb_matrix_multiply", &["__matmul__", "__rmatmul__"]),
+    ("Py_nb_true_divide", &["__truediv__", "__rtruediv__"]),
+    ("Py_nb_floor_divide", &["__floordiv__", "__rfloordiv__"]),
+    ("Py_nb_power", &["__pow__", "__rpow__"]),
+    (
+        "Py_tp_richcompare",
+        &["__lt__", "__le__", "__eq__", "__ne__", "__gt__", "__ge__"],
+    ),
+];
+
+/// The type slot which a method of a `#[pymethods]` block is registered with.
+pub struct TypeSlot {
+    /// The name of the slot, e.g. `Py_tp_repr`
+    slot: &'static str,
+    /// The slot fragment implemented by the method, if the slot is shared with other methods
+    fragment: Option<&'static str>,
+    method_name: String,
+    span: Span,
+}
+
+impl TypeSlot {
+    fn new(slot: &'static str, method_name: &str, span: Span) -> Self {
+        let fragment = SHARED_SLOTS
+            .iter()
+            .flat_map(|(_, fragments)| fragments.iter())
+            .find(|fragment| **fragment == method_name)
+            .copied();
+        TypeSlot {
+            slot,
+            fragment,
+            method_name: method_name.to_owned(),
+            span,
+        }
+    }
+
+    /// Fails if `self` and `other` cannot both be defined for a class, because one would
+    /// overwrite the slot set by the other.
+    pub fn ensure_compatible(&self, other: &TypeSlot) -> Result<()> {
+        if self.slot != other.slot {
+            return Ok(());
+        }
+        ensure_spanned!(
+            self.method_name != other.method_name,
+            self.span => format!("`{}` is defined more than once for this class", self.method_name)
+        );
+        ensure_spanned!(
+            self.fragment.is_some() && other.fragment.is_some(),
+            self.span => format!(
+                "`{}` cannot be defined together with `{}`, because both are implemented with the `{}` type slot",
+                self.method_name, other.method_name, self.slot
+            )
+        );
+        Ok(())
+    }
+
+    /// Generates hidden associated constants which make the compiler reject conflicting
+    /// definitions of the slot in other `#[pymethods]` blocks of the class (including impls of the
+    /// legacy protocol traits), as duplicate definitions of these constants. The constants are
+    /// named after the slot and the fragment, and the error points at both methods.
+    ///
+    /// Every slot fragment gets a constant; a method which sets a shared slot on its own (such as
+    /// `__richcmp__`) defines the constants of all fragments of the slot.
+    pub fn markers(&self) -> TokenStream {
+        let shared_slot = SHARED_SLOTS.iter().find(|(slot, _)| *slot == self.slot);
+        let names: Vec<String> = match (self.fragment, shared_slot) {
+            (Some(fragment), _) => vec![self.fragment_marker(fragment)],
+            (None, Some((_, fragments))) => fragments
+                .iter()
+                .map(|fragment| self.fragment_marker(fragment))
+                .collect(),
+            (None, None) => vec![format!("__pyo3_slot_{}", self.slot)],
+        };
+        let markers = names.iter().map(|name| syn::Ident::new(name, self.span));
+        quote_spanned! { self.span =>
+            #(
+                #[allow(non_upper_case_globals, dead_code)]
+                const #markers: () = ();
+            )*
+        }
+    }
+
+    fn fragment_marker(&self, fragment: &str) -> String {
+        format!("__pyo3_slot_{}_{}", self.slot, fragment)
+    }
+}
+
 pub fn gen_py_method(
     cls: &syn::Type,
     sig: &mut syn::Signature,
     meth_attrs: &mut Vec<syn::Attribute>,
     options: PyFunctionOptions,
-) -> Result<GeneratedPyMethod> {
+) -> Result<(GeneratedPyMethod, Option<TypeSlot>)> {
     check_generic(sig)?;
     ensure_function_options_valid(&options)?;
+    let span = sig.ident.span();
     let method = PyMethod::parse(sig, meth_attrs, options)?;
     let spec = &method.spec;
 
-    Ok(match (method.kind, &spec.tp) {
+    let slot = match (&method.kind, &spec.tp) {
+        (_, FnType::ClassAttribute) => None,
+        (PyMethodKind::Proto(proto_kind), _) => Some(match proto_kind {
+            PyMethodProtoKind::Slot(slot_def) => slot_def.slot.0,
+            PyMethodProtoKind::Call => "Py_tp_call",
+            PyMethodProtoKind::Traverse => "Py_tp_traverse",
+            PyMethodProtoKind::SlotFragment(slot_fragment_def) => SHARED_SLOTS
+                .iter()
+                .find(|(_, fragments)| fragments.contains(&slot_fragment_def.fragment))
+                .map(|(slot, _)| *slot)
+                .expect("slot fragment is not part of a shared slot"),
+        }),
+        (_, FnType::FnNew) | (_, FnType::FnNewClass(_)) => Some("Py_tp_new"),
+        (_, FnType::FnInit(_)) => Some("Py_tp_init"),
+        _ => None,
+    }
+    .map(|slot| TypeSlot::new(slot, &method.method_name, span));
+
+    let generated = match (method.kind, &spec.tp) {
         // Class attributes go before protos so that class attributes can be used to set proto
         // method to None.
         (_, FnType::ClassAttribute) => {
@@ -206,7 +341,8 @@ pub fn gen_py_method(
                     GeneratedPyMethod::Proto(slot)
                 }
                 PyMethodProtoKind::Call => {
-                    GeneratedPyMethod::Proto(impl_call_slot(cls, method.spec)?)
+                    let (slot, vectorcall_impl) = impl_call_slot(cls, method.spec)?;
+                    GeneratedPyMethod::ProtoWithTraitImpl(slot, vectorcall_impl)
                 }
                 PyMethodProtoKind::Traverse => {
                     GeneratedPyMethod::Proto(impl_traverse_slot(cls, spec)?)
@@ -240,6 +376,7 @@ pub fn gen_py_method(
         (_, FnType::FnNew) | (_, FnType::FnNewClass(_)) => {
             GeneratedPyMethod::Proto(impl_py_method_def_new(cls, spec)?)
         }
+        (_, FnType::FnInit(_)) => GeneratedPyMethod::Proto(impl_init_slot(cls, method.spec)?),
 
         (_, FnType::Getter(self_type)) => GeneratedPyMethod::Method(impl_py_getter_def(
             cls,
@@ -257,10 +394,17 @@ pub fn gen_py_method(
                 doc: spec.get_doc(meth_attrs),
             },
         )?),
+        (_, FnType::Deleter(self_type)) => GeneratedPyMethod::Method(impl_py_deleter_def(
+            cls,
+            spec,
+            self_type,
+            &spec.get_doc(meth_attrs),
+        )?),
         (_, FnType::FnModule(_)) => {
             unreachable!("methods cannot be FnModule")
         }
-    })
+    };
+    Ok((generated, slot))
 }
 
 pub fn check_generic(sig: &syn::Signature) -> syn::Result<()> {
@@ -279,6 +423,9 @@ fn ensure_function_options_valid(options: &PyFunctionOptions) -> syn::Result<()>
     if let Some(pass_module) = &options.pass_module {
         bail_spanned!(pass_module.span() => "`pass_module` cannot be used on Python methods");
     }
+    if let Some(unexported) = &options.unexported {
+        bail_spanned!(unexported.span() => "`unexported` cannot be used on Python methods");
+    }
     Ok(())
 }
 
@@ -373,13 +520,57 @@ pub fn impl_py_method_def_new(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<Meth
     })
 }
 
-fn impl_call_slot(cls: &syn::Type, mut spec: FnSpec<'_>) -> Result<MethodAndSlotDef> {
+fn impl_call_slot(
+    cls: &syn::Type,
+    mut spec: FnSpec<'_>,
+) -> Result<(MethodAndSlotDef, TokenStream)> {
+    // The vectorcall implementation, used by `#[pyclass(vectorcall)]`, shares the signature of
+    // the fastcall convention, so it is only available where fastcall is.
+    let (vectorcall_method, vectorcall_impl) = if utils::has_fastcall_with_keywords() {
+        spec.convention = CallingConvention::Fastcall;
+        let vectorcall_ident =
+            syn::Ident::new("__pymethod___call___vectorcall__", Span::call_site());
+        let vectorcall_method = spec.get_wrapper_function(&vectorcall_ident, Some(cls))?;
+        let vectorcall_impl = quote! {
+            impl _pyo3::impl_::pyclass::PyClassCallVectorcall<#cls>
+                for _pyo3::impl_::pyclass::PyClassImplCollector<#cls>
+            {
+                #[inline]
+                fn vectorcall(self) -> ::std::option::Option<_pyo3::impl_::pyclass::VectorcallFunc> {
+                    unsafe extern "C" fn trampoline(
+                        slf: *mut _pyo3::ffi::PyObject,
+                        args: *const *mut _pyo3::ffi::PyObject,
+                        nargsf: usize,
+                        kwnames: *mut _pyo3::ffi::PyObject,
+                    ) -> *mut _pyo3::ffi::PyObject
+                    {
+                        _pyo3::impl_::trampoline::vectorcallfunc(
+                            slf,
+                            args,
+                            nargsf,
+                            kwnames,
+                            #cls::#vectorcall_ident
+                        )
+                    }
+                    ::std::option::Option::Some(trampoline)
+                }
+            }
+        };
+        (vectorcall_method, vectorcall_impl)
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
     // HACK: __call__ proto slot must always use varargs calling convention, so change the spec.
     // Probably indicates there's a refactoring opportunity somewhere.
     spec.convention = CallingConvention::Varargs;
 
     let wrapper_ident = syn::Ident::new("__pymethod___call____", Span::call_site());
     let associated_method = spec.get_wrapper_function(&wrapper_ident, Some(cls))?;
+    let associated_method = quote! {
+        #associated_method
+        #vectorcall_method
+    };
     let slot_def = quote! {
         _pyo3::ffi::PyType_Slot {
             slot: _pyo3::ffi::Py_tp_call,
@@ -401,6 +592,70 @@ fn impl_call_slot(cls: &syn::Type, mut spec: FnSpec<'_>) -> Result<MethodAndSlot
             } as _pyo3::ffi::ternaryfunc as _
         }
     };
+    Ok((
+        MethodAndSlotDef {
+            associated_method,
+            slot_def,
+        },
+        vectorcall_impl,
+    ))
+}
+
+fn impl_init_slot(cls: &syn::Type, mut spec: FnSpec<'_>) -> Result<MethodAndSlotDef> {
+    if let Some(asyncness) = &spec.asyncness {
+        bail_spanned!(asyncness.span() => "`#[init]` cannot be `async`");
+    }
+
+    // An init hook which takes no Python arguments ignores the arguments passed to the
+    // constructor (which are for `#[new]`), otherwise it receives them just like `__init__`.
+    let ignores_args = spec
+        .signature
+        .arguments
+        .iter()
+        .all(|arg| arg.injected_value().is_some());
+    spec.convention = if ignores_args {
+        CallingConvention::Noargs
+    } else {
+        CallingConvention::Varargs
+    };
+
+    let wrapper_ident = syn::Ident::new("__pymethod___init____", Span::call_site());
+    let associated_method = spec.get_wrapper_function(&wrapper_ident, Some(cls))?;
+    let call_wrapper = if ignores_args {
+        quote! { #cls::#wrapper_ident(py, slf) }
+    } else {
+        quote! { #cls::#wrapper_ident(py, slf, _args, _kwargs) }
+    };
+    let slot_def = quote! {
+        _pyo3::ffi::PyType_Slot {
+            slot: _pyo3::ffi::Py_tp_init,
+            pfunc: {
+                unsafe extern "C" fn trampoline(
+                    slf: *mut _pyo3::ffi::PyObject,
+                    args: *mut _pyo3::ffi::PyObject,
+                    kwargs: *mut _pyo3::ffi::PyObject,
+                ) -> ::std::os::raw::c_int
+                {
+                    unsafe fn wrap(
+                        py: _pyo3::Python<'_>,
+                        slf: *mut _pyo3::ffi::PyObject,
+                        _args: *mut _pyo3::ffi::PyObject,
+                        _kwargs: *mut _pyo3::ffi::PyObject,
+                    ) -> _pyo3::PyResult<*mut _pyo3::ffi::PyObject> {
+                        #call_wrapper
+                    }
+
+                    _pyo3::impl_::trampoline::initproc(
+                        slf,
+                        args,
+                        kwargs,
+                        wrap
+                    )
+                }
+                trampoline
+            } as _pyo3::ffi::initproc as _
+        }
+    };
     Ok(MethodAndSlotDef {
         associated_method,
         slot_def,
@@ -454,12 +709,12 @@ fn impl_py_class_attribute(cls: &syn::Type, spec: &FnSpec<'_>) -> syn::Result<Me
 
     let wrapper_ident = format_ident!("__pymethod_{}__", name);
     let python_name = spec.null_terminated_python_name();
-    let body = quotes::ok_wrap(fncall);
+    let body = quotes::map_result_into_py(quotes::ok_wrap(fncall));
 
     let associated_method = quote! {
         fn #wrapper_ident(py: _pyo3::Python<'_>) -> _pyo3::PyResult<_pyo3::PyObject> {
             let function = #cls::#name; // Shadow the method name to avoid #3017
-            _pyo3::impl_::wrap::map_result_into_py(py, #body)
+            #body
         }
     };
 
@@ -600,6 +855,58 @@ pub fn impl_py_setter_def(
     })
 }
 
+pub fn impl_py_deleter_def(
+    cls: &syn::Type,
+    spec: &FnSpec<'_>,
+    self_type: &SelfType,
+    doc: &PythonDoc,
+) -> Result<MethodAndMethodDef> {
+    let (py_arg, args) = split_off_python_arg(&spec.signature.arguments);
+    if !args.is_empty() {
+        bail_spanned!(
+            args[0].ty.span() =>
+            "deleter function can have at most one argument ([pyo3::Python,])"
+        );
+    }
+
+    let mut holders = Vec::new();
+    let slf = self_type.receiver(cls, ExtractErrorMode::Raise, &mut holders);
+    let name = &spec.name;
+    let fncall = if py_arg.is_some() {
+        quote!(#cls::#name(#slf, py))
+    } else {
+        quote!(#cls::#name(#slf))
+    };
+
+    let python_name = spec.null_terminated_python_name();
+    let wrapper_ident = format_ident!("__pymethod_del_{}__", name);
+
+    let associated_method = quote! {
+        unsafe fn #wrapper_ident(
+            py: _pyo3::Python<'_>,
+            _slf: *mut _pyo3::ffi::PyObject,
+        ) -> _pyo3::PyResult<::std::os::raw::c_int> {
+            #( #holders )*
+            _pyo3::callback::convert(py, #fncall)
+        }
+    };
+
+    let method_def = quote! {
+        _pyo3::class::PyMethodDefType::Deleter(
+            _pyo3::class::PyDeleterDef::new(
+                #python_name,
+                _pyo3::impl_::pymethods::PyDeleter(#cls::#wrapper_ident),
+                #doc
+            )
+        )
+    };
+
+    Ok(MethodAndMethodDef {
+        associated_method,
+        method_def,
+    })
+}
+
 fn impl_call_getter(
     cls: &syn::Type,
     spec: &FnSpec<'_>,
@@ -652,14 +959,11 @@ pub fn impl_py_getter_def(
                 ::std::clone::Clone::clone(&(#slf.#field_token))
             }))
         }
-        // Forward to `IntoPyCallbackOutput`, to handle `#[getter]`s returning results.
         PropertyType::Function {
             spec, self_type, ..
         } => {
             let call = impl_call_getter(cls, spec, self_type, &mut holders)?;
-            quote! {
-                _pyo3::callback::convert(py, #call)
-            }
+            quotes::map_result_into_ptr(quotes::ok_wrap(call))
         }
     };
 
@@ -804,16 +1108,16 @@ const __ANEXT__: SlotDef = SlotDef::new("Py_am_anext", "unaryfunc").return_speci
     TokenGenerator(|| quote! { AsyncIterBaseKind, AsyncIterOptionKind, AsyncIterResultOptionKind }),
     TokenGenerator(|| quote! { async_iter_tag }),
 );
-const __LEN__: SlotDef = SlotDef::new("Py_mp_length", "lenfunc").ret_ty(Ty::PySsizeT);
+pub const __LEN__: SlotDef = SlotDef::new("Py_mp_length", "lenfunc").ret_ty(Ty::PySsizeT);
 const __CONTAINS__: SlotDef = SlotDef::new("Py_sq_contains", "objobjproc")
     .arguments(&[Ty::Object])
     .ret_ty(Ty::Int);
 const __CONCAT__: SlotDef = SlotDef::new("Py_sq_concat", "binaryfunc").arguments(&[Ty::Object]);
 const __REPEAT__: SlotDef = SlotDef::new("Py_sq_repeat", "ssizeargfunc").arguments(&[Ty::PySsizeT]);
 const __INPLACE_CONCAT__: SlotDef =
-    SlotDef::new("Py_sq_concat", "binaryfunc").arguments(&[Ty::Object]);
+    SlotDef::new("Py_sq_inplace_concat", "binaryfunc").arguments(&[Ty::Object]);
 const __INPLACE_REPEAT__: SlotDef =
-    SlotDef::new("Py_sq_repeat", "ssizeargfunc").arguments(&[Ty::PySsizeT]);
+    SlotDef::new("Py_sq_inplace_repeat", "ssizeargfunc").arguments(&[Ty::PySsizeT]);
 const __GETITEM__: SlotDef = SlotDef::new("Py_mp_subscript", "binaryfunc").arguments(&[Ty::Object]);
 
 const __POS__: SlotDef = SlotDef::new("Py_nb_positive", "unaryfunc");
@@ -823,7 +1127,7 @@ const __INVERT__: SlotDef = SlotDef::new("Py_nb_invert", "unaryfunc");
 const __INDEX__: SlotDef = SlotDef::new("Py_nb_index", "unaryfunc");
 pub const __INT__: SlotDef = SlotDef::new("Py_nb_int", "unaryfunc");
 const __FLOAT__: SlotDef = SlotDef::new("Py_nb_float", "unaryfunc");
-const __BOOL__: SlotDef = SlotDef::new("Py_nb_bool", "inquiry").ret_ty(Ty::Int);
+pub const __BOOL__: SlotDef = SlotDef::new("Py_nb_bool", "inquiry").ret_ty(Ty::Int);
 
 const __IADD__: SlotDef = SlotDef::new("Py_nb_inplace_add", "binaryfunc")
     .arguments(&[Ty::Object])
@@ -888,6 +1192,9 @@ const __RELEASEBUFFER__: SlotDef = SlotDef::new("Py_bf_releasebuffer", "releaseb
 const __CLEAR__: SlotDef = SlotDef::new("Py_tp_clear", "inquiry")
     .arguments(&[])
     .ret_ty(Ty::Int);
+const __DEL__: SlotDef = SlotDef::new("Py_tp_finalize", "destructor")
+    .ret_ty(Ty::Void)
+    .not_abi3();
 
 #[derive(Clone, Copy)]
 enum Ty {
@@ -1040,6 +1347,7 @@ pub struct SlotDef {
     extract_error_mode: ExtractErrorMode,
     return_mode: Option<ReturnMode>,
     require_unsafe: bool,
+    not_abi3: bool,
 }
 
 const NO_ARGUMENTS: &[Ty] = &[];
@@ -1054,6 +1362,7 @@ impl SlotDef {
             extract_error_mode: ExtractErrorMode::Raise,
             return_mode: None,
             require_unsafe: false,
+            not_abi3: false,
         }
     }
 
@@ -1096,6 +1405,12 @@ impl SlotDef {
         self
     }
 
+    /// The slot is not part of the stable ABI, so cannot be used with the `abi3` feature.
+    const fn not_abi3(mut self) -> Self {
+        self.not_abi3 = true;
+        self
+    }
+
     pub fn generate_type_slot(
         &self,
         cls: &syn::Type,
@@ -1110,7 +1425,14 @@ impl SlotDef {
             ret_ty,
             return_mode,
             require_unsafe,
+            not_abi3,
         } = self;
+        if *not_abi3 {
+            ensure_spanned!(
+                !pyo3_build_config::get().abi3,
+                spec.name.span() => format!("`{}` is not supported with the `abi3` feature", method_name)
+            );
+        }
         if *require_unsafe {
             ensure_spanned!(
                 spec.unsafety.is_some(),
@@ -1122,7 +1444,6 @@ impl SlotDef {
             .map(|i| format_ident!("arg{}", i))
             .collect();
         let wrapper_ident = format_ident!("__pymethod_{}__", method_name);
-        let ret_ty = ret_ty.ffi_type();
         let mut holders = Vec::new();
         let body = generate_method_body(
             cls,
@@ -1131,7 +1452,9 @@ impl SlotDef {
             *extract_error_mode,
             &mut holders,
             return_mode.as_ref(),
+            *ret_ty,
         )?;
+        let ret_ty = ret_ty.ffi_type();
         let name = spec.name;
         let associated_method = quote! {
             unsafe fn #wrapper_ident(
@@ -1177,15 +1500,23 @@ fn generate_method_body(
     extract_error_mode: ExtractErrorMode,
     holders: &mut Vec<TokenStream>,
     return_mode: Option<&ReturnMode>,
+    ret_ty: Ty,
 ) -> Result<TokenStream> {
     let self_arg = spec.tp.self_arg(Some(cls), extract_error_mode, holders);
     let rust_name = spec.name;
     let args = extract_proto_arguments(spec, arguments, extract_error_mode, holders)?;
+    let call_context = spec.call_context(quote! { new(py, _slf) });
     let call = quote! { #cls::#rust_name(#self_arg #(#args),*) };
-    Ok(if let Some(return_mode) = return_mode {
+    let body = if let Some(return_mode) = return_mode {
         return_mode.return_call_output(call)
+    } else if let Ty::Object = ret_ty {
+        quotes::map_result_into_ptr(quotes::ok_wrap(call))
     } else {
         quote! { _pyo3::callback::convert(py, #call) }
+    };
+    Ok(quote! {
+        #call_context
+        #body
     })
 }
 
@@ -1216,7 +1547,11 @@ impl SlotFragmentDef {
         self
     }
 
-    fn generate_pyproto_fragment(&self, cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStream> {
+    fn generate_pyproto_fragment(
+        &self,
+        cls: &syn::Type,
+        spec: &FnSpec<'_>,
+    ) -> Result<SlotFragmentImpl> {
         let SlotFragmentDef {
             fragment,
             arguments,
@@ -1238,32 +1573,43 @@ impl SlotFragmentDef {
             *extract_error_mode,
             &mut holders,
             None,
+            *ret_ty,
         )?;
         let ret_ty = ret_ty.ffi_type();
-        Ok(quote! {
-            impl _pyo3::impl_::pyclass::#fragment_trait<#cls> for _pyo3::impl_::pyclass::PyClassImplCollector<#cls> {
-
-                #[inline]
-                unsafe fn #method(
-                    self,
-                    py: _pyo3::Python,
-                    _raw_slf: *mut _pyo3::ffi::PyObject,
-                    #(#arg_idents: #arg_types),*
-                ) -> _pyo3::PyResult<#ret_ty> {
-                    impl #cls {
-                        unsafe fn #wrapper_ident(
-                            py: _pyo3::Python,
-                            _raw_slf: *mut _pyo3::ffi::PyObject,
-                            #(#arg_idents: #arg_types),*
-                        ) -> _pyo3::PyResult<#ret_ty> {
-                            let _slf = _raw_slf;
-                            #( #holders )*
-                            #body
-                        }
+        // The wrapper is implemented through a local trait rather than an inherent impl, so that
+        // it cannot clash with the wrapper of a conflicting definition in another block.
+        let method = quote! {
+            #[inline]
+            unsafe fn #method(
+                self,
+                py: _pyo3::Python,
+                _raw_slf: *mut _pyo3::ffi::PyObject,
+                #(#arg_idents: #arg_types),*
+            ) -> _pyo3::PyResult<#ret_ty> {
+                trait SlotFragmentWrapper {
+                    unsafe fn #wrapper_ident(
+                        py: _pyo3::Python,
+                        _raw_slf: *mut _pyo3::ffi::PyObject,
+                        #(#arg_idents: #arg_types),*
+                    ) -> _pyo3::PyResult<#ret_ty>;
+                }
+                impl SlotFragmentWrapper for #cls {
+                    unsafe fn #wrapper_ident(
+                        py: _pyo3::Python,
+                        _raw_slf: *mut _pyo3::ffi::PyObject,
+                        #(#arg_idents: #arg_types),*
+                    ) -> _pyo3::PyResult<#ret_ty> {
+                        let _slf = _raw_slf;
+                        #( #holders )*
+                        #body
                     }
-                    #cls::#wrapper_ident(py, _raw_slf, #(#arg_idents),*)
                 }
+                <#cls as SlotFragmentWrapper>::#wrapper_ident(py, _raw_slf, #(#arg_idents),*)
             }
+        };
+        Ok(SlotFragmentImpl {
+            fragment_trait,
+            method,
         })
     }
 }
@@ -1352,8 +1698,8 @@ fn extract_proto_arguments(
     let mut non_python_args = 0;
 
     for arg in &spec.signature.arguments {
-        if arg.py {
-            args.push(quote! { py });
+        if let Some(value) = arg.injected_value() {
+            args.push(value);
         } else {
             let ident = syn::Ident::new(&format!("arg{}", non_python_args), Span::call_site());
             let conversions = proto_args.get(non_python_args)
@@ -0,0 +1,406 @@
+//! Compatibility layer for impls of the legacy `#[pyproto]` protocol traits, either with the
+//! deprecated `#[pyproto]` attribute or in `#[pymethods]`.
+
+use crate::pyimpl::{impl_methods, PyClassMethodsType, PyImplOptions};
+use crate::utils::get_pyo3_crate;
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{ParseStream, Parser},
+    spanned::Spanned,
+    Result,
+};
+
+/// A legacy protocol trait which can be implemented with `#[pymethods]`.
+struct PyProtoDef {
+    /// Name of the (marker) protocol trait, e.g. `PyObjectProtocol`
+    trait_name: &'static str,
+    /// Collector trait which holds the generated items
+    items_trait: &'static str,
+    /// Method of `items_trait` which returns the generated items
+    items_fn: &'static str,
+    /// Methods which may be implemented as part of the protocol
+    methods: &'static [&'static str],
+}
+
+const PROTOCOLS: &[PyProtoDef] = &[
+    PyProtoDef {
+        trait_name: "PyObjectProtocol",
+        items_trait: "PyObjectProtocolItems",
+        items_fn: "object_protocol_items",
+        methods: &[
+            "__getattr__",
+            "__setattr__",
+            "__delattr__",
+            "__str__",
+            "__repr__",
+            "__format__",
+            "__hash__",
+            "__bytes__",
+            "__richcmp__",
+            "__bool__",
+        ],
+    },
+    PyProtoDef {
+        trait_name: "PyNumberProtocol",
+        items_trait: "PyNumberProtocolItems",
+        items_fn: "number_protocol_items",
+        methods: &[
+            "__add__",
+            "__sub__",
+            "__mul__",
+            "__matmul__",
+            "__truediv__",
+            "__floordiv__",
+            "__mod__",
+            "__divmod__",
+            "__pow__",
+            "__lshift__",
+            "__rshift__",
+            "__and__",
+            "__xor__",
+            "__or__",
+            "__radd__",
+            "__rsub__",
+            "__rmul__",
+            "__rmatmul__",
+            "__rtruediv__",
+            "__rfloordiv__",
+            "__rmod__",
+            "__rdivmod__",
+            "__rpow__",
+            "__rlshift__",
+            "__rrshift__",
+            "__rand__",
+            "__rxor__",
+            "__ror__",
+            "__iadd__",
+            "__isub__",
+            "__imul__",
+            "__imatmul__",
+            "__itruediv__",
+            "__ifloordiv__",
+            "__imod__",
+            "__ipow__",
+            "__ilshift__",
+            "__irshift__",
+            "__iand__",
+            "__ixor__",
+            "__ior__",
+            "__neg__",
+            "__pos__",
+            "__abs__",
+            "__invert__",
+            "__complex__",
+            "__int__",
+            "__float__",
+            "__index__",
+            "__round__",
+        ],
+    },
+    PyProtoDef {
+        trait_name: "PyMappingProtocol",
+        items_trait: "PyMappingProtocolItems",
+        items_fn: "mapping_protocol_items",
+        methods: &["__len__", "__getitem__", "__setitem__", "__delitem__"],
+    },
+    PyProtoDef {
+        trait_name: "PySequenceProtocol",
+        items_trait: "PySequenceProtocolItems",
+        items_fn: "sequence_protocol_items",
+        methods: &[
+            "__len__",
+            "__getitem__",
+            "__setitem__",
+            "__delitem__",
+            "__contains__",
+            "__concat__",
+            "__repeat__",
+            "__inplace_concat__",
+            "__inplace_repeat__",
+        ],
+    },
+    PyProtoDef {
+        trait_name: "PyIterProtocol",
+        items_trait: "PyIterProtocolItems",
+        items_fn: "iter_protocol_items",
+        methods: &["__iter__", "__next__"],
+    },
+    PyProtoDef {
+        trait_name: "PyAsyncProtocol",
+        items_trait: "PyAsyncProtocolItems",
+        items_fn: "async_protocol_items",
+        methods: &["__await__", "__aiter__", "__anext__"],
+    },
+    PyProtoDef {
+        trait_name: "PyContextProtocol",
+        items_trait: "PyContextProtocolItems",
+        items_fn: "context_protocol_items",
+        methods: &["__enter__", "__exit__"],
+    },
+    PyProtoDef {
+        trait_name: "PyDescrProtocol",
+        items_trait: "PyDescrProtocolItems",
+        items_fn: "descr_protocol_items",
+        methods: &["__get__", "__set__", "__delete__", "__set_name__"],
+    },
+    PyProtoDef {
+        trait_name: "PyGCProtocol",
+        items_trait: "PyGCProtocolItems",
+        items_fn: "gc_protocol_items",
+        methods: &["__traverse__", "__clear__"],
+    },
+    PyProtoDef {
+        trait_name: "PyBufferProtocol",
+        items_trait: "PyBufferProtocolItems",
+        items_fn: "buffer_protocol_items",
+        methods: &["bf_getbuffer", "bf_releasebuffer"],
+    },
+];
+
+/// Calls to collect the items of every protocol trait impl, used by `#[pyclass]`.
+pub fn pyproto_items() -> impl Iterator<Item = TokenStream> {
+    PROTOCOLS.iter().map(|proto| {
+        let items_fn = syn::Ident::new(proto.items_fn, Span::call_site());
+        quote!(collector.#items_fn())
+    })
+}
+
+/// Expands the deprecated `#[pyproto] impl PyObjectProtocol for MyClass { ... }` exactly like
+/// `#[pymethods]` on the same impl, with a deprecation warning pointing at `#[pymethods]`.
+pub fn build_py_proto(ast: &mut syn::ItemImpl) -> Result<TokenStream> {
+    ensure_spanned!(
+        ast.trait_.is_some(),
+        ast.self_ty.span() => "#[pyproto] can only be used on impls of protocol traits, e.g. `impl PyObjectProtocol for MyClass`"
+    );
+    impl_protocol(ast, true)
+}
+
+/// Expands `#[pymethods] impl PyObjectProtocol for MyClass { ... }`; see [`impl_protocol`].
+pub(crate) fn build_protocol_methods(ast: &mut syn::ItemImpl) -> Result<TokenStream> {
+    impl_protocol(ast, false)
+}
+
+/// Expands an impl of a protocol trait, whose methods are registered exactly like those of an
+/// inherent `#[pymethods]` block, into:
+/// - an empty impl of the (marker) protocol trait,
+/// - an impl of a hidden trait, declared alongside, which holds the methods.
+///
+/// Keeping the methods and their wrappers out of inherent impls means that a method which is also
+/// defined in an inherent `#[pymethods]` block of the class is only reported once, by the slot
+/// markers (see `TypeSlot::markers`), instead of as a series of duplicate definitions.
+fn impl_protocol(ast: &mut syn::ItemImpl, pyproto_attribute: bool) -> Result<TokenStream> {
+    let attribute = if pyproto_attribute {
+        "#[pyproto]"
+    } else {
+        "#[pymethods]"
+    };
+    let (mut trait_path, for_token) = match ast.trait_.take() {
+        Some((None, path, for_token)) => (path, for_token),
+        Some((Some(bang), _, _)) => {
+            bail_spanned!(bang.span() => format!("{} cannot be used on negative impls", attribute))
+        }
+        None => unreachable!("only called for trait impls"),
+    };
+    if let Some(param) = ast
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+    {
+        bail_spanned!(param.span() => format!("{} cannot be used with generics", attribute));
+    }
+    let cls = match &*ast.self_ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last().unwrap().ident.clone()
+        }
+        other => bail_spanned!(
+            other.span() => format!("{} expected a path to a #[pyclass]", attribute)
+        ),
+    };
+
+    let trait_segment = trait_path.segments.last_mut().unwrap();
+    // The protocol traits take a lifetime parameter, which used to be left out
+    if trait_segment.arguments.is_empty() {
+        trait_segment.arguments = syn::PathArguments::AngleBracketed(syn::parse_quote!(<'_>));
+    }
+    let trait_ident = &trait_segment.ident;
+    let proto = match PROTOCOLS
+        .iter()
+        .find(|proto| trait_ident == proto.trait_name)
+    {
+        Some(proto) => proto,
+        None => {
+            let protocols = PROTOCOLS
+                .iter()
+                .map(|proto| format!("`{}`", proto.trait_name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if pyproto_attribute {
+                bail_spanned!(trait_ident.span() => format!(
+                    "`{}` is not a protocol supported by #[pyproto], expected one of: {}",
+                    trait_ident, protocols
+                ))
+            }
+            bail_spanned!(trait_ident.span() => format!(
+                "#[pymethods] cannot be used on trait impl blocks, except for the legacy protocol traits: {}",
+                protocols
+            ))
+        }
+    };
+
+    for item in &mut ast.items {
+        match item {
+            syn::ImplItem::Fn(meth) => {
+                let name = meth.sig.ident.to_string();
+                ensure_spanned!(
+                    proto.methods.contains(&name.as_str()),
+                    meth.sig.ident.span() => format!("`{}` is not part of `{}`", name, proto.trait_name)
+                );
+                // The buffer protocol used to be implemented with the C slot names
+                if let Some(slot) = name.strip_prefix("bf_") {
+                    let python_name = syn::LitStr::new(&format!("__{}__", slot), Span::call_site());
+                    meth.attrs
+                        .push(syn::parse_quote!(#[pyo3(name = #python_name)]));
+                }
+            }
+            other => bail_spanned!(
+                other.span() => format!("{} impls of protocol traits may only contain methods", attribute)
+            ),
+        }
+    }
+
+    let options = PyImplOptions::from_attrs(&mut ast.attrs)?;
+    let deprecation = if pyproto_attribute {
+        let krate = get_pyo3_crate(&options.krate);
+        let span = trait_path.span();
+        Some(quote_spanned! { span =>
+            const _: () = {
+                use #krate as _pyo3;
+                #[allow(clippy::let_unit_value)]
+                let _ = _pyo3::impl_::deprecations::PYPROTO_ATTRIBUTE;
+            };
+        })
+    } else {
+        None
+    };
+    let methods_type = PyClassMethodsType::PyProto {
+        items_trait: proto.items_trait,
+        items_fn: proto.items_fn,
+    };
+    let expanded = impl_methods(&ast.self_ty, &mut ast.items, methods_type, options)?;
+
+    let methods_trait = format_ident!("__pyo3_{}_{}", proto.trait_name, cls);
+    let ty = &ast.self_ty;
+    let declarations = ast.items.iter().map(|item| match item {
+        syn::ImplItem::Fn(meth) => declare(meth, ty),
+        _ => unreachable!("checked above"),
+    });
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let trait_generics = &ast.generics.params;
+    let methods_trait_decl = quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        trait #methods_trait<#trait_generics> #where_clause {
+            #(#declarations)*
+        }
+    };
+    let protocol_impl = quote! {
+        impl #impl_generics #trait_path for #ty #where_clause {}
+    };
+    ast.trait_ = Some((
+        None,
+        syn::parse_quote!(#methods_trait #ty_generics),
+        for_token,
+    ));
+
+    Ok(quote! {
+        #methods_trait_decl
+
+        #protocol_impl
+
+        #expanded
+
+        #deprecation
+    })
+}
+
+/// Moves the wrappers generated for a protocol trait impl into a local trait, rather than an
+/// inherent impl of the class.
+pub(crate) fn impl_wrappers(
+    ty: &syn::Type,
+    associated_methods: Vec<TokenStream>,
+) -> Result<TokenStream> {
+    let mut wrappers = Vec::new();
+    for tokens in associated_methods {
+        let parse_methods = |input: ParseStream<'_>| {
+            let mut methods = Vec::new();
+            while !input.is_empty() {
+                methods.push(input.parse::<syn::ImplItemFn>()?);
+            }
+            Ok(methods)
+        };
+        wrappers.extend(parse_methods.parse2(tokens)?);
+    }
+    for wrapper in &mut wrappers {
+        wrapper.vis = syn::Visibility::Inherited;
+    }
+    let declarations = wrappers.iter().map(|wrapper| declare(wrapper, ty));
+    Ok(quote! {
+        #[allow(non_snake_case)]
+        trait PyProtoWrappers {
+            #(#declarations)*
+        }
+
+        #[allow(non_snake_case)]
+        impl PyProtoWrappers for #ty {
+            #(#wrappers)*
+        }
+    })
+}
+
+/// The declaration of `method`, implemented for `ty`, in a trait.
+fn declare(method: &syn::ImplItemFn, ty: &syn::Type) -> syn::TraitItemFn {
+    let mut sig = method.sig.clone();
+    for input in &mut sig.inputs {
+        if let syn::FnArg::Typed(pat_type) = input {
+            // Patterns are not allowed in declarations without a body
+            pat_type.attrs.clear();
+            *pat_type.pat = syn::parse_quote!(_);
+            *pat_type.ty = replace_self(&pat_type.ty, ty);
+        }
+    }
+    if let syn::ReturnType::Type(_, output) = &mut sig.output {
+        **output = replace_self(output, ty);
+    }
+    syn::TraitItemFn {
+        attrs: method
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .cloned()
+            .collect(),
+        sig,
+        default: None,
+        semi_token: Some(Default::default()),
+    }
+}
+
+/// Replaces `Self` in `input` with `ty`, as the trait does not know the implementing type.
+fn replace_self(input: &syn::Type, ty: &syn::Type) -> syn::Type {
+    fn replace(tokens: TokenStream, ty: &syn::Type) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Ident(ident) if ident == "Self" => ty.to_token_stream(),
+                TokenTree::Group(group) => {
+                    let mut replaced = Group::new(group.delimiter(), replace(group.stream(), ty));
+                    replaced.set_span(group.span());
+                    TokenTree::Group(replaced).into()
+                }
+                other => other.into(),
+            })
+            .collect()
+    }
+    syn::parse2(replace(input.to_token_stream(), ty)).expect("replacing `Self` keeps a valid type")
+}
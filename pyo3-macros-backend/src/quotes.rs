@@ -8,14 +8,28 @@ pub(crate) fn some_wrap(obj: TokenStream) -> TokenStream {
 }
 
 pub(crate) fn ok_wrap(obj: TokenStream) -> TokenStream {
+    // `match` rather than `let`, so that temporaries borrowed by `obj` live long enough
     quote! {
-        _pyo3::impl_::wrap::OkWrap::wrap(#obj)
-            .map_err(::core::convert::Into::<_pyo3::PyErr>::into)
+        match #obj {
+            obj => _pyo3::impl_::wrap::converter(&obj)
+                .wrap(obj)
+                .map_err(::core::convert::Into::<_pyo3::PyErr>::into),
+        }
     }
 }
 
 pub(crate) fn map_result_into_ptr(result: TokenStream) -> TokenStream {
     quote! {
-        _pyo3::impl_::wrap::map_result_into_ptr(py, #result)
+        match #result {
+            result => _pyo3::impl_::wrap::converter(&result).map_into_ptr(py, result),
+        }
+    }
+}
+
+pub(crate) fn map_result_into_py(result: TokenStream) -> TokenStream {
+    quote! {
+        match #result {
+            result => _pyo3::impl_::wrap::converter(&result).map_into_py(py, result),
+        }
     }
 }
@@ -167,6 +167,44 @@ pub fn apply_renaming_rule(rule: RenamingRule, name: &str) -> String {
     }
 }
 
-pub(crate) fn is_abi3() -> bool {
-    pyo3_build_config::get().abi3
+/// Whether the `METH_FASTCALL | METH_KEYWORDS` calling convention is available, which is only
+/// part of the stable ABI since Python 3.10.
+pub(crate) fn has_fastcall_with_keywords() -> bool {
+    let config = pyo3_build_config::get();
+    !config.abi3
+        || config.version
+            >= pyo3_build_config::PythonVersion {
+                major: 3,
+                minor: 10,
+            }
+}
+
+/// Whether `Py_TPFLAGS_IMMUTABLETYPE` is available, which was added in Python 3.10. The class
+/// attributes of immutable types are stored in `tp_dict` directly, which the limited API does not
+/// give access to.
+pub(crate) fn has_immutable_types() -> bool {
+    let config = pyo3_build_config::get();
+    !config.abi3
+        && config.version
+            >= pyo3_build_config::PythonVersion {
+                major: 3,
+                minor: 10,
+            }
+}
+
+/// Whether type objects can be made immortal, which PyO3 only supports on CPython 3.12 and newer
+/// with the GIL, when not using the limited API.
+pub(crate) fn has_immortal_types() -> bool {
+    let config = pyo3_build_config::get();
+    !config.abi3
+        && !config.implementation.is_pypy()
+        && !config
+            .build_flags
+            .0
+            .contains(&pyo3_build_config::BuildFlag::Py_GIL_DISABLED)
+        && config.version
+            >= pyo3_build_config::PythonVersion {
+                major: 3,
+                minor: 12,
+            }
 }
@@ -5,7 +5,8 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use pyo3_macros_backend::{
-    build_derive_from_pyobject, build_py_class, build_py_enum, build_py_function, build_py_methods,
+    build_derive_from_pyobject, build_derive_into_pyerr, build_derive_into_pytuple, build_py_class,
+    build_py_class_alias, build_py_enum, build_py_function, build_py_methods, check_module_doc,
     get_doc, process_functions_in_module, pymodule_impl, PyClassArgs, PyClassMethodsType,
     PyFunctionOptions, PyModuleOptions,
 };
@@ -47,6 +48,10 @@ pub fn pymodule(args: TokenStream, input: TokenStream) -> TokenStream {
         return err.into_compile_error().into();
     }
 
+    if let Err(err) = check_module_doc(&ast.attrs) {
+        return err.into_compile_error().into();
+    }
+
     let doc = get_doc(&ast.attrs, None);
 
     let expanded = pymodule_impl(&ast.sig.ident, options, doc, &ast.vis);
@@ -65,11 +70,13 @@ pub fn pyclass(attr: TokenStream, input: TokenStream) -> TokenStream {
     match item {
         Item::Struct(struct_) => pyclass_impl(attr, struct_, methods_type()),
         Item::Enum(enum_) => pyclass_enum_impl(attr, enum_, methods_type()),
-        unsupported => {
-            syn::Error::new_spanned(unsupported, "#[pyclass] only supports structs and enums.")
-                .into_compile_error()
-                .into()
-        }
+        Item::Type(alias) => pyclass_alias_impl(attr, alias, methods_type()),
+        unsupported => syn::Error::new_spanned(
+            unsupported,
+            "#[pyclass] only supports structs, enums and type aliases.",
+        )
+        .into_compile_error()
+        .into(),
     }
 }
 
@@ -90,10 +97,21 @@ pub fn pyclass(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// For more on creating class methods,
 /// see the [class section of the guide][1].
 ///
+/// With `#[pymethods(from_trait = SomeTrait)]`, methods in the block declared without a body
+/// forward to the class's implementation of `SomeTrait`; see [the guide][12].
+///
+/// With `#[pymethods(rename_all = "camelCase")]`, methods, getters and setters without an explicit
+/// `name` are renamed following the given rule, like `#[pyclass(rename_all = ...)]`.
+///
 /// If the [`multiple-pymethods`][2] feature is enabled, it is possible to implement
 /// multiple `#[pymethods]` blocks for a single `#[pyclass]`.
 /// This will add a transitive dependency on the [`inventory`][3] crate.
 ///
+/// To help migrating code written for the deprecated `#[pyproto]` attribute, `#[pymethods]` also
+/// accepts impls of the legacy protocol traits, e.g. `#[pymethods] impl PyObjectProtocol for
+/// MyClass { ... }`, alongside the class's inherent `#[pymethods]` block; see
+/// [the migration guide][13].
+///
 /// [1]: https://pyo3.rs/latest/class.html#instance-methods
 /// [2]: https://pyo3.rs/latest/features.html#multiple-pymethods
 /// [3]: https://docs.rs/inventory/
@@ -105,6 +123,8 @@ pub fn pyclass(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// [9]: https://pyo3.rs/latest/class.html#class-attributes
 /// [10]: https://pyo3.rs/latest/class.html#method-arguments
 /// [11]: https://pyo3.rs/latest/class.html#object-properties-using-pyo3get-set
+/// [12]: https://pyo3.rs/latest/class.html#methods-implemented-by-a-rust-trait
+/// [13]: https://pyo3.rs/latest/migration.html#pyproto-has-been-deprecated
 #[proc_macro_attribute]
 pub fn pymethods(attr: TokenStream, input: TokenStream) -> TokenStream {
     let methods_type = if cfg!(feature = "multiple-pymethods") {
@@ -115,6 +135,29 @@ pub fn pymethods(attr: TokenStream, input: TokenStream) -> TokenStream {
     pymethods_impl(attr, input, methods_type)
 }
 
+/// Deprecated compatibility attribute for the legacy `#[pyproto]` API, available with the
+/// `pyproto` feature.
+///
+/// `#[pyproto] impl PyObjectProtocol for MyClass { ... }` is expanded exactly like
+/// `#[pymethods]` on the same impl, and emits a deprecation warning suggesting to use
+/// `#[pymethods]` instead. See [the migration guide][1].
+///
+/// [1]: https://pyo3.rs/latest/migration.html#pyproto-has-been-deprecated
+#[cfg(feature = "pyproto")]
+#[proc_macro_attribute]
+pub fn pyproto(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut ast = parse_macro_input!(input as syn::ItemImpl);
+    let attr: TokenStream2 = attr.into();
+    ast.attrs.push(syn::parse_quote!( #[pyo3(#attr)] ));
+    let expanded = pyo3_macros_backend::build_py_proto(&mut ast).unwrap_or_compile_error();
+
+    quote!(
+        #ast
+        #expanded
+    )
+    .into()
+}
+
 /// A proc macro used to expose Rust functions to Python.
 ///
 /// Functions annotated with `#[pyfunction]` can also be annotated with the following `#[pyo3]`
@@ -158,6 +201,50 @@ pub fn derive_from_py_object(item: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Derives `From<T> for PyErr` for an error struct or enum.
+///
+/// Each enum variant (or the struct) takes one of the following annotations:
+///
+/// |  Annotation  |  Description |
+/// | :-  | :- |
+/// | `#[pyerr(exception = ExceptionType)]` | Raises `ExceptionType`, with the `Display` output of the value as message. |
+/// | `#[pyerr(transparent)]` | Forwards to the `From<Field> for PyErr` implementation of the only field. |
+///
+/// `#[pyerr(exception = ...)]` on an enum sets the exception for variants without an annotation.
+/// `#[pyerr(crate = "...")]` on the enum or struct changes the path to the `pyo3` crate.
+///
+/// For more, see the [error handling section of the guide][1].
+///
+/// [1]: https://pyo3.rs/latest/function/error_handling.html
+#[proc_macro_derive(IntoPyErr, attributes(pyerr))]
+pub fn derive_into_pyerr(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_into_pyerr(&ast).unwrap_or_compile_error();
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
+/// Derives `ToPyObject`, `IntoPy<PyObject>` and `IntoPy<Py<PyTuple>>` for a struct, converting it
+/// to a Python tuple of its fields in declaration order.
+///
+/// Combined with `#[derive(FromPyObject)]` and `#[pyo3(from_tuple)]`, a struct with named fields
+/// round-trips through a Python tuple. `#[pyo3(crate = "...")]` changes the path to the `pyo3` crate.
+///
+/// For more, see the [conversions section of the guide][1].
+///
+/// [1]: https://pyo3.rs/latest/conversions/traits.html
+#[proc_macro_derive(IntoPyTuple, attributes(pyo3))]
+pub fn derive_into_pytuple(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_into_pytuple(&ast).unwrap_or_compile_error();
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
 fn pyclass_impl(
     attrs: TokenStream,
     mut ast: syn::ItemStruct,
@@ -173,6 +260,21 @@ fn pyclass_impl(
     .into()
 }
 
+fn pyclass_alias_impl(
+    attrs: TokenStream,
+    mut ast: syn::ItemType,
+    methods_type: PyClassMethodsType,
+) -> TokenStream {
+    let args = parse_macro_input!(attrs with PyClassArgs::parse_stuct_args);
+    let expanded = build_py_class_alias(&mut ast, args, methods_type).unwrap_or_compile_error();
+
+    quote!(
+        #ast
+        #expanded
+    )
+    .into()
+}
+
 fn pyclass_enum_impl(
     attrs: TokenStream,
     mut ast: syn::ItemEnum,
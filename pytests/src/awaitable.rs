@@ -4,6 +4,15 @@
 //! Both IterAwaitable and FutureAwaitable will return a value immediately
 //! when awaited, see guide examples related to pyo3-asyncio for ways
 //! to suspend tasks and await results.
+//!
+//! `sleep` is an `async fn` woken from another thread, which can be awaited
+//! from any async runtime supported by PyO3.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 use pyo3::exceptions::PyStopIteration;
 use pyo3::prelude::*;
@@ -78,9 +87,59 @@ impl FutureAwaitable {
     }
 }
 
+#[derive(Default)]
+struct SleepState {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+/// Future completed by a thread after the given duration.
+struct Sleep {
+    duration: Duration,
+    state: Option<Arc<Mutex<SleepState>>>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let duration = self.duration;
+        let state = self.state.get_or_insert_with(|| {
+            let state = Arc::new(Mutex::new(SleepState::default()));
+            let thread_state = state.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(duration);
+                let mut state = thread_state.lock().unwrap();
+                state.done = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+            state
+        });
+        let mut state = state.lock().unwrap();
+        if state.done {
+            return Poll::Ready(());
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[pyfunction]
+async fn sleep(seconds: f64, result: Option<PyObject>) -> Option<PyObject> {
+    Sleep {
+        duration: Duration::from_secs_f64(seconds),
+        state: None,
+    }
+    .await;
+    result
+}
+
 #[pymodule]
 pub fn awaitable(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<IterAwaitable>()?;
     m.add_class::<FutureAwaitable>()?;
+    m.add_function(wrap_pyfunction!(sleep, m)?)?;
     Ok(())
 }
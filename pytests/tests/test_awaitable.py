@@ -1,6 +1,8 @@
+import asyncio
+
 import pytest
 
-from pyo3_pytests.awaitable import IterAwaitable, FutureAwaitable
+from pyo3_pytests.awaitable import IterAwaitable, FutureAwaitable, sleep
 
 
 @pytest.mark.asyncio
@@ -11,3 +13,30 @@ async def test_iter_awaitable():
 @pytest.mark.asyncio
 async def test_future_awaitable():
     assert await FutureAwaitable(5) == 5
+
+
+def test_sleep_asyncio():
+    assert asyncio.run(sleep(0.01, 42)) == 42
+
+
+def test_sleep_trio():
+    trio = pytest.importorskip("trio")
+    assert trio.run(sleep, 0.01, 42) == 42
+
+
+def test_sleep_trio_cancelled():
+    trio = pytest.importorskip("trio")
+
+    async def main():
+        with trio.move_on_after(0.01) as scope:
+            await sleep(999)
+        return scope.cancelled_caught
+
+    assert trio.run(main)
+
+
+@pytest.mark.parametrize("backend", ["asyncio", "trio"])
+def test_sleep_anyio(backend):
+    anyio = pytest.importorskip("anyio")
+    pytest.importorskip(backend)
+    assert anyio.run(sleep, 0.01, 42, backend=backend) == 42
@@ -0,0 +1,512 @@
+//! Support for exchanging data with Apache Arrow libraries through the [Arrow C Data Interface].
+//!
+//! Libraries such as pyarrow and polars implement the [Arrow PyCapsule Interface]: an object
+//! exposes its schema from `__arrow_c_schema__` as a [`PyCapsule`] named `"arrow_schema"`, and
+//! its data from `__arrow_c_array__` as a tuple of that capsule and one named `"arrow_array"`.
+//! The capsules wrap the [`ArrowSchema`] and [`ArrowArray`] structs of the C Data Interface, which
+//! describe the memory of the data without copying it.
+//!
+//! - To produce data, build the structs (for example with [`ArrowArray::from_vec`], or with the
+//!   export functions of the `arrow` crate) and return them from [`export_array`] or
+//!   [`ArrowSchema::into_capsule`].
+//! - To consume data, take the structs from any object implementing the protocol with
+//!   [`import_array`] or [`import_schema`].
+//!
+//! Both structs release the memory they describe when dropped, unless they have been moved
+//! elsewhere, so that ownership is transferred exactly once.
+//!
+//! ```rust
+//! use pyo3::arrow::{self, ArrowArray, ArrowSchema};
+//! use pyo3::prelude::*;
+//! use pyo3::types::PyTuple;
+//!
+//! #[pyclass]
+//! struct Ramp {
+//!     len: i64,
+//! }
+//!
+//! #[pymethods]
+//! impl Ramp {
+//!     #[pyo3(signature = (requested_schema = None))]
+//!     fn __arrow_c_array__<'py>(
+//!         &self,
+//!         py: Python<'py>,
+//!         requested_schema: Option<&Bound<'py, PyAny>>,
+//!     ) -> PyResult<Bound<'py, PyTuple>> {
+//!         let _ = requested_schema;
+//!         let data: Vec<i64> = (0..self.len).collect();
+//!         arrow::export_array(py, ArrowSchema::primitive::<i64>(), ArrowArray::from_vec(data))
+//!     }
+//! }
+//!
+//! # fn main() -> PyResult<()> {
+//! Python::with_gil(|py| {
+//!     let ramp = Bound::new(py, Ramp { len: 4 })?;
+//!     // e.g. `pyarrow.array(ramp)` in Python
+//!     let (schema, array) = arrow::import_array(ramp.as_any())?;
+//!     assert_eq!(array.len(), 4);
+//!     assert_eq!(array.primitive_values::<i64>(&schema)?, [0, 1, 2, 3]);
+//!     Ok(())
+//! })
+//! # }
+//! ```
+//!
+//! [Arrow C Data Interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+//! [Arrow PyCapsule Interface]: https://arrow.apache.org/docs/format/CDataInterface/PyCapsuleInterface.html
+
+use crate::exceptions::{PyTypeError, PyValueError};
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::types::{any::PyAnyMethods, PyCapsule, PyTuple};
+use crate::{ffi, intern, Bound, PyAny, PyErr, PyResult, Python};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+
+const ARROW_SCHEMA: &[u8] = b"arrow_schema\0";
+const ARROW_ARRAY: &[u8] = b"arrow_array\0";
+
+/// The field described by an [`ArrowSchema`] may contain nulls.
+pub const ARROW_FLAG_NULLABLE: i64 = 2;
+
+/// The type of a field, as in the C Data Interface's `struct ArrowSchema`.
+///
+/// Dropping an `ArrowSchema` calls its `release` callback, if any.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowSchema {
+    /// The type of the field, as a [format string].
+    ///
+    /// [format string]: https://arrow.apache.org/docs/format/CDataInterface.html#data-type-description-format-strings
+    pub format: *const c_char,
+    /// The name of the field; may be null.
+    pub name: *const c_char,
+    /// Metadata of the field, in a binary encoding; may be null.
+    pub metadata: *const c_char,
+    /// Flags of the field, such as [`ARROW_FLAG_NULLABLE`].
+    pub flags: i64,
+    /// The number of child fields.
+    pub n_children: i64,
+    /// The child fields, an array of `n_children` pointers.
+    pub children: *mut *mut ArrowSchema,
+    /// The type of the dictionary values, for dictionary-encoded fields; null otherwise.
+    pub dictionary: *mut ArrowSchema,
+    /// Releases the schema; `None` once the schema is released.
+    pub release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    /// Private data of the producer, for use by `release`.
+    pub private_data: *mut c_void,
+}
+
+/// The data of an array, as in the C Data Interface's `struct ArrowArray`.
+///
+/// Dropping an `ArrowArray` calls its `release` callback, if any.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowArray {
+    /// The number of elements.
+    pub length: i64,
+    /// The number of null elements, or -1 if not computed.
+    pub null_count: i64,
+    /// The index of the first element in the buffers.
+    pub offset: i64,
+    /// The number of buffers.
+    pub n_buffers: i64,
+    /// The number of child arrays.
+    pub n_children: i64,
+    /// The buffers, an array of `n_buffers` pointers, whose layout depends on the type.
+    pub buffers: *mut *const c_void,
+    /// The child arrays, an array of `n_children` pointers.
+    pub children: *mut *mut ArrowArray,
+    /// The dictionary values, for dictionary-encoded arrays; null otherwise.
+    pub dictionary: *mut ArrowArray,
+    /// Releases the array; `None` once the array is released.
+    pub release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    /// Private data of the producer, for use by `release`.
+    pub private_data: *mut c_void,
+}
+
+/// Rust types which Arrow stores as fixed-width primitive values.
+///
+/// # Safety
+///
+/// `FORMAT` must be the nul-terminated Arrow format string of a type with the same in-memory
+/// representation.
+pub unsafe trait ArrowPrimitive: Copy + Send + 'static {
+    /// The nul-terminated format string of the Arrow type.
+    const FORMAT: &'static [u8];
+}
+
+macro_rules! impl_arrow_primitive {
+    ($t:ty, $format:literal) => {
+        unsafe impl ArrowPrimitive for $t {
+            const FORMAT: &'static [u8] = $format;
+        }
+    };
+}
+
+impl_arrow_primitive!(i8, b"c\0");
+impl_arrow_primitive!(u8, b"C\0");
+impl_arrow_primitive!(i16, b"s\0");
+impl_arrow_primitive!(u16, b"S\0");
+impl_arrow_primitive!(i32, b"i\0");
+impl_arrow_primitive!(u32, b"I\0");
+impl_arrow_primitive!(i64, b"l\0");
+impl_arrow_primitive!(u64, b"L\0");
+impl_arrow_primitive!(f32, b"f\0");
+impl_arrow_primitive!(f64, b"g\0");
+
+/// Implemented by the two structs which can be wrapped in a capsule.
+trait CapsuleContents: Sized {
+    const NAME: &'static [u8];
+    fn released() -> Self;
+}
+
+impl CapsuleContents for ArrowSchema {
+    const NAME: &'static [u8] = ARROW_SCHEMA;
+    fn released() -> Self {
+        ArrowSchema::released()
+    }
+}
+
+impl CapsuleContents for ArrowArray {
+    const NAME: &'static [u8] = ARROW_ARRAY;
+    fn released() -> Self {
+        ArrowArray::released()
+    }
+}
+
+/// Destructor of capsules created by PyO3: releases the struct if no consumer moved it out.
+unsafe extern "C" fn capsule_destructor<T: CapsuleContents>(capsule: *mut ffi::PyObject) {
+    let ptr = ffi::PyCapsule_GetPointer(capsule, T::NAME.as_ptr() as *const c_char);
+    if ptr.is_null() {
+        ffi::PyErr_Clear();
+    } else {
+        drop(Box::from_raw(ptr as *mut T));
+    }
+}
+
+fn into_capsule<T: CapsuleContents>(py: Python<'_>, value: T) -> PyResult<Bound<'_, PyCapsule>> {
+    let ptr = Box::into_raw(Box::new(value));
+    unsafe {
+        let capsule = ffi::PyCapsule_New(
+            ptr as *mut c_void,
+            T::NAME.as_ptr() as *const c_char,
+            Some(capsule_destructor::<T>),
+        );
+        if capsule.is_null() {
+            drop(Box::from_raw(ptr));
+            return Err(PyErr::fetch(py));
+        }
+        Ok(capsule.assume_owned(py).downcast_into_unchecked())
+    }
+}
+
+fn from_capsule<T: CapsuleContents>(capsule: &Bound<'_, PyAny>) -> PyResult<T> {
+    let capsule = capsule.downcast::<PyCapsule>()?;
+    let name = T::NAME.as_ptr() as *const c_char;
+    unsafe {
+        if ffi::PyCapsule_IsValid(capsule.as_ptr(), name) != 1 {
+            return Err(PyValueError::new_err(format!(
+                "expected a capsule named '{}'",
+                CStr::from_bytes_with_nul_unchecked(T::NAME).to_string_lossy()
+            )));
+        }
+        let ptr = ffi::PyCapsule_GetPointer(capsule.as_ptr(), name) as *mut T;
+        // Move the struct out, leaving a released one behind for the capsule destructor.
+        Ok(std::ptr::replace(ptr, T::released()))
+    }
+}
+
+impl ArrowSchema {
+    /// Creates an already released schema, as a placeholder to move another schema into.
+    pub const fn released() -> Self {
+        ArrowSchema {
+            format: std::ptr::null(),
+            name: std::ptr::null(),
+            metadata: std::ptr::null(),
+            flags: 0,
+            n_children: 0,
+            children: std::ptr::null_mut(),
+            dictionary: std::ptr::null_mut(),
+            release: None,
+            private_data: std::ptr::null_mut(),
+        }
+    }
+
+    /// Creates the schema of an unnamed, nullable field of primitive values of type `T`.
+    pub fn primitive<T: ArrowPrimitive>() -> Self {
+        unsafe extern "C" fn release_static(schema: *mut ArrowSchema) {
+            (*schema).release = None;
+        }
+
+        ArrowSchema {
+            format: T::FORMAT.as_ptr() as *const c_char,
+            flags: ARROW_FLAG_NULLABLE,
+            release: Some(release_static),
+            ..ArrowSchema::released()
+        }
+    }
+
+    /// Returns `true` if the schema was released, so that it no longer describes a field.
+    #[inline]
+    pub fn is_released(&self) -> bool {
+        self.release.is_none()
+    }
+
+    /// Gets the format string of the field's type.
+    pub fn format(&self) -> Option<&CStr> {
+        if self.is_released() || self.format.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(self.format) })
+        }
+    }
+
+    /// Gets the name of the field.
+    pub fn name(&self) -> Option<&CStr> {
+        if self.is_released() || self.name.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(self.name) })
+        }
+    }
+
+    /// Wraps the schema in an `"arrow_schema"` capsule, as returned from `__arrow_c_schema__`.
+    pub fn into_capsule(self, py: Python<'_>) -> PyResult<Bound<'_, PyCapsule>> {
+        into_capsule(py, self)
+    }
+
+    /// Takes the schema out of an `"arrow_schema"` capsule.
+    pub fn from_capsule(capsule: &Bound<'_, PyAny>) -> PyResult<Self> {
+        from_capsule(capsule)
+    }
+}
+
+impl Drop for ArrowSchema {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) }
+        }
+    }
+}
+
+/// The allocation behind an array created by [`ArrowArray::from_vec`].
+struct VecPrivateData<T> {
+    buffers: [*const c_void; 2],
+    _values: Vec<T>,
+}
+
+unsafe extern "C" fn release_vec<T>(array: *mut ArrowArray) {
+    drop(Box::from_raw(
+        (*array).private_data as *mut VecPrivateData<T>,
+    ));
+    (*array).release = None;
+}
+
+impl ArrowArray {
+    /// Creates an already released array, as a placeholder to move another array into.
+    pub const fn released() -> Self {
+        ArrowArray {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: std::ptr::null_mut(),
+            children: std::ptr::null_mut(),
+            dictionary: std::ptr::null_mut(),
+            release: None,
+            private_data: std::ptr::null_mut(),
+        }
+    }
+
+    /// Creates an array of primitive values without nulls, taking ownership of `values`.
+    ///
+    /// The matching schema is [`ArrowSchema::primitive::<T>()`](ArrowSchema::primitive).
+    pub fn from_vec<T: ArrowPrimitive>(values: Vec<T>) -> Self {
+        let length = values.len() as i64;
+        let mut private_data = Box::new(VecPrivateData {
+            // No validity bitmap, as there are no nulls.
+            buffers: [std::ptr::null(), values.as_ptr() as *const c_void],
+            _values: values,
+        });
+        let buffers = private_data.buffers.as_mut_ptr();
+        ArrowArray {
+            length,
+            n_buffers: 2,
+            buffers,
+            release: Some(release_vec::<T>),
+            private_data: Box::into_raw(private_data) as *mut c_void,
+            ..ArrowArray::released()
+        }
+    }
+
+    /// Returns `true` if the array was released, so that it no longer holds data.
+    #[inline]
+    pub fn is_released(&self) -> bool {
+        self.release.is_none()
+    }
+
+    /// Gets the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.length as usize
+    }
+
+    /// Returns `true` if the array has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Gets the buffer at `index`, or a null pointer if the array does not have it.
+    pub fn buffer(&self, index: usize) -> *const c_void {
+        if self.is_released() || index as i64 >= self.n_buffers {
+            std::ptr::null()
+        } else {
+            unsafe { *self.buffers.add(index) }
+        }
+    }
+
+    /// Gets the values of an array of primitive values of type `T`, described by `schema`.
+    ///
+    /// Fails if the schema's format does not match `T`. The values of null elements are
+    /// unspecified; check the validity bitmap in [`buffer(0)`](ArrowArray::buffer) when
+    /// `null_count` is not 0.
+    pub fn primitive_values<T: ArrowPrimitive>(&self, schema: &ArrowSchema) -> PyResult<&[T]> {
+        let expected = unsafe { CStr::from_bytes_with_nul_unchecked(T::FORMAT) };
+        match schema.format() {
+            Some(format) if format == expected => {}
+            format => {
+                return Err(PyTypeError::new_err(format!(
+                    "cannot read Arrow array of format {:?} as {}",
+                    format,
+                    std::any::type_name::<T>()
+                )))
+            }
+        }
+        if self.is_released() {
+            return Err(PyValueError::new_err("Arrow array was released"));
+        }
+        if self.length == 0 {
+            return Ok(&[]);
+        }
+        let values = self.buffer(1) as *const T;
+        if values.is_null() {
+            return Err(PyValueError::new_err("Arrow array has no values buffer"));
+        }
+        // Safety: the values buffer of a primitive array holds `offset + length` values.
+        Ok(unsafe { std::slice::from_raw_parts(values.add(self.offset as usize), self.len()) })
+    }
+
+    /// Wraps the array in an `"arrow_array"` capsule.
+    pub fn into_capsule(self, py: Python<'_>) -> PyResult<Bound<'_, PyCapsule>> {
+        into_capsule(py, self)
+    }
+
+    /// Takes the array out of an `"arrow_array"` capsule.
+    pub fn from_capsule(capsule: &Bound<'_, PyAny>) -> PyResult<Self> {
+        from_capsule(capsule)
+    }
+}
+
+impl Drop for ArrowArray {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) }
+        }
+    }
+}
+
+/// Creates the `(schema, array)` tuple of capsules returned from `__arrow_c_array__`.
+pub fn export_array(
+    py: Python<'_>,
+    schema: ArrowSchema,
+    array: ArrowArray,
+) -> PyResult<Bound<'_, PyTuple>> {
+    let schema = schema.into_capsule(py);
+    let array = array.into_capsule(py);
+    Ok(PyTuple::new_bound(py, [schema?, array?]))
+}
+
+/// Takes the schema and data of `obj`, which is either an object implementing
+/// `__arrow_c_array__` or the tuple of capsules returned by it.
+pub fn import_array(obj: &Bound<'_, PyAny>) -> PyResult<(ArrowSchema, ArrowArray)> {
+    let capsules = if obj.is_instance_of::<PyTuple>() {
+        obj.clone()
+    } else {
+        obj.call_method0(intern!(obj.py(), "__arrow_c_array__"))?
+    };
+    let (schema, array): (Bound<'_, PyAny>, Bound<'_, PyAny>) = capsules.extract()?;
+    Ok((
+        ArrowSchema::from_capsule(&schema)?,
+        ArrowArray::from_capsule(&array)?,
+    ))
+}
+
+/// Takes the schema of `obj`, which is either an object implementing `__arrow_c_schema__` or the
+/// capsule returned by it.
+pub fn import_schema(obj: &Bound<'_, PyAny>) -> PyResult<ArrowSchema> {
+    if obj.is_instance_of::<PyCapsule>() {
+        return ArrowSchema::from_capsule(obj);
+    }
+    let capsule = obj.call_method0(intern!(obj.py(), "__arrow_c_schema__"))?;
+    ArrowSchema::from_capsule(&capsule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_array, import_array, import_schema, ArrowArray, ArrowSchema};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::Python;
+
+    #[test]
+    fn test_array_round_trip() {
+        Python::with_gil(|py| {
+            let capsules = export_array(
+                py,
+                ArrowSchema::primitive::<f64>(),
+                ArrowArray::from_vec(vec![0.5, 1.5]),
+            )
+            .unwrap();
+            let (schema, array) = import_array(&capsules).unwrap();
+            assert_eq!(schema.format().unwrap().to_bytes(), b"g");
+            assert_eq!(schema.name(), None);
+            assert_eq!(array.len(), 2);
+            assert_eq!(array.primitive_values::<f64>(&schema).unwrap(), [0.5, 1.5]);
+            assert!(array.primitive_values::<i64>(&schema).is_err());
+
+            // The capsules were emptied by the import.
+            let (schema, array) = import_array(&capsules).unwrap();
+            assert!(schema.is_released());
+            assert!(array.is_released());
+        });
+    }
+
+    #[test]
+    fn test_unconsumed_capsule_releases() {
+        static RELEASED: AtomicUsize = AtomicUsize::new(0);
+
+        unsafe extern "C" fn release(schema: *mut ArrowSchema) {
+            RELEASED.fetch_add(1, Ordering::SeqCst);
+            (*schema).release = None;
+        }
+
+        Python::with_gil(|py| {
+            let schema = || ArrowSchema {
+                format: b"i\0".as_ptr() as *const _,
+                release: Some(release),
+                ..ArrowSchema::released()
+            };
+
+            drop(schema().into_capsule(py).unwrap());
+            assert_eq!(RELEASED.load(Ordering::SeqCst), 1);
+
+            let capsule = schema().into_capsule(py).unwrap();
+            let imported = import_schema(&capsule).unwrap();
+            drop(capsule);
+            assert_eq!(RELEASED.load(Ordering::SeqCst), 1);
+            drop(imported);
+            assert_eq!(RELEASED.load(Ordering::SeqCst), 2);
+        });
+    }
+}
@@ -18,8 +18,12 @@
 // DEALINGS IN THE SOFTWARE.
 
 //! `PyBuffer` implementation
-use crate::{err, exceptions::PyBufferError, ffi, FromPyObject, PyAny, PyResult, Python};
-use crate::{Bound, PyNativeType};
+use crate::exceptions::{PyBufferError, PyIndexError, PyValueError};
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::sync::GILOnceCell;
+use crate::types::{any::PyAnyMethods, PyType};
+use crate::{err, ffi, intern, FromPyObject, IntoPy, Py, PyAny, PyObject, PyResult, Python};
+use crate::{Bound, PyNativeType, ToPyObject};
 use std::marker::PhantomData;
 use std::os::raw;
 use std::pin::Pin;
@@ -670,6 +674,240 @@ impl<T: Element> ReadOnlyCell<T> {
     }
 }
 
+/// A typed, n-dimensional view of an object exporting the buffer protocol.
+///
+/// This is a lightweight alternative to the numpy bindings for simple numeric interop: it
+/// accepts `numpy` arrays, `array.array`, `memoryview` and any other buffer exporter whose
+/// element type matches `T`, and gives checked access to the elements by index.
+///
+/// ```rust
+/// # use pyo3::prelude::*;
+/// use pyo3::buffer::PyArrayView;
+///
+/// # fn main() -> PyResult<()> {
+/// Python::with_gil(|py| {
+///     let matrix = py.eval_bound(
+///         "memoryview(bytes(range(6))).cast('B', (2, 3))",
+///         None,
+///         None,
+///     )?;
+///     let view: PyArrayView<u8> = matrix.extract()?;
+///     assert_eq!(view.shape(), [2, 3]);
+///     assert_eq!(view.get(py, &[1, 2]), Some(5));
+///     assert_eq!(view.get(py, &[2, 0]), None);
+///     Ok(())
+/// })
+/// # }
+/// ```
+///
+/// With the `ndarray` feature, the view can be converted to an `ndarray` array with
+/// [`to_array`](PyArrayView::to_array) or [`as_array`](PyArrayView::as_array).
+#[derive(Debug)]
+pub struct PyArrayView<T>(PyBuffer<T>);
+
+impl<'py, T: Element> FromPyObject<'py> for PyArrayView<T> {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Self::from_buffer(PyBuffer::get_bound(obj)?)
+    }
+}
+
+impl<T: Element> PyArrayView<T> {
+    /// Creates a view of the buffer exported by `obj`.
+    ///
+    /// Fails if the buffer elements are not compatible with `T`, or if the buffer is made of
+    /// pointers to sub-arrays (it has suboffsets), as for example PIL-style images.
+    pub fn new_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Self::from_buffer(PyBuffer::get_bound(obj)?)
+    }
+
+    /// Creates a view of an existing buffer.
+    ///
+    /// Fails if the buffer has suboffsets.
+    pub fn from_buffer(buffer: PyBuffer<T>) -> PyResult<Self> {
+        if buffer
+            .suboffsets()
+            .map_or(false, |suboffsets| suboffsets.iter().any(|&s| s >= 0))
+        {
+            return Err(PyBufferError::new_err(
+                "buffers with suboffsets are not supported",
+            ));
+        }
+        Ok(PyArrayView(buffer))
+    }
+
+    /// Gets the number of dimensions, which is 0 for a single scalar value.
+    #[inline]
+    pub fn ndim(&self) -> usize {
+        self.0.dimensions()
+    }
+
+    /// Gets the length of the view in each dimension.
+    #[inline]
+    pub fn shape(&self) -> &[usize] {
+        self.0.shape()
+    }
+
+    /// Gets the number of bytes between consecutive elements in each dimension.
+    ///
+    /// Strides may be zero or negative.
+    #[inline]
+    pub fn strides(&self) -> &[isize] {
+        self.0.strides()
+    }
+
+    /// Gets the total number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shape().iter().product()
+    }
+
+    /// Returns `true` if the view has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets whether the underlying buffer is read-only.
+    #[inline]
+    pub fn readonly(&self) -> bool {
+        self.0.readonly()
+    }
+
+    /// Fails with a `ValueError` unless the view has the given shape.
+    ///
+    /// `None` accepts any length in that dimension.
+    pub fn check_shape(&self, expected: &[Option<usize>]) -> PyResult<()> {
+        let matches = self.ndim() == expected.len()
+            && self
+                .shape()
+                .iter()
+                .zip(expected)
+                .all(|(&actual, expected)| expected.map_or(true, |e| e == actual));
+        if matches {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err(format!(
+                "expected an array of shape {:?}, got shape {:?}",
+                expected,
+                self.shape()
+            )))
+        }
+    }
+
+    /// Returns the address of the element at `index`, or `None` if it is out of bounds.
+    fn element_ptr(&self, index: &[usize]) -> Option<*mut T> {
+        if index.len() != self.ndim() {
+            return None;
+        }
+        let mut offset = 0isize;
+        for ((&i, &len), &stride) in index.iter().zip(self.shape()).zip(self.strides()) {
+            if i >= len {
+                return None;
+            }
+            offset += i as isize * stride;
+        }
+        // Safety: the index is in bounds, so the offset stays within the buffer.
+        Some(unsafe { (self.0.buf_ptr() as *mut u8).offset(offset) as *mut T })
+    }
+
+    /// Gets a copy of the element at `index`.
+    ///
+    /// Returns `None` if the number of indices does not match the number of dimensions, or if
+    /// any index is out of bounds.
+    pub fn get(&self, _py: Python<'_>, index: &[usize]) -> Option<T> {
+        self.element_ptr(index)
+            // Safety: the pointer is in bounds, and the GIL protects the buffer contents.
+            // Strides need not be multiples of the alignment of `T`.
+            .map(|ptr| unsafe { ptr::read_unaligned(ptr) })
+    }
+
+    /// Sets the element at `index` to `value`.
+    ///
+    /// Fails if the buffer is read-only or the index is not valid for the view.
+    pub fn set(&self, _py: Python<'_>, index: &[usize], value: T) -> PyResult<()> {
+        if self.readonly() {
+            return Err(PyBufferError::new_err("cannot write to a read-only buffer"));
+        }
+        let ptr = self.element_ptr(index).ok_or_else(|| {
+            PyIndexError::new_err(format!(
+                "index {:?} is out of bounds for an array of shape {:?}",
+                index,
+                self.shape()
+            ))
+        })?;
+        // Safety: as for `get`, and the buffer is writable.
+        unsafe { ptr::write_unaligned(ptr, value) };
+        Ok(())
+    }
+
+    /// Copies the elements to a newly allocated vector, in C-style order.
+    pub fn to_vec(&self, py: Python<'_>) -> PyResult<Vec<T>> {
+        self.0.to_vec(py)
+    }
+
+    /// Gets the underlying buffer.
+    #[inline]
+    pub fn as_buffer(&self) -> &PyBuffer<T> {
+        &self.0
+    }
+
+    /// Unwraps the underlying buffer.
+    #[inline]
+    pub fn into_buffer(self) -> PyBuffer<T> {
+        self.0
+    }
+
+    /// Copies the elements into a new `ndarray` array with the same shape.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array(&self, py: Python<'_>) -> PyResult<ndarray::ArrayD<T>> {
+        ndarray::ArrayD::from_shape_vec(self.shape(), self.to_vec(py)?)
+            .map_err(|err| PyBufferError::new_err(err.to_string()))
+    }
+
+    /// Borrows the elements as an `ndarray` array view, without copying.
+    ///
+    /// Fails if the strides are not multiples of the element size.
+    ///
+    /// # Safety
+    ///
+    /// The buffer contents must not be modified while the returned view exists, neither from
+    /// Rust (e.g. with [`set`](PyArrayView::set) or [`PyBuffer::as_mut_slice`]) nor from Python.
+    #[cfg(feature = "ndarray")]
+    pub unsafe fn as_array<'a>(&'a self, _py: Python<'a>) -> PyResult<ndarray::ArrayViewD<'a, T>> {
+        use ndarray::{Axis, IxDyn, ShapeBuilder};
+
+        let item_size = mem::size_of::<T>() as isize;
+        if self.strides().iter().any(|stride| stride % item_size != 0) {
+            return Err(PyBufferError::new_err(
+                "strides are not multiples of the element size",
+            ));
+        }
+        // ndarray views start from their lowest address, so begin at the last element of each
+        // axis with a negative stride and flip those axes afterwards.
+        let mut ptr = self.0.buf_ptr() as *const T;
+        let mut strides = Vec::with_capacity(self.ndim());
+        if !self.is_empty() {
+            for (&len, &stride) in self.shape().iter().zip(self.strides()) {
+                let stride = stride / item_size;
+                if stride < 0 {
+                    ptr = ptr.offset((len as isize - 1) * stride);
+                }
+                strides.push(stride.unsigned_abs());
+            }
+        } else {
+            strides.resize(self.ndim(), 0);
+        }
+        let mut view =
+            ndarray::ArrayViewD::from_shape_ptr(IxDyn(self.shape()).strides(IxDyn(&strides)), ptr);
+        for (axis, &stride) in self.strides().iter().enumerate() {
+            if stride < 0 {
+                view.invert_axis(Axis(axis));
+            }
+        }
+        Ok(view)
+    }
+}
+
 macro_rules! impl_element(
     ($t:ty, $f:ident) => {
         unsafe impl Element for $t {
@@ -697,6 +935,138 @@ impl_element!(isize, SignedInteger);
 impl_element!(f32, Float);
 impl_element!(f64, Float);
 
+/// Element types which can be stored in a Python `array.array`.
+///
+/// # Safety
+///
+/// `TYPECODE` must be an `array.array` type code whose items have the same size and
+/// representation as the implementing type.
+pub unsafe trait ArrayElement: Element {
+    /// The `array.array` type code, such as `"d"` for `f64`.
+    const TYPECODE: &'static str;
+}
+
+macro_rules! impl_array_element(
+    ($($t:ty => $typecode:literal),* $(,)?) => {
+        $(
+            unsafe impl ArrayElement for $t {
+                const TYPECODE: &'static str = $typecode;
+            }
+        )*
+    }
+);
+
+impl_array_element!(
+    u8 => "B",
+    u16 => "H",
+    u32 => "I",
+    u64 => "Q",
+    i8 => "b",
+    i16 => "h",
+    i32 => "i",
+    i64 => "q",
+    f32 => "f",
+    f64 => "d",
+);
+
+#[cfg(target_pointer_width = "64")]
+impl_array_element!(usize => "Q", isize => "q");
+#[cfg(target_pointer_width = "32")]
+impl_array_element!(usize => "I", isize => "i");
+
+/// A vector of numbers which converts to a Python `array.array`.
+///
+/// Converting a `Vec<f64>` to Python creates a `list` with a separate `float` object for
+/// every element. Returning a `PyArrayVec` instead copies the elements into an `array.array`
+/// with a single `memcpy`, which is much faster and more compact for large amounts of numbers.
+/// The array supports the buffer protocol, so it can also be viewed by `numpy` without
+/// copying.
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::buffer::PyArrayVec;
+///
+/// #[pyfunction]
+/// fn squares(n: usize) -> PyArrayVec<f64> {
+///     (0..n).map(|i| (i * i) as f64).collect::<Vec<_>>().into()
+/// }
+/// #
+/// # Python::with_gil(|py| {
+/// #     let squares = wrap_pyfunction!(squares, py).unwrap();
+/// #     pyo3::py_run!(py, squares, r#"
+/// #         import array
+/// #         assert squares(4) == array.array("d", [0.0, 1.0, 4.0, 9.0])
+/// #     "#);
+/// # });
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PyArrayVec<T>(pub Vec<T>);
+
+impl<T: ArrayElement> PyArrayVec<T> {
+    /// Creates a new `array.array` holding a copy of the elements.
+    pub fn to_array_bound<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let array = get_array_type(py)?.call1((T::TYPECODE,))?;
+        if !self.0.is_empty() {
+            let len = mem::size_of_val(self.0.as_slice());
+            // Safety: the memoryview is only used by `frombytes` below, while `self` is borrowed,
+            // and is read-only.
+            let view = unsafe {
+                ffi::PyMemoryView_FromMemory(
+                    self.0.as_ptr() as *mut raw::c_char,
+                    len as ffi::Py_ssize_t,
+                    ffi::PyBUF_READ,
+                )
+                .assume_owned_or_err(py)?
+            };
+            array.call_method1(intern!(py, "frombytes"), (view,))?;
+        }
+        Ok(array)
+    }
+}
+
+impl<T> From<Vec<T>> for PyArrayVec<T> {
+    #[inline]
+    fn from(vec: Vec<T>) -> Self {
+        PyArrayVec(vec)
+    }
+}
+
+impl<T> From<PyArrayVec<T>> for Vec<T> {
+    #[inline]
+    fn from(vec: PyArrayVec<T>) -> Self {
+        vec.0
+    }
+}
+
+impl<T: ArrayElement> ToPyObject for PyArrayVec<T> {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.to_array_bound(py)
+            .expect("failed to create array.array")
+            .unbind()
+    }
+}
+
+impl<T: ArrayElement> IntoPy<PyObject> for PyArrayVec<T> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!([T: ArrayElement] PyArrayVec<T>);
+
+impl<'py, T: ArrayElement> FromPyObject<'py> for PyArrayVec<T> {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        PyBuffer::<T>::get_bound(obj)?
+            .to_vec(obj.py())
+            .map(PyArrayVec)
+    }
+}
+
+fn get_array_type(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
+    static ARRAY_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    ARRAY_TYPE.get_or_try_init_type_ref(py, "array", "array")
+}
+
 #[cfg(test)]
 mod tests {
     use super::PyBuffer;
@@ -956,4 +1326,122 @@ mod tests {
             assert_eq!(buffer.to_fortran_vec(py).unwrap(), [10.0, 11.0, 12.0, 13.0]);
         });
     }
+
+    #[test]
+    fn test_array_view_two_dimensional() {
+        use super::PyArrayView;
+
+        Python::with_gil(|py| {
+            let matrix = py
+                .eval_bound(
+                    "memoryview(bytearray(range(6))).cast('B', (2, 3))",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let view: PyArrayView<u8> = matrix.extract().unwrap();
+            assert_eq!(view.ndim(), 2);
+            assert_eq!(view.shape(), [2, 3]);
+            assert_eq!(view.strides(), [3, 1]);
+            assert_eq!(view.len(), 6);
+            assert!(!view.readonly());
+
+            assert_eq!(view.get(py, &[0, 0]), Some(0));
+            assert_eq!(view.get(py, &[1, 2]), Some(5));
+            assert_eq!(view.get(py, &[2, 0]), None);
+            assert_eq!(view.get(py, &[1]), None);
+
+            view.set(py, &[1, 0], 42).unwrap();
+            assert_eq!(view.to_vec(py).unwrap(), [0, 1, 2, 42, 4, 5]);
+            assert!(view.set(py, &[0, 3], 0).is_err());
+
+            view.check_shape(&[Some(2), None]).unwrap();
+            assert_eq!(
+                view.check_shape(&[Some(3)]).unwrap_err().to_string(),
+                "ValueError: expected an array of shape [Some(3)], got shape [2, 3]"
+            );
+        });
+    }
+
+    #[test]
+    fn test_array_vec() {
+        use super::PyArrayVec;
+        use crate::ToPyObject;
+
+        Python::with_gil(|py| {
+            let vec = PyArrayVec(vec![1.5f64, -2.0, 3.25]);
+            let array = vec.to_object(py).into_bound(py);
+            assert_eq!(array.getattr("typecode").unwrap().to_string(), "d");
+            assert_eq!(array.len().unwrap(), 3);
+            assert_eq!(array.extract::<Vec<f64>>().unwrap(), vec.0);
+            assert_eq!(array.extract::<PyArrayVec<f64>>().unwrap(), vec);
+
+            let empty = PyArrayVec(Vec::<i32>::new()).to_object(py).into_bound(py);
+            assert_eq!(empty.getattr("typecode").unwrap().to_string(), "i");
+            assert_eq!(empty.len().unwrap(), 0);
+
+            let numbers = PyArrayVec((0..1000u64).collect::<Vec<_>>()).to_object(py);
+            let numbers: Vec<u64> = numbers.extract(py).unwrap();
+            assert_eq!(numbers, (0..1000).collect::<Vec<_>>());
+        });
+    }
+
+    #[test]
+    fn test_array_view_strides() {
+        use super::PyArrayView;
+
+        Python::with_gil(|py| {
+            let reversed = py
+                .eval_bound(
+                    "memoryview(__import__('array').array('i', range(6)))[::-2]",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let view = PyArrayView::<i32>::new_bound(&reversed).unwrap();
+            assert_eq!(view.shape(), [3]);
+            assert_eq!(view.strides(), [-8]);
+            assert_eq!(view.get(py, &[0]), Some(5));
+            assert_eq!(view.get(py, &[2]), Some(1));
+            assert_eq!(view.to_vec(py).unwrap(), [5, 3, 1]);
+
+            // read-only, and not compatible with other element types
+            let bytes = py.eval_bound("b'abc'", None, None).unwrap();
+            let view = PyArrayView::<u8>::new_bound(&bytes).unwrap();
+            assert!(view.set(py, &[0], 0).is_err());
+            assert!(PyArrayView::<f64>::new_bound(&bytes).is_err());
+        });
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_array_view_ndarray() {
+        use super::PyArrayView;
+
+        Python::with_gil(|py| {
+            let matrix = py
+                .eval_bound(
+                    "memoryview(__import__('array').array('d', range(6))).cast('B').cast('d', (2, 3))",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let view: PyArrayView<f64> = matrix.extract().unwrap();
+            let expected = ndarray::array![[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]].into_dyn();
+            assert_eq!(view.to_array(py).unwrap(), expected);
+            assert_eq!(unsafe { view.as_array(py) }.unwrap(), expected);
+
+            let reversed = py
+                .eval_bound(
+                    "memoryview(__import__('array').array('d', range(6)))[::-2]",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let view: PyArrayView<f64> = reversed.extract().unwrap();
+            let expected = ndarray::array![5.0, 3.0, 1.0].into_dyn();
+            assert_eq!(view.to_array(py).unwrap(), expected);
+            assert_eq!(unsafe { view.as_array(py) }.unwrap(), expected);
+        });
+    }
 }
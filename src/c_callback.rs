@@ -0,0 +1,240 @@
+//! Calling into Python from callbacks invoked by C libraries.
+//!
+//! Event loops (such as libuv or GLib), GUI toolkits and audio libraries call back into the
+//! application from their own threads, without the GIL held, through `extern "C"` functions
+//! which must not unwind. [`call`] runs a Rust closure in such a callback: it acquires the GIL with
+//! `PyGILState_Ensure`, catches panics, and handles errors according to an [`ErrorPolicy`], since
+//! there is no Python caller to raise them to.
+//!
+//! Most C APIs take a function pointer and a `void *` user data pointer which is passed back to
+//! the function. [`RawCallback`] packages a Rust closure as such a pair:
+//!
+//! ```rust
+//! use pyo3::c_callback::{ErrorPolicy, RawCallback};
+//! use pyo3::prelude::*;
+//! use std::os::raw::{c_int, c_void};
+//!
+//! // Stands in for a C function which registers a callback, e.g. for an event loop.
+//! unsafe fn register_handler(
+//!     function: unsafe extern "C" fn(*mut c_void) -> c_int,
+//!     data: *mut c_void,
+//! ) -> c_int {
+//!     function(data)
+//! }
+//!
+//! # fn main() -> PyResult<()> {
+//! Python::with_gil(|py| {
+//!     let handler: PyObject = py.eval_bound("lambda: 42", None, None)?.unbind();
+//!     let callback = RawCallback::new(ErrorPolicy::Unraisable(-1), move |py| {
+//!         handler.call0(py)?.extract::<c_int>(py)
+//!     });
+//!     let result = unsafe { register_handler(callback.function, callback.data) };
+//!     assert_eq!(result, 42);
+//!     // once the C library no longer uses the callback
+//!     unsafe { (callback.destroy)(callback.data) };
+//!     Ok(())
+//! })
+//! # }
+//! ```
+
+use crate::impl_::panic::PanicTrap;
+use crate::panic::PanicException;
+use crate::{ffi, PyErr, PyResult, Python};
+use std::os::raw::c_void;
+use std::panic::{self, AssertUnwindSafe};
+
+/// How [`call`] handles an error returned by, or a panic in, the callback.
+#[derive(Clone)]
+pub enum ErrorPolicy<R> {
+    /// Reports the error with `sys.unraisablehook`, like an exception raised in `__del__`, and
+    /// returns the given value to the C library.
+    Unraisable(R),
+    /// Prints the error and its traceback to `sys.stderr`, and returns the given value to the C
+    /// library.
+    Print(R),
+    /// Passes the error to a function, which returns the value for the C library.
+    Handle(fn(Python<'_>, PyErr) -> R),
+    /// Prints the error and aborts the process.
+    Abort,
+}
+
+impl<R: std::fmt::Debug> std::fmt::Debug for ErrorPolicy<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorPolicy::Unraisable(value) => f.debug_tuple("Unraisable").field(value).finish(),
+            ErrorPolicy::Print(value) => f.debug_tuple("Print").field(value).finish(),
+            ErrorPolicy::Handle(_) => f.write_str("Handle(..)"),
+            ErrorPolicy::Abort => f.write_str("Abort"),
+        }
+    }
+}
+
+impl<R: Clone> ErrorPolicy<R> {
+    fn handle(&self, py: Python<'_>, err: PyErr) -> R {
+        match self {
+            ErrorPolicy::Unraisable(value) => {
+                err.write_unraisable_bound(py, None);
+                value.clone()
+            }
+            ErrorPolicy::Print(value) => {
+                err.print(py);
+                value.clone()
+            }
+            ErrorPolicy::Handle(handler) => handler(py, err),
+            ErrorPolicy::Abort => {
+                err.print(py);
+                std::process::abort()
+            }
+        }
+    }
+
+    fn without_interpreter(&self) -> R {
+        match self {
+            ErrorPolicy::Unraisable(value) | ErrorPolicy::Print(value) => value.clone(),
+            ErrorPolicy::Handle(_) | ErrorPolicy::Abort => {
+                eprintln!(
+                    "PyO3: C callback invoked while the Python interpreter is not initialized"
+                );
+                std::process::abort()
+            }
+        }
+    }
+}
+
+/// Runs `f` with the GIL held, for use inside an `extern "C"` callback.
+///
+/// The GIL is acquired with `PyGILState_Ensure`, so this works from any thread, whether or not it
+/// already holds the GIL. A panic in `f` is converted to a [`PanicException`]; it and any error
+/// returned by `f` are then handled according to `policy`, so that no unwinding crosses the FFI
+/// boundary.
+///
+/// If the interpreter is not initialized, for example because the C library calls back after
+/// Python was finalized, `f` is not run: the value of [`ErrorPolicy::Unraisable`] or
+/// [`ErrorPolicy::Print`] is returned, and the process aborts for the other policies.
+pub fn call<R, F>(policy: &ErrorPolicy<R>, f: F) -> R
+where
+    R: Clone,
+    F: for<'py> FnOnce(Python<'py>) -> PyResult<R>,
+{
+    let trap = PanicTrap::new("uncaught panic in C callback");
+    let out = if unsafe { ffi::Py_IsInitialized() } == 0 {
+        policy.without_interpreter()
+    } else {
+        Python::with_gil(|py| {
+            panic::catch_unwind(AssertUnwindSafe(move || f(py)))
+                .unwrap_or_else(|payload| Err(PanicException::from_panic_payload(py, payload)))
+                .unwrap_or_else(|err| policy.handle(py, err))
+        })
+    };
+    trap.disarm();
+    out
+}
+
+/// A Rust closure packaged as a C function pointer and user data pointer.
+///
+/// The C library calls `function(data)` to run the closure through [`call`]. When the C library
+/// no longer uses the callback, `destroy(data)` must be called exactly once to free the closure;
+/// many C APIs accept such a "destroy notify" function alongside the callback.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct RawCallback<R> {
+    /// The function to pass to the C library.
+    pub function: unsafe extern "C" fn(*mut c_void) -> R,
+    /// The user data pointer to pass to the C library.
+    pub data: *mut c_void,
+    /// Frees the closure.
+    pub destroy: unsafe extern "C" fn(*mut c_void),
+}
+
+struct Callback<R, F> {
+    policy: ErrorPolicy<R>,
+    f: F,
+}
+
+impl<R> RawCallback<R>
+where
+    R: Clone + Send + 'static,
+{
+    /// Packages `f`, whose errors are handled according to `policy`.
+    ///
+    /// The closure must be `Send` and `Sync`, because C libraries may call it from any thread,
+    /// including from several threads at once.
+    pub fn new<F>(policy: ErrorPolicy<R>, f: F) -> Self
+    where
+        F: for<'py> Fn(Python<'py>) -> PyResult<R> + Send + Sync + 'static,
+    {
+        let callback = Box::new(Callback { policy, f });
+        RawCallback {
+            function: trampoline::<R, F>,
+            data: Box::into_raw(callback).cast(),
+            destroy: destroy::<R, F>,
+        }
+    }
+}
+
+unsafe extern "C" fn trampoline<R, F>(data: *mut c_void) -> R
+where
+    R: Clone,
+    F: for<'py> Fn(Python<'py>) -> PyResult<R>,
+{
+    let callback = &*data.cast::<Callback<R, F>>();
+    call(&callback.policy, |py| (callback.f)(py))
+}
+
+unsafe extern "C" fn destroy<R, F>(data: *mut c_void) {
+    let trap = PanicTrap::new("uncaught panic while dropping C callback");
+    drop(Box::from_raw(data.cast::<Callback<R, F>>()));
+    trap.disarm();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{call, ErrorPolicy, RawCallback};
+    use crate::exceptions::PyValueError;
+    use crate::panic::PanicException;
+    use crate::{PyErr, Python};
+    use std::os::raw::c_int;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn error_code(py: Python<'_>, err: PyErr) -> c_int {
+        if err.is_instance_of::<PanicException>(py) {
+            -2
+        } else {
+            -1
+        }
+    }
+
+    #[test]
+    fn test_call() {
+        let policy = ErrorPolicy::Handle(error_code);
+        Python::with_gil(|_py| {
+            assert_eq!(call(&policy, |_py| Ok(1)), 1);
+            assert_eq!(call(&policy, |_py| Err(PyValueError::new_err("error"))), -1);
+            assert_eq!(call(&policy, |_py| panic!("panic")), -2);
+        });
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))] // requires thread support
+    fn test_raw_callback() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let callback = RawCallback::new(ErrorPolicy::Unraisable(-1), move |_py| {
+            Ok(counter.fetch_add(1, Ordering::SeqCst) as c_int)
+        });
+
+        let (function, data) = (callback.function, callback.data as usize);
+        // called from a thread which does not hold the GIL
+        let result = std::thread::spawn(move || unsafe { function(data as *mut _) })
+            .join()
+            .unwrap();
+        assert_eq!(result, 0);
+        assert_eq!(unsafe { (callback.function)(callback.data) }, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        unsafe { (callback.destroy)(callback.data) };
+        assert_eq!(Arc::strong_count(&calls), 1);
+    }
+}
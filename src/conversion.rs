@@ -2,11 +2,15 @@
 use crate::err::{self, PyDowncastError, PyResult};
 #[cfg(feature = "experimental-inspect")]
 use crate::inspect::types::TypeInfo;
+use crate::pycell::PyCellLayout;
 use crate::pyclass::boolean_struct::False;
 use crate::type_object::PyTypeInfo;
-use crate::types::PyTuple;
+use crate::types::{
+    any::PyAnyMethods, dict::PyDictMethods, tuple::PyTupleMethods, PyDict, PyModule, PyTuple,
+};
 use crate::{
-    ffi, gil, Bound, Py, PyAny, PyCell, PyClass, PyNativeType, PyObject, PyRef, PyRefMut, Python,
+    ffi, gil, Borrowed, Bound, Py, PyAny, PyCell, PyClass, PyNativeType, PyObject, PyRef, PyRefMut,
+    Python,
 };
 use std::cell::Cell;
 use std::ptr::NonNull;
@@ -177,6 +181,62 @@ pub trait IntoPy<T>: Sized {
     }
 }
 
+/// Defines a fallible conversion from a Rust type to a Python object.
+///
+/// Unlike [`IntoPy`] and [`ToPyObject`], which must always succeed, this trait returns a
+/// [`PyResult`], so conversions which can fail (for example values out of range of the target
+/// Python type) raise an exception instead of panicking.
+///
+/// The values returned from `#[pyfunction]`s, `#[pymethods]` and `#[pyo3(get)]` fields are
+/// converted with this trait when their type implements it, and with [`IntoPy<PyObject>`]
+/// otherwise. PyO3 implements it for all the types it can convert with `IntoPy<PyObject>`, and
+/// `#[pyclass]` implements it for the class. Containers such as `Vec<T>` implement it whenever
+/// their elements do.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::exceptions::PyValueError;
+///
+/// /// A percentage, which is only valid between 0 and 100.
+/// struct Percentage(u32);
+///
+/// impl<'py> IntoPyObject<'py> for Percentage {
+///     fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+///         if self.0 > 100 {
+///             return Err(PyValueError::new_err(format!("{}% is not a valid percentage", self.0)));
+///         }
+///         Ok(self.0.into_py(py).into_bound(py))
+///     }
+/// }
+///
+/// #[pyfunction]
+/// fn ratio_to_percentage(ratio: f64) -> Percentage {
+///     Percentage((ratio * 100.0) as u32)
+/// }
+///
+/// # fn main() {
+/// Python::with_gil(|py| {
+///     let f = wrap_pyfunction!(ratio_to_percentage, py).unwrap();
+///     assert_eq!(f.call1((0.5,)).unwrap().extract::<u32>().unwrap(), 50);
+///     assert!(f.call1((2.0,)).unwrap_err().is_instance_of::<PyValueError>(py));
+/// });
+/// # }
+/// ```
+pub trait IntoPyObject<'py>: Sized {
+    /// Performs the conversion.
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>>;
+
+    /// Extracts the type hint information for this type when it appears as a return value.
+    ///
+    /// See [`IntoPy::type_output`].
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::Any
+    }
+}
+
 /// Extract a type from a Python object.
 ///
 ///
@@ -249,6 +309,315 @@ pub trait FromPyObject<'py>: Sized {
     }
 }
 
+/// Provide a value for a `#[pyfunction]` or `#[pymethods]` argument which is not part of the
+/// Python signature.
+///
+/// Arguments marked with `#[pyo3(inject)]` are not passed from Python; instead the generated
+/// wrapper calls [`InjectedArgument::inject`] on every call, with the [`CallContext`] of the
+/// call. This allows frameworks to hand their own context objects to every wrapped function.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::conversion::{CallContext, InjectedArgument};
+///
+/// struct RequestContext {
+///     interpreter_version: String,
+///     num_args: usize,
+/// }
+///
+/// impl<'py> InjectedArgument<'py> for RequestContext {
+///     fn inject(context: &CallContext<'_, 'py>) -> PyResult<Self> {
+///         Ok(RequestContext {
+///             interpreter_version: context.py().version().to_owned(),
+///             num_args: context.args().len(),
+///         })
+///     }
+/// }
+///
+/// #[pyfunction]
+/// #[pyo3(signature = (*_args))]
+/// fn version(_args: &PyAny, #[pyo3(inject)] ctx: RequestContext) -> (String, usize) {
+///     (ctx.interpreter_version, ctx.num_args)
+/// }
+///
+/// # Python::with_gil(|py| {
+/// #     let f = pyo3::wrap_pyfunction!(version, py).unwrap();
+/// #     let (version, num_args): (String, usize) = f.call1((1, 2)).unwrap().extract().unwrap();
+/// #     assert_eq!(version, py.version());
+/// #     assert_eq!(num_args, 2);
+/// # });
+/// ```
+pub trait InjectedArgument<'py>: Sized {
+    /// Produces the value for the injected argument.
+    fn inject(context: &CallContext<'_, 'py>) -> PyResult<Self>;
+}
+
+/// The call of a `#[pyfunction]` or `#[pymethods]` function, passed to
+/// [`InjectedArgument::inject`].
+pub struct CallContext<'a, 'py> {
+    py: Python<'py>,
+    slf: Option<Borrowed<'a, 'py, PyAny>>,
+    arguments: CallArguments<'a, 'py>,
+}
+
+enum CallArguments<'a, 'py> {
+    /// Special methods implemented as type slots, and functions without arguments.
+    None,
+    /// The vectorcall convention: the positional arguments followed by the values of the keyword
+    /// arguments named in `kwnames`.
+    Vector {
+        args: &'a [*mut ffi::PyObject],
+        nargs: usize,
+        kwnames: Option<Borrowed<'a, 'py, PyTuple>>,
+    },
+    /// The `tp_call` convention.
+    Tuple {
+        args: Borrowed<'a, 'py, PyTuple>,
+        kwargs: Option<Borrowed<'a, 'py, PyDict>>,
+    },
+}
+
+impl<'a, 'py> CallContext<'a, 'py> {
+    #[doc(hidden)]
+    pub unsafe fn new(py: Python<'py>, slf: *mut ffi::PyObject) -> Self {
+        CallContext {
+            py,
+            slf: Borrowed::from_ptr_or_opt(py, slf),
+            arguments: CallArguments::None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn fastcall(
+        py: Python<'py>,
+        slf: *mut ffi::PyObject,
+        args: *const *mut ffi::PyObject,
+        nargs: ffi::Py_ssize_t,
+        kwnames: *mut ffi::PyObject,
+    ) -> Self {
+        let kwnames: Option<Borrowed<'a, 'py, PyTuple>> =
+            Borrowed::from_ptr_or_opt(py, kwnames).map(|kwnames| kwnames.downcast_unchecked());
+        let nargs = nargs as usize;
+        let len = nargs + kwnames.as_ref().map_or(0, |kwnames| kwnames.len());
+        let args = if args.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(args, len)
+        };
+        CallContext {
+            py,
+            slf: Borrowed::from_ptr_or_opt(py, slf),
+            arguments: CallArguments::Vector {
+                args,
+                nargs,
+                kwnames,
+            },
+        }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn varargs(
+        py: Python<'py>,
+        slf: *mut ffi::PyObject,
+        args: *mut ffi::PyObject,
+        kwargs: *mut ffi::PyObject,
+    ) -> Self {
+        CallContext {
+            py,
+            slf: Borrowed::from_ptr_or_opt(py, slf),
+            arguments: CallArguments::Tuple {
+                args: Borrowed::from_ptr(py, args).downcast_unchecked(),
+                kwargs: Borrowed::from_ptr_or_opt(py, kwargs)
+                    .map(|kwargs| kwargs.downcast_unchecked()),
+            },
+        }
+    }
+
+    /// Returns a GIL marker for the duration of the call.
+    #[inline]
+    pub fn py(&self) -> Python<'py> {
+        self.py
+    }
+
+    /// Returns the object the function was called on.
+    ///
+    /// This is the instance for instance methods and special methods, the class for
+    /// `#[classmethod]` and `#[new]`, the module for a `#[pyfunction]` added to a module, and
+    /// `None` for `#[staticmethod]` and a `#[pyfunction]` created without a module.
+    #[inline]
+    pub fn slf(&self) -> Option<&Bound<'py, PyAny>> {
+        self.slf.as_deref()
+    }
+
+    /// Returns the module of a `#[pyfunction]` which was added to a module.
+    pub fn module(&self) -> Option<&Bound<'py, PyModule>> {
+        self.slf()?.downcast::<PyModule>().ok()
+    }
+
+    /// Returns the positional arguments of the call.
+    ///
+    /// Special methods implemented as type slots, such as `__add__`, receive their arguments
+    /// directly rather than through a call, so this is empty for them.
+    pub fn args(&self) -> Bound<'py, PyTuple> {
+        match &self.arguments {
+            CallArguments::None => PyTuple::empty_bound(self.py),
+            CallArguments::Vector { args, nargs, .. } => PyTuple::new_bound(
+                self.py,
+                args[..*nargs]
+                    .iter()
+                    .map(|arg| unsafe { Borrowed::from_ptr(self.py, *arg) }.to_owned()),
+            ),
+            CallArguments::Tuple { args, .. } => (*args).to_owned(),
+        }
+    }
+
+    /// Returns the keyword arguments of the call, if any were passed.
+    pub fn kwargs(&self) -> PyResult<Option<Bound<'py, PyDict>>> {
+        match &self.arguments {
+            CallArguments::None => Ok(None),
+            CallArguments::Vector {
+                args,
+                nargs,
+                kwnames,
+            } => match kwnames {
+                Some(kwnames) => {
+                    let kwargs = PyDict::new_bound(self.py);
+                    for (name, value) in kwnames.iter().zip(&args[*nargs..]) {
+                        kwargs.set_item(name, unsafe { Borrowed::from_ptr(self.py, *value) })?;
+                    }
+                    Ok(Some(kwargs))
+                }
+                None => Ok(None),
+            },
+            CallArguments::Tuple { kwargs, .. } => Ok(kwargs.map(|kwargs| (*kwargs).to_owned())),
+        }
+    }
+}
+
+/// Wrapper which extracts a number or `bool` without Python's implicit numeric coercions.
+///
+/// By default, extracting an `f64` also accepts an `int` (or any object with a `__float__`
+/// method), and extracting a Rust integer also accepts a `bool` (or any object with an
+/// `__index__` method). Where such coercions would hide bugs in the caller, extract a
+/// `Strict<T>` instead:
+///
+/// - `Strict<f32>` and `Strict<f64>` only accept `float` objects;
+/// - `Strict` integers only accept `int` objects which are not `bool`s;
+/// - `Strict<bool>` only accepts `bool` objects.
+///
+/// Anything else fails with a `TypeError`.
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::conversion::Strict;
+///
+/// #[pyfunction]
+/// fn set_threshold(threshold: Strict<f64>) -> f64 {
+///     threshold.0
+/// }
+///
+/// # Python::with_gil(|py| {
+/// #     let f = wrap_pyfunction!(set_threshold, py).unwrap();
+/// #     pyo3::py_run!(py, f, r#"
+/// #         assert f(0.5) == 0.5
+/// #         try:
+/// #             f(1)
+/// #         except TypeError:
+/// #             pass
+/// #         else:
+/// #             assert False
+/// #     "#);
+/// # });
+/// ```
+///
+/// Converting a `Strict<T>` to Python is the same as converting `T`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Strict<T>(pub T);
+
+impl<T: ToPyObject> ToPyObject for Strict<T> {
+    #[inline]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.0.to_object(py)
+    }
+}
+
+impl<T: IntoPy<PyObject>> IntoPy<PyObject> for Strict<T> {
+    #[inline]
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.0.into_py(py)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+}
+
+into_pyobject_via_into_py!([T] Strict<T> where T: IntoPy<PyObject>);
+
+/// Wrapper which extracts the truth value of any Python object.
+///
+/// Extracting a `bool` requires a Python `bool` (or a `numpy.bool_`), so that passing for
+/// example a non-empty string where a flag is expected fails with a `TypeError`. Extracting
+/// `Truthy` instead accepts any object and applies Python's truth testing, like `if obj:` does.
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::conversion::Truthy;
+///
+/// #[pyfunction]
+/// fn is_set(value: Truthy) -> bool {
+///     value.0
+/// }
+///
+/// # Python::with_gil(|py| {
+/// #     let f = wrap_pyfunction!(is_set, py).unwrap();
+/// #     pyo3::py_run!(py, f, r#"
+/// #         assert f([1]) is True
+/// #         assert f("") is False
+/// #     "#);
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Truthy(pub bool);
+
+impl From<Truthy> for bool {
+    #[inline]
+    fn from(value: Truthy) -> Self {
+        value.0
+    }
+}
+
+impl ToPyObject for Truthy {
+    #[inline]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.0.to_object(py)
+    }
+}
+
+impl IntoPy<PyObject> for Truthy {
+    #[inline]
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.0.into_py(py)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        <bool as IntoPy<PyObject>>::type_output()
+    }
+}
+
+into_pyobject_via_into_py!(Truthy);
+
+impl<'py> InjectedArgument<'py> for Python<'py> {
+    #[inline]
+    fn inject(context: &CallContext<'_, 'py>) -> PyResult<Self> {
+        Ok(context.py())
+    }
+}
+
 /// Identity conversion: allows using existing `PyObject` instances where
 /// `T: ToPyObject` is expected.
 impl<T: ?Sized + ToPyObject> ToPyObject for &'_ T {
@@ -279,6 +648,18 @@ where
     }
 }
 
+impl<'py, T> IntoPyObject<'py> for Option<T>
+where
+    T: IntoPyObject<'py>,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Some(val) => val.into_pyobject(py),
+            None => Ok(py.None().into_bound(py)),
+        }
+    }
+}
+
 impl IntoPy<PyObject> for &'_ PyAny {
     #[inline]
     fn into_py(self, py: Python<'_>) -> PyObject {
@@ -286,6 +667,8 @@ impl IntoPy<PyObject> for &'_ PyAny {
     }
 }
 
+into_pyobject_via_into_py!(&'_ PyAny);
+
 impl<T> IntoPy<PyObject> for &'_ T
 where
     T: AsRef<PyAny>,
@@ -296,6 +679,8 @@ where
     }
 }
 
+into_pyobject_via_into_py!([T] &'_ T where T: AsRef<PyAny>);
+
 impl<T: Copy + ToPyObject> ToPyObject for Cell<T> {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         self.get().to_object(py)
@@ -308,6 +693,8 @@ impl<T: Copy + IntoPy<PyObject>> IntoPy<PyObject> for Cell<T> {
     }
 }
 
+into_pyobject_via_into_py!([T] Cell<T> where T: Copy + IntoPy<PyObject>);
+
 impl<'py, T: FromPyObject<'py>> FromPyObject<'py> for Cell<T> {
     fn extract(ob: &'py PyAny) -> PyResult<Self> {
         T::extract(ob).map(Cell::new)
@@ -339,6 +726,7 @@ where
 {
     fn extract(obj: &'py PyAny) -> PyResult<Self> {
         let cell: &PyCell<T> = obj.downcast()?;
+        cell.try_ensure_threadsafe()?;
         cell.try_borrow().map_err(Into::into)
     }
 }
@@ -349,6 +737,7 @@ where
 {
     fn extract(obj: &'py PyAny) -> PyResult<Self> {
         let cell: &PyCell<T> = obj.downcast()?;
+        cell.try_ensure_threadsafe()?;
         cell.try_borrow_mut().map_err(Into::into)
     }
 }
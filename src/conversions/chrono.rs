@@ -53,7 +53,8 @@ use crate::types::{
 #[cfg(Py_LIMITED_API)]
 use crate::{intern, DowncastError};
 use crate::{
-    Bound, FromPyObject, IntoPy, PyAny, PyErr, PyNativeType, PyObject, PyResult, Python, ToPyObject,
+    Bound, FromPyObject, IntoPy, IntoPyObject, PyAny, PyErr, PyNativeType, PyObject, PyResult,
+    Python, ToPyObject,
 };
 use chrono::offset::{FixedOffset, Utc};
 use chrono::{
@@ -62,10 +63,25 @@ use chrono::{
 
 impl ToPyObject for Duration {
     fn to_object(&self, py: Python<'_>) -> PyObject {
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to construct datetime.timedelta")
+            .unbind()
+    }
+}
+
+impl IntoPy<PyObject> for Duration {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Duration {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         // Total number of days
         let days = self.num_days();
         // Remainder of seconds
-        let secs_dur = *self - Duration::days(days);
+        let secs_dur = self - Duration::days(days);
         let secs = secs_dur.num_seconds();
         // Fractional part of the microseconds
         let micros = (secs_dur - Duration::seconds(secs_dur.num_seconds()))
@@ -77,7 +93,7 @@ impl ToPyObject for Duration {
         #[cfg(not(Py_LIMITED_API))]
         {
             // We do not need to check the days i64 to i32 cast from rust because
-            // python will panic with OverflowError.
+            // python will raise OverflowError.
             // We pass true as the `normalize` parameter since we'd need to do several checks here to
             // avoid that, and it shouldn't have a big performance impact.
             // The seconds and microseconds cast should never overflow since it's at most the number of seconds per day
@@ -88,25 +104,18 @@ impl ToPyObject for Duration {
                 micros.try_into().unwrap(),
                 true,
             )
-            .expect("failed to construct delta")
-            .into()
+            .map(Bound::into_any)
         }
         #[cfg(Py_LIMITED_API)]
         {
-            DatetimeTypes::get(py)
+            DatetimeTypes::try_get(py)?
                 .timedelta
-                .call1(py, (days, secs, micros))
-                .expect("failed to construct datetime.timedelta")
+                .bind(py)
+                .call1((days, secs, micros))
         }
     }
 }
 
-impl IntoPy<PyObject> for Duration {
-    fn into_py(self, py: Python<'_>) -> PyObject {
-        self.to_object(py)
-    }
-}
-
 impl FromPyObject<'_> for Duration {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Duration> {
         // Python size are much lower than rust size so we do not need bound checks.
@@ -141,29 +150,36 @@ impl FromPyObject<'_> for Duration {
 
 impl ToPyObject for NaiveDate {
     fn to_object(&self, py: Python<'_>) -> PyObject {
-        let DateArgs { year, month, day } = self.into();
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to construct datetime.date")
+            .unbind()
+    }
+}
+
+impl IntoPy<PyObject> for NaiveDate {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for NaiveDate {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let DateArgs { year, month, day } = (&self).into();
         #[cfg(not(Py_LIMITED_API))]
         {
-            PyDate::new_bound(py, year, month, day)
-                .expect("failed to construct date")
-                .into()
+            PyDate::new_bound(py, year, month, day).map(Bound::into_any)
         }
         #[cfg(Py_LIMITED_API)]
         {
-            DatetimeTypes::get(py)
+            DatetimeTypes::try_get(py)?
                 .date
-                .call1(py, (year, month, day))
-                .expect("failed to construct datetime.date")
+                .bind(py)
+                .call1((year, month, day))
         }
     }
 }
 
-impl IntoPy<PyObject> for NaiveDate {
-    fn into_py(self, py: Python<'_>) -> PyObject {
-        self.to_object(py)
-    }
-}
-
 impl FromPyObject<'_> for NaiveDate {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<NaiveDate> {
         #[cfg(not(Py_LIMITED_API))]
@@ -181,32 +197,39 @@ impl FromPyObject<'_> for NaiveDate {
 
 impl ToPyObject for NaiveTime {
     fn to_object(&self, py: Python<'_>) -> PyObject {
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to construct datetime.time")
+            .unbind()
+    }
+}
+
+impl IntoPy<PyObject> for NaiveTime {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for NaiveTime {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let TimeArgs {
             hour,
             min,
             sec,
             micro,
             truncated_leap_second,
-        } = self.into();
+        } = (&self).into();
         #[cfg(not(Py_LIMITED_API))]
-        let time =
-            PyTime::new_bound(py, hour, min, sec, micro, None).expect("Failed to construct time");
+        let time = PyTime::new_bound(py, hour, min, sec, micro, None)?.into_any();
         #[cfg(Py_LIMITED_API)]
-        let time = DatetimeTypes::get(py)
+        let time = DatetimeTypes::try_get(py)?
             .time
             .bind(py)
-            .call1((hour, min, sec, micro))
-            .expect("failed to construct datetime.time");
+            .call1((hour, min, sec, micro))?;
         if truncated_leap_second {
             warn_truncated_leap_second(&time);
         }
-        time.into()
-    }
-}
-
-impl IntoPy<PyObject> for NaiveTime {
-    fn into_py(self, py: Python<'_>) -> PyObject {
-        self.to_object(py)
+        Ok(time)
     }
 }
 
@@ -227,7 +250,10 @@ impl FromPyObject<'_> for NaiveTime {
 
 impl ToPyObject for NaiveDateTime {
     fn to_object(&self, py: Python<'_>) -> PyObject {
-        naive_datetime_to_py_datetime(py, self, None)
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to construct datetime.datetime")
+            .unbind()
     }
 }
 
@@ -237,6 +263,12 @@ impl IntoPy<PyObject> for NaiveDateTime {
     }
 }
 
+impl<'py> IntoPyObject<'py> for NaiveDateTime {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        naive_datetime_to_py_datetime(py, &self, None)
+    }
+}
+
 impl FromPyObject<'_> for NaiveDateTime {
     fn extract_bound(dt: &Bound<'_, PyAny>) -> PyResult<NaiveDateTime> {
         #[cfg(not(Py_LIMITED_API))]
@@ -262,11 +294,9 @@ impl FromPyObject<'_> for NaiveDateTime {
 
 impl<Tz: TimeZone> ToPyObject for DateTime<Tz> {
     fn to_object(&self, py: Python<'_>) -> PyObject {
-        // FIXME: convert to better timezone representation here than just convert to fixed offset
-        // See https://github.com/PyO3/pyo3/issues/3266
-        let tz = self.offset().fix().to_object(py);
-        let tz = tz.bind(py).downcast().unwrap();
-        naive_datetime_to_py_datetime(py, &self.naive_local(), Some(tz))
+        datetime_to_py_datetime(py, self)
+            .expect("failed to construct datetime.datetime")
+            .unbind()
     }
 }
 
@@ -276,6 +306,12 @@ impl<Tz: TimeZone> IntoPy<PyObject> for DateTime<Tz> {
     }
 }
 
+impl<'py, Tz: TimeZone> IntoPyObject<'py> for DateTime<Tz> {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        datetime_to_py_datetime(py, &self)
+    }
+}
+
 impl<Tz: TimeZone + for<'py> FromPyObject<'py>> FromPyObject<'_> for DateTime<Tz> {
     fn extract_bound(dt: &Bound<'_, PyAny>) -> PyResult<DateTime<Tz>> {
         #[cfg(not(Py_LIMITED_API))]
@@ -307,33 +343,36 @@ impl<Tz: TimeZone + for<'py> FromPyObject<'py>> FromPyObject<'_> for DateTime<Tz
 
 impl ToPyObject for FixedOffset {
     fn to_object(&self, py: Python<'_>) -> PyObject {
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to construct datetime.timezone")
+            .unbind()
+    }
+}
+
+impl IntoPy<PyObject> for FixedOffset {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for FixedOffset {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let seconds_offset = self.local_minus_utc();
 
         #[cfg(not(Py_LIMITED_API))]
         {
-            let td = PyDelta::new_bound(py, 0, seconds_offset, 0, true)
-                .expect("failed to construct timedelta");
-            timezone_from_offset(&td)
-                .expect("Failed to construct PyTimezone")
-                .into()
+            let td = PyDelta::new_bound(py, 0, seconds_offset, 0, true)?;
+            timezone_from_offset(&td).map(Bound::into_any)
         }
         #[cfg(Py_LIMITED_API)]
         {
-            let td = Duration::seconds(seconds_offset.into()).into_py(py);
-            DatetimeTypes::get(py)
-                .timezone
-                .call1(py, (td,))
-                .expect("failed to construct datetime.timezone")
+            let td = Duration::seconds(seconds_offset.into()).into_pyobject(py)?;
+            DatetimeTypes::try_get(py)?.timezone.bind(py).call1((td,))
         }
     }
 }
 
-impl IntoPy<PyObject> for FixedOffset {
-    fn into_py(self, py: Python<'_>) -> PyObject {
-        self.to_object(py)
-    }
-}
-
 impl FromPyObject<'_> for FixedOffset {
     /// Convert python tzinfo to rust [`FixedOffset`].
     ///
@@ -377,6 +416,19 @@ impl IntoPy<PyObject> for Utc {
     }
 }
 
+impl<'py> IntoPyObject<'py> for Utc {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        #[cfg(not(Py_LIMITED_API))]
+        {
+            Ok(timezone_utc_bound(py).into_any())
+        }
+        #[cfg(Py_LIMITED_API)]
+        {
+            Ok(DatetimeTypes::try_get(py)?.timezone_utc.bind(py).clone())
+        }
+    }
+}
+
 impl FromPyObject<'_> for Utc {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Utc> {
         let py_utc = timezone_utc_bound(ob.py());
@@ -428,12 +480,24 @@ impl From<&NaiveTime> for TimeArgs {
     }
 }
 
-fn naive_datetime_to_py_datetime(
-    py: Python<'_>,
+fn datetime_to_py_datetime<'py, Tz: TimeZone>(
+    py: Python<'py>,
+    datetime: &DateTime<Tz>,
+) -> PyResult<Bound<'py, PyAny>> {
+    // FIXME: convert to better timezone representation here than just convert to fixed offset
+    // See https://github.com/PyO3/pyo3/issues/3266
+    let tz = datetime.offset().fix().into_pyobject(py)?;
+    #[cfg(not(Py_LIMITED_API))]
+    let tz = tz.downcast_into::<PyTzInfo>()?;
+    naive_datetime_to_py_datetime(py, &datetime.naive_local(), Some(&tz))
+}
+
+fn naive_datetime_to_py_datetime<'py>(
+    py: Python<'py>,
     naive_datetime: &NaiveDateTime,
-    #[cfg(not(Py_LIMITED_API))] tzinfo: Option<&Bound<'_, PyTzInfo>>,
-    #[cfg(Py_LIMITED_API)] tzinfo: Option<&Bound<'_, PyAny>>,
-) -> PyObject {
+    #[cfg(not(Py_LIMITED_API))] tzinfo: Option<&Bound<'py, PyTzInfo>>,
+    #[cfg(Py_LIMITED_API)] tzinfo: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
     let DateArgs { year, month, day } = (&naive_datetime.date()).into();
     let TimeArgs {
         hour,
@@ -443,18 +507,17 @@ fn naive_datetime_to_py_datetime(
         truncated_leap_second,
     } = (&naive_datetime.time()).into();
     #[cfg(not(Py_LIMITED_API))]
-    let datetime = PyDateTime::new_bound(py, year, month, day, hour, min, sec, micro, tzinfo)
-        .expect("failed to construct datetime");
+    let datetime =
+        PyDateTime::new_bound(py, year, month, day, hour, min, sec, micro, tzinfo)?.into_any();
     #[cfg(Py_LIMITED_API)]
-    let datetime = DatetimeTypes::get(py)
+    let datetime = DatetimeTypes::try_get(py)?
         .datetime
         .bind(py)
-        .call1((year, month, day, hour, min, sec, micro, tzinfo))
-        .expect("failed to construct datetime.datetime");
+        .call1((year, month, day, hour, min, sec, micro, tzinfo))?;
     if truncated_leap_second {
         warn_truncated_leap_second(&datetime);
     }
-    datetime.into()
+    Ok(datetime)
 }
 
 fn warn_truncated_leap_second(obj: &Bound<'_, PyAny>) {
@@ -539,22 +602,24 @@ struct DatetimeTypes {
 #[cfg(Py_LIMITED_API)]
 impl DatetimeTypes {
     fn get(py: Python<'_>) -> &Self {
+        Self::try_get(py).expect("failed to load datetime module")
+    }
+
+    fn try_get(py: Python<'_>) -> PyResult<&Self> {
         static TYPES: GILOnceCell<DatetimeTypes> = GILOnceCell::new();
-        TYPES
-            .get_or_try_init(py, || {
-                let datetime = py.import_bound("datetime")?;
-                let timezone = datetime.getattr("timezone")?;
-                Ok::<_, PyErr>(Self {
-                    date: datetime.getattr("date")?.into(),
-                    datetime: datetime.getattr("datetime")?.into(),
-                    time: datetime.getattr("time")?.into(),
-                    timedelta: datetime.getattr("timedelta")?.into(),
-                    timezone_utc: timezone.getattr("utc")?.into(),
-                    timezone: timezone.into(),
-                    tzinfo: datetime.getattr("tzinfo")?.into(),
-                })
+        TYPES.get_or_try_init(py, || {
+            let datetime = py.import_bound("datetime")?;
+            let timezone = datetime.getattr("timezone")?;
+            Ok::<_, PyErr>(Self {
+                date: datetime.getattr("date")?.into(),
+                datetime: datetime.getattr("datetime")?.into(),
+                time: datetime.getattr("time")?.into(),
+                timedelta: datetime.getattr("timedelta")?.into(),
+                timezone_utc: timezone.getattr("utc")?.into(),
+                timezone: timezone.into(),
+                tzinfo: datetime.getattr("tzinfo")?.into(),
             })
-            .expect("failed to load datetime module")
+        })
     }
 }
 
@@ -710,6 +775,10 @@ mod tests {
         Python::with_gil(|py| {
             assert!(panic::catch_unwind(|| Duration::min_value().to_object(py)).is_err());
             assert!(panic::catch_unwind(|| Duration::max_value().to_object(py)).is_err());
+            assert!(Duration::max_value()
+                .into_pyobject(py)
+                .unwrap_err()
+                .is_instance_of::<crate::exceptions::PyOverflowError>(py));
         });
     }
 
@@ -797,6 +866,22 @@ mod tests {
         eq_ymd("max date", 9999, 12, 31);
     }
 
+    #[test]
+    fn test_pyo3_date_into_pyobject_out_of_range() {
+        Python::with_gil(|py| {
+            let date = NaiveDate::from_ymd_opt(10000, 1, 1).unwrap();
+            assert!(date
+                .into_pyobject(py)
+                .unwrap_err()
+                .is_instance_of::<PyValueError>(py));
+            let datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+            assert!(datetime
+                .into_pyobject(py)
+                .unwrap_err()
+                .is_instance_of::<PyValueError>(py));
+        });
+    }
+
     #[test]
     fn test_pyo3_date_frompyobject() {
         let eq_ymd = |name: &'static str, year, month, day| {
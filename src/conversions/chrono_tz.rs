@@ -60,6 +60,8 @@ impl IntoPy<PyObject> for Tz {
     }
 }
 
+into_pyobject_via_into_py!(Tz);
+
 impl FromPyObject<'_> for Tz {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Tz> {
         Tz::from_str(ob.getattr(intern!(ob.py(), "key"))?.extract()?)
@@ -46,8 +46,8 @@
 #[cfg(feature = "experimental-inspect")]
 use crate::inspect::types::TypeInfo;
 use crate::{
-    exceptions::PyTypeError, types::any::PyAnyMethods, Bound, FromPyObject, IntoPy, PyAny,
-    PyObject, PyResult, Python, ToPyObject,
+    exceptions::PyTypeError, types::any::PyAnyMethods, Bound, FromPyObject, IntoPy, IntoPyObject,
+    PyAny, PyObject, PyResult, Python, ToPyObject,
 };
 use either::Either;
 
@@ -66,6 +66,21 @@ where
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "either")))]
+impl<'py, L, R> IntoPyObject<'py> for Either<L, R>
+where
+    L: IntoPyObject<'py>,
+    R: IntoPyObject<'py>,
+{
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Either::Left(l) => l.into_pyobject(py),
+            Either::Right(r) => r.into_pyobject(py),
+        }
+    }
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "either")))]
 impl<L, R> ToPyObject for Either<L, R>
 where
@@ -20,9 +20,10 @@ use crate::{
     types::any::PyAnyMethods,
     types::dict::PyDictMethods,
     types::frozenset::PyFrozenSetMethods,
-    types::set::{new_from_iter, PySetMethods},
+    types::set::{new_from_iter, try_new_from_iter, PySetMethods},
     types::{IntoPyDict, PyDict, PyFrozenSet, PySet},
-    Bound, FromPyObject, IntoPy, PyAny, PyErr, PyObject, PyResult, Python, ToPyObject,
+    Bound, FromPyObject, IntoPy, IntoPyObject, PyAny, PyErr, PyObject, PyResult, Python,
+    ToPyObject,
 };
 use std::{cmp, hash};
 
@@ -51,6 +52,21 @@ where
     }
 }
 
+impl<'py, K, V, H> IntoPyObject<'py> for hashbrown::HashMap<K, V, H>
+where
+    K: hash::Hash + cmp::Eq + IntoPyObject<'py>,
+    V: IntoPyObject<'py>,
+    H: hash::BuildHasher,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let dict = PyDict::new_bound(py);
+        for (k, v) in self {
+            dict.set_item(k.into_pyobject(py)?, v.into_pyobject(py)?)?;
+        }
+        Ok(dict.into_any())
+    }
+}
+
 impl<'py, K, V, S> FromPyObject<'py> for hashbrown::HashMap<K, V, S>
 where
     K: FromPyObject<'py> + cmp::Eq + hash::Hash,
@@ -90,6 +106,17 @@ where
     }
 }
 
+impl<'py, K, S> IntoPyObject<'py> for hashbrown::HashSet<K, S>
+where
+    K: IntoPyObject<'py> + Eq + hash::Hash,
+    S: hash::BuildHasher + Default,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut iter = self.into_iter().map(|item| item.into_pyobject(py));
+        try_new_from_iter(py, &mut iter).map(Bound::into_any)
+    }
+}
+
 impl<'py, K, S> FromPyObject<'py> for hashbrown::HashSet<K, S>
 where
     K: FromPyObject<'py> + cmp::Eq + hash::Hash,
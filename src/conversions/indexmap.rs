@@ -90,7 +90,9 @@
 use crate::types::any::PyAnyMethods;
 use crate::types::dict::PyDictMethods;
 use crate::types::*;
-use crate::{Bound, FromPyObject, IntoPy, PyErr, PyObject, Python, ToPyObject};
+use crate::{
+    Bound, FromPyObject, IntoPy, IntoPyObject, PyErr, PyObject, PyResult, Python, ToPyObject,
+};
 use std::{cmp, hash};
 
 impl<K, V, H> ToPyObject for indexmap::IndexMap<K, V, H>
@@ -118,6 +120,21 @@ where
     }
 }
 
+impl<'py, K, V, H> IntoPyObject<'py> for indexmap::IndexMap<K, V, H>
+where
+    K: hash::Hash + cmp::Eq + IntoPyObject<'py>,
+    V: IntoPyObject<'py>,
+    H: hash::BuildHasher,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let dict = PyDict::new_bound(py);
+        for (k, v) in self {
+            dict.set_item(k.into_pyobject(py)?, v.into_pyobject(py)?)?;
+        }
+        Ok(dict.into_any())
+    }
+}
+
 impl<'py, K, V, S> FromPyObject<'py> for indexmap::IndexMap<K, V, S>
 where
     K: FromPyObject<'py> + cmp::Eq + hash::Hash,
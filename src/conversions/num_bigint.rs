@@ -47,13 +47,17 @@
 //! assert n + 1 == value
 //! ```
 
+#[cfg(not(Py_LIMITED_API))]
+use crate::ffi_ptr_ext::FfiPtrExt;
 #[cfg(Py_LIMITED_API)]
 use crate::types::bytes::PyBytesMethods;
+#[cfg(Py_LIMITED_API)]
+use crate::PyNativeType;
 use crate::{
     ffi,
     instance::Bound,
     types::{any::PyAnyMethods, *},
-    FromPyObject, IntoPy, Py, PyAny, PyObject, PyResult, Python, ToPyObject,
+    FromPyObject, IntoPy, IntoPyObject, Py, PyAny, PyObject, PyResult, Python, ToPyObject,
 };
 
 use num_bigint::{BigInt, BigUint};
@@ -66,46 +70,55 @@ macro_rules! bigint_conversion {
     ($rust_ty: ty, $is_signed: expr, $to_bytes: path) => {
         #[cfg_attr(docsrs, doc(cfg(feature = "num-bigint")))]
         impl ToPyObject for $rust_ty {
-            #[cfg(not(Py_LIMITED_API))]
             fn to_object(&self, py: Python<'_>) -> PyObject {
+                self.into_pyobject(py)
+                    .expect("int.from_bytes() failed during to_object()")
+                    .unbind()
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "num-bigint")))]
+        impl IntoPy<PyObject> for $rust_ty {
+            fn into_py(self, py: Python<'_>) -> PyObject {
+                self.to_object(py)
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "num-bigint")))]
+        impl<'py> IntoPyObject<'py> for $rust_ty {
+            #[inline]
+            fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+                (&self).into_pyobject(py)
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "num-bigint")))]
+        impl<'py> IntoPyObject<'py> for &$rust_ty {
+            #[cfg(not(Py_LIMITED_API))]
+            fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
                 let bytes = $to_bytes(self);
                 unsafe {
-                    let obj = ffi::_PyLong_FromByteArray(
-                        bytes.as_ptr().cast(),
-                        bytes.len(),
-                        1,
-                        $is_signed,
-                    );
-                    PyObject::from_owned_ptr(py, obj)
+                    ffi::_PyLong_FromByteArray(bytes.as_ptr().cast(), bytes.len(), 1, $is_signed)
+                        .assume_owned_or_err(py)
                 }
             }
 
             #[cfg(Py_LIMITED_API)]
-            fn to_object(&self, py: Python<'_>) -> PyObject {
+            fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
                 let bytes = $to_bytes(self);
                 let bytes_obj = PyBytes::new_bound(py, &bytes);
                 let kwargs = if $is_signed > 0 {
                     let kwargs = PyDict::new_bound(py);
-                    kwargs.set_item(crate::intern!(py, "signed"), true).unwrap();
+                    kwargs.set_item(crate::intern!(py, "signed"), true)?;
                     Some(kwargs)
                 } else {
                     None
                 };
-                py.get_type::<PyLong>()
-                    .call_method(
-                        "from_bytes",
-                        (bytes_obj, "little"),
-                        kwargs.as_ref().map(crate::Bound::as_gil_ref),
-                    )
-                    .expect("int.from_bytes() failed during to_object()") // FIXME: #1813 or similar
-                    .into()
-            }
-        }
-
-        #[cfg_attr(docsrs, doc(cfg(feature = "num-bigint")))]
-        impl IntoPy<PyObject> for $rust_ty {
-            fn into_py(self, py: Python<'_>) -> PyObject {
-                self.to_object(py)
+                py.get_type::<PyLong>().as_borrowed().call_method(
+                    "from_bytes",
+                    (bytes_obj, "little"),
+                    kwargs.as_ref(),
+                )
             }
         }
     };
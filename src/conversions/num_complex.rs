@@ -133,6 +133,8 @@ macro_rules! complex_conversion {
             }
         }
 
+        into_pyobject_via_into_py!(#[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))] Complex<$float>);
+
         #[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))]
         impl FromPyObject<'_> for Complex<$float> {
             fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Complex<$float>> {
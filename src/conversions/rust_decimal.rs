@@ -55,7 +55,8 @@ use crate::types::any::PyAnyMethods;
 use crate::types::string::PyStringMethods;
 use crate::types::PyType;
 use crate::{
-    intern, Bound, FromPyObject, IntoPy, Py, PyAny, PyObject, PyResult, Python, ToPyObject,
+    intern, Bound, FromPyObject, IntoPy, IntoPyObject, Py, PyAny, PyNativeType, PyObject, PyResult,
+    Python, ToPyObject,
 };
 use rust_decimal::Decimal;
 use std::str::FromStr;
@@ -86,15 +87,10 @@ fn get_decimal_cls(py: Python<'_>) -> PyResult<&PyType> {
 
 impl ToPyObject for Decimal {
     fn to_object(&self, py: Python<'_>) -> PyObject {
-        // TODO: handle error gracefully when ToPyObject can error
-        // look up the decimal.Decimal
-        let dec_cls = get_decimal_cls(py).expect("failed to load decimal.Decimal");
-        // now call the constructor with the Rust Decimal string-ified
-        // to not be lossy
-        let ret = dec_cls
-            .call1((self.to_string(),))
-            .expect("failed to call decimal.Decimal(value)");
-        ret.to_object(py)
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to call decimal.Decimal(value)")
+            .unbind()
     }
 }
 
@@ -104,6 +100,16 @@ impl IntoPy<PyObject> for Decimal {
     }
 }
 
+impl<'py> IntoPyObject<'py> for Decimal {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        // look up the decimal.Decimal
+        let dec_cls = get_decimal_cls(py)?.as_borrowed();
+        // now call the constructor with the Rust Decimal string-ified
+        // to not be lossy
+        dec_cls.call1((self.to_string(),))
+    }
+}
+
 #[cfg(test)]
 mod test_rust_decimal {
     use super::*;
@@ -1,9 +1,10 @@
 use crate::instance::Bound;
 use crate::types::any::PyAnyMethods;
+use crate::types::list::try_new_from_iter;
 use crate::types::PySequence;
 use crate::{
-    err::DowncastError, ffi, FromPyObject, IntoPy, Py, PyAny, PyObject, PyResult, Python,
-    ToPyObject,
+    err::DowncastError, ffi, FromPyObject, IntoPy, IntoPyObject, Py, PyAny, PyObject, PyResult,
+    Python, ToPyObject,
 };
 use crate::{exceptions, PyErr};
 
@@ -36,6 +37,16 @@ where
     }
 }
 
+impl<'py, T, const N: usize> IntoPyObject<'py> for [T; N]
+where
+    T: IntoPyObject<'py>,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut iter = self.into_iter().map(|e| e.into_pyobject(py));
+        try_new_from_iter(py, &mut iter).map(Bound::into_any)
+    }
+}
+
 impl<T, const N: usize> ToPyObject for [T; N]
 where
     T: ToPyObject,
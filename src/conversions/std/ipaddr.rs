@@ -28,6 +28,30 @@ impl FromPyObject<'_> for IpAddr {
     }
 }
 
+impl FromPyObject<'_> for Ipv4Addr {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        match obj.extract::<IpAddr>()? {
+            IpAddr::V4(ip) => Ok(ip),
+            IpAddr::V6(ip) => Err(PyValueError::new_err(format!(
+                "expected an IPv4 address, got {}",
+                ip
+            ))),
+        }
+    }
+}
+
+impl FromPyObject<'_> for Ipv6Addr {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        match obj.extract::<IpAddr>()? {
+            IpAddr::V6(ip) => Ok(ip),
+            IpAddr::V4(ip) => Err(PyValueError::new_err(format!(
+                "expected an IPv6 address, got {}",
+                ip
+            ))),
+        }
+    }
+}
+
 impl ToPyObject for Ipv4Addr {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         static IPV4_ADDRESS: GILOnceCell<Py<PyType>> = GILOnceCell::new();
@@ -52,6 +76,22 @@ impl ToPyObject for Ipv6Addr {
     }
 }
 
+impl IntoPy<PyObject> for Ipv4Addr {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!(Ipv4Addr);
+
+impl IntoPy<PyObject> for Ipv6Addr {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!(Ipv6Addr);
+
 impl ToPyObject for IpAddr {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         match self {
@@ -67,6 +107,8 @@ impl IntoPy<PyObject> for IpAddr {
     }
 }
 
+into_pyobject_via_into_py!(IpAddr);
+
 #[cfg(test)]
 mod test_ipaddr {
     use std::str::FromStr;
@@ -99,6 +141,25 @@ mod test_ipaddr {
         });
     }
 
+    #[test]
+    fn test_specific_versions() {
+        Python::with_gil(|py| {
+            let v4 = Ipv4Addr::new(192, 168, 0, 1);
+            let obj = v4.into_py(py);
+            assert_eq!(obj.extract::<Ipv4Addr>(py).unwrap(), v4);
+            assert!(obj.extract::<Ipv6Addr>(py).is_err());
+
+            let v6 = Ipv6Addr::LOCALHOST;
+            let obj = v6.into_py(py);
+            assert_eq!(obj.extract::<Ipv6Addr>(py).unwrap(), v6);
+            let err = obj.extract::<Ipv4Addr>(py).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "ValueError: expected an IPv4 address, got ::1"
+            );
+        });
+    }
+
     #[test]
     fn test_from_pystring() {
         Python::with_gil(|py| {
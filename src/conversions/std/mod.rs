@@ -6,6 +6,8 @@ mod osstr;
 mod path;
 mod set;
 mod slice;
+mod smart_ptr;
+mod socketaddr;
 mod string;
 mod time;
 mod vec;
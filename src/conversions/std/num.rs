@@ -1,9 +1,12 @@
+use crate::conversion::Strict;
+use crate::ffi_ptr_ext::FfiPtrExt;
 #[cfg(feature = "experimental-inspect")]
 use crate::inspect::types::TypeInfo;
 use crate::types::any::PyAnyMethods;
+use crate::types::{PyBool, PyLong};
 use crate::{
-    exceptions, ffi, Bound, FromPyObject, IntoPy, PyAny, PyErr, PyObject, PyResult, Python,
-    ToPyObject,
+    exceptions, ffi, Bound, DowncastError, FromPyObject, IntoPy, PyAny, PyErr, PyObject, PyResult,
+    Python, ToPyObject,
 };
 use std::convert::TryFrom;
 use std::num::{
@@ -31,11 +34,14 @@ macro_rules! int_fits_larger_int {
             }
         }
 
+        into_pyobject_via_into_py!($rust_type);
+
         impl FromPyObject<'_> for $rust_type {
             fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
-                let val: $larger_type = obj.extract()?;
+                let val: $larger_type =
+                    extract_index(obj, stringify!($rust_type), |index| index.extract())?;
                 <$rust_type>::try_from(val)
-                    .map_err(|e| exceptions::PyOverflowError::new_err(e.to_string()))
+                    .map_err(|_| out_of_range_error(val, stringify!($rust_type)))
             }
 
             #[cfg(feature = "experimental-inspect")]
@@ -47,38 +53,22 @@ macro_rules! int_fits_larger_int {
 }
 
 macro_rules! extract_int {
-    ($obj:ident, $error_val:expr, $pylong_as:expr) => {
-        extract_int!($obj, $error_val, $pylong_as, false)
-    };
-
-    ($obj:ident, $error_val:expr, $pylong_as:expr, $force_index_call: literal) => {
+    ($obj:ident, $error_val:expr, $pylong_as:expr, $rust_type:ty) => {
         // In python 3.8+ `PyLong_AsLong` and friends takes care of calling `PyNumber_Index`,
-        // however 3.8 & 3.9 do lossy conversion of floats, hence we only use the
-        // simplest logic for 3.10+ where that was fixed - python/cpython#82180.
-        // `PyLong_AsUnsignedLongLong` does not call `PyNumber_Index`, hence the `force_index_call` argument
+        // however 3.8 & 3.9 do lossy conversion of floats - python/cpython#82180, and
+        // `PyLong_AsUnsignedLongLong` does not call `PyNumber_Index`. Calling it up front also
+        // gives the `int` to name in overflow errors.
         // See https://github.com/PyO3/pyo3/pull/3742 for detials
-        if cfg!(Py_3_10) && !$force_index_call {
-            err_if_invalid_value($obj.py(), $error_val, unsafe { $pylong_as($obj.as_ptr()) })
-        } else if let Ok(long) = $obj.downcast::<crate::types::PyLong>() {
-            // fast path - checking for subclass of `int` just checks a bit in the type $object
-            err_if_invalid_value($obj.py(), $error_val, unsafe { $pylong_as(long.as_ptr()) })
-        } else {
-            unsafe {
-                let num = ffi::PyNumber_Index($obj.as_ptr());
-                if num.is_null() {
-                    Err(PyErr::fetch($obj.py()))
-                } else {
-                    let result = err_if_invalid_value($obj.py(), $error_val, $pylong_as(num));
-                    ffi::Py_DECREF(num);
-                    result
-                }
-            }
-        }
+        extract_index($obj, stringify!($rust_type), |index| {
+            err_if_invalid_value(index.py(), $error_val, unsafe {
+                $pylong_as(index.as_ptr())
+            })
+        })
     };
 }
 
 macro_rules! int_convert_u64_or_i64 {
-    ($rust_type:ty, $pylong_from_ll_or_ull:expr, $pylong_as_ll_or_ull:expr, $force_index_call:literal) => {
+    ($rust_type:ty, $pylong_from_ll_or_ull:expr, $pylong_as_ll_or_ull:expr) => {
         impl ToPyObject for $rust_type {
             #[inline]
             fn to_object(&self, py: Python<'_>) -> PyObject {
@@ -96,9 +86,12 @@ macro_rules! int_convert_u64_or_i64 {
                 TypeInfo::builtin("int")
             }
         }
+
+        into_pyobject_via_into_py!($rust_type);
+
         impl FromPyObject<'_> for $rust_type {
             fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<$rust_type> {
-                extract_int!(obj, !0, $pylong_as_ll_or_ull, $force_index_call)
+                extract_int!(obj, !0, $pylong_as_ll_or_ull, $rust_type)
             }
 
             #[cfg(feature = "experimental-inspect")]
@@ -127,11 +120,13 @@ macro_rules! int_fits_c_long {
             }
         }
 
+        into_pyobject_via_into_py!($rust_type);
+
         impl<'py> FromPyObject<'py> for $rust_type {
             fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
-                let val: c_long = extract_int!(obj, -1, ffi::PyLong_AsLong)?;
+                let val: c_long = extract_int!(obj, -1, ffi::PyLong_AsLong, $rust_type)?;
                 <$rust_type>::try_from(val)
-                    .map_err(|e| exceptions::PyOverflowError::new_err(e.to_string()))
+                    .map_err(|_| out_of_range_error(val, stringify!($rust_type)))
             }
 
             #[cfg(feature = "experimental-inspect")]
@@ -159,7 +154,7 @@ int_fits_c_long!(i64);
 
 // manual implementation for i64 on systems with 32-bit long
 #[cfg(any(target_pointer_width = "32", target_os = "windows"))]
-int_convert_u64_or_i64!(i64, ffi::PyLong_FromLongLong, ffi::PyLong_AsLongLong, false);
+int_convert_u64_or_i64!(i64, ffi::PyLong_FromLongLong, ffi::PyLong_AsLongLong);
 
 #[cfg(all(target_pointer_width = "64", not(target_os = "windows")))]
 int_fits_c_long!(isize);
@@ -172,8 +167,7 @@ int_fits_larger_int!(usize, u64);
 int_convert_u64_or_i64!(
     u64,
     ffi::PyLong_FromUnsignedLongLong,
-    ffi::PyLong_AsUnsignedLongLong,
-    true
+    ffi::PyLong_AsUnsignedLongLong
 );
 
 #[cfg(not(Py_LIMITED_API))]
@@ -212,22 +206,23 @@ mod fast_128bit_int_conversion {
                 }
             }
 
+            into_pyobject_via_into_py!($rust_type);
+
             impl FromPyObject<'_> for $rust_type {
                 fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<$rust_type> {
-                    let num = unsafe {
-                        PyObject::from_owned_ptr_or_err(ob.py(), ffi::PyNumber_Index(ob.as_ptr()))?
-                    };
-                    let mut buffer = [0; std::mem::size_of::<$rust_type>()];
-                    crate::err::error_on_minusone(ob.py(), unsafe {
-                        ffi::_PyLong_AsByteArray(
-                            num.as_ptr() as *mut ffi::PyLongObject,
-                            buffer.as_mut_ptr(),
-                            buffer.len(),
-                            1,
-                            $is_signed,
-                        )
-                    })?;
-                    Ok(<$rust_type>::from_le_bytes(buffer))
+                    extract_index(ob, stringify!($rust_type), |num| {
+                        let mut buffer = [0; std::mem::size_of::<$rust_type>()];
+                        crate::err::error_on_minusone(num.py(), unsafe {
+                            ffi::_PyLong_AsByteArray(
+                                num.as_ptr() as *mut ffi::PyLongObject,
+                                buffer.as_mut_ptr(),
+                                buffer.len(),
+                                1,
+                                $is_signed,
+                            )
+                        })?;
+                        Ok(<$rust_type>::from_le_bytes(buffer))
+                    })
                 }
 
                 #[cfg(feature = "experimental-inspect")]
@@ -281,23 +276,29 @@ mod slow_128bit_int_conversion {
                 }
             }
 
+            into_pyobject_via_into_py!($rust_type);
+
             impl FromPyObject<'_> for $rust_type {
                 fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<$rust_type> {
-                    let py = ob.py();
-                    unsafe {
-                        let lower = err_if_invalid_value(
-                            py,
-                            -1 as _,
-                            ffi::PyLong_AsUnsignedLongLongMask(ob.as_ptr()),
-                        )? as $rust_type;
-                        let shift = SHIFT.into_py(py);
-                        let shifted = PyObject::from_owned_ptr_or_err(
-                            py,
-                            ffi::PyNumber_Rshift(ob.as_ptr(), shift.as_ptr()),
-                        )?;
-                        let upper: $half_type = shifted.extract(py)?;
-                        Ok((<$rust_type>::from(upper) << SHIFT) | lower)
+                    fn extract_parts(ob: &Bound<'_, PyAny>) -> PyResult<$rust_type> {
+                        let py = ob.py();
+                        unsafe {
+                            let lower = err_if_invalid_value(
+                                py,
+                                -1 as _,
+                                ffi::PyLong_AsUnsignedLongLongMask(ob.as_ptr()),
+                            )? as $rust_type;
+                            let shift = SHIFT.into_py(py);
+                            let shifted = PyObject::from_owned_ptr_or_err(
+                                py,
+                                ffi::PyNumber_Rshift(ob.as_ptr(), shift.as_ptr()),
+                            )?;
+                            let upper: $half_type = shifted.extract(py)?;
+                            Ok((<$rust_type>::from(upper) << SHIFT) | lower)
+                        }
                     }
+
+                    extract_index(ob, stringify!($rust_type), extract_parts)
                 }
 
                 #[cfg(feature = "experimental-inspect")]
@@ -326,6 +327,48 @@ fn err_if_invalid_value<T: PartialEq>(
     Ok(actual_value)
 }
 
+/// Creates the `OverflowError` raised when `value` does not fit into `rust_type`.
+fn out_of_range_error(value: impl std::fmt::Display, rust_type: &str) -> PyErr {
+    let mut value = value.to_string();
+    // Keep the message readable for huge integers.
+    if value.len() > 40 {
+        value = format!("{}...{}", &value[..18], &value[value.len() - 18..]);
+    }
+    exceptions::PyOverflowError::new_err(format!(
+        "Python int {} is out of range for {}",
+        value, rust_type
+    ))
+}
+
+/// Converts `obj` to an `int` with `PyNumber_Index`, unless it is one already, and passes it to
+/// `convert`. An `OverflowError` raised by `convert` is replaced with one which names the value
+/// and the target type; other errors are returned unchanged.
+fn extract_index<'py, T>(
+    obj: &Bound<'py, PyAny>,
+    rust_type: &str,
+    convert: impl FnOnce(&Bound<'py, PyAny>) -> PyResult<T>,
+) -> PyResult<T> {
+    let py = obj.py();
+    let num;
+    // fast path - checking for subclass of `int` just checks a bit in the type object
+    let index = if obj.is_instance_of::<PyLong>() {
+        obj
+    } else {
+        num = unsafe { ffi::PyNumber_Index(obj.as_ptr()).assume_owned_or_err(py)? };
+        &num
+    };
+    convert(index).map_err(|err| {
+        if !err.is_instance_of::<exceptions::PyOverflowError>(py) {
+            return err;
+        }
+        match index.str() {
+            Ok(value) => out_of_range_error(value, rust_type),
+            // e.g. the value has too many digits to be converted to a string
+            Err(_) => err,
+        }
+    })
+}
+
 macro_rules! nonzero_int_impl {
     ($nonzero_type:ty, $primitive_type:ty) => {
         impl ToPyObject for $nonzero_type {
@@ -340,6 +383,8 @@ macro_rules! nonzero_int_impl {
             }
         }
 
+        into_pyobject_via_into_py!($nonzero_type);
+
         impl FromPyObject<'_> for $nonzero_type {
             fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
                 let val: $primitive_type = obj.extract()?;
@@ -368,6 +413,43 @@ nonzero_int_impl!(NonZeroU64, u64);
 nonzero_int_impl!(NonZeroU128, u128);
 nonzero_int_impl!(NonZeroUsize, usize);
 
+macro_rules! strict_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromPyObject<'_> for Strict<$t> {
+                fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+                    if obj.is_instance_of::<PyLong>() && !obj.is_instance_of::<PyBool>() {
+                        obj.extract().map(Strict)
+                    } else {
+                        Err(DowncastError::new(obj, "int").into())
+                    }
+                }
+
+                #[cfg(feature = "experimental-inspect")]
+                fn type_input() -> TypeInfo {
+                    <$t>::type_input()
+                }
+            }
+        )*
+    };
+}
+
+strict_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+strict_int!(
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize
+);
+
 #[cfg(test)]
 mod test_128bit_integers {
     use super::*;
@@ -494,6 +576,58 @@ mod test_128bit_integers {
         })
     }
 
+    #[test]
+    fn test_overflow_error_message() {
+        Python::with_gil(|py| {
+            let message = |code: &str, extract: fn(&Bound<'_, PyAny>) -> PyErr| {
+                let obj = py.eval_bound(code, None, None).unwrap();
+                let err = extract(&obj);
+                assert!(err.is_instance_of::<crate::exceptions::PyOverflowError>(py));
+                err.value(py).to_string()
+            };
+            assert_eq!(
+                message("300", |obj| obj.extract::<u8>().unwrap_err()),
+                "Python int 300 is out of range for u8"
+            );
+            assert_eq!(
+                message("-1", |obj| obj.extract::<u64>().unwrap_err()),
+                "Python int -1 is out of range for u64"
+            );
+            assert_eq!(
+                message("1 << 64", |obj| obj.extract::<i64>().unwrap_err()),
+                "Python int 18446744073709551616 is out of range for i64"
+            );
+            assert_eq!(
+                message("-(1 << 127) - 1", |obj| obj.extract::<i128>().unwrap_err()),
+                "Python int -170141183460469231731687303715884105729 is out of range for i128"
+            );
+            assert_eq!(
+                message("1 << 200", |obj| obj.extract::<u128>().unwrap_err()),
+                "Python int 160693804425899027...993782792835301376 is out of range for u128"
+            );
+        })
+    }
+
+    #[test]
+    fn test_overflow_error_calls_index_once() {
+        Python::with_gil(|py| {
+            let obj = py
+                .eval_bound(
+                    "type('Index', (), {'calls': 0, '__index__': lambda self: \
+                        setattr(type(self), 'calls', type(self).calls + 1) or 300})()",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let err = obj.extract::<u8>().unwrap_err();
+            assert_eq!(
+                err.value(py).to_string(),
+                "Python int 300 is out of range for u8"
+            );
+            assert_eq!(obj.getattr("calls").unwrap().extract::<u32>().unwrap(), 1);
+        })
+    }
+
     #[test]
     fn test_nonzero_i128_max() {
         Python::with_gil(|py| {
@@ -780,4 +914,38 @@ mod tests {
             assert!(err.is_instance_of::<crate::exceptions::PyTypeError>(py));
         })
     }
+
+    #[test]
+    fn test_strict_int() {
+        use crate::conversion::Strict;
+        use crate::types::any::PyAnyMethods;
+
+        Python::with_gil(|py| {
+            let int = 5.to_object(py).into_bound(py);
+            assert_eq!(int.extract::<Strict<u8>>().unwrap(), Strict(5));
+            assert_eq!(
+                int.extract::<Strict<NonZeroI64>>().unwrap(),
+                Strict(NonZeroI64::new(5).unwrap())
+            );
+
+            let bool = true.to_object(py).into_bound(py);
+            assert_eq!(bool.extract::<i32>().unwrap(), 1);
+            assert_eq!(
+                bool.extract::<Strict<i32>>().unwrap_err().to_string(),
+                "TypeError: 'bool' object cannot be converted to 'int'"
+            );
+            let float = 5.0.to_object(py).into_bound(py);
+            assert!(float.extract::<Strict<i64>>().is_err());
+
+            let index = py
+                .eval_bound(
+                    "type('Index', (), {'__index__': lambda self: 5})()",
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(index.extract::<u64>().unwrap(), 5);
+            assert!(index.extract::<Strict<u64>>().is_err());
+        });
+    }
 }
@@ -114,6 +114,8 @@ impl IntoPy<PyObject> for &'_ OsStr {
     }
 }
 
+into_pyobject_via_into_py!(&'_ OsStr);
+
 impl ToPyObject for Cow<'_, OsStr> {
     #[inline]
     fn to_object(&self, py: Python<'_>) -> PyObject {
@@ -128,6 +130,8 @@ impl IntoPy<PyObject> for Cow<'_, OsStr> {
     }
 }
 
+into_pyobject_via_into_py!(Cow<'_, OsStr>);
+
 impl ToPyObject for OsString {
     #[inline]
     fn to_object(&self, py: Python<'_>) -> PyObject {
@@ -141,12 +145,16 @@ impl IntoPy<PyObject> for OsString {
     }
 }
 
+into_pyobject_via_into_py!(OsString);
+
 impl<'a> IntoPy<PyObject> for &'a OsString {
     fn into_py(self, py: Python<'_>) -> PyObject {
         self.to_object(py)
     }
 }
 
+into_pyobject_via_into_py!(['a] &'a OsString);
+
 #[cfg(test)]
 mod tests {
     use crate::{types::PyString, IntoPy, PyObject, Python, ToPyObject};
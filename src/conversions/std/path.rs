@@ -29,6 +29,8 @@ impl<'a> IntoPy<PyObject> for &'a Path {
     }
 }
 
+into_pyobject_via_into_py!(['a] &'a Path);
+
 impl<'a> ToPyObject for Cow<'a, Path> {
     #[inline]
     fn to_object(&self, py: Python<'_>) -> PyObject {
@@ -43,6 +45,8 @@ impl<'a> IntoPy<PyObject> for Cow<'a, Path> {
     }
 }
 
+into_pyobject_via_into_py!(['a] Cow<'a, Path>);
+
 impl ToPyObject for PathBuf {
     #[inline]
     fn to_object(&self, py: Python<'_>) -> PyObject {
@@ -56,12 +60,16 @@ impl IntoPy<PyObject> for PathBuf {
     }
 }
 
+into_pyobject_via_into_py!(PathBuf);
+
 impl<'a> IntoPy<PyObject> for &'a PathBuf {
     fn into_py(self, py: Python<'_>) -> PyObject {
         self.as_os_str().to_object(py)
     }
 }
 
+into_pyobject_via_into_py!(['a] &'a PathBuf);
+
 #[cfg(test)]
 mod tests {
     use crate::{types::PyString, IntoPy, PyObject, Python, ToPyObject};
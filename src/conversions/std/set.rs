@@ -5,10 +5,10 @@ use crate::inspect::types::TypeInfo;
 use crate::{
     instance::Bound,
     types::any::PyAnyMethods,
-    types::frozenset::PyFrozenSetMethods,
-    types::set::{new_from_iter, PySetMethods},
-    types::{PyFrozenSet, PySet},
-    FromPyObject, IntoPy, PyAny, PyErr, PyObject, PyResult, Python, ToPyObject,
+    types::frozenset::{self, PyFrozenSetMethods},
+    types::set::{self, new_from_iter, PySetMethods},
+    types::{AsFrozenSet, PyFrozenSet, PySet},
+    FromPyObject, IntoPy, IntoPyObject, PyAny, PyErr, PyObject, PyResult, Python, ToPyObject,
 };
 
 impl<T, S> ToPyObject for collections::HashSet<T, S>
@@ -51,6 +51,22 @@ where
     }
 }
 
+impl<'py, K, S> IntoPyObject<'py> for collections::HashSet<K, S>
+where
+    K: IntoPyObject<'py> + Eq + hash::Hash,
+    S: hash::BuildHasher + Default,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut iter = self.into_iter().map(|item| item.into_pyobject(py));
+        set::try_new_from_iter(py, &mut iter).map(Bound::into_any)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::set_of(K::type_output())
+    }
+}
+
 impl<'py, K, S> FromPyObject<'py> for collections::HashSet<K, S>
 where
     K: FromPyObject<'py> + cmp::Eq + hash::Hash,
@@ -91,6 +107,21 @@ where
     }
 }
 
+impl<'py, K> IntoPyObject<'py> for collections::BTreeSet<K>
+where
+    K: IntoPyObject<'py> + cmp::Ord,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut iter = self.into_iter().map(|item| item.into_pyobject(py));
+        set::try_new_from_iter(py, &mut iter).map(Bound::into_any)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::set_of(K::type_output())
+    }
+}
+
 impl<'py, K> FromPyObject<'py> for collections::BTreeSet<K>
 where
     K: FromPyObject<'py> + cmp::Ord,
@@ -114,9 +145,110 @@ where
     }
 }
 
+impl<T, S> ToPyObject for AsFrozenSet<collections::HashSet<T, S>>
+where
+    T: hash::Hash + Eq + ToPyObject,
+    S: hash::BuildHasher + Default,
+{
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        frozenset::new_from_iter(py, &self.0)
+            .expect("Failed to create Python frozenset from HashSet")
+            .into()
+    }
+}
+
+impl<T> ToPyObject for AsFrozenSet<collections::BTreeSet<T>>
+where
+    T: hash::Hash + Eq + ToPyObject,
+{
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        frozenset::new_from_iter(py, &self.0)
+            .expect("Failed to create Python frozenset from BTreeSet")
+            .into()
+    }
+}
+
+impl<K, S> IntoPy<PyObject> for AsFrozenSet<collections::HashSet<K, S>>
+where
+    K: IntoPy<PyObject> + Eq + hash::Hash,
+    S: hash::BuildHasher + Default,
+{
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        frozenset::new_from_iter(py, self.0.into_iter().map(|item| item.into_py(py)))
+            .expect("Failed to create Python frozenset from HashSet")
+            .into()
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::frozen_set_of(K::type_output())
+    }
+}
+
+impl<'py, K, S> IntoPyObject<'py> for AsFrozenSet<collections::HashSet<K, S>>
+where
+    K: IntoPyObject<'py> + Eq + hash::Hash,
+    S: hash::BuildHasher + Default,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut iter = self.0.into_iter().map(|item| item.into_pyobject(py));
+        frozenset::try_new_from_iter(py, &mut iter).map(Bound::into_any)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::frozen_set_of(K::type_output())
+    }
+}
+
+impl<K> IntoPy<PyObject> for AsFrozenSet<collections::BTreeSet<K>>
+where
+    K: IntoPy<PyObject> + cmp::Ord,
+{
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        frozenset::new_from_iter(py, self.0.into_iter().map(|item| item.into_py(py)))
+            .expect("Failed to create Python frozenset from BTreeSet")
+            .into()
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::frozen_set_of(K::type_output())
+    }
+}
+
+impl<'py, K> IntoPyObject<'py> for AsFrozenSet<collections::BTreeSet<K>>
+where
+    K: IntoPyObject<'py> + cmp::Ord,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut iter = self.0.into_iter().map(|item| item.into_pyobject(py));
+        frozenset::try_new_from_iter(py, &mut iter).map(Bound::into_any)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::frozen_set_of(K::type_output())
+    }
+}
+
+impl<'py, T> FromPyObject<'py> for AsFrozenSet<T>
+where
+    T: FromPyObject<'py>,
+{
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        T::extract_bound(ob).map(AsFrozenSet)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_input() -> TypeInfo {
+        T::type_input()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::types::{any::PyAnyMethods, PyFrozenSet, PySet};
+    use crate::types::{any::PyAnyMethods, AsFrozenSet, PyFrozenSet, PySet};
     use crate::{IntoPy, PyObject, Python, ToPyObject};
     use std::collections::{BTreeSet, HashSet};
 
@@ -160,6 +292,30 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_as_frozenset() {
+        Python::with_gil(|py| {
+            let bt: BTreeSet<u64> = [1, 2, 3].iter().cloned().collect();
+            let hs: HashSet<u64> = [1, 2, 3].iter().cloned().collect();
+
+            for obj in [
+                AsFrozenSet(bt.clone()).to_object(py),
+                AsFrozenSet(bt.clone()).into_py(py),
+                AsFrozenSet(hs.clone()).to_object(py),
+                AsFrozenSet(hs.clone()).into_py(py),
+            ] {
+                let obj = obj.into_bound(py);
+                assert!(obj.is_instance_of::<PyFrozenSet>());
+                assert_eq!(obj.extract::<AsFrozenSet<BTreeSet<u64>>>().unwrap().0, bt);
+                assert_eq!(obj.extract::<HashSet<u64>>().unwrap(), hs);
+            }
+
+            // Sets can also be extracted
+            let set = PySet::new_bound(py, &[1, 2, 3]).unwrap();
+            assert_eq!(set.extract::<AsFrozenSet<BTreeSet<u64>>>().unwrap().0, bt);
+        });
+    }
+
     #[test]
     fn test_set_to_object() {
         Python::with_gil(|py| {
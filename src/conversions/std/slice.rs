@@ -18,6 +18,8 @@ impl<'a> IntoPy<PyObject> for &'a [u8] {
     }
 }
 
+into_pyobject_via_into_py!(['a] &'a [u8]);
+
 impl<'py> FromPyObject<'py> for &'py [u8] {
     fn extract(obj: &'py PyAny) -> PyResult<Self> {
         Ok(obj.downcast::<PyBytes>()?.as_bytes())
@@ -57,6 +59,8 @@ impl IntoPy<Py<PyAny>> for Cow<'_, [u8]> {
     }
 }
 
+into_pyobject_via_into_py!(Cow<'_, [u8]>);
+
 #[cfg(test)]
 mod tests {
     use std::borrow::Cow;
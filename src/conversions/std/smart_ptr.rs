@@ -0,0 +1,131 @@
+#[cfg(feature = "experimental-inspect")]
+use crate::inspect::types::TypeInfo;
+use crate::{Bound, IntoPy, IntoPyObject, PyAny, PyObject, PyResult, Python, ToPyObject};
+use std::rc::Rc;
+use std::sync::Arc;
+
+impl<T> ToPyObject for Box<T>
+where
+    T: ?Sized + ToPyObject,
+{
+    #[inline]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        (**self).to_object(py)
+    }
+}
+
+impl<T> IntoPy<PyObject> for Box<T>
+where
+    T: IntoPy<PyObject>,
+{
+    #[inline]
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        (*self).into_py(py)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+}
+
+impl<'py, T> IntoPyObject<'py> for Box<T>
+where
+    T: IntoPyObject<'py>,
+{
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        (*self).into_pyobject(py)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+}
+
+/// `Rc<T>` and `Arc<T>` are converted like `T`. As the value may be shared, the conversion by
+/// value goes through [`ToPyObject`] rather than consuming `T`.
+impl<T> ToPyObject for Rc<T>
+where
+    T: ?Sized + ToPyObject,
+{
+    #[inline]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        (**self).to_object(py)
+    }
+}
+
+impl<T> IntoPy<PyObject> for Rc<T>
+where
+    T: ?Sized + ToPyObject,
+{
+    #[inline]
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        (*self).to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!([T] Rc<T> where T: ?Sized + ToPyObject);
+
+impl<T> ToPyObject for Arc<T>
+where
+    T: ?Sized + ToPyObject,
+{
+    #[inline]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        (**self).to_object(py)
+    }
+}
+
+impl<T> IntoPy<PyObject> for Arc<T>
+where
+    T: ?Sized + ToPyObject,
+{
+    #[inline]
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        (*self).to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!([T] Arc<T> where T: ?Sized + ToPyObject);
+
+#[cfg(test)]
+mod tests {
+    use crate::types::any::PyAnyMethods;
+    use crate::{IntoPy, PyObject, Python, ToPyObject};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    fn extract_repr(py: Python<'_>, obj: PyObject) -> String {
+        obj.into_bound(py).repr().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_box_conversions() {
+        Python::with_gil(|py| {
+            let boxed = Box::new(vec![1, 2, 3]);
+            assert_eq!(extract_repr(py, boxed.to_object(py)), "[1, 2, 3]");
+            assert_eq!(extract_repr(py, boxed.into_py(py)), "[1, 2, 3]");
+
+            let boxed_str: Box<str> = "hello".into();
+            assert_eq!(extract_repr(py, boxed_str.to_object(py)), "'hello'");
+        });
+    }
+
+    #[test]
+    fn test_rc_and_arc_conversions() {
+        Python::with_gil(|py| {
+            let rc = Rc::new(String::from("shared"));
+            assert_eq!(extract_repr(py, rc.to_object(py)), "'shared'");
+            assert_eq!(extract_repr(py, rc.clone().into_py(py)), "'shared'");
+
+            let rc_slice: Rc<[u32]> = Rc::from(vec![4, 5]);
+            assert_eq!(extract_repr(py, rc_slice.into_py(py)), "[4, 5]");
+
+            let arc: Arc<str> = Arc::from("atomic");
+            assert_eq!(extract_repr(py, arc.to_object(py)), "'atomic'");
+            assert_eq!(extract_repr(py, arc.into_py(py)), "'atomic'");
+        });
+    }
+}
@@ -0,0 +1,169 @@
+//! Conversions of socket addresses to and from the tuples used by Python's `socket` module:
+//! `(host, port)` for IPv4 and `(host, port, flowinfo, scope_id)` for IPv6.
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use crate::exceptions::PyValueError;
+use crate::instance::Bound;
+use crate::types::any::PyAnyMethods;
+use crate::types::tuple::PyTupleMethods;
+use crate::types::PyTuple;
+use crate::{FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject};
+
+impl FromPyObject<'_> for SocketAddrV4 {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let (ip, port): (Ipv4Addr, u16) = obj.extract()?;
+        Ok(SocketAddrV4::new(ip, port))
+    }
+}
+
+impl FromPyObject<'_> for SocketAddrV6 {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let tuple = obj.downcast::<PyTuple>()?;
+        if tuple.len() == 2 {
+            let (ip, port): (Ipv6Addr, u16) = tuple.extract()?;
+            Ok(SocketAddrV6::new(ip, port, 0, 0))
+        } else {
+            let (ip, port, flowinfo, scope_id): (Ipv6Addr, u16, u32, u32) = tuple.extract()?;
+            Ok(SocketAddrV6::new(ip, port, flowinfo, scope_id))
+        }
+    }
+}
+
+impl FromPyObject<'_> for SocketAddr {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let tuple = obj.downcast::<PyTuple>()?;
+        match tuple.len() {
+            2 => {
+                let (ip, port): (IpAddr, u16) = tuple.extract()?;
+                Ok(SocketAddr::new(ip, port))
+            }
+            4 => tuple.extract::<SocketAddrV6>().map(SocketAddr::V6),
+            len => Err(PyValueError::new_err(format!(
+                "expected a socket address tuple of length 2 or 4, got length {}",
+                len
+            ))),
+        }
+    }
+}
+
+impl ToPyObject for SocketAddrV4 {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        (self.ip().to_string(), self.port()).to_object(py)
+    }
+}
+
+impl ToPyObject for SocketAddrV6 {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        (
+            self.ip().to_string(),
+            self.port(),
+            self.flowinfo(),
+            self.scope_id(),
+        )
+            .to_object(py)
+    }
+}
+
+impl ToPyObject for SocketAddr {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        match self {
+            SocketAddr::V4(addr) => addr.to_object(py),
+            SocketAddr::V6(addr) => addr.to_object(py),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for SocketAddrV4 {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!(SocketAddrV4);
+
+impl IntoPy<PyObject> for SocketAddrV6 {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!(SocketAddrV6);
+
+impl IntoPy<PyObject> for SocketAddr {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!(SocketAddr);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_roundtrip() {
+        Python::with_gil(|py| {
+            fn roundtrip(py: Python<'_>, addr: &str, expected_repr: &str) {
+                let addr = SocketAddr::from_str(addr).unwrap();
+                let obj = addr.into_py(py);
+                let repr = obj.bind(py).repr().unwrap().to_string();
+                assert_eq!(repr, expected_repr);
+                assert_eq!(obj.extract::<SocketAddr>(py).unwrap(), addr);
+            }
+            roundtrip(py, "127.0.0.1:8080", "('127.0.0.1', 8080)");
+            roundtrip(py, "[::1]:443", "('::1', 443, 0, 0)");
+        });
+    }
+
+    #[test]
+    fn test_extract_from_python_tuples() {
+        Python::with_gil(|py| {
+            let addr: SocketAddr = py
+                .eval_bound("('10.0.0.1', 22)", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(addr, SocketAddr::from_str("10.0.0.1:22").unwrap());
+
+            let addr: SocketAddrV6 = py
+                .eval_bound(
+                    "(__import__('ipaddress').ip_address('fe80::1'), 80)",
+                    None,
+                    None,
+                )
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(addr, SocketAddrV6::from_str("[fe80::1]:80").unwrap());
+
+            let addr: SocketAddrV6 = py
+                .eval_bound("('fe80::1', 80, 1, 2)", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!((addr.flowinfo(), addr.scope_id()), (1, 2));
+
+            let err = py
+                .eval_bound("('::1', 80)", None, None)
+                .unwrap()
+                .extract::<SocketAddrV4>()
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "ValueError: expected an IPv4 address, got ::1"
+            );
+
+            let err = py
+                .eval_bound("('::1', 80, 0)", None, None)
+                .unwrap()
+                .extract::<SocketAddr>()
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "ValueError: expected a socket address tuple of length 2 or 4, got length 3"
+            );
+        });
+    }
+}
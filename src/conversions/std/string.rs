@@ -29,6 +29,8 @@ impl<'a> IntoPy<PyObject> for &'a str {
     }
 }
 
+into_pyobject_via_into_py!(['a] &'a str);
+
 impl<'a> IntoPy<Py<PyString>> for &'a str {
     #[inline]
     fn into_py(self, py: Python<'_>) -> Py<PyString> {
@@ -62,6 +64,8 @@ impl IntoPy<PyObject> for Cow<'_, str> {
     }
 }
 
+into_pyobject_via_into_py!(Cow<'_, str>);
+
 /// Converts a Rust `String` to a Python object.
 /// See `PyString::new` for details on the conversion.
 impl ToPyObject for String {
@@ -89,6 +93,8 @@ impl IntoPy<PyObject> for char {
     }
 }
 
+into_pyobject_via_into_py!(char);
+
 impl IntoPy<PyObject> for String {
     fn into_py(self, py: Python<'_>) -> PyObject {
         PyString::new_bound(py, &self).into()
@@ -100,6 +106,8 @@ impl IntoPy<PyObject> for String {
     }
 }
 
+into_pyobject_via_into_py!(String);
+
 impl<'a> IntoPy<PyObject> for &'a String {
     #[inline]
     fn into_py(self, py: Python<'_>) -> PyObject {
@@ -112,6 +120,8 @@ impl<'a> IntoPy<PyObject> for &'a String {
     }
 }
 
+into_pyobject_via_into_py!(['a] &'a String);
+
 /// Allows extracting strings from Python objects.
 /// Accepts Python `str` and `unicode` objects.
 impl<'py> FromPyObject<'py> for &'py str {
@@ -145,9 +155,10 @@ impl FromPyObject<'_> for char {
         if let (Some(ch), None) = (iter.next(), iter.next()) {
             Ok(ch)
         } else {
-            Err(crate::exceptions::PyValueError::new_err(
-                "expected a string of length 1",
-            ))
+            Err(crate::exceptions::PyValueError::new_err(format!(
+                "expected a string of length 1, got a string of length {}",
+                s.chars().count()
+            )))
         }
     }
 
@@ -221,10 +232,16 @@ mod tests {
             let s = "Hello Python";
             let py_string = s.to_object(py);
             let err: crate::PyResult<char> = py_string.as_ref(py).extract();
-            assert!(err
-                .unwrap_err()
-                .to_string()
-                .contains("expected a string of length 1"));
+            assert_eq!(
+                err.unwrap_err().to_string(),
+                "ValueError: expected a string of length 1, got a string of length 12"
+            );
+
+            let err: crate::PyResult<char> = "".to_object(py).as_ref(py).extract();
+            assert_eq!(
+                err.unwrap_err().to_string(),
+                "ValueError: expected a string of length 1, got a string of length 0"
+            );
         })
     }
 
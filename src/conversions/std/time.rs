@@ -8,7 +8,8 @@ use crate::types::{timezone_utc_bound, PyDateTime, PyDelta, PyDeltaAccess};
 #[cfg(Py_LIMITED_API)]
 use crate::Py;
 use crate::{
-    intern, Bound, FromPyObject, IntoPy, PyAny, PyErr, PyObject, PyResult, Python, ToPyObject,
+    intern, Bound, FromPyObject, IntoPy, IntoPyObject, PyAny, PyErr, PyObject, PyResult, Python,
+    ToPyObject,
 };
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -53,42 +54,49 @@ impl FromPyObject<'_> for Duration {
 
 impl ToPyObject for Duration {
     fn to_object(&self, py: Python<'_>) -> PyObject {
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to construct timedelta (overflow?)")
+            .unbind()
+    }
+}
+
+impl IntoPy<PyObject> for Duration {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Duration {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let days = self.as_secs() / SECONDS_PER_DAY;
         let seconds = self.as_secs() % SECONDS_PER_DAY;
         let microseconds = self.subsec_micros();
 
         #[cfg(not(Py_LIMITED_API))]
         {
+            let days = days
+                .try_into()
+                .map_err(|_| PyOverflowError::new_err("Too large Rust duration for timedelta"))?;
             PyDelta::new_bound(
                 py,
-                days.try_into()
-                    .expect("Too large Rust duration for timedelta"),
+                days,
                 seconds.try_into().unwrap(),
                 microseconds.try_into().unwrap(),
                 false,
             )
-            .expect("failed to construct timedelta (overflow?)")
-            .into()
+            .map(Bound::into_any)
         }
         #[cfg(Py_LIMITED_API)]
         {
             static TIMEDELTA: GILOnceCell<Py<PyType>> = GILOnceCell::new();
             TIMEDELTA
-                .get_or_try_init_type_ref(py, "datetime", "timedelta")
-                .unwrap()
+                .get_or_try_init_type_ref(py, "datetime", "timedelta")?
                 .call1((days, seconds, microseconds))
-                .unwrap()
-                .into()
         }
     }
 }
 
-impl IntoPy<PyObject> for Duration {
-    fn into_py(self, py: Python<'_>) -> PyObject {
-        self.to_object(py)
-    }
-}
-
 // Conversions between SystemTime and datetime do not rely on the floating point timestamp of the
 // timestamp/fromtimestamp APIs to avoid possible precision loss but goes through the
 // timedelta/std::time::Duration types by taking for reference point the UNIX epoch.
@@ -98,7 +106,7 @@ impl IntoPy<PyObject> for Duration {
 impl FromPyObject<'_> for SystemTime {
     fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
         let duration_since_unix_epoch: Duration = obj
-            .call_method1(intern!(obj.py(), "__sub__"), (unix_epoch_py(obj.py()),))?
+            .call_method1(intern!(obj.py(), "__sub__"), (unix_epoch_py(obj.py())?,))?
             .extract()?;
         UNIX_EPOCH
             .checked_add(duration_since_unix_epoch)
@@ -110,10 +118,10 @@ impl FromPyObject<'_> for SystemTime {
 
 impl ToPyObject for SystemTime {
     fn to_object(&self, py: Python<'_>) -> PyObject {
-        let duration_since_unix_epoch = self.duration_since(UNIX_EPOCH).unwrap().into_py(py);
-        unix_epoch_py(py)
-            .call_method1(py, intern!(py, "__add__"), (duration_since_unix_epoch,))
-            .unwrap()
+        (*self)
+            .into_pyobject(py)
+            .expect("failed to construct datetime.datetime")
+            .unbind()
     }
 }
 
@@ -123,41 +131,40 @@ impl IntoPy<PyObject> for SystemTime {
     }
 }
 
-fn unix_epoch_py(py: Python<'_>) -> &PyObject {
+impl<'py> IntoPyObject<'py> for SystemTime {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let duration_since_unix_epoch = self.duration_since(UNIX_EPOCH).map_err(|_| {
+            PyValueError::new_err("It is not possible to convert a time before the UNIX epoch")
+        })?;
+        unix_epoch_py(py)?.bind(py).call_method1(
+            intern!(py, "__add__"),
+            (duration_since_unix_epoch.into_pyobject(py)?,),
+        )
+    }
+}
+
+fn unix_epoch_py(py: Python<'_>) -> PyResult<&PyObject> {
     static UNIX_EPOCH: GILOnceCell<PyObject> = GILOnceCell::new();
-    UNIX_EPOCH
-        .get_or_try_init(py, || {
-            #[cfg(not(Py_LIMITED_API))]
-            {
-                Ok::<_, PyErr>(
-                    PyDateTime::new_bound(
-                        py,
-                        1970,
-                        1,
-                        1,
-                        0,
-                        0,
-                        0,
-                        0,
-                        Some(&timezone_utc_bound(py)),
-                    )?
+    UNIX_EPOCH.get_or_try_init(py, || {
+        #[cfg(not(Py_LIMITED_API))]
+        {
+            Ok::<_, PyErr>(
+                PyDateTime::new_bound(py, 1970, 1, 1, 0, 0, 0, 0, Some(&timezone_utc_bound(py)))?
                     .into(),
-                )
-            }
-            #[cfg(Py_LIMITED_API)]
-            {
-                let datetime = py.import_bound("datetime")?;
-                let utc = datetime.getattr("timezone")?.getattr("utc")?;
-                Ok::<_, PyErr>(
-                    datetime
-                        .getattr("datetime")?
-                        .call1((1970, 1, 1, 0, 0, 0, 0, utc))
-                        .unwrap()
-                        .into(),
-                )
-            }
-        })
-        .unwrap()
+            )
+        }
+        #[cfg(Py_LIMITED_API)]
+        {
+            let datetime = py.import_bound("datetime")?;
+            let utc = datetime.getattr("timezone")?.getattr("utc")?;
+            Ok::<_, PyErr>(
+                datetime
+                    .getattr("datetime")?
+                    .call1((1970, 1, 1, 0, 0, 0, 0, utc))?
+                    .into(),
+            )
+        }
+    })
 }
 
 #[cfg(test)]
@@ -255,6 +262,10 @@ mod tests {
     fn test_duration_topyobject_overflow() {
         Python::with_gil(|py| {
             assert!(panic::catch_unwind(|| Duration::MAX.to_object(py)).is_err());
+            assert!(Duration::MAX
+                .into_pyobject(py)
+                .unwrap_err()
+                .is_instance_of::<PyOverflowError>(py));
         })
     }
 
@@ -360,6 +371,10 @@ mod tests {
             .unwrap();
         Python::with_gil(|py| {
             assert!(panic::catch_unwind(|| big_system_time.into_py(py)).is_err());
+            assert!(big_system_time
+                .into_pyobject(py)
+                .unwrap_err()
+                .is_instance_of::<PyOverflowError>(py));
         })
     }
 
@@ -1,7 +1,7 @@
 #[cfg(feature = "experimental-inspect")]
 use crate::inspect::types::TypeInfo;
-use crate::types::list::new_from_iter;
-use crate::{IntoPy, PyObject, Python, ToPyObject};
+use crate::types::list::{new_from_iter, try_new_from_iter};
+use crate::{Bound, IntoPy, IntoPyObject, PyAny, PyObject, PyResult, Python, ToPyObject};
 
 impl<T> ToPyObject for [T]
 where
@@ -38,3 +38,45 @@ where
         TypeInfo::list_of(T::type_output())
     }
 }
+
+impl<'py, T> IntoPyObject<'py> for Vec<T>
+where
+    T: IntoPyObject<'py>,
+{
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut iter = self.into_iter().map(|e| e.into_pyobject(py));
+        try_new_from_iter(py, &mut iter).map(Bound::into_any)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_output() -> TypeInfo {
+        TypeInfo::list_of(T::type_output())
+    }
+}
+
+impl<T> IntoPy<PyObject> for &'_ Vec<T>
+where
+    T: ToPyObject,
+{
+    #[inline]
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.as_slice().to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!([T] &'_ Vec<T> where T: ToPyObject);
+
+#[cfg(test)]
+mod tests {
+    use crate::types::any::PyAnyMethods;
+    use crate::{IntoPy, Python};
+
+    #[test]
+    fn test_vec_reference_into_py() {
+        Python::with_gil(|py| {
+            let vec = vec![1.5, 2.5];
+            let list = (&vec).into_py(py).into_bound(py);
+            assert_eq!(list.extract::<Vec<f64>>().unwrap(), vec);
+        });
+    }
+}
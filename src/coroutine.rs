@@ -11,13 +11,18 @@ use std::{
 use pyo3_macros::{pyclass, pymethods};
 
 use crate::{
-    coroutine::{cancel::ThrowCallback, waker::AsyncioWaker},
+    coroutine::{
+        cancel::ThrowCallback,
+        waker::{AsyncRuntime, CoroutineWaker},
+    },
     exceptions::{PyAttributeError, PyRuntimeError, PyStopIteration},
+    intern,
     panic::PanicException,
-    types::{PyIterator, PyString},
-    IntoPy, Py, PyAny, PyErr, PyNativeType, PyObject, PyResult, Python,
+    types::{any::PyAnyMethods, PyIterator, PyString},
+    IntoPy, Py, PyErr, PyObject, PyResult, Python,
 };
 
+pub mod asyncio;
 pub(crate) mod cancel;
 mod waker;
 
@@ -32,7 +37,9 @@ pub struct Coroutine {
     qualname_prefix: Option<&'static str>,
     throw_callback: Option<ThrowCallback>,
     future: Option<Pin<Box<dyn Future<Output = PyResult<PyObject>> + Send>>>,
-    waker: Option<Arc<AsyncioWaker>>,
+    waker: Option<Arc<CoroutineWaker>>,
+    waiter: Option<PyObject>,
+    runtime: Option<AsyncRuntime>,
 }
 
 impl Coroutine {
@@ -64,15 +71,35 @@ impl Coroutine {
             throw_callback,
             future: Some(Box::pin(wrap)),
             waker: None,
+            waiter: None,
+            runtime: None,
         }
     }
 
-    fn poll(&mut self, py: Python<'_>, throw: Option<PyObject>) -> PyResult<PyObject> {
+    fn poll(
+        &mut self,
+        py: Python<'_>,
+        value: Option<PyObject>,
+        throw: Option<PyObject>,
+    ) -> PyResult<PyObject> {
         // raise if the coroutine has already been run to completion
-        let future_rs = match self.future {
-            Some(ref mut fut) => fut,
-            None => return Err(PyRuntimeError::new_err(COROUTINE_REUSED_ERROR)),
+        if self.future.is_none() {
+            return Err(PyRuntimeError::new_err(COROUTINE_REUSED_ERROR));
+        }
+        // resume the awaitable returned by the waker, which can raise,
+        // e.g. when trio cancels the task
+        let throw = match (throw, self.waiter.take()) {
+            (None, Some(waiter)) => match waiter.call_method1(py, intern!(py, "send"), (value,)) {
+                Ok(next) => {
+                    self.waiter = Some(waiter);
+                    return Ok(next);
+                }
+                Err(err) if err.is_instance_of::<PyStopIteration>(py) => None,
+                Err(err) => Some(err.into_value(py).into()),
+            },
+            (throw, _) => throw,
         };
+        let future_rs = self.future.as_mut().unwrap();
         // reraise thrown exception it
         match (throw, &self.throw_callback) {
             (Some(exc), Some(cb)) => cb.throw(exc.as_ref(py)),
@@ -86,7 +113,7 @@ impl Coroutine {
         if let Some(waker) = self.waker.as_mut().and_then(Arc::get_mut) {
             waker.reset();
         } else {
-            self.waker = Some(Arc::new(AsyncioWaker::new()));
+            self.waker = Some(Arc::new(CoroutineWaker::new()));
         }
         let waker = Waker::from(self.waker.clone().unwrap());
         // poll the Rust future and forward its results if ready
@@ -99,21 +126,23 @@ impl Coroutine {
             }
             Err(err) => {
                 self.close();
-                return Err(PanicException::from_panic_payload(err));
+                return Err(PanicException::from_panic_payload(py, err));
             }
             _ => {}
         }
-        // otherwise, initialize the waker `asyncio.Future`
-        if let Some(future) = self.waker.as_ref().unwrap().initialize_future(py)? {
-            // `asyncio.Future` must be awaited; fortunately, it implements `__iter__ = __await__`
-            // and will yield itself if its result has not been set in polling above
-            if let Some(future) = PyIterator::from_bound_object(&future.as_borrowed())
-                .unwrap()
-                .next()
-            {
-                // future has not been leaked into Python for now, and Rust code can only call
-                // `set_result(None)` in `Wake` implementation, so it's safe to unwrap
-                return Ok(future.unwrap().into());
+        // otherwise, initialize the waker, and delegate to the awaitable it returns
+        // (an `asyncio.Future`, or a trio wait) until the coroutine is resumed
+        let runtime = match self.runtime {
+            Some(runtime) => runtime,
+            None => *self.runtime.insert(AsyncRuntime::detect(py)?),
+        };
+        let waker = self.waker.as_ref().unwrap();
+        if let Some(awaitable) = waker.initialize_future(py, runtime)? {
+            let waiter = awaitable.call_method0(intern!(py, "__await__"))?;
+            if let Some(next) = PyIterator::from_bound_object(&waiter)?.next() {
+                let next = next?.into();
+                self.waiter = Some(waiter.unbind());
+                return Ok(next);
             }
         }
         // if waker has been waken during future polling, this is roughly equivalent to
@@ -143,18 +172,19 @@ impl Coroutine {
         }
     }
 
-    fn send(&mut self, py: Python<'_>, _value: &PyAny) -> PyResult<PyObject> {
-        self.poll(py, None)
+    fn send(&mut self, py: Python<'_>, value: PyObject) -> PyResult<PyObject> {
+        self.poll(py, Some(value), None)
     }
 
     fn throw(&mut self, py: Python<'_>, exc: PyObject) -> PyResult<PyObject> {
-        self.poll(py, Some(exc))
+        self.poll(py, None, Some(exc))
     }
 
     fn close(&mut self) {
         // the Rust future is dropped, and the field set to `None`
         // to indicate the coroutine has been run to completion
         drop(self.future.take());
+        drop(self.waiter.take());
     }
 
     fn __await__(self_: Py<Self>) -> Py<Self> {
@@ -162,6 +192,6 @@ impl Coroutine {
     }
 
     fn __next__(&mut self, py: Python<'_>) -> PyResult<PyObject> {
-        self.poll(py, None)
+        self.poll(py, None, None)
     }
 }
@@ -0,0 +1,256 @@
+//! Conversions between Rust futures and Python `asyncio` awaitables.
+//!
+//! - [`future_into_py`] wraps a Rust [`Future`] into a Python awaitable, which is driven by the
+//!   `asyncio` event loop awaiting it.
+//! - [`into_future`] schedules a Python awaitable on an `asyncio` event loop and returns a Rust
+//!   [`Future`] resolving to its result. This future is runtime-agnostic: it can be awaited in
+//!   another PyO3 coroutine, or on a Rust executor running on a different thread.
+//!
+//! The event loop used by [`into_future`] is the one set for the current task with [`scope`],
+//! falling back to the event loop running in the current thread.
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+use parking_lot::Mutex;
+use pyo3_macros::{pyclass, pyfunction, pymethods};
+
+use crate::coroutine::Coroutine;
+use crate::sync::GILOnceCell;
+use crate::types::any::PyAnyMethods;
+use crate::types::PyCFunction;
+use crate::{intern, wrap_pyfunction, Bound, IntoPy, Py, PyAny, PyErr, PyObject, PyResult, Python};
+
+thread_local! {
+    static TASK_EVENT_LOOP: RefCell<Option<PyObject>> = RefCell::new(None);
+}
+
+/// Wrap a Rust future into a Python awaitable.
+///
+/// The future is polled each time the returned coroutine is resumed by the `asyncio` event loop,
+/// with the GIL held, exactly like the `async fn` wrapped by `#[pyfunction]`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(dead_code)]
+/// use pyo3::coroutine::asyncio::future_into_py;
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction]
+/// fn answer(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+///     future_into_py(py, async { Ok::<_, PyErr>(42) }).map(Bound::into_any)
+/// }
+/// ```
+pub fn future_into_py<F, T, E>(py: Python<'_>, future: F) -> PyResult<Bound<'_, Coroutine>>
+where
+    F: Future<Output = Result<T, E>> + Send + 'static,
+    T: IntoPy<PyObject>,
+    E: Into<PyErr>,
+{
+    Bound::new(py, Coroutine::new(None, None, None, future))
+}
+
+/// Convert a Python awaitable into a Rust future.
+///
+/// The awaitable is scheduled as an `asyncio` task on the event loop returned by
+/// [`get_current_loop`]; the returned future resolves to the task result, or to the exception
+/// raised by the task (`asyncio.CancelledError` if the task has been cancelled).
+///
+/// The returned future does not require the GIL to be polled, so it can be awaited on any Rust
+/// executor. Dropping it does not cancel the `asyncio` task.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(dead_code)]
+/// use pyo3::coroutine::asyncio::into_future;
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction]
+/// async fn sleep_then_add(a: i32, b: i32) -> PyResult<i32> {
+///     let sleep = Python::with_gil(|py| {
+///         let sleep = py.import_bound("asyncio")?.getattr("sleep")?.call1((0.01, a))?;
+///         into_future(&sleep)
+///     })?;
+///     let a = sleep.await?;
+///     Python::with_gil(|py| Ok(a.extract::<i32>(py)? + b))
+/// }
+/// ```
+pub fn into_future(
+    awaitable: &Bound<'_, PyAny>,
+) -> PyResult<impl Future<Output = PyResult<PyObject>> + Send + 'static> {
+    static SPAWN_AWAITABLE: GILOnceCell<Py<PyCFunction>> = GILOnceCell::new();
+    let py = awaitable.py();
+    let spawn_awaitable = SPAWN_AWAITABLE
+        .get_or_try_init(py, || wrap_pyfunction!(spawn_awaitable, py).map(Into::into))?;
+    let event_loop = get_current_loop(py)?;
+    let shared = Arc::new(Mutex::new(Inner::default()));
+    let sender = Py::new(py, ResultSender(shared.clone()))?;
+    // the awaitable must be scheduled from the event loop thread,
+    // which may not be the current one
+    event_loop.call_method1(
+        intern!(py, "call_soon_threadsafe"),
+        (spawn_awaitable, awaitable, sender),
+    )?;
+    Ok(AwaitableFuture(shared))
+}
+
+/// Run a future with `event_loop` as the event loop used by [`into_future`].
+///
+/// This is required to call [`into_future`] from a future running on a Rust executor, for
+/// example a tokio worker thread, as there is no `asyncio` event loop running in that thread.
+/// The event loop is attached to the future itself, so it is kept when the future is moved
+/// between threads.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(dead_code)]
+/// use pyo3::coroutine::asyncio::{get_current_loop, into_future, scope};
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction]
+/// fn spawn_sleep(py: Python<'_>) -> PyResult<()> {
+///     let event_loop = get_current_loop(py)?.unbind();
+///     let future = scope(event_loop, async {
+///         let sleep = Python::with_gil(|py| {
+///             let sleep = py.import_bound("asyncio")?.getattr("sleep")?.call1((0.01,))?;
+///             into_future(&sleep)
+///         })?;
+///         sleep.await
+///     });
+///     // `future` can now be spawned on any Rust executor
+///     # drop(future);
+///     Ok(())
+/// }
+/// ```
+pub fn scope<F>(event_loop: PyObject, future: F) -> impl Future<Output = F::Output> + Send
+where
+    F: Future + Send,
+{
+    Scope {
+        event_loop: Some(event_loop),
+        future: Box::pin(future),
+    }
+}
+
+/// Get the event loop used by [`into_future`].
+///
+/// This is the event loop set with [`scope`] for the current future, or the running event loop
+/// of the current thread, as returned by `asyncio.get_running_loop()`.
+pub fn get_current_loop(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    static GET_RUNNING_LOOP: GILOnceCell<PyObject> = GILOnceCell::new();
+    let task_loop = TASK_EVENT_LOOP.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .map(|event_loop| event_loop.bind(py).clone())
+    });
+    if let Some(event_loop) = task_loop {
+        return Ok(event_loop);
+    }
+    let import = || -> PyResult<_> {
+        let module = py.import_bound("asyncio")?;
+        Ok(module.getattr("get_running_loop")?.into())
+    };
+    GET_RUNNING_LOOP
+        .get_or_try_init(py, import)?
+        .bind(py)
+        .call0()
+}
+
+struct Scope<F> {
+    event_loop: Option<PyObject>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Scope<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Swap the event loop in and out of the thread local, restoring the previous one even
+        // if the future panics; no reference count is touched, so the GIL is not required.
+        struct Restore<'a>(&'a mut Option<PyObject>);
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                TASK_EVENT_LOOP.with(|cell| std::mem::swap(self.0, &mut *cell.borrow_mut()));
+            }
+        }
+        let this = &mut *self;
+        TASK_EVENT_LOOP.with(|cell| std::mem::swap(&mut this.event_loop, &mut *cell.borrow_mut()));
+        let _restore = Restore(&mut this.event_loop);
+        this.future.as_mut().poll(cx)
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    result: Option<PyResult<PyObject>>,
+    waker: Option<Waker>,
+}
+
+struct AwaitableFuture(Arc<Mutex<Inner>>);
+
+impl Future for AwaitableFuture {
+    type Output = PyResult<PyObject>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.0.lock();
+        if let Some(result) = inner.result.take() {
+            return Poll::Ready(result);
+        }
+        if let Some(ref waker) = inner.waker {
+            if cx.waker().will_wake(waker) {
+                return Poll::Pending;
+            }
+        }
+        inner.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// `asyncio.Future` done callback, forwarding the result to the associated [`AwaitableFuture`].
+#[pyclass(crate = "crate")]
+struct ResultSender(Arc<Mutex<Inner>>);
+
+impl ResultSender {
+    fn send(&self, result: PyResult<PyObject>) {
+        let mut inner = self.0.lock();
+        inner.result = Some(result);
+        if let Some(waker) = inner.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[pymethods(crate = "crate")]
+impl ResultSender {
+    fn __call__(&self, future: &Bound<'_, PyAny>) {
+        self.send(
+            future
+                .call_method0(intern!(future.py(), "result"))
+                .map(Bound::unbind),
+        );
+    }
+}
+
+/// Wrap the awaitable into an `asyncio.Task` and register the result callback.
+///
+/// Called in the event loop thread with `call_soon_threadsafe`.
+#[pyfunction(crate = "crate")]
+fn spawn_awaitable(awaitable: &Bound<'_, PyAny>, sender: &Bound<'_, ResultSender>) {
+    let py = awaitable.py();
+    let spawn = || -> PyResult<()> {
+        let task = py
+            .import_bound("asyncio")?
+            .call_method1(intern!(py, "ensure_future"), (awaitable,))?;
+        task.call_method1(intern!(py, "add_done_callback"), (sender,))?;
+        Ok(())
+    };
+    // the error cannot be raised in the event loop, so forward it to the Rust future instead
+    if let Err(err) = spawn() {
+        sender.borrow().send(Err(err));
+    }
+}
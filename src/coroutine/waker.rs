@@ -1,22 +1,24 @@
 use crate::sync::GILOnceCell;
 use crate::types::any::PyAnyMethods;
-use crate::types::PyCFunction;
-use crate::{intern, wrap_pyfunction, Py, PyAny, PyObject, PyResult, Python};
-use pyo3_macros::pyfunction;
+use crate::types::dict::PyDictMethods;
+use crate::types::{PyCFunction, PyDict};
+use crate::{intern, wrap_pyfunction, Bound, Py, PyAny, PyObject, PyResult, Python};
+use pyo3_macros::{pyclass, pyfunction, pymethods};
 use std::sync::Arc;
 use std::task::Wake;
 
-/// Lazy `asyncio.Future` wrapper, implementing [`Wake`] by calling `Future.set_result`.
+/// Lazy waiter wrapper, implementing [`Wake`] by resuming the coroutine in its async runtime.
 ///
-/// asyncio future is let uninitialized until [`initialize_future`][1] is called.
-/// If [`wake`][2] is called before future initialization (during Rust future polling),
-/// [`initialize_future`][1] will return `None` (it is roughly equivalent to `asyncio.sleep(0)`)
+/// The waiter is let uninitialized until [`initialize_future`][1] is called.
+/// If [`wake`][2] is called before waiter initialization (during Rust future polling),
+/// [`initialize_future`][1] will return a checkpoint of the runtime instead, if it needs one
+/// (for asyncio, it is roughly equivalent to `asyncio.sleep(0)`)
 ///
-/// [1]: AsyncioWaker::initialize_future
-/// [2]: AsyncioWaker::wake
-pub struct AsyncioWaker(GILOnceCell<Option<LoopAndFuture>>);
+/// [1]: CoroutineWaker::initialize_future
+/// [2]: CoroutineWaker::wake
+pub struct CoroutineWaker(GILOnceCell<Option<Waiter>>);
 
-impl AsyncioWaker {
+impl CoroutineWaker {
     pub(super) fn new() -> Self {
         Self(GILOnceCell::new())
     }
@@ -25,66 +27,160 @@ impl AsyncioWaker {
         self.0.take();
     }
 
-    pub(super) fn initialize_future<'a>(&'a self, py: Python<'a>) -> PyResult<Option<&'a PyAny>> {
-        let init = || LoopAndFuture::new(py).map(Some);
-        let loop_and_future = self.0.get_or_try_init(py, init)?.as_ref();
-        Ok(loop_and_future.map(|LoopAndFuture { future, .. }| future.as_ref(py)))
+    /// Returns the awaitable the coroutine must delegate to until it is woken.
+    pub(super) fn initialize_future<'py>(
+        &self,
+        py: Python<'py>,
+        runtime: AsyncRuntime,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let init = || Waiter::new(py, runtime).map(Some);
+        match self.0.get_or_try_init(py, init)? {
+            Some(waiter) => waiter.wait(py).map(Some),
+            None => runtime.checkpoint(py),
+        }
     }
 }
 
-impl Wake for AsyncioWaker {
+impl Wake for CoroutineWaker {
     fn wake(self: Arc<Self>) {
         self.wake_by_ref()
     }
 
     fn wake_by_ref(self: &Arc<Self>) {
         Python::with_gil(|gil| {
-            if let Some(loop_and_future) = self.0.get_or_init(gil, || None) {
-                loop_and_future
-                    .set_result(gil)
+            if let Some(waiter) = self.0.get_or_init(gil, || None) {
+                waiter
+                    .wake(gil)
                     .expect("unexpected error in coroutine waker");
             }
         });
     }
 }
 
-struct LoopAndFuture {
-    event_loop: PyObject,
-    future: PyObject,
+/// Python async runtime driving the coroutine.
+#[derive(Clone, Copy)]
+pub(super) enum AsyncRuntime {
+    Asyncio,
+    Trio,
 }
 
-impl LoopAndFuture {
-    fn new(py: Python<'_>) -> PyResult<Self> {
-        static GET_RUNNING_LOOP: GILOnceCell<PyObject> = GILOnceCell::new();
-        let import = || -> PyResult<_> {
-            let module = py.import_bound("asyncio")?;
-            Ok(module.getattr("get_running_loop")?.into())
-        };
-        let event_loop = GET_RUNNING_LOOP.get_or_try_init(py, import)?.call0(py)?;
-        let future = event_loop.call_method0(py, "create_future")?;
-        Ok(Self { event_loop, future })
+impl AsyncRuntime {
+    /// Detect the runtime the same way as `sniffio`: trio is checked first, but only if it has
+    /// already been imported, otherwise it cannot be running. This covers anyio too, as it runs
+    /// on top of one of these runtimes.
+    ///
+    /// A coroutine is driven by a single runtime, so this only needs to run once per coroutine.
+    pub(super) fn detect(py: Python<'_>) -> PyResult<Self> {
+        let modules = py.import_bound("sys")?.getattr(intern!(py, "modules"))?;
+        if let Some(trio) = modules.downcast::<PyDict>()?.get_item("trio")? {
+            let lowlevel = trio.getattr(intern!(py, "lowlevel"))?;
+            if lowlevel.call_method0(intern!(py, "current_task")).is_ok() {
+                return Ok(AsyncRuntime::Trio);
+            }
+        }
+        Ok(AsyncRuntime::Asyncio)
+    }
+
+    /// Awaitable yielding once to the runtime, or `None` if yielding `None` is enough.
+    fn checkpoint(self, py: Python<'_>) -> PyResult<Option<Bound<'_, PyAny>>> {
+        match self {
+            AsyncRuntime::Asyncio => Ok(None),
+            AsyncRuntime::Trio => trio_lowlevel(py)?
+                .call_method0(intern!(py, "checkpoint"))
+                .map(Some),
+        }
     }
+}
+
+fn trio_lowlevel(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    static TRIO_LOWLEVEL: GILOnceCell<PyObject> = GILOnceCell::new();
+    let import = || -> PyResult<_> { Ok(py.import_bound("trio.lowlevel")?.into()) };
+    Ok(TRIO_LOWLEVEL.get_or_try_init(py, import)?.bind(py).clone())
+}
+
+enum Waiter {
+    Asyncio {
+        event_loop: PyObject,
+        future: PyObject,
+    },
+    Trio {
+        token: PyObject,
+        waiter: Py<TrioWaiter>,
+    },
+}
 
-    fn set_result(&self, py: Python<'_>) -> PyResult<()> {
-        static RELEASE_WAITER: GILOnceCell<Py<PyCFunction>> = GILOnceCell::new();
-        let release_waiter = RELEASE_WAITER
-            .get_or_try_init(py, || wrap_pyfunction!(release_waiter, py).map(Into::into))?;
-        // `Future.set_result` must be called in event loop thread,
-        // so it requires `call_soon_threadsafe`
-        let call_soon_threadsafe = self.event_loop.call_method1(
-            py,
-            intern!(py, "call_soon_threadsafe"),
-            (release_waiter, self.future.as_ref(py)),
-        );
-        if let Err(err) = call_soon_threadsafe {
-            // `call_soon_threadsafe` will raise if the event loop is closed;
-            // instead of catching an unspecific `RuntimeError`, check directly if it's closed.
-            let is_closed = self.event_loop.call_method0(py, "is_closed")?;
-            if !is_closed.extract(py)? {
-                return Err(err);
+impl Waiter {
+    fn new(py: Python<'_>, runtime: AsyncRuntime) -> PyResult<Self> {
+        match runtime {
+            AsyncRuntime::Asyncio => {
+                static GET_RUNNING_LOOP: GILOnceCell<PyObject> = GILOnceCell::new();
+                let import = || -> PyResult<_> {
+                    let module = py.import_bound("asyncio")?;
+                    Ok(module.getattr("get_running_loop")?.into())
+                };
+                let event_loop = GET_RUNNING_LOOP.get_or_try_init(py, import)?.call0(py)?;
+                let future = event_loop.call_method0(py, "create_future")?;
+                Ok(Waiter::Asyncio { event_loop, future })
+            }
+            AsyncRuntime::Trio => {
+                let lowlevel = trio_lowlevel(py)?;
+                let token = lowlevel.call_method0("current_trio_token")?.unbind();
+                let task = lowlevel.call_method0("current_task")?.unbind();
+                let waiter = Py::new(py, TrioWaiter { task, done: false })?;
+                Ok(Waiter::Trio { token, waiter })
+            }
+        }
+    }
+
+    fn wait<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Waiter::Asyncio { future, .. } => Ok(future.bind(py).clone()),
+            Waiter::Trio { waiter, .. } => {
+                let abort = waiter.bind(py).as_any().getattr(intern!(py, "abort"))?;
+                trio_lowlevel(py)?.call_method1(intern!(py, "wait_task_rescheduled"), (abort,))
+            }
+        }
+    }
+
+    fn wake(&self, py: Python<'_>) -> PyResult<()> {
+        match self {
+            Waiter::Asyncio { event_loop, future } => {
+                static RELEASE_WAITER: GILOnceCell<Py<PyCFunction>> = GILOnceCell::new();
+                let release_waiter = RELEASE_WAITER
+                    .get_or_try_init(py, || wrap_pyfunction!(release_waiter, py).map(Into::into))?;
+                // `Future.set_result` must be called in event loop thread,
+                // so it requires `call_soon_threadsafe`
+                let call_soon_threadsafe = event_loop.call_method1(
+                    py,
+                    intern!(py, "call_soon_threadsafe"),
+                    (release_waiter, future.as_ref(py)),
+                );
+                if let Err(err) = call_soon_threadsafe {
+                    // `call_soon_threadsafe` will raise if the event loop is closed;
+                    // instead of catching an unspecific `RuntimeError`, check directly if it's closed.
+                    let is_closed = event_loop.call_method0(py, "is_closed")?;
+                    if !is_closed.extract(py)? {
+                        return Err(err);
+                    }
+                }
+                Ok(())
+            }
+            Waiter::Trio { token, waiter } => {
+                // `reschedule` must be called in trio thread, so it requires `run_sync_soon`
+                let release = waiter.bind(py).as_any().getattr(intern!(py, "release"))?;
+                let run_sync_soon = token
+                    .bind(py)
+                    .call_method1(intern!(py, "run_sync_soon"), (release,));
+                if let Err(err) = run_sync_soon {
+                    // `run_sync_soon` raises `RunFinishedError` if the run is finished
+                    let run_finished = py.import_bound("trio")?.getattr("RunFinishedError")?;
+                    if !err.matches(py, run_finished) {
+                        return Err(err);
+                    }
+                }
+                Ok(())
             }
         }
-        Ok(())
     }
 }
 
@@ -100,3 +196,30 @@ fn release_waiter(future: &PyAny) -> PyResult<()> {
     }
     Ok(())
 }
+
+/// Trio task suspended in `trio.lowlevel.wait_task_rescheduled`.
+///
+/// The task must be rescheduled exactly once, either by the waker, or by trio itself when the
+/// wait is aborted (i.e. the task is cancelled).
+#[pyclass(crate = "crate")]
+struct TrioWaiter {
+    task: PyObject,
+    done: bool,
+}
+
+#[pymethods(crate = "crate")]
+impl TrioWaiter {
+    fn abort(&mut self, py: Python<'_>, _raise_cancel: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        self.done = true;
+        let abort = trio_lowlevel(py)?.getattr(intern!(py, "Abort"))?;
+        Ok(abort.getattr(intern!(py, "SUCCEEDED"))?.unbind())
+    }
+
+    fn release(&mut self, py: Python<'_>) -> PyResult<()> {
+        if !self.done {
+            self.done = true;
+            trio_lowlevel(py)?.call_method1(intern!(py, "reschedule"), (&self.task,))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+//! Debugging tools for finding reference leaks, enabled by the `debug-refcounts` feature.
+//!
+//! With this feature, PyO3 records a backtrace each time an owned reference is registered in the
+//! GIL pool (for example by APIs which return `&'py PyAny`). These references are only released
+//! when the [`GILPool`](crate::GILPool) they belong to is dropped, so a loop which creates many
+//! of them while holding the GIL can grow memory without bound. [`pooled_objects`] shows which
+//! references are accumulating and where they were created.
+//!
+//! The hook set with [`set_leak_hook`] receives the references which may have leaked:
+//!
+//! - when a [`GILPool`](crate::GILPool) is dropped, each of its references whose object is kept
+//!   alive by other references after the pool releases it ([`LeakKind::OutlivedPool`]);
+//! - when [`with_embedded_python_interpreter`] finalizes the interpreter, each reference which is
+//!   still registered, having been created outside of any pool ([`LeakKind::NeverReleased`]).
+//!
+//! Capturing backtraces is slow, so this feature should only be used while debugging. Backtraces
+//! need Rust 1.65 or later; with older compilers only the objects themselves are recorded.
+//!
+//! [`with_embedded_python_interpreter`]: crate::with_embedded_python_interpreter
+#![allow(clippy::incompatible_msrv)] // `std::backtrace` is only used on Rust 1.65 and later
+
+use crate::types::any::PyAnyMethods;
+use crate::{ffi, gil, Python};
+use parking_lot::{const_mutex, Mutex};
+#[cfg(std_backtrace)]
+use std::backtrace::Backtrace;
+use std::fmt;
+use std::ptr::NonNull;
+#[cfg(std_backtrace)]
+use std::sync::Arc;
+
+/// Where a reference was registered in the pool.
+#[derive(Clone, Debug)]
+pub(crate) struct RegisteredAt {
+    #[cfg(std_backtrace)]
+    backtrace: Arc<Backtrace>,
+}
+
+impl RegisteredAt {
+    pub(crate) fn capture() -> Self {
+        RegisteredAt {
+            #[cfg(std_backtrace)]
+            backtrace: Arc::new(Backtrace::force_capture()),
+        }
+    }
+}
+
+/// An owned reference held by the GIL pool.
+#[derive(Clone, Debug)]
+pub struct PooledObject {
+    ptr: NonNull<ffi::PyObject>,
+    type_name: String,
+    refcnt: isize,
+    #[cfg_attr(not(std_backtrace), allow(dead_code))]
+    registered_at: RegisteredAt,
+}
+
+impl PooledObject {
+    /// The pointer to the object.
+    pub fn as_ptr(&self) -> *mut ffi::PyObject {
+        self.ptr.as_ptr()
+    }
+
+    /// The name of the object's type.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The reference count of the object when [`pooled_objects`] was called.
+    pub fn refcnt(&self) -> isize {
+        self.refcnt
+    }
+
+    /// Where the reference was registered in the pool.
+    #[cfg(std_backtrace)]
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.registered_at.backtrace
+    }
+}
+
+impl fmt::Display for PooledObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} object at {:p} (refcount {})",
+            self.type_name, self.ptr, self.refcnt
+        )?;
+        #[cfg(std_backtrace)]
+        write!(f, ", registered at:\n{}", self.registered_at.backtrace)?;
+        Ok(())
+    }
+}
+
+/// Why a reference was passed to the [`LeakHook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeakKind {
+    /// The GIL pool holding the reference was dropped, but other references keep the object
+    /// alive.
+    ///
+    /// This is expected for objects which are stored elsewhere, but objects which are expected to
+    /// be temporary point at a leaked reference.
+    OutlivedPool,
+    /// The interpreter was finalized while the reference was still registered in the GIL pool.
+    NeverReleased,
+}
+
+/// Callback receiving a reference which may have leaked.
+///
+/// See [`set_leak_hook`].
+pub type LeakHook = fn(py: Python<'_>, object: &PooledObject, kind: LeakKind);
+
+static LEAK_HOOK: Mutex<Option<LeakHook>> = const_mutex(None);
+
+/// Sets the hook called for each reference which may have leaked, returning the previous one.
+///
+/// The hook is called when a [`GILPool`](crate::GILPool) is dropped, for each of its references
+/// whose object outlives it, and when
+/// [`with_embedded_python_interpreter`](crate::with_embedded_python_interpreter) finalizes the
+/// interpreter, for each reference still registered in the pool. See [`LeakKind`].
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::debug_refcounts::{set_leak_hook, LeakKind};
+///
+/// set_leak_hook(Some(|_py, object, kind| {
+///     if kind == LeakKind::NeverReleased {
+///         eprintln!("reference never released by a GILPool: {}", object);
+///     }
+/// }));
+/// ```
+pub fn set_leak_hook(hook: Option<LeakHook>) -> Option<LeakHook> {
+    std::mem::replace(&mut *LEAK_HOOK.lock(), hook)
+}
+
+pub(crate) fn leak_hook() -> Option<LeakHook> {
+    *LEAK_HOOK.lock()
+}
+
+/// Returns the references currently held by this thread's GIL pools, oldest first.
+pub fn pooled_objects(py: Python<'_>) -> Vec<PooledObject> {
+    gil::owned_objects_with_backtraces()
+        .into_iter()
+        .map(|(ptr, registered_at)| PooledObject::new(py, ptr, registered_at))
+        .collect()
+}
+
+impl PooledObject {
+    fn new(py: Python<'_>, ptr: NonNull<ffi::PyObject>, registered_at: RegisteredAt) -> Self {
+        // Safety: objects in the pool are valid until the pool releases them
+        let obj = unsafe { crate::Borrowed::from_ptr(py, ptr.as_ptr()) };
+        let type_name = obj
+            .get_type()
+            .name()
+            .map_or_else(|_| "<unknown>".to_owned(), |name| name.to_string());
+        PooledObject {
+            ptr,
+            type_name,
+            refcnt: obj.get_refcnt(),
+            registered_at,
+        }
+    }
+}
+
+/// Passes the references of a dropped GIL pool whose objects will outlive it to the leak hook,
+/// before the pool releases them.
+pub(crate) fn report_outlived_pool(
+    py: Python<'_>,
+    objects: &[NonNull<ffi::PyObject>],
+    registered_at: Vec<RegisteredAt>,
+) {
+    let hook = match leak_hook() {
+        Some(hook) => hook,
+        None => return,
+    };
+    for (ptr, registered_at) in objects.iter().zip(registered_at) {
+        // The pool holds one reference, so the object is only freed if it is the last one.
+        if unsafe { ffi::Py_REFCNT(ptr.as_ptr()) } <= 1 || is_immortal(*ptr) {
+            continue;
+        }
+        hook(
+            py,
+            &PooledObject::new(py, *ptr, registered_at),
+            LeakKind::OutlivedPool,
+        );
+    }
+}
+
+/// Immortal objects such as `None` are never freed, so outliving a pool is not a leak for them.
+fn is_immortal(ptr: NonNull<ffi::PyObject>) -> bool {
+    #[cfg(any(Py_3_12, py_sys_config = "Py_GIL_DISABLED"))]
+    {
+        unsafe { ffi::_Py_IsImmortal(ptr.as_ptr()) != 0 }
+    }
+    #[cfg(not(any(Py_3_12, py_sys_config = "Py_GIL_DISABLED")))]
+    {
+        let _ = ptr;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pooled_objects, set_leak_hook, LeakKind, PooledObject};
+    use crate::{types::PyList, IntoPy, Py, Python};
+    use parking_lot::{const_mutex, Mutex};
+
+    #[test]
+    fn test_pooled_objects() {
+        Python::with_gil(|py| {
+            let before = pooled_objects(py).len();
+
+            let pool = unsafe { py.new_pool() };
+            let pool_py = pool.python();
+            #[allow(deprecated)]
+            let list = PyList::empty(pool_py);
+            let objects = pooled_objects(pool_py);
+            assert_eq!(objects.len(), before + 1);
+
+            let object = objects.last().unwrap();
+            assert_eq!(object.as_ptr(), list.as_ptr());
+            assert_eq!(object.type_name(), "list");
+            assert_eq!(object.refcnt(), 1);
+            assert!(object.to_string().starts_with("list object at"));
+
+            drop(pool);
+            assert_eq!(pooled_objects(py).len(), before);
+        });
+    }
+
+    #[test]
+    fn test_leak_hook_on_pool_drop() {
+        static REPORTED: Mutex<Vec<(usize, LeakKind)>> = const_mutex(Vec::new());
+
+        fn hook(_py: Python<'_>, object: &PooledObject, kind: LeakKind) {
+            REPORTED.lock().push((object.as_ptr() as usize, kind));
+        }
+
+        Python::with_gil(|py| {
+            let previous = set_leak_hook(Some(hook));
+
+            let pool = unsafe { py.new_pool() };
+            #[allow(deprecated)]
+            let temporary = PyList::empty(pool.python()).as_ptr() as usize;
+            #[allow(deprecated)]
+            let kept: Py<PyList> = PyList::empty(pool.python()).into_py(py);
+            drop(pool);
+
+            set_leak_hook(previous);
+            let reported = REPORTED.lock();
+            let kept_ptr = kept.as_ptr() as usize;
+            assert!(reported.contains(&(kept_ptr, LeakKind::OutlivedPool)));
+            assert!(!reported.iter().any(|(ptr, _)| *ptr == temporary));
+        });
+    }
+}
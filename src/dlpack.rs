@@ -0,0 +1,624 @@
+//! Support for exchanging tensors with other libraries through the [DLPack] protocol.
+//!
+//! DLPack is the protocol behind `__dlpack__` and `__dlpack_device__`, which NumPy, PyTorch, JAX
+//! and others use to share tensor memory without copying. A producer hands out a
+//! [`PyCapsule`] named `"dltensor"` wrapping a [`DLManagedTensor`]; a consumer takes ownership of
+//! the tensor by renaming the capsule to `"used_dltensor"` and calls the tensor's deleter once it
+//! no longer needs the memory.
+//!
+//! - To produce tensors, return the capsule from [`export_vec`] or [`export_borrowed`] in a
+//!   `__dlpack__` method, and a [`DLDevice`] from `__dlpack_device__`.
+//! - To consume tensors, extract a [`DLPackTensor`] from any object implementing `__dlpack__`.
+//!
+//! ```rust
+//! use pyo3::dlpack::{self, DLDevice, DLPackTensor};
+//! use pyo3::prelude::*;
+//! use pyo3::types::PyCapsule;
+//!
+//! #[pyclass]
+//! struct Ramp {
+//!     len: usize,
+//! }
+//!
+//! #[pymethods]
+//! impl Ramp {
+//!     #[pyo3(signature = (**_kwargs))]
+//!     fn __dlpack__<'py>(
+//!         &self,
+//!         py: Python<'py>,
+//!         _kwargs: Option<&Bound<'py, PyAny>>,
+//!     ) -> PyResult<Bound<'py, PyCapsule>> {
+//!         let data: Vec<f32> = (0..self.len).map(|i| i as f32).collect();
+//!         dlpack::export_vec(py, data, &[self.len])
+//!     }
+//!
+//!     fn __dlpack_device__(&self) -> DLDevice {
+//!         DLDevice::cpu()
+//!     }
+//! }
+//!
+//! # fn main() -> PyResult<()> {
+//! Python::with_gil(|py| {
+//!     let ramp = Bound::new(py, Ramp { len: 4 })?;
+//!     // e.g. `numpy.from_dlpack(ramp)` in Python
+//!     let tensor: DLPackTensor = ramp.as_any().extract()?;
+//!     assert_eq!(tensor.shape(), [4]);
+//!     assert_eq!(tensor.as_slice::<f32>()?, [0.0, 1.0, 2.0, 3.0]);
+//!     Ok(())
+//! })
+//! # }
+//! ```
+//!
+//! [DLPack]: https://dmlc.github.io/dlpack/latest/
+
+use crate::exceptions::{PyBufferError, PyValueError};
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::types::{any::PyAnyMethods, PyCapsule, PyTuple};
+use crate::{
+    ffi, intern, Bound, FromPyObject, IntoPy, Py, PyAny, PyErr, PyObject, PyResult, Python,
+};
+use std::fmt;
+use std::os::raw::{c_char, c_void};
+use std::ptr::NonNull;
+
+/// Name of a capsule holding a tensor which has not been consumed yet.
+const DLTENSOR: &[u8] = b"dltensor\0";
+/// Name a consumer gives to a capsule once it has taken ownership of the tensor.
+const USED_DLTENSOR: &[u8] = b"used_dltensor\0";
+
+/// The kind of device a tensor's memory lives on, as in `DLDeviceType`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DLDeviceType(pub i32);
+
+impl DLDeviceType {
+    /// CPU memory.
+    pub const CPU: Self = Self(1);
+    /// CUDA GPU memory.
+    pub const CUDA: Self = Self(2);
+    /// Pinned CUDA CPU memory.
+    pub const CUDA_HOST: Self = Self(3);
+    /// OpenCL device memory.
+    pub const OPENCL: Self = Self(4);
+    /// Vulkan buffer.
+    pub const VULKAN: Self = Self(7);
+    /// Metal buffer on Apple GPUs.
+    pub const METAL: Self = Self(8);
+    /// ROCm GPU memory.
+    pub const ROCM: Self = Self(10);
+    /// Pinned ROCm CPU memory.
+    pub const ROCM_HOST: Self = Self(11);
+    /// CUDA managed (unified) memory.
+    pub const CUDA_MANAGED: Self = Self(13);
+}
+
+/// A device on which a tensor lives, as in `DLDevice`.
+///
+/// Converts to the `(device_type, device_id)` tuple expected from `__dlpack_device__`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DLDevice {
+    /// The kind of device.
+    pub device_type: DLDeviceType,
+    /// The index of the device, for devices of the same kind; 0 for the CPU.
+    pub device_id: i32,
+}
+
+impl DLDevice {
+    /// The CPU device.
+    pub const fn cpu() -> Self {
+        DLDevice {
+            device_type: DLDeviceType::CPU,
+            device_id: 0,
+        }
+    }
+}
+
+impl IntoPy<PyObject> for DLDevice {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        (self.device_type.0, self.device_id).into_py(py)
+    }
+}
+
+into_pyobject_via_into_py!(DLDevice);
+
+impl IntoPy<Py<PyTuple>> for DLDevice {
+    fn into_py(self, py: Python<'_>) -> Py<PyTuple> {
+        (self.device_type.0, self.device_id).into_py(py)
+    }
+}
+
+/// The kind of value held by a tensor, as in `DLDataTypeCode`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DLDataTypeCode(pub u8);
+
+impl DLDataTypeCode {
+    /// Signed integers.
+    pub const INT: Self = Self(0);
+    /// Unsigned integers.
+    pub const UINT: Self = Self(1);
+    /// IEEE floating point numbers.
+    pub const FLOAT: Self = Self(2);
+    /// Opaque handles.
+    pub const OPAQUE_HANDLE: Self = Self(3);
+    /// bfloat16 floating point numbers.
+    pub const BFLOAT: Self = Self(4);
+    /// Complex numbers, made of two floating point numbers.
+    pub const COMPLEX: Self = Self(5);
+    /// Booleans.
+    pub const BOOL: Self = Self(6);
+}
+
+/// The type of the elements of a tensor, as in `DLDataType`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DLDataType {
+    /// The kind of value.
+    pub code: DLDataTypeCode,
+    /// The size of a single lane, in bits.
+    pub bits: u8,
+    /// The number of lanes, for vector types; 1 otherwise.
+    pub lanes: u16,
+}
+
+/// A tensor: a pointer to its memory along with its shape and element type, as in `DLTensor`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLTensor {
+    /// Pointer to the memory of the tensor, which may not be accessible from the CPU.
+    pub data: *mut c_void,
+    /// The device the memory lives on.
+    pub device: DLDevice,
+    /// The number of dimensions.
+    pub ndim: i32,
+    /// The type of the elements.
+    pub dtype: DLDataType,
+    /// The length of the tensor in each dimension, an array of `ndim` values.
+    pub shape: *mut i64,
+    /// The strides of the tensor in each dimension, counted in elements rather than bytes. May be
+    /// null for a C-contiguous tensor.
+    pub strides: *mut i64,
+    /// Offset of the first element from `data`, in bytes.
+    pub byte_offset: u64,
+}
+
+/// A tensor along with the means to release it, as in `DLManagedTensor`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLManagedTensor {
+    /// The tensor.
+    pub dl_tensor: DLTensor,
+    /// Context of the producer, for use by `deleter`.
+    pub manager_ctx: *mut c_void,
+    /// Releases the tensor; called by the consumer once it no longer needs it.
+    pub deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+/// Rust types which can be the elements of a DLPack tensor.
+///
+/// # Safety
+///
+/// `DTYPE` must describe the in-memory representation of the type.
+pub unsafe trait DLPackElement: Copy + Send + 'static {
+    /// The DLPack element type corresponding to this type.
+    const DTYPE: DLDataType;
+}
+
+macro_rules! impl_dlpack_element {
+    ($t:ty, $code:ident) => {
+        unsafe impl DLPackElement for $t {
+            const DTYPE: DLDataType = DLDataType {
+                code: DLDataTypeCode::$code,
+                bits: (std::mem::size_of::<$t>() * 8) as u8,
+                lanes: 1,
+            };
+        }
+    };
+}
+
+impl_dlpack_element!(u8, UINT);
+impl_dlpack_element!(u16, UINT);
+impl_dlpack_element!(u32, UINT);
+impl_dlpack_element!(u64, UINT);
+impl_dlpack_element!(i8, INT);
+impl_dlpack_element!(i16, INT);
+impl_dlpack_element!(i32, INT);
+impl_dlpack_element!(i64, INT);
+impl_dlpack_element!(f32, FLOAT);
+impl_dlpack_element!(f64, FLOAT);
+impl_dlpack_element!(bool, BOOL);
+
+/// The allocation behind a tensor exported by PyO3: freed by `delete_export` when the consumer is
+/// done with the tensor.
+#[repr(C)]
+struct ExportContext<O> {
+    // Must be the first field, so that the `DLManagedTensor` pointer is the context pointer.
+    managed: DLManagedTensor,
+    shape: Vec<i64>,
+    strides: Option<Vec<i64>>,
+    owner: O,
+}
+
+unsafe extern "C" fn delete_export<O>(managed: *mut DLManagedTensor) {
+    drop(Box::from_raw(managed as *mut ExportContext<O>));
+}
+
+/// Destructor of capsules created by PyO3: releases the tensor if no consumer took it.
+unsafe extern "C" fn dlpack_capsule_destructor(capsule: *mut ffi::PyObject) {
+    let name = DLTENSOR.as_ptr() as *const c_char;
+    if ffi::PyCapsule_IsValid(capsule, name) == 1 {
+        let managed = ffi::PyCapsule_GetPointer(capsule, name) as *mut DLManagedTensor;
+        if let Some(deleter) = (*managed).deleter {
+            deleter(managed);
+        }
+    }
+}
+
+fn to_i64(value: usize) -> PyResult<i64> {
+    i64::try_from(value).map_err(|_| PyValueError::new_err("tensor dimension is too large"))
+}
+
+/// Wraps a tensor whose memory is kept alive by `owner` into a `"dltensor"` capsule.
+///
+/// Safety: `data` must point to memory valid for the given shape and strides for as long as
+/// `owner` is alive.
+unsafe fn export<'py, O: Send + 'static>(
+    py: Python<'py>,
+    owner: O,
+    data: *mut c_void,
+    dtype: DLDataType,
+    shape: &[usize],
+    strides: Option<&[isize]>,
+) -> PyResult<Bound<'py, PyCapsule>> {
+    let ndim = i32::try_from(shape.len())
+        .map_err(|_| PyValueError::new_err("tensor has too many dimensions"))?;
+    let shape = shape
+        .iter()
+        .map(|&len| to_i64(len))
+        .collect::<PyResult<Vec<_>>>()?;
+    let strides = match strides {
+        Some(strides) => {
+            if strides.len() != shape.len() {
+                return Err(PyValueError::new_err(
+                    "strides must have one value per dimension",
+                ));
+            }
+            Some(strides.iter().map(|&s| s as i64).collect::<Vec<_>>())
+        }
+        None => None,
+    };
+
+    let context = Box::into_raw(Box::new(ExportContext {
+        managed: DLManagedTensor {
+            dl_tensor: DLTensor {
+                data,
+                device: DLDevice::cpu(),
+                ndim,
+                dtype,
+                shape: std::ptr::null_mut(),
+                strides: std::ptr::null_mut(),
+                byte_offset: 0,
+            },
+            manager_ctx: std::ptr::null_mut(),
+            deleter: Some(delete_export::<O>),
+        },
+        shape,
+        strides,
+        owner,
+    }));
+    // The vectors do not move when the box does, so they can be pointed to from the tensor.
+    let ctx = &mut *context;
+    ctx.managed.dl_tensor.shape = ctx.shape.as_mut_ptr();
+    if let Some(strides) = &mut ctx.strides {
+        ctx.managed.dl_tensor.strides = strides.as_mut_ptr();
+    }
+    ctx.managed.manager_ctx = context as *mut c_void;
+
+    let capsule = ffi::PyCapsule_New(
+        context as *mut c_void,
+        DLTENSOR.as_ptr() as *const c_char,
+        Some(dlpack_capsule_destructor),
+    );
+    if capsule.is_null() {
+        delete_export::<O>(context as *mut DLManagedTensor);
+        return Err(PyErr::fetch(py));
+    }
+    Ok(capsule.assume_owned(py).downcast_into_unchecked())
+}
+
+/// Exports `data`, a C-contiguous tensor of the given shape, as a DLPack capsule.
+///
+/// The tensor takes ownership of the vector, which is freed once the consumer releases it.
+/// Fails if the number of elements in `data` does not match `shape`.
+pub fn export_vec<'py, T: DLPackElement>(
+    py: Python<'py>,
+    mut data: Vec<T>,
+    shape: &[usize],
+) -> PyResult<Bound<'py, PyCapsule>> {
+    if shape.iter().product::<usize>() != data.len() {
+        return Err(PyValueError::new_err(format!(
+            "cannot export {} elements as a tensor of shape {:?}",
+            data.len(),
+            shape
+        )));
+    }
+    let ptr = data.as_mut_ptr() as *mut c_void;
+    // Safety: the vector owns the memory, and moving it does not move its contents.
+    unsafe { export(py, data, ptr, T::DTYPE, shape, None) }
+}
+
+/// Exports memory owned by a Python object, such as a `#[pyclass]`, as a DLPack capsule.
+///
+/// The tensor keeps a reference to `owner` until the consumer releases it. `strides` are counted
+/// in elements; `None` means the tensor is C-contiguous.
+///
+/// # Safety
+///
+/// `data` must point to memory which is valid for the given shape and strides, and which stays
+/// valid, and is not resized or reallocated, for as long as `owner` is alive.
+pub unsafe fn export_borrowed<'py, T: DLPackElement>(
+    owner: &Bound<'py, PyAny>,
+    data: *mut T,
+    shape: &[usize],
+    strides: Option<&[isize]>,
+) -> PyResult<Bound<'py, PyCapsule>> {
+    export(
+        owner.py(),
+        owner.clone().unbind(),
+        data as *mut c_void,
+        T::DTYPE,
+        shape,
+        strides,
+    )
+}
+
+/// A tensor received from another library through the DLPack protocol.
+///
+/// Extracting a `DLPackTensor` from a Python object calls its `__dlpack__` method, or uses the
+/// object directly if it is already a `"dltensor"` capsule, and takes ownership of the tensor.
+/// The tensor is released back to its producer when the `DLPackTensor` is dropped.
+pub struct DLPackTensor {
+    managed: NonNull<DLManagedTensor>,
+}
+
+impl DLPackTensor {
+    /// Takes ownership of the tensor in a `"dltensor"` capsule, marking the capsule as used.
+    ///
+    /// Fails if the capsule has a different name, for example because the tensor was already
+    /// consumed.
+    pub fn from_capsule(capsule: &Bound<'_, PyCapsule>) -> PyResult<Self> {
+        let name = DLTENSOR.as_ptr() as *const c_char;
+        unsafe {
+            if ffi::PyCapsule_IsValid(capsule.as_ptr(), name) != 1 {
+                return Err(PyValueError::new_err(
+                    "expected a DLPack capsule named 'dltensor'; was the tensor already consumed?",
+                ));
+            }
+            let managed = ffi::PyCapsule_GetPointer(capsule.as_ptr(), name) as *mut DLManagedTensor;
+            let managed = NonNull::new(managed).ok_or_else(|| PyErr::fetch(capsule.py()))?;
+            crate::err::error_on_minusone(
+                capsule.py(),
+                ffi::PyCapsule_SetName(capsule.as_ptr(), USED_DLTENSOR.as_ptr() as *const c_char),
+            )?;
+            Ok(DLPackTensor { managed })
+        }
+    }
+
+    /// Takes ownership of the tensor exported by `obj.__dlpack__()`.
+    pub fn from_object(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(capsule) = obj.downcast::<PyCapsule>() {
+            return Self::from_capsule(capsule);
+        }
+        let capsule = obj.call_method0(intern!(obj.py(), "__dlpack__"))?;
+        Self::from_capsule(capsule.downcast()?)
+    }
+
+    /// Gets the underlying `DLTensor`.
+    #[inline]
+    pub fn tensor(&self) -> &DLTensor {
+        // Safety: the tensor is valid until the deleter is called on drop.
+        unsafe { &self.managed.as_ref().dl_tensor }
+    }
+
+    /// Gets the device the tensor's memory lives on.
+    #[inline]
+    pub fn device(&self) -> DLDevice {
+        self.tensor().device
+    }
+
+    /// Gets the type of the elements.
+    #[inline]
+    pub fn dtype(&self) -> DLDataType {
+        self.tensor().dtype
+    }
+
+    /// Gets the number of dimensions.
+    #[inline]
+    pub fn ndim(&self) -> usize {
+        self.tensor().ndim as usize
+    }
+
+    /// Gets the length of the tensor in each dimension.
+    pub fn shape(&self) -> &[i64] {
+        let tensor = self.tensor();
+        if tensor.ndim == 0 {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(tensor.shape, self.ndim()) }
+        }
+    }
+
+    /// Gets the strides of the tensor in each dimension, counted in elements.
+    ///
+    /// Returns `None` if the producer did not provide strides, meaning the tensor is
+    /// C-contiguous.
+    pub fn strides(&self) -> Option<&[i64]> {
+        let tensor = self.tensor();
+        if tensor.strides.is_null() || tensor.ndim == 0 {
+            None
+        } else {
+            Some(unsafe { std::slice::from_raw_parts(tensor.strides, self.ndim()) })
+        }
+    }
+
+    /// Gets the total number of elements.
+    pub fn len(&self) -> usize {
+        self.shape().iter().map(|&len| len as usize).product()
+    }
+
+    /// Returns `true` if the tensor has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets a pointer to the first element, which may not be accessible from the CPU.
+    pub fn data_ptr(&self) -> *mut c_void {
+        let tensor = self.tensor();
+        (tensor.data as *mut u8).wrapping_add(tensor.byte_offset as usize) as *mut c_void
+    }
+
+    /// Returns `true` if the elements are laid out contiguously, in C order.
+    pub fn is_c_contiguous(&self) -> bool {
+        let strides = match self.strides() {
+            Some(strides) => strides,
+            None => return true,
+        };
+        let mut expected = 1;
+        for (&len, &stride) in self.shape().iter().zip(strides).rev() {
+            // The stride of a dimension of length 1 is irrelevant.
+            if len != 1 && stride != expected {
+                return false;
+            }
+            expected *= len;
+        }
+        true
+    }
+
+    /// Gets the elements as a slice, in C order.
+    ///
+    /// Fails unless the tensor is in CPU memory, is C-contiguous and has elements of type `T`.
+    /// The producer may still modify the memory, for example if it is shared with a mutable
+    /// array, so the slice should not be held across calls into Python code.
+    pub fn as_slice<T: DLPackElement>(&self) -> PyResult<&[T]> {
+        if self.device().device_type != DLDeviceType::CPU {
+            return Err(PyBufferError::new_err(format!(
+                "tensor is not in CPU memory: {:?}",
+                self.device()
+            )));
+        }
+        if self.dtype() != T::DTYPE {
+            return Err(PyBufferError::new_err(format!(
+                "tensor elements of type {:?} are not compatible with {}",
+                self.dtype(),
+                std::any::type_name::<T>()
+            )));
+        }
+        if !self.is_c_contiguous() {
+            return Err(PyBufferError::new_err("tensor is not C-contiguous"));
+        }
+        let len = self.len();
+        if len == 0 {
+            return Ok(&[]);
+        }
+        let ptr = self.data_ptr() as *const T;
+        if ptr.align_offset(std::mem::align_of::<T>()) != 0 {
+            return Err(PyBufferError::new_err(format!(
+                "tensor memory is insufficiently aligned for {}",
+                std::any::type_name::<T>()
+            )));
+        }
+        // Safety: the tensor is a contiguous CPU array of `len` elements of type `T`.
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+}
+
+impl Drop for DLPackTensor {
+    fn drop(&mut self) {
+        let managed = self.managed.as_ptr();
+        unsafe {
+            if let Some(deleter) = (*managed).deleter {
+                deleter(managed);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for DLPackTensor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DLPackTensor")
+            .field("device", &self.device())
+            .field("dtype", &self.dtype())
+            .field("shape", &self.shape())
+            .field("strides", &self.strides())
+            .finish()
+    }
+}
+
+impl FromPyObject<'_> for DLPackTensor {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Self::from_object(obj)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_borrowed, export_vec, DLDevice, DLPackTensor};
+    use crate::types::{any::PyAnyMethods, bytearray::PyByteArrayMethods, PyByteArray};
+    use crate::{IntoPy, PyObject, Python};
+
+    #[test]
+    fn test_export_vec_round_trip() {
+        Python::with_gil(|py| {
+            let capsule = export_vec(py, vec![1i32, 2, 3, 4, 5, 6], &[2, 3]).unwrap();
+            let tensor = DLPackTensor::from_capsule(&capsule).unwrap();
+            assert_eq!(tensor.device(), DLDevice::cpu());
+            assert_eq!(tensor.shape(), [2, 3]);
+            assert_eq!(tensor.strides(), None);
+            assert!(tensor.is_c_contiguous());
+            assert_eq!(tensor.as_slice::<i32>().unwrap(), [1, 2, 3, 4, 5, 6]);
+            assert!(tensor.as_slice::<u32>().is_err());
+
+            // The capsule can only be consumed once.
+            let err = DLPackTensor::from_capsule(&capsule).unwrap_err();
+            assert!(err.to_string().contains("already consumed"));
+
+            assert!(export_vec(py, vec![1.0f64; 5], &[2, 3]).is_err());
+        });
+    }
+
+    #[test]
+    fn test_export_borrowed_releases_owner() {
+        Python::with_gil(|py| {
+            let owner = PyByteArray::new_bound(py, &[1, 2, 3, 4]);
+            let refcnt = owner.get_refcnt();
+            let data = owner.data();
+
+            // Released by the consumer.
+            let capsule = unsafe { export_borrowed(&owner, data, &[2, 2], Some(&[1, 2])) }.unwrap();
+            assert_eq!(owner.get_refcnt(), refcnt + 1);
+            let tensor = DLPackTensor::from_capsule(&capsule).unwrap();
+            assert_eq!(tensor.strides(), Some(&[1i64, 2][..]));
+            assert!(!tensor.is_c_contiguous());
+            assert!(tensor.as_slice::<u8>().is_err());
+            drop(capsule);
+            assert_eq!(owner.get_refcnt(), refcnt + 1);
+            drop(tensor);
+            assert_eq!(owner.get_refcnt(), refcnt);
+
+            // Released by the capsule, when no consumer took the tensor.
+            let capsule = unsafe { export_borrowed(&owner, data, &[4], None) }.unwrap();
+            assert_eq!(owner.get_refcnt(), refcnt + 1);
+            drop(capsule);
+            assert_eq!(owner.get_refcnt(), refcnt);
+        });
+    }
+
+    #[test]
+    fn test_device_into_py() {
+        Python::with_gil(|py| {
+            let device: PyObject = DLDevice::cpu().into_py(py);
+            assert_eq!(device.extract::<(i32, i32)>(py).unwrap(), (1, 0));
+        });
+    }
+}
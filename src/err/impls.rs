@@ -36,6 +36,11 @@ impl From<PyErr> for io::Error {
 /// Create `PyErr` from `io::Error`
 /// (`OSError` except if the `io::Error` is wrapping a Python exception,
 /// in this case the exception is returned)
+///
+/// The `OSError` subclass is chosen from the error kind, e.g. `FileNotFoundError` for
+/// `io::ErrorKind::NotFound`. With the `io-error-errno` feature, errors created from an OS error
+/// code keep it as the `errno` attribute (and `winerror` on Windows), and Python selects the
+/// subclass from the code for kinds which have no mapping here, e.g. `IsADirectoryError`.
 impl From<io::Error> for PyErr {
     fn from(err: io::Error) -> PyErr {
         // If the error wraps a Python error we return it
@@ -59,9 +64,33 @@ impl From<io::Error> for PyErr {
 }
 
 impl PyErrArguments for io::Error {
+    #[cfg(not(feature = "io-error-errno"))]
     fn arguments(self, py: Python<'_>) -> PyObject {
         self.to_string().into_py(py)
     }
+
+    #[cfg(feature = "io-error-errno")]
+    fn arguments(self, py: Python<'_>) -> PyObject {
+        match self.raw_os_error() {
+            Some(code) => {
+                // `OSError(errno, strerror)` sets the `errno` and `strerror` attributes; the
+                // message of `io::Error` ends with the code, which Python displays itself.
+                let message = self.to_string();
+                let suffix = format!(" (os error {})", code);
+                let strerror = message.strip_suffix(&suffix).unwrap_or(&message);
+                #[cfg(not(windows))]
+                {
+                    (code, strerror).into_py(py)
+                }
+                // On Windows, the code is a Windows error code, from which Python derives `errno`.
+                #[cfg(windows)]
+                {
+                    (py.None(), strerror, py.None(), code).into_py(py)
+                }
+            }
+            None => self.to_string().into_py(py),
+        }
+    }
 }
 
 impl<W> From<io::IntoInnerError<W>> for PyErr {
@@ -155,4 +184,53 @@ mod tests {
         check_err(io::ErrorKind::WouldBlock, "BlockingIOError");
         check_err(io::ErrorKind::TimedOut, "TimeoutError");
     }
+
+    #[test]
+    #[cfg(all(unix, feature = "io-error-errno"))]
+    fn io_errors_from_os_error_codes() {
+        use crate::types::any::PyAnyMethods;
+        use crate::PyNativeType;
+
+        let check_err = |code: i32, expected_ty: &str| {
+            Python::with_gil(|py| {
+                let rust_err = io::Error::from_raw_os_error(code);
+                let strerror = rust_err
+                    .to_string()
+                    .replace(&format!(" (os error {})", code), "");
+
+                let py_err: PyErr = rust_err.into();
+                assert_eq!(
+                    py_err
+                        .get_type_bound(py)
+                        .getattr("__name__")
+                        .unwrap()
+                        .to_string(),
+                    expected_ty
+                );
+                assert_eq!(
+                    py_err.to_string(),
+                    format!("{}: [Errno {}] {}", expected_ty, code, strerror)
+                );
+                let value = py_err.value(py).as_borrowed();
+                assert_eq!(
+                    value.getattr("errno").unwrap().extract::<i32>().unwrap(),
+                    code
+                );
+                assert_eq!(
+                    value
+                        .getattr("strerror")
+                        .unwrap()
+                        .extract::<String>()
+                        .unwrap(),
+                    strerror
+                );
+            })
+        };
+
+        // ENOENT and EACCES have the same values on all Unix platforms
+        check_err(2, "FileNotFoundError");
+        check_err(13, "PermissionError");
+        // EISDIR has no `io::ErrorKind` on the MSRV, Python picks the subclass from the code
+        check_err(21, "IsADirectoryError");
+    }
 }
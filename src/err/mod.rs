@@ -1,8 +1,9 @@
 use crate::instance::Bound;
+use crate::intern;
 use crate::panic::PanicException;
 use crate::type_object::PyTypeInfo;
 use crate::types::any::PyAnyMethods;
-use crate::types::{PyTraceback, PyType};
+use crate::types::{PyString, PyTraceback, PyType};
 use crate::{
     exceptions::{self, PyBaseException},
     ffi,
@@ -187,7 +188,7 @@ impl PyErr {
     where
         A: PyErrArguments + Send + Sync + 'static,
     {
-        PyErr::from_state(PyErrState::lazy(ty, args))
+        PyErr::from_state(PyErrState::lazy(ty.into(), args))
     }
 
     /// Creates a new PyErr.
@@ -222,12 +223,44 @@ impl PyErr {
     /// });
     /// ```
     pub fn from_value(obj: &PyAny) -> PyErr {
-        let state = if let Ok(obj) = obj.downcast::<PyBaseException>() {
-            PyErrState::normalized(obj)
-        } else {
-            // Assume obj is Type[Exception]; let later normalization handle if this
-            // is not the case
-            PyErrState::lazy(obj, obj.py().None())
+        PyErr::from_value_bound(obj.as_borrowed().to_owned())
+    }
+
+    /// Creates a new PyErr from a [`Bound`] object.
+    ///
+    /// This behaves like [`PyErr::from_value`]. It is convenient for raising instances of
+    /// `#[pyclass(extends = PyException)]` types which carry Rust data (not available with the
+    /// limited API):
+    ///
+    /// ```rust
+    /// # #[cfg(not(Py_LIMITED_API))] {
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::PyException;
+    ///
+    /// #[pyclass(extends = PyException)]
+    /// struct CodedError {
+    ///     #[pyo3(get)]
+    ///     code: u32,
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let value = Bound::new(py, CodedError { code: 42 }).unwrap();
+    ///     let err = PyErr::from_value_bound(value.into_any());
+    ///     assert!(err.is_instance_of::<CodedError>(py));
+    ///     assert!(err.is_instance_of::<PyException>(py));
+    /// });
+    /// # }
+    /// ```
+    pub fn from_value_bound(obj: Bound<'_, PyAny>) -> PyErr {
+        let state = match obj.downcast_into::<PyBaseException>() {
+            Ok(obj) => PyErrState::normalized(obj),
+            Err(err) => {
+                // Assume obj is Type[Exception]; let later normalization handle if this
+                // is not the case
+                let obj = err.into_inner();
+                let py = obj.py();
+                PyErrState::lazy(obj.unbind(), py.None())
+            }
         };
 
         PyErr::from_state(state)
@@ -548,6 +581,44 @@ impl PyErr {
         }
     }
 
+    /// Formats the error as the standard Python traceback text, exactly as it would be printed by
+    /// [`PyErr::display`] or by the interpreter for an uncaught exception.
+    ///
+    /// This is the concatenation of the lines returned by Python's
+    /// [`traceback.format_exception`][1], including the traceback (if any), chained causes and
+    /// contexts, and the final `ExceptionType: message` line. It is useful for logging Python
+    /// errors somewhere other than `sys.stderr`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// Python::with_gil(|py| {
+    ///     let err = py
+    ///         .run_bound("raise ValueError('banana')", None, None)
+    ///         .expect_err("raise will create a Python error");
+    ///
+    ///     let formatted = err.format_traceback(py).unwrap();
+    ///     assert!(formatted.starts_with("Traceback (most recent call last):\n"));
+    ///     assert!(formatted.ends_with("ValueError: banana\n"));
+    /// });
+    /// ```
+    ///
+    /// [1]: https://docs.python.org/3/library/traceback.html#traceback.format_exception
+    pub fn format_traceback(&self, py: Python<'_>) -> PyResult<String> {
+        let lines = py
+            .import_bound(intern!(py, "traceback"))?
+            .getattr(intern!(py, "format_exception"))?
+            .call1((
+                self.get_type_bound(py),
+                self.value(py),
+                self.traceback_bound(py),
+            ))?;
+        PyString::new_bound(py, "")
+            .call_method1(intern!(py, "join"), (lines,))?
+            .extract()
+    }
+
     /// Calls `sys.excepthook` and then prints a standard traceback to `sys.stderr`.
     pub fn print(&self, py: Python<'_>) {
         self.clone_ref(py).restore(py);
@@ -819,6 +890,45 @@ impl PyErr {
         }
     }
 
+    /// Adds a traceback entry pointing at the Rust source location which called this method.
+    ///
+    /// A traceback for an exception raised by Rust code normally stops at the Python line which
+    /// called into the extension. Calling this when converting a Rust error into a `PyErr` appends
+    /// a synthetic frame carrying the file and line of the caller (as recorded by
+    /// `#[track_caller]`), so the printed traceback shows where the failure originated.
+    ///
+    /// If the frame cannot be created, the error is returned unchanged.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::{exceptions::PyValueError, PyResult, Python};
+    ///
+    /// fn parse(py: Python<'_>, value: &str) -> PyResult<i32> {
+    ///     value
+    ///         .parse()
+    ///         .map_err(|_| PyValueError::new_err("not a number").with_caller_frame(py))
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let err = parse(py, "x").unwrap_err();
+    ///     // the traceback now ends at the `map_err` call above
+    ///     assert!(err.traceback_bound(py).is_some());
+    /// });
+    /// ```
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    #[track_caller]
+    pub fn with_caller_frame(self, py: Python<'_>) -> PyErr {
+        let location = std::panic::Location::caller();
+        match rust_frame(py, location.file(), location.line()) {
+            Ok(frame) => {
+                self.restore(py);
+                unsafe { ffi::PyTraceBack_Here(frame.as_ptr().cast()) };
+                PyErr::fetch(py)
+            }
+            Err(_) => self,
+        }
+    }
+
     #[inline]
     fn from_state(state: PyErrState) -> PyErr {
         PyErr {
@@ -863,6 +973,30 @@ impl PyErr {
     }
 }
 
+/// Creates an empty frame object for a Rust source location, suitable for adding to a traceback.
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+fn rust_frame<'py>(py: Python<'py>, file: &str, line: u32) -> PyResult<Bound<'py, PyAny>> {
+    use crate::ffi_ptr_ext::FfiPtrExt;
+    use crate::types::PyDict;
+
+    let filename = CString::new(file)?;
+    let line = std::os::raw::c_int::try_from(line).unwrap_or(std::os::raw::c_int::MAX);
+    unsafe {
+        let code = ffi::PyCode_NewEmpty(filename.as_ptr(), "<rust>\0".as_ptr().cast(), line)
+            .cast::<ffi::PyObject>()
+            .assume_owned_or_err(py)?;
+        let globals = PyDict::new_bound(py);
+        ffi::PyFrame_New(
+            ffi::PyThreadState_Get(),
+            code.as_ptr().cast(),
+            globals.as_ptr(),
+            std::ptr::null_mut(),
+        )
+        .cast::<ffi::PyObject>()
+        .assume_owned_or_err(py)
+    }
+}
+
 impl std::fmt::Debug for PyErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         Python::with_gil(|py| {
@@ -898,6 +1032,8 @@ impl IntoPy<PyObject> for PyErr {
     }
 }
 
+into_pyobject_via_into_py!(PyErr);
+
 impl ToPyObject for PyErr {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         self.clone_ref(py).into_py(py)
@@ -910,6 +1046,8 @@ impl<'a> IntoPy<PyObject> for &'a PyErr {
     }
 }
 
+into_pyobject_via_into_py!(['a] &'a PyErr);
+
 struct PyDowncastErrorArguments {
     from: Py<PyType>,
     to: Cow<'static, str>,
@@ -1166,6 +1304,32 @@ mod tests {
         });
     }
 
+    #[test]
+    fn err_format_traceback() {
+        Python::with_gil(|py| {
+            let err = py
+                .run_bound(
+                    "try:\n    1 / 0\nexcept ZeroDivisionError as e:\n    raise ValueError('banana') from e",
+                    None,
+                    None,
+                )
+                .expect_err("raising should have given us an error");
+            let formatted = err.format_traceback(py).unwrap();
+            assert!(formatted.starts_with("Traceback (most recent call last):\n"));
+            assert!(formatted.contains("ZeroDivisionError: division by zero\n"));
+            assert!(formatted
+                .contains("The above exception was the direct cause of the following exception:"));
+            assert!(formatted.ends_with("ValueError: banana\n"));
+
+            // errors which were never raised have no traceback
+            let err = PyValueError::new_err("no traceback");
+            assert_eq!(
+                err.format_traceback(py).unwrap(),
+                "ValueError: no traceback\n"
+            );
+        });
+    }
+
     #[test]
     fn test_pyerr_send_sync() {
         fn is_send<T: Send>() {}
@@ -1235,6 +1399,48 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    fn test_pyerr_with_caller_frame() {
+        Python::with_gil(|py| {
+            let err = PyValueError::new_err("banana");
+            assert!(err.traceback_bound(py).is_none());
+
+            let line = line!() + 1;
+            let err = err.with_caller_frame(py);
+            assert!(err.is_instance_of::<PyValueError>(py));
+
+            let traceback = err.traceback_bound(py).unwrap();
+            let summary = py
+                .import_bound("traceback")
+                .unwrap()
+                .call_method1("extract_tb", (traceback,))
+                .unwrap()
+                .get_item(-1)
+                .unwrap();
+            assert_eq!(
+                summary
+                    .getattr("filename")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                file!()
+            );
+            assert_eq!(
+                summary.getattr("lineno").unwrap().extract::<u32>().unwrap(),
+                line
+            );
+            assert_eq!(
+                summary
+                    .getattr("name")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "<rust>"
+            );
+        });
+    }
+
     #[test]
     fn warnings() {
         // Note: although the warning filter is interpreter global, keeping the
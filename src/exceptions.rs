@@ -9,7 +9,11 @@
 //! yourself to import Python classes that are ultimately derived from
 //! `BaseException`.
 
-use crate::{ffi, PyResult, Python};
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::types::any::PyAnyMethods;
+use crate::types::dict::PyDictMethods;
+use crate::types::PyDict;
+use crate::{ffi, intern, Bound, PyErr, PyResult, PyTypeInfo, Python};
 use std::ffi::CStr;
 use std::ops;
 use std::os::raw::c_char;
@@ -415,6 +419,79 @@ impl_native_exception!(
 impl_native_exception!(PyOSError, PyExc_OSError, native_doc!("OSError"));
 impl_native_exception!(PyImportError, PyExc_ImportError, native_doc!("ImportError"));
 
+impl PyOSError {
+    /// Creates a new `OSError` from an `errno` code and an optional filename.
+    ///
+    /// The arguments are passed as `OSError(errno, strerror, filename)`, so Python selects the
+    /// matching subclass (for example `FileNotFoundError` for `ENOENT`) and sets the `errno`,
+    /// `strerror` and `filename` attributes. `strerror` is obtained from `os.strerror`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::{PyFileNotFoundError, PyOSError};
+    ///
+    /// Python::with_gil(|py| {
+    ///     let err = PyOSError::from_errno(py, 2, Some("data.txt"));
+    ///     assert!(err.is_instance_of::<PyFileNotFoundError>(py));
+    ///     assert_eq!(
+    ///         err.value(py).getattr("filename").unwrap().extract::<String>().unwrap(),
+    ///         "data.txt"
+    ///     );
+    /// });
+    /// ```
+    pub fn from_errno(py: Python<'_>, errno: i32, filename: Option<&str>) -> PyErr {
+        let strerror = match py
+            .import_bound(intern!(py, "os"))
+            .and_then(|os| os.call_method1(intern!(py, "strerror"), (errno,)))
+        {
+            Ok(strerror) => strerror.unbind(),
+            Err(err) => return err,
+        };
+        match filename {
+            Some(filename) => PyOSError::new_err((errno, strerror, filename.to_owned())),
+            None => PyOSError::new_err((errno, strerror)),
+        }
+    }
+}
+
+impl PyImportError {
+    /// Creates a new `ImportError` with the `name` and `path` attributes set.
+    ///
+    /// This is equivalent to `ImportError(msg, name=name, path=path)` in Python.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::PyImportError;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let err = PyImportError::from_name_and_path(py, "no module", Some("spam"), None);
+    ///     let value = err.value(py);
+    ///     assert_eq!(value.getattr("name").unwrap().extract::<String>().unwrap(), "spam");
+    ///     assert!(value.getattr("path").unwrap().is_none());
+    /// });
+    /// ```
+    pub fn from_name_and_path(
+        py: Python<'_>,
+        msg: &str,
+        name: Option<&str>,
+        path: Option<&str>,
+    ) -> PyErr {
+        let kwargs = PyDict::new_bound(py);
+        let result = kwargs
+            .set_item(intern!(py, "name"), name)
+            .and_then(|()| kwargs.set_item(intern!(py, "path"), path))
+            .and_then(|()| PyImportError::type_object_bound(py).call((msg,), Some(&kwargs)));
+        match result {
+            Ok(value) => PyErr::from_value_bound(value),
+            Err(err) => err,
+        }
+    }
+}
+
 impl_native_exception!(
     PyModuleNotFoundError,
     PyExc_ModuleNotFoundError,
@@ -633,6 +710,49 @@ impl PyUnicodeDecodeError {
         }
     }
 
+    /// Creates a Python `UnicodeDecodeError`.
+    ///
+    /// `input` becomes the `object` attribute, `range` the `start` and `end` attributes, and
+    /// `encoding` and `reason` the attributes of the same names.
+    pub fn new_bound<'p>(
+        py: Python<'p>,
+        encoding: &CStr,
+        input: &[u8],
+        range: ops::Range<usize>,
+        reason: &CStr,
+    ) -> PyResult<Bound<'p, PyUnicodeDecodeError>> {
+        unsafe {
+            ffi::PyUnicodeDecodeError_Create(
+                encoding.as_ptr(),
+                input.as_ptr() as *const c_char,
+                input.len() as ffi::Py_ssize_t,
+                range.start as ffi::Py_ssize_t,
+                range.end as ffi::Py_ssize_t,
+                reason.as_ptr(),
+            )
+            .assume_owned_or_err(py)
+            .map(|err| err.downcast_into_unchecked())
+        }
+    }
+
+    /// Creates a Python `UnicodeDecodeError` from a Rust UTF-8 decoding error.
+    ///
+    /// This is the [`Bound`] variant of [`PyUnicodeDecodeError::new_utf8`].
+    pub fn new_utf8_bound<'p>(
+        py: Python<'p>,
+        input: &[u8],
+        err: std::str::Utf8Error,
+    ) -> PyResult<Bound<'p, PyUnicodeDecodeError>> {
+        let pos = err.valid_up_to();
+        PyUnicodeDecodeError::new_bound(
+            py,
+            CStr::from_bytes_with_nul(b"utf-8\0").unwrap(),
+            input,
+            pos..(pos + 1),
+            CStr::from_bytes_with_nul(b"invalid utf-8\0").unwrap(),
+        )
+    }
+
     /// Creates a Python `UnicodeDecodeError` from a Rust UTF-8 decoding error.
     ///
     /// # Examples
@@ -1041,6 +1161,103 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn unicode_decode_error_bound() {
+        Python::with_gil(|py| {
+            let decode_err = PyUnicodeDecodeError::new_bound(
+                py,
+                CStr::from_bytes_with_nul(b"ascii\0").unwrap(),
+                b"ab\xffc",
+                2..3,
+                CStr::from_bytes_with_nul(b"bad byte\0").unwrap(),
+            )
+            .unwrap();
+            assert_eq!(
+                decode_err
+                    .getattr("object")
+                    .unwrap()
+                    .extract::<&[u8]>()
+                    .unwrap(),
+                b"ab\xffc"
+            );
+            assert_eq!(
+                decode_err
+                    .getattr("start")
+                    .unwrap()
+                    .extract::<usize>()
+                    .unwrap(),
+                2
+            );
+            assert_eq!(
+                decode_err
+                    .getattr("end")
+                    .unwrap()
+                    .extract::<usize>()
+                    .unwrap(),
+                3
+            );
+            assert_eq!(
+                decode_err
+                    .getattr("reason")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "bad byte"
+            );
+        });
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn os_error_from_errno() {
+        Python::with_gil(|py| {
+            let err = PyOSError::from_errno(py, 2, Some("missing.txt"));
+            assert!(err.is_instance_of::<PyFileNotFoundError>(py));
+            let value = err.value(py);
+            assert_eq!(value.getattr("errno").unwrap().extract::<i32>().unwrap(), 2);
+            assert_eq!(
+                value
+                    .getattr("filename")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "missing.txt"
+            );
+            assert_eq!(
+                err.to_string(),
+                "FileNotFoundError: [Errno 2] No such file or directory: 'missing.txt'"
+            );
+
+            let err = PyOSError::from_errno(py, 13, None);
+            assert!(err.is_instance_of::<PyPermissionError>(py));
+            assert!(err.value(py).getattr("filename").unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn import_error_from_name_and_path() {
+        Python::with_gil(|py| {
+            let err = PyImportError::from_name_and_path(
+                py,
+                "no spam",
+                Some("spam"),
+                Some("/tmp/spam.py"),
+            );
+            assert!(err.is_instance_of::<PyImportError>(py));
+            assert_eq!(err.to_string(), "ImportError: no spam");
+            let value = err.value(py);
+            assert_eq!(
+                value.getattr("name").unwrap().extract::<String>().unwrap(),
+                "spam"
+            );
+            assert_eq!(
+                value.getattr("path").unwrap().extract::<String>().unwrap(),
+                "/tmp/spam.py"
+            );
+        });
+    }
+
     #[cfg(Py_3_11)]
     test_exception!(PyBaseExceptionGroup, |_| PyBaseExceptionGroup::new_err((
         "msg",
@@ -0,0 +1,117 @@
+//! Python generator implementation, to return lazily-evaluated Rust iterators from
+//! `#[pyfunction]`/`#[pymethods]`.
+use pyo3_macros::{pyclass, pymethods};
+
+use crate::{exceptions::PyStopIteration, IntoPy, Py, PyAny, PyErr, PyObject, PyResult, Python};
+
+type NextFn = Box<dyn FnMut(Python<'_>, PyObject) -> PyResult<Option<PyObject>> + Send>;
+
+/// Python generator wrapping a Rust iterator, or a closure receiving the values passed to
+/// `generator.send`.
+///
+/// Values are produced one at a time, when requested by Python, so that large result sets can
+/// be streamed with bounded memory. The generator implements the whole generator protocol
+/// (`send`, `throw` and `close`), so it is also an instance of `collections.abc.Generator`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(dead_code)]
+/// use pyo3::generator::Generator;
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction]
+/// fn squares(n: u64) -> Generator {
+///     Generator::from_iterator((0..n).map(|i| i * i))
+/// }
+/// ```
+#[pyclass(crate = "crate")]
+pub struct Generator {
+    next: Option<NextFn>,
+}
+
+impl Generator {
+    /// Wrap an iterator into a Python generator.
+    ///
+    /// Values passed to `generator.send` are ignored.
+    pub fn from_iterator<I>(iter: I) -> Self
+    where
+        I: IntoIterator,
+        I::IntoIter: Send + 'static,
+        I::Item: IntoPy<PyObject>,
+    {
+        let mut iter = iter.into_iter();
+        Self::from_fn(move |_py, _value| Ok(iter.next()))
+    }
+
+    /// Wrap an iterator of results into a Python generator.
+    ///
+    /// An error item is raised in Python, and terminates the generator.
+    /// Values passed to `generator.send` are ignored.
+    pub fn try_from_iterator<I, T, E>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+        I::IntoIter: Send + 'static,
+        T: IntoPy<PyObject>,
+        E: Into<PyErr>,
+    {
+        let mut iter = iter.into_iter();
+        Self::from_fn(move |_py, _value| iter.next().transpose().map_err(Into::into))
+    }
+
+    /// Create a Python generator from a closure called each time a value is requested.
+    ///
+    /// The closure receives the value passed to `generator.send`, or `None` when the generator
+    /// is iterated; it returns `Ok(None)` to terminate the generator.
+    pub fn from_fn<F, T>(mut f: F) -> Self
+    where
+        F: FnMut(Python<'_>, PyObject) -> PyResult<Option<T>> + Send + 'static,
+        T: IntoPy<PyObject>,
+    {
+        Self {
+            next: Some(Box::new(move |py, value| {
+                Ok(f(py, value)?.map(|item| item.into_py(py)))
+            })),
+        }
+    }
+
+    fn resume(&mut self, py: Python<'_>, value: PyObject) -> PyResult<Option<PyObject>> {
+        let next = match self.next {
+            Some(ref mut next) => next,
+            None => return Ok(None),
+        };
+        let result = next(py, value);
+        // the generator is exhausted after returning or raising
+        if !matches!(result, Ok(Some(_))) {
+            self.close();
+        }
+        result
+    }
+}
+
+#[pymethods(crate = "crate")]
+impl Generator {
+    fn __iter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.resume(py, py.None())
+    }
+
+    fn send(&mut self, py: Python<'_>, value: PyObject) -> PyResult<PyObject> {
+        self.resume(py, value)?
+            .ok_or_else(|| PyStopIteration::new_err(()))
+    }
+
+    fn throw(&mut self, exc: &PyAny) -> PyResult<PyObject> {
+        // the Rust iterator cannot catch the exception, so it is dropped and the exception
+        // propagates, like in a Python generator without `try` block
+        self.close();
+        Err(PyErr::from_value(exc))
+    }
+
+    fn close(&mut self) {
+        drop(self.next.take());
+    }
+}
@@ -1,13 +1,21 @@
 //! Interaction with Python's global interpreter lock
 
 use crate::impl_::not_send::{NotSend, NOT_SEND};
+use crate::types::{any::PyAnyMethods, PyCFunction, PyDict, PyTuple};
 use crate::{ffi, Python};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{Py, PyResult};
 use parking_lot::{const_mutex, Mutex, Once};
 use std::cell::Cell;
 #[cfg(debug_assertions)]
 use std::cell::RefCell;
 #[cfg(not(debug_assertions))]
 use std::cell::UnsafeCell;
+#[cfg(not(PyPy))]
+use std::os::raw::c_int;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{mem, ptr::NonNull};
 
 static START: Once = Once::new();
@@ -43,6 +51,13 @@ thread_local_const_init! {
     static OWNED_OBJECTS: UnsafeCell<PyObjVec> = const { UnsafeCell::new(Vec::new()) };
 }
 
+#[cfg(feature = "debug-refcounts")]
+thread_local! {
+    /// Where each object in `OWNED_OBJECTS` was registered, in the same order.
+    static OWNED_BACKTRACES: std::cell::RefCell<Vec<crate::debug_refcounts::RegisteredAt>> =
+        std::cell::RefCell::new(Vec::new());
+}
+
 const GIL_LOCKED_DURING_TRAVERSE: isize = -1;
 
 /// Checks whether the GIL is acquired.
@@ -80,13 +95,48 @@ fn gil_is_acquired() -> bool {
 /// ```
 #[cfg(not(PyPy))]
 pub fn prepare_freethreaded_python() {
+    initialize(false);
+}
+
+/// Prepares the use of Python in a free-threaded context, with Python's signal handlers.
+///
+/// This is the same as [`prepare_freethreaded_python`], except that if this function initializes
+/// the Python interpreter, it installs Python's signal handlers, so that `Ctrl+C` raises
+/// `KeyboardInterrupt` (from [`Python::check_signals`] in Rust code) like in the `python`
+/// executable. The handlers replace any handlers for `SIGINT` and the other signals Python
+/// handles which are already installed in the process.
+///
+/// Call this function from the thread which should handle signals, usually the main thread of an
+/// embedding application, as it becomes Python's main thread.
+///
+/// If the Python interpreter is already initialized, this function has no effect.
+///
+/// # Examples
+/// ```rust
+/// use pyo3::prelude::*;
+///
+/// # fn main() -> PyResult<()> {
+/// pyo3::prepare_freethreaded_python_with_signal_handlers();
+/// Python::with_gil(|py| {
+///     // e.g. a long-running loop
+///     py.check_signals()
+/// })
+/// # }
+/// ```
+#[cfg(not(PyPy))]
+pub fn prepare_freethreaded_python_with_signal_handlers() {
+    initialize(true);
+}
+
+#[cfg(not(PyPy))]
+fn initialize(install_signal_handlers: bool) {
     // Protect against race conditions when Python is not yet initialized and multiple threads
     // concurrently call 'prepare_freethreaded_python()'. Note that we do not protect against
     // concurrent initialization of the Python runtime by other users of the Python C API.
     START.call_once_force(|_| unsafe {
         // Use call_once_force because if initialization panics, it's okay to try again.
         if ffi::Py_IsInitialized() == 0 {
-            ffi::Py_InitializeEx(0);
+            ffi::Py_InitializeEx(install_signal_handlers as c_int);
 
             // Release the GIL.
             ffi::PyEval_SaveThread();
@@ -127,6 +177,34 @@ pub fn prepare_freethreaded_python() {
 /// ```
 #[cfg(not(PyPy))]
 pub unsafe fn with_embedded_python_interpreter<F, R>(f: F) -> R
+where
+    F: for<'p> FnOnce(Python<'p>) -> R,
+{
+    embedded_python_interpreter(false, f)
+}
+
+/// Executes the provided closure with an embedded Python interpreter which handles signals.
+///
+/// This is the same as [`with_embedded_python_interpreter`], except that Python's signal
+/// handlers are installed while the interpreter runs, so that `Ctrl+C` raises
+/// `KeyboardInterrupt` (from [`Python::check_signals`] in Rust code).
+///
+/// # Panics
+/// - If the Python interpreter is already initialized before calling this function.
+///
+/// # Safety
+/// The same requirements as [`with_embedded_python_interpreter`] apply. In addition, this
+/// function should be called from the main thread, which handles signals.
+#[cfg(not(PyPy))]
+pub unsafe fn with_embedded_python_interpreter_and_signal_handlers<F, R>(f: F) -> R
+where
+    F: for<'p> FnOnce(Python<'p>) -> R,
+{
+    embedded_python_interpreter(true, f)
+}
+
+#[cfg(not(PyPy))]
+unsafe fn embedded_python_interpreter<F, R>(install_signal_handlers: bool, f: F) -> R
 where
     F: for<'p> FnOnce(Python<'p>) -> R,
 {
@@ -136,10 +214,13 @@ where
         "called `with_embedded_python_interpreter` but a Python interpreter is already running."
     );
 
-    ffi::Py_InitializeEx(0);
+    // References left over from a previous interpreter were discarded when it was finalized.
+    POOL.reset();
+    ffi::Py_InitializeEx(install_signal_handlers as c_int);
 
     // Safety: the GIL is already held because of the Py_IntializeEx call.
     let pool = GILPool::new();
+    POOL.register_atexit_hook(pool.python());
 
     // Import the threading module - this ensures that it will associate this thread as the "main"
     // thread, which is important to avoid an `AssertionError` at finalization.
@@ -151,8 +232,18 @@ where
     // Drop the pool before finalizing.
     drop(pool);
 
+    // Anything still registered was created outside of any pool, and so was never released.
+    #[cfg(feature = "debug-refcounts")]
+    if let Some(hook) = crate::debug_refcounts::leak_hook() {
+        let py = Python::assume_gil_acquired();
+        for object in crate::debug_refcounts::pooled_objects(py) {
+            hook(py, &object, crate::debug_refcounts::LeakKind::NeverReleased);
+        }
+    }
+
     // Finalize the Python interpreter.
     ffi::Py_Finalize();
+    POOL.discard();
 
     result
 }
@@ -175,12 +266,20 @@ impl GILGuard {
 
         // Maybe auto-initialize the GIL:
         //  - If auto-initialize feature set and supported, try to initialize the interpreter.
+        //  - The auto-initialize-embedded feature does the same, except when building an extension
+        //    module, which must be loaded by an already running interpreter.
         //  - If the auto-initialize feature is set but unsupported, emit hard errors only when the
         //    extension-module feature is not activated - extension modules don't care about
         //    auto-initialize so this avoids breaking existing builds.
         //  - Otherwise, just check the GIL is initialized.
         cfg_if::cfg_if! {
-            if #[cfg(all(feature = "auto-initialize", not(PyPy)))] {
+            if #[cfg(all(
+                any(
+                    feature = "auto-initialize",
+                    all(feature = "auto-initialize-embedded", not(feature = "extension-module"))
+                ),
+                not(PyPy)
+            ))] {
                 prepare_freethreaded_python();
             } else {
                 // This is a "hack" to make running `cargo test` for PyO3 convenient (i.e. no need
@@ -192,23 +291,33 @@ impl GILGuard {
                     prepare_freethreaded_python();
                 }
 
+                // Explains why the GIL cannot be acquired when the interpreter is not running.
+                const NOT_INITIALIZED_MESSAGE: &str = if cfg!(feature = "extension-module") {
+                    "The Python interpreter is not initialized.\n\n\
+                     PyO3 was built with the `extension-module` feature, so it expects to be loaded as an \
+                     extension module by a running Python interpreter, and does not initialize one itself. \
+                     This library may have been loaded by a program which does not run Python, or linked \
+                     against a different Python library than the one which is running. To embed Python in a \
+                     binary, build it without the `extension-module` feature."
+                } else {
+                    "The Python interpreter is not initialized and the `auto-initialize` \
+                     feature is not enabled.\n\n\
+                     Consider calling `pyo3::prepare_freethreaded_python()` before attempting \
+                     to use Python APIs."
+                };
+
                 START.call_once_force(|_| unsafe {
                     // Use call_once_force because if there is a panic because the interpreter is
                     // not initialized, it's fine for the user to initialize the interpreter and
                     // retry.
-                    assert_ne!(
-                        ffi::Py_IsInitialized(),
-                        0,
-                        "The Python interpreter is not initialized and the `auto-initialize` \
-                         feature is not enabled.\n\n\
-                         Consider calling `pyo3::prepare_freethreaded_python()` before attempting \
-                         to use Python APIs."
-                    );
+                    assert_ne!(ffi::Py_IsInitialized(), 0, "{}", NOT_INITIALIZED_MESSAGE);
                 });
             }
         }
 
-        Self::acquire_unchecked()
+        let guard = Self::acquire_unchecked()?;
+        POOL.register_atexit_hook(guard.pool.python());
+        Some(guard)
     }
 
     /// Acquires the `GILGuard` without performing any state checking.
@@ -244,24 +353,39 @@ impl Drop for GILGuard {
 type PyObjVec = Vec<NonNull<ffi::PyObject>>;
 
 /// Thread-safe storage for objects which were inc_ref / dec_ref while the GIL was not held.
+///
+/// The pending changes are applied the next time the GIL is acquired, and one last time by an
+/// `atexit` hook when the interpreter finalizes. After that, reference count changes are no longer
+/// queued: the objects may have been freed by finalization, so their references are leaked instead.
 struct ReferencePool {
     // .0 is INCREFs, .1 is DECREFs
     pointer_ops: Mutex<(PyObjVec, PyObjVec)>,
+    // Only modified with `pointer_ops` locked, so that no pointer is queued once it is set.
+    finalized: AtomicBool,
+    atexit_hook_registered: AtomicBool,
 }
 
 impl ReferencePool {
     const fn new() -> Self {
         Self {
             pointer_ops: const_mutex((Vec::new(), Vec::new())),
+            finalized: AtomicBool::new(false),
+            atexit_hook_registered: AtomicBool::new(false),
         }
     }
 
     fn register_incref(&self, obj: NonNull<ffi::PyObject>) {
-        self.pointer_ops.lock().0.push(obj);
+        let mut ops = self.pointer_ops.lock();
+        if !self.finalized.load(Ordering::Relaxed) {
+            ops.0.push(obj);
+        }
     }
 
     fn register_decref(&self, obj: NonNull<ffi::PyObject>) {
-        self.pointer_ops.lock().1.push(obj);
+        let mut ops = self.pointer_ops.lock();
+        if !self.finalized.load(Ordering::Relaxed) {
+            ops.1.push(obj);
+        }
     }
 
     fn update_counts(&self, _py: Python<'_>) {
@@ -284,6 +408,60 @@ impl ReferencePool {
             unsafe { ffi::Py_DECREF(ptr.as_ptr()) };
         }
     }
+
+    /// Registers the `atexit` hook which calls [`ReferencePool::finalize`], once per interpreter.
+    fn register_atexit_hook(&self, py: Python<'_>) {
+        if self.atexit_hook_registered.load(Ordering::Relaxed)
+            || self.atexit_hook_registered.swap(true, Ordering::Relaxed)
+        {
+            return;
+        }
+        let result = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            |args: &PyTuple, _kwargs: Option<&PyDict>| POOL.finalize(args.py()),
+        )
+        .and_then(|hook| {
+            py.import_bound("atexit")?
+                .call_method1("register", (hook,))
+                .map(drop)
+        });
+        if result.is_err() {
+            // e.g. during multi-phase initialization; try again the next time
+            self.atexit_hook_registered.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Applies the pending changes while the interpreter is still usable, then stops queuing.
+    fn finalize(&self, py: Python<'_>) {
+        loop {
+            // Decrefs can run arbitrary code, which may queue more changes.
+            self.update_counts(py);
+            let ops = self.pointer_ops.lock();
+            if ops.0.is_empty() && ops.1.is_empty() {
+                self.finalized.store(true, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    /// Forgets any pending changes, when the interpreter was finalized without running the hook.
+    #[cfg(not(PyPy))]
+    fn discard(&self) {
+        let mut ops = self.pointer_ops.lock();
+        *ops = (Vec::new(), Vec::new());
+        self.finalized.store(true, Ordering::Relaxed);
+    }
+
+    /// Prepares the pool for a new interpreter.
+    #[cfg(not(PyPy))]
+    fn reset(&self) {
+        let mut ops = self.pointer_ops.lock();
+        *ops = (Vec::new(), Vec::new());
+        self.finalized.store(false, Ordering::Relaxed);
+        self.atexit_hook_registered.store(false, Ordering::Relaxed);
+    }
 }
 
 unsafe impl Sync for ReferencePool {}
@@ -351,6 +529,94 @@ impl Drop for LockGIL {
     }
 }
 
+/// Gives a thread started with [`Python::spawn_with_gilstate`] access to the GIL for as long as
+/// the interpreter is running.
+///
+/// The interpreter does not wait for threads which it did not start before it finalizes, and a
+/// thread which tries to acquire the GIL after finalization can crash or hang. [`GILState::with_gil`]
+/// prevents this: when the interpreter starts to finalize, it waits for the calls which are in
+/// progress to complete, then later calls return `None` instead of acquiring the GIL.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct GILState {
+    shutdown: std::sync::Arc<ThreadShutdown>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default)]
+struct ThreadShutdown {
+    finalizing: AtomicBool,
+    active: AtomicUsize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl GILState {
+    /// Creates a new state, and registers the `atexit` hook which marks it as finalizing.
+    pub(crate) fn register(py: Python<'_>) -> PyResult<(Self, Py<PyCFunction>)> {
+        let shutdown = std::sync::Arc::new(ThreadShutdown::default());
+        let hook_shutdown = shutdown.clone();
+        let hook = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            move |args: &PyTuple, _kwargs: Option<&PyDict>| {
+                hook_shutdown.finalizing.store(true, Ordering::SeqCst);
+                let shutdown = &hook_shutdown;
+                args.py().allow_threads(|| {
+                    while shutdown.active.load(Ordering::SeqCst) != 0 {
+                        std::thread::sleep(std::time::Duration::from_millis(1));
+                    }
+                });
+            },
+        )?;
+        py.import_bound("atexit")?
+            .call_method1("register", (&hook,))?;
+        Ok((GILState { shutdown }, hook.unbind()))
+    }
+
+    /// Removes the `atexit` hook, if the interpreter is still running.
+    pub(crate) fn unregister(&self, hook: Py<PyCFunction>) {
+        let _ = self.with_gil(|py| {
+            py.import_bound("atexit")
+                .and_then(|atexit| atexit.call_method1("unregister", (hook,)).map(drop))
+                .unwrap_or_else(|err| err.write_unraisable_bound(py, None))
+        });
+    }
+
+    /// Acquires the GIL and runs `f`, unless the interpreter is finalizing or is not initialized,
+    /// in which case `f` is not run and `None` is returned.
+    ///
+    /// While `f` runs, finalization of the interpreter waits for it to complete, so `f` should
+    /// not block on work done by the thread which finalizes the interpreter.
+    pub fn with_gil<F, R>(&self, f: F) -> Option<R>
+    where
+        F: for<'py> FnOnce(Python<'py>) -> R,
+    {
+        struct Active<'a>(&'a ThreadShutdown);
+
+        impl Drop for Active<'_> {
+            fn drop(&mut self) {
+                self.0.active.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        // Mark the call as active before checking for finalization, so that the `atexit` hook
+        // either sees this call and waits for it, or this call sees the hook's flag.
+        self.shutdown.active.fetch_add(1, Ordering::SeqCst);
+        let _active = Active(&self.shutdown);
+        if self.is_finalizing() || unsafe { ffi::Py_IsInitialized() } == 0 {
+            return None;
+        }
+        Some(Python::with_gil(f))
+    }
+
+    /// Returns `true` once the interpreter has started to finalize, after which the thread
+    /// should stop calling into Python.
+    pub fn is_finalizing(&self) -> bool {
+        self.shutdown.finalizing.load(Ordering::SeqCst)
+    }
+}
+
 /// A RAII pool which PyO3 uses to store owned Python references.
 ///
 /// See the [Memory Management] chapter of the guide for more information about how PyO3 uses
@@ -417,6 +683,22 @@ impl Drop for GILPool {
                     Vec::new()
                 }
             });
+            #[cfg(feature = "debug-refcounts")]
+            {
+                let registered_at = OWNED_BACKTRACES
+                    .try_with(|backtraces| {
+                        let mut backtraces = backtraces.borrow_mut();
+                        if start < backtraces.len() {
+                            backtraces.split_off(start)
+                        } else {
+                            Vec::new()
+                        }
+                    })
+                    .unwrap_or_default();
+                // Safety: the GIL is held while the pool is alive
+                let py = unsafe { Python::assume_gil_acquired() };
+                crate::debug_refcounts::report_outlived_pool(py, &owned_objects, registered_at);
+            }
             for obj in owned_objects {
                 unsafe {
                     ffi::Py_DECREF(obj.as_ptr());
@@ -427,12 +709,20 @@ impl Drop for GILPool {
     }
 }
 
+/// Makes sure that the pending reference count changes are applied before the interpreter
+/// finalizes, for interpreters which were not started by PyO3.
+pub(crate) fn register_atexit_hook(py: Python<'_>) {
+    POOL.register_atexit_hook(py);
+}
+
 /// Registers a Python object pointer inside the release pool, to have its reference count increased
 /// the next time the GIL is acquired in pyo3.
 ///
 /// If the GIL is held, the reference count will be increased immediately instead of being queued
 /// for later.
 ///
+/// Once the interpreter has finalized, the change is ignored, leaking the reference.
+///
 /// # Safety
 /// The object must be an owned Python reference.
 pub unsafe fn register_incref(obj: NonNull<ffi::PyObject>) {
@@ -449,6 +739,8 @@ pub unsafe fn register_incref(obj: NonNull<ffi::PyObject>) {
 /// If the GIL is held, the reference count will be decreased immediately instead of being queued
 /// for later.
 ///
+/// Once the interpreter has finalized, the change is ignored, leaking the reference.
+///
 /// # Safety
 /// The object must be an owned Python reference.
 pub unsafe fn register_decref(obj: NonNull<ffi::PyObject>) {
@@ -475,6 +767,33 @@ pub unsafe fn register_owned(_py: Python<'_>, obj: NonNull<ffi::PyObject>) {
             (*owned_objects.get()).push(obj);
         }
     });
+    #[cfg(feature = "debug-refcounts")]
+    let _ = OWNED_BACKTRACES.try_with(|backtraces| {
+        backtraces
+            .borrow_mut()
+            .push(crate::debug_refcounts::RegisteredAt::capture())
+    });
+}
+
+/// Returns the objects currently registered in this thread's GIL pools, along with where each
+/// was registered.
+#[cfg(feature = "debug-refcounts")]
+pub(crate) fn owned_objects_with_backtraces(
+) -> Vec<(NonNull<ffi::PyObject>, crate::debug_refcounts::RegisteredAt)> {
+    let owned_objects = OWNED_OBJECTS
+        .try_with(|owned_objects| {
+            #[cfg(debug_assertions)]
+            let owned_objects = owned_objects.borrow().clone();
+            #[cfg(not(debug_assertions))]
+            // SAFETY: This is not re-entrant.
+            let owned_objects = unsafe { (*owned_objects.get()).clone() };
+            owned_objects
+        })
+        .unwrap_or_default();
+    let backtraces = OWNED_BACKTRACES
+        .try_with(|backtraces| backtraces.borrow().clone())
+        .unwrap_or_default();
+    owned_objects.into_iter().zip(backtraces).collect()
 }
 
 /// Increments pyo3's internal GIL count - to be called whenever GILPool or GILGuard is created.
@@ -506,12 +825,15 @@ fn decrement_gil_count() {
 
 #[cfg(test)]
 mod tests {
-    use super::{gil_is_acquired, GILPool, GIL_COUNT, OWNED_OBJECTS, POOL};
+    #[cfg(not(target_arch = "wasm32"))]
+    use super::GILState;
+    use super::{gil_is_acquired, GILPool, ReferencePool, GIL_COUNT, OWNED_OBJECTS, POOL};
     use crate::types::any::PyAnyMethods;
     use crate::{ffi, gil, PyObject, Python, ToPyObject};
     #[cfg(not(target_arch = "wasm32"))]
     use parking_lot::{const_mutex, Condvar, Mutex};
     use std::ptr::NonNull;
+    use std::sync::atomic::Ordering;
 
     fn get_object(py: Python<'_>) -> PyObject {
         // Convenience function for getting a single unique object, using `new_pool` so as to leave
@@ -926,4 +1248,76 @@ mod tests {
             POOL.update_counts(py);
         })
     }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))] // We are building wasm Python with pthreads disabled
+    fn test_spawn_with_gilstate() {
+        let handle = Python::with_gil(|py| {
+            py.spawn_with_gilstate(|gil| {
+                (0..3)
+                    .map(|i| {
+                        gil.with_gil(|py| py.eval_bound("1 + 1", None, None)?.extract::<i32>())
+                            .unwrap()
+                            .unwrap()
+                            + i
+                    })
+                    .sum::<i32>()
+            })
+        })
+        .unwrap();
+        assert_eq!(handle.join().unwrap(), 9);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))] // We are building wasm Python with pthreads disabled
+    fn test_gil_state_finalizing() {
+        Python::with_gil(|py| {
+            let (state, hook) = GILState::register(py).unwrap();
+            assert!(!state.is_finalizing());
+            assert_eq!(state.with_gil(|_py| 1), Some(1));
+
+            // what the interpreter does on finalization
+            hook.call0(py).unwrap();
+            assert!(state.is_finalizing());
+            assert_eq!(state.with_gil(|_py| 1), None);
+
+            py.import_bound("atexit")
+                .unwrap()
+                .call_method1("unregister", (hook,))
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn test_reference_pool_finalize() {
+        Python::with_gil(|py| {
+            assert!(POOL.atexit_hook_registered.load(Ordering::Relaxed));
+
+            let pool = ReferencePool::new();
+            let obj = get_object(py);
+            let ptr = NonNull::new(obj.as_ptr()).unwrap();
+            let refcnt = obj.get_refcnt(py);
+
+            // a reference dropped without the GIL is released on finalization
+            unsafe { ffi::Py_INCREF(ptr.as_ptr()) };
+            pool.register_decref(ptr);
+            pool.finalize(py);
+            assert_eq!(obj.get_refcnt(py), refcnt);
+
+            // afterwards, changes are no longer queued
+            pool.register_incref(ptr);
+            pool.register_decref(ptr);
+            assert!(*pool.pointer_ops.lock() == (Vec::new(), Vec::new()));
+
+            #[cfg(not(PyPy))]
+            {
+                pool.reset();
+                pool.register_incref(ptr);
+                pool.register_decref(ptr);
+                assert!(*pool.pointer_ops.lock() == (vec![ptr], vec![ptr]));
+                pool.update_counts(py);
+                assert_eq!(obj.get_refcnt(py), refcnt);
+            }
+        })
+    }
 }
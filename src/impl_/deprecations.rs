@@ -2,3 +2,9 @@
 
 #[deprecated(since = "0.20.0", note = "use `#[new]` instead of `#[__new__]`")]
 pub const PYMETHODS_NEW_DEPRECATED_FORM: () = ();
+
+#[deprecated(
+    since = "0.21.0",
+    note = "`#[pyproto]` is deprecated, use `#[pymethods]` on the same impl block instead"
+)]
+pub const PYPROTO_ATTRIBUTE: () = ();
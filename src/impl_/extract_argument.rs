@@ -2,8 +2,10 @@ use crate::{
     exceptions::PyTypeError,
     ffi,
     pyclass::boolean_struct::False,
+    sync::GILOnceCell,
     types::{PyDict, PyString, PyTuple},
-    Bound, FromPyObject, PyAny, PyClass, PyErr, PyRef, PyRefMut, PyResult, PyTypeCheck, Python,
+    Bound, FromPyObject, IntoPy, PyAny, PyClass, PyErr, PyObject, PyRef, PyRefMut, PyResult,
+    PyTypeCheck, Python,
 };
 
 /// A trait which is used to help PyO3 macros extract function arguments.
@@ -131,6 +133,53 @@ where
     }
 }
 
+/// Alternative to [`extract_argument`] used when the argument has a `#[pyo3(default_factory)]`
+/// annotation: the factory is called on every call which omits the argument.
+#[doc(hidden)]
+pub fn extract_argument_with_default_factory<'a, 'py, T>(
+    obj: Option<&'py PyAny>,
+    holder: &'a mut T::Holder,
+    arg_name: &str,
+    py: Python<'py>,
+    default: fn(Python<'py>) -> PyResult<T>,
+) -> PyResult<T>
+where
+    T: PyFunctionArgument<'a, 'py>,
+{
+    match obj {
+        Some(obj) => extract_argument(obj, holder, arg_name),
+        None => default(py),
+    }
+}
+
+/// Alternative to [`extract_argument`] used when the argument has a `#[pyo3(default_once)]`
+/// annotation: the factory is called only once, and the Python object it returns is cached and
+/// extracted on every call which omits the argument.
+#[doc(hidden)]
+pub fn extract_argument_with_default_once<'a, 'py, T, D>(
+    obj: Option<&'py PyAny>,
+    holder: &'a mut T::Holder,
+    arg_name: &str,
+    py: Python<'py>,
+    cell: &'static GILOnceCell<PyObject>,
+    default: fn(Python<'py>) -> PyResult<D>,
+) -> PyResult<T>
+where
+    T: PyFunctionArgument<'a, 'py>,
+    D: IntoPy<PyObject>,
+{
+    let obj = match obj {
+        Some(obj) => obj,
+        None => {
+            let default =
+                cell.get_or_try_init(py, || default(py).map(|value| value.into_py(py)))?;
+            // The cached object lives in a static, so it can be borrowed for the GIL lifetime.
+            default.bind_borrowed(py).into_gil_ref()
+        }
+    };
+    extract_argument(obj, holder, arg_name)
+}
+
 /// Alternative to [`extract_argument`] used when the argument has a `#[pyo3(from_py_with)]` annotation.
 #[doc(hidden)]
 pub fn from_py_with<'a, 'py, T>(
@@ -206,6 +255,12 @@ pub struct FunctionDescription {
     pub positional_only_parameters: usize,
     pub required_positional_parameters: usize,
     pub keyword_only_parameters: &'static [KeywordOnlyParameterDescription],
+    /// Maps a keyword name to its index in the output array (positional parameters first,
+    /// then keyword-only parameters).
+    ///
+    /// The macros generate a specialized lookup for each signature; when `None`, the parameter
+    /// names are scanned linearly instead.
+    pub keyword_index: Option<fn(&str) -> Option<usize>>,
 }
 
 impl FunctionDescription {
@@ -223,7 +278,7 @@ impl FunctionDescription {
     /// - `args` must be a pointer to a C-style array of valid `ffi::PyObject` pointers, or NULL.
     /// - `kwnames` must be a pointer to a PyTuple, or NULL.
     /// - `nargs + kwnames.len()` is the total length of the `args` array.
-    #[cfg(not(Py_LIMITED_API))]
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
     pub unsafe fn extract_arguments_fastcall<'py, V, K>(
         &self,
         py: Python<'py>,
@@ -373,19 +428,7 @@ impl FunctionDescription {
             // error if this function doesn't accept **kwargs). Rust source is always UTF-8
             // and so all argument names in `#[pyfunction]` signature must be UTF-8.
             if let Ok(kwarg_name) = kwarg_name_py.downcast::<PyString>()?.to_str() {
-                // Try to place parameter in keyword only parameters
-                if let Some(i) = self.find_keyword_parameter_in_keyword_only(kwarg_name) {
-                    if output[i + num_positional_parameters]
-                        .replace(value)
-                        .is_some()
-                    {
-                        return Err(self.multiple_values_for_argument(kwarg_name));
-                    }
-                    continue;
-                }
-
-                // Repeat for positional parameters
-                if let Some(i) = self.find_keyword_parameter_in_positional(kwarg_name) {
+                if let Some(i) = self.find_keyword_parameter(kwarg_name) {
                     if i < self.positional_only_parameters {
                         // If accepting **kwargs, then it's allowed for the name of the
                         // kwarg to conflict with a postional-only argument - the value
@@ -410,6 +453,18 @@ impl FunctionDescription {
         Ok(())
     }
 
+    /// Returns the index in the output array of the parameter named `kwarg_name`.
+    #[inline]
+    fn find_keyword_parameter(&self, kwarg_name: &str) -> Option<usize> {
+        if let Some(keyword_index) = self.keyword_index {
+            return keyword_index(kwarg_name);
+        }
+        // Try to place parameter in keyword only parameters, then repeat for positional parameters
+        self.find_keyword_parameter_in_keyword_only(kwarg_name)
+            .map(|i| i + self.positional_parameter_names.len())
+            .or_else(|| self.find_keyword_parameter_in_positional(kwarg_name))
+    }
+
     #[inline]
     fn find_keyword_parameter_in_positional(&self, kwarg_name: &str) -> Option<usize> {
         self.positional_parameter_names
@@ -421,7 +476,8 @@ impl FunctionDescription {
     fn find_keyword_parameter_in_keyword_only(&self, kwarg_name: &str) -> Option<usize> {
         // Compare the keyword name against each parameter in turn. This is exactly the same method
         // which CPython uses to map keyword names. Although it's O(num_parameters), the number of
-        // parameters is expected to be small so it's not worth constructing a mapping.
+        // parameters is expected to be small so it's not worth constructing a mapping at runtime;
+        // the macros instead generate a `keyword_index` lookup.
         self.keyword_only_parameters
             .iter()
             .position(|param_desc| param_desc.name == kwarg_name)
@@ -724,6 +780,7 @@ mod tests {
             positional_only_parameters: 0,
             required_positional_parameters: 0,
             keyword_only_parameters: &[],
+            keyword_index: None,
         };
 
         Python::with_gil(|py| {
@@ -755,6 +812,7 @@ mod tests {
             positional_only_parameters: 0,
             required_positional_parameters: 0,
             keyword_only_parameters: &[],
+            keyword_index: None,
         };
 
         Python::with_gil(|py| {
@@ -786,6 +844,7 @@ mod tests {
             positional_only_parameters: 0,
             required_positional_parameters: 2,
             keyword_only_parameters: &[],
+            keyword_index: None,
         };
 
         Python::with_gil(|py| {
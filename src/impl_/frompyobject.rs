@@ -1,6 +1,9 @@
+use crate::exceptions::PyTypeError;
 use crate::types::any::PyAnyMethods;
+use crate::types::string::PyStringMethods;
+use crate::types::PyString;
 use crate::Bound;
-use crate::{exceptions::PyTypeError, FromPyObject, PyAny, PyErr, PyResult, Python};
+use crate::{FromPyObject, PyAny, PyErr, PyResult, Python};
 
 pub enum Extractor<'a, 'py, T> {
     Bound(fn(&'a Bound<'py, PyAny>) -> PyResult<T>),
@@ -167,3 +170,78 @@ fn failed_to_extract_tuple_struct_field(
     new_err.set_cause(py, ::std::option::Option::Some(inner_err));
     new_err
 }
+
+/// Collects the errors from extracting each field of a struct, so that they are all reported
+/// together rather than one at a time.
+pub struct FieldErrors<'a> {
+    struct_name: &'a str,
+    errors: Vec<PyErr>,
+}
+
+impl<'a> FieldErrors<'a> {
+    pub fn new(struct_name: &'a str) -> Self {
+        FieldErrors {
+            struct_name,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Records the error from `result`, if any.
+    pub fn check<T>(&mut self, result: PyResult<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.errors.push(err);
+                None
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Records an error if the mapping `obj` has any keys other than `known_keys`.
+    pub fn check_unknown_keys(&mut self, obj: &Bound<'_, PyAny>, known_keys: &[&str]) {
+        let result = (|| -> PyResult<Vec<String>> {
+            let mut unknown = Vec::new();
+            for key in obj.call_method0(crate::intern!(obj.py(), "keys"))?.iter()? {
+                let key = key?;
+                let is_known = match key.downcast::<PyString>() {
+                    Ok(key) => known_keys.contains(&&*key.to_cow()?),
+                    Err(_) => false,
+                };
+                if !is_known {
+                    unknown.push(key.repr()?.to_cow()?.into_owned());
+                }
+            }
+            Ok(unknown)
+        })();
+        match result {
+            Ok(unknown) if unknown.is_empty() => {}
+            Ok(unknown) => self.errors.push(PyTypeError::new_err(format!(
+                "{} got unexpected key{} {}",
+                self.struct_name,
+                if unknown.len() == 1 { "" } else { "s" },
+                unknown.join(", ")
+            ))),
+            Err(err) => self.errors.push(err),
+        }
+    }
+
+    /// Converts the collected errors into a single error.
+    ///
+    /// A single error is returned unchanged; otherwise a `TypeError` lists all of them.
+    #[cold]
+    pub fn into_err(mut self, py: Python<'_>) -> PyErr {
+        if self.errors.len() == 1 {
+            return self.errors.pop().unwrap();
+        }
+        let mut err_msg = format!("failed to extract {}", self.struct_name);
+        for error in self.errors {
+            use std::fmt::Write;
+            write!(&mut err_msg, "\n- {}", extract_traceback(py, error)).unwrap();
+        }
+        PyTypeError::new_err(err_msg)
+    }
+}
@@ -6,8 +6,11 @@ use crate::{
     internal_tricks::extract_c_string,
     pycell::PyCellLayout,
     pyclass_init::PyObjectInit,
-    types::PyBool,
-    Py, PyAny, PyCell, PyClass, PyErr, PyMethodDefType, PyNativeType, PyResult, PyTypeInfo, Python,
+    types::{
+        any::PyAnyMethods, dict::PyDictMethods, string::PyStringMethods, PyBool, PyDict, PyType,
+    },
+    Bound, FromPyObject, IntoPy, IntoPyObject, Py, PyAny, PyCell, PyClass, PyErr, PyMethodDefType,
+    PyNativeType, PyObject, PyResult, PyTypeInfo, Python,
 };
 use std::{
     borrow::Cow,
@@ -19,6 +22,8 @@ use std::{
 };
 
 mod lazy_type_object;
+pub use crate::pyclass::array::{py_array, py_array_interface};
+pub use crate::pyclass::len::{py_len, py_length_hint};
 pub use lazy_type_object::LazyTypeObject;
 
 /// Gets the offset of the dictionary from the start of the object in bytes.
@@ -33,6 +38,21 @@ pub fn weaklist_offset<T: PyClass>() -> ffi::Py_ssize_t {
     PyCell::<T>::weaklist_offset()
 }
 
+/// Gets the offset of the vectorcall function pointer from the start of the object in bytes.
+#[inline]
+pub fn vectorcall_offset<T: PyClass>() -> ffi::Py_ssize_t {
+    PyCell::<T>::vectorcall_offset()
+}
+
+/// Signature of `vectorcallfunc`, defined for all Python versions so that the code generated by
+/// `#[pymethods]` does not depend on them.
+pub type VectorcallFunc = unsafe extern "C" fn(
+    *mut ffi::PyObject,
+    *const *mut ffi::PyObject,
+    usize,
+    *mut ffi::PyObject,
+) -> *mut ffi::PyObject;
+
 /// Represents the `__dict__` field for `#[pyclass]`.
 pub trait PyClassDict {
     /// Initial form of a [PyObject](crate::ffi::PyObject) `__dict__` reference.
@@ -57,6 +77,13 @@ pub trait PyClassWeakRef {
     private_decl! {}
 }
 
+/// Represents the vectorcall function pointer field for `#[pyclass]`.
+pub trait PyClassVectorcall {
+    /// Initializes the field with the class' vectorcall implementation, if any.
+    fn new(vectorcall: Option<VectorcallFunc>) -> Self;
+    private_decl! {}
+}
+
 /// Zero-sized dummy field.
 pub struct PyClassDummySlot;
 
@@ -70,6 +97,14 @@ impl PyClassWeakRef for PyClassDummySlot {
     const INIT: Self = PyClassDummySlot;
 }
 
+impl PyClassVectorcall for PyClassDummySlot {
+    private_impl! {}
+    #[inline]
+    fn new(_vectorcall: Option<VectorcallFunc>) -> Self {
+        PyClassDummySlot
+    }
+}
+
 /// Actual dict field, which holds the pointer to `__dict__`.
 ///
 /// `#[pyclass(dict)]` automatically adds this.
@@ -104,6 +139,20 @@ impl PyClassWeakRef for PyClassWeakRefSlot {
     }
 }
 
+/// Actual vectorcall field, which holds the pointer to the `__call__` implementation.
+///
+/// `#[pyclass(vectorcall)]` automatically adds this.
+#[repr(transparent)]
+pub struct PyClassVectorcallSlot(Option<VectorcallFunc>);
+
+impl PyClassVectorcall for PyClassVectorcallSlot {
+    private_impl! {}
+    #[inline]
+    fn new(vectorcall: Option<VectorcallFunc>) -> Self {
+        Self(vectorcall)
+    }
+}
+
 /// This type is used as a "dummy" type on which dtolnay specializations are
 /// applied to apply implementations from `#[pymethods]`
 pub struct PyClassImplCollector<T>(PhantomData<T>);
@@ -153,6 +202,18 @@ pub trait PyClassImpl: Sized + 'static {
     /// #[pyclass(sequence)]
     const IS_SEQUENCE: bool = false;
 
+    /// #[pyclass(immutable_type)]
+    const IS_IMMUTABLE_TYPE: bool = false;
+
+    /// #[pyclass(immortal)]
+    const IS_IMMORTAL: bool = false;
+
+    /// #[pyclass(abc = "...")]
+    const ABC: Option<&'static str> = None;
+
+    /// #[pyclass(shared = "...")]
+    const SHARED: Option<&'static str> = None;
+
     /// Base class
     type BaseType: PyTypeInfo + PyClassBaseType;
 
@@ -165,6 +226,9 @@ pub trait PyClassImpl: Sized + 'static {
     /// Specify this class has `#[pyclass(weakref)]` or not.
     type WeakRef: PyClassWeakRef;
 
+    /// Specify this class has `#[pyclass(vectorcall)]` or not.
+    type Vectorcall: PyClassVectorcall;
+
     /// The closest native ancestor. This is `PyAny` by default, and when you declare
     /// `#[pyclass(extends=PyDict)]`, it's `PyDict`.
     type BaseNativeType: PyTypeInfo + PyNativeType;
@@ -196,6 +260,23 @@ pub trait PyClassImpl: Sized + 'static {
         None
     }
 
+    #[inline]
+    fn vectorcall_offset() -> Option<ffi::Py_ssize_t> {
+        None
+    }
+
+    /// Vectorcall implementation of `__call__`, stored in instances of `#[pyclass(vectorcall)]`.
+    #[inline]
+    fn vectorcall() -> Option<VectorcallFunc> {
+        None
+    }
+
+    /// #[pyclass(metaclass=...)]
+    #[inline]
+    fn metaclass_type_object(_py: Python<'_>) -> Option<*mut ffi::PyTypeObject> {
+        None
+    }
+
     fn lazy_type_object() -> &'static LazyTypeObject<Self>;
 }
 
@@ -222,6 +303,204 @@ pub fn build_pyclass_doc(
     }
 }
 
+/// Implementation of `__class_getitem__` for `#[pyclass(generic)]`.
+///
+/// Equivalent to the Python expression `types.GenericAlias(cls, key)`, which requires Python 3.9.
+pub fn generic_alias<'py>(
+    cls: &Bound<'py, PyType>,
+    key: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = cls.py();
+    py.import_bound(crate::intern!(py, "types"))?
+        .getattr(crate::intern!(py, "GenericAlias"))?
+        .call1((cls, key))
+}
+
+/// Creates an instance of `cls` holding `value`, for the reconstructor of `#[pyclass(pickle)]`,
+/// so that instances of subclasses defined in Python unpickle as the subclass.
+///
+/// `dict` is the instance `__dict__` returned by [`pickle_instance_dict`] at pickling time.
+pub fn unpickle_as<'py, T: PyClass>(
+    cls: &Bound<'py, PyType>,
+    value: impl Into<crate::PyClassInitializer<T>>,
+    dict: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, T>> {
+    let py = cls.py();
+    let target_type = cls.as_ptr().cast::<ffi::PyTypeObject>();
+    if unsafe { ffi::PyType_IsSubtype(target_type, T::type_object_raw(py)) } == 0 {
+        return Err(crate::exceptions::PyTypeError::new_err(format!(
+            "{} is not a subclass of {}",
+            cls,
+            T::NAME
+        )));
+    }
+    // Safety: `target_type` is the type object of `T` or a subclass, checked above
+    let obj = unsafe { value.into().create_class_object_of_type(py, target_type)? };
+    if let Some(dict) = dict {
+        obj.as_any()
+            .getattr(crate::intern!(py, "__dict__"))?
+            .downcast_into::<PyDict>()?
+            .update(dict.as_mapping())?;
+    }
+    Ok(obj)
+}
+
+/// Returns the `__dict__` of `obj` if it has a non-empty one, for `__reduce_ex__` of
+/// `#[pyclass(pickle)]`, so that attributes of instances of subclasses defined in Python survive
+/// pickling.
+pub fn pickle_instance_dict<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyDict>>> {
+    let py = obj.py();
+    match obj.getattr(crate::intern!(py, "__dict__")) {
+        Ok(dict) => {
+            let dict = dict.downcast_into::<PyDict>()?;
+            Ok(if dict.is_empty() { None } else { Some(dict) })
+        }
+        Err(err) if err.is_instance_of::<PyAttributeError>(py) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Creates an instance of the type of `slf` holding `value`, for `__copy__` and `__deepcopy__` of
+/// `#[pyclass(copy)]`, so that copies of instances of subclasses defined in Python keep the subclass.
+pub fn copy_as_type_of<'py, T: PyClass>(
+    slf: &Bound<'py, T>,
+    value: impl Into<crate::PyClassInitializer<T>>,
+) -> PyResult<Bound<'py, T>> {
+    // Safety: the type of `slf` is the type object of `T` or a subclass
+    unsafe {
+        value
+            .into()
+            .create_class_object_of_type(slf.py(), ffi::Py_TYPE(slf.as_ptr()))
+    }
+}
+
+/// Deep-copies a field of a `#[pyclass(copy)]` type, by calling `copy.deepcopy(field, memo)` on
+/// the field converted to a Python object and extracting the result back into the field's type.
+pub fn deepcopy_field<'py, T: FromPyObject<'py>>(
+    py: Python<'py>,
+    field: PyObject,
+    memo: &Bound<'py, PyDict>,
+) -> PyResult<T> {
+    py.import_bound(crate::intern!(py, "copy"))?
+        .call_method1(crate::intern!(py, "deepcopy"), (field, memo))?
+        .extract()
+}
+
+/// Implementation of `__dataclass_fields__` for `#[pyclass(dataclass)]`.
+///
+/// The `dataclasses.Field` objects are taken from an equivalent dataclass created with
+/// `dataclasses.make_dataclass`, so that `dataclasses.fields` and `dataclasses.asdict` accept
+/// instances of the class.
+pub fn dataclass_fields(
+    py: Python<'_>,
+    class_name: &str,
+    field_names: &[&str],
+) -> PyResult<PyObject> {
+    let dataclass = py
+        .import_bound(crate::intern!(py, "dataclasses"))?
+        .call_method1(
+            crate::intern!(py, "make_dataclass"),
+            (class_name, field_names.to_vec()),
+        )?;
+    dataclass
+        .getattr(crate::intern!(py, "__dataclass_fields__"))
+        .map(Bound::unbind)
+}
+
+/// Resolves the type hint of a `#[pyclass]` field for `__annotations__`.
+///
+/// Type hints are only available with the `experimental-inspect` feature; otherwise this
+/// returns `None` and the field is left out of `__annotations__`.
+pub type FieldTypeHint = for<'py> fn(Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>>;
+
+/// [`FieldTypeHint`] for a field of type `T`, taken from [`IntoPyObject::type_output`].
+pub fn field_type_hint<'py, T: for<'a> IntoPyObject<'a>>(
+    py: Python<'py>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    #[cfg(feature = "experimental-inspect")]
+    {
+        <T as IntoPyObject<'py>>::type_output()
+            .to_type_hint(py)
+            .map(Some)
+    }
+    #[cfg(not(feature = "experimental-inspect"))]
+    {
+        let _ = py;
+        Ok(None)
+    }
+}
+
+/// [`FieldTypeHint`] for a field of type `T`, taken from [`IntoPy::type_output`].
+///
+/// Used for field types which do not implement [`IntoPyObject`].
+pub fn field_type_hint_into_py<'py, T: IntoPy<PyObject>>(
+    py: Python<'py>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    #[cfg(feature = "experimental-inspect")]
+    {
+        <T as IntoPy<PyObject>>::type_output()
+            .to_type_hint(py)
+            .map(Some)
+    }
+    #[cfg(not(feature = "experimental-inspect"))]
+    {
+        let _ = py;
+        Ok(None)
+    }
+}
+
+/// Implementation of `__annotations__` for `#[pyclass]` structs with `get` fields.
+pub fn class_annotations(
+    py: Python<'_>,
+    fields: &[(&'static str, FieldTypeHint)],
+) -> PyResult<PyObject> {
+    let annotations = PyDict::new_bound(py);
+    for (name, type_hint) in fields {
+        if let Some(type_hint) = type_hint(py)? {
+            annotations.set_item(name, type_hint)?;
+        }
+    }
+    Ok(annotations.into_any().unbind())
+}
+
+/// Appends an `Attributes:` section to the class doc, for `#[pyclass]` structs with fields which
+/// have a getter when the `experimental-inspect` feature is enabled.
+///
+/// Each field is listed with its type hint and doc comment, in the Google docstring style which
+/// Sphinx understands through its `napoleon` extension.
+pub fn class_doc_with_attributes(
+    py: Python<'_>,
+    doc: Cow<'static, CStr>,
+    fields: &[(&'static str, FieldTypeHint, &'static str)],
+) -> PyResult<Cow<'static, CStr>> {
+    let mut doc = doc.to_string_lossy().into_owned();
+    if !doc.is_empty() && !doc.ends_with('\n') {
+        doc.push_str("\n\n");
+    }
+    doc.push_str("Attributes:");
+    for (name, type_hint, field_doc) in fields {
+        doc.push_str("\n    ");
+        doc.push_str(name);
+        if let Some(type_hint) = type_hint(py)? {
+            let type_hint = match type_hint.downcast::<PyType>() {
+                Ok(ty) => ty.getattr(crate::intern!(py, "__qualname__"))?.str()?,
+                Err(_) => type_hint.str()?,
+            };
+            doc.push_str(" (");
+            doc.push_str(&type_hint.to_cow()?);
+            doc.push(')');
+        }
+        let field_doc = field_doc.trim_end_matches('\0');
+        if !field_doc.is_empty() {
+            doc.push_str(": ");
+            doc.push_str(&field_doc.replace('\n', "\n        "));
+        }
+    }
+    CString::new(doc)
+        .map(Cow::Owned)
+        .map_err(|_| PyValueError::new_err("class doc cannot contain nul bytes"))
+}
+
 /// Iterator used to process all class items during type instantiation.
 pub struct PyClassItemsIter {
     /// Iteration state
@@ -234,6 +513,8 @@ pub struct PyClassItemsIter {
     /// Items from the `#[pymethods]` macro with inventory
     #[cfg(feature = "multiple-pymethods")]
     pymethods_items: Box<dyn Iterator<Item = &'static PyClassItems>>,
+    /// Items from `#[pymethods]` impls of the legacy protocol traits
+    pyproto_items: std::array::IntoIter<&'static PyClassItems, PYPROTO_PROTOCOLS>,
 }
 
 impl PyClassItemsIter {
@@ -248,8 +529,21 @@ impl PyClassItemsIter {
             idx: 0,
             pyclass_items,
             pymethods_items,
+            pyproto_items: [&EMPTY_ITEMS; PYPROTO_PROTOCOLS].into_iter(),
         }
     }
+
+    pub fn with_pyproto_items(
+        mut self,
+        pyproto_items: [&'static PyClassItems; PYPROTO_PROTOCOLS],
+    ) -> Self {
+        self.pyproto_items = pyproto_items.into_iter();
+        self
+    }
+
+    fn next_pyproto(&mut self) -> Option<&'static PyClassItems> {
+        self.pyproto_items.next()
+    }
 }
 
 impl Iterator for PyClassItemsIter {
@@ -267,7 +561,7 @@ impl Iterator for PyClassItemsIter {
                 Some(self.pymethods_items)
             }
             // Termination clause
-            _ => None,
+            _ => self.next_pyproto(),
         }
     }
 
@@ -279,12 +573,22 @@ impl Iterator for PyClassItemsIter {
                 Some(self.pyclass_items)
             }
             // Termination clause
-            _ => self.pymethods_items.next(),
+            _ => match self.pymethods_items.next() {
+                Some(items) => Some(items),
+                None => self.next_pyproto(),
+            },
         }
     }
 }
 
 // Traits describing known special methods.
+//
+// The slots call these through a `&PyClassImplCollector<T>`, so method resolution picks, in
+// order of priority:
+// - the implementation for `&PyClassImplCollector<T>` from an inherent `#[pymethods]` block,
+// - the implementation for `&&PyClassImplCollector<T>` from a `#[pymethods]` impl of a legacy
+//   protocol trait, which therefore never conflicts with the inherent one,
+// - the default implementation for `PyClassImplCollector<T>`.
 
 macro_rules! slot_fragment_trait {
     ($trait_name:ident, $($default_method:tt)*) => {
@@ -293,7 +597,7 @@ macro_rules! slot_fragment_trait {
             $($default_method)*
         }
 
-        impl<T> $trait_name<T> for &'_ PyClassImplCollector<T> {}
+        impl<T> $trait_name<T> for PyClassImplCollector<T> {}
     }
 }
 
@@ -345,7 +649,7 @@ macro_rules! generate_pyclass_getattro_slot {
             $crate::impl_::trampoline::getattrofunc(_slf, attr, |py, _slf, attr| {
                 use ::std::result::Result::*;
                 use $crate::impl_::pyclass::*;
-                let collector = PyClassImplCollector::<$cls>::new();
+                let collector = &PyClassImplCollector::<$cls>::new();
 
                 // Strategy:
                 // - Try __getattribute__ first. Its default is PyObject_GenericGetAttr.
@@ -434,7 +738,7 @@ macro_rules! define_pyclass_setattr_slot {
                             use ::std::option::Option::*;
                             use $crate::callback::IntoPyCallbackOutput;
                             use $crate::impl_::pyclass::*;
-                            let collector = PyClassImplCollector::<$cls>::new();
+                            let collector = &PyClassImplCollector::<$cls>::new();
                             if let Some(value) = ::std::ptr::NonNull::new(value) {
                                 collector.$set(py, _slf, attr, value).convert(py)
                             } else {
@@ -543,7 +847,7 @@ macro_rules! define_pyclass_binary_operator_slot {
                 ) -> *mut $crate::ffi::PyObject {
                     $crate::impl_::trampoline::binaryfunc(_slf, _other, |py, _slf, _other| {
                         use $crate::impl_::pyclass::*;
-                        let collector = PyClassImplCollector::<$cls>::new();
+                        let collector = &PyClassImplCollector::<$cls>::new();
                         let lhs_result = collector.$lhs(py, _slf, _other)?;
                         if lhs_result == $crate::ffi::Py_NotImplemented() {
                             $crate::ffi::Py_DECREF(lhs_result);
@@ -736,7 +1040,7 @@ macro_rules! generate_pyclass_pow_slot {
         ) -> *mut $crate::ffi::PyObject {
             $crate::impl_::trampoline::ternaryfunc(_slf, _other, _mod, |py, _slf, _other, _mod| {
                 use $crate::impl_::pyclass::*;
-                let collector = PyClassImplCollector::<$cls>::new();
+                let collector = &PyClassImplCollector::<$cls>::new();
                 let lhs_result = collector.__pow__(py, _slf, _other, _mod)?;
                 if lhs_result == $crate::ffi::Py_NotImplemented() {
                     $crate::ffi::Py_DECREF(lhs_result);
@@ -851,31 +1155,28 @@ slot_fragment_trait! {
 #[macro_export]
 macro_rules! generate_pyclass_richcompare_slot {
     ($cls:ty) => {{
-        impl $cls {
-            #[allow(non_snake_case)]
-            unsafe extern "C" fn __pymethod___richcmp____(
-                slf: *mut $crate::ffi::PyObject,
-                other: *mut $crate::ffi::PyObject,
-                op: ::std::os::raw::c_int,
-            ) -> *mut $crate::ffi::PyObject {
-                $crate::impl_::trampoline::richcmpfunc(slf, other, op, |py, slf, other, op| {
-                    use $crate::class::basic::CompareOp;
-                    use $crate::impl_::pyclass::*;
-                    let collector = PyClassImplCollector::<$cls>::new();
-                    match CompareOp::from_raw(op).expect("invalid compareop") {
-                        CompareOp::Lt => collector.__lt__(py, slf, other),
-                        CompareOp::Le => collector.__le__(py, slf, other),
-                        CompareOp::Eq => collector.__eq__(py, slf, other),
-                        CompareOp::Ne => collector.__ne__(py, slf, other),
-                        CompareOp::Gt => collector.__gt__(py, slf, other),
-                        CompareOp::Ge => collector.__ge__(py, slf, other),
-                    }
-                })
-            }
+        unsafe extern "C" fn __wrap(
+            slf: *mut $crate::ffi::PyObject,
+            other: *mut $crate::ffi::PyObject,
+            op: ::std::os::raw::c_int,
+        ) -> *mut $crate::ffi::PyObject {
+            $crate::impl_::trampoline::richcmpfunc(slf, other, op, |py, slf, other, op| {
+                use $crate::class::basic::CompareOp;
+                use $crate::impl_::pyclass::*;
+                let collector = &PyClassImplCollector::<$cls>::new();
+                match CompareOp::from_raw(op).expect("invalid compareop") {
+                    CompareOp::Lt => collector.__lt__(py, slf, other),
+                    CompareOp::Le => collector.__le__(py, slf, other),
+                    CompareOp::Eq => collector.__eq__(py, slf, other),
+                    CompareOp::Ne => collector.__ne__(py, slf, other),
+                    CompareOp::Gt => collector.__gt__(py, slf, other),
+                    CompareOp::Ge => collector.__ge__(py, slf, other),
+                }
+            })
         }
         $crate::ffi::PyType_Slot {
             slot: $crate::ffi::Py_tp_richcompare,
-            pfunc: <$cls>::__pymethod___richcmp____ as $crate::ffi::richcmpfunc as _,
+            pfunc: __wrap as $crate::ffi::richcmpfunc as _,
         }
     }};
 }
@@ -996,6 +1297,46 @@ impl<T> PyMethods<T> for &'_ PyClassImplCollector<T> {
     }
 }
 
+static EMPTY_ITEMS: PyClassItems = PyClassItems {
+    methods: &[],
+    slots: &[],
+};
+
+/// Number of legacy protocol traits which `#[pymethods]` can implement, each with its own
+/// collector trait.
+pub const PYPROTO_PROTOCOLS: usize = 10;
+
+// Items from #[pymethods] impls of the legacy protocol traits, one trait per protocol so that a
+// class can implement several.
+macro_rules! pyproto_items_traits {
+    ($($trait_name:ident::$method:ident),* $(,)?) => {
+        $(
+            pub trait $trait_name<T> {
+                fn $method(self) -> &'static PyClassItems;
+            }
+
+            impl<T> $trait_name<T> for &'_ PyClassImplCollector<T> {
+                fn $method(self) -> &'static PyClassItems {
+                    &EMPTY_ITEMS
+                }
+            }
+        )*
+    };
+}
+
+pyproto_items_traits!(
+    PyObjectProtocolItems::object_protocol_items,
+    PyNumberProtocolItems::number_protocol_items,
+    PyMappingProtocolItems::mapping_protocol_items,
+    PySequenceProtocolItems::sequence_protocol_items,
+    PyIterProtocolItems::iter_protocol_items,
+    PyAsyncProtocolItems::async_protocol_items,
+    PyContextProtocolItems::context_protocol_items,
+    PyDescrProtocolItems::descr_protocol_items,
+    PyGCProtocolItems::gc_protocol_items,
+    PyBufferProtocolItems::buffer_protocol_items,
+);
+
 // Text signature for __new__
 pub trait PyClassNewTextSignature<T> {
     fn new_text_signature(self) -> Option<&'static str>;
@@ -1008,11 +1349,25 @@ impl<T> PyClassNewTextSignature<T> for &'_ PyClassImplCollector<T> {
     }
 }
 
+// Vectorcall implementation of __call__
+pub trait PyClassCallVectorcall<T> {
+    fn vectorcall(self) -> Option<VectorcallFunc>;
+}
+
+impl<T> PyClassCallVectorcall<T> for &'_ PyClassImplCollector<T> {
+    #[inline]
+    fn vectorcall(self) -> Option<VectorcallFunc> {
+        None
+    }
+}
+
 // Thread checkers
 
 #[doc(hidden)]
 pub trait PyClassThreadChecker<T>: Sized {
     fn ensure(&self);
+    /// Like `ensure`, but raises `RuntimeError` instead of panicking.
+    fn try_ensure(&self) -> PyResult<()>;
     fn check(&self) -> bool;
     fn can_drop(&self, py: Python<'_>) -> bool;
     fn new() -> Self;
@@ -1029,6 +1384,9 @@ pub struct SendablePyClass<T: Send>(PhantomData<T>);
 
 impl<T: Send> PyClassThreadChecker<T> for SendablePyClass<T> {
     fn ensure(&self) {}
+    fn try_ensure(&self) -> PyResult<()> {
+        Ok(())
+    }
     fn check(&self) -> bool {
         true
     }
@@ -1057,6 +1415,17 @@ impl ThreadCheckerImpl {
         );
     }
 
+    fn try_ensure(&self, type_name: &'static str) -> PyResult<()> {
+        if self.check() {
+            Ok(())
+        } else {
+            Err(PyRuntimeError::new_err(format!(
+                "{} is unsendable, but sent to another thread",
+                type_name
+            )))
+        }
+    }
+
     fn check(&self) -> bool {
         thread::current().id() == self.0
     }
@@ -1079,6 +1448,9 @@ impl<T> PyClassThreadChecker<T> for ThreadCheckerImpl {
     fn ensure(&self) {
         self.ensure(std::any::type_name::<T>());
     }
+    fn try_ensure(&self) -> PyResult<()> {
+        self.try_ensure(std::any::type_name::<T>())
+    }
     fn check(&self) -> bool {
         self.check()
     }
@@ -1111,11 +1483,18 @@ impl<T: PyClass> PyClassBaseType for T {
 
 /// Implementation of tp_dealloc for pyclasses without gc
 pub(crate) unsafe extern "C" fn tp_dealloc<T: PyClass>(obj: *mut ffi::PyObject) {
+    if call_finalizer_from_dealloc(obj) {
+        return;
+    }
     crate::impl_::trampoline::dealloc(obj, PyCell::<T>::tp_dealloc)
 }
 
 /// Implementation of tp_dealloc for pyclasses with gc
 pub(crate) unsafe extern "C" fn tp_dealloc_with_gc<T: PyClass>(obj: *mut ffi::PyObject) {
+    // The finalizer runs while the object is still tracked, as in CPython's own dealloc.
+    if call_finalizer_from_dealloc(obj) {
+        return;
+    }
     #[cfg(not(PyPy))]
     {
         ffi::PyObject_GC_UnTrack(obj.cast());
@@ -1123,6 +1502,22 @@ pub(crate) unsafe extern "C" fn tp_dealloc_with_gc<T: PyClass>(obj: *mut ffi::Py
     crate::impl_::trampoline::dealloc(obj, PyCell::<T>::tp_dealloc)
 }
 
+/// Calls `tp_finalize` (i.e. `__del__`) if the type has one, before the object is deallocated.
+///
+/// Returns `true` if the finalizer resurrected the object, in which case it must not be freed.
+#[cfg(not(Py_LIMITED_API))]
+#[inline]
+unsafe fn call_finalizer_from_dealloc(obj: *mut ffi::PyObject) -> bool {
+    (*ffi::Py_TYPE(obj)).tp_finalize.is_some() && ffi::PyObject_CallFinalizerFromDealloc(obj) < 0
+}
+
+/// `__del__` is not supported with the limited API.
+#[cfg(Py_LIMITED_API)]
+#[inline]
+unsafe fn call_finalizer_from_dealloc(_obj: *mut ffi::PyObject) -> bool {
+    false
+}
+
 pub(crate) unsafe extern "C" fn get_sequence_item_from_mapping(
     obj: *mut ffi::PyObject,
     index: ffi::Py_ssize_t,
@@ -1,16 +1,18 @@
+use parking_lot::{const_mutex, const_reentrant_mutex, Mutex, ReentrantMutex, ReentrantMutexGuard};
 use std::{
     borrow::Cow,
-    cell::RefCell,
+    cell::Cell,
     ffi::CStr,
     marker::PhantomData,
     thread::{self, ThreadId},
+    time::Instant,
 };
 
 use crate::{
     exceptions::PyRuntimeError,
     ffi,
-    pyclass::{create_type_object, PyClassTypeObject},
-    sync::{GILOnceCell, GILProtected},
+    pyclass::{create_type_object, type_init_hook, PyClassTypeObject},
+    sync::GILOnceCell,
     types::PyType,
     PyClass, PyErr, PyMethodDefType, PyObject, PyResult, Python,
 };
@@ -24,9 +26,13 @@ pub struct LazyTypeObject<T>(LazyTypeObjectInner, PhantomData<T>);
 // Non-generic inner of LazyTypeObject to keep code size down
 struct LazyTypeObjectInner {
     value: GILOnceCell<PyClassTypeObject>,
+    // Held while creating the type object, so that it is only created once even if
+    // `create_type_object` releases the GIL (except as described in `lock_init`). Reentrant, as
+    // creating the type object may request it again on the same thread.
+    init_lock: ReentrantMutex<()>,
     // Threads which have begun initialization of the `tp_dict`. Used for
     // reentrant initialization detection.
-    initializing_threads: GILProtected<RefCell<Vec<ThreadId>>>,
+    initializing_threads: Mutex<Vec<ThreadId>>,
     tp_dict_filled: GILOnceCell<()>,
 }
 
@@ -36,7 +42,8 @@ impl<T> LazyTypeObject<T> {
         LazyTypeObject(
             LazyTypeObjectInner {
                 value: GILOnceCell::new(),
-                initializing_threads: GILProtected::new(RefCell::new(Vec::new())),
+                init_lock: const_reentrant_mutex(()),
+                initializing_threads: const_mutex(Vec::new()),
                 tp_dict_filled: GILOnceCell::new(),
             },
             PhantomData,
@@ -72,12 +79,25 @@ impl LazyTypeObjectInner {
         items_iter: PyClassItemsIter,
     ) -> PyResult<&'py PyType> {
         (|| -> PyResult<_> {
-            let type_object = self
-                .value
-                .get_or_try_init(py, || init(py))?
-                .type_object
-                .as_ref(py);
+            let mut started = None;
+            let value = match self.value.get(py) {
+                Some(value) => value,
+                None => {
+                    let _guard = lock_init(py, &self.init_lock);
+                    self.value.get_or_try_init(py, || {
+                        started = Some(Instant::now());
+                        init(py)
+                    })?
+                }
+            };
+            let type_object = value.type_object.as_ref(py);
             self.ensure_init(type_object, name, items_iter)?;
+            // Only look up the hook (which takes a lock) when the type object was just created
+            if let Some(started) = started {
+                if let Some(hook) = type_init_hook() {
+                    hook(py, name, started.elapsed());
+                }
+            }
             Ok(type_object)
         })()
         .map_err(|err| {
@@ -115,7 +135,7 @@ impl LazyTypeObjectInner {
 
         let thread_id = thread::current().id();
         {
-            let mut threads = self.initializing_threads.get(py).borrow_mut();
+            let mut threads = self.initializing_threads.lock();
             if threads.contains(&thread_id) {
                 // Reentrant call: just return the type object, even if the
                 // `tp_dict` is not filled yet.
@@ -125,20 +145,18 @@ impl LazyTypeObjectInner {
         }
 
         struct InitializationGuard<'a> {
-            initializing_threads: &'a GILProtected<RefCell<Vec<ThreadId>>>,
-            py: Python<'a>,
+            initializing_threads: &'a Mutex<Vec<ThreadId>>,
             thread_id: ThreadId,
         }
         impl Drop for InitializationGuard<'_> {
             fn drop(&mut self) {
-                let mut threads = self.initializing_threads.get(self.py).borrow_mut();
+                let mut threads = self.initializing_threads.lock();
                 threads.retain(|id| *id != self.thread_id);
             }
         }
 
         let guard = InitializationGuard {
             initializing_threads: &self.initializing_threads,
-            py,
             thread_id,
         };
 
@@ -178,7 +196,7 @@ impl LazyTypeObjectInner {
             // Initialization successfully complete, can clear the thread list.
             // (No further calls to get_or_init() will try to init, on any thread.)
             std::mem::forget(guard);
-            self.initializing_threads.get(py).replace(Vec::new());
+            *self.initializing_threads.lock() = Vec::new();
             result
         });
 
@@ -194,11 +212,60 @@ impl LazyTypeObjectInner {
     }
 }
 
+thread_local! {
+    /// The number of type objects this thread is creating while holding their `init_lock`.
+    static TYPE_OBJECTS_BEING_CREATED: Cell<usize> = Cell::new(0);
+}
+
+struct InitLockGuard<'a> {
+    _guard: ReentrantMutexGuard<'a, ()>,
+}
+
+impl Drop for InitLockGuard<'_> {
+    fn drop(&mut self) {
+        TYPE_OBJECTS_BEING_CREATED.with(|count| count.set(count.get() - 1));
+    }
+}
+
+/// Acquires `init_lock` before creating a type object, releasing the GIL while waiting as the
+/// thread which holds the lock may need the GIL to finish creating it.
+///
+/// A thread which is already creating another type object does not wait: two threads could
+/// then each be waiting for the type object the other one is creating. It creates the type
+/// object itself instead, and the first one stored is used.
+fn lock_init<'a>(py: Python<'_>, init_lock: &'a ReentrantMutex<()>) -> Option<InitLockGuard<'a>> {
+    loop {
+        if let Some(guard) = init_lock.try_lock() {
+            TYPE_OBJECTS_BEING_CREATED.with(|count| count.set(count.get() + 1));
+            return Some(InitLockGuard { _guard: guard });
+        }
+        if TYPE_OBJECTS_BEING_CREATED.with(Cell::get) > 0 {
+            return None;
+        }
+        py.allow_threads(|| drop(init_lock.lock()));
+    }
+}
+
 fn initialize_tp_dict(
     py: Python<'_>,
     type_object: *mut ffi::PyObject,
     items: Vec<(Cow<'static, CStr>, PyObject)>,
 ) -> PyResult<()> {
+    // Immutable types reject setting attributes through `type.__setattr__`, and the `type`
+    // descriptors such as `__annotations__` reject them too, so their attributes are inserted
+    // into `tp_dict` directly, after which the type cache must be invalidated.
+    #[cfg(all(Py_3_10, not(Py_LIMITED_API)))]
+    if unsafe { ffi::PyType_HasFeature(type_object.cast(), ffi::Py_TPFLAGS_IMMUTABLETYPE) } != 0 {
+        let dict = unsafe { (*type_object.cast::<ffi::PyTypeObject>()).tp_dict };
+        for (key, val) in items {
+            crate::err::error_on_minusone(py, unsafe {
+                ffi::PyDict_SetItemString(dict, key.as_ptr(), val.as_ptr())
+            })?;
+        }
+        unsafe { ffi::PyType_Modified(type_object.cast()) };
+        return Ok(());
+    }
+
     // We hold the GIL: the dictionary update can be considered atomic from
     // the POV of other threads.
     for (key, val) in items {
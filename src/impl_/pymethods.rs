@@ -62,42 +62,31 @@ pub enum PyMethodDefType {
     Getter(PyGetterDef),
     /// Represents setter descriptor, used by `#[setter]`
     Setter(PySetterDef),
-}
-
-#[derive(Copy, Clone, Debug)]
-pub enum PyMethodType {
-    PyCFunction(PyCFunction),
-    PyCFunctionWithKeywords(PyCFunctionWithKeywords),
-    #[cfg(not(Py_LIMITED_API))]
-    PyCFunctionFastWithKeywords(PyCFunctionFastWithKeywords),
+    /// Represents deleter descriptor, used by `#[deleter]`
+    Deleter(PyDeleterDef),
 }
 
 // These newtype structs serve no purpose other than wrapping which are function pointers - because
 // function pointers aren't allowed in const fn, but types wrapping them are!
-#[derive(Clone, Copy, Debug)]
-pub struct PyCFunction(pub ffi::PyCFunction);
-#[derive(Clone, Copy, Debug)]
-pub struct PyCFunctionWithKeywords(pub ffi::PyCFunctionWithKeywords);
-#[cfg(not(Py_LIMITED_API))]
-#[derive(Clone, Copy, Debug)]
-pub struct PyCFunctionFastWithKeywords(pub ffi::_PyCFunctionFastWithKeywords);
 #[derive(Clone, Copy)]
 pub struct PyGetter(pub Getter);
 #[derive(Clone, Copy)]
 pub struct PySetter(pub Setter);
 #[derive(Clone, Copy)]
+pub struct PyDeleter(pub Deleter);
+#[derive(Clone, Copy)]
 pub struct PyClassAttributeFactory(pub for<'p> fn(Python<'p>) -> PyResult<PyObject>);
 
 // TODO: it would be nice to use CStr in these types, but then the constructors can't be const fn
 // until `CStr::from_bytes_with_nul_unchecked` is const fn.
 
-#[derive(Clone, Debug)]
-pub struct PyMethodDef {
-    pub(crate) ml_name: &'static str,
-    pub(crate) ml_meth: PyMethodType,
-    pub(crate) ml_flags: c_int,
-    pub(crate) ml_doc: &'static str,
-}
+/// Definition of a method or function, in the form expected by the Python C API.
+///
+/// Definitions generated by the macros point to `'static` data baked at compile time, so they can
+/// be copied into the method table of a type, or used to create a function object, as is.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct PyMethodDef(ffi::PyMethodDef);
 
 #[derive(Copy, Clone)]
 pub struct PyClassAttributeDef {
@@ -125,82 +114,121 @@ pub struct PySetterDef {
     pub(crate) doc: &'static str,
 }
 
+#[derive(Clone)]
+pub struct PyDeleterDef {
+    pub(crate) name: &'static str,
+    pub(crate) meth: PyDeleter,
+    pub(crate) doc: &'static str,
+}
+
+// Only points to `'static` data, which is never mutated.
 unsafe impl Sync for PyMethodDef {}
 
 unsafe impl Sync for PyGetterDef {}
 
 unsafe impl Sync for PySetterDef {}
 
+unsafe impl Sync for PyDeleterDef {}
+
 impl PyMethodDef {
+    /// Define a method from its C function pointer and flags.
+    ///
+    /// # Safety
+    ///
+    /// `name` and `doc` must be nul-terminated, and `flags` must match the calling convention of
+    /// `meth`.
+    pub const unsafe fn new(
+        name: &'static str,
+        meth: ffi::PyMethodDefPointer,
+        flags: c_int,
+        doc: &'static str,
+    ) -> Self {
+        Self(ffi::PyMethodDef {
+            ml_name: name.as_ptr().cast(),
+            ml_meth: meth,
+            ml_flags: flags,
+            ml_doc: doc.as_ptr().cast(),
+        })
+    }
+
     /// Define a function with no `*args` and `**kwargs`.
-    pub const fn noargs(name: &'static str, cfunction: PyCFunction, doc: &'static str) -> Self {
-        Self {
-            ml_name: name,
-            ml_meth: PyMethodType::PyCFunction(cfunction),
-            ml_flags: ffi::METH_NOARGS,
-            ml_doc: doc,
-        }
+    ///
+    /// Unlike [`PyMethodDef::new`], `name` and `doc` do not need to be nul-terminated: they are
+    /// copied if needed, and the copies are kept alive by the returned destructor.
+    pub(crate) fn noargs(
+        name: &'static str,
+        cfunction: ffi::PyCFunction,
+        doc: &'static str,
+    ) -> PyResult<(Self, PyMethodDefDestructor)> {
+        Self::with_owned_strings(
+            name,
+            ffi::PyMethodDefPointer {
+                PyCFunction: cfunction,
+            },
+            ffi::METH_NOARGS,
+            doc,
+        )
     }
 
     /// Define a function that can take `*args` and `**kwargs`.
-    pub const fn cfunction_with_keywords(
+    ///
+    /// See [`PyMethodDef::noargs`] about `name` and `doc`.
+    pub(crate) fn cfunction_with_keywords(
         name: &'static str,
-        cfunction: PyCFunctionWithKeywords,
+        cfunction: ffi::PyCFunctionWithKeywords,
         doc: &'static str,
-    ) -> Self {
-        Self {
-            ml_name: name,
-            ml_meth: PyMethodType::PyCFunctionWithKeywords(cfunction),
-            ml_flags: ffi::METH_VARARGS | ffi::METH_KEYWORDS,
-            ml_doc: doc,
-        }
+    ) -> PyResult<(Self, PyMethodDefDestructor)> {
+        Self::with_owned_strings(
+            name,
+            ffi::PyMethodDefPointer {
+                PyCFunctionWithKeywords: cfunction,
+            },
+            ffi::METH_VARARGS | ffi::METH_KEYWORDS,
+            doc,
+        )
     }
 
-    /// Define a function that can take `*args` and `**kwargs`.
-    #[cfg(not(Py_LIMITED_API))]
-    pub const fn fastcall_cfunction_with_keywords(
+    fn with_owned_strings(
         name: &'static str,
-        cfunction: PyCFunctionFastWithKeywords,
+        meth: ffi::PyMethodDefPointer,
+        flags: c_int,
         doc: &'static str,
-    ) -> Self {
-        Self {
-            ml_name: name,
-            ml_meth: PyMethodType::PyCFunctionFastWithKeywords(cfunction),
-            ml_flags: ffi::METH_FASTCALL | ffi::METH_KEYWORDS,
-            ml_doc: doc,
-        }
+    ) -> PyResult<(Self, PyMethodDefDestructor)> {
+        let name = get_name(name)?;
+        let doc = get_doc(doc)?;
+        let def = ffi::PyMethodDef {
+            ml_name: name.as_ptr(),
+            ml_meth: meth,
+            ml_flags: flags,
+            ml_doc: doc.as_ptr(),
+        };
+        Ok((Self(def), PyMethodDefDestructor { name, doc }))
     }
 
     pub const fn flags(mut self, flags: c_int) -> Self {
-        self.ml_flags |= flags;
+        self.0.ml_flags |= flags;
         self
     }
 
-    /// Convert `PyMethodDef` to Python method definition struct `ffi::PyMethodDef`
-    pub(crate) fn as_method_def(&self) -> PyResult<(ffi::PyMethodDef, PyMethodDefDestructor)> {
-        let meth = match self.ml_meth {
-            PyMethodType::PyCFunction(meth) => ffi::PyMethodDefPointer {
-                PyCFunction: meth.0,
-            },
-            PyMethodType::PyCFunctionWithKeywords(meth) => ffi::PyMethodDefPointer {
-                PyCFunctionWithKeywords: meth.0,
-            },
-            #[cfg(not(Py_LIMITED_API))]
-            PyMethodType::PyCFunctionFastWithKeywords(meth) => ffi::PyMethodDefPointer {
-                _PyCFunctionFastWithKeywords: meth.0,
-            },
-        };
+    /// The Python method definition struct `ffi::PyMethodDef`.
+    pub(crate) fn as_method_def(&self) -> ffi::PyMethodDef {
+        self.0
+    }
 
-        let name = get_name(self.ml_name)?;
-        let doc = get_doc(self.ml_doc)?;
-        let def = ffi::PyMethodDef {
-            ml_name: name.as_ptr(),
-            ml_meth: meth,
-            ml_flags: self.ml_flags,
-            ml_doc: doc.as_ptr(),
-        };
-        let destructor = PyMethodDefDestructor { name, doc };
-        Ok((def, destructor))
+    /// Pointer to the definition, for `PyCFunction_NewEx`.
+    pub(crate) fn as_ptr(&'static self) -> *mut ffi::PyMethodDef {
+        // Python never mutates method definitions
+        &self.0 as *const ffi::PyMethodDef as *mut ffi::PyMethodDef
+    }
+}
+
+impl fmt::Debug for PyMethodDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = unsafe { CStr::from_ptr(self.0.ml_name) };
+        f.debug_struct("PyMethodDef")
+            .field("ml_name", &name)
+            .field("ml_flags", &self.0.ml_flags)
+            .finish()
     }
 }
 
@@ -221,11 +249,12 @@ impl fmt::Debug for PyClassAttributeDef {
     }
 }
 
-/// Class getter / setters
+/// Class getter / setters / deleters
 pub(crate) type Getter =
     for<'py> unsafe fn(Python<'py>, *mut ffi::PyObject) -> PyResult<*mut ffi::PyObject>;
 pub(crate) type Setter =
     for<'py> unsafe fn(Python<'py>, *mut ffi::PyObject, *mut ffi::PyObject) -> PyResult<c_int>;
+pub(crate) type Deleter = for<'py> unsafe fn(Python<'py>, *mut ffi::PyObject) -> PyResult<c_int>;
 
 impl PyGetterDef {
     /// Define a getter.
@@ -249,6 +278,17 @@ impl PySetterDef {
     }
 }
 
+impl PyDeleterDef {
+    /// Define a deleter.
+    pub const fn new(name: &'static str, deleter: PyDeleter, doc: &'static str) -> Self {
+        Self {
+            name,
+            meth: deleter,
+            doc,
+        }
+    }
+}
+
 /// Calls an implementation of __traverse__ for tp_traverse
 #[doc(hidden)]
 pub unsafe fn _call_traverse<T>(
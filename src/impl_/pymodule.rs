@@ -19,6 +19,9 @@ pub struct ModuleDef {
     interpreter: AtomicI64,
     /// Initialized module object, cached to avoid reinitialization.
     module: GILOnceCell<Py<PyModule>>,
+    /// Whether the module relies on the GIL (only applicable on the free-threaded build).
+    #[cfg_attr(not(py_sys_config = "Py_GIL_DISABLED"), allow(dead_code))]
+    gil_used: bool,
 }
 
 /// Wrapper to enable initializer to be used in const fns.
@@ -26,16 +29,46 @@ pub struct ModuleInitializer(pub for<'py> fn(Python<'py>, &PyModule) -> PyResult
 
 unsafe impl Sync for ModuleDef {}
 
+/// Fails const evaluation (or panics at runtime) unless the only nul byte in `s` is its last byte.
+///
+/// On Rust 1.56, where `assert!` is not usable in const context, this indexes out of bounds
+/// instead, so the error does not have a message.
+const fn check_nul_terminated(s: &str) {
+    let bytes = s.as_bytes();
+    let mut nul_count = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == 0 {
+            nul_count += 1;
+        }
+        i += 1;
+    }
+    let valid = nul_count == 1 && bytes[bytes.len() - 1] == 0;
+    #[cfg(const_panic)]
+    assert!(
+        valid,
+        "module name and doc must be nul-terminated and must not contain interior nul bytes"
+    );
+    // string must be nul-terminated and must not contain interior nul bytes
+    #[cfg(not(const_panic))]
+    let _: () = [()][!valid as usize];
+}
+
 impl ModuleDef {
     /// Make new module definition with given module name.
     ///
-    /// # Safety
-    /// `name` and `doc` must be null-terminated strings.
-    pub const unsafe fn new(
+    /// `name` and `doc` must be null-terminated strings without any interior nul bytes;
+    /// `#[pymodule]` appends the terminators itself, and rejects docs containing nul bytes. This is
+    /// also checked when the definition is built, so a malformed string used to initialize a
+    /// `static` (as `#[pymodule]` does) is a compile-time error rather than undefined behaviour.
+    pub const fn new(
         name: &'static str,
         doc: &'static str,
         initializer: ModuleInitializer,
     ) -> Self {
+        check_nul_terminated(name);
+        check_nul_terminated(doc);
+
         const INIT: ffi::PyModuleDef = ffi::PyModuleDef {
             m_base: ffi::PyModuleDef_HEAD_INIT,
             m_name: std::ptr::null(),
@@ -61,8 +94,18 @@ impl ModuleDef {
             #[cfg(all(not(PyPy), Py_3_9, not(all(windows, Py_LIMITED_API, not(Py_3_10)))))]
             interpreter: AtomicI64::new(-1),
             module: GILOnceCell::new(),
+            gil_used: true,
         }
     }
+
+    /// Sets whether the module relies on the GIL to protect its state.
+    ///
+    /// On the free-threaded build of Python, importing a module which uses the GIL enables the
+    /// GIL for the whole interpreter. Used for `#[pyo3(gil_used = false)]`.
+    pub const fn gil_used(mut self, gil_used: bool) -> Self {
+        self.gil_used = gil_used;
+        self
+    }
     /// Builds a module using user given initializer. Used for [`#[pymodule]`][crate::pymodule].
     pub fn make_module(&'static self, py: Python<'_>) -> PyResult<Py<PyModule>> {
         #[cfg(all(PyPy, not(Py_3_8)))]
@@ -118,6 +161,8 @@ impl ModuleDef {
                 }
             }
         }
+        // References dropped without the GIL should be released before the interpreter exits.
+        crate::gil::register_atexit_hook(py);
         self.module
             .get_or_try_init(py, || {
                 let module = unsafe {
@@ -126,6 +171,17 @@ impl ModuleDef {
                         ffi::PyModule_Create(self.ffi_def.get()),
                     )?
                 };
+                #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+                {
+                    let gil_used = if self.gil_used {
+                        ffi::Py_MOD_GIL_USED
+                    } else {
+                        ffi::Py_MOD_GIL_NOT_USED
+                    };
+                    crate::err::error_on_minusone(py, unsafe {
+                        ffi::PyUnstable_Module_SetGIL(module.as_ptr(), gil_used)
+                    })?;
+                }
                 (self.initializer.0)(py, module.as_ref(py))?;
                 Ok(module)
             })
@@ -133,6 +189,126 @@ impl ModuleDef {
     }
 }
 
+/// Module `__getattr__` ([PEP 562]) creating the type objects of the classes added with
+/// [`add_class_lazy`] on first access.
+///
+/// Created classes are stored in the module, so that `__getattr__` is only called once for each
+/// class. The module's `__dir__` is replaced by [`LazyClasses::dir`] to list the classes which
+/// were not created yet.
+///
+/// [PEP 562]: https://peps.python.org/pep-0562/
+/// [`add_class_lazy`]: crate::types::PyModuleMethods::add_class_lazy
+#[cfg(feature = "macros")]
+#[pyo3_macros::pyclass(crate = "crate")]
+pub(crate) struct LazyClasses {
+    /// The module, cleared when breaking reference cycles (see `__clear__`).
+    module: Option<Py<PyModule>>,
+    module_name: String,
+    classes: std::collections::HashMap<&'static str, LazyClass>,
+    /// `__getattr__` previously defined by the module, if any.
+    fallback: Option<crate::PyObject>,
+    /// `__dir__` previously defined by the module, if any.
+    dir_fallback: Option<crate::PyObject>,
+}
+
+#[cfg(feature = "macros")]
+type LazyClass = for<'py> fn(Python<'py>) -> PyResult<&'py crate::types::PyType>;
+
+#[cfg(feature = "macros")]
+impl LazyClasses {
+    /// Gets the registry used as `__getattr__` by `module`, installing a new one if needed.
+    pub(crate) fn get_or_install<'py>(
+        module: &crate::Bound<'py, PyModule>,
+    ) -> PyResult<crate::Bound<'py, LazyClasses>> {
+        use crate::types::{any::PyAnyMethods, dict::PyDictMethods, module::PyModuleMethods};
+
+        let py = module.py();
+        let getattr = crate::intern!(py, "__getattr__");
+        let fallback = match module.getattr(getattr) {
+            Ok(existing) => match existing.downcast_into::<LazyClasses>() {
+                Ok(registry) => return Ok(registry),
+                Err(err) => Some(err.into_inner().unbind()),
+            },
+            Err(_) => None,
+        };
+        let dir = crate::intern!(py, "__dir__");
+        let dir_fallback = module.dict().get_item(dir)?.map(crate::Bound::unbind);
+        let registry = crate::Bound::new(
+            py,
+            LazyClasses {
+                module: Some(module.clone().unbind()),
+                module_name: module.name()?.extract()?,
+                classes: std::collections::HashMap::new(),
+                fallback,
+                dir_fallback,
+            },
+        )?;
+        module.setattr(getattr, &registry)?;
+        module.setattr(dir, registry.as_any().getattr(crate::intern!(py, "dir"))?)?;
+        Ok(registry)
+    }
+
+    pub(crate) fn insert<T: crate::PyClass>(&mut self) {
+        self.classes
+            .insert(T::NAME, |py| T::lazy_type_object().get_or_try_init(py));
+    }
+}
+
+#[cfg(feature = "macros")]
+#[pyo3_macros::pymethods(crate = "crate")]
+impl LazyClasses {
+    fn __call__(&self, py: Python<'_>, name: &str) -> PyResult<crate::PyObject> {
+        if let Some(init) = self.classes.get(name) {
+            let ty: crate::PyObject = init(py)?.into();
+            if let Some(module) = &self.module {
+                module.setattr(py, name, &ty)?;
+            }
+            return Ok(ty);
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.call1(py, (name,)),
+            None => Err(crate::exceptions::PyAttributeError::new_err(format!(
+                "module '{}' has no attribute '{}'",
+                self.module_name, name
+            ))),
+        }
+    }
+
+    /// Module `__dir__`, which adds the classes not created yet to the attributes of the module.
+    fn dir(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        use crate::types::{any::PyAnyMethods, dict::PyDictMethods, module::PyModuleMethods};
+
+        let mut names: Vec<String> = match (&self.dir_fallback, &self.module) {
+            (Some(dir_fallback), _) => dir_fallback.call0(py)?.extract(py)?,
+            (None, Some(module)) => module.bind(py).dict().keys().extract()?,
+            (None, None) => Vec::new(),
+        };
+        names.extend(self.classes.keys().map(|name| name.to_string()));
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    fn __traverse__(
+        &self,
+        visit: crate::pyclass::PyVisit<'_>,
+    ) -> Result<(), crate::pyclass::PyTraverseError> {
+        if let Some(module) = &self.module {
+            visit.call(module)?;
+        }
+        for object in [&self.fallback, &self.dir_fallback].into_iter().flatten() {
+            visit.call(object)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.module = None;
+        self.fallback = None;
+        self.dir_fallback = None;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::{AtomicBool, Ordering};
@@ -143,16 +319,14 @@ mod tests {
 
     #[test]
     fn module_init() {
-        static MODULE_DEF: ModuleDef = unsafe {
-            ModuleDef::new(
-                "test_module\0",
-                "some doc\0",
-                ModuleInitializer(|_, m| {
-                    m.add("SOME_CONSTANT", 42)?;
-                    Ok(())
-                }),
-            )
-        };
+        static MODULE_DEF: ModuleDef = ModuleDef::new(
+            "test_module\0",
+            "some doc\0",
+            ModuleInitializer(|_, m| {
+                m.add("SOME_CONSTANT", 42)?;
+                Ok(())
+            }),
+        );
         Python::with_gil(|py| {
             let module = MODULE_DEF.make_module(py).unwrap().into_ref(py);
             assert_eq!(
@@ -197,16 +371,38 @@ mod tests {
             Ok(())
         }
 
+        let module_def: ModuleDef = ModuleDef::new(NAME, DOC, ModuleInitializer(init));
         unsafe {
-            let module_def: ModuleDef = ModuleDef::new(NAME, DOC, ModuleInitializer(init));
             assert_eq!((*module_def.ffi_def.get()).m_name, NAME.as_ptr() as _);
             assert_eq!((*module_def.ffi_def.get()).m_doc, DOC.as_ptr() as _);
-
-            Python::with_gil(|py| {
-                module_def.initializer.0(py, py.import_bound("builtins").unwrap().into_gil_ref())
-                    .unwrap();
-                assert!(INIT_CALLED.load(Ordering::SeqCst));
-            })
         }
+
+        Python::with_gil(|py| {
+            module_def.initializer.0(py, py.import_bound("builtins").unwrap().into_gil_ref())
+                .unwrap();
+            assert!(INIT_CALLED.load(Ordering::SeqCst));
+        })
+    }
+
+    #[test]
+    #[cfg_attr(const_panic, should_panic(expected = "must be nul-terminated"))]
+    #[cfg_attr(not(const_panic), should_panic(expected = "index out of bounds"))]
+    fn module_def_new_missing_nul() {
+        let _ = ModuleDef::new(
+            "test_module",
+            "some doc\0",
+            ModuleInitializer(|_, _| Ok(())),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(const_panic, should_panic(expected = "must be nul-terminated"))]
+    #[cfg_attr(not(const_panic), should_panic(expected = "index out of bounds"))]
+    fn module_def_new_interior_nul() {
+        let _ = ModuleDef::new(
+            "test_module\0",
+            "some\0doc\0",
+            ModuleInitializer(|_, _| Ok(())),
+        );
     }
 }
@@ -134,6 +134,33 @@ pub unsafe fn releasebufferproc(
     trampoline_unraisable(|py| f(py, slf, buf), slf)
 }
 
+// Errors raised by `__del__` are reported to `sys.unraisablehook`, as for Python classes. A
+// finalizer may run while another exception is being raised, which is preserved.
+//
+// The error is reported against the type rather than the object itself, so that the hook
+// cannot resurrect the object by keeping a reference to it.
+#[cfg(not(Py_LIMITED_API))]
+#[inline]
+pub unsafe fn destructor(
+    slf: *mut ffi::PyObject,
+    f: for<'py> unsafe fn(Python<'py>, *mut ffi::PyObject) -> PyResult<()>,
+) {
+    let ctx = ffi::Py_TYPE(slf).cast::<ffi::PyObject>();
+    trampoline_unraisable(
+        |py| {
+            let pending = crate::PyErr::take(py);
+            if let Err(err) = f(py, slf) {
+                err.write_unraisable_bound(py, ctx.assume_borrowed_or_opt(py).as_deref());
+            }
+            if let Some(pending) = pending {
+                pending.restore(py);
+            }
+            Ok(())
+        },
+        ctx,
+    )
+}
+
 #[inline]
 pub(crate) unsafe fn dealloc(
     slf: *mut ffi::PyObject,
@@ -152,6 +179,28 @@ pub(crate) unsafe fn dealloc(
     )
 }
 
+/// `vectorcallfunc` trampoline, which is the same as `fastcall_with_keywords` except that the
+/// number of arguments may be tagged with `PY_VECTORCALL_ARGUMENTS_OFFSET`.
+#[inline]
+pub unsafe fn vectorcallfunc(
+    slf: *mut ffi::PyObject,
+    args: *const *mut ffi::PyObject,
+    nargsf: libc::size_t,
+    kwnames: *mut ffi::PyObject,
+    f: for<'py> unsafe fn(
+        Python<'py>,
+        *mut ffi::PyObject,
+        *const *mut ffi::PyObject,
+        ffi::Py_ssize_t,
+        *mut ffi::PyObject,
+    ) -> PyResult<*mut ffi::PyObject>,
+) -> *mut ffi::PyObject {
+    const PY_VECTORCALL_ARGUMENTS_OFFSET: libc::size_t =
+        1 << (8 * std::mem::size_of::<libc::size_t>() - 1);
+    let nargs = (nargsf & !PY_VECTORCALL_ARGUMENTS_OFFSET) as ffi::Py_ssize_t;
+    fastcall_with_keywords(slf, args, nargs, kwnames, f)
+}
+
 // Ipowfunc is a unique case where PyO3 has its own type
 // to workaround a problem on 3.7 (see IPowModulo type definition).
 // Once 3.7 support dropped can just remove this.
@@ -163,6 +212,28 @@ trampoline!(
     ) -> *mut ffi::PyObject;
 );
 
+// The `#[init]` wrapper returns the hook's (ignored) return value as an object, which
+// is released here so that `tp_init` can report success as 0.
+#[inline]
+pub unsafe fn initproc(
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+    f: for<'py> unsafe fn(
+        Python<'py>,
+        *mut ffi::PyObject,
+        *mut ffi::PyObject,
+        *mut ffi::PyObject,
+    ) -> PyResult<*mut ffi::PyObject>,
+) -> c_int {
+    trampoline(|py| {
+        f(py, slf, args, kwargs).map(|result| {
+            ffi::Py_DECREF(result);
+            0
+        })
+    })
+}
+
 /// Implementation of trampoline functions, which sets up a GILPool and calls F.
 ///
 /// Panics during execution are trapped so that they don't propagate through any
@@ -197,7 +268,7 @@ where
     let py_err = match panic_result {
         Ok(Ok(value)) => return value,
         Ok(Err(py_err)) => py_err,
-        Err(payload) => PanicException::from_panic_payload(payload),
+        Err(payload) => PanicException::from_panic_payload(py, payload),
     };
     py_err.restore(py);
     R::ERR_VALUE
@@ -222,7 +293,7 @@ where
     let pool = GILPool::new();
     let py = pool.python();
     if let Err(py_err) = panic::catch_unwind(move || body(py))
-        .unwrap_or_else(|payload| Err(PanicException::from_panic_payload(payload)))
+        .unwrap_or_else(|payload| Err(PanicException::from_panic_payload(py, payload)))
     {
         py_err.write_unraisable_bound(py, ctx.assume_borrowed_or_opt(py).as_deref());
     }
@@ -1,6 +1,10 @@
-use std::convert::Infallible;
+use std::{convert::Infallible, marker::PhantomData, ops::Deref};
 
-use crate::{ffi, IntoPy, PyObject, PyResult, Python};
+use crate::{
+    ffi,
+    impl_::pyclass::{field_type_hint, field_type_hint_into_py, FieldTypeHint},
+    Bound, IntoPy, IntoPyObject, PyObject, PyResult, Python,
+};
 
 /// Used to wrap values in `Option<T>` for default arguments.
 pub trait SomeWrap<T> {
@@ -19,76 +23,225 @@ impl<T> SomeWrap<T> for Option<T> {
     }
 }
 
-/// Used to wrap the result of `#[pyfunction]` and `#[pymethods]`.
-pub trait OkWrap<T> {
-    type Error;
-    fn wrap(self) -> Result<T, Self::Error>;
+/// Converts the return values of `#[pyfunction]`s and `#[pymethods]` to Python objects.
+///
+/// The conversion goes through [`IntoPyObject`] when the returned type implements it, and falls
+/// back to [`IntoPy<PyObject>`] otherwise. The generated code calls the methods on the value
+/// returned by [`converter`]; method resolution tries [`IntoPyObjectConverter`] first and only
+/// derefs to [`IntoPyConverter`] when the bounds of the former are not satisfied.
+pub struct Converter<T>(IntoPyObjectConverter<T>);
+/// See [`Converter`].
+pub struct IntoPyObjectConverter<T>(IntoPyConverter<T>);
+/// See [`Converter`].
+pub struct IntoPyConverter<T>(UnknownReturnResultType<T>);
+/// Reached when the return type implements neither conversion trait, to report the missing
+/// `IntoPyObject` implementation.
+pub struct UnknownReturnResultType<T>(UnknownReturnType<T>);
+/// See [`UnknownReturnResultType`].
+pub struct UnknownReturnType<T>(PhantomData<T>);
+
+/// Creates the [`Converter`] for a value of type `T`.
+#[inline]
+pub fn converter<T>(_: &T) -> Converter<T> {
+    type_converter()
+}
+
+/// Creates the [`Converter`] for the type `T`, when there is no value at hand.
+#[inline]
+pub fn type_converter<T>() -> Converter<T> {
+    Converter(IntoPyObjectConverter(IntoPyConverter(
+        UnknownReturnResultType(UnknownReturnType(PhantomData)),
+    )))
+}
+
+impl<T> Deref for Converter<T> {
+    type Target = IntoPyObjectConverter<T>;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Deref for IntoPyObjectConverter<T> {
+    type Target = IntoPyConverter<T>;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Deref for IntoPyConverter<T> {
+    type Target = UnknownReturnResultType<T>;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Deref for UnknownReturnResultType<T> {
+    type Target = UnknownReturnType<T>;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> IntoPyObjectConverter<T>
+where
+    T: for<'py> IntoPyObject<'py>,
+{
+    #[inline]
+    pub fn wrap(&self, obj: T) -> Result<T, Infallible> {
+        Ok(obj)
+    }
+
+    /// The type hint of a `#[pyclass]` field of type `T`.
+    #[inline]
+    pub fn type_hint(&self) -> FieldTypeHint {
+        field_type_hint::<T>
+    }
+}
+
+impl<T, E> IntoPyObjectConverter<Result<T, E>>
+where
+    T: for<'py> IntoPyObject<'py>,
+{
+    #[inline]
+    pub fn wrap(&self, obj: Result<T, E>) -> Result<T, E> {
+        obj
+    }
+
+    /// Follow-up to `wrap`, which converts the result into a `*mut ffi::PyObject` pointer.
+    #[inline]
+    pub fn map_into_ptr(&self, py: Python<'_>, obj: PyResult<T>) -> PyResult<*mut ffi::PyObject> {
+        obj.and_then(|obj| obj.into_pyobject(py))
+            .map(Bound::into_ptr)
+    }
+
+    /// Follow-up to `wrap`, which converts the result into a safe wrapper.
+    #[inline]
+    pub fn map_into_py(&self, py: Python<'_>, obj: PyResult<T>) -> PyResult<PyObject> {
+        obj.and_then(|obj| obj.into_pyobject(py)).map(Bound::unbind)
+    }
 }
 
-// The T: IntoPy<PyObject> bound here is necessary to prevent the
-// implementation for Result<T, E> from conflicting
-impl<T> OkWrap<T> for T
+impl<T> IntoPyConverter<T>
 where
     T: IntoPy<PyObject>,
 {
-    type Error = Infallible;
     #[inline]
-    fn wrap(self) -> Result<T, Infallible> {
-        Ok(self)
+    pub fn wrap(&self, obj: T) -> Result<T, Infallible> {
+        Ok(obj)
+    }
+
+    /// The type hint of a `#[pyclass]` field of type `T`.
+    #[inline]
+    pub fn type_hint(&self) -> FieldTypeHint {
+        field_type_hint_into_py::<T>
     }
 }
 
-impl<T, E> OkWrap<T> for Result<T, E>
+impl<T, E> IntoPyConverter<Result<T, E>>
 where
     T: IntoPy<PyObject>,
 {
-    type Error = E;
     #[inline]
-    fn wrap(self) -> Result<T, Self::Error> {
-        self
+    pub fn wrap(&self, obj: Result<T, E>) -> Result<T, E> {
+        obj
+    }
+
+    /// Follow-up to `wrap`, which converts the result into a `*mut ffi::PyObject` pointer.
+    #[inline]
+    pub fn map_into_ptr(&self, py: Python<'_>, obj: PyResult<T>) -> PyResult<*mut ffi::PyObject> {
+        obj.map(|obj| obj.into_py(py).into_ptr())
+    }
+
+    /// Follow-up to `wrap`, which converts the result into a safe wrapper.
+    #[inline]
+    pub fn map_into_py(&self, py: Python<'_>, obj: PyResult<T>) -> PyResult<PyObject> {
+        obj.map(|obj| obj.into_py(py))
     }
 }
 
-/// This is a follow-up function to `OkWrap::wrap` that converts the result into
-/// a `*mut ffi::PyObject` pointer.
-pub fn map_result_into_ptr<T: IntoPy<PyObject>>(
-    py: Python<'_>,
-    result: PyResult<T>,
-) -> PyResult<*mut ffi::PyObject> {
-    result.map(|obj| obj.into_py(py).into_ptr())
+impl<T, E> UnknownReturnResultType<Result<T, E>> {
+    pub fn wrap<'py>(&self, _: Result<T, E>) -> Result<T, E>
+    where
+        T: IntoPyObject<'py>,
+    {
+        unreachable!("the return type does not implement `IntoPyObject`")
+    }
+
+    pub fn map_into_ptr<'py>(&self, _: Python<'py>, _: PyResult<T>) -> PyResult<*mut ffi::PyObject>
+    where
+        T: IntoPyObject<'py>,
+    {
+        unreachable!("the return type does not implement `IntoPyObject`")
+    }
+
+    pub fn map_into_py<'py>(&self, _: Python<'py>, _: PyResult<T>) -> PyResult<PyObject>
+    where
+        T: IntoPyObject<'py>,
+    {
+        unreachable!("the return type does not implement `IntoPyObject`")
+    }
 }
 
-/// This is a follow-up function to `OkWrap::wrap` that converts the result into
-/// a safe wrapper.
-pub fn map_result_into_py<T: IntoPy<PyObject>>(
-    py: Python<'_>,
-    result: PyResult<T>,
-) -> PyResult<PyObject> {
-    result.map(|err| err.into_py(py))
+impl<T> UnknownReturnType<T> {
+    pub fn wrap<'py>(&self, _: T) -> Result<T, Infallible>
+    where
+        T: IntoPyObject<'py>,
+    {
+        unreachable!("the return type does not implement `IntoPyObject`")
+    }
+
+    pub fn map_into_ptr<'py>(&self, _: Python<'py>, _: PyResult<T>) -> PyResult<*mut ffi::PyObject>
+    where
+        T: IntoPyObject<'py>,
+    {
+        unreachable!("the return type does not implement `IntoPyObject`")
+    }
+
+    pub fn map_into_py<'py>(&self, _: Python<'py>, _: PyResult<T>) -> PyResult<PyObject>
+    where
+        T: IntoPyObject<'py>,
+    {
+        unreachable!("the return type does not implement `IntoPyObject`")
+    }
+
+    pub fn type_hint<'py>(&self) -> FieldTypeHint
+    where
+        T: IntoPyObject<'py>,
+    {
+        unreachable!("the field type does not implement `IntoPyObject`")
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    // `SomeWrap` is not imported, as its `wrap` would shadow the inherent `wrap` of `converter`
+    use super::converter;
+    use crate::PyResult;
 
     #[test]
     fn wrap_option() {
-        let a: Option<u8> = SomeWrap::wrap(42);
+        let a: Option<u8> = super::SomeWrap::wrap(42);
         assert_eq!(a, Some(42));
 
-        let b: Option<u8> = SomeWrap::wrap(None);
+        let b: Option<u8> = super::SomeWrap::wrap(None);
         assert_eq!(b, None);
     }
 
     #[test]
     fn wrap_result() {
-        let a: Result<u8, _> = OkWrap::wrap(42u8);
+        let a: Result<u8, _> = converter(&42u8).wrap(42u8);
         assert!(matches!(a, Ok(42)));
 
-        let b: PyResult<u8> = OkWrap::wrap(Ok(42u8));
+        let ok: PyResult<u8> = Ok(42u8);
+        let b = converter(&ok).wrap(ok);
         assert!(matches!(b, Ok(42)));
 
-        let c: Result<u8, &str> = OkWrap::wrap(Err("error"));
+        let err: Result<u8, &str> = Err("error");
+        let c = converter(&err).wrap(err);
         assert_eq!(c, Err("error"));
     }
 }
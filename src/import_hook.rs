@@ -0,0 +1,344 @@
+//! Import hooks to serve Python modules which are embedded in a Rust binary.
+//!
+//! A [`ModuleFinder`] holds the source code or bytecode of a set of modules and packages. Once
+//! [installed](ModuleFinder::install) in `sys.meta_path`, these modules can be imported by Python
+//! code like any other module, for example to ship plugins written in Python inside an
+//! application which embeds the interpreter:
+//!
+//! ```rust
+//! use pyo3::import_hook::{ModuleCode, ModuleFinder};
+//! use pyo3::prelude::*;
+//!
+//! # fn main() -> PyResult<()> {
+//! Python::with_gil(|py| {
+//!     let mut finder = ModuleFinder::new();
+//!     finder.add_package("plugins", ModuleCode::Source("".into()));
+//!     finder.add_module(
+//!         "plugins.greeting",
+//!         ModuleCode::Source("def greet(name):\n    return f'Hello, {name}!'\n".into()),
+//!     );
+//!     finder.install(py)?;
+//!
+//!     let greeting = py.import_bound("plugins.greeting")?;
+//!     let message: String = greeting.getattr("greet")?.call1(("world",))?.extract()?;
+//!     assert_eq!(message, "Hello, world!");
+//!     Ok(())
+//! })
+//! # }
+//! ```
+use pyo3_macros::{pyclass, pymethods};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::CString;
+
+use crate::exceptions::PyImportError;
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::types::any::PyAnyMethods;
+use crate::types::list::PyListMethods;
+use crate::types::module::PyModuleMethods;
+use crate::types::{PyBytes, PyDict, PyList, PyModule};
+use crate::{ffi, intern, Bound, Py, PyAny, PyObject, PyResult, Python};
+
+/// The code of a module embedded with a [`ModuleFinder`].
+#[derive(Clone, Debug)]
+pub enum ModuleCode {
+    /// Python source code.
+    Source(Cow<'static, str>),
+    /// A code object serialized with the `marshal` module, e.g. with
+    /// `marshal.dumps(compile(source, filename, "exec"))`.
+    ///
+    /// The format of marshalled code objects changes between Python versions, so the bytecode
+    /// must have been produced by the same Python version as the one which imports it.
+    Bytecode(Cow<'static, [u8]>),
+}
+
+impl ModuleCode {
+    /// Compiles the source code, or loads the bytecode, into a code object.
+    ///
+    /// `file_name` is used in tracebacks for code compiled from source.
+    pub fn to_code_object<'py>(
+        &self,
+        py: Python<'py>,
+        file_name: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            ModuleCode::Source(source) => py
+                .import_bound(intern!(py, "builtins"))?
+                .call_method1(intern!(py, "compile"), (&**source, file_name, "exec")),
+            ModuleCode::Bytecode(bytecode) => py
+                .import_bound(intern!(py, "marshal"))?
+                .call_method1(intern!(py, "loads"), (PyBytes::new_bound(py, bytecode),)),
+        }
+    }
+
+    /// Creates a module named `module_name` and executes the code in it.
+    ///
+    /// Unlike an import through a [`ModuleFinder`], the module is not added to `sys.modules`.
+    pub fn to_module<'py>(
+        &self,
+        py: Python<'py>,
+        file_name: &str,
+        module_name: &str,
+    ) -> PyResult<Bound<'py, PyModule>> {
+        let module_name = CString::new(module_name)?;
+        let module = unsafe {
+            ffi::PyModule_New(module_name.as_ptr())
+                .assume_owned_or_err(py)?
+                .downcast_into_unchecked::<PyModule>()
+        };
+        module.setattr(intern!(py, "__file__"), file_name)?;
+        exec_code(py, &self.to_code_object(py, file_name)?, &module.dict())?;
+        Ok(module)
+    }
+
+    fn source(&self) -> Option<&str> {
+        match self {
+            ModuleCode::Source(source) => Some(source),
+            ModuleCode::Bytecode(_) => None,
+        }
+    }
+}
+
+fn exec_code(py: Python<'_>, code: &Bound<'_, PyAny>, globals: &Bound<'_, PyDict>) -> PyResult<()> {
+    py.import_bound(intern!(py, "builtins"))?
+        .call_method1(intern!(py, "exec"), (code, globals))
+        .map(drop)
+}
+
+#[derive(Debug)]
+struct EmbeddedModule {
+    code: ModuleCode,
+    is_package: bool,
+}
+
+/// A `sys.meta_path` finder and loader for modules embedded in the binary.
+///
+/// See the [module documentation](self) for an example.
+///
+/// Modules are executed when they are imported, as for modules loaded from files. In tracebacks,
+/// their file name is their dotted name converted to a path, such as `plugins/greeting.py`, or
+/// `plugins/__init__.py` for a package. The finder implements the
+/// [`get_source`](https://docs.python.org/3/library/importlib.html#importlib.abc.InspectLoader.get_source)
+/// method of loaders, so that the `traceback` module can show the source of modules embedded as
+/// source code.
+#[pyclass(crate = "crate", frozen, module = "pyo3")]
+#[derive(Debug, Default)]
+pub struct ModuleFinder {
+    modules: HashMap<String, EmbeddedModule>,
+}
+
+impl ModuleFinder {
+    /// Creates a finder without any modules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the module `name`, which must be the full dotted name of the module.
+    ///
+    /// The parent packages of the module, if any, must be added with [`add_package`][Self::add_package]
+    /// or be importable from elsewhere.
+    pub fn add_module(&mut self, name: impl Into<String>, code: ModuleCode) {
+        self.add(name.into(), code, false);
+    }
+
+    /// Adds the package `name`, whose `__init__` module has the given code.
+    ///
+    /// Its submodules can then be added with [`add_module`][Self::add_module] or `add_package`.
+    pub fn add_package(&mut self, name: impl Into<String>, code: ModuleCode) {
+        self.add(name.into(), code, true);
+    }
+
+    fn add(&mut self, name: String, code: ModuleCode, is_package: bool) {
+        self.modules
+            .insert(name, EmbeddedModule { code, is_package });
+    }
+
+    /// Installs the finder at the start of `sys.meta_path`, so that its modules take precedence
+    /// over the modules found on `sys.path`.
+    ///
+    /// Returns the installed finder, which can be removed with [`uninstall`][Self::uninstall].
+    pub fn install(self, py: Python<'_>) -> PyResult<Py<ModuleFinder>> {
+        let finder = Py::new(py, self)?;
+        meta_path(py)?.insert(0, &finder)?;
+        Ok(finder)
+    }
+
+    /// Removes an installed finder from `sys.meta_path`.
+    ///
+    /// Modules which have already been imported stay in `sys.modules`.
+    pub fn uninstall(py: Python<'_>, finder: &Py<ModuleFinder>) -> PyResult<()> {
+        meta_path(py)?
+            .call_method1(intern!(py, "remove"), (finder,))
+            .map(drop)
+    }
+
+    fn get(&self, fullname: &str) -> PyResult<&EmbeddedModule> {
+        self.modules.get(fullname).ok_or_else(|| {
+            PyImportError::new_err(format!("no embedded module named '{}'", fullname))
+        })
+    }
+
+    fn file_name(&self, fullname: &str, module: &EmbeddedModule) -> String {
+        let path = fullname.replace('.', "/");
+        if module.is_package {
+            format!("{}/__init__.py", path)
+        } else {
+            format!("{}.py", path)
+        }
+    }
+}
+
+fn meta_path(py: Python<'_>) -> PyResult<Bound<'_, PyList>> {
+    py.import_bound(intern!(py, "sys"))?
+        .getattr(intern!(py, "meta_path"))?
+        .downcast_into()
+        .map_err(Into::into)
+}
+
+#[pymethods(crate = "crate")]
+impl ModuleFinder {
+    #[pyo3(signature = (fullname, path=None, target=None))]
+    fn find_spec(
+        slf: Py<Self>,
+        py: Python<'_>,
+        fullname: &str,
+        path: Option<&Bound<'_, PyAny>>,
+        target: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Option<PyObject>> {
+        let _ = (path, target);
+        let finder = slf.get();
+        let module = match finder.modules.get(fullname) {
+            Some(module) => module,
+            None => return Ok(None),
+        };
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("origin", finder.file_name(fullname, module))?;
+        kwargs.set_item("is_package", module.is_package)?;
+        py.import_bound(intern!(py, "importlib.machinery"))?
+            .getattr(intern!(py, "ModuleSpec"))?
+            .call((fullname, slf), Some(&kwargs))
+            .map(|spec| Some(spec.unbind()))
+    }
+
+    fn create_module(&self, spec: &Bound<'_, PyAny>) -> Option<PyObject> {
+        // use the default module creation
+        let _ = spec;
+        None
+    }
+
+    fn exec_module(&self, module: &Bound<'_, PyModule>) -> PyResult<()> {
+        let py = module.py();
+        let fullname: String = module.getattr(intern!(py, "__name__"))?.extract()?;
+        let embedded = self.get(&fullname)?;
+        let file_name = self.file_name(&fullname, embedded);
+        let code = embedded.code.to_code_object(py, &file_name)?;
+        exec_code(py, &code, &module.dict())
+    }
+
+    fn is_package(&self, fullname: &str) -> PyResult<bool> {
+        self.get(fullname).map(|module| module.is_package)
+    }
+
+    fn get_source(&self, fullname: &str) -> PyResult<Option<&str>> {
+        self.get(fullname).map(|module| module.code.source())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModuleCode, ModuleFinder};
+    use crate::types::any::PyAnyMethods;
+    use crate::types::bytes::PyBytesMethods;
+    use crate::types::PyBytes;
+    use crate::Python;
+
+    #[test]
+    fn test_module_finder() {
+        Python::with_gil(|py| {
+            let bytecode = py
+                .eval_bound(
+                    "__import__('marshal').dumps(compile('ANSWER = 42', 'answer.py', 'exec'))",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let bytecode = bytecode.downcast::<PyBytes>().unwrap().as_bytes().to_vec();
+
+            let mut finder = ModuleFinder::new();
+            finder.add_package(
+                "pyo3_test_embedded",
+                ModuleCode::Source("NAME = __name__".into()),
+            );
+            finder.add_module(
+                "pyo3_test_embedded.answer",
+                ModuleCode::Bytecode(bytecode.into()),
+            );
+            finder.add_module(
+                "pyo3_test_embedded.failing",
+                ModuleCode::Source("def fail():\n    raise ValueError('oops')\n".into()),
+            );
+            let finder = finder.install(py).unwrap();
+
+            let package = py.import_bound("pyo3_test_embedded").unwrap();
+            assert_eq!(
+                package
+                    .getattr("NAME")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "pyo3_test_embedded"
+            );
+            assert!(package.hasattr("__path__").unwrap());
+
+            let answer = py.import_bound("pyo3_test_embedded.answer").unwrap();
+            assert_eq!(
+                answer.getattr("ANSWER").unwrap().extract::<i32>().unwrap(),
+                42
+            );
+
+            let failing = py.import_bound("pyo3_test_embedded.failing").unwrap();
+            let err = failing.getattr("fail").unwrap().call0().unwrap_err();
+            // `linecache` gets the source of the lines from the loader
+            let formatted: Vec<String> = py
+                .import_bound("traceback")
+                .unwrap()
+                .call_method1("format_tb", (err.traceback_bound(py),))
+                .unwrap()
+                .extract()
+                .unwrap();
+            let formatted = formatted.concat();
+            assert!(
+                formatted.contains("pyo3_test_embedded/failing.py"),
+                "{}",
+                formatted
+            );
+            assert!(
+                formatted.contains("raise ValueError('oops')"),
+                "{}",
+                formatted
+            );
+
+            assert!(py.import_bound("pyo3_test_embedded.missing").is_err());
+
+            ModuleFinder::uninstall(py, &finder).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_module_code_to_module() {
+        Python::with_gil(|py| {
+            let module = ModuleCode::Source("X = 1 + 1".into())
+                .to_module(py, "x.py", "pyo3_test_to_module")
+                .unwrap();
+            assert_eq!(module.getattr("X").unwrap().extract::<i32>().unwrap(), 2);
+            assert_eq!(
+                module
+                    .getattr("__file__")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "x.py"
+            );
+        });
+    }
+}
@@ -3,6 +3,10 @@
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
+use crate::types::any::PyAnyMethods;
+use crate::types::{PyList, PyString, PyTuple};
+use crate::{Bound, PyAny, PyResult, Python};
+
 /// Designation of a Python type.
 ///
 /// This enum is used to handle advanced types, such as types with generics.
@@ -198,6 +202,62 @@ impl TypeInfo {
     }
 }
 
+impl TypeInfo {
+    /// Resolves this type to the Python object it designates, e.g. `typing.List[int]`.
+    ///
+    /// Types from the current module cannot be imported, so they are returned as a string
+    /// forward reference (e.g. `'MyClass'`), as they would appear in a type hint.
+    pub fn to_type_hint<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let typing = |name: &str| py.import_bound("typing")?.getattr(name);
+        let resolve_all = |types: &[TypeInfo]| {
+            types
+                .iter()
+                .map(|t| t.to_type_hint(py))
+                .collect::<PyResult<Vec<_>>>()
+        };
+        match self {
+            TypeInfo::Any => typing("Any"),
+            TypeInfo::None => Ok(py.None().into_bound(py)),
+            TypeInfo::NoReturn => typing("NoReturn"),
+            TypeInfo::Callable(input, output) => {
+                let input = match input {
+                    Some(input) => PyList::new_bound(py, resolve_all(input)?).into_any(),
+                    None => py.Ellipsis().into_bound(py),
+                };
+                typing("Callable")?.get_item((input, output.to_type_hint(py)?))
+            }
+            TypeInfo::Tuple(None) => typing("Tuple"),
+            TypeInfo::Tuple(Some(types)) => {
+                typing("Tuple")?.get_item(PyTuple::new_bound(py, resolve_all(types)?))
+            }
+            TypeInfo::UnsizedTypedTuple(t) => {
+                typing("Tuple")?.get_item((t.to_type_hint(py)?, py.Ellipsis()))
+            }
+            TypeInfo::Class {
+                module: ModuleName::CurrentModule,
+                ..
+            } => Ok(PyString::new_bound(py, &self.to_string()).into_any()),
+            TypeInfo::Class {
+                module,
+                name,
+                type_vars,
+            } => {
+                let module = match module {
+                    ModuleName::Module(module) => module,
+                    _ => "builtins",
+                };
+                let class = py.import_bound(module)?.getattr(name.as_ref())?;
+                match type_vars.as_slice() {
+                    [] => Ok(class),
+                    // `typing.Optional` and friends reject a 1-tuple of parameters
+                    [t] => class.get_item(t.to_type_hint(py)?),
+                    type_vars => class.get_item(PyTuple::new_bound(py, resolve_all(type_vars)?)),
+                }
+            }
+        }
+    }
+}
+
 impl Display for TypeInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -480,4 +540,49 @@ mod conversion {
 
         assert_display(&<(usize, f32)>::type_input(), "Tuple[int, float]");
     }
+
+    #[test]
+    fn to_type_hint() {
+        use std::borrow::Cow;
+
+        use crate::inspect::types::{ModuleName, TypeInfo};
+        use crate::types::any::PyAnyMethods;
+        use crate::Python;
+
+        Python::with_gil(|py| {
+            let assert_hint = |t: TypeInfo, expected: &str| {
+                let hint = t.to_type_hint(py).unwrap();
+                let expected = py
+                    .eval_bound(&format!("__import__('typing').{}", expected), None, None)
+                    .unwrap();
+                assert!(hint.eq(&expected).unwrap(), "{} != {}", hint, expected);
+            };
+
+            assert_hint(TypeInfo::Any, "Any");
+            assert_hint(<Vec<usize>>::type_output(), "List[int]");
+            assert_hint(<HashMap<usize, f32>>::type_input(), "Mapping[int, float]");
+            assert_hint(<(usize, f32)>::type_input(), "Tuple[int, float]");
+            assert_hint(TypeInfo::Tuple(Some(vec![])), "Tuple[()]");
+            assert_hint(
+                TypeInfo::UnsizedTypedTuple(Box::new(TypeInfo::builtin("bool"))),
+                "Tuple[bool, ...]",
+            );
+            assert_hint(
+                TypeInfo::optional_of(TypeInfo::builtin("str")),
+                "Optional[str]",
+            );
+            assert_hint(
+                TypeInfo::Callable(None, Box::new(TypeInfo::None)),
+                "Callable[..., None]",
+            );
+
+            let class = TypeInfo::Class {
+                module: ModuleName::CurrentModule,
+                name: Cow::from("MyClass"),
+                type_vars: vec![],
+            };
+            let hint = class.to_type_hint(py).unwrap();
+            assert_eq!(hint.extract::<&str>().unwrap(), "MyClass");
+        });
+    }
 }
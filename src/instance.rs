@@ -1,5 +1,4 @@
 use crate::err::{self, PyDowncastError, PyErr, PyResult};
-use crate::ffi_ptr_ext::FfiPtrExt;
 use crate::pycell::{PyBorrowError, PyBorrowMutError, PyCell};
 use crate::pyclass::boolean_struct::{False, True};
 use crate::type_object::HasPyGilRef;
@@ -93,14 +92,7 @@ where
         py: Python<'py>,
         value: impl Into<PyClassInitializer<T>>,
     ) -> PyResult<Bound<'py, T>> {
-        let initializer = value.into();
-        let obj = initializer.create_cell(py)?;
-        let ob = unsafe {
-            obj.cast::<ffi::PyObject>()
-                .assume_owned(py)
-                .downcast_into_unchecked()
-        };
-        Ok(ob)
+        value.into().create_class_object(py)
     }
 }
 
@@ -589,6 +581,8 @@ impl<T> IntoPy<PyObject> for Borrowed<'_, '_, T> {
     }
 }
 
+into_pyobject_via_into_py!([T] Borrowed<'_, '_, T>);
+
 /// A GIL-independent reference to an object allocated on the Python heap.
 ///
 /// This type does not auto-dereference to the inner object because you must prove you hold the GIL to access it.
@@ -803,10 +797,7 @@ where
     /// # }
     /// ```
     pub fn new(py: Python<'_>, value: impl Into<PyClassInitializer<T>>) -> PyResult<Py<T>> {
-        let initializer = value.into();
-        let obj = initializer.create_cell(py)?;
-        let ob = unsafe { Py::from_owned_ptr(py, obj as _) };
-        Ok(ob)
+        Bound::new(py, value).map(Bound::unbind)
     }
 }
 
@@ -1501,6 +1492,8 @@ impl<T> IntoPy<PyObject> for Py<T> {
     }
 }
 
+into_pyobject_via_into_py!([T] Py<T>);
+
 impl<T> IntoPy<PyObject> for &'_ Py<T> {
     #[inline]
     fn into_py(self, py: Python<'_>) -> PyObject {
@@ -1508,6 +1501,8 @@ impl<T> IntoPy<PyObject> for &'_ Py<T> {
     }
 }
 
+into_pyobject_via_into_py!([T] &'_ Py<T>);
+
 impl<T> ToPyObject for Bound<'_, T> {
     /// Converts `&Bound` instance -> PyObject, increasing the reference count.
     #[inline]
@@ -1524,6 +1519,8 @@ impl<T> IntoPy<PyObject> for Bound<'_, T> {
     }
 }
 
+into_pyobject_via_into_py!([T] Bound<'_, T>);
+
 impl<T> IntoPy<PyObject> for &Bound<'_, T> {
     /// Converts `&Bound` instance -> PyObject, increasing the reference count.
     #[inline]
@@ -1532,6 +1529,8 @@ impl<T> IntoPy<PyObject> for &Bound<'_, T> {
     }
 }
 
+into_pyobject_via_into_py!([T] &Bound<'_, T>);
+
 unsafe impl<T> crate::AsPyPointer for Py<T> {
     /// Gets the underlying FFI pointer, returns a borrowed pointer.
     #[inline]
@@ -1619,6 +1618,10 @@ impl<T> Clone for Py<T> {
 }
 
 /// Dropping a `Py` instance decrements the reference count on the object by 1.
+///
+/// This is safe without the GIL: the decrement is then deferred until PyO3 next acquires the GIL,
+/// or until the interpreter starts to finalize. A `Py` dropped after the interpreter has finalized
+/// is leaked.
 impl<T> Drop for Py<T> {
     fn drop(&mut self) {
         unsafe {
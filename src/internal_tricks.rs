@@ -39,6 +39,39 @@ macro_rules! pyo3_exception {
     };
 }
 
+/// Implements `IntoPyObject` for a type which already implements `IntoPy<PyObject>`, as an
+/// infallible conversion.
+///
+/// The generic parameters go in brackets and the where clause must repeat the bounds of the
+/// `IntoPy<PyObject>` implementation, e.g.
+/// `into_pyobject_via_into_py!([T] Vec<T> where T: IntoPy<PyObject>);`.
+macro_rules! into_pyobject_via_into_py {
+    ($(#[$meta:meta])* [$($generics:tt)*] $ty:ty $(where $($bounds:tt)+)?) => {
+        $(#[$meta])*
+        impl<'py, $($generics)*> $crate::conversion::IntoPyObject<'py> for $ty
+        $(where $($bounds)+)?
+        {
+            #[inline]
+            fn into_pyobject(
+                self,
+                py: $crate::Python<'py>,
+            ) -> $crate::PyResult<$crate::Bound<'py, $crate::PyAny>> {
+                ::std::result::Result::Ok(
+                    $crate::IntoPy::<$crate::PyObject>::into_py(self, py).into_bound(py),
+                )
+            }
+
+            #[cfg(feature = "experimental-inspect")]
+            fn type_output() -> $crate::inspect::types::TypeInfo {
+                <$ty as $crate::IntoPy<$crate::PyObject>>::type_output()
+            }
+        }
+    };
+    ($(#[$meta:meta])* $ty:ty) => {
+        into_pyobject_via_into_py!($(#[$meta])* [] $ty);
+    };
+}
+
 /// Convert an usize index into a Py_ssize_t index, clamping overflow to
 /// PY_SSIZE_T_MAX.
 pub(crate) fn get_ssize_index(index: usize) -> Py_ssize_t {
@@ -0,0 +1,591 @@
+//! Adapters between Rust streams and Python file objects.
+//!
+//! - [`PyFileLike`] wraps any Python file object, such as an open file or an `io.BytesIO`, and
+//!   implements [`Read`], [`Write`] and [`Seek`] by calling its `read`, `write` and `seek`
+//!   methods. It can be extracted from function arguments, so that Rust code written against the
+//!   standard I/O traits can consume Python files.
+//! - [`PyFileLikeObject`] goes the other way: it exposes a Rust reader or writer to Python as a
+//!   raw binary file object, following the semantics of `io.RawIOBase`.
+//!
+//! ```rust
+//! use pyo3::io::PyFileLike;
+//! use pyo3::prelude::*;
+//! use std::io::Read;
+//!
+//! /// Counts the bytes of a path or a binary file object.
+//! #[pyfunction]
+//! fn count_bytes(source: &Bound<'_, PyAny>) -> PyResult<usize> {
+//!     let mut reader: Box<dyn Read> = match source.extract::<std::path::PathBuf>() {
+//!         Ok(path) => Box::new(std::fs::File::open(path)?),
+//!         Err(_) => Box::new(source.extract::<PyFileLike>()?),
+//!     };
+//!     Ok(std::io::copy(&mut reader, &mut std::io::sink())? as usize)
+//! }
+//! #
+//! # Python::with_gil(|py| {
+//! #     let count_bytes = wrap_pyfunction!(count_bytes, py).unwrap();
+//! #     pyo3::py_run!(py, count_bytes, "import io; assert count_bytes(io.BytesIO(b'abc')) == 3");
+//! # });
+//! ```
+
+use crate::{
+    exceptions::PyTypeError,
+    intern,
+    pybacked::PyBackedBytes,
+    types::{any::PyAnyMethods, PyBytes, PyString},
+    Bound, FromPyObject, Py, PyAny, PyResult, Python,
+};
+#[cfg(feature = "macros")]
+use crate::{types::PyTuple, PyTypeInfo};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// A Python file object, used from Rust through the [`Read`], [`Write`] and [`Seek`] traits.
+///
+/// Each operation acquires the GIL and calls the corresponding method of the object, so a
+/// `PyFileLike` can also be used inside [`Python::allow_threads`]. The object must be opened in
+/// binary mode: reading from a text file fails with [`io::ErrorKind::InvalidData`].
+///
+/// Python exceptions are converted to [`io::Error`]s which wrap the original exception, and are
+/// restored when the error is converted back to a `PyErr`.
+#[derive(Debug)]
+pub struct PyFileLike {
+    inner: Py<PyAny>,
+}
+
+impl PyFileLike {
+    /// Wraps `obj`, which must have at least one of the `read` and `write` methods.
+    pub fn new(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let py = obj.py();
+        if !obj.hasattr(intern!(py, "read"))? && !obj.hasattr(intern!(py, "write"))? {
+            return Err(PyTypeError::new_err(format!(
+                "expected a file-like object with a `read` or `write` method, got '{}'",
+                obj.get_type().name()?
+            )));
+        }
+        Ok(PyFileLike {
+            inner: obj.clone().unbind(),
+        })
+    }
+
+    /// Gets the wrapped Python object.
+    pub fn as_any(&self) -> &Py<PyAny> {
+        &self.inner
+    }
+
+    /// Returns the wrapped Python object.
+    pub fn into_inner(self) -> Py<PyAny> {
+        self.inner
+    }
+}
+
+impl FromPyObject<'_> for PyFileLike {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Self::new(obj)
+    }
+}
+
+impl Read for PyFileLike {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Python::with_gil(|py| {
+            let data = self
+                .inner
+                .bind(py)
+                .call_method1(intern!(py, "read"), (buf.len(),))?;
+            if data.is_none() {
+                // A non-blocking raw stream with no data available.
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            if data.is_instance_of::<PyString>() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "read() returned str; the file must be opened in binary mode",
+                ));
+            }
+            let data: PyBackedBytes = data.extract()?;
+            if data.len() > buf.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "read() returned {} bytes, but only {} were requested",
+                        data.len(),
+                        buf.len()
+                    ),
+                ));
+            }
+            buf[..data.len()].copy_from_slice(&data);
+            Ok(data.len())
+        })
+    }
+}
+
+impl Write for PyFileLike {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Python::with_gil(|py| {
+            let written = self
+                .inner
+                .bind(py)
+                .call_method1(intern!(py, "write"), (PyBytes::new_bound(py, buf),))?;
+            if written.is_none() {
+                // A non-blocking raw stream which could not take any data.
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            Ok(written.extract()?)
+        })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Python::with_gil(|py| {
+            let inner = self.inner.bind(py);
+            if inner.hasattr(intern!(py, "flush"))? {
+                inner.call_method0(intern!(py, "flush"))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Seek for PyFileLike {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        Python::with_gil(|py| {
+            let seek = intern!(py, "seek");
+            let inner = self.inner.bind(py);
+            let position = match pos {
+                SeekFrom::Start(offset) => inner.call_method1(seek, (offset, 0))?,
+                SeekFrom::Current(offset) => inner.call_method1(seek, (offset, 1))?,
+                SeekFrom::End(offset) => inner.call_method1(seek, (offset, 2))?,
+            };
+            Ok(position.extract()?)
+        })
+    }
+}
+
+/// The operations of a Rust stream behind a [`PyFileLikeObject`]; `None` when unsupported.
+#[cfg(feature = "macros")]
+trait RawStream: Send {
+    fn read(&mut self, _buf: &mut [u8]) -> Option<io::Result<usize>> {
+        None
+    }
+
+    fn write(&mut self, _buf: &[u8]) -> Option<io::Result<usize>> {
+        None
+    }
+
+    fn seek(&mut self, _pos: SeekFrom) -> Option<io::Result<u64>> {
+        None
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "macros")]
+macro_rules! raw_stream {
+    ($name:ident<$t:ident: $($bound:path),+> { $($op:ident),* }) => {
+        struct $name<$t>($t);
+
+        impl<$t: $($bound +)+ Send> RawStream for $name<$t> {
+            $(raw_stream!(@op $op);)*
+        }
+    };
+    (@op read) => {
+        fn read(&mut self, buf: &mut [u8]) -> Option<io::Result<usize>> {
+            Some(self.0.read(buf))
+        }
+    };
+    (@op write) => {
+        fn write(&mut self, buf: &[u8]) -> Option<io::Result<usize>> {
+            Some(self.0.write(buf))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.flush()
+        }
+    };
+    (@op seek) => {
+        fn seek(&mut self, pos: SeekFrom) -> Option<io::Result<u64>> {
+            Some(self.0.seek(pos))
+        }
+    };
+}
+
+#[cfg(feature = "macros")]
+raw_stream!(Reader<R: Read> { read });
+#[cfg(feature = "macros")]
+raw_stream!(SeekableReader<R: Read, Seek> { read, seek });
+#[cfg(feature = "macros")]
+raw_stream!(Writer<W: Write> { write });
+#[cfg(feature = "macros")]
+raw_stream!(SeekableWriter<W: Write, Seek> { write, seek });
+#[cfg(feature = "macros")]
+raw_stream!(Stream<S: Read, Write, Seek> { read, write, seek });
+
+/// A Rust reader or writer exposed to Python as a raw binary file object.
+///
+/// The object implements the methods of `io.RawIOBase` (`read`, `readinto`, `readall`, `write`,
+/// `seek`, `tell`, `flush`, `close`, ...) and is registered as a virtual subclass of it. Like
+/// other raw streams, it can be wrapped in `io.BufferedReader` or `io.TextIOWrapper` for
+/// buffering and line-oriented access. Operations which the Rust stream does not support raise
+/// `io.UnsupportedOperation`, and the GIL is released while the Rust stream is in use.
+///
+/// Closing the object flushes and drops the Rust stream.
+///
+/// ```rust
+/// use pyo3::io::PyFileLikeObject;
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction]
+/// fn open_archive_member() -> PyFileLikeObject {
+///     PyFileLikeObject::seekable_reader(std::io::Cursor::new(b"first\nsecond\n".to_vec()))
+/// }
+/// #
+/// # Python::with_gil(|py| {
+/// #     let open_archive_member = wrap_pyfunction!(open_archive_member, py).unwrap();
+/// #     pyo3::py_run!(py, open_archive_member, r#"
+/// #         import io
+/// #         lines = io.TextIOWrapper(io.BufferedReader(open_archive_member())).readlines()
+/// #         assert lines == ["first\n", "second\n"]
+/// #     "#);
+/// # });
+/// ```
+#[cfg(feature = "macros")]
+#[pyo3_macros::pyclass(crate = "crate", abc = "io.RawIOBase")]
+pub struct PyFileLikeObject {
+    stream: Option<Box<dyn RawStream>>,
+    readable: bool,
+    writable: bool,
+    seekable: bool,
+}
+
+#[cfg(feature = "macros")]
+impl PyFileLikeObject {
+    fn new(stream: Box<dyn RawStream>, readable: bool, writable: bool, seekable: bool) -> Self {
+        PyFileLikeObject {
+            stream: Some(stream),
+            readable,
+            writable,
+            seekable,
+        }
+    }
+
+    /// Exposes a reader as a readable, non-seekable file object.
+    pub fn reader<R: Read + Send + 'static>(reader: R) -> Self {
+        Self::new(Box::new(Reader(reader)), true, false, false)
+    }
+
+    /// Exposes a reader as a readable, seekable file object.
+    pub fn seekable_reader<R: Read + Seek + Send + 'static>(reader: R) -> Self {
+        Self::new(Box::new(SeekableReader(reader)), true, false, true)
+    }
+
+    /// Exposes a writer as a writable, non-seekable file object.
+    pub fn writer<W: Write + Send + 'static>(writer: W) -> Self {
+        Self::new(Box::new(Writer(writer)), false, true, false)
+    }
+
+    /// Exposes a writer as a writable, seekable file object.
+    pub fn seekable_writer<W: Write + Seek + Send + 'static>(writer: W) -> Self {
+        Self::new(Box::new(SeekableWriter(writer)), false, true, true)
+    }
+
+    /// Exposes a stream as a readable, writable and seekable file object.
+    pub fn stream<S: Read + Write + Seek + Send + 'static>(stream: S) -> Self {
+        Self::new(Box::new(Stream(stream)), true, true, true)
+    }
+
+    fn open_stream(&mut self) -> PyResult<&mut Box<dyn RawStream>> {
+        self.stream
+            .as_mut()
+            .ok_or_else(|| crate::exceptions::PyValueError::new_err("I/O operation on closed file"))
+    }
+
+    fn read_into_vec(&mut self, py: Python<'_>, size: usize) -> PyResult<Option<Vec<u8>>> {
+        let stream = self.open_stream()?;
+        let mut buf = vec![0; size];
+        let result = py.allow_threads(|| loop {
+            match stream.read(&mut buf) {
+                Some(Err(err)) if err.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        });
+        match result {
+            Some(Ok(n)) => {
+                buf.truncate(n);
+                Ok(Some(buf))
+            }
+            Some(Err(err)) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Some(Err(err)) => Err(err.into()),
+            None => Err(unsupported_operation(py, "read")),
+        }
+    }
+
+    fn read_to_end(&mut self, py: Python<'_>) -> PyResult<Option<Vec<u8>>> {
+        let stream = self.open_stream()?;
+        let mut data = Vec::new();
+        let result = py.allow_threads(|| {
+            let mut chunk = [0; 8192];
+            loop {
+                match stream.read(&mut chunk) {
+                    Some(Ok(0)) => break Some(Ok(())),
+                    Some(Ok(n)) => data.extend_from_slice(&chunk[..n]),
+                    Some(Err(err)) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Some(Err(err)) => break Some(Err(err)),
+                    None => break None,
+                }
+            }
+        });
+        match result {
+            Some(Ok(())) => Ok(Some(data)),
+            // Like `RawIOBase.readall`, return what was read before the stream would block.
+            Some(Err(err)) if err.kind() == io::ErrorKind::WouldBlock => {
+                Ok(if data.is_empty() { None } else { Some(data) })
+            }
+            Some(Err(err)) => Err(err.into()),
+            None => Err(unsupported_operation(py, "read")),
+        }
+    }
+}
+
+/// Creates an `io.UnsupportedOperation` error for the operation `op`.
+#[cfg(feature = "macros")]
+fn unsupported_operation(py: Python<'_>, op: &str) -> crate::PyErr {
+    match py
+        .import_bound(intern!(py, "io"))
+        .and_then(|io| io.getattr(intern!(py, "UnsupportedOperation")))
+    {
+        Ok(ty) => crate::PyErr::from_value_bound(match ty.call1((op,)) {
+            Ok(err) => err,
+            Err(err) => return err,
+        }),
+        Err(err) => err,
+    }
+}
+
+#[cfg(feature = "macros")]
+#[pyo3_macros::pymethods(crate = "crate")]
+impl PyFileLikeObject {
+    fn readable(&mut self) -> PyResult<bool> {
+        self.open_stream()?;
+        Ok(self.readable)
+    }
+
+    fn writable(&mut self) -> PyResult<bool> {
+        self.open_stream()?;
+        Ok(self.writable)
+    }
+
+    fn seekable(&mut self) -> PyResult<bool> {
+        self.open_stream()?;
+        Ok(self.seekable)
+    }
+
+    #[pyo3(signature = (size = -1))]
+    fn read(&mut self, py: Python<'_>, size: Option<isize>) -> PyResult<Option<Py<PyBytes>>> {
+        let data = match size {
+            Some(size) if size >= 0 => self.read_into_vec(py, size as usize)?,
+            _ => self.read_to_end(py)?,
+        };
+        Ok(data.map(|data| PyBytes::new_bound(py, &data).unbind()))
+    }
+
+    fn readall(&mut self, py: Python<'_>) -> PyResult<Option<Py<PyBytes>>> {
+        self.read(py, None)
+    }
+
+    fn readinto(&mut self, buffer: &Bound<'_, PyAny>) -> PyResult<Option<usize>> {
+        let py = buffer.py();
+        let data = match self.read_into_vec(py, buffer.len()?)? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let target = crate::types::PySlice::new_bound(py, 0, data.len() as isize, 1);
+        buffer.set_item(target, PyBytes::new_bound(py, &data))?;
+        Ok(Some(data.len()))
+    }
+
+    fn write(&mut self, py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Option<usize>> {
+        // Accept any bytes-like object, such as a `memoryview`.
+        let data: PyBackedBytes = match data.extract() {
+            Ok(data) => data,
+            Err(_) => PyBytes::type_object_bound(py).call1((data,))?.extract()?,
+        };
+        let stream = self.open_stream()?;
+        let result = py.allow_threads(|| loop {
+            match stream.write(&data) {
+                Some(Err(err)) if err.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        });
+        match result {
+            Some(Ok(n)) => Ok(Some(n)),
+            Some(Err(err)) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Some(Err(err)) => Err(err.into()),
+            None => Err(unsupported_operation(py, "write")),
+        }
+    }
+
+    #[pyo3(signature = (offset, whence = 0))]
+    fn seek(&mut self, py: Python<'_>, offset: i64, whence: i32) -> PyResult<u64> {
+        let pos = match whence {
+            0 => SeekFrom::Start(u64::try_from(offset).map_err(|_| {
+                crate::exceptions::PyValueError::new_err(format!(
+                    "negative seek position {}",
+                    offset
+                ))
+            })?),
+            1 => SeekFrom::Current(offset),
+            2 => SeekFrom::End(offset),
+            _ => {
+                return Err(crate::exceptions::PyValueError::new_err(format!(
+                    "invalid whence ({}, should be 0, 1 or 2)",
+                    whence
+                )))
+            }
+        };
+        let stream = self.open_stream()?;
+        match py.allow_threads(|| stream.seek(pos)) {
+            Some(result) => Ok(result?),
+            None => Err(unsupported_operation(py, "seek")),
+        }
+    }
+
+    fn tell(&mut self, py: Python<'_>) -> PyResult<u64> {
+        self.seek(py, 0, 1)
+    }
+
+    fn flush(&mut self, py: Python<'_>) -> PyResult<()> {
+        let stream = self.open_stream()?;
+        Ok(py.allow_threads(|| stream.flush())?)
+    }
+
+    fn close(&mut self, py: Python<'_>) -> PyResult<()> {
+        if let Some(mut stream) = self.stream.take() {
+            py.allow_threads(move || stream.flush())?;
+        }
+        Ok(())
+    }
+
+    #[getter]
+    fn closed(&self) -> bool {
+        self.stream.is_none()
+    }
+
+    fn isatty(&mut self) -> PyResult<bool> {
+        self.open_stream()?;
+        Ok(false)
+    }
+
+    fn fileno(&self, py: Python<'_>) -> PyResult<i32> {
+        Err(unsupported_operation(py, "fileno"))
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, py: Python<'_>, _args: &Bound<'_, PyTuple>) -> PyResult<bool> {
+        self.close(py)?;
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyFileLike;
+    use crate::types::any::PyAnyMethods;
+    use crate::Python;
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    #[test]
+    fn test_py_file_like() {
+        Python::with_gil(|py| {
+            let bytes_io = py
+                .import_bound("io")
+                .unwrap()
+                .call_method0("BytesIO")
+                .unwrap();
+            let mut file: PyFileLike = bytes_io.extract().unwrap();
+            file.write_all(b"hello world").unwrap();
+            file.flush().unwrap();
+            assert_eq!(file.seek(SeekFrom::Start(6)).unwrap(), 6);
+            let mut rest = String::new();
+            file.read_to_string(&mut rest).unwrap();
+            assert_eq!(rest, "world");
+            assert_eq!(file.seek(SeekFrom::End(-5)).unwrap(), 6);
+
+            let err = bytes_io.call_method0("close").and_then(|_| {
+                file.read(&mut [0; 4])
+                    .map_err(crate::PyErr::from)
+                    .map(|_| ())
+            });
+            assert!(err
+                .unwrap_err()
+                .is_instance_of::<crate::exceptions::PyValueError>(py));
+
+            let string_io = py
+                .import_bound("io")
+                .unwrap()
+                .call_method1("StringIO", ("text",))
+                .unwrap();
+            let mut file: PyFileLike = string_io.extract().unwrap();
+            let err = file.read(&mut [0; 4]).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+            assert!(py.None().bind(py).extract::<PyFileLike>().is_err());
+        });
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_py_file_like_object() {
+        use super::PyFileLikeObject;
+        use crate::Py;
+        use std::io::Cursor;
+
+        Python::with_gil(|py| {
+            let stream = Py::new(
+                py,
+                PyFileLikeObject::stream(Cursor::new(b"abcdef".to_vec())),
+            )
+            .unwrap();
+            let reader = Py::new(py, PyFileLikeObject::reader(&b"line 1\nline 2\n"[..])).unwrap();
+            crate::py_run!(
+                py,
+                stream reader,
+                r#"
+import io
+assert isinstance(stream, io.RawIOBase)
+assert stream.readable() and stream.writable() and stream.seekable()
+assert stream.read(2) == b"ab"
+buf = bytearray(3)
+assert stream.readinto(buf) == 3 and buf == b"cde"
+assert stream.tell() == 5
+assert stream.seek(-1, 2) == 5
+assert stream.write(memoryview(b"XYZ")) == 3
+stream.seek(0)
+assert stream.read() == b"abcdeXYZ"
+with stream:
+    pass
+assert stream.closed
+try:
+    stream.read()
+except ValueError:
+    pass
+else:
+    assert False
+
+assert not reader.seekable()
+for op, args in ((reader.write, (b"",)), (reader.seek, (0,)), (reader.fileno, ())):
+    try:
+        op(*args)
+    except io.UnsupportedOperation:
+        pass
+    else:
+        assert False
+assert io.BufferedReader(reader).readlines() == [b"line 1\n", b"line 2\n"]
+"#
+            );
+        });
+    }
+}
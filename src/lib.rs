@@ -78,6 +78,8 @@
 //! - `multiple-pymethods`: Enables the use of multiple [`#[pymethods]`](macro@crate::pymethods)
 //! blocks per [`#[pyclass]`](macro@crate::pyclass). This adds a dependency on the [inventory]
 //! crate, which is not supported on all platforms.
+//! - `pyproto`: Enables the deprecated `#[pyproto]` attribute, as a compatibility layer on top of
+//! [`#[pymethods]`](macro@crate::pymethods) for migrating existing code.
 //!
 //! The following features enable interactions with other crates in the Rust ecosystem:
 //! - [`anyhow`]: Enables a conversion from [anyhow]’s [`Error`][anyhow_error] type to [`PyErr`].
@@ -297,15 +299,22 @@
 //! [Features chapter of the guide]: https://pyo3.rs/latest/features.html#features-reference "Features Reference - PyO3 user guide"
 //! [`Ungil`]: crate::marker::Ungil
 pub use crate::class::*;
-pub use crate::conversion::{AsPyPointer, FromPyObject, FromPyPointer, IntoPy, ToPyObject};
+pub use crate::conversion::{
+    AsPyPointer, FromPyObject, FromPyPointer, IntoPy, IntoPyObject, ToPyObject,
+};
 #[allow(deprecated)]
 pub use crate::conversion::{PyTryFrom, PyTryInto};
 pub use crate::err::{
     DowncastError, DowncastIntoError, PyDowncastError, PyErr, PyErrArguments, PyResult,
 };
 pub use crate::gil::GILPool;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::gil::GILState;
 #[cfg(not(PyPy))]
-pub use crate::gil::{prepare_freethreaded_python, with_embedded_python_interpreter};
+pub use crate::gil::{
+    prepare_freethreaded_python, prepare_freethreaded_python_with_signal_handlers,
+    with_embedded_python_interpreter, with_embedded_python_interpreter_and_signal_handlers,
+};
 pub use crate::instance::{Borrowed, Bound, Py, PyNativeType, PyObject};
 pub use crate::marker::Python;
 pub use crate::pycell::{PyCell, PyRef, PyRefMut};
@@ -313,7 +322,7 @@ pub use crate::pyclass::PyClass;
 pub use crate::pyclass_init::PyClassInitializer;
 pub use crate::type_object::{PyTypeCheck, PyTypeInfo};
 pub use crate::types::PyAny;
-pub use crate::version::PythonVersionInfo;
+pub use crate::version::{PythonImplementation, PythonVersionInfo};
 
 pub(crate) mod ffi_ptr_ext;
 pub(crate) mod py_result_ext;
@@ -331,9 +340,15 @@ pub mod class {
 
     pub use self::gc::{PyTraverseError, PyVisit};
 
+    #[cfg(feature = "macros")]
+    pub use crate::pyproto::{
+        PyAsyncProtocol, PyBufferProtocol, PyContextProtocol, PyDescrProtocol, PyGCProtocol,
+        PyIterProtocol, PyMappingProtocol, PyNumberProtocol, PyObjectProtocol, PySequenceProtocol,
+    };
+
     #[doc(hidden)]
     pub use self::methods::{
-        PyClassAttributeDef, PyGetterDef, PyMethodDef, PyMethodDefType, PyMethodType, PySetterDef,
+        PyClassAttributeDef, PyDeleterDef, PyGetterDef, PyMethodDef, PyMethodDefType, PySetterDef,
     };
 
     /// Old module which contained some implementation details of the `#[pyproto]` module.
@@ -345,6 +360,45 @@ pub mod class {
     /// once <https://github.com/rust-lang/rust/issues/30827> is resolved.
     pub mod basic {
         pub use crate::pyclass::CompareOp;
+
+        #[cfg(feature = "macros")]
+        pub use crate::pyproto::PyObjectProtocol;
+    }
+
+    /// Legacy `#[pyproto]` protocol trait for numeric operators.
+    #[cfg(feature = "macros")]
+    pub mod number {
+        pub use crate::pyproto::PyNumberProtocol;
+    }
+
+    /// Legacy `#[pyproto]` protocol trait for mapping containers.
+    #[cfg(feature = "macros")]
+    pub mod mapping {
+        pub use crate::pyproto::PyMappingProtocol;
+    }
+
+    /// Legacy `#[pyproto]` protocol trait for sequence containers.
+    #[cfg(feature = "macros")]
+    pub mod sequence {
+        pub use crate::pyproto::PySequenceProtocol;
+    }
+
+    /// Legacy `#[pyproto]` protocol trait for context managers.
+    #[cfg(feature = "macros")]
+    pub mod context {
+        pub use crate::pyproto::PyContextProtocol;
+    }
+
+    /// Legacy `#[pyproto]` protocol trait for descriptors.
+    #[cfg(feature = "macros")]
+    pub mod descr {
+        pub use crate::pyproto::PyDescrProtocol;
+    }
+
+    /// Legacy `#[pyproto]` protocol trait for the buffer protocol.
+    #[cfg(feature = "macros")]
+    pub mod buffer {
+        pub use crate::pyproto::PyBufferProtocol;
     }
 
     /// Old module which contained some implementation details of the `#[pyproto]` module.
@@ -357,6 +411,9 @@ pub mod class {
     pub mod pyasync {
         #[allow(deprecated)]
         pub use crate::pyclass::{IterANextOutput, PyIterANextOutput};
+
+        #[cfg(feature = "macros")]
+        pub use crate::pyproto::PyAsyncProtocol;
     }
 
     /// Old module which contained some implementation details of the `#[pyproto]` module.
@@ -369,6 +426,9 @@ pub mod class {
     pub mod iter {
         #[allow(deprecated)]
         pub use crate::pyclass::{IterNextOutput, PyIterNextOutput};
+
+        #[cfg(feature = "macros")]
+        pub use crate::pyproto::PyIterProtocol;
     }
 
     /// Old module which contained some implementation details of the `#[pyproto]` module.
@@ -380,6 +440,9 @@ pub mod class {
     /// once <https://github.com/rust-lang/rust/issues/30827> is resolved.
     pub mod gc {
         pub use crate::pyclass::{PyTraverseError, PyVisit};
+
+        #[cfg(feature = "macros")]
+        pub use crate::pyproto::PyGCProtocol;
     }
 }
 
@@ -403,25 +466,37 @@ mod tests;
 #[macro_use]
 mod internal_tricks;
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod buffer;
+pub mod c_callback;
 #[doc(hidden)]
 pub mod callback;
 pub mod conversion;
 mod conversions;
 #[cfg(feature = "macros")]
 pub mod coroutine;
+#[cfg(feature = "debug-refcounts")]
+pub mod debug_refcounts;
 #[macro_use]
 #[doc(hidden)]
 pub mod derive_utils;
+pub mod dlpack;
 mod err;
 pub mod exceptions;
 pub mod ffi;
+#[cfg(feature = "macros")]
+pub mod generator;
 mod gil;
 #[doc(hidden)]
 pub mod impl_;
+#[cfg(feature = "macros")]
+pub mod import_hook;
 mod instance;
+pub mod io;
 pub mod marker;
 pub mod marshal;
+pub mod opaque;
 #[macro_use]
 pub mod sync;
 pub mod panic;
@@ -430,7 +505,11 @@ pub mod pybacked;
 pub mod pycell;
 pub mod pyclass;
 pub mod pyclass_init;
-
+#[cfg(feature = "macros")]
+mod pyproto;
+pub mod sys;
+#[cfg(all(not(Py_LIMITED_API), not(PyPy)))]
+pub mod trace;
 pub mod type_object;
 pub mod types;
 mod version;
@@ -439,7 +518,7 @@ mod version;
 pub use crate::conversions::*;
 
 #[cfg(feature = "macros")]
-pub use pyo3_macros::{pyfunction, pymethods, pymodule, FromPyObject};
+pub use pyo3_macros::{pyfunction, pymethods, pymodule, FromPyObject, IntoPyErr, IntoPyTuple};
 
 /// A proc macro used to expose Rust structs and fieldless enums as Python objects.
 ///
@@ -452,6 +531,9 @@ pub use pyo3_macros::{pyfunction, pymethods, pymodule, FromPyObject};
 #[cfg(feature = "macros")]
 pub use pyo3_macros::pyclass;
 
+#[cfg(feature = "pyproto")]
+pub use pyo3_macros::pyproto;
+
 #[cfg(feature = "macros")]
 #[macro_use]
 mod macros;
@@ -118,15 +118,18 @@
 //! [`Py`]: crate::Py
 use crate::err::{self, PyDowncastError, PyErr, PyResult};
 use crate::ffi_ptr_ext::FfiPtrExt;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::gil::GILState;
 use crate::gil::{GILGuard, GILPool, SuspendGIL};
 use crate::impl_::not_send::NotSend;
 use crate::py_result_ext::PyResultExt;
+use crate::sys::Sys;
 use crate::type_object::HasPyGilRef;
 use crate::types::any::PyAnyMethods;
 use crate::types::{
     PyAny, PyDict, PyEllipsis, PyModule, PyNone, PyNotImplemented, PyString, PyType,
 };
-use crate::version::PythonVersionInfo;
+use crate::version::{PythonImplementation, PythonVersionInfo};
 use crate::{
     ffi, Bound, FromPyPointer, IntoPy, Py, PyNativeType, PyObject, PyTypeCheck, PyTypeInfo,
 };
@@ -441,6 +444,138 @@ impl Python<'_> {
         f(unsafe { Python::assume_gil_acquired() })
     }
 
+    /// Attaches the current thread to the Python interpreter, allowing access to it. The provided
+    /// closure `F` will be executed with a `Python` marker token.
+    ///
+    /// This is the same operation as [`Python::with_gil`], named after what it does on the
+    /// free-threaded build of Python: there is no GIL to acquire, so the token proves that the
+    /// thread is attached to the interpreter, but does not imply that no other thread is running
+    /// Python code at the same time. Code which should work on both builds must not rely on the
+    /// token for exclusive access to Python objects or Rust data, and should use
+    /// [`GILOnceCell`](crate::sync::GILOnceCell) or a mutex instead of
+    /// `GILProtected`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::attach(|py| -> PyResult<()> {
+    ///     let x: i32 = py.eval_bound("5", None, None)?.extract()?;
+    ///     assert_eq!(x, 5);
+    ///     Ok(())
+    /// })
+    /// # }
+    /// ```
+    #[inline]
+    pub fn attach<F, R>(f: F) -> R
+    where
+        F: for<'py> FnOnce(Python<'py>) -> R,
+    {
+        Self::with_gil(f)
+    }
+
+    /// Runs `f` with the GIL held on a new background thread, without waiting for the GIL on the
+    /// current thread.
+    ///
+    /// This is useful from threads which must not block on the GIL, such as the workers of an
+    /// async runtime, for example to call a Python callback when a task completes. The GIL is
+    /// only acquired by the background thread, once it is available. The result of `f` can be
+    /// retrieved from the returned [`JoinHandle`](std::thread::JoinHandle).
+    ///
+    /// Only values which are [`Send`] can be moved into `f`: this includes [`Py<T>`](crate::Py)
+    /// and [`PyErr`](crate::PyErr), but not GIL-bound references such as [`Bound<'py, T>`](crate::Bound).
+    ///
+    /// Not available on `wasm32` targets, where Python is built without thread support.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// let callback: Py<PyAny> = Python::with_gil(|py| {
+    ///     py.eval_bound("lambda x: x * 2", None, None).map(Bound::unbind)
+    /// })?;
+    ///
+    /// // e.g. from a worker thread which must not block
+    /// let handle = Python::spawn_with_gil(move |py| callback.call1(py, (21,))?.extract::<i32>(py));
+    ///
+    /// assert_eq!(handle.join().unwrap()?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// GIL-bound references cannot be moved to the background thread:
+    ///
+    /// ```compile_fail
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::types::PyList;
+    /// Python::with_gil(|py| {
+    ///     let list = PyList::empty_bound(py);
+    ///     Python::spawn_with_gil(move |_py| list.len());
+    /// });
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_with_gil<F, R>(f: F) -> std::thread::JoinHandle<R>
+    where
+        F: for<'py> FnOnce(Python<'py>) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        std::thread::spawn(move || Python::with_gil(f))
+    }
+
+    /// Spawns a Rust thread which can call into Python for as long as the interpreter is running.
+    ///
+    /// `f` receives a [`GILState`], whose [`with_gil`](GILState::with_gil) method acquires the
+    /// GIL like [`Python::with_gil`], but returns `None` instead of crashing or hanging once the
+    /// interpreter is finalizing. This makes it safe for background threads which periodically
+    /// call into Python, such as pollers or metrics reporters, to outlive the interpreter: they
+    /// should stop when `with_gil` returns `None`.
+    ///
+    /// This registers an `atexit` hook for the thread, which is removed when `f` returns.
+    ///
+    /// Not available on `wasm32` targets, where Python is built without thread support.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// let handle = Python::with_gil(|py| {
+    ///     py.spawn_with_gilstate(|gil| {
+    ///         let mut ticks = 0;
+    ///         // stops early if the interpreter finalizes
+    ///         while ticks < 3 && gil.with_gil(|py| py.run_bound("pass", None, None)).is_some() {
+    ///             ticks += 1;
+    ///             std::thread::sleep(Duration::from_millis(1));
+    ///         }
+    ///         ticks
+    ///     })
+    /// })?;
+    ///
+    /// assert_eq!(handle.join().unwrap(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_with_gilstate<F, T>(self, f: F) -> PyResult<std::thread::JoinHandle<T>>
+    where
+        F: FnOnce(GILState) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (state, hook) = GILState::register(self)?;
+        Ok(std::thread::spawn(move || {
+            let result =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(state.clone())));
+            state.unregister(hook);
+            result.unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+        }))
+    }
+
     /// Like [`Python::with_gil`] except Python interpreter state checking is skipped.
     ///
     /// Normally when the GIL is acquired, we check that the Python interpreter is an
@@ -763,6 +898,14 @@ impl<'py> Python<'py> {
             .map(crate::Borrowed::to_owned)
     }
 
+    /// Returns a typed facade for the `sys` module, to read and modify the configuration of the
+    /// interpreter such as `sys.argv` and `sys.path`.
+    ///
+    /// See the [`sys`](crate::sys) module for an example.
+    pub fn sys(self) -> PyResult<Sys<'py>> {
+        Sys::new(self)
+    }
+
     /// Gets the Python builtin value `None`.
     #[allow(non_snake_case)] // the Python keyword starts with uppercase
     #[inline]
@@ -825,6 +968,85 @@ impl<'py> Python<'py> {
         PythonVersionInfo::from_str(version_number_str).unwrap()
     }
 
+    /// Gets the frame of the Python code currently executing on this thread, if any.
+    ///
+    /// This is `None` when no Python code is running, for example when the GIL was acquired from
+    /// Rust code which was not called from Python. See [`PyFrameMethods`] for the information
+    /// which can be read from the frame, such as the file name and line number of the call site.
+    ///
+    /// [`PyFrameMethods`]: crate::prelude::PyFrameMethods
+    #[cfg(all(not(Py_LIMITED_API), not(PyPy)))]
+    pub fn current_frame(self) -> Option<Bound<'py, crate::types::PyFrame>> {
+        unsafe {
+            ffi::PyEval_GetFrame()
+                .cast::<ffi::PyObject>()
+                .assume_borrowed_or_opt(self)
+                .map(|frame| frame.to_owned().downcast_into_unchecked())
+        }
+    }
+
+    /// Gets the implementation of the running Python interpreter, as named by
+    /// `sys.implementation.name`.
+    ///
+    /// This allows code to branch on the interpreter at runtime, for example in a library compiled
+    /// against the stable ABI, which can be loaded by several implementations.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::{Python, PythonImplementation};
+    /// Python::with_gil(|py| {
+    ///     let implementation = py.implementation();
+    ///     assert!(matches!(
+    ///         implementation,
+    ///         PythonImplementation::CPython | PythonImplementation::PyPy | PythonImplementation::GraalPy
+    ///     ));
+    /// });
+    /// ```
+    pub fn implementation(self) -> PythonImplementation {
+        use crate::sync::GILOnceCell;
+        static IMPLEMENTATION: GILOnceCell<PythonImplementation> = GILOnceCell::new();
+
+        *IMPLEMENTATION.get_or_init(self, || {
+            let name = self
+                .import_bound("sys")
+                .and_then(|sys| sys.getattr("implementation")?.getattr("name"))
+                .and_then(|name| name.extract::<String>())
+                .expect("failed to read sys.implementation.name");
+            PythonImplementation::from_name(&name)
+        })
+    }
+
+    /// Returns whether the running Python interpreter is a free-threaded build, which can run
+    /// without the global interpreter lock (see [PEP 703](https://peps.python.org/pep-0703/)).
+    ///
+    /// This checks how the interpreter was built, as given by the `Py_GIL_DISABLED` configuration
+    /// variable, rather than whether the GIL is currently disabled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::Python;
+    /// Python::with_gil(|py| {
+    ///     if !py.is_free_threaded() {
+    ///         // only one thread can run Python code at a time
+    ///     }
+    /// });
+    /// ```
+    pub fn is_free_threaded(self) -> bool {
+        use crate::sync::GILOnceCell;
+        static IS_FREE_THREADED: GILOnceCell<bool> = GILOnceCell::new();
+
+        *IS_FREE_THREADED.get_or_init(self, || {
+            // `sysconfig` may be missing from stripped-down embedded distributions, which are
+            // never free-threaded builds.
+            self.import_bound("sysconfig")
+                .and_then(|sysconfig| {
+                    sysconfig.call_method1("get_config_var", ("Py_GIL_DISABLED",))
+                })
+                .and_then(|value| value.extract::<Option<i32>>())
+                .map_or(false, |value| value == Some(1))
+        })
+    }
+
     /// Registers the object in the release pool, and tries to downcast to specific type.
     pub fn checked_cast_as<T>(self, obj: PyObject) -> Result<&'py T, PyDowncastError<'py>>
     where
@@ -1046,12 +1268,12 @@ impl<'py> Python<'py> {
 impl Python<'_> {
     /// Creates a scope using a new pool for managing PyO3's owned references.
     ///
-    /// This is a safe alterantive to [`new_pool`][Self::new_pool] as
+    /// This is a safe alternative to [`new_pool`][Self::new_pool] as
     /// it limits the closure to using the new GIL token at the cost of
     /// being unable to capture existing GIL-bound references.
     ///
-    /// Note that on stable Rust, this API suffers from the same the `SendWrapper` loophole
-    /// as [`allow_threads`][Self::allow_threads], c.f. the documentation of the [`Ungil`] trait,
+    /// Note that on stable Rust, this API suffers from the same `SendWrapper` loophole
+    /// as [`allow_threads`][Self::allow_threads], c.f. the documentation of the [`Ungil`] trait.
     ///
     /// # Examples
     ///
@@ -1139,6 +1361,31 @@ mod tests {
     use crate::types::{any::PyAnyMethods, IntoPyDict, PyDict, PyList};
     use std::sync::Arc;
 
+    #[test]
+    fn test_thread_safe_handles() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<crate::Py<PyAny>>();
+        assert_send_sync::<crate::Py<PyList>>();
+        assert_send_sync::<crate::PyErr>();
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))] // We are building wasm Python with pthreads disabled
+    fn test_spawn_with_gil() {
+        use crate::types::list::PyListMethods;
+
+        let list = Python::with_gil(|py| PyList::empty_bound(py).unbind());
+        let handle = {
+            let list = Python::with_gil(|py| list.clone_ref(py));
+            Python::spawn_with_gil(move |py| {
+                list.bind(py).append(1).unwrap();
+                list.bind(py).len()
+            })
+        };
+        assert_eq!(handle.join().unwrap(), 1);
+        Python::with_gil(|py| assert_eq!(list.bind(py).len(), 1));
+    }
+
     #[test]
     fn test_eval() {
         Python::with_gil(|py| {
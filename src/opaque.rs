@@ -0,0 +1,166 @@
+//! Contains [`OpaquePyObject`], for passing Python objects through Rust code untouched.
+
+use std::fmt;
+
+use crate::{
+    instance::Bound, FromPyObject, IntoPy, Py, PyAny, PyObject, PyResult, Python, ToPyObject,
+};
+
+/// A Python object which Rust code carries around without looking at it.
+///
+/// Rust plumbing (queues, channels, actor systems, ...) often only ferries Python objects between
+/// Python-side producers and consumers. `OpaquePyObject` is a minimal wrapper for this case:
+///
+/// - extracting it from any Python object and converting it back performs no conversion and
+///   returns the identical object;
+/// - it is `Send` and `Sync`, and has the same size as a pointer;
+/// - cloning and dropping it do not require the GIL. If the GIL is not held, the reference count
+///   change is deferred until PyO3 next acquires the GIL.
+///
+/// The wrapped object can only be accessed again by converting back to [`Py<PyAny>`] or by
+/// [binding](OpaquePyObject::bind) it to a GIL token.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::opaque::OpaquePyObject;
+/// use pyo3::prelude::*;
+/// use std::sync::{mpsc, Mutex};
+///
+/// #[pyclass]
+/// struct Queue {
+///     sender: mpsc::Sender<OpaquePyObject>,
+///     receiver: Mutex<mpsc::Receiver<OpaquePyObject>>,
+/// }
+///
+/// #[pymethods]
+/// impl Queue {
+///     fn put(&self, item: OpaquePyObject) {
+///         self.sender.send(item).unwrap();
+///     }
+///
+///     fn get(&self, py: Python<'_>) -> Option<OpaquePyObject> {
+///         py.allow_threads(|| self.receiver.lock().unwrap().recv().ok())
+///     }
+/// }
+/// ```
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct OpaquePyObject(PyObject);
+
+impl OpaquePyObject {
+    /// Returns the wrapped object as a [`Py<PyAny>`].
+    #[inline]
+    pub fn into_inner(self) -> PyObject {
+        self.0
+    }
+
+    /// Attaches the wrapped object to the GIL lifetime `'py`.
+    #[inline]
+    pub fn bind<'py>(&self, py: Python<'py>) -> &Bound<'py, PyAny> {
+        self.0.bind(py)
+    }
+
+    /// Returns the raw pointer of the wrapped object, without touching its reference count.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut crate::ffi::PyObject {
+        self.0.as_ptr()
+    }
+}
+
+impl<T> From<Py<T>> for OpaquePyObject {
+    #[inline]
+    fn from(obj: Py<T>) -> Self {
+        OpaquePyObject(obj.into_any())
+    }
+}
+
+impl<T> From<Bound<'_, T>> for OpaquePyObject {
+    #[inline]
+    fn from(obj: Bound<'_, T>) -> Self {
+        OpaquePyObject(obj.into_any().unbind())
+    }
+}
+
+impl From<OpaquePyObject> for PyObject {
+    #[inline]
+    fn from(obj: OpaquePyObject) -> Self {
+        obj.0
+    }
+}
+
+impl FromPyObject<'_> for OpaquePyObject {
+    #[inline]
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(OpaquePyObject(obj.clone().unbind()))
+    }
+}
+
+impl ToPyObject for OpaquePyObject {
+    #[inline]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.0.clone_ref(py)
+    }
+}
+
+impl IntoPy<PyObject> for OpaquePyObject {
+    #[inline]
+    fn into_py(self, _py: Python<'_>) -> PyObject {
+        self.0
+    }
+}
+
+into_pyobject_via_into_py!(OpaquePyObject);
+
+/// Formats only the object's address, as the object itself cannot be inspected without the GIL.
+impl fmt::Debug for OpaquePyObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OpaquePyObject")
+            .field(&self.as_ptr())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OpaquePyObject;
+    use crate::prelude::*;
+    use crate::types::PyList;
+
+    #[test]
+    fn test_opaque_roundtrip_is_identity() {
+        Python::with_gil(|py| {
+            let list = PyList::empty_bound(py);
+            let opaque: OpaquePyObject = list.extract().unwrap();
+            assert_eq!(opaque.as_ptr(), list.as_ptr());
+            assert!(opaque.into_py(py).bind(py).is(&list));
+        });
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))] // We are building wasm Python with pthreads disabled
+    fn test_opaque_crosses_threads_without_gil() {
+        let opaque = Python::with_gil(|py| OpaquePyObject::from(PyList::empty_bound(py)));
+        let opaque = std::thread::spawn(move || {
+            // Cloning and dropping without the GIL defers the reference count changes
+            let copy = opaque.clone();
+            drop(opaque);
+            copy
+        })
+        .join()
+        .unwrap();
+        Python::with_gil(|py| {
+            let list = opaque.bind(py).downcast::<PyList>().unwrap();
+            list.append(1).unwrap();
+            assert_eq!(list.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_opaque_is_pointer_sized() {
+        assert_eq!(
+            std::mem::size_of::<OpaquePyObject>(),
+            std::mem::size_of::<*mut crate::ffi::PyObject>()
+        );
+    }
+}
@@ -1,7 +1,11 @@
 //! Helper to convert Rust panics to Python exceptions.
 use crate::exceptions::PyBaseException;
-use crate::PyErr;
+use crate::types::any::PyAnyMethods;
+use crate::{PyErr, PyNativeType, Python};
+use parking_lot::{const_mutex, Mutex};
 use std::any::Any;
+use std::cell::RefCell;
+use std::sync::Once;
 
 pyo3_exception!(
     "
@@ -10,6 +14,11 @@ The exception raised when Rust code called from Python panics.
 Like SystemExit, this exception is derived from BaseException so that
 it will typically propagate all the way through the stack and cause the
 Python interpreter to exit.
+
+If the panic hook installed by `pyo3::panic::install_panic_hook` is active,
+the `location` attribute is set to the source location of the panic, and the
+`backtrace` attribute to the Rust backtrace if one was captured (controlled by
+the `RUST_BACKTRACE` environment variable). Otherwise both are `None`.
 ",
     PanicException,
     PyBaseException
@@ -18,15 +27,184 @@ Python interpreter to exit.
 impl PanicException {
     /// Creates a new PanicException from a panic payload.
     ///
-    /// Attempts to format the error in the same way panic does.
+    /// Attempts to format the error in the same way panic does. If a [`PanicTranslator`] is set,
+    /// it is given the chance to create a different exception first.
     #[cold]
-    pub(crate) fn from_panic_payload(payload: Box<dyn Any + Send + 'static>) -> PyErr {
+    pub(crate) fn from_panic_payload(
+        py: Python<'_>,
+        payload: Box<dyn Any + Send + 'static>,
+    ) -> PyErr {
+        let captured = LAST_PANIC
+            .try_with(|last| last.try_borrow_mut().ok()?.take())
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let details = PanicDetails {
+            payload: &*payload,
+            location: captured.location.as_deref(),
+            backtrace: captured.backtrace.as_deref(),
+        };
+
+        let translator = *PANIC_TRANSLATOR.lock();
+        if let Some(err) = translator.and_then(|translator| translator(py, &details)) {
+            return err;
+        }
+
+        let err = Self::new_err((details
+            .message()
+            .unwrap_or("panic from Rust code")
+            .to_owned(),));
+        let value = err.value(py).as_borrowed();
+        // Setting attributes on a fresh exception instance does not fail in practice; if it did,
+        // the exception is still more useful without them than replaced by the failure.
+        let _ = value.setattr(intern!(py, "location"), details.location);
+        let _ = value.setattr(intern!(py, "backtrace"), details.backtrace);
+        err
+    }
+}
+
+/// Information about a Rust panic which is being converted into a Python exception.
+///
+/// See [`set_panic_translator`].
+pub struct PanicDetails<'a> {
+    payload: &'a (dyn Any + Send),
+    location: Option<&'a str>,
+    backtrace: Option<&'a str>,
+}
+
+impl<'a> PanicDetails<'a> {
+    /// The payload the panic was started with, as passed to [`std::panic::panic_any`].
+    pub fn payload(&self) -> &'a (dyn Any + Send) {
+        self.payload
+    }
+
+    /// The panic message, if the payload is a string (as it is for panics started with
+    /// `panic!("...")`).
+    pub fn message(&self) -> Option<&'a str> {
+        let payload = self.payload;
         if let Some(string) = payload.downcast_ref::<String>() {
-            Self::new_err((string.clone(),))
-        } else if let Some(s) = payload.downcast_ref::<&str>() {
-            Self::new_err((s.to_string(),))
+            Some(string)
         } else {
-            Self::new_err(("panic from Rust code",))
+            payload.downcast_ref::<&str>().copied()
         }
     }
+
+    /// The source location of the panic, formatted as `file:line:column`.
+    ///
+    /// This is only available if [`install_panic_hook`] has been called.
+    pub fn location(&self) -> Option<&'a str> {
+        self.location
+    }
+
+    /// The Rust backtrace of the panic.
+    ///
+    /// This is only available if [`install_panic_hook`] has been called, backtraces are enabled
+    /// with the `RUST_BACKTRACE` environment variable, and PyO3 was compiled with Rust 1.65 or
+    /// later.
+    pub fn backtrace(&self) -> Option<&'a str> {
+        self.backtrace
+    }
+}
+
+/// Function converting a Rust panic into a Python exception.
+///
+/// Returning `None` falls back to raising a [`PanicException`].
+///
+/// See [`set_panic_translator`].
+pub type PanicTranslator = fn(py: Python<'_>, details: &PanicDetails<'_>) -> Option<PyErr>;
+
+static PANIC_TRANSLATOR: Mutex<Option<PanicTranslator>> = const_mutex(None);
+
+/// Sets the function used to convert Rust panics into Python exceptions, returning the previous
+/// one.
+///
+/// By default, a panic in Rust code called from Python raises a [`PanicException`], which
+/// derives from `BaseException` and so is typically not caught by Python code. Applications can
+/// use a translator to raise their own exception types instead.
+///
+/// Note that only [`PanicException`]s are resumed as Rust panics when they propagate back into
+/// Rust through [`PyErr::fetch`] or [`PyErr::take`]; exceptions created by a translator are
+/// handled like any other Python exception.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::exceptions::PyRuntimeError;
+/// use pyo3::panic::{set_panic_translator, PanicDetails};
+/// use pyo3::prelude::*;
+///
+/// fn translate(_py: Python<'_>, details: &PanicDetails<'_>) -> Option<PyErr> {
+///     let message = details.message().unwrap_or("unknown panic");
+///     Some(PyRuntimeError::new_err(format!("internal error: {}", message)))
+/// }
+///
+/// set_panic_translator(Some(translate));
+/// # set_panic_translator(None);
+/// ```
+pub fn set_panic_translator(translator: Option<PanicTranslator>) -> Option<PanicTranslator> {
+    std::mem::replace(&mut *PANIC_TRANSLATOR.lock(), translator)
+}
+
+#[derive(Default)]
+struct CapturedPanic {
+    location: Option<String>,
+    backtrace: Option<String>,
+}
+
+thread_local! {
+    /// Details of the last panic on this thread, recorded by the hook from `install_panic_hook`.
+    static LAST_PANIC: RefCell<Option<CapturedPanic>> = RefCell::new(None);
+}
+
+/// Installs a Rust panic hook recording the location and backtrace of panics, so that they are
+/// available as the `location` and `backtrace` attributes of the resulting [`PanicException`],
+/// and to a [`PanicTranslator`].
+///
+/// The previously installed panic hook is still called after the details are recorded, so the
+/// usual panic message is still printed. Calling this function more than once has no effect.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+///
+/// #[pymodule]
+/// fn my_extension(_py: Python<'_>, _module: &PyModule) -> PyResult<()> {
+///     pyo3::panic::install_panic_hook();
+///     Ok(())
+/// }
+/// ```
+pub fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let captured = CapturedPanic {
+                location: info.location().map(ToString::to_string),
+                backtrace: capture_backtrace(),
+            };
+            let _ = LAST_PANIC.try_with(|last| {
+                if let Ok(mut last) = last.try_borrow_mut() {
+                    *last = Some(captured);
+                }
+            });
+            previous(info);
+        }));
+    });
+}
+
+#[cfg(std_backtrace)]
+#[allow(clippy::incompatible_msrv)] // only compiled on Rust 1.65 and later
+fn capture_backtrace() -> Option<String> {
+    use std::backtrace::{Backtrace, BacktraceStatus};
+    let backtrace = Backtrace::capture();
+    match backtrace.status() {
+        BacktraceStatus::Captured => Some(backtrace.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(not(std_backtrace))]
+fn capture_backtrace() -> Option<String> {
+    None
 }
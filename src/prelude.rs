@@ -8,7 +8,7 @@
 //! use pyo3::prelude::*;
 //! ```
 
-pub use crate::conversion::{FromPyObject, IntoPy, ToPyObject};
+pub use crate::conversion::{FromPyObject, IntoPy, IntoPyObject, ToPyObject};
 #[allow(deprecated)]
 pub use crate::conversion::{PyTryFrom, PyTryInto};
 pub use crate::err::{PyErr, PyResult};
@@ -22,6 +22,9 @@ pub use crate::PyNativeType;
 #[cfg(feature = "macros")]
 pub use pyo3_macros::{pyclass, pyfunction, pymethods, pymodule, FromPyObject};
 
+#[cfg(feature = "pyproto")]
+pub use pyo3_macros::pyproto;
+
 #[cfg(feature = "macros")]
 pub use crate::wrap_pyfunction;
 
@@ -30,8 +33,14 @@ pub use crate::types::boolobject::PyBoolMethods;
 pub use crate::types::bytearray::PyByteArrayMethods;
 pub use crate::types::bytes::PyBytesMethods;
 pub use crate::types::capsule::PyCapsuleMethods;
+#[cfg(not(Py_LIMITED_API))]
+pub use crate::types::code::PyCodeMethods;
 pub use crate::types::dict::PyDictMethods;
+#[cfg(not(PyPy))]
+pub use crate::types::dict::{PyDictSetViewMethods, PyDictViewMethods};
 pub use crate::types::float::PyFloatMethods;
+#[cfg(all(not(Py_LIMITED_API), not(PyPy)))]
+pub use crate::types::frame::PyFrameMethods;
 pub use crate::types::frozenset::PyFrozenSetMethods;
 pub use crate::types::list::PyListMethods;
 pub use crate::types::mapping::PyMappingMethods;
@@ -1,18 +1,66 @@
 //! Contains types for working with Python objects that own the underlying data.
 
-use std::{ops::Deref, ptr::NonNull};
+use std::{fmt, hash::Hash, ops::Deref, ptr::NonNull, sync::Arc};
 
 use crate::{
     types::{
         any::PyAnyMethods, bytearray::PyByteArrayMethods, bytes::PyBytesMethods,
         string::PyStringMethods, PyByteArray, PyBytes, PyString,
     },
-    Bound, DowncastError, FromPyObject, Py, PyAny, PyErr, PyResult,
+    Bound, DowncastError, FromPyObject, IntoPy, Py, PyAny, PyErr, PyObject, PyResult, Python,
+    ToPyObject,
 };
 
+/// Implements comparison and hashing by the contents of the data.
+macro_rules! impl_traits {
+    ($slf:ty, $equiv:ty) => {
+        impl PartialEq for $slf {
+            fn eq(&self, other: &Self) -> bool {
+                **self == **other
+            }
+        }
+
+        impl Eq for $slf {}
+
+        impl PartialEq<$equiv> for $slf {
+            fn eq(&self, other: &$equiv) -> bool {
+                &**self == other
+            }
+        }
+
+        impl PartialEq<$slf> for $equiv {
+            fn eq(&self, other: &$slf) -> bool {
+                self == &**other
+            }
+        }
+
+        impl PartialOrd for $slf {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $slf {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                (**self).cmp(&**other)
+            }
+        }
+
+        impl Hash for $slf {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                (**self).hash(state)
+            }
+        }
+    };
+}
+
 /// A wrapper around `str` where the storage is owned by a Python `bytes` or `str` object.
 ///
-/// This type gives access to the underlying data via a `Deref` implementation.
+/// This type gives access to the underlying data via a `Deref` implementation. It does not
+/// need the GIL to access the data, so it can be held and read inside
+/// [`Python::allow_threads`](crate::Python::allow_threads) or sent to other threads without
+/// copying the string.
+#[derive(Clone)]
 pub struct PyBackedStr {
     #[allow(dead_code)] // only held so that the storage is not dropped
     storage: Py<PyAny>,
@@ -27,6 +75,37 @@ impl Deref for PyBackedStr {
     }
 }
 
+impl AsRef<str> for PyBackedStr {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<[u8]> for PyBackedStr {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// Safety: the storage is an immutable Python object, which is only read through `data`, and
+// `Py<T>` can be dropped without the GIL.
+unsafe impl Send for PyBackedStr {}
+unsafe impl Sync for PyBackedStr {}
+
+impl fmt::Debug for PyBackedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl fmt::Display for PyBackedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl_traits!(PyBackedStr, str);
+
 impl TryFrom<Bound<'_, PyString>> for PyBackedStr {
     type Error = PyErr;
     fn try_from(py_string: Bound<'_, PyString>) -> Result<Self, Self::Error> {
@@ -59,9 +138,38 @@ impl FromPyObject<'_> for PyBackedStr {
     }
 }
 
-/// A wrapper around `[u8]` where the storage is either owned by a Python `bytes` object, or a Rust `Box<[u8]>`.
+/// Returns the backing `str` object without copying, where possible.
+impl ToPyObject for PyBackedStr {
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.storage.clone_ref(py)
+    }
+
+    #[cfg(not(any(Py_3_10, not(Py_LIMITED_API))))]
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        PyString::new_bound(py, self).into_any().unbind()
+    }
+}
+
+impl IntoPy<PyObject> for PyBackedStr {
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    fn into_py(self, _py: Python<'_>) -> PyObject {
+        self.storage
+    }
+
+    #[cfg(not(any(Py_3_10, not(Py_LIMITED_API))))]
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+into_pyobject_via_into_py!(PyBackedStr);
+
+/// A wrapper around `[u8]` where the storage is either owned by a Python `bytes` object, or a Rust `Arc<[u8]>`.
 ///
-/// This type gives access to the underlying data via a `Deref` implementation.
+/// This type gives access to the underlying data via a `Deref` implementation. Like
+/// [`PyBackedStr`], it does not need the GIL to access the data.
+#[derive(Clone)]
 pub struct PyBackedBytes {
     #[allow(dead_code)] // only held so that the storage is not dropped
     storage: PyBackedBytesStorage,
@@ -69,9 +177,10 @@ pub struct PyBackedBytes {
 }
 
 #[allow(dead_code)]
+#[derive(Clone)]
 enum PyBackedBytesStorage {
     Python(Py<PyBytes>),
-    Rust(Box<[u8]>),
+    Rust(Arc<[u8]>),
 }
 
 impl Deref for PyBackedBytes {
@@ -82,6 +191,25 @@ impl Deref for PyBackedBytes {
     }
 }
 
+impl AsRef<[u8]> for PyBackedBytes {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+// Safety: the storage is either an immutable Python object or an `Arc<[u8]>`, which are only
+// read through `data`, and `Py<T>` can be dropped without the GIL.
+unsafe impl Send for PyBackedBytes {}
+unsafe impl Sync for PyBackedBytes {}
+
+impl fmt::Debug for PyBackedBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl_traits!(PyBackedBytes, [u8]);
+
 impl From<Bound<'_, PyBytes>> for PyBackedBytes {
     fn from(py_bytes: Bound<'_, PyBytes>) -> Self {
         let b = py_bytes.as_bytes();
@@ -95,7 +223,7 @@ impl From<Bound<'_, PyBytes>> for PyBackedBytes {
 
 impl From<Bound<'_, PyByteArray>> for PyBackedBytes {
     fn from(py_bytearray: Bound<'_, PyByteArray>) -> Self {
-        let s = py_bytearray.to_vec().into_boxed_slice();
+        let s = Arc::<[u8]>::from(py_bytearray.to_vec());
         let data = NonNull::from(s.as_ref());
         Self {
             storage: PyBackedBytesStorage::Rust(s),
@@ -116,6 +244,27 @@ impl FromPyObject<'_> for PyBackedBytes {
     }
 }
 
+/// Returns the backing `bytes` object without copying, if the data came from `bytes`.
+impl ToPyObject for PyBackedBytes {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        match &self.storage {
+            PyBackedBytesStorage::Python(bytes) => bytes.to_object(py),
+            PyBackedBytesStorage::Rust(bytes) => PyBytes::new_bound(py, bytes).into_any().unbind(),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for PyBackedBytes {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self.storage {
+            PyBackedBytesStorage::Python(bytes) => bytes.into_any(),
+            PyBackedBytesStorage::Rust(bytes) => PyBytes::new_bound(py, &bytes).into_any().unbind(),
+        }
+    }
+}
+
+into_pyobject_via_into_py!(PyBackedBytes);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -183,4 +332,67 @@ mod test {
             assert_eq!(&*py_backed_bytes, b"abcde");
         });
     }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))] // We are building wasm Python with pthreads disabled
+    fn py_backed_across_allow_threads() {
+        Python::with_gil(|py| {
+            let s: PyBackedStr = PyString::new_bound(py, "hello").extract().unwrap();
+            let b: PyBackedBytes = PyByteArray::new_bound(py, b"abcde").extract().unwrap();
+            let (s, b) = py.allow_threads(move || {
+                assert_eq!(s.len(), 5);
+                std::thread::spawn(move || (s.to_uppercase(), b.to_vec()))
+                    .join()
+                    .unwrap()
+            });
+            assert_eq!(s, "HELLO");
+            assert_eq!(b, b"abcde");
+        });
+    }
+
+    #[test]
+    fn py_backed_clone_and_compare() {
+        Python::with_gil(|py| {
+            let s: PyBackedStr = PyString::new_bound(py, "hello").extract().unwrap();
+            let s2 = s.clone();
+            assert_eq!(s, s2);
+            assert_eq!(s, *"hello");
+            assert!(
+                s < PyString::new_bound(py, "world")
+                    .extract::<PyBackedStr>()
+                    .unwrap()
+            );
+            assert_eq!(format!("{} {:?}", s, s), "hello \"hello\"");
+
+            let b: PyBackedBytes = PyByteArray::new_bound(py, b"abc").extract().unwrap();
+            let b2 = b.clone();
+            drop(b);
+            assert_eq!(b2, *b"abc".as_slice());
+
+            let mut set = std::collections::HashSet::new();
+            set.insert(s);
+            assert!(set.contains(&s2));
+        });
+    }
+
+    #[test]
+    fn py_backed_into_py() {
+        Python::with_gil(|py| {
+            let orig = PyBytes::new_bound(py, b"abc");
+            let b: PyBackedBytes = orig.extract().unwrap();
+            assert!(b.to_object(py).is(&orig));
+            assert!(b.into_py(py).is(&orig));
+
+            let b: PyBackedBytes = PyByteArray::new_bound(py, b"abc").extract().unwrap();
+            let obj = b.into_py(py);
+            assert_eq!(obj.extract::<&[u8]>(py).unwrap(), b"abc");
+
+            let orig = PyString::new_bound(py, "hello");
+            let s: PyBackedStr = orig.extract().unwrap();
+            let obj = s.into_py(py);
+            assert_eq!(obj.extract::<String>(py).unwrap(), "hello");
+            #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+            assert!(obj.is(&orig));
+        });
+    }
 }
@@ -201,13 +201,13 @@ use crate::pyclass::{
 };
 use crate::pyclass_init::PyClassInitializer;
 use crate::type_object::{PyLayout, PySizedLayout};
-use crate::types::PyAny;
+use crate::types::{PyAny, PyString, PyTuple};
 use crate::{
-    conversion::{AsPyPointer, FromPyPointer, ToPyObject},
+    conversion::{AsPyPointer, ToPyObject},
     type_object::get_tp_free,
     PyTypeInfo,
 };
-use crate::{ffi, Bound, IntoPy, PyErr, PyNativeType, PyObject, PyResult, PyTypeCheck, Python};
+use crate::{ffi, Bound, IntoPy, Py, PyErr, PyNativeType, PyObject, PyResult, PyTypeCheck, Python};
 use std::cell::UnsafeCell;
 use std::fmt;
 use std::mem::ManuallyDrop;
@@ -273,6 +273,7 @@ pub(crate) struct PyCellContents<T: PyClassImpl> {
     pub(crate) thread_checker: T::ThreadChecker,
     pub(crate) dict: T::Dict,
     pub(crate) weakref: T::WeakRef,
+    pub(crate) vectorcall: T::Vectorcall,
 }
 
 unsafe impl<T: PyClass> PyNativeType for PyCell<T> {
@@ -285,11 +286,10 @@ impl<T: PyClass> PyCell<T> {
     /// In cases where the value in the cell does not need to be accessed immediately after
     /// creation, consider [`Py::new`](crate::Py::new) as a more efficient alternative.
     pub fn new(py: Python<'_>, value: impl Into<PyClassInitializer<T>>) -> PyResult<&Self> {
-        unsafe {
-            let initializer = value.into();
-            let self_ = initializer.create_cell(py)?;
-            FromPyPointer::from_owned_ptr_or_err(py, self_ as _)
-        }
+        value
+            .into()
+            .create_class_object(py)
+            .map(Bound::into_gil_ref)
     }
 
     /// Immutably borrows the value `T`. This borrow lasts as long as the returned `PyRef` exists.
@@ -527,6 +527,17 @@ impl<T: PyClass> PyCell<T> {
         offset.try_into().expect("offset should fit in Py_ssize_t")
     }
 
+    /// Gets the offset of the vectorcall function pointer from the start of the struct in bytes.
+    pub(crate) fn vectorcall_offset() -> ffi::Py_ssize_t {
+        use memoffset::offset_of;
+
+        let offset = offset_of!(PyCell<T>, contents) + offset_of!(PyCellContents<T>, vectorcall);
+
+        // Py_ssize_t may not be equal to isize on all platforms
+        #[allow(clippy::useless_conversion)]
+        offset.try_into().expect("offset should fit in Py_ssize_t")
+    }
+
     #[cfg(feature = "macros")]
     pub(crate) fn release_ref(&self) {
         self.borrow_checker().release_borrow();
@@ -655,6 +666,97 @@ impl<'p, T: PyClass> PyRef<'p, T> {
     pub fn py(&self) -> Python<'p> {
         self.inner.py()
     }
+
+    /// Returns the Python object which holds the borrowed value.
+    ///
+    /// This allows a method taking `slf: PyRef<'_, Self>` to also treat `self` as a Python
+    /// object, for example to return it or call methods which a Python subclass may override.
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// #[pyclass(subclass)]
+    /// struct Greeter;
+    ///
+    /// #[pymethods]
+    /// impl Greeter {
+    ///     #[new]
+    ///     fn new() -> Self {
+    ///         Greeter
+    ///     }
+    ///
+    ///     fn name(&self) -> &'static str {
+    ///         "Greeter"
+    ///     }
+    ///
+    ///     fn greet(slf: PyRef<'_, Self>) -> PyResult<String> {
+    ///         let name: String = slf.as_pyobject().call_method0("name")?.extract()?;
+    ///         Ok(format!("Hello from {}", name))
+    ///     }
+    /// }
+    /// # Python::with_gil(|py| {
+    /// #     let cls = py.get_type::<Greeter>();
+    /// #     pyo3::py_run!(py, cls, r#"
+    /// #         class Sub(cls):
+    /// #             def name(self):
+    /// #                 return "Sub"
+    /// #         assert Sub().greet() == "Hello from Sub"
+    /// #     "#);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn as_pyobject(&self) -> &'p PyCell<T> {
+        self.inner
+    }
+
+    /// Calls the method `name` of this object with positional arguments `args`, looking it up
+    /// as a Python attribute.
+    ///
+    /// Calling a Rust method directly, e.g. `self.name()`, always runs the Rust implementation.
+    /// This helper instead dispatches like the Python expression `self.name(*args)`, so an
+    /// override in a Python subclass is called when there is one.
+    ///
+    /// The value stays borrowed for the duration of the call, so the method which is called
+    /// (either the override or the Rust implementation) cannot take `&mut self`; doing so fails
+    /// with `RuntimeError: Already borrowed`.
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// #[pyclass(subclass)]
+    /// struct Shape;
+    ///
+    /// #[pymethods]
+    /// impl Shape {
+    ///     #[new]
+    ///     fn new() -> Self {
+    ///         Shape
+    ///     }
+    ///
+    ///     fn scale(&self, factor: f64) -> f64 {
+    ///         factor
+    ///     }
+    ///
+    ///     fn area(slf: PyRef<'_, Self>) -> PyResult<f64> {
+    ///         slf.call_method_on_self("scale", (2.0,))?.extract()
+    ///     }
+    /// }
+    /// # Python::with_gil(|py| {
+    /// #     let cls = py.get_type::<Shape>();
+    /// #     pyo3::py_run!(py, cls, r#"
+    /// #         class Big(cls):
+    /// #             def scale(self, factor):
+    /// #                 return 10 * factor
+    /// #         assert cls().area() == 2.0
+    /// #         assert Big().area() == 20.0
+    /// #     "#);
+    /// # });
+    /// ```
+    pub fn call_method_on_self<N, A>(&self, name: N, args: A) -> PyResult<&'p PyAny>
+    where
+        N: IntoPy<Py<PyString>>,
+        A: IntoPy<Py<PyTuple>>,
+    {
+        self.inner.call_method1(name, args)
+    }
 }
 
 impl<'p, T, U> AsRef<U> for PyRef<'p, T>
@@ -773,12 +875,16 @@ impl<T: PyClass> IntoPy<PyObject> for PyRef<'_, T> {
     }
 }
 
+into_pyobject_via_into_py!([T: PyClass] PyRef<'_, T>);
+
 impl<T: PyClass> IntoPy<PyObject> for &'_ PyRef<'_, T> {
     fn into_py(self, py: Python<'_>) -> PyObject {
         self.inner.into_py(py)
     }
 }
 
+into_pyobject_via_into_py!([T: PyClass] &'_ PyRef<'_, T>);
+
 impl<'a, T: PyClass> std::convert::TryFrom<&'a PyCell<T>> for crate::PyRef<'a, T> {
     type Error = PyBorrowError;
     fn try_from(cell: &'a crate::PyCell<T>) -> Result<Self, Self::Error> {
@@ -810,6 +916,15 @@ impl<'p, T: PyClass<Frozen = False>> PyRefMut<'p, T> {
     pub fn py(&self) -> Python<'p> {
         self.inner.py()
     }
+
+    /// Returns the Python object which holds the borrowed value.
+    ///
+    /// See [`PyRef::as_pyobject`]. Note that the value stays mutably borrowed, so Python code
+    /// called through the object cannot borrow it again until this `PyRefMut` is dropped.
+    #[inline]
+    pub fn as_pyobject(&self) -> &'p PyCell<T> {
+        self.inner
+    }
 }
 
 impl<'p, T, U> AsRef<U> for PyRefMut<'p, T>
@@ -903,12 +1018,16 @@ impl<T: PyClass<Frozen = False>> IntoPy<PyObject> for PyRefMut<'_, T> {
     }
 }
 
+into_pyobject_via_into_py!([T: PyClass<Frozen = False>] PyRefMut<'_, T>);
+
 impl<T: PyClass<Frozen = False>> IntoPy<PyObject> for &'_ PyRefMut<'_, T> {
     fn into_py(self, py: Python<'_>) -> PyObject {
         self.inner.into_py(py)
     }
 }
 
+into_pyobject_via_into_py!([T: PyClass<Frozen = False>] &'_ PyRefMut<'_, T>);
+
 unsafe impl<'a, T: PyClass<Frozen = False>> AsPyPointer for PyRefMut<'a, T> {
     fn as_ptr(&self) -> *mut ffi::PyObject {
         self.inner.as_ptr()
@@ -983,6 +1102,8 @@ impl From<PyBorrowMutError> for PyErr {
 #[doc(hidden)]
 pub trait PyCellLayout<T>: PyLayout<T> {
     fn ensure_threadsafe(&self);
+    /// Like `ensure_threadsafe`, but raises `RuntimeError` instead of panicking.
+    fn try_ensure_threadsafe(&self) -> PyResult<()>;
     fn check_threadsafe(&self) -> Result<(), PyBorrowError>;
     /// Implementation of tp_dealloc.
     /// # Safety
@@ -997,6 +1118,9 @@ where
     T: PyTypeInfo,
 {
     fn ensure_threadsafe(&self) {}
+    fn try_ensure_threadsafe(&self) -> PyResult<()> {
+        Ok(())
+    }
     fn check_threadsafe(&self) -> Result<(), PyBorrowError> {
         Ok(())
     }
@@ -1037,6 +1161,10 @@ where
         self.contents.thread_checker.ensure();
         self.ob_base.ensure_threadsafe();
     }
+    fn try_ensure_threadsafe(&self) -> PyResult<()> {
+        self.contents.thread_checker.try_ensure()?;
+        self.ob_base.try_ensure_threadsafe()
+    }
     fn check_threadsafe(&self) -> Result<(), PyBorrowError> {
         if !self.contents.thread_checker.check() {
             return Err(PyBorrowError { _private: () });
@@ -1,8 +1,11 @@
 #![allow(missing_docs)]
 //! Crate-private implementation of pycell
 
+#[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
 use std::cell::Cell;
 use std::marker::PhantomData;
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::impl_::pyclass::{PyClassBaseType, PyClassImpl};
 use crate::PyCell;
@@ -46,22 +49,108 @@ impl<M: PyClassMutability> PyClassMutability for ExtendsMutableAncestor<M> {
     type MutableChild = ExtendsMutableAncestor<MutableClass>;
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct BorrowFlag(usize);
+#[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
+struct BorrowFlag(Cell<usize>);
 
+// Atomic, as on the free-threaded build of Python several threads can borrow the same object
+// at the same time.
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+struct BorrowFlag(AtomicUsize);
+
+impl BorrowFlag {
+    const UNUSED: usize = 0;
+    const HAS_MUTABLE_BORROW: usize = usize::max_value();
+}
+
+#[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
 impl BorrowFlag {
-    pub(crate) const UNUSED: BorrowFlag = BorrowFlag(0);
-    const HAS_MUTABLE_BORROW: BorrowFlag = BorrowFlag(usize::max_value());
-    const fn increment(self) -> Self {
-        Self(self.0 + 1)
+    fn new() -> Self {
+        Self(Cell::new(BorrowFlag::UNUSED))
+    }
+
+    fn has_mutable_borrow(&self) -> bool {
+        self.0.get() == BorrowFlag::HAS_MUTABLE_BORROW
     }
-    const fn decrement(self) -> Self {
-        Self(self.0 - 1)
+
+    fn increment(&self) -> Result<(), PyBorrowError> {
+        let value = self.0.get();
+        if value != BorrowFlag::HAS_MUTABLE_BORROW {
+            self.0.set(value + 1);
+            Ok(())
+        } else {
+            Err(PyBorrowError { _private: () })
+        }
+    }
+
+    fn decrement(&self) {
+        self.0.set(self.0.get() - 1)
+    }
+
+    fn set_mutable_borrow(&self) -> Result<(), PyBorrowMutError> {
+        if self.0.get() == BorrowFlag::UNUSED {
+            self.0.set(BorrowFlag::HAS_MUTABLE_BORROW);
+            Ok(())
+        } else {
+            Err(PyBorrowMutError { _private: () })
+        }
+    }
+
+    fn clear_mutable_borrow(&self) {
+        self.0.set(BorrowFlag::UNUSED)
+    }
+}
+
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+impl BorrowFlag {
+    fn new() -> Self {
+        Self(AtomicUsize::new(BorrowFlag::UNUSED))
+    }
+
+    fn has_mutable_borrow(&self) -> bool {
+        self.0.load(Ordering::Acquire) == BorrowFlag::HAS_MUTABLE_BORROW
+    }
+
+    fn increment(&self) -> Result<(), PyBorrowError> {
+        let mut value = self.0.load(Ordering::Relaxed);
+        loop {
+            if value == BorrowFlag::HAS_MUTABLE_BORROW {
+                return Err(PyBorrowError { _private: () });
+            }
+            match self.0.compare_exchange_weak(
+                value,
+                value + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Ok(()),
+                Err(changed) => value = changed,
+            }
+        }
+    }
+
+    fn decrement(&self) {
+        self.0.fetch_sub(1, Ordering::Release);
+    }
+
+    fn set_mutable_borrow(&self) -> Result<(), PyBorrowMutError> {
+        match self.0.compare_exchange(
+            BorrowFlag::UNUSED,
+            BorrowFlag::HAS_MUTABLE_BORROW,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(PyBorrowMutError { _private: () }),
+        }
+    }
+
+    fn clear_mutable_borrow(&self) {
+        self.0.store(BorrowFlag::UNUSED, Ordering::Release)
     }
 }
 
 pub struct EmptySlot(());
-pub struct BorrowChecker(Cell<BorrowFlag>);
+pub struct BorrowChecker(BorrowFlag);
 
 pub trait PyClassBorrowChecker {
     /// Initial value for self
@@ -113,22 +202,15 @@ impl PyClassBorrowChecker for EmptySlot {
 impl PyClassBorrowChecker for BorrowChecker {
     #[inline]
     fn new() -> Self {
-        Self(Cell::new(BorrowFlag::UNUSED))
+        Self(BorrowFlag::new())
     }
 
     fn try_borrow(&self) -> Result<(), PyBorrowError> {
-        let flag = self.0.get();
-        if flag != BorrowFlag::HAS_MUTABLE_BORROW {
-            self.0.set(flag.increment());
-            Ok(())
-        } else {
-            Err(PyBorrowError { _private: () })
-        }
+        self.0.increment()
     }
 
     fn try_borrow_unguarded(&self) -> Result<(), PyBorrowError> {
-        let flag = self.0.get();
-        if flag != BorrowFlag::HAS_MUTABLE_BORROW {
+        if !self.0.has_mutable_borrow() {
             Ok(())
         } else {
             Err(PyBorrowError { _private: () })
@@ -136,22 +218,15 @@ impl PyClassBorrowChecker for BorrowChecker {
     }
 
     fn release_borrow(&self) {
-        let flag = self.0.get();
-        self.0.set(flag.decrement())
+        self.0.decrement()
     }
 
     fn try_borrow_mut(&self) -> Result<(), PyBorrowMutError> {
-        let flag = self.0.get();
-        if flag == BorrowFlag::UNUSED {
-            self.0.set(BorrowFlag::HAS_MUTABLE_BORROW);
-            Ok(())
-        } else {
-            Err(PyBorrowMutError { _private: () })
-        }
+        self.0.set_mutable_borrow()
     }
 
     fn release_borrow_mut(&self) {
-        self.0.set(BorrowFlag::UNUSED)
+        self.0.clear_mutable_borrow()
     }
 }
 
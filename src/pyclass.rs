@@ -5,11 +5,18 @@ use crate::{
 };
 use std::{cmp::Ordering, os::raw::c_int};
 
+pub(crate) mod array;
 mod create_type_object;
 mod gc;
+mod init_hook;
+pub(crate) mod len;
 
+pub use self::array::{PyArrayData, PyArrayElement};
 pub(crate) use self::create_type_object::{create_type_object, PyClassTypeObject};
 pub use self::gc::{PyTraverseError, PyVisit};
+pub(crate) use self::init_hook::type_init_hook;
+pub use self::init_hook::{set_type_init_hook, TypeInitHook};
+pub use self::len::PyLen;
 
 /// Types that can be used as Python classes.
 ///
@@ -0,0 +1,213 @@
+use crate::exceptions::PyValueError;
+use crate::types::{any::PyAnyMethods, dict::PyDictMethods, PyDict, PyTuple};
+use crate::{Bound, PyAny, PyObject, PyResult, Python};
+
+/// Numeric types which can be the elements of an array exposed by `#[pyclass(array)]`.
+///
+/// # Safety
+///
+/// `TYPESTR` must be the NumPy [array interface] type string of a type with the same in-memory
+/// representation.
+///
+/// [array interface]: https://numpy.org/doc/stable/reference/arrays.interface.html
+pub unsafe trait PyArrayElement: Copy {
+    /// The type string of the element, such as `"<f8"` for a little-endian `f64`.
+    const TYPESTR: &'static str;
+}
+
+macro_rules! impl_array_element {
+    ($($t:ty => $kind:literal $size:literal),* $(,)?) => {
+        $(
+            unsafe impl PyArrayElement for $t {
+                const TYPESTR: &'static str = if std::mem::size_of::<$t>() == 1 {
+                    concat!("|", $kind, $size)
+                } else if cfg!(target_endian = "little") {
+                    concat!("<", $kind, $size)
+                } else {
+                    concat!(">", $kind, $size)
+                };
+            }
+        )*
+    };
+}
+
+impl_array_element!(
+    bool => "b" "1",
+    i8 => "i" "1",
+    i16 => "i" "2",
+    i32 => "i" "4",
+    i64 => "i" "8",
+    u8 => "u" "1",
+    u16 => "u" "2",
+    u32 => "u" "4",
+    u64 => "u" "8",
+    f32 => "f" "4",
+    f64 => "f" "8",
+);
+
+/// The contiguous numeric data of a Rust-backed array, used by `#[pyclass(array)]`.
+///
+/// `#[pyclass(array)]` generates the `__array_interface__` property and the `__array__` method
+/// from this trait, so that `numpy.asarray(obj)` views the data without copying it:
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::PyArrayData;
+///
+/// #[pyclass(array, frozen)]
+/// struct Matrix {
+///     rows: usize,
+///     values: Box<[f64]>,
+/// }
+///
+/// unsafe impl PyArrayData for Matrix {
+///     type Element = f64;
+///
+///     fn array_data(&self) -> &[f64] {
+///         &self.values
+///     }
+///
+///     fn array_shape(&self) -> Vec<usize> {
+///         vec![self.rows, self.values.len() / self.rows]
+///     }
+/// }
+/// #
+/// # Python::with_gil(|py| {
+/// #     let values = vec![1.0, 2.0, 3.0, 4.0].into_boxed_slice();
+/// #     let matrix = Py::new(py, Matrix { rows: 2, values }).unwrap();
+/// #     pyo3::py_run!(py, matrix, "assert matrix.__array_interface__['shape'] == (2, 2)");
+/// # });
+/// ```
+///
+/// The view is read-only, and keeps the object alive for as long as it is in use.
+///
+/// # Safety
+///
+/// NumPy reads the memory returned by `array_data` for as long as it holds a view of the object,
+/// without borrowing the object. The memory must therefore stay valid, and not be reallocated,
+/// for the whole lifetime of the object: for example, the data of a `Vec` which a `&mut self`
+/// method can push to does not qualify, while a `Box<[T]>` of a `frozen` class does.
+pub unsafe trait PyArrayData {
+    /// The type of the elements.
+    type Element: PyArrayElement;
+
+    /// Returns the elements, in C order.
+    fn array_data(&self) -> &[Self::Element];
+
+    /// Returns the length of the array in each dimension.
+    ///
+    /// Defaults to a one-dimensional array of all the elements.
+    fn array_shape(&self) -> Vec<usize> {
+        vec![self.array_data().len()]
+    }
+}
+
+/// Implementation of `__array_interface__` for `#[pyclass(array)]`.
+pub fn py_array_interface<T: PyArrayData + ?Sized>(
+    py: Python<'_>,
+    container: &T,
+) -> PyResult<PyObject> {
+    let data = container.array_data();
+    let shape = container.array_shape();
+    if shape.iter().product::<usize>() != data.len() {
+        return Err(PyValueError::new_err(format!(
+            "array of {} elements does not have shape {:?}",
+            data.len(),
+            shape
+        )));
+    }
+    let interface = PyDict::new_bound(py);
+    interface.set_item("version", 3)?;
+    interface.set_item("typestr", T::Element::TYPESTR)?;
+    interface.set_item("shape", PyTuple::new_bound(py, shape))?;
+    // The data is exposed as read-only, as it is only borrowed immutably.
+    interface.set_item("data", (data.as_ptr() as usize, true))?;
+    Ok(interface.into_any().unbind())
+}
+
+/// Implementation of `__array__` for `#[pyclass(array)]`.
+///
+/// Converts the object through its `__array_interface__`, copying the data only if `copy` is
+/// true or `dtype` requires it.
+pub fn py_array<'py>(
+    obj: &Bound<'py, PyAny>,
+    dtype: Option<&Bound<'py, PyAny>>,
+    copy: Option<bool>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let numpy = obj.py().import_bound(crate::intern!(obj.py(), "numpy"))?;
+    let function = if copy == Some(true) {
+        "array"
+    } else {
+        "asarray"
+    };
+    let kwargs = PyDict::new_bound(obj.py());
+    kwargs.set_item("dtype", dtype)?;
+    numpy.call_method(function, (obj,), Some(&kwargs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{py_array_interface, PyArrayData, PyArrayElement};
+    use crate::types::{any::PyAnyMethods, dict::PyDictMethods, PyDict};
+    use crate::Python;
+
+    struct Grid {
+        shape: Vec<usize>,
+        cells: Vec<u16>,
+    }
+
+    unsafe impl PyArrayData for Grid {
+        type Element = u16;
+
+        fn array_data(&self) -> &[u16] {
+            &self.cells
+        }
+
+        fn array_shape(&self) -> Vec<usize> {
+            self.shape.clone()
+        }
+    }
+
+    #[test]
+    fn test_typestr() {
+        assert_eq!(u8::TYPESTR, "|u1");
+        assert_eq!(bool::TYPESTR, "|b1");
+        if cfg!(target_endian = "little") {
+            assert_eq!(f64::TYPESTR, "<f8");
+        } else {
+            assert_eq!(f64::TYPESTR, ">f8");
+        }
+    }
+
+    #[test]
+    fn test_array_interface() {
+        Python::with_gil(|py| {
+            let grid = Grid {
+                shape: vec![2, 3],
+                cells: vec![0; 6],
+            };
+            let interface = py_array_interface(py, &grid).unwrap();
+            let interface = interface.bind(py).downcast::<PyDict>().unwrap();
+            let shape: (usize, usize) = interface
+                .get_item("shape")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(shape, (2, 3));
+            let data: (usize, bool) = interface
+                .get_item("data")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(data, (grid.cells.as_ptr() as usize, true));
+
+            let bad = Grid {
+                shape: vec![4],
+                cells: vec![0; 6],
+            };
+            assert!(py_array_interface(py, &bad).is_err());
+        });
+    }
+}
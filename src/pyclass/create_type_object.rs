@@ -1,18 +1,22 @@
 use pyo3_ffi::PyType_IS_GC;
 
 use crate::{
-    exceptions::PyTypeError,
+    exceptions::{PyAttributeError, PyTypeError},
     ffi,
     impl_::pyclass::{
         assign_sequence_item_from_mapping, get_sequence_item_from_mapping, tp_dealloc,
         tp_dealloc_with_gc, PyClassItemsIter,
     },
     impl_::{
-        pymethods::{get_doc, get_name, Getter, Setter},
+        pymethods::{get_doc, get_name, Deleter, Getter, Setter},
         trampoline::trampoline,
     },
-    types::PyType,
-    Py, PyCell, PyClass, PyGetterDef, PyMethodDefType, PyResult, PySetterDef, PyTypeInfo, Python,
+    types::{
+        any::PyAnyMethods, capsule::PyCapsuleMethods, dict::PyDictMethods, PyCapsule, PyDict,
+        PyType,
+    },
+    Bound, Py, PyCell, PyClass, PyDeleterDef, PyGetterDef, PyMethodDefType, PyResult, PySetterDef,
+    PyTypeInfo, Python,
 };
 use std::{
     borrow::Cow,
@@ -33,6 +37,15 @@ pub(crate) fn create_type_object<T>(py: Python<'_>) -> PyResult<PyClassTypeObjec
 where
     T: PyClass,
 {
+    if let Some(shared) = T::SHARED {
+        if let Some(type_object) = lookup_shared_type(py, shared, shared_type_fingerprint::<T>())? {
+            return Ok(PyClassTypeObject {
+                type_object,
+                getset_destructors: Vec::new(),
+            });
+        }
+    }
+
     // Written this way to monomorphize the majority of the logic.
     #[allow(clippy::too_many_arguments)]
     unsafe fn inner(
@@ -45,11 +58,15 @@ where
         doc: &'static CStr,
         dict_offset: Option<ffi::Py_ssize_t>,
         weaklist_offset: Option<ffi::Py_ssize_t>,
+        vectorcall_offset: Option<ffi::Py_ssize_t>,
         is_basetype: bool,
+        is_immutable_type: bool,
+        is_immortal: bool,
         items_iter: PyClassItemsIter,
         name: &'static str,
         module: Option<&'static str>,
         size_of: usize,
+        metaclass: Option<*mut ffi::PyTypeObject>,
     ) -> PyResult<PyClassTypeObject> {
         PyTypeBuilder {
             slots: Vec::new(),
@@ -57,6 +74,7 @@ where
             getset_builders: HashMap::new(),
             cleanup: Vec::new(),
             tp_base: base,
+            metaclass,
             tp_dealloc: dealloc,
             tp_dealloc_with_gc: dealloc_with_gc,
             is_mapping,
@@ -68,18 +86,20 @@ where
             has_traverse: false,
             has_clear: false,
             has_dict: false,
+            is_immortal,
             class_flags: 0,
             #[cfg(all(not(Py_3_9), not(Py_LIMITED_API)))]
             buffer_procs: Default::default(),
         }
         .type_doc(doc)
-        .offsets(dict_offset, weaklist_offset)
+        .offsets(dict_offset, weaklist_offset, vectorcall_offset)
         .set_is_basetype(is_basetype)
+        .set_is_immutable_type(is_immutable_type)
         .class_items(items_iter)
         .build(py, name, module, size_of)
     }
 
-    unsafe {
+    let mut type_object = unsafe {
         inner(
             py,
             T::BaseType::type_object_raw(py),
@@ -90,13 +110,154 @@ where
             T::doc(py)?,
             T::dict_offset(),
             T::weaklist_offset(),
+            // the offset is only used if the class has a `__call__` implementation to store there
+            T::vectorcall().and(T::vectorcall_offset()),
             T::IS_BASETYPE,
+            T::IS_IMMUTABLE_TYPE,
+            T::IS_IMMORTAL,
             T::items_iter(),
             T::NAME,
             T::MODULE,
             std::mem::size_of::<PyCell<T>>(),
-        )
+            T::metaclass_type_object(py),
+        )?
+    };
+
+    if let Some(abc) = T::ABC {
+        register_with_abc(py, type_object.type_object.as_ref(py), abc)?;
+    }
+
+    if let Some(shared) = T::SHARED {
+        // If another module published the type first, use theirs, so that there is only one.
+        type_object.type_object = publish_shared_type(
+            py,
+            shared,
+            &type_object.type_object,
+            shared_type_fingerprint::<T>(),
+        )?;
+    }
+
+    Ok(type_object)
+}
+
+/// Registers `type_object` as a virtual subclass of the ABC with the qualified name `abc`.
+fn register_with_abc(py: Python<'_>, type_object: &PyType, abc: &str) -> PyResult<()> {
+    let (module, name) = abc
+        .rsplit_once('.')
+        .expect("`#[pyclass(abc = ...)]` is validated to be a qualified name");
+    py.import_bound(module)?
+        .getattr(name)?
+        .call_method1(intern!(py, "register"), (type_object,))?;
+    Ok(())
+}
+
+/// Name of the attribute of the `sys` module holding the registry of shared type objects.
+const SHARED_TYPES_ATTR: &str = "_pyo3_shared_types";
+
+/// Returns the process-wide registry which maps the names of `#[pyclass(shared = ...)]` classes
+/// to capsules holding their type objects, creating it if needed.
+///
+/// The registry lives in the `sys` module, so that all extension modules built with PyO3 in the
+/// same interpreter find the same one.
+fn shared_types_registry(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let sys = py.import_bound("sys")?;
+    match sys.getattr(intern!(py, SHARED_TYPES_ATTR)) {
+        Ok(registry) => Ok(registry.downcast_into::<PyDict>()?),
+        Err(err) if err.is_instance_of::<PyAttributeError>(py) => {
+            let registry = PyDict::new_bound(py);
+            sys.setattr(intern!(py, SHARED_TYPES_ATTR), &registry)?;
+            Ok(registry)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Contents of the capsule which publishes a shared type object.
+///
+/// The capsule may have been created by a module built with another version of PyO3, so the
+/// layout is fixed and the fingerprint comes first: nothing else is read unless it matches.
+#[repr(C)]
+struct SharedType {
+    fingerprint: u64,
+    type_object: Py<PyType>,
+}
+
+/// Fingerprint of the PyO3 version, the rustc version, the Rust type name and the layout of
+/// instances of `T`, which must match between all modules sharing the type object of `T`.
+///
+/// This cannot detect every difference between the definitions of `T` in two modules, e.g. fields
+/// of the same size in a different order, so the definitions must be identical.
+fn shared_type_fingerprint<T: PyClass>() -> u64 {
+    let size = std::mem::size_of::<PyCell<T>>() as u64;
+    let align = std::mem::align_of::<PyCell<T>>() as u64;
+    // FNV-1a, which unlike `DefaultHasher` gives the same result in every build
+    [
+        env!("CARGO_PKG_VERSION"),
+        env!("PYO3_RUSTC_VERSION"),
+        std::any::type_name::<T>(),
+    ]
+    .iter()
+    .flat_map(|s| s.as_bytes().iter().chain(&[0]))
+    .chain(&size.to_le_bytes())
+    .chain(&align.to_le_bytes())
+    .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Looks up the type object published under `name` by another module, if any.
+fn lookup_shared_type(
+    py: Python<'_>,
+    name: &str,
+    fingerprint: u64,
+) -> PyResult<Option<Py<PyType>>> {
+    match shared_types_registry(py)?.get_item(name)? {
+        Some(capsule) => shared_type_from_capsule(capsule.downcast()?, name, fingerprint).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Publishes `type_object` under `name`, returning the type object which was published first.
+fn publish_shared_type(
+    py: Python<'_>,
+    name: &str,
+    type_object: &Py<PyType>,
+    fingerprint: u64,
+) -> PyResult<Py<PyType>> {
+    let shared_type = SharedType {
+        fingerprint,
+        type_object: type_object.clone_ref(py),
+    };
+    let capsule = PyCapsule::new_bound(py, shared_type, Some(CString::new(name)?))?;
+    let published =
+        shared_types_registry(py)?.call_method1(intern!(py, "setdefault"), (name, capsule))?;
+    shared_type_from_capsule(published.downcast()?, name, fingerprint)
+}
+
+/// Extracts the type object from a capsule in the shared type registry, checking that the
+/// capsule has the expected name, and that it was published with the same fingerprint.
+fn shared_type_from_capsule(
+    capsule: &Bound<'_, PyCapsule>,
+    name: &str,
+    fingerprint: u64,
+) -> PyResult<Py<PyType>> {
+    if capsule.name()?.and_then(|name| name.to_str().ok()) != Some(name) {
+        return Err(PyTypeError::new_err(format!(
+            "capsule for shared class `{}` has an unexpected name",
+            name
+        )));
+    }
+    // Safety: capsules with this name are only created by `publish_shared_type`, and the
+    // fingerprint is checked before the rest of the contents is used.
+    let shared_type = unsafe { capsule.reference::<SharedType>() };
+    if shared_type.fingerprint != fingerprint {
+        return Err(PyTypeError::new_err(format!(
+            "shared class `{}` was published by a module built with a different PyO3 or rustc \
+             version, or with a different definition of the class",
+            name
+        )));
     }
+    Ok(shared_type.type_object.clone_ref(capsule.py()))
 }
 
 type PyTypeBuilderCleanup = Box<dyn Fn(&PyTypeBuilder, *mut ffi::PyTypeObject)>;
@@ -110,6 +271,7 @@ struct PyTypeBuilder {
     /// except for that it does and we have tests.
     cleanup: Vec<PyTypeBuilderCleanup>,
     tp_base: *mut ffi::PyTypeObject,
+    metaclass: Option<*mut ffi::PyTypeObject>,
     tp_dealloc: ffi::destructor,
     tp_dealloc_with_gc: ffi::destructor,
     is_mapping: bool,
@@ -121,6 +283,7 @@ struct PyTypeBuilder {
     has_traverse: bool,
     has_clear: bool,
     has_dict: bool,
+    is_immortal: bool,
     class_flags: c_ulong,
     // Before Python 3.9, need to patch in buffer methods manually (they don't work in slots)
     #[cfg(all(not(Py_3_9), not(Py_LIMITED_API)))]
@@ -141,6 +304,9 @@ impl PyTypeBuilder {
                 self.class_flags |= ffi::Py_TPFLAGS_HAVE_GC;
             }
             ffi::Py_tp_clear => self.has_clear = true,
+            // Before Python 3.8, `tp_finalize` is ignored unless this flag is set
+            #[cfg(not(Py_3_8))]
+            ffi::Py_tp_finalize => self.class_flags |= ffi::Py_TPFLAGS_HAVE_FINALIZE,
             #[cfg(all(not(Py_3_9), not(Py_LIMITED_API)))]
             ffi::Py_bf_getbuffer => {
                 // Safety: slot.pfunc is a valid function pointer
@@ -184,13 +350,16 @@ impl PyTypeBuilder {
                     .or_default()
                     .add_setter(setter);
             }
+            PyMethodDefType::Deleter(deleter) => {
+                self.getset_builders
+                    .entry(deleter.name)
+                    .or_default()
+                    .add_deleter(deleter);
+            }
             PyMethodDefType::Method(def)
             | PyMethodDefType::Class(def)
             | PyMethodDefType::Static(def) => {
-                let (def, destructor) = def.as_method_def().unwrap();
-                // FIXME: stop leaking destructor
-                std::mem::forget(destructor);
-                self.method_defs.push(def);
+                self.method_defs.push(def.as_method_def());
             }
             // These class attributes are added after the type gets created by LazyStaticType
             PyMethodDefType::ClassAttribute(_) => {}
@@ -198,6 +367,9 @@ impl PyTypeBuilder {
     }
 
     fn finalize_methods_and_properties(&mut self) -> PyResult<Vec<GetSetDefDestructor>> {
+        // The tables themselves are built here rather than by the macros: their entries come from
+        // the `#[pyclass]` and every `#[pymethods]` block, and the getter, setter and deleter of
+        // one property can be defined in different blocks.
         let method_defs: Vec<pyo3_ffi::PyMethodDef> = std::mem::take(&mut self.method_defs);
         // Safety: Py_tp_methods expects a raw vec of PyMethodDef
         unsafe { self.push_raw_vec_slot(ffi::Py_tp_methods, method_defs) };
@@ -269,6 +441,17 @@ impl PyTypeBuilder {
         self
     }
 
+    #[allow(unused_variables)]
+    fn set_is_immutable_type(mut self, is_immutable_type: bool) -> Self {
+        // Immutable types are only supported from Python 3.10 without the limited API;
+        // `#[pyclass]` rejects the option elsewhere.
+        #[cfg(all(Py_3_10, not(Py_LIMITED_API)))]
+        if is_immutable_type {
+            self.class_flags |= ffi::Py_TPFLAGS_IMMUTABLETYPE;
+        }
+        self
+    }
+
     /// # Safety
     /// All slots in the PyClassItemsIter should be correct
     unsafe fn class_items(mut self, iter: PyClassItemsIter) -> Self {
@@ -311,9 +494,16 @@ impl PyTypeBuilder {
         mut self,
         dict_offset: Option<ffi::Py_ssize_t>,
         #[allow(unused_variables)] weaklist_offset: Option<ffi::Py_ssize_t>,
+        #[allow(unused_variables)] vectorcall_offset: Option<ffi::Py_ssize_t>,
     ) -> Self {
         self.has_dict = dict_offset.is_some();
 
+        // vectorcall support, with the function pointer stored in each instance
+        #[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
+        if vectorcall_offset.is_some() {
+            self.class_flags |= ffi::Py_TPFLAGS_HAVE_VECTORCALL;
+        }
+
         #[cfg(Py_3_9)]
         {
             #[inline(always)]
@@ -342,6 +532,12 @@ impl PyTypeBuilder {
                 members.push(offset_def("__weaklistoffset__\0", weaklist_offset));
             }
 
+            // vectorcall support
+            #[cfg(not(Py_LIMITED_API))]
+            if let Some(vectorcall_offset) = vectorcall_offset {
+                members.push(offset_def("__vectorcalloffset__\0", vectorcall_offset));
+            }
+
             // Safety: Py_tp_members expects a raw vec of PyMemberDef
             unsafe { self.push_raw_vec_slot(ffi::Py_tp_members, members) };
         }
@@ -363,6 +559,11 @@ impl PyTypeBuilder {
                     if let Some(weaklist_offset) = weaklist_offset {
                         (*type_object).tp_weaklistoffset = weaklist_offset;
                     }
+
+                    #[cfg(Py_3_8)]
+                    if let Some(vectorcall_offset) = vectorcall_offset {
+                        (*type_object).tp_vectorcall_offset = vectorcall_offset;
+                    }
                 }));
         }
         self
@@ -402,6 +603,17 @@ impl PyTypeBuilder {
             )));
         }
 
+        // Allow `match` statements to recognise the class as a sequence or mapping
+        #[cfg(all(Py_3_10, not(Py_LIMITED_API)))]
+        {
+            if self.is_sequence {
+                self.class_flags |= ffi::Py_TPFLAGS_SEQUENCE;
+            }
+            if self.is_mapping {
+                self.class_flags |= ffi::Py_TPFLAGS_MAPPING;
+            }
+        }
+
         // For sequences, implement sq_length instead of mp_length
         if self.is_sequence {
             for slot in &mut self.slots {
@@ -416,6 +628,10 @@ impl PyTypeBuilder {
         unsafe { self.push_slot(0, ptr::null_mut::<c_void>()) }
 
         let class_name = py_class_qualified_name(module_name, name)?;
+        // `Py_TPFLAGS_DEFAULT` includes `Py_TPFLAGS_HAVE_VERSION_TAG` before Python 3.10, where
+        // it is not implied, so that CPython's type attribute cache serves lookups of methods
+        // and class attributes. The type's dictionary must therefore only be modified through
+        // `PyObject_SetAttr` or followed by `PyType_Modified`, which invalidate the cache.
         let mut spec = ffi::PyType_Spec {
             name: class_name.as_ptr() as _,
             basicsize: basicsize as c_int,
@@ -428,8 +644,9 @@ impl PyTypeBuilder {
         };
 
         // Safety: We've correctly setup the PyType_Spec at this point
-        let type_object: Py<PyType> =
-            unsafe { Py::from_owned_ptr_or_err(py, ffi::PyType_FromSpec(&mut spec))? };
+        let type_object: Py<PyType> = unsafe {
+            Py::from_owned_ptr_or_err(py, create_type(&mut spec, self.metaclass, name)?)?
+        };
 
         #[cfg(not(Py_3_11))]
         bpo_45315_workaround(py, class_name);
@@ -438,6 +655,11 @@ impl PyTypeBuilder {
             cleanup(&self, type_object.as_ref(py).as_type_ptr());
         }
 
+        if self.is_immortal {
+            // Safety: the type object was just created, and is kept alive by `type_object`.
+            unsafe { make_immortal(type_object.as_ptr()) };
+        }
+
         Ok(PyClassTypeObject {
             type_object,
             getset_destructors,
@@ -445,6 +667,118 @@ impl PyTypeBuilder {
     }
 }
 
+/// Makes `object` immortal, so that its reference count is never modified again and it is never
+/// deallocated.
+///
+/// This writes the reference count directly, as CPython does not offer an API for it, so it is
+/// only done where the layout of the reference count is known: on CPython 3.12 and newer with the
+/// GIL, when not using the limited API. `#[pyclass]` rejects the `immortal` option elsewhere.
+#[allow(unused_variables)]
+unsafe fn make_immortal(object: *mut ffi::PyObject) {
+    #[cfg(all(
+        Py_3_12,
+        not(any(Py_LIMITED_API, PyPy, py_sys_config = "Py_GIL_DISABLED"))
+    ))]
+    {
+        (*object).ob_refcnt.ob_refcnt = ffi::_Py_IMMORTAL_REFCNT;
+    }
+}
+
+/// Creates the type object from `spec`, using `metaclass` as its type if given.
+///
+/// The type object is not created by calling the metaclass, so metaclasses which override
+/// `__new__` or `__init__` are rejected, as those would never run. From Python 3.12 the type is
+/// created with `PyType_FromMetaclass`; before that, it is created as usual and its `ob_type`
+/// replaced afterwards, which is sound as long as the metaclass does not change the layout of
+/// `type`.
+unsafe fn create_type(
+    spec: &mut ffi::PyType_Spec,
+    metaclass: Option<*mut ffi::PyTypeObject>,
+    name: &str,
+) -> PyResult<*mut ffi::PyObject> {
+    let metaclass = match metaclass {
+        Some(metaclass) => metaclass,
+        None => return Ok(ffi::PyType_FromSpec(spec)),
+    };
+
+    #[cfg(all(Py_LIMITED_API, not(Py_3_12)))]
+    {
+        let _ = metaclass;
+        Err(PyTypeError::new_err(format!(
+            "`#[pyclass]` {} cannot use a metaclass with the limited API before Python 3.12",
+            name
+        )))
+    }
+
+    #[cfg(any(not(Py_LIMITED_API), Py_3_12))]
+    {
+        let type_type = ptr::addr_of_mut!(ffi::PyType_Type);
+        if ffi::PyType_IsSubtype(metaclass, type_type) == 0 {
+            return Err(PyTypeError::new_err(format!(
+                "metaclass of `#[pyclass]` {} must be a subclass of `type`",
+                name
+            )));
+        }
+        if new_and_init_slots(metaclass) != new_and_init_slots(type_type) {
+            return Err(PyTypeError::new_err(format!(
+                "metaclass of `#[pyclass]` {} must not override `__new__` or `__init__`",
+                name
+            )));
+        }
+
+        #[cfg(Py_3_12)]
+        {
+            Ok(ffi::PyType_FromMetaclass(
+                metaclass,
+                ptr::null_mut(),
+                spec,
+                ptr::null_mut(),
+            ))
+        }
+
+        #[cfg(not(Py_3_12))]
+        {
+            if (*metaclass).tp_basicsize != (*type_type).tp_basicsize
+                || (*metaclass).tp_itemsize != (*type_type).tp_itemsize
+            {
+                return Err(PyTypeError::new_err(format!(
+                    "metaclass of `#[pyclass]` {} must not change the layout of `type`",
+                    name
+                )));
+            }
+
+            let type_object = ffi::PyType_FromSpec(spec);
+            if !type_object.is_null() {
+                let previous = ffi::Py_TYPE(type_object);
+                ffi::Py_INCREF(metaclass.cast());
+                (*type_object).ob_type = metaclass;
+                ffi::Py_DECREF(previous.cast());
+            }
+            Ok(type_object)
+        }
+    }
+}
+
+/// The `tp_new` and `tp_init` slots of `ty`, to compare those of a metaclass with those of `type`.
+#[cfg(any(not(Py_LIMITED_API), Py_3_12))]
+unsafe fn new_and_init_slots(ty: *mut ffi::PyTypeObject) -> (*mut c_void, *mut c_void) {
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        (
+            (*ty).tp_new.map_or(ptr::null_mut(), |f| f as *mut c_void),
+            (*ty).tp_init.map_or(ptr::null_mut(), |f| f as *mut c_void),
+        )
+    }
+
+    #[cfg(Py_LIMITED_API)]
+    {
+        (
+            ffi::PyType_GetSlot(ty, ffi::Py_tp_new),
+            ffi::PyType_GetSlot(ty, ffi::Py_tp_init),
+        )
+    }
+}
+
 fn py_class_qualified_name(module_name: Option<&str>, class_name: &str) -> PyResult<CString> {
     Ok(CString::new(format!(
         "{}.{}",
@@ -493,28 +827,38 @@ unsafe extern "C" fn no_constructor_defined(
 
 #[derive(Default)]
 struct GetSetDefBuilder {
-    doc: Option<&'static str>,
-    getter: Option<Getter>,
-    setter: Option<Setter>,
+    getter: Option<(Getter, &'static str)>,
+    setter: Option<(Setter, &'static str)>,
+    deleter: Option<(Deleter, &'static str)>,
 }
 
 impl GetSetDefBuilder {
     fn add_getter(&mut self, getter: &PyGetterDef) {
-        // TODO: be smarter about merging getter and setter docs
-        if self.doc.is_none() {
-            self.doc = Some(getter.doc);
-        }
         // TODO: return an error if getter already defined?
-        self.getter = Some(getter.meth.0)
+        self.getter = Some((getter.meth.0, getter.doc))
     }
 
     fn add_setter(&mut self, setter: &PySetterDef) {
-        // TODO: be smarter about merging getter and setter docs
-        if self.doc.is_none() {
-            self.doc = Some(setter.doc);
-        }
         // TODO: return an error if setter already defined?
-        self.setter = Some(setter.meth.0)
+        self.setter = Some((setter.meth.0, setter.doc))
+    }
+
+    fn add_deleter(&mut self, deleter: &PyDeleterDef) {
+        // TODO: return an error if deleter already defined?
+        self.deleter = Some((deleter.meth.0, deleter.doc))
+    }
+
+    /// The property docstring is the first non-empty one of the getter, setter and deleter docs.
+    fn doc(&self) -> Option<&'static str> {
+        let docs = [
+            self.getter.map(|(_, doc)| doc),
+            self.setter.map(|(_, doc)| doc),
+            self.deleter.map(|(_, doc)| doc),
+        ];
+        let mut docs = docs.iter().flatten().copied();
+        docs.clone()
+            .find(|doc| !doc.trim_end_matches('\0').is_empty())
+            .or_else(|| docs.next())
     }
 
     fn as_get_set_def(
@@ -522,16 +866,24 @@ impl GetSetDefBuilder {
         name: &'static str,
     ) -> PyResult<(ffi::PyGetSetDef, GetSetDefDestructor)> {
         let name = get_name(name)?;
-        let doc = self.doc.map(get_doc).transpose()?;
+        let doc = self.doc().map(get_doc).transpose()?;
+
+        let getter = self.getter.map(|(getter, _)| getter);
+        let setter = self.setter.map(|(setter, _)| setter);
+        let deleter = self.deleter.map(|(deleter, _)| deleter);
 
-        let getset_type = match (self.getter, self.setter) {
-            (Some(getter), None) => GetSetDefType::Getter(getter),
-            (None, Some(setter)) => GetSetDefType::Setter(setter),
-            (Some(getter), Some(setter)) => {
-                GetSetDefType::GetterAndSetter(Box::new(GetterAndSetter { getter, setter }))
+        let getset_type = match (getter, setter, deleter) {
+            (Some(getter), None, None) => GetSetDefType::Getter(getter),
+            (None, Some(setter), None) => GetSetDefType::Setter(setter),
+            (None, None, None) => {
+                unreachable!("GetSetDefBuilder expected to always have a getter, setter or deleter")
             }
-            (None, None) => {
-                unreachable!("GetSetDefBuilder expected to always have either getter or setter")
+            (getter, setter, deleter) => {
+                GetSetDefType::GetterAndSetter(Box::new(GetterAndSetter {
+                    getter,
+                    setter,
+                    deleter,
+                }))
             }
         };
 
@@ -552,7 +904,7 @@ struct GetSetDefDestructor {
     closure: GetSetDefType,
 }
 
-/// Possible forms of property - either a getter, setter, or both
+/// Possible forms of property - either a getter, setter, or a combination with a deleter
 enum GetSetDefType {
     Getter(Getter),
     Setter(Setter),
@@ -562,8 +914,9 @@ enum GetSetDefType {
 }
 
 pub(crate) struct GetterAndSetter {
-    getter: Getter,
-    setter: Setter,
+    getter: Option<Getter>,
+    setter: Option<Setter>,
+    deleter: Option<Deleter>,
 }
 
 impl GetSetDefType {
@@ -606,7 +959,10 @@ impl GetSetDefType {
                         closure: *mut c_void,
                     ) -> *mut ffi::PyObject {
                         let getset: &GetterAndSetter = &*(closure as *const GetterAndSetter);
-                        trampoline(|py| (getset.getter)(py, slf))
+                        trampoline(|py| match getset.getter {
+                            Some(getter) => getter(py, slf),
+                            None => Err(PyAttributeError::new_err("unreadable attribute")),
+                        })
                     }
 
                     unsafe extern "C" fn getset_setter(
@@ -615,10 +971,18 @@ impl GetSetDefType {
                         closure: *mut c_void,
                     ) -> c_int {
                         let getset: &GetterAndSetter = &*(closure as *const GetterAndSetter);
-                        trampoline(|py| (getset.setter)(py, slf, value))
+                        trampoline(|py| match (getset.setter, getset.deleter) {
+                            // a null value means the attribute is being deleted
+                            (_, Some(deleter)) if value.is_null() => deleter(py, slf),
+                            (Some(setter), _) => setter(py, slf, value),
+                            (None, _) if value.is_null() => {
+                                Err(PyAttributeError::new_err("can't delete attribute"))
+                            }
+                            (None, _) => Err(PyAttributeError::new_err("can't set attribute")),
+                        })
                     }
                     (
-                        Some(getset_getter),
+                        closure.getter.map(|_| getset_getter as ffi::getter),
                         Some(getset_setter),
                         closure.as_ref() as *const GetterAndSetter as _,
                     )
@@ -633,3 +997,27 @@ impl GetSetDefType {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "macros")]
+mod tests {
+    use super::{create_type_object, publish_shared_type, shared_type_fingerprint};
+    use crate::{types::any::PyAnyMethods, PyTypeInfo, Python};
+
+    #[crate::pyclass(crate = "crate", shared = "pyo3.tests.SharedClass")]
+    struct SharedClass {}
+
+    #[test]
+    fn test_shared_type_reused() {
+        Python::with_gil(|py| {
+            // Stand-in for the type object published by another module built from the same
+            // definition of the class.
+            let published = create_type_object::<SharedClass>(py).unwrap().type_object;
+            let fingerprint = shared_type_fingerprint::<SharedClass>();
+            let shared =
+                publish_shared_type(py, "pyo3.tests.SharedClass", &published, fingerprint).unwrap();
+            assert!(shared.is(&published));
+            assert!(SharedClass::type_object_bound(py).is(published.bind(py)));
+        });
+    }
+}
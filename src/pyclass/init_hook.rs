@@ -0,0 +1,44 @@
+//! Hook reporting the creation of `#[pyclass]` type objects, to profile import times.
+use parking_lot::{const_mutex, Mutex};
+use std::time::Duration;
+
+use crate::Python;
+
+/// Callback receiving the name of a `#[pyclass]` and the time taken to create its type object.
+///
+/// See [`set_type_init_hook`].
+pub type TypeInitHook = fn(py: Python<'_>, name: &str, elapsed: Duration);
+
+static TYPE_INIT_HOOK: Mutex<Option<TypeInitHook>> = const_mutex(None);
+
+/// Sets the hook called each time the type object of a `#[pyclass]` is created, returning the
+/// previous one.
+///
+/// Type objects are created when a class is added to a module with
+/// [`add_class`](crate::types::PyModuleMethods::add_class), or when the class is first used
+/// otherwise. The reported duration includes the creation of class attributes, and of the type
+/// objects of base classes which were not created yet.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::set_type_init_hook;
+///
+/// #[pyclass]
+/// struct Foo {}
+///
+/// set_type_init_hook(Some(|_py, name, elapsed| {
+///     eprintln!("type object of {} created in {:?}", name, elapsed);
+/// }));
+/// # Python::with_gil(|py| {
+/// #     let _ = py.get_type::<Foo>();
+/// # });
+/// ```
+pub fn set_type_init_hook(hook: Option<TypeInitHook>) -> Option<TypeInitHook> {
+    std::mem::replace(&mut *TYPE_INIT_HOOK.lock(), hook)
+}
+
+pub(crate) fn type_init_hook() -> Option<TypeInitHook> {
+    *TYPE_INIT_HOOK.lock()
+}
@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+
+use crate::{exceptions::PyOverflowError, PyResult};
+
+/// The number of items in a Rust-backed container, used by `#[pyclass(len)]`.
+///
+/// `#[pyclass(len)]` generates `__len__`, `__length_hint__` and `__bool__` from this trait, so
+/// that the three always agree. Implementations are provided for the standard library
+/// collections, so a container class can usually forward to one of its fields:
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::PyLen;
+///
+/// #[pyclass(len)]
+/// struct Stack {
+///     items: Vec<PyObject>,
+/// }
+///
+/// impl PyLen for Stack {
+///     fn py_len(&self) -> usize {
+///         self.items.py_len()
+///     }
+/// }
+/// #
+/// # Python::with_gil(|py| {
+/// #     let stack = Py::new(py, Stack { items: vec![py.None()] }).unwrap();
+/// #     pyo3::py_run!(py, stack, "assert len(stack) == 1 and stack");
+/// # });
+/// ```
+///
+/// Python requires `len()` to fit in `isize`. Larger logical lengths make `__len__` raise
+/// `OverflowError`, while `__length_hint__` saturates at `isize::MAX` and `__bool__` is still
+/// correct.
+pub trait PyLen {
+    /// Returns the number of items in the container.
+    fn py_len(&self) -> usize;
+
+    /// Returns `true` if the container has no items.
+    fn py_is_empty(&self) -> bool {
+        self.py_len() == 0
+    }
+}
+
+/// Implementation of `__len__` for `#[pyclass(len)]`.
+pub fn py_len<T: PyLen + ?Sized>(container: &T) -> PyResult<usize> {
+    let len = container.py_len();
+    if len > isize::MAX as usize {
+        Err(PyOverflowError::new_err(format!(
+            "length {} does not fit in a Python index",
+            len
+        )))
+    } else {
+        Ok(len)
+    }
+}
+
+/// Implementation of `__length_hint__` for `#[pyclass(len)]`.
+pub fn py_length_hint<T: PyLen + ?Sized>(container: &T) -> usize {
+    container.py_len().min(isize::MAX as usize)
+}
+
+impl<T: PyLen + ?Sized> PyLen for &T {
+    fn py_len(&self) -> usize {
+        (**self).py_len()
+    }
+
+    fn py_is_empty(&self) -> bool {
+        (**self).py_is_empty()
+    }
+}
+
+impl<T: PyLen + ?Sized> PyLen for Box<T> {
+    fn py_len(&self) -> usize {
+        (**self).py_len()
+    }
+
+    fn py_is_empty(&self) -> bool {
+        (**self).py_is_empty()
+    }
+}
+
+macro_rules! impl_py_len {
+    ($([$($generics:tt)*] $ty:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> PyLen for $ty {
+                #[inline]
+                fn py_len(&self) -> usize {
+                    self.len()
+                }
+
+                #[inline]
+                fn py_is_empty(&self) -> bool {
+                    self.is_empty()
+                }
+            }
+        )*
+    };
+}
+
+impl_py_len!(
+    [] str,
+    [] String,
+    [T] [T],
+    [T, const N: usize] [T; N],
+    [T] Vec<T>,
+    [T] VecDeque<T>,
+    [T] LinkedList<T>,
+    [T] BinaryHeap<T>,
+    [K, V, S] HashMap<K, V, S>,
+    [T, S] HashSet<T, S>,
+    [K, V] BTreeMap<K, V>,
+    [T] BTreeSet<T>,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{py_len, py_length_hint, PyLen};
+
+    struct Huge;
+
+    impl PyLen for Huge {
+        fn py_len(&self) -> usize {
+            usize::MAX
+        }
+    }
+
+    #[test]
+    fn test_collections() {
+        assert_eq!(vec![1, 2, 3].py_len(), 3);
+        assert!(String::new().py_is_empty());
+        assert_eq!([0u8; 4].py_len(), 4);
+        assert_eq!(Box::new(vec![1]).py_len(), 1);
+    }
+
+    #[test]
+    fn test_overflow() {
+        crate::Python::with_gil(|py| {
+            let err = py_len(&Huge).unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyOverflowError>(py));
+        });
+        assert_eq!(py_length_hint(&Huge), isize::MAX as usize);
+        assert!(!Huge.py_is_empty());
+    }
+}
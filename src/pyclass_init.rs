@@ -1,7 +1,11 @@
 //! Contains initialization utilities for `#[pyclass]`.
 use crate::callback::IntoPyCallbackOutput;
-use crate::impl_::pyclass::{PyClassBaseType, PyClassDict, PyClassThreadChecker, PyClassWeakRef};
-use crate::{ffi, Py, PyCell, PyClass, PyErr, PyResult, Python};
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::impl_::pyclass::{
+    PyClassBaseType, PyClassDict, PyClassThreadChecker, PyClassVectorcall, PyClassWeakRef,
+};
+use crate::types::any::PyAnyMethods;
+use crate::{ffi, Bound, Py, PyCell, PyClass, PyErr, PyResult, Python};
 use crate::{
     ffi::PyTypeObject,
     pycell::{
@@ -206,13 +210,42 @@ impl<T: PyClass> PyClassInitializer<T> {
         PyClassInitializer::new(subclass_value, self)
     }
 
+    /// Creates a new class object and initializes it.
+    ///
+    /// The returned [`Bound`] owns its reference, which is released when it is dropped rather
+    /// than being held until the `GILPool` is dropped.
+    pub fn create_class_object(self, py: Python<'_>) -> PyResult<Bound<'_, T>>
+    where
+        T: PyClass,
+    {
+        unsafe { self.create_class_object_of_type(py, T::type_object_raw(py)) }
+    }
+
+    /// Creates a new class object and initializes it given a typeobject `target_type`.
+    /// Called by the Python `tp_new` implementation generated by a `#[new]` function in a `#[pymethods]` block.
+    ///
+    /// # Safety
+    /// `target_type` must be a valid pointer to the type object of T or a subclass.
+    pub unsafe fn create_class_object_of_type(
+        self,
+        py: Python<'_>,
+        target_type: *mut crate::ffi::PyTypeObject,
+    ) -> PyResult<Bound<'_, T>>
+    where
+        T: PyClass,
+    {
+        let obj = self.into_new_object(py, target_type)?;
+        Ok(obj.assume_owned(py).downcast_into_unchecked())
+    }
+
     /// Creates a new PyCell and initializes it.
     #[doc(hidden)]
     pub fn create_cell(self, py: Python<'_>) -> PyResult<*mut PyCell<T>>
     where
         T: PyClass,
     {
-        unsafe { self.create_cell_from_subtype(py, T::type_object_raw(py)) }
+        self.create_class_object(py)
+            .map(|obj| obj.into_ptr().cast())
     }
 
     /// Creates a new PyCell and initializes it given a typeobject `subtype`.
@@ -229,7 +262,8 @@ impl<T: PyClass> PyClassInitializer<T> {
     where
         T: PyClass,
     {
-        self.into_new_object(py, subtype).map(|obj| obj as _)
+        self.create_class_object_of_type(py, subtype)
+            .map(|obj| obj.into_ptr().cast())
     }
 }
 
@@ -263,6 +297,7 @@ impl<T: PyClass> PyObjectInit<T> for PyClassInitializer<T> {
                 thread_checker: T::ThreadChecker::new(),
                 dict: T::Dict::INIT,
                 weakref: T::WeakRef::INIT,
+                vectorcall: T::Vectorcall::new(T::vectorcall()),
             },
         );
         Ok(obj)
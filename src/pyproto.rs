@@ -0,0 +1,46 @@
+//! Protocol traits of the legacy `#[pyproto]` API, kept so that existing code can migrate to
+//! inherent `#[pymethods]` blocks incrementally.
+//!
+//! The traits no longer have any methods of their own: [`#[pymethods]`](crate::pymethods) on an
+//! impl of one of these traits moves its methods into an inherent impl of the class and registers
+//! them as if they were in an inherent `#[pymethods]` block. An empty impl of the trait is kept so
+//! that existing imports and trait bounds continue to work.
+
+use crate::PyClass;
+
+macro_rules! pyproto_traits {
+    ($($(#[$doc:meta])* $trait_name:ident),* $(,)?) => {
+        $(
+            $(#[$doc])*
+            ///
+            /// Implemented by [`#[pymethods]`](crate::pymethods); see the [migration guide] for
+            /// how to move the methods to an inherent `#[pymethods]` block.
+            ///
+            /// [migration guide]: https://pyo3.rs/latest/migration.html#pyproto-has-been-deprecated
+            pub trait $trait_name<'p>: PyClass {}
+        )*
+    };
+}
+
+pyproto_traits!(
+    /// Basic customization (`__str__`, `__repr__`, `__getattr__`, `__richcmp__`, ...).
+    PyObjectProtocol,
+    /// Numeric operators (`__add__`, `__neg__`, `__index__`, ...).
+    PyNumberProtocol,
+    /// Mapping container methods (`__len__`, `__getitem__`, `__setitem__`, `__delitem__`).
+    PyMappingProtocol,
+    /// Sequence container methods (`__len__`, `__getitem__`, `__contains__`, `__concat__`, ...).
+    PySequenceProtocol,
+    /// Iterator methods (`__iter__`, `__next__`).
+    PyIterProtocol,
+    /// Awaitable and asynchronous iterator methods (`__await__`, `__aiter__`, `__anext__`).
+    PyAsyncProtocol,
+    /// Context manager methods (`__enter__`, `__exit__`).
+    PyContextProtocol,
+    /// Descriptor methods (`__get__`, `__set__`, `__delete__`, `__set_name__`).
+    PyDescrProtocol,
+    /// Garbage collector support (`__traverse__`, `__clear__`).
+    PyGCProtocol,
+    /// Buffer protocol (`bf_getbuffer`, `bf_releasebuffer`).
+    PyBufferProtocol,
+);
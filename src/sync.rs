@@ -1,9 +1,11 @@
 //! Synchronization mechanisms based on the Python GIL.
 use crate::{
     types::{any::PyAnyMethods, PyString, PyType},
-    Bound, Py, PyResult, PyVisit, Python,
+    Bound, Py, PyAny, PyResult, Python,
 };
 use std::cell::UnsafeCell;
+#[cfg(py_sys_config = "Py_GIL_DISABLED")]
+use std::sync::Once;
 
 /// Value with concurrent access protected by the GIL.
 ///
@@ -11,6 +13,9 @@ use std::cell::UnsafeCell;
 /// It ensures that only one thread at a time can access the inner value via shared references.
 /// It can be combined with interior mutability to obtain mutable references.
 ///
+/// This type is not available on the free-threaded build of Python, which does not have a GIL to
+/// provide this guarantee. Use a mutex instead.
+///
 /// # Example
 ///
 /// Combining `GILProtected` with `RefCell` enables mutable access to static data:
@@ -26,10 +31,12 @@ use std::cell::UnsafeCell;
 ///     NUMBERS.get(py).borrow_mut().push(42);
 /// });
 /// ```
+#[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
 pub struct GILProtected<T> {
     value: T,
 }
 
+#[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
 impl<T> GILProtected<T> {
     /// Place the given value under the protection of the GIL.
     pub const fn new(value: T) -> Self {
@@ -42,11 +49,12 @@ impl<T> GILProtected<T> {
     }
 
     /// Gain access to the inner value by giving proof that garbage collection is happening.
-    pub fn traverse<'py>(&'py self, _visit: PyVisit<'py>) -> &'py T {
+    pub fn traverse<'py>(&'py self, _visit: crate::PyVisit<'py>) -> &'py T {
         &self.value
     }
 }
 
+#[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
 unsafe impl<T> Sync for GILProtected<T> where T: Send {}
 
 /// A write-once cell similar to [`once_cell::OnceCell`](https://docs.rs/once_cell/latest/once_cell/).
@@ -65,6 +73,9 @@ unsafe impl<T> Sync for GILProtected<T> where T: Send {}
 ///     happens `GILOnceCell` guarantees that only **one** write to the cell ever occurs
 ///     - this is treated as a race, other threads will discard the value they compute and
 ///     return the result of the first complete computation.
+///  3) On the free-threaded build of Python, where several threads can run Python code at the
+///     same time, the same race can happen even when `f` does not release the GIL. Writes to the
+///     cell are synchronized, so that only one value is ever stored.
 ///
 /// # Examples
 ///
@@ -85,7 +96,12 @@ unsafe impl<T> Sync for GILProtected<T> where T: Send {}
 /// }
 /// # Python::with_gil(|py| assert_eq!(get_shared_list(py).len(), 0));
 /// ```
-pub struct GILOnceCell<T>(UnsafeCell<Option<T>>);
+pub struct GILOnceCell<T> {
+    // Without the GIL, other threads can read or write the cell concurrently.
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    once: Once,
+    data: UnsafeCell<Option<T>>,
+}
 
 // T: Send is needed for Sync because the thread which drops the GILOnceCell can be different
 // to the thread which fills it.
@@ -95,14 +111,29 @@ unsafe impl<T: Send> Send for GILOnceCell<T> {}
 impl<T> GILOnceCell<T> {
     /// Create a `GILOnceCell` which does not yet contain a value.
     pub const fn new() -> Self {
-        Self(UnsafeCell::new(None))
+        Self {
+            #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+            once: Once::new(),
+            data: UnsafeCell::new(None),
+        }
     }
 
     /// Get a reference to the contained value, or `None` if the cell has not yet been written.
     #[inline]
     pub fn get(&self, _py: Python<'_>) -> Option<&T> {
-        // Safe because if the cell has not yet been written, None is returned.
-        unsafe { &*self.0.get() }.as_ref()
+        #[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
+        {
+            // Safe because if the cell has not yet been written, None is returned.
+            unsafe { &*self.data.get() }.as_ref()
+        }
+
+        #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+        if self.once.is_completed() {
+            // Safe because the cell is never written again once `once` has completed.
+            unsafe { &*self.data.get() }.as_ref()
+        } else {
+            None
+        }
     }
 
     /// Get a reference to the contained value, initializing it if needed using the provided
@@ -157,7 +188,7 @@ impl<T> GILOnceCell<T> {
     /// Get the contents of the cell mutably. This is only possible if the reference to the cell is
     /// unique.
     pub fn get_mut(&mut self) -> Option<&mut T> {
-        self.0.get_mut().as_mut()
+        self.data.get_mut().as_mut()
     }
 
     /// Set the value in the cell.
@@ -165,28 +196,51 @@ impl<T> GILOnceCell<T> {
     /// If the cell has already been written, `Err(value)` will be returned containing the new
     /// value which was not written.
     pub fn set(&self, _py: Python<'_>, value: T) -> Result<(), T> {
-        // Safe because GIL is held, so no other thread can be writing to this cell concurrently.
-        let inner = unsafe { &mut *self.0.get() };
-        if inner.is_some() {
-            return Err(value);
+        #[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
+        {
+            // Safe because GIL is held, so no other thread can be writing to this cell concurrently.
+            let inner = unsafe { &mut *self.data.get() };
+            if inner.is_some() {
+                return Err(value);
+            }
+
+            *inner = Some(value);
+            Ok(())
         }
 
-        *inner = Some(value);
-        Ok(())
+        #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+        {
+            let mut value = Some(value);
+            // `Once` blocks other threads only while the value is moved into the cell, which
+            // cannot deadlock with the GIL.
+            self.once.call_once_force(|_| {
+                // Safe because `once` guarantees that no other thread is accessing the cell.
+                unsafe { *self.data.get() = value.take() };
+            });
+
+            match value {
+                Some(value) => Err(value),
+                None => Ok(()),
+            }
+        }
     }
 
     /// Takes the value out of the cell, moving it back to an uninitialized state.
     ///
     /// Has no effect and returns None if the cell has not yet been written.
     pub fn take(&mut self) -> Option<T> {
-        self.0.get_mut().take()
+        #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+        {
+            self.once = Once::new();
+        }
+        self.data.get_mut().take()
     }
 
     /// Consumes the cell, returning the wrapped value.
     ///
     /// Returns None if the cell has not yet been written.
     pub fn into_inner(self) -> Option<T> {
-        self.0.into_inner()
+        self.data.into_inner()
     }
 }
 
@@ -207,6 +261,114 @@ impl GILOnceCell<Py<PyType>> {
     }
 }
 
+/// Runs `f` while holding the per-object lock of `object`, using a critical section.
+///
+/// On the free-threaded build of Python, this is equivalent to the `Py_BEGIN_CRITICAL_SECTION`
+/// and `Py_END_CRITICAL_SECTION` macros of the C API: no other thread can run a critical section
+/// on the same object at the same time, which can protect invariants which involve the object or
+/// data owned by it. On builds with the GIL, the GIL already provides this guarantee, so `f` is
+/// simply called.
+///
+/// The lock is released if the thread detaches from the interpreter while running `f`, for
+/// example in [`Python::allow_threads`] or while waiting for another lock, and acquired again
+/// before `f` continues. Nested critical sections may also release the lock of the outer ones to
+/// avoid deadlocks. Critical sections therefore cannot be used to hold a lock across such calls.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::sync::with_critical_section;
+/// use pyo3::types::PyList;
+///
+/// Python::with_gil(|py| {
+///     let list = PyList::new_bound(py, [1, 2]);
+///     // Another thread cannot change the list between the length check and the append.
+///     with_critical_section(&list, || {
+///         if list.len() < 3 {
+///             list.append(3).unwrap();
+///         }
+///     });
+///     assert_eq!(list.len(), 3);
+/// });
+/// ```
+pub fn with_critical_section<F, R>(object: &Bound<'_, PyAny>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    {
+        struct Guard(crate::ffi::PyCriticalSection);
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                unsafe { crate::ffi::PyCriticalSection_End(&mut self.0) }
+            }
+        }
+
+        // The critical section must not move while it is active, as the thread state points to it.
+        let mut guard = Guard(unsafe { std::mem::zeroed() });
+        unsafe { crate::ffi::PyCriticalSection_Begin(&mut guard.0, object.as_ptr()) };
+        f()
+    }
+    #[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
+    {
+        let _ = object;
+        f()
+    }
+}
+
+/// Runs `f` while holding the per-object locks of both `a` and `b`, using a critical section.
+///
+/// This is equivalent to the `Py_BEGIN_CRITICAL_SECTION2` and `Py_END_CRITICAL_SECTION2` macros
+/// of the C API. The locks are acquired in a consistent order, so that two threads locking the
+/// same objects in the opposite order cannot deadlock. See [`with_critical_section`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::sync::with_critical_section2;
+/// use pyo3::types::PyList;
+///
+/// Python::with_gil(|py| {
+///     let source = PyList::new_bound(py, [1, 2, 3]);
+///     let destination = PyList::empty_bound(py);
+///     // Other threads see the item in exactly one of the lists.
+///     with_critical_section2(&source, &destination, || {
+///         let item = source.get_item(0).unwrap();
+///         source.del_item(0).unwrap();
+///         destination.append(item).unwrap();
+///     });
+///     assert_eq!(source.len() + destination.len(), 3);
+/// });
+/// ```
+pub fn with_critical_section2<F, R>(a: &Bound<'_, PyAny>, b: &Bound<'_, PyAny>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+    {
+        struct Guard(crate::ffi::PyCriticalSection2);
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                unsafe { crate::ffi::PyCriticalSection2_End(&mut self.0) }
+            }
+        }
+
+        // The critical section must not move while it is active, as the thread state points to it.
+        let mut guard = Guard(unsafe { std::mem::zeroed() });
+        unsafe { crate::ffi::PyCriticalSection2_Begin(&mut guard.0, a.as_ptr(), b.as_ptr()) };
+        f()
+    }
+    #[cfg(not(py_sys_config = "Py_GIL_DISABLED"))]
+    {
+        let _ = (a, b);
+        f()
+    }
+}
+
 /// Interns `text` as a Python string and stores a reference to it in static storage.
 ///
 /// A reference to the same Python string is returned on each invocation.
@@ -317,4 +479,22 @@ mod tests {
             assert_eq!(cell.into_inner(), None)
         })
     }
+
+    #[test]
+    fn test_critical_section() {
+        Python::with_gil(|py| {
+            let first = PyDict::new_bound(py);
+            let second = PyDict::new_bound(py);
+
+            let len = with_critical_section(&first, || {
+                first.set_item("a", 1).unwrap();
+                // Nesting critical sections, even on the same object, does not deadlock.
+                with_critical_section2(&first, &second, || {
+                    second.set_item("b", 2).unwrap();
+                    first.len() + second.len()
+                })
+            });
+            assert_eq!(len, 2);
+        });
+    }
 }
@@ -0,0 +1,234 @@
+//! Typed access to the configuration of the interpreter in the [`sys`] module.
+//!
+//! Applications which embed Python often need to prepare `sys` before running scripts, for
+//! example to pass command line arguments or to make their own Python packages importable.
+//! [`Python::sys`] returns a [`Sys`] facade for this:
+//!
+//! ```rust
+//! use pyo3::prelude::*;
+//!
+//! # fn main() -> PyResult<()> {
+//! Python::with_gil(|py| {
+//!     let sys = py.sys()?;
+//!     sys.set_argv(["script.py", "--verbose"])?;
+//!     sys.path()?.insert(0, "scripts")?;
+//!
+//!     let argv: Vec<String> = py.eval_bound("__import__('sys').argv", None, None)?.extract()?;
+//!     assert_eq!(argv, ["script.py", "--verbose"]);
+//!     assert!(!sys.flags()?.isolated);
+//!     Ok(())
+//! })
+//! # }
+//! ```
+//!
+//! [`sys`]: https://docs.python.org/3/library/sys.html
+
+use crate::types::any::PyAnyMethods;
+use crate::types::{PyList, PyModule, PyString};
+use crate::{Bound, PyResult, Python, ToPyObject};
+use std::path::PathBuf;
+
+/// A typed facade for the [`sys`] module, created by [`Python::sys`].
+///
+/// See the [module documentation](self) for an example.
+///
+/// [`sys`]: https://docs.python.org/3/library/sys.html
+#[derive(Clone, Debug)]
+pub struct Sys<'py> {
+    module: Bound<'py, PyModule>,
+}
+
+impl<'py> Sys<'py> {
+    pub(crate) fn new(py: Python<'py>) -> PyResult<Self> {
+        Ok(Sys {
+            module: py.import_bound(intern!(py, "sys"))?,
+        })
+    }
+
+    /// Returns the `sys` module itself, to access attributes without a typed accessor.
+    pub fn as_module(&self) -> &Bound<'py, PyModule> {
+        &self.module
+    }
+
+    /// Returns `sys.argv`, the command line arguments passed to the Python script.
+    ///
+    /// The list is returned by reference, so changes to it are seen by Python code.
+    pub fn argv(&self) -> PyResult<Bound<'py, PyList>> {
+        self.list(intern!(self.module.py(), "argv"))
+    }
+
+    /// Replaces `sys.argv` with a new list of `args`.
+    ///
+    /// Code which already holds a reference to the previous list does not see the new arguments.
+    pub fn set_argv<T, U>(&self, args: impl IntoIterator<Item = T, IntoIter = U>) -> PyResult<()>
+    where
+        T: ToPyObject,
+        U: ExactSizeIterator<Item = T>,
+    {
+        let py = self.module.py();
+        self.module
+            .setattr(intern!(py, "argv"), PyList::new_bound(py, args))
+    }
+
+    /// Returns `sys.path`, the list of directories searched for modules.
+    ///
+    /// The list is returned by reference, so entries inserted or appended to it are searched by
+    /// later imports.
+    pub fn path(&self) -> PyResult<Bound<'py, PyList>> {
+        self.list(intern!(self.module.py(), "path"))
+    }
+
+    /// Returns the command line flags the interpreter was started with, from `sys.flags`.
+    pub fn flags(&self) -> PyResult<SysFlags> {
+        let py = self.module.py();
+        let flags = self.module.getattr(intern!(py, "flags"))?;
+        let get = |name: &str| -> PyResult<i32> { flags.getattr(name)?.extract() };
+        Ok(SysFlags {
+            debug: get("debug")?,
+            inspect: get("inspect")? != 0,
+            interactive: get("interactive")? != 0,
+            optimize: get("optimize")?,
+            dont_write_bytecode: get("dont_write_bytecode")? != 0,
+            no_user_site: get("no_user_site")? != 0,
+            no_site: get("no_site")? != 0,
+            ignore_environment: get("ignore_environment")? != 0,
+            verbose: get("verbose")?,
+            bytes_warning: get("bytes_warning")?,
+            quiet: get("quiet")? != 0,
+            hash_randomization: get("hash_randomization")? != 0,
+            isolated: get("isolated")? != 0,
+            dev_mode: get("dev_mode")? != 0,
+            utf8_mode: get("utf8_mode")? != 0,
+        })
+    }
+
+    /// Returns `sys.executable`, the path of the Python interpreter binary.
+    ///
+    /// This is `None` if Python cannot determine the path, which is common when the interpreter
+    /// is embedded in an application.
+    pub fn executable(&self) -> PyResult<Option<PathBuf>> {
+        let executable: Option<String> = self
+            .module
+            .getattr(intern!(self.module.py(), "executable"))?
+            .extract()?;
+        Ok(executable
+            .filter(|executable| !executable.is_empty())
+            .map(PathBuf::from))
+    }
+
+    fn list(&self, name: &Bound<'py, PyString>) -> PyResult<Bound<'py, PyList>> {
+        self.module
+            .getattr(name)?
+            .downcast_into()
+            .map_err(Into::into)
+    }
+}
+
+/// The command line flags of the interpreter, as found in [`sys.flags`].
+///
+/// Flags which can be given several times, such as `-v`, are counts; the other flags are
+/// booleans.
+///
+/// [`sys.flags`]: https://docs.python.org/3/library/sys.html#sys.flags
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SysFlags {
+    /// `-d`: the debug level of the parser.
+    pub debug: i32,
+    /// `-i`: enter interactive mode after running the script.
+    pub inspect: bool,
+    /// `-i`: the interpreter runs interactively.
+    pub interactive: bool,
+    /// `-O` or `-OO`: the optimization level.
+    pub optimize: i32,
+    /// `-B`: `.pyc` files are not written.
+    pub dont_write_bytecode: bool,
+    /// `-s`: the user site-packages directory is not added to `sys.path`.
+    pub no_user_site: bool,
+    /// `-S`: the `site` module is not imported on startup.
+    pub no_site: bool,
+    /// `-E`: the `PYTHON*` environment variables are ignored.
+    pub ignore_environment: bool,
+    /// `-v`: the verbosity level.
+    pub verbose: i32,
+    /// `-b` or `-bb`: the level of warnings about comparing `bytes` with `str`.
+    pub bytes_warning: i32,
+    /// `-q`: the version and copyright messages are not printed on interactive startup.
+    pub quiet: bool,
+    /// `-R`: hash randomization is enabled.
+    pub hash_randomization: bool,
+    /// `-I`: isolated mode.
+    pub isolated: bool,
+    /// `-X dev`: Python Development Mode.
+    pub dev_mode: bool,
+    /// `-X utf8`: Python UTF-8 Mode.
+    pub utf8_mode: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::any::PyAnyMethods;
+    use crate::types::list::PyListMethods;
+    use crate::Python;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_argv() {
+        Python::with_gil(|py| {
+            let sys = py.sys().unwrap();
+            let original = sys.argv().unwrap();
+
+            sys.set_argv(["script.py", "-x"]).unwrap();
+            let argv: Vec<String> = sys.argv().unwrap().extract().unwrap();
+            assert_eq!(argv, ["script.py", "-x"]);
+
+            sys.as_module().setattr("argv", original).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_path() {
+        Python::with_gil(|py| {
+            let sys = py.sys().unwrap();
+            let path = sys.path().unwrap();
+            path.append("pyo3_test_sys_path").unwrap();
+            let from_python: Vec<String> = py
+                .eval_bound("__import__('sys').path", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert!(from_python
+                .iter()
+                .any(|entry| entry == "pyo3_test_sys_path"));
+            path.call_method1("remove", ("pyo3_test_sys_path",))
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn test_flags_and_executable() {
+        Python::with_gil(|py| {
+            let sys = py.sys().unwrap();
+            let flags = sys.flags().unwrap();
+            let optimize: i32 = py
+                .eval_bound("__import__('sys').flags.optimize", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(flags.optimize, optimize);
+
+            let executable: String = sys
+                .as_module()
+                .getattr("executable")
+                .unwrap()
+                .extract()
+                .unwrap();
+            let expected = if executable.is_empty() {
+                None
+            } else {
+                Some(PathBuf::from(executable))
+            };
+            assert_eq!(sys.executable().unwrap(), expected);
+        });
+    }
+}
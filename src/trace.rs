@@ -0,0 +1,420 @@
+//! Profiling and tracing of Python code with Rust callbacks.
+//!
+//! [`set_profile`] and [`set_trace`] install a Rust closure as the profile or trace function of
+//! the current thread, like [`sys.setprofile`] and [`sys.settrace`] do for Python functions. The
+//! closure receives the frame being executed and a [`TraceEvent`] describing what happened:
+//!
+//! ```rust
+//! use pyo3::prelude::*;
+//! use pyo3::trace::{self, TraceEvent};
+//! use std::sync::{Arc, Mutex};
+//!
+//! # fn main() -> PyResult<()> {
+//! Python::with_gil(|py| {
+//!     let calls = Arc::new(Mutex::new(Vec::new()));
+//!     let recorded = calls.clone();
+//!     trace::set_profile(py, move |frame, event| {
+//!         if let TraceEvent::Call = event {
+//!             recorded.lock().unwrap().push(frame.code().name()?.to_string());
+//!         }
+//!         Ok(())
+//!     });
+//!
+//!     py.run_bound("def double(x):\n    return 2 * x\ndouble(21)", None, None)?;
+//!     trace::clear_profile(py);
+//!
+//!     assert!(calls.lock().unwrap().contains(&"double".to_string()));
+//!     Ok(())
+//! })
+//! # }
+//! ```
+//!
+//! On Python 3.12 and newer, the [`monitoring`] module gives access to [`sys.monitoring`], which
+//! is the preferred way to implement profilers and coverage tools.
+//!
+//! [`sys.setprofile`]: https://docs.python.org/3/library/sys.html#sys.setprofile
+//! [`sys.settrace`]: https://docs.python.org/3/library/sys.html#sys.settrace
+//! [`sys.monitoring`]: https://docs.python.org/3/library/sys.monitoring.html
+
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::impl_::trampoline::trampoline;
+use crate::types::any::PyAnyMethods;
+use crate::types::capsule::PyCapsuleMethods;
+use crate::types::{PyAny, PyCapsule, PyFrame, PyTuple};
+use crate::{ffi, Bound, PyResult, Python};
+use std::cell::RefCell;
+use std::os::raw::c_int;
+
+/// An event reported to a profile or trace function.
+///
+/// See the documentation of [`sys.settrace`] for when each event is reported.
+///
+/// [`sys.settrace`]: https://docs.python.org/3/library/sys.html#sys.settrace
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TraceEvent<'py> {
+    /// A function is called, or a generator is entered.
+    Call,
+    /// An exception was raised; contains the `(type, value, traceback)` tuple of the exception.
+    ///
+    /// Only reported to trace functions.
+    Exception(Bound<'py, PyTuple>),
+    /// A new line of code is about to be executed.
+    ///
+    /// Only reported to trace functions.
+    Line,
+    /// A function is about to return; contains the returned value, or `None` if the function
+    /// is exiting because of an exception.
+    Return(Option<Bound<'py, PyAny>>),
+    /// A function implemented in C (or Rust) is about to be called; contains the function.
+    CCall(Bound<'py, PyAny>),
+    /// A function implemented in C (or Rust) raised an exception; contains the function.
+    CException(Bound<'py, PyAny>),
+    /// A function implemented in C (or Rust) returned; contains the function.
+    CReturn(Bound<'py, PyAny>),
+    /// A new opcode is about to be executed.
+    ///
+    /// Only reported to trace functions, for frames which set `f_trace_opcodes`.
+    Opcode,
+}
+
+type TraceCallback =
+    Box<dyn for<'py> FnMut(&Bound<'py, PyFrame>, TraceEvent<'py>) -> PyResult<()> + Send>;
+
+/// Sets the profile function of the current thread, like [`sys.setprofile`].
+///
+/// This replaces the previous profile function, whether it was set from Rust or from Python.
+/// If `profiler` returns an error, the error is raised in the Python code being profiled and the
+/// profile function is removed.
+///
+/// [`sys.setprofile`]: https://docs.python.org/3/library/sys.html#sys.setprofile
+pub fn set_profile<F>(py: Python<'_>, profiler: F)
+where
+    F: for<'py> FnMut(&Bound<'py, PyFrame>, TraceEvent<'py>) -> PyResult<()> + Send + 'static,
+{
+    let capsule = new_callback_capsule(py, Box::new(profiler));
+    unsafe { ffi::PyEval_SetProfile(Some(trace_trampoline), capsule.as_ptr()) }
+}
+
+/// Removes the profile function of the current thread.
+pub fn clear_profile(_py: Python<'_>) {
+    unsafe { ffi::PyEval_SetProfile(None, std::ptr::null_mut()) }
+}
+
+/// Sets the trace function of the current thread, like [`sys.settrace`].
+///
+/// This replaces the previous trace function, whether it was set from Rust or from Python.
+/// Unlike a profile function, the trace function also receives [`TraceEvent::Line`],
+/// [`TraceEvent::Exception`] and [`TraceEvent::Opcode`] events. If `tracer` returns an error, the
+/// error is raised in the Python code being traced and the trace function is removed.
+///
+/// [`sys.settrace`]: https://docs.python.org/3/library/sys.html#sys.settrace
+pub fn set_trace<F>(py: Python<'_>, tracer: F)
+where
+    F: for<'py> FnMut(&Bound<'py, PyFrame>, TraceEvent<'py>) -> PyResult<()> + Send + 'static,
+{
+    let capsule = new_callback_capsule(py, Box::new(tracer));
+    unsafe { ffi::PyEval_SetTrace(Some(trace_trampoline), capsule.as_ptr()) }
+}
+
+/// Removes the trace function of the current thread.
+pub fn clear_trace(_py: Python<'_>) {
+    unsafe { ffi::PyEval_SetTrace(None, std::ptr::null_mut()) }
+}
+
+fn new_callback_capsule(py: Python<'_>, callback: TraceCallback) -> Bound<'_, PyCapsule> {
+    // The interpreter keeps the capsule alive for as long as the callback is installed.
+    PyCapsule::new_bound(py, RefCell::new(callback), None)
+        .expect("failed to create capsule for trace function")
+}
+
+unsafe extern "C" fn trace_trampoline(
+    obj: *mut ffi::PyObject,
+    frame: *mut ffi::PyFrameObject,
+    what: c_int,
+    arg: *mut ffi::PyObject,
+) -> c_int {
+    trampoline(move |py| {
+        // Hold a reference to the capsule, in case the callback replaces itself.
+        let capsule = obj.assume_borrowed(py).to_owned();
+        let capsule = capsule.downcast_unchecked::<PyCapsule>();
+        let callback = capsule.reference::<RefCell<TraceCallback>>();
+        let frame = frame
+            .cast::<ffi::PyObject>()
+            .assume_borrowed(py)
+            .to_owned()
+            .downcast_into_unchecked::<PyFrame>();
+        let arg = arg.assume_borrowed_or_opt(py).map(|arg| arg.to_owned());
+        let event = match (what, arg) {
+            (ffi::PyTrace_CALL, _) => TraceEvent::Call,
+            (ffi::PyTrace_EXCEPTION, Some(arg)) => {
+                TraceEvent::Exception(arg.downcast_into_unchecked())
+            }
+            (ffi::PyTrace_LINE, _) => TraceEvent::Line,
+            (ffi::PyTrace_RETURN, arg) => TraceEvent::Return(arg),
+            (ffi::PyTrace_C_CALL, Some(arg)) => TraceEvent::CCall(arg),
+            (ffi::PyTrace_C_EXCEPTION, Some(arg)) => TraceEvent::CException(arg),
+            (ffi::PyTrace_C_RETURN, Some(arg)) => TraceEvent::CReturn(arg),
+            (ffi::PyTrace_OPCODE, _) => TraceEvent::Opcode,
+            _ => return Ok(0),
+        };
+        // The interpreter does not call trace functions while one is running, but guard
+        // against reentrancy through e.g. a nested interpreter loop anyway.
+        match callback.try_borrow_mut() {
+            Ok(mut callback) => callback(&frame, event).map(|()| 0),
+            Err(_) => Ok(0),
+        }
+    })
+}
+
+/// Access to [`sys.monitoring`], the low-overhead monitoring API of Python 3.12 and newer.
+///
+/// A tool first reserves one of the six tool IDs with [`use_tool_id`], registers a callback for
+/// each of the [`events`] it is interested in, then turns these events on with [`set_events`]:
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::trace::monitoring::{self, events};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// # fn main() -> PyResult<()> {
+/// Python::with_gil(|py| {
+///     let starts = Arc::new(AtomicUsize::new(0));
+///     let counter = starts.clone();
+///     monitoring::use_tool_id(py, monitoring::PROFILER_ID, "counter")?;
+///     monitoring::register_callback(py, monitoring::PROFILER_ID, events::PY_START, move |args| {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///         Ok(args.py().None())
+///     })?;
+///     monitoring::set_events(py, monitoring::PROFILER_ID, events::PY_START)?;
+///
+///     py.run_bound("def f():\n    pass\nf()\nf()", None, None)?;
+///
+///     monitoring::set_events(py, monitoring::PROFILER_ID, events::NO_EVENTS)?;
+///     monitoring::free_tool_id(py, monitoring::PROFILER_ID)?;
+///     assert!(starts.load(Ordering::Relaxed) >= 2);
+///     Ok(())
+/// })
+/// # }
+/// ```
+///
+/// [`sys.monitoring`]: https://docs.python.org/3/library/sys.monitoring.html
+#[cfg(Py_3_12)]
+pub mod monitoring {
+    use crate::types::any::PyAnyMethods;
+    use crate::types::{PyCFunction, PyDict, PyTuple};
+    use crate::{Bound, PyAny, PyNativeType, PyObject, PyResult, Python};
+
+    /// The tool ID reserved for debuggers.
+    pub const DEBUGGER_ID: u8 = 0;
+    /// The tool ID reserved for coverage tools.
+    pub const COVERAGE_ID: u8 = 1;
+    /// The tool ID reserved for profilers.
+    pub const PROFILER_ID: u8 = 2;
+    /// The tool ID reserved for optimizers.
+    pub const OPTIMIZER_ID: u8 = 5;
+
+    /// The events which can be monitored, as the bit flags of `sys.monitoring.events`.
+    ///
+    /// See the [Python documentation](https://docs.python.org/3/library/sys.monitoring.html#events)
+    /// for the arguments passed to the callback of each event.
+    pub mod events {
+        /// No events.
+        pub const NO_EVENTS: u32 = 0;
+        /// Start of a Python function.
+        pub const PY_START: u32 = 1 << 0;
+        /// Resumption of a generator or coroutine.
+        pub const PY_RESUME: u32 = 1 << 1;
+        /// Return from a Python function.
+        pub const PY_RETURN: u32 = 1 << 2;
+        /// Yield from a generator or coroutine.
+        pub const PY_YIELD: u32 = 1 << 3;
+        /// A call from Python code.
+        pub const CALL: u32 = 1 << 4;
+        /// A new line of code is about to be executed.
+        pub const LINE: u32 = 1 << 5;
+        /// A new instruction is about to be executed.
+        pub const INSTRUCTION: u32 = 1 << 6;
+        /// An unconditional jump.
+        pub const JUMP: u32 = 1 << 7;
+        /// A conditional branch.
+        pub const BRANCH: u32 = 1 << 8;
+        /// An artificial `StopIteration` was raised.
+        pub const STOP_ITERATION: u32 = 1 << 9;
+        /// An exception was raised.
+        pub const RAISE: u32 = 1 << 10;
+        /// An exception was handled.
+        pub const EXCEPTION_HANDLED: u32 = 1 << 11;
+        /// Exit from a Python function because of an exception.
+        pub const PY_UNWIND: u32 = 1 << 12;
+        /// A Python function was resumed by a `throw()` call.
+        pub const PY_THROW: u32 = 1 << 13;
+        /// An exception was re-raised.
+        pub const RERAISE: u32 = 1 << 14;
+        /// Return from a function implemented in C (or Rust).
+        pub const C_RETURN: u32 = 1 << 15;
+        /// An exception was raised by a function implemented in C (or Rust).
+        pub const C_RAISE: u32 = 1 << 16;
+    }
+
+    fn monitoring(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        py.import_bound(intern!(py, "sys"))?
+            .getattr(intern!(py, "monitoring"))
+    }
+
+    /// Reserves `tool_id` for the tool named `name`, like `sys.monitoring.use_tool_id`.
+    ///
+    /// Fails if the ID is already in use.
+    pub fn use_tool_id(py: Python<'_>, tool_id: u8, name: &str) -> PyResult<()> {
+        monitoring(py)?
+            .call_method1(intern!(py, "use_tool_id"), (tool_id, name))
+            .map(drop)
+    }
+
+    /// Releases `tool_id`, like `sys.monitoring.free_tool_id`.
+    pub fn free_tool_id(py: Python<'_>, tool_id: u8) -> PyResult<()> {
+        monitoring(py)?
+            .call_method1(intern!(py, "free_tool_id"), (tool_id,))
+            .map(drop)
+    }
+
+    /// Turns on the given [`events`] for `tool_id`, and turns off all the others, like
+    /// `sys.monitoring.set_events`.
+    pub fn set_events(py: Python<'_>, tool_id: u8, events: u32) -> PyResult<()> {
+        monitoring(py)?
+            .call_method1(intern!(py, "set_events"), (tool_id, events))
+            .map(drop)
+    }
+
+    /// Registers `callback` for a single one of the [`events`] of `tool_id`, like
+    /// `sys.monitoring.register_callback`.
+    ///
+    /// The callback receives the arguments of the event as a tuple. It can return [`disable`] to
+    /// stop receiving the event for the current code location.
+    pub fn register_callback<F>(
+        py: Python<'_>,
+        tool_id: u8,
+        event: u32,
+        callback: F,
+    ) -> PyResult<()>
+    where
+        F: for<'py> Fn(&Bound<'py, PyTuple>) -> PyResult<PyObject> + Send + 'static,
+    {
+        let function = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            move |args: &PyTuple, _kwargs: Option<&PyDict>| callback(&args.as_borrowed()),
+        )?;
+        monitoring(py)?
+            .call_method1(intern!(py, "register_callback"), (tool_id, event, function))
+            .map(drop)
+    }
+
+    /// Removes the callback of `tool_id` for `event`.
+    pub fn unregister_callback(py: Python<'_>, tool_id: u8, event: u32) -> PyResult<()> {
+        monitoring(py)?
+            .call_method1(
+                intern!(py, "register_callback"),
+                (tool_id, event, py.None()),
+            )
+            .map(drop)
+    }
+
+    /// Returns `sys.monitoring.DISABLE`, which a callback can return to stop receiving its event
+    /// for the current code location.
+    pub fn disable(py: Python<'_>) -> PyResult<PyObject> {
+        monitoring(py)?
+            .getattr(intern!(py, "DISABLE"))
+            .map(Bound::unbind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clear_profile, clear_trace, set_profile, set_trace, TraceEvent};
+    use crate::exceptions::PyRuntimeError;
+    use crate::types::any::PyAnyMethods;
+    use crate::types::code::PyCodeMethods;
+    use crate::types::frame::PyFrameMethods;
+    use crate::types::PyDict;
+    use crate::Python;
+    use std::sync::{Arc, Mutex};
+
+    const CODE: &str = "
+def add(a, b):
+    c = a + b
+    return c
+
+result = add(1, 2)
+";
+
+    #[test]
+    fn test_set_profile() {
+        Python::with_gil(|py| {
+            let events = Arc::new(Mutex::new(Vec::new()));
+            let recorded = events.clone();
+            set_profile(py, move |frame, event| {
+                let name = frame.code().name()?.to_string();
+                if name == "add" {
+                    let event = match event {
+                        TraceEvent::Call => "call".to_owned(),
+                        TraceEvent::Return(value) => format!("return {}", value.unwrap()),
+                        _ => return Ok(()),
+                    };
+                    recorded.lock().unwrap().push(event);
+                }
+                Ok(())
+            });
+            let globals = PyDict::new_bound(py);
+            py.run_bound(CODE, Some(&globals), None).unwrap();
+            clear_profile(py);
+            assert_eq!(*events.lock().unwrap(), ["call", "return 3"]);
+        });
+    }
+
+    #[test]
+    fn test_set_trace() {
+        Python::with_gil(|py| {
+            let lines = Arc::new(Mutex::new(Vec::new()));
+            let recorded = lines.clone();
+            set_trace(py, move |frame, event| {
+                if let TraceEvent::Line = event {
+                    if frame.code().name()?.to_string() == "add" {
+                        recorded.lock().unwrap().push(frame.line_number());
+                    }
+                }
+                Ok(())
+            });
+            let globals = PyDict::new_bound(py);
+            py.run_bound(CODE, Some(&globals), None).unwrap();
+            clear_trace(py);
+            assert_eq!(*lines.lock().unwrap(), [3, 4]);
+        });
+    }
+
+    #[test]
+    fn test_profile_error() {
+        Python::with_gil(|py| {
+            set_profile(py, |_frame, event| match event {
+                TraceEvent::Call => Err(PyRuntimeError::new_err("stop")),
+                _ => Ok(()),
+            });
+            let globals = PyDict::new_bound(py);
+            let err = py.run_bound(CODE, Some(&globals), None).unwrap_err();
+            clear_profile(py);
+            assert!(err.is_instance_of::<PyRuntimeError>(py));
+            // The profile function was removed by the error
+            py.run_bound(CODE, Some(&globals), None).unwrap();
+            assert_eq!(
+                globals
+                    .get_item("result")
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap(),
+                3
+            );
+        });
+    }
+}
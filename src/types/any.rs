@@ -232,6 +232,38 @@ impl PyAny {
         self.as_borrowed().compare(other)
     }
 
+    /// Returns an [`Ordering`] between `self` and `other`, or `None` if they are unordered.
+    ///
+    /// This is the [`PartialOrd::partial_cmp`]-style counterpart of [`compare`](Self::compare):
+    /// where `compare` raises `TypeError` because every comparison returned false (for example
+    /// when comparing with `float('nan')`), this returns `Ok(None)` instead. Errors raised by the
+    /// comparisons themselves are still propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyFloat;
+    /// use std::cmp::Ordering;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let a = PyFloat::new_bound(py, 0_f64);
+    ///     let b = PyFloat::new_bound(py, 42_f64);
+    ///     let nan = PyFloat::new_bound(py, f64::NAN);
+    ///     assert_eq!(a.partial_compare(&b)?, Some(Ordering::Less));
+    ///     assert_eq!(a.partial_compare(&nan)?, None);
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())}
+    /// ```
+    pub fn partial_compare<O>(&self, other: O) -> PyResult<Option<Ordering>>
+    where
+        O: ToPyObject,
+    {
+        self.as_borrowed().partial_compare(other)
+    }
+
     /// Tests whether two Python objects obey a given [`CompareOp`].
     ///
     /// [`lt`](Self::lt), [`le`](Self::le), [`eq`](Self::eq), [`ne`](Self::ne),
@@ -1085,6 +1117,35 @@ pub trait PyAnyMethods<'py> {
     where
         O: ToPyObject;
 
+    /// Returns an [`Ordering`] between `self` and `other`, or `None` if they are unordered.
+    ///
+    /// This is the [`PartialOrd::partial_cmp`]-style counterpart of [`compare`](Self::compare):
+    /// where `compare` raises `TypeError` because every comparison returned false (for example
+    /// when comparing with `float('nan')`), this returns `Ok(None)` instead. Errors raised by the
+    /// comparisons themselves are still propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyFloat;
+    /// use std::cmp::Ordering;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let a = PyFloat::new_bound(py, 0_f64);
+    ///     let b = PyFloat::new_bound(py, 42_f64);
+    ///     let nan = PyFloat::new_bound(py, f64::NAN);
+    ///     assert_eq!(a.partial_compare(&b)?, Some(Ordering::Less));
+    ///     assert_eq!(a.partial_compare(&nan)?, None);
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())}
+    /// ```
+    fn partial_compare<O>(&self, other: O) -> PyResult<Option<Ordering>>
+    where
+        O: ToPyObject;
+
     /// Tests whether two Python objects obey a given [`CompareOp`].
     ///
     /// [`lt`](Self::lt), [`le`](Self::le), [`eq`](Self::eq), [`ne`](Self::ne),
@@ -1812,7 +1873,15 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
     where
         O: ToPyObject,
     {
-        fn inner(any: &Bound<'_, PyAny>, other: Bound<'_, PyAny>) -> PyResult<Ordering> {
+        self.partial_compare(other)?
+            .ok_or_else(|| PyTypeError::new_err("PyAny::compare(): All comparisons returned false"))
+    }
+
+    fn partial_compare<O>(&self, other: O) -> PyResult<Option<Ordering>>
+    where
+        O: ToPyObject,
+    {
+        fn inner(any: &Bound<'_, PyAny>, other: Bound<'_, PyAny>) -> PyResult<Option<Ordering>> {
             let other = other.as_ptr();
             // Almost the same as ffi::PyObject_RichCompareBool, but this one doesn't try self == other.
             // See https://github.com/PyO3/pyo3/issues/985 for more.
@@ -1822,15 +1891,13 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
                     .and_then(|obj| obj.is_truthy())
             };
             if do_compare(other, ffi::Py_EQ)? {
-                Ok(Ordering::Equal)
+                Ok(Some(Ordering::Equal))
             } else if do_compare(other, ffi::Py_LT)? {
-                Ok(Ordering::Less)
+                Ok(Some(Ordering::Less))
             } else if do_compare(other, ffi::Py_GT)? {
-                Ok(Ordering::Greater)
+                Ok(Some(Ordering::Greater))
             } else {
-                Err(PyTypeError::new_err(
-                    "PyAny::compare(): All comparisons returned false",
-                ))
+                Ok(None)
             }
         }
 
@@ -1861,16 +1928,14 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
     where
         O: ToPyObject,
     {
-        self.rich_compare(other, CompareOp::Lt)
-            .and_then(|any| any.is_truthy())
+        rich_compare_bool(self, other, CompareOp::Lt)
     }
 
     fn le<O>(&self, other: O) -> PyResult<bool>
     where
         O: ToPyObject,
     {
-        self.rich_compare(other, CompareOp::Le)
-            .and_then(|any| any.is_truthy())
+        rich_compare_bool(self, other, CompareOp::Le)
     }
 
     fn eq<O>(&self, other: O) -> PyResult<bool>
@@ -1893,16 +1958,14 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
     where
         O: ToPyObject,
     {
-        self.rich_compare(other, CompareOp::Gt)
-            .and_then(|any| any.is_truthy())
+        rich_compare_bool(self, other, CompareOp::Gt)
     }
 
     fn ge<O>(&self, other: O) -> PyResult<bool>
     where
         O: ToPyObject,
     {
-        self.rich_compare(other, CompareOp::Ge)
-            .and_then(|any| any.is_truthy())
+        rich_compare_bool(self, other, CompareOp::Ge)
     }
 
     implement_binop!(add, PyNumber_Add, "+");
@@ -1979,6 +2042,11 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
                 unsafe {
                     ffi::PyObject_CallNoArgs(self.as_ptr()).assume_owned_or_err(self.py())
                 }
+            } else if #[cfg(all(Py_3_8, not(any(Py_LIMITED_API, PyPy))))] {
+                // Vectorcall path on python 3.8
+                unsafe {
+                    ffi::_PyObject_CallNoArg(self.as_ptr()).assume_owned_or_err(self.py())
+                }
             } else {
                 self.call((), None)
             }
@@ -1986,7 +2054,21 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
     }
 
     fn call1(&self, args: impl IntoPy<Py<PyTuple>>) -> PyResult<Bound<'py, PyAny>> {
-        self.call(args, None)
+        cfg_if::cfg_if! {
+            if #[cfg(all(Py_3_8, not(any(Py_LIMITED_API, PyPy))))] {
+                let py = self.py();
+                let args = args.into_py(py).into_bound(py);
+                // Optimized path for a single argument on python 3.8+
+                if let Some(arg) = single_arg(&args) {
+                    return unsafe {
+                        ffi::PyObject_CallOneArg(self.as_ptr(), arg).assume_owned_or_err(py)
+                    };
+                }
+                self.call(args, None)
+            } else {
+                self.call(args, None)
+            }
+        }
     }
 
     fn call_method<N, A>(
@@ -2027,7 +2109,23 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
         N: IntoPy<Py<PyString>>,
         A: IntoPy<Py<PyTuple>>,
     {
-        self.call_method(name, args, None)
+        cfg_if::cfg_if! {
+            if #[cfg(all(Py_3_9, not(any(Py_LIMITED_API, PyPy))))] {
+                let py = self.py();
+                let args = args.into_py(py).into_bound(py);
+                // Optimized path for a single argument on python 3.9+
+                if let Some(arg) = single_arg(&args) {
+                    return unsafe {
+                        let name = name.into_py(py).into_bound(py);
+                        ffi::PyObject_CallMethodOneArg(self.as_ptr(), name.as_ptr(), arg)
+                            .assume_owned_or_err(py)
+                    };
+                }
+                self.call_method(name, args, None)
+            } else {
+                self.call_method(name, args, None)
+            }
+        }
     }
 
     fn is_truthy(&self) -> PyResult<bool> {
@@ -2269,6 +2367,43 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
     }
 }
 
+/// Evaluates `any <op> other` to a `bool` without materializing the intermediate result object.
+///
+/// Only used for the ordering comparisons: for `==` and `!=`, `PyObject_RichCompareBool` assumes
+/// that identical objects are equal, which does not match the Python `==` operator (e.g. for NaN).
+fn rich_compare_bool<O>(any: &Bound<'_, PyAny>, other: O, compare_op: CompareOp) -> PyResult<bool>
+where
+    O: ToPyObject,
+{
+    fn inner(
+        any: &Bound<'_, PyAny>,
+        other: Bound<'_, PyAny>,
+        compare_op: CompareOp,
+    ) -> PyResult<bool> {
+        let result = unsafe {
+            ffi::PyObject_RichCompareBool(any.as_ptr(), other.as_ptr(), compare_op as c_int)
+        };
+        err::error_on_minusone(any.py(), result)?;
+        Ok(result == 1)
+    }
+
+    let py = any.py();
+    inner(any, other.to_object(py).into_bound(py), compare_op)
+}
+
+/// Returns the only element of `args`, to call with the one-argument fast paths of the C API.
+#[cfg(all(Py_3_8, not(any(Py_LIMITED_API, PyPy))))]
+#[inline]
+fn single_arg(args: &Bound<'_, PyTuple>) -> Option<*mut ffi::PyObject> {
+    unsafe {
+        if ffi::PyTuple_GET_SIZE(args.as_ptr()) == 1 {
+            Some(ffi::PyTuple_GET_ITEM(args.as_ptr(), 0))
+        } else {
+            None
+        }
+    }
+}
+
 impl<'py> Bound<'py, PyAny> {
     /// Retrieve an attribute value, skipping the instance dictionary during the lookup but still
     /// binding the object to the instance.
@@ -2325,9 +2460,14 @@ impl<'py> Bound<'py, PyAny> {
 mod tests {
     use crate::{
         basic::CompareOp,
-        types::{any::PyAnyMethods, IntoPyDict, PyAny, PyBool, PyList, PyLong, PyModule},
+        exceptions::PyTypeError,
+        types::{
+            any::PyAnyMethods, IntoPyDict, PyAny, PyBool, PyList, PyLong, PyModule, PyString,
+            PyTuple,
+        },
         PyNativeType, PyTypeInfo, Python, ToPyObject,
     };
+    use std::cmp::Ordering;
 
     #[test]
     fn test_lookup_special() {
@@ -2454,6 +2594,57 @@ class SimpleClass:
         })
     }
 
+    #[test]
+    fn test_call1_and_call_method1() {
+        Python::with_gil(|py| {
+            let module = PyModule::from_code(
+                py,
+                r#"
+def args(*args):
+    return args
+
+class SimpleClass:
+    def args(self, *args):
+        return args
+"#,
+                file!(),
+                "test_module",
+            )
+            .expect("module creation failed");
+
+            let args = module.getattr("args").unwrap();
+            let simple_class = module.getattr("SimpleClass").unwrap().call0().unwrap();
+            for callable_result in [args.call1(()), simple_class.call_method1("args", ())] {
+                assert!(callable_result
+                    .unwrap()
+                    .downcast::<PyTuple>()
+                    .unwrap()
+                    .is_empty());
+            }
+            for callable_result in [args.call1((1,)), simple_class.call_method1("args", (1,))] {
+                assert_eq!(callable_result.unwrap().extract::<(i32,)>().unwrap(), (1,));
+            }
+            for callable_result in [
+                args.call1(((1, 2),)),
+                simple_class.call_method1("args", ((1, 2),)),
+            ] {
+                assert_eq!(
+                    callable_result.unwrap().extract::<((i32, i32),)>().unwrap(),
+                    ((1, 2),)
+                );
+            }
+            for callable_result in [
+                args.call1((1, 2)),
+                simple_class.call_method1("args", (1, 2)),
+            ] {
+                assert_eq!(
+                    callable_result.unwrap().extract::<(i32, i32)>().unwrap(),
+                    (1, 2)
+                );
+            }
+        })
+    }
+
     #[test]
     fn test_type() {
         Python::with_gil(|py| {
@@ -2523,6 +2714,26 @@ class SimpleClass:
         Python::with_gil(|py| {
             let nan = py.eval("float('nan')", None, None).unwrap();
             assert!(nan.compare(nan).is_err());
+            assert_eq!(nan.partial_compare(nan).unwrap(), None);
+            assert!(!nan.eq(nan).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_partial_compare() {
+        Python::with_gil(|py| {
+            let one = 1i32.to_object(py).into_bound(py);
+            let two = 2i32.to_object(py).into_bound(py);
+            assert_eq!(one.partial_compare(&two).unwrap(), Some(Ordering::Less));
+            assert_eq!(two.partial_compare(&one).unwrap(), Some(Ordering::Greater));
+            assert_eq!(one.partial_compare(&one).unwrap(), Some(Ordering::Equal));
+
+            // errors raised by the comparison itself are propagated
+            let s = PyString::new_bound(py, "one");
+            assert!(one
+                .partial_compare(&s)
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
         });
     }
 
@@ -1,8 +1,12 @@
 #[cfg(feature = "experimental-inspect")]
 use crate::inspect::types::TypeInfo;
 use crate::{
-    exceptions::PyTypeError, ffi, ffi_ptr_ext::FfiPtrExt, instance::Bound, Borrowed, FromPyObject,
-    IntoPy, PyAny, PyNativeType, PyObject, PyResult, Python, ToPyObject,
+    conversion::{Strict, Truthy},
+    exceptions::PyTypeError,
+    ffi,
+    ffi_ptr_ext::FfiPtrExt,
+    instance::Bound,
+    Borrowed, FromPyObject, IntoPy, PyAny, PyNativeType, PyObject, PyResult, Python, ToPyObject,
 };
 
 use super::any::PyAnyMethods;
@@ -95,6 +99,8 @@ impl IntoPy<PyObject> for bool {
     }
 }
 
+into_pyobject_via_into_py!(bool);
+
 /// Converts a Python `bool` to a Rust `bool`.
 ///
 /// Fails with `TypeError` if the input is not a Python `bool`.
@@ -154,6 +160,28 @@ impl FromPyObject<'_> for bool {
     }
 }
 
+impl FromPyObject<'_> for Strict<bool> {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Strict(obj.downcast::<PyBool>()?.is_true()))
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_input() -> TypeInfo {
+        bool::type_input()
+    }
+}
+
+impl FromPyObject<'_> for Truthy {
+    fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        obj.is_truthy().map(Truthy)
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_input() -> TypeInfo {
+        TypeInfo::Any
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::any::PyAnyMethods;
@@ -181,4 +209,37 @@ mod tests {
             assert!(false.to_object(py).is(&*PyBool::new_bound(py, false)));
         });
     }
+
+    #[test]
+    fn test_strict_bool() {
+        use crate::conversion::Strict;
+
+        Python::with_gil(|py| {
+            let t = true.to_object(py).into_bound(py);
+            assert_eq!(t.extract::<Strict<bool>>().unwrap(), Strict(true));
+            let one = 1.to_object(py).into_bound(py);
+            assert!(one.extract::<Strict<bool>>().is_err());
+        });
+    }
+
+    #[test]
+    fn test_truthy() {
+        use crate::conversion::Truthy;
+
+        Python::with_gil(|py| {
+            let truthy = |code: &str| {
+                py.eval_bound(code, None, None)
+                    .unwrap()
+                    .extract::<Truthy>()
+                    .unwrap()
+            };
+            assert_eq!(truthy("True"), Truthy(true));
+            assert_eq!(truthy("'yes'"), Truthy(true));
+            assert_eq!(truthy("[]"), Truthy(false));
+            assert_eq!(truthy("None"), Truthy(false));
+
+            let string = py.eval_bound("'yes'", None, None).unwrap();
+            assert!(string.extract::<bool>().is_err());
+        });
+    }
 }
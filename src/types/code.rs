@@ -1,5 +1,6 @@
-use crate::ffi;
-use crate::PyAny;
+use crate::types::any::PyAnyMethods;
+use crate::types::PyString;
+use crate::{ffi, Bound, PyAny, PyNativeType, PyResult};
 
 /// Represents a Python code object.
 #[repr(transparent)]
@@ -10,3 +11,78 @@ pyobject_native_type_core!(
     pyobject_native_static_type_object!(ffi::PyCode_Type),
     #checkfunction=ffi::PyCode_Check
 );
+
+impl PyCode {
+    /// Returns the name of the file the code was compiled from (`co_filename`).
+    pub fn filename(&self) -> PyResult<&PyString> {
+        self.as_borrowed().filename().map(Bound::into_gil_ref)
+    }
+
+    /// Returns the name of the function, class or module the code belongs to (`co_name`).
+    pub fn name(&self) -> PyResult<&PyString> {
+        self.as_borrowed().name().map(Bound::into_gil_ref)
+    }
+
+    /// Returns the number of the first line of the code in its file (`co_firstlineno`).
+    pub fn first_line_number(&self) -> PyResult<i32> {
+        self.as_borrowed().first_line_number()
+    }
+}
+
+/// Implementation of functionality for [`PyCode`].
+///
+/// These methods are defined for the `Bound<'py, PyCode>` smart pointer, so to use method call
+/// syntax these methods are separated into a trait, because stable Rust does not yet support
+/// `arbitrary_self_types`.
+#[doc(alias = "PyCode")]
+pub trait PyCodeMethods<'py> {
+    /// Returns the name of the file the code was compiled from (`co_filename`).
+    fn filename(&self) -> PyResult<Bound<'py, PyString>>;
+
+    /// Returns the name of the function, class or module the code belongs to (`co_name`).
+    fn name(&self) -> PyResult<Bound<'py, PyString>>;
+
+    /// Returns the number of the first line of the code in its file (`co_firstlineno`).
+    fn first_line_number(&self) -> PyResult<i32>;
+}
+
+impl<'py> PyCodeMethods<'py> for Bound<'py, PyCode> {
+    fn filename(&self) -> PyResult<Bound<'py, PyString>> {
+        self.getattr(intern!(self.py(), "co_filename"))?
+            .downcast_into()
+            .map_err(Into::into)
+    }
+
+    fn name(&self) -> PyResult<Bound<'py, PyString>> {
+        self.getattr(intern!(self.py(), "co_name"))?
+            .downcast_into()
+            .map_err(Into::into)
+    }
+
+    fn first_line_number(&self) -> PyResult<i32> {
+        self.getattr(intern!(self.py(), "co_firstlineno"))?
+            .extract()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyCodeMethods;
+    use crate::types::any::PyAnyMethods;
+    use crate::types::string::PyStringMethods;
+    use crate::types::PyCode;
+    use crate::Python;
+
+    #[test]
+    fn test_code_attributes() {
+        Python::with_gil(|py| {
+            let code = py
+                .eval_bound("compile('\\n\\nx = 1', 'script.py', 'exec')", None, None)
+                .unwrap();
+            let code = code.downcast::<PyCode>().unwrap();
+            assert_eq!(code.filename().unwrap().to_cow().unwrap(), "script.py");
+            assert_eq!(code.name().unwrap().to_cow().unwrap(), "<module>");
+            assert_eq!(code.first_line_number().unwrap(), 1);
+        });
+    }
+}
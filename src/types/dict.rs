@@ -1,12 +1,19 @@
+use std::iter::FusedIterator;
+
 use super::PyMapping;
 use crate::err::{self, PyErr, PyResult};
+use crate::exceptions::PyRuntimeError;
 use crate::ffi::Py_ssize_t;
 use crate::ffi_ptr_ext::FfiPtrExt;
 use crate::instance::{Borrowed, Bound};
 use crate::py_result_ext::PyResultExt;
 use crate::types::any::PyAnyMethods;
 use crate::types::{PyAny, PyList};
-use crate::{ffi, PyNativeType, Python, ToPyObject};
+#[cfg(not(PyPy))]
+use crate::types::{PySet, PyString};
+#[cfg(not(PyPy))]
+use crate::PyTypeInfo;
+use crate::{ffi, FromPyObject, PyNativeType, Python, ToPyObject};
 
 /// Represents a Python `dict`.
 #[repr(transparent)]
@@ -185,6 +192,32 @@ impl PyDict {
         }
     }
 
+    /// Gets an item from the dictionary and extracts it to `T`.
+    ///
+    /// Returns `Ok(None)` if the item is not present. Unlike `get_item(key)?.extract()`, failing
+    /// to convert the item is an error rather than being confused with a missing key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::IntoPyDict;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let dict = [("a", 1)].into_py_dict_bound(py);
+    ///     assert_eq!(dict.get_item_as::<i32, _>("a").unwrap(), Some(1));
+    ///     assert_eq!(dict.get_item_as::<i32, _>("b").unwrap(), None);
+    ///     assert!(dict.get_item_as::<String, _>("a").is_err());
+    /// });
+    /// ```
+    pub fn get_item_as<'py, T, K>(&'py self, key: K) -> PyResult<Option<T>>
+    where
+        T: FromPyObject<'py>,
+        K: ToPyObject,
+    {
+        self.as_borrowed().get_item_as(key)
+    }
+
     /// Deprecated version of `get_item`.
     #[deprecated(
         since = "0.20.0",
@@ -240,6 +273,30 @@ impl PyDict {
         self.as_borrowed().items().into_gil_ref()
     }
 
+    /// Returns a view of the dict keys.
+    ///
+    /// This is equivalent to the Python expression `dict.keys()`.
+    #[cfg(not(PyPy))]
+    pub fn keys_view(&self) -> &PyDictKeys {
+        self.as_borrowed().keys_view().into_gil_ref()
+    }
+
+    /// Returns a view of the dict values.
+    ///
+    /// This is equivalent to the Python expression `dict.values()`.
+    #[cfg(not(PyPy))]
+    pub fn values_view(&self) -> &PyDictValues {
+        self.as_borrowed().values_view().into_gil_ref()
+    }
+
+    /// Returns a view of the dict items.
+    ///
+    /// This is equivalent to the Python expression `dict.items()`.
+    #[cfg(not(PyPy))]
+    pub fn items_view(&self) -> &PyDictItems {
+        self.as_borrowed().items_view().into_gil_ref()
+    }
+
     /// Returns an iterator of `(key, value)` pairs in this dictionary.
     ///
     /// # Panics
@@ -251,6 +308,15 @@ impl PyDict {
         PyDictIterator(self.as_borrowed().iter())
     }
 
+    /// Returns an iterator of `(key, value)` pairs in this dictionary, which returns a
+    /// `RuntimeError` instead of panicking if the dictionary is mutated during iteration.
+    ///
+    /// This is useful when the loop body runs arbitrary Python code, which may modify the
+    /// dictionary. After returning an error the iterator is exhausted.
+    pub fn try_iter(&self) -> PyDictTryIterator<'_> {
+        PyDictTryIterator(self.as_borrowed().try_iter())
+    }
+
     /// Returns `self` cast as a `PyMapping`.
     pub fn as_mapping(&self) -> &PyMapping {
         unsafe { self.downcast_unchecked() }
@@ -316,6 +382,15 @@ pub trait PyDictMethods<'py> {
     where
         K: ToPyObject;
 
+    /// Gets an item from the dictionary and extracts it to `T`.
+    ///
+    /// Returns `Ok(None)` if the item is not present. Unlike `get_item(key)?.extract()`, failing
+    /// to convert the item is an error rather than being confused with a missing key.
+    fn get_item_as<T, K>(&self, key: K) -> PyResult<Option<T>>
+    where
+        T: FromPyObject<'py>,
+        K: ToPyObject;
+
     /// Sets an item value.
     ///
     /// This is equivalent to the Python statement `self[key] = value`.
@@ -346,6 +421,46 @@ pub trait PyDictMethods<'py> {
     /// This is equivalent to the Python expression `list(dict.items())`.
     fn items(&self) -> Bound<'py, PyList>;
 
+    /// Returns a view of the dict keys.
+    ///
+    /// This is equivalent to the Python expression `dict.keys()`. Unlike [`keys`][Self::keys],
+    /// this does not copy the keys into a new list, and the view reflects later changes to
+    /// the dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::IntoPyDict;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let dict = [("a", 1), ("b", 2)].into_py_dict_bound(py);
+    ///     let keys = dict.keys_view();
+    ///     assert!(keys.contains("a")?);
+    ///
+    ///     let other = [("b", 3), ("c", 4)].into_py_dict_bound(py);
+    ///     let common = keys.intersection(&other.keys_view())?;
+    ///     assert!(common.contains("b")?);
+    ///     assert_eq!(common.len(), 1);
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    #[cfg(not(PyPy))]
+    fn keys_view(&self) -> Bound<'py, PyDictKeys>;
+
+    /// Returns a view of the dict values.
+    ///
+    /// This is equivalent to the Python expression `dict.values()`.
+    #[cfg(not(PyPy))]
+    fn values_view(&self) -> Bound<'py, PyDictValues>;
+
+    /// Returns a view of the dict items.
+    ///
+    /// This is equivalent to the Python expression `dict.items()`.
+    #[cfg(not(PyPy))]
+    fn items_view(&self) -> Bound<'py, PyDictItems>;
+
     /// Returns an iterator of `(key, value)` pairs in this dictionary.
     ///
     /// # Panics
@@ -355,6 +470,34 @@ pub trait PyDictMethods<'py> {
     /// so long as the set of keys does not change.
     fn iter(&self) -> BoundDictIterator<'py>;
 
+    /// Returns an iterator of `(key, value)` pairs in this dictionary, which returns a
+    /// `RuntimeError` instead of panicking if the dictionary is mutated during iteration.
+    ///
+    /// This is useful when the loop body runs arbitrary Python code, which may modify the
+    /// dictionary. After returning an error the iterator is exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::exceptions::PyRuntimeError;
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::IntoPyDict;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let dict = [("a", 1), ("b", 2)].into_py_dict_bound(py);
+    ///     let mut iter = dict.try_iter();
+    ///     let (key, _value) = iter.next().unwrap()?;
+    ///     dict.del_item(key)?;
+    ///
+    ///     let err = iter.next().unwrap().unwrap_err();
+    ///     assert!(err.is_instance_of::<PyRuntimeError>(py));
+    ///     assert!(iter.next().is_none());
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    fn try_iter(&self) -> BoundDictTryIterator<'py>;
+
     /// Returns `self` cast as a `PyMapping`.
     fn as_mapping(&self) -> &Bound<'py, PyMapping>;
 
@@ -435,6 +578,14 @@ impl<'py> PyDictMethods<'py> for Bound<'py, PyDict> {
         inner(self, key.to_object(py).into_bound(py))
     }
 
+    fn get_item_as<T, K>(&self, key: K) -> PyResult<Option<T>>
+    where
+        T: FromPyObject<'py>,
+        K: ToPyObject,
+    {
+        self.get_item(key)?.map(|item| item.extract()).transpose()
+    }
+
     fn set_item<K, V>(&self, key: K, value: V) -> PyResult<()>
     where
         K: ToPyObject,
@@ -496,10 +647,29 @@ impl<'py> PyDictMethods<'py> for Bound<'py, PyDict> {
         }
     }
 
+    #[cfg(not(PyPy))]
+    fn keys_view(&self) -> Bound<'py, PyDictKeys> {
+        dict_view(self, intern!(self.py(), "keys"))
+    }
+
+    #[cfg(not(PyPy))]
+    fn values_view(&self) -> Bound<'py, PyDictValues> {
+        dict_view(self, intern!(self.py(), "values"))
+    }
+
+    #[cfg(not(PyPy))]
+    fn items_view(&self) -> Bound<'py, PyDictItems> {
+        dict_view(self, intern!(self.py(), "items"))
+    }
+
     fn iter(&self) -> BoundDictIterator<'py> {
         BoundDictIterator::new(self.clone())
     }
 
+    fn try_iter(&self) -> BoundDictTryIterator<'py> {
+        BoundDictTryIterator(BoundDictIterator::new(self.clone()))
+    }
+
     fn as_mapping(&self) -> &Bound<'py, PyMapping> {
         unsafe { self.downcast_unchecked() }
     }
@@ -517,6 +687,161 @@ impl<'py> PyDictMethods<'py> for Bound<'py, PyDict> {
     }
 }
 
+#[cfg(not(PyPy))]
+fn dict_view<'py, T: PyTypeInfo>(
+    dict: &Bound<'py, PyDict>,
+    method: &Bound<'py, PyString>,
+) -> Bound<'py, T> {
+    // Call e.g. `dict.keys(d)` rather than `d.keys()`, so that subclasses overriding
+    // the method cannot return something other than the builtin view type.
+    unsafe {
+        ffi::PyObject_CallMethodObjArgs(
+            PyDict::type_object_raw(dict.py()).cast(),
+            method.as_ptr(),
+            dict.as_ptr(),
+            std::ptr::null_mut::<ffi::PyObject>(),
+        )
+        .assume_owned(dict.py())
+        .downcast_into_unchecked()
+    }
+}
+
+/// Implementation of functionality shared by [`PyDictKeys`], [`PyDictValues`] and
+/// [`PyDictItems`].
+///
+/// These methods are defined for the `Bound<'py, PyDictKeys>` (and friends) smart pointers,
+/// so to use the methods you need to import this trait.
+#[cfg(not(PyPy))]
+pub trait PyDictViewMethods<'py> {
+    /// Returns the number of items in the underlying dictionary.
+    ///
+    /// This is equivalent to the Python expression `len(view)`.
+    fn len(&self) -> usize;
+
+    /// Checks if the underlying dictionary is empty.
+    fn is_empty(&self) -> bool;
+
+    /// Determines if the view contains the specified element.
+    ///
+    /// This is equivalent to the Python expression `value in view`.
+    fn contains<V>(&self, value: V) -> PyResult<bool>
+    where
+        V: ToPyObject;
+}
+
+/// Set operations on [`PyDictKeys`] and [`PyDictItems`].
+///
+/// Each operation returns a new `set` and does not modify the underlying dictionary.
+#[cfg(not(PyPy))]
+pub trait PyDictSetViewMethods<'py>: PyDictViewMethods<'py> {
+    /// Returns a new set with the elements of both this view and `other`.
+    ///
+    /// This is equivalent to the Python expression `self | other`.
+    fn union(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>>;
+
+    /// Returns a new set with the elements common to this view and `other`.
+    ///
+    /// This is equivalent to the Python expression `self & other`.
+    fn intersection(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>>;
+
+    /// Returns a new set with the elements of this view that are not in `other`.
+    ///
+    /// This is equivalent to the Python expression `self - other`.
+    fn difference(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>>;
+
+    /// Returns a new set with the elements in either this view or `other`, but not both.
+    ///
+    /// This is equivalent to the Python expression `self ^ other`.
+    fn symmetric_difference(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>>;
+
+    /// Returns `true` if this view has no elements in common with `other`.
+    ///
+    /// This is equivalent to the Python expression `self.isdisjoint(other)`.
+    fn is_disjoint(&self, other: &Bound<'_, PyAny>) -> PyResult<bool>;
+}
+
+#[cfg(not(PyPy))]
+macro_rules! impl_dict_view_methods {
+    ($ty:ty) => {
+        impl<'py> PyDictViewMethods<'py> for Bound<'py, $ty> {
+            fn len(&self) -> usize {
+                unsafe { ffi::PyObject_Size(self.as_ptr()) as usize }
+            }
+
+            fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            fn contains<V>(&self, value: V) -> PyResult<bool>
+            where
+                V: ToPyObject,
+            {
+                let py = self.py();
+                let value = value.to_object(py);
+                match unsafe { ffi::PySequence_Contains(self.as_ptr(), value.as_ptr()) } {
+                    1 => Ok(true),
+                    0 => Ok(false),
+                    _ => Err(PyErr::fetch(py)),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(PyPy))]
+impl_dict_view_methods!(PyDictKeys);
+#[cfg(not(PyPy))]
+impl_dict_view_methods!(PyDictValues);
+#[cfg(not(PyPy))]
+impl_dict_view_methods!(PyDictItems);
+
+#[cfg(not(PyPy))]
+fn dict_view_set_op<'py>(
+    view: &Bound<'py, PyAny>,
+    other: &Bound<'_, PyAny>,
+    op: unsafe extern "C" fn(*mut ffi::PyObject, *mut ffi::PyObject) -> *mut ffi::PyObject,
+) -> PyResult<Bound<'py, PySet>> {
+    unsafe { op(view.as_ptr(), other.as_ptr()).assume_owned_or_err(view.py()) }?
+        .downcast_into()
+        .map_err(Into::into)
+}
+
+#[cfg(not(PyPy))]
+macro_rules! impl_dict_set_view_methods {
+    ($ty:ty) => {
+        impl<'py> PyDictSetViewMethods<'py> for Bound<'py, $ty> {
+            fn union(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>> {
+                dict_view_set_op(self.as_any(), other, ffi::PyNumber_Or)
+            }
+
+            fn intersection(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>> {
+                dict_view_set_op(self.as_any(), other, ffi::PyNumber_And)
+            }
+
+            fn difference(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>> {
+                dict_view_set_op(self.as_any(), other, ffi::PyNumber_Subtract)
+            }
+
+            fn symmetric_difference(
+                &self,
+                other: &Bound<'_, PyAny>,
+            ) -> PyResult<Bound<'py, PySet>> {
+                dict_view_set_op(self.as_any(), other, ffi::PyNumber_Xor)
+            }
+
+            fn is_disjoint(&self, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+                self.call_method1(intern!(self.py(), "isdisjoint"), (other,))?
+                    .is_truthy()
+            }
+        }
+    };
+}
+
+#[cfg(not(PyPy))]
+impl_dict_set_view_methods!(PyDictKeys);
+#[cfg(not(PyPy))]
+impl_dict_set_view_methods!(PyDictItems);
+
 fn dict_len(dict: &Bound<'_, PyDict>) -> Py_ssize_t {
     #[cfg(any(not(Py_3_8), PyPy, Py_LIMITED_API))]
     unsafe {
@@ -575,19 +900,52 @@ impl<'py> Iterator for BoundDictIterator<'py> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(item) => item,
+            Err(msg) => panic!("{}", msg),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'py> ExactSizeIterator for BoundDictIterator<'py> {
+    fn len(&self) -> usize {
+        self.len as usize
+    }
+}
+
+impl<'py> BoundDictIterator<'py> {
+    fn new(dict: Bound<'py, PyDict>) -> Self {
+        let len = dict_len(&dict);
+        BoundDictIterator {
+            dict,
+            ppos: 0,
+            di_used: len,
+            len,
+        }
+    }
+
+    /// Advances the iterator, or returns an error message if the dictionary was mutated.
+    #[inline]
+    fn try_next(&mut self) -> Result<Option<<Self as Iterator>::Item>, &'static str> {
         let ma_used = dict_len(&self.dict);
 
         // These checks are similar to what CPython does.
         //
         // If the dimension of the dict changes e.g. key-value pairs are removed
-        // or added during iteration, this will panic next time when `next` is called
+        // or added during iteration, this will fail next time when `next` is called
         if self.di_used != ma_used {
             self.di_used = -1;
-            panic!("dictionary changed size during iteration");
+            return Err("dictionary changed size during iteration");
         };
 
         // If the dict is changed in such a way that the length remains constant
-        // then this will panic at the end of iteration - similar to this:
+        // then this will fail at the end of iteration - similar to this:
         //
         // d = {"a":1, "b":2, "c": 3}
         //
@@ -598,7 +956,7 @@ impl<'py> Iterator for BoundDictIterator<'py> {
         //
         if self.len == -1 {
             self.di_used = -1;
-            panic!("dictionary keys changed during iteration");
+            return Err("dictionary keys changed during iteration");
         };
 
         let mut key: *mut ffi::PyObject = std::ptr::null_mut();
@@ -612,40 +970,56 @@ impl<'py> Iterator for BoundDictIterator<'py> {
             // Safety:
             // - PyDict_Next returns borrowed values
             // - we have already checked that `PyDict_Next` succeeded, so we can assume these to be non-null
-            Some((
+            Ok(Some((
                 unsafe { key.assume_borrowed_unchecked(py) }.to_owned(),
                 unsafe { value.assume_borrowed_unchecked(py) }.to_owned(),
-            ))
+            )))
         } else {
-            None
+            Ok(None)
         }
     }
+}
+
+/// PyO3 implementation of a fallible iterator for a Python `dict` object.
+///
+/// Returned by [`PyDict::try_iter`].
+pub struct PyDictTryIterator<'py>(BoundDictTryIterator<'py>);
+
+impl<'py> Iterator for PyDictTryIterator<'py> {
+    type Item = PyResult<(&'py PyAny, &'py PyAny)>;
 
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.len();
-        (len, Some(len))
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.0.next()?;
+        Some(item.map(|(key, value)| (key.into_gil_ref(), value.into_gil_ref())))
     }
 }
 
-impl<'py> ExactSizeIterator for BoundDictIterator<'py> {
-    fn len(&self) -> usize {
-        self.len as usize
-    }
-}
+impl FusedIterator for PyDictTryIterator<'_> {}
 
-impl<'py> BoundDictIterator<'py> {
-    fn new(dict: Bound<'py, PyDict>) -> Self {
-        let len = dict_len(&dict);
-        BoundDictIterator {
-            dict,
-            ppos: 0,
-            di_used: len,
-            len,
+/// PyO3 implementation of a fallible iterator for a Python `dict` object.
+///
+/// Returned by [`PyDictMethods::try_iter`].
+pub struct BoundDictTryIterator<'py>(BoundDictIterator<'py>);
+
+impl<'py> Iterator for BoundDictTryIterator<'py> {
+    type Item = PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>)>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // The error has already been returned
+        if self.0.di_used == -1 {
+            return None;
         }
+        self.0
+            .try_next()
+            .map_err(PyRuntimeError::new_err)
+            .transpose()
     }
 }
 
+impl FusedIterator for BoundDictTryIterator<'_> {}
+
 impl<'py> IntoIterator for Bound<'py, PyDict> {
     type Item = (Bound<'py, PyAny>, Bound<'py, PyAny>);
     type IntoIter = BoundDictIterator<'py>;
@@ -736,6 +1110,8 @@ mod tests {
     #[cfg(not(PyPy))]
     use crate::exceptions;
     #[cfg(not(PyPy))]
+    use crate::types::set::PySetMethods;
+    #[cfg(not(PyPy))]
     use crate::types::PyList;
     use crate::{types::PyTuple, Python, ToPyObject};
     use std::collections::{BTreeMap, HashMap};
@@ -864,6 +1240,23 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_item_as() {
+        Python::with_gil(|py| {
+            let dict = [("a", 1)].into_py_dict_bound(py);
+            assert_eq!(dict.get_item_as::<i32, _>("a").unwrap(), Some(1));
+            assert_eq!(dict.get_item_as::<i32, _>("b").unwrap(), None);
+
+            // A conversion failure is not mistaken for a missing key
+            let err = dict.get_item_as::<String, _>("a").unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyTypeError>(py));
+
+            // Neither is an unhashable key
+            let unhashable = PyDict::new_bound(py);
+            assert!(dict.get_item_as::<i32, _>(unhashable).is_err());
+        });
+    }
+
     #[test]
     #[allow(deprecated)]
     #[cfg(not(PyPy))]
@@ -1118,6 +1511,70 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_try_iter_key_mutated() {
+        Python::with_gil(|py| {
+            let dict = [(0, 0), (2, 2), (4, 4)].into_py_dict_bound(py);
+
+            let mut errors = 0;
+            for (i, item) in dict.try_iter().enumerate() {
+                match item {
+                    Ok((key, value)) => {
+                        let key = key.extract::<i32>().unwrap();
+                        dict.set_item(key + 1, value).unwrap();
+                    }
+                    Err(err) => {
+                        assert!(err.is_instance_of::<crate::exceptions::PyRuntimeError>(py));
+                        assert_eq!(
+                            err.to_string(),
+                            "RuntimeError: dictionary changed size during iteration"
+                        );
+                        errors += 1;
+                    }
+                }
+                assert!(i < 10, "iteration should stop after the error");
+            }
+            assert_eq!(errors, 1);
+        });
+    }
+
+    #[test]
+    fn test_try_iter_key_mutated_constant_len() {
+        Python::with_gil(|py| {
+            let dict = (0..10).map(|i| (i * 2, i * 2)).into_py_dict_bound(py);
+
+            let result: PyResult<Vec<_>> = dict
+                .try_iter()
+                .take(1000)
+                .map(|item| {
+                    let (key, value) = item?;
+                    let key = key.extract::<i32>()?;
+                    dict.del_item(key)?;
+                    dict.set_item(key + 1, value)?;
+                    Ok(key)
+                })
+                .collect();
+            assert!(result
+                .unwrap_err()
+                .is_instance_of::<crate::exceptions::PyRuntimeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_try_iter_unmodified() {
+        Python::with_gil(|py| {
+            let dict = [(7, 32), (8, 42)].into_py_dict(py);
+            let items: PyResult<Vec<(i32, i32)>> = dict
+                .try_iter()
+                .map(|item| {
+                    let (key, value) = item?;
+                    Ok((key.extract()?, value.extract()?))
+                })
+                .collect();
+            assert_eq!(items.unwrap(), [(7, 32), (8, 42)]);
+        });
+    }
+
     #[test]
     fn test_iter_size_hint() {
         Python::with_gil(|py| {
@@ -1310,6 +1767,92 @@ mod tests {
         })
     }
 
+    #[test]
+    #[cfg(not(PyPy))]
+    fn dict_views_reflect_mutation() {
+        Python::with_gil(|py| {
+            let dict = abc_dict(py);
+            let keys = dict.keys_view();
+            let values = dict.values_view();
+            let items = dict.items_view();
+            assert_eq!(keys.len(), 3);
+            assert!(!values.is_empty());
+
+            dict.set_item("d", 4).unwrap();
+            assert_eq!(keys.len(), 4);
+            assert!(keys.contains("d").unwrap());
+            assert!(values.contains(4).unwrap());
+            assert!(items.contains(("d", 4)).unwrap());
+            assert!(!items.contains(("d", 5)).unwrap());
+
+            dict.clear();
+            assert!(keys.is_empty());
+            assert!(!keys.contains("a").unwrap());
+        })
+    }
+
+    #[test]
+    #[cfg(not(PyPy))]
+    fn dict_view_set_operations() {
+        Python::with_gil(|py| {
+            let dict = abc_dict(py);
+            let keys = dict.keys_view();
+            let other = [("b", 1), ("z", 26)].into_py_dict_bound(py).keys_view();
+
+            let union = keys.union(&other).unwrap();
+            assert_eq!(union.len(), 4);
+            let intersection = keys.intersection(&other).unwrap();
+            assert_eq!(intersection.len(), 1);
+            assert!(intersection.contains("b").unwrap());
+            let difference = keys.difference(&other).unwrap();
+            assert_eq!(difference.len(), 2);
+            assert!(!difference.contains("b").unwrap());
+            let symmetric_difference = keys.symmetric_difference(&other).unwrap();
+            assert_eq!(symmetric_difference.len(), 3);
+            assert!(symmetric_difference.contains("z").unwrap());
+
+            assert!(!keys.is_disjoint(&other).unwrap());
+            assert!(keys.is_disjoint(&PyList::new_bound(py, ["x"])).unwrap());
+
+            let items = dict.items_view();
+            let common = items
+                .intersection(&PyList::new_bound(py, [("a", 1), ("b", 5)]))
+                .unwrap();
+            assert_eq!(common.len(), 1);
+            assert!(common.contains(("a", 1)).unwrap());
+
+            // unsupported operand types raise TypeError
+            assert!(keys
+                .union(&1.to_object(py).into_bound(py))
+                .unwrap_err()
+                .is_instance_of::<exceptions::PyTypeError>(py));
+        })
+    }
+
+    #[test]
+    #[cfg(not(PyPy))]
+    fn dict_views_ignore_overridden_methods() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new_bound(py);
+            py.run_bound(
+                r#"
+class D(dict):
+    def keys(self):
+        return ["not", "a", "view"]
+
+d = D(a=1)
+"#,
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+            let dict = locals.get_item("d").unwrap().unwrap();
+            let keys = dict.downcast::<PyDict>().unwrap().keys_view();
+            assert!(keys.is_instance_of::<PyDictKeys>());
+            assert_eq!(keys.len(), 1);
+        })
+    }
+
     #[test]
     fn dict_update() {
         Python::with_gil(|py| {
@@ -1,8 +1,8 @@
 #[cfg(feature = "experimental-inspect")]
 use crate::inspect::types::TypeInfo;
 use crate::{
-    ffi, ffi_ptr_ext::FfiPtrExt, instance::Bound, FromPyObject, IntoPy, PyAny, PyErr, PyNativeType,
-    PyObject, PyResult, Python, ToPyObject,
+    conversion::Strict, ffi, ffi_ptr_ext::FfiPtrExt, instance::Bound, DowncastError, FromPyObject,
+    IntoPy, PyAny, PyErr, PyNativeType, PyObject, PyResult, Python, ToPyObject,
 };
 use std::os::raw::c_double;
 
@@ -95,6 +95,8 @@ impl IntoPy<PyObject> for f64 {
     }
 }
 
+into_pyobject_via_into_py!(f64);
+
 impl<'py> FromPyObject<'py> for f64 {
     // PyFloat_AsDouble returns -1.0 upon failure
     #![allow(clippy::float_cmp)]
@@ -142,6 +144,8 @@ impl IntoPy<PyObject> for f32 {
     }
 }
 
+into_pyobject_via_into_py!(f32);
+
 impl<'py> FromPyObject<'py> for f32 {
     fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         Ok(obj.extract::<f64>()? as f32)
@@ -153,6 +157,29 @@ impl<'py> FromPyObject<'py> for f32 {
     }
 }
 
+macro_rules! strict_float {
+    ($($t:ty),*) => {
+        $(
+            impl<'py> FromPyObject<'py> for Strict<$t> {
+                fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+                    if obj.is_instance_of::<PyFloat>() {
+                        obj.extract().map(Strict)
+                    } else {
+                        Err(DowncastError::new(obj, "float").into())
+                    }
+                }
+
+                #[cfg(feature = "experimental-inspect")]
+                fn type_input() -> TypeInfo {
+                    <$t>::type_input()
+                }
+            }
+        )*
+    };
+}
+
+strict_float!(f32, f64);
+
 #[cfg(test)]
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
@@ -188,4 +215,25 @@ mod tests {
             assert_approx_eq!(v, obj.value());
         });
     }
+
+    #[test]
+    fn test_strict_float() {
+        use crate::conversion::Strict;
+        use crate::types::any::PyAnyMethods;
+
+        Python::with_gil(|py| {
+            let float = 1.5f64.to_object(py).into_bound(py);
+            assert_eq!(float.extract::<Strict<f64>>().unwrap(), Strict(1.5));
+            assert_eq!(float.extract::<Strict<f32>>().unwrap(), Strict(1.5));
+
+            let int = 1.to_object(py).into_bound(py);
+            assert_eq!(int.extract::<f64>().unwrap(), 1.0);
+            assert_eq!(
+                int.extract::<Strict<f64>>().unwrap_err().to_string(),
+                "TypeError: 'int' object cannot be converted to 'float'"
+            );
+            let bool = true.to_object(py).into_bound(py);
+            assert!(bool.extract::<Strict<f64>>().is_err());
+        });
+    }
 }
@@ -1,5 +1,7 @@
-use crate::ffi;
-use crate::PyAny;
+use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::types::any::PyAnyMethods;
+use crate::types::{PyCode, PyDict};
+use crate::{ffi, Bound, PyAny, PyNativeType, PyResult};
 
 /// Represents a Python frame.
 #[repr(transparent)]
@@ -10,3 +12,197 @@ pyobject_native_type_core!(
     pyobject_native_static_type_object!(ffi::PyFrame_Type),
     #checkfunction=ffi::PyFrame_Check
 );
+
+impl PyFrame {
+    /// Returns the code object being executed in the frame.
+    pub fn code(&self) -> &PyCode {
+        self.as_borrowed().code().into_gil_ref()
+    }
+
+    /// Returns the line number currently being executed in the frame.
+    pub fn line_number(&self) -> i32 {
+        self.as_borrowed().line_number()
+    }
+
+    /// Returns the local variables of the frame (`f_locals`).
+    pub fn locals(&self) -> PyResult<&PyAny> {
+        self.as_borrowed().locals().map(Bound::into_gil_ref)
+    }
+
+    /// Returns the global variables of the frame (`f_globals`).
+    pub fn globals(&self) -> PyResult<&PyDict> {
+        self.as_borrowed().globals().map(Bound::into_gil_ref)
+    }
+
+    /// Returns the frame which called this frame, if any (`f_back`).
+    pub fn back(&self) -> Option<&PyFrame> {
+        self.as_borrowed().back().map(Bound::into_gil_ref)
+    }
+}
+
+/// Implementation of functionality for [`PyFrame`].
+///
+/// These methods are defined for the `Bound<'py, PyFrame>` smart pointer, so to use method call
+/// syntax these methods are separated into a trait, because stable Rust does not yet support
+/// `arbitrary_self_types`.
+///
+/// # Examples
+///
+/// Reporting the Python code which called a Rust function:
+///
+/// ```rust
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction]
+/// fn call_site(py: Python<'_>) -> PyResult<String> {
+///     let frame = py.current_frame().expect("called from Python");
+///     let code = frame.code();
+///     Ok(format!(
+///         "{}:{} in {}",
+///         code.filename()?,
+///         frame.line_number(),
+///         code.name()?
+///     ))
+/// }
+/// #
+/// # Python::with_gil(|py| {
+/// #     let call_site = wrap_pyfunction!(call_site, py).unwrap();
+/// #     pyo3::py_run!(py, call_site, "assert call_site() == '<string>:1 in <module>'");
+/// # });
+/// ```
+#[doc(alias = "PyFrame")]
+pub trait PyFrameMethods<'py> {
+    /// Returns the code object being executed in the frame.
+    fn code(&self) -> Bound<'py, PyCode>;
+
+    /// Returns the line number currently being executed in the frame.
+    fn line_number(&self) -> i32;
+
+    /// Returns the local variables of the frame (`f_locals`).
+    ///
+    /// This is usually a `dict`, but can be another mapping: for example, it is a write-through
+    /// proxy for function frames on Python 3.13 and newer.
+    fn locals(&self) -> PyResult<Bound<'py, PyAny>>;
+
+    /// Returns the global variables of the frame (`f_globals`).
+    fn globals(&self) -> PyResult<Bound<'py, PyDict>>;
+
+    /// Returns the frame which called this frame, if any (`f_back`).
+    fn back(&self) -> Option<Bound<'py, PyFrame>>;
+}
+
+impl<'py> PyFrameMethods<'py> for Bound<'py, PyFrame> {
+    fn code(&self) -> Bound<'py, PyCode> {
+        let frame = self.as_ptr().cast::<ffi::PyFrameObject>();
+        unsafe {
+            #[cfg(Py_3_9)]
+            let code = ffi::PyFrame_GetCode(frame)
+                .cast::<ffi::PyObject>()
+                .assume_owned(self.py());
+            #[cfg(not(Py_3_9))]
+            let code = (*frame)
+                .f_code
+                .cast::<ffi::PyObject>()
+                .assume_borrowed(self.py())
+                .to_owned();
+            code.downcast_into_unchecked()
+        }
+    }
+
+    fn line_number(&self) -> i32 {
+        unsafe { ffi::PyFrame_GetLineNumber(self.as_ptr().cast()) }
+    }
+
+    fn locals(&self) -> PyResult<Bound<'py, PyAny>> {
+        #[cfg(Py_3_11)]
+        unsafe {
+            ffi::PyFrame_GetLocals(self.as_ptr().cast()).assume_owned_or_err(self.py())
+        }
+        #[cfg(not(Py_3_11))]
+        {
+            self.getattr(intern!(self.py(), "f_locals"))
+        }
+    }
+
+    fn globals(&self) -> PyResult<Bound<'py, PyDict>> {
+        #[cfg(Py_3_11)]
+        let globals =
+            unsafe { ffi::PyFrame_GetGlobals(self.as_ptr().cast()).assume_owned_or_err(self.py()) };
+        #[cfg(not(Py_3_11))]
+        let globals = self.getattr(intern!(self.py(), "f_globals"));
+        globals?.downcast_into().map_err(Into::into)
+    }
+
+    fn back(&self) -> Option<Bound<'py, PyFrame>> {
+        let frame = self.as_ptr().cast::<ffi::PyFrameObject>();
+        unsafe {
+            #[cfg(Py_3_9)]
+            let back = ffi::PyFrame_GetBack(frame)
+                .cast::<ffi::PyObject>()
+                .assume_owned_or_opt(self.py());
+            #[cfg(not(Py_3_9))]
+            let back = (*frame)
+                .f_back
+                .cast::<ffi::PyObject>()
+                .assume_borrowed_or_opt(self.py())
+                .map(|back| back.to_owned());
+            back.map(|back| back.downcast_into_unchecked())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyFrameMethods;
+    use crate::types::any::PyAnyMethods;
+    use crate::types::code::PyCodeMethods;
+    use crate::types::dict::PyDictMethods;
+    use crate::types::string::PyStringMethods;
+    use crate::types::{PyDict, PyFrame};
+    use crate::Python;
+
+    #[test]
+    fn test_frame_inspection() {
+        Python::with_gil(|py| {
+            let globals = PyDict::new_bound(py);
+            py.run_bound(
+                r#"
+import sys
+
+def outer():
+    return inner(10)
+
+def inner(x):
+    y = x + 1
+    return sys._getframe()
+"#,
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let frame = py.eval_bound("outer()", Some(&globals), None).unwrap();
+            let frame = frame.downcast::<PyFrame>().unwrap();
+
+            let code = frame.code();
+            assert_eq!(code.name().unwrap().to_cow().unwrap(), "inner");
+            assert_eq!(frame.line_number(), 9);
+
+            let locals = frame.locals().unwrap();
+            assert_eq!(locals.get_item("x").unwrap().extract::<i32>().unwrap(), 10);
+            assert_eq!(locals.get_item("y").unwrap().extract::<i32>().unwrap(), 11);
+            assert!(frame.globals().unwrap().is(&globals));
+
+            let back = frame.back().unwrap();
+            assert_eq!(back.code().name().unwrap().to_cow().unwrap(), "outer");
+            assert_eq!(back.line_number(), 5);
+            assert!(back.globals().unwrap().contains("inner").unwrap());
+        });
+    }
+
+    #[test]
+    fn test_current_frame() {
+        Python::with_gil(|py| {
+            assert!(py.current_frame().is_none());
+        });
+    }
+}
@@ -45,6 +45,29 @@ impl<'py> PyFrozenSetBuilder<'py> {
     }
 }
 
+/// Wrapper which converts a Rust set to a Python `frozenset`, rather than a `set`.
+///
+/// Sets such as [`HashSet`](std::collections::HashSet) and
+/// [`BTreeSet`](std::collections::BTreeSet) are converted to a mutable `set` by default. A
+/// `frozenset` is hashable, so it can be used as a dictionary key or an item of another set:
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::types::AsFrozenSet;
+/// use std::collections::{BTreeSet, HashMap};
+///
+/// Python::with_gil(|py| {
+///     let mut groups = HashMap::new();
+///     groups.insert(AsFrozenSet(BTreeSet::from([1, 2])), "small");
+///     let groups = groups.into_py(py);
+///     pyo3::py_run!(py, groups, "assert groups[frozenset({1, 2})] == 'small'");
+/// });
+/// ```
+///
+/// Extracting an `AsFrozenSet<T>` accepts the same objects as extracting `T`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsFrozenSet<T>(pub T);
+
 /// Represents a  Python `frozenset`
 #[repr(transparent)]
 pub struct PyFrozenSet(PyAny);
@@ -313,27 +336,30 @@ pub(crate) fn new_from_iter<T: ToPyObject>(
     py: Python<'_>,
     elements: impl IntoIterator<Item = T>,
 ) -> PyResult<Bound<'_, PyFrozenSet>> {
-    fn inner<'py>(
-        py: Python<'py>,
-        elements: &mut dyn Iterator<Item = PyObject>,
-    ) -> PyResult<Bound<'py, PyFrozenSet>> {
-        let set = unsafe {
-            // We create the  `Py` pointer because its Drop cleans up the set if user code panics.
-            ffi::PyFrozenSet_New(std::ptr::null_mut())
-                .assume_owned_or_err(py)?
-                .downcast_into_unchecked()
-        };
-        let ptr = set.as_ptr();
+    let mut iter = elements
+        .into_iter()
+        .map(|e| Ok(e.to_object(py).into_bound(py)));
+    try_new_from_iter(py, &mut iter)
+}
 
-        for obj in elements {
-            err::error_on_minusone(py, unsafe { ffi::PySet_Add(ptr, obj.as_ptr()) })?;
-        }
+/// Like [`new_from_iter`], for elements whose conversion can fail; the first error is returned.
+pub(crate) fn try_new_from_iter<'py>(
+    py: Python<'py>,
+    elements: &mut dyn Iterator<Item = PyResult<Bound<'py, PyAny>>>,
+) -> PyResult<Bound<'py, PyFrozenSet>> {
+    let set = unsafe {
+        // We create the  `Py` pointer because its Drop cleans up the set if user code panics.
+        ffi::PyFrozenSet_New(std::ptr::null_mut())
+            .assume_owned_or_err(py)?
+            .downcast_into_unchecked()
+    };
+    let ptr = set.as_ptr();
 
-        Ok(set)
+    for obj in elements {
+        err::error_on_minusone(py, unsafe { ffi::PySet_Add(ptr, obj?.as_ptr()) })?;
     }
 
-    let mut iter = elements.into_iter().map(|e| e.to_object(py));
-    inner(py, &mut iter)
+    Ok(set)
 }
 
 #[cfg(test)]
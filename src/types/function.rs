@@ -6,7 +6,7 @@ use crate::py_result_ext::PyResultExt;
 use crate::types::capsule::PyCapsuleMethods;
 use crate::{
     ffi,
-    impl_::pymethods::{self, PyMethodDef},
+    impl_::pymethods::PyMethodDef,
     types::{PyCapsule, PyDict, PyString, PyTuple},
 };
 use std::cell::UnsafeCell;
@@ -43,14 +43,8 @@ impl PyCFunction {
         doc: &'static str,
         py_or_module: PyFunctionArguments<'a>,
     ) -> PyResult<Bound<'a, Self>> {
-        Self::internal_new(
-            &PyMethodDef::cfunction_with_keywords(
-                name,
-                pymethods::PyCFunctionWithKeywords(fun),
-                doc,
-            ),
-            py_or_module,
-        )
+        let (def, destructor) = PyMethodDef::cfunction_with_keywords(name, fun, doc)?;
+        Self::internal_new(leak_method_def(def, destructor), py_or_module)
     }
 
     /// Deprecated form of [`PyCFunction::new`]
@@ -77,10 +71,8 @@ impl PyCFunction {
         doc: &'static str,
         py_or_module: PyFunctionArguments<'a>,
     ) -> PyResult<Bound<'a, Self>> {
-        Self::internal_new(
-            &PyMethodDef::noargs(name, pymethods::PyCFunction(fun), doc),
-            py_or_module,
-        )
+        let (def, destructor) = PyMethodDef::noargs(name, fun, doc)?;
+        Self::internal_new(leak_method_def(def, destructor), py_or_module)
     }
 
     /// Deprecated form of [`PyCFunction::new_closure`]
@@ -131,12 +123,12 @@ impl PyCFunction {
         F: Fn(&PyTuple, Option<&PyDict>) -> R + Send + 'static,
         R: crate::callback::IntoPyCallbackOutput<*mut ffi::PyObject>,
     {
-        let method_def = pymethods::PyMethodDef::cfunction_with_keywords(
+        let (method_def, def_destructor) = PyMethodDef::cfunction_with_keywords(
             name.unwrap_or("pyo3-closure\0"),
-            pymethods::PyCFunctionWithKeywords(run_closure::<F, R>),
+            run_closure::<F, R>,
             doc.unwrap_or("\0"),
-        );
-        let (def, def_destructor) = method_def.as_method_def()?;
+        )?;
+        let def = method_def.as_method_def();
 
         let capsule = PyCapsule::new_bound(
             py,
@@ -160,7 +152,7 @@ impl PyCFunction {
 
     #[doc(hidden)]
     pub fn internal_new<'py>(
-        method_def: &PyMethodDef,
+        method_def: &'static PyMethodDef,
         py_or_module: PyFunctionArguments<'py>,
     ) -> PyResult<Bound<'py, Self>> {
         let (py, module) = py_or_module.into_py_and_maybe_module();
@@ -170,24 +162,25 @@ impl PyCFunction {
         } else {
             (std::ptr::null_mut(), None)
         };
-        let (def, destructor) = method_def.as_method_def()?;
-
-        // FIXME: stop leaking the def and destructor
-        let def = Box::into_raw(Box::new(def));
-        std::mem::forget(destructor);
-
         let module_name_ptr = module_name
             .as_ref()
             .map_or(std::ptr::null_mut(), Py::as_ptr);
 
         unsafe {
-            ffi::PyCFunction_NewEx(def, mod_ptr, module_name_ptr)
+            ffi::PyCFunction_NewEx(method_def.as_ptr(), mod_ptr, module_name_ptr)
                 .assume_owned_or_err(py)
                 .downcast_into_unchecked()
         }
     }
 }
 
+/// Leaks a method definition created at runtime, as Python requires it to outlive the function.
+fn leak_method_def(def: PyMethodDef, destructor: PyMethodDefDestructor) -> &'static PyMethodDef {
+    // FIXME: stop leaking the def and destructor
+    std::mem::forget(destructor);
+    Box::leak(Box::new(def))
+}
+
 fn closure_capsule_name() -> &'static CStr {
     // TODO replace this with const CStr once MSRV new enough
     CStr::from_bytes_with_nul(b"pyo3-closure\0").unwrap()
@@ -6,8 +6,8 @@ use crate::ffi::{self, Py_ssize_t};
 use crate::ffi_ptr_ext::FfiPtrExt;
 use crate::instance::Borrowed;
 use crate::internal_tricks::get_ssize_index;
-use crate::types::{PySequence, PyTuple};
-use crate::{Bound, PyAny, PyNativeType, PyObject, Python, ToPyObject};
+use crate::types::{IntoPyDict, PySequence, PyTuple};
+use crate::{Bound, FromPyObject, PyAny, PyNativeType, PyObject, Python, ToPyObject};
 
 use crate::types::any::PyAnyMethods;
 use crate::types::sequence::PySequenceMethods;
@@ -55,6 +55,44 @@ pub(crate) fn new_from_iter<'py>(
     }
 }
 
+/// Like [`new_from_iter`], for elements whose conversion can fail; the first error is returned.
+#[inline]
+#[track_caller]
+pub(crate) fn try_new_from_iter<'py>(
+    py: Python<'py>,
+    elements: &mut dyn ExactSizeIterator<Item = PyResult<Bound<'py, PyAny>>>,
+) -> PyResult<Bound<'py, PyList>> {
+    unsafe {
+        // PyList_New checks for overflow but has a bad error message, so we check ourselves
+        let len: Py_ssize_t = elements
+            .len()
+            .try_into()
+            .expect("out of range integral type conversion attempted on `elements.len()`");
+
+        let ptr = ffi::PyList_New(len);
+
+        // The list is owned here so that its Drop cleans it up (including the items which are
+        // still NULL) if a conversion fails or the asserts panic.
+        let list: Bound<'py, PyList> = ptr.assume_owned(py).downcast_into_unchecked();
+
+        let mut counter: Py_ssize_t = 0;
+
+        for obj in elements.take(len as usize) {
+            let obj = obj?;
+            #[cfg(not(Py_LIMITED_API))]
+            ffi::PyList_SET_ITEM(ptr, counter, obj.into_ptr());
+            #[cfg(Py_LIMITED_API)]
+            ffi::PyList_SetItem(ptr, counter, obj.into_ptr());
+            counter += 1;
+        }
+
+        assert!(elements.next().is_none(), "Attempted to create PyList but `elements` was larger than reported by its `ExactSizeIterator` implementation.");
+        assert_eq!(len, counter, "Attempted to create PyList but `elements` was smaller than reported by its `ExactSizeIterator` implementation.");
+
+        Ok(list)
+    }
+}
+
 impl PyList {
     /// Deprecated form of [`PyList::new_bound`].
     #[inline]
@@ -265,6 +303,12 @@ impl PyList {
         self.as_borrowed().sort()
     }
 
+    /// Sorts the list in-place, comparing the results of calling `key` on each item.
+    /// Equivalent to the Python expression `l.sort(key=key)`.
+    pub fn sort_by_key(&self, key: &PyAny) -> PyResult<()> {
+        self.as_borrowed().sort_by_key(&key.as_borrowed())
+    }
+
     /// Reverses the list in-place. Equivalent to the Python expression `l.reverse()`.
     pub fn reverse(&self) -> PyResult<()> {
         self.as_borrowed().reverse()
@@ -276,6 +320,17 @@ impl PyList {
     pub fn to_tuple(&self) -> &PyTuple {
         self.as_borrowed().to_tuple().into_gil_ref()
     }
+
+    /// Extracts each item of the list, returning them in a `Vec`.
+    ///
+    /// This is equivalent to `self.extract::<Vec<T>>()`, but skips the type check, which also
+    /// accepts other sequences.
+    pub fn to_vec<'py, T>(&'py self) -> PyResult<Vec<T>>
+    where
+        T: FromPyObject<'py>,
+    {
+        self.as_borrowed().to_vec()
+    }
 }
 
 index_impls!(PyList, "list", PyList::len, PyList::get_slice);
@@ -376,6 +431,10 @@ pub trait PyListMethods<'py> {
     /// Sorts the list in-place. Equivalent to the Python expression `l.sort()`.
     fn sort(&self) -> PyResult<()>;
 
+    /// Sorts the list in-place, comparing the results of calling `key` on each item.
+    /// Equivalent to the Python expression `l.sort(key=key)`.
+    fn sort_by_key(&self, key: &Bound<'_, PyAny>) -> PyResult<()>;
+
     /// Reverses the list in-place. Equivalent to the Python expression `l.reverse()`.
     fn reverse(&self) -> PyResult<()>;
 
@@ -383,6 +442,14 @@ pub trait PyListMethods<'py> {
     ///
     /// This method is equivalent to `self.as_sequence().to_tuple()` and faster than `PyTuple::new(py, this_list)`.
     fn to_tuple(&self) -> Bound<'py, PyTuple>;
+
+    /// Extracts each item of the list, returning them in a `Vec`.
+    ///
+    /// This is equivalent to `self.extract::<Vec<T>>()`, but skips the type check, which also
+    /// accepts other sequences.
+    fn to_vec<T>(&self) -> PyResult<Vec<T>>
+    where
+        T: FromPyObject<'py>;
 }
 
 impl<'py> PyListMethods<'py> for Bound<'py, PyList> {
@@ -565,6 +632,15 @@ impl<'py> PyListMethods<'py> for Bound<'py, PyList> {
         err::error_on_minusone(self.py(), unsafe { ffi::PyList_Sort(self.as_ptr()) })
     }
 
+    /// Sorts the list in-place, comparing the results of calling `key` on each item.
+    /// Equivalent to the Python expression `l.sort(key=key)`.
+    fn sort_by_key(&self, key: &Bound<'_, PyAny>) -> PyResult<()> {
+        let py = self.py();
+        let kwargs = [(intern!(py, "key"), key)].into_py_dict_bound(py);
+        self.call_method(intern!(py, "sort"), (), Some(&kwargs))
+            .map(drop)
+    }
+
     /// Reverses the list in-place. Equivalent to the Python expression `l.reverse()`.
     fn reverse(&self) -> PyResult<()> {
         err::error_on_minusone(self.py(), unsafe { ffi::PyList_Reverse(self.as_ptr()) })
@@ -580,6 +656,14 @@ impl<'py> PyListMethods<'py> for Bound<'py, PyList> {
                 .downcast_into_unchecked()
         }
     }
+
+    /// Extracts each item of the list, returning them in a `Vec`.
+    fn to_vec<T>(&self) -> PyResult<Vec<T>>
+    where
+        T: FromPyObject<'py>,
+    {
+        self.iter().map(|item| item.extract()).collect()
+    }
 }
 
 /// Used by `PyList::iter()`.
@@ -707,6 +791,7 @@ impl<'py> IntoIterator for Bound<'py, PyList> {
 #[cfg(test)]
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
+    use crate::types::list::PyListMethods;
     use crate::types::{PyList, PyTuple};
     use crate::Python;
     use crate::{IntoPy, PyObject, ToPyObject};
@@ -956,6 +1041,30 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_sort_by_key() {
+        Python::with_gil(|py| {
+            let list = PyList::new_bound(py, ["ccc", "a", "bb"]);
+            let len = py.eval_bound("len", None, None).unwrap();
+            list.sort_by_key(&len).unwrap();
+            assert_eq!(list.to_vec::<String>().unwrap(), ["a", "bb", "ccc"]);
+
+            let not_callable = 1.to_object(py).into_bound(py);
+            assert!(list.sort_by_key(&not_callable).is_err());
+        });
+    }
+
+    #[test]
+    fn test_to_vec() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, [2, 3, 5]);
+            assert_eq!(list.to_vec::<i32>().unwrap(), [2, 3, 5]);
+
+            list.append("seven").unwrap();
+            assert!(list.to_vec::<i32>().is_err());
+        });
+    }
+
     #[test]
     fn test_array_into_py() {
         Python::with_gil(|py| {
@@ -23,7 +23,7 @@ pub use self::ellipsis::PyEllipsis;
 pub use self::float::PyFloat;
 #[cfg(all(not(Py_LIMITED_API), not(PyPy)))]
 pub use self::frame::PyFrame;
-pub use self::frozenset::{PyFrozenSet, PyFrozenSetBuilder};
+pub use self::frozenset::{AsFrozenSet, PyFrozenSet, PyFrozenSetBuilder};
 pub use self::function::PyCFunction;
 #[cfg(all(not(Py_LIMITED_API), not(PyPy)))]
 pub use self::function::PyFunction;
@@ -31,7 +31,7 @@ pub use self::iterator::PyIterator;
 pub use self::list::PyList;
 pub use self::mapping::PyMapping;
 pub use self::memoryview::PyMemoryView;
-pub use self::module::PyModule;
+pub use self::module::{FromCodeOptions, PyModule};
 pub use self::none::PyNone;
 pub use self::notimplemented::PyNotImplemented;
 pub use self::num::PyLong;
@@ -73,13 +73,17 @@ pub use self::typeobject::PyType;
 /// # }
 ///  ```
 ///
-/// If PyO3 detects that the collection is mutated during iteration, it will panic.
+/// If PyO3 detects that the collection is mutated during iteration, it will panic. For
+/// dictionaries, [`PyDictMethods::try_iter`](crate::prelude::PyDictMethods::try_iter) returns an
+/// error instead.
 ///
 /// These iterators use Python's C-API directly. However in certain cases, like when compiling for
 /// the Limited API and PyPy, the underlying structures are opaque and that may not be possible.
 /// In these cases the iterators are implemented by forwarding to [`PyIterator`].
 pub mod iter {
-    pub use super::dict::{BoundDictIterator, PyDictIterator};
+    pub use super::dict::{
+        BoundDictIterator, BoundDictTryIterator, PyDictIterator, PyDictTryIterator,
+    };
     pub use super::frozenset::{BoundFrozenSetIterator, PyFrozenSetIterator};
     pub use super::list::{BoundListIterator, PyListIterator};
     pub use super::set::{BoundSetIterator, PySetIterator};
@@ -282,7 +286,7 @@ pub(crate) mod bytearray;
 pub(crate) mod bytes;
 pub(crate) mod capsule;
 #[cfg(not(Py_LIMITED_API))]
-mod code;
+pub(crate) mod code;
 mod complex;
 #[cfg(not(Py_LIMITED_API))]
 pub(crate) mod datetime;
@@ -290,7 +294,7 @@ pub(crate) mod dict;
 mod ellipsis;
 pub(crate) mod float;
 #[cfg(all(not(Py_LIMITED_API), not(PyPy)))]
-mod frame;
+pub(crate) mod frame;
 pub(crate) mod frozenset;
 mod function;
 pub(crate) mod iterator;
@@ -3,9 +3,10 @@ use crate::err::{PyErr, PyResult};
 use crate::ffi_ptr_ext::FfiPtrExt;
 use crate::pyclass::PyClass;
 use crate::types::{
-    any::PyAnyMethods, list::PyListMethods, PyAny, PyCFunction, PyDict, PyList, PyString,
+    any::PyAnyMethods, dict::PyDictMethods, list::PyListMethods, PyAny, PyCFunction, PyDict,
+    PyList, PyString, PyType,
 };
-use crate::{exceptions, ffi, Bound, FromPyObject, IntoPy, Py, PyNativeType, PyObject, Python};
+use crate::{exceptions, ffi, Bound, IntoPy, Py, PyNativeType, PyObject, PyTypeInfo, Python};
 use std::ffi::CString;
 use std::str;
 
@@ -130,24 +131,159 @@ impl PyModule {
         file_name: &str,
         module_name: &str,
     ) -> PyResult<&'p PyModule> {
+        Self::from_code_bound(py, code, file_name, module_name).map(Bound::into_gil_ref)
+    }
+
+    /// Creates and loads a module named `module_name`,
+    /// containing the Python code passed to `code`
+    /// and pretending to live at `file_name`.
+    ///
+    /// The module is added to `sys.modules`, like a module loaded by the import system. Use
+    /// [`PyModule::from_code_with_options`] to control this, or to create a package.
+    ///
+    /// See [`PyModule::from_code`] for examples and the conditions under which this fails.
+    pub fn from_code_bound<'py>(
+        py: Python<'py>,
+        code: &str,
+        file_name: &str,
+        module_name: &str,
+    ) -> PyResult<Bound<'py, PyModule>> {
         let data = CString::new(code)?;
         let filename = CString::new(file_name)?;
         let module = CString::new(module_name)?;
 
         unsafe {
-            let cptr = ffi::Py_CompileString(data.as_ptr(), filename.as_ptr(), ffi::Py_file_input);
-            if cptr.is_null() {
-                return Err(PyErr::fetch(py));
-            }
+            let code = ffi::Py_CompileString(data.as_ptr(), filename.as_ptr(), ffi::Py_file_input)
+                .assume_owned_or_err(py)?;
+
+            ffi::PyImport_ExecCodeModuleEx(module.as_ptr(), code.as_ptr(), filename.as_ptr())
+                .assume_owned_or_err(py)?
+                .downcast_into()
+                .map_err(Into::into)
+        }
+    }
 
-            let mptr = ffi::PyImport_ExecCodeModuleEx(module.as_ptr(), cptr, filename.as_ptr());
-            ffi::Py_DECREF(cptr);
-            if mptr.is_null() {
-                return Err(PyErr::fetch(py));
+    /// Creates a module named `module_name` and executes the Python code passed to `code` in it,
+    /// pretending that it lives at `file_name`.
+    ///
+    /// Before the code runs, `__name__` is set to `module_name` and `__file__` to `file_name`.
+    /// [`FromCodeOptions`] controls whether the module is registered in `sys.modules` and whether
+    /// it is a package.
+    ///
+    /// Unlike [`PyModule::from_code_bound`], this does not create a loader or a spec for the module,
+    /// so `__loader__` and `__spec__` are `None`.
+    ///
+    /// <div class="information">
+    ///     <div class="tooltip compile_fail" style="">&#x26a0; &#xfe0f;</div>
+    /// </div><div class="example-wrap" style="display:inline-block"><pre class="compile_fail" style="white-space:normal;font:inherit;">
+    //
+    ///  <strong>Warning</strong>: This will compile and execute code. <strong>Never</strong> pass untrusted code to this function!
+    ///
+    /// </pre></div>
+    ///
+    /// # Errors
+    ///
+    /// Returns `PyErr` if:
+    /// - `code` is not syntactically correct Python.
+    /// - Any Python exceptions are raised while initializing the module, in which case the module
+    ///   is removed from `sys.modules` again.
+    /// - Any of the arguments cannot be converted to [`CString`]s.
+    ///
+    /// # Example: a package with a submodule
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::FromCodeOptions;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let options = FromCodeOptions::new().package(true);
+    ///     PyModule::from_code_with_options(py, "VERSION = 2", "plugins/__init__.py", "plugins", options)?;
+    ///
+    ///     let greeting = PyModule::from_code_with_options(
+    ///         py,
+    ///         "from . import VERSION\nGREETING = f'hello from v{VERSION}'",
+    ///         "plugins/greeting.py",
+    ///         "plugins.greeting",
+    ///         FromCodeOptions::new(),
+    ///     )?;
+    ///     let message: String = greeting.getattr("GREETING")?.extract()?;
+    ///     assert_eq!(message, "hello from v2");
+    ///
+    ///     let from_import = py.import_bound("plugins.greeting")?;
+    ///     assert!(from_import.is(&greeting));
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_code_with_options<'py>(
+        py: Python<'py>,
+        code: &str,
+        file_name: &str,
+        module_name: &str,
+        options: FromCodeOptions,
+    ) -> PyResult<Bound<'py, PyModule>> {
+        let data = CString::new(code)?;
+        let filename = CString::new(file_name)?;
+        let name = CString::new(module_name)?;
+
+        let code = unsafe {
+            ffi::Py_CompileString(data.as_ptr(), filename.as_ptr(), ffi::Py_file_input)
+                .assume_owned_or_err(py)?
+        };
+        let module = unsafe {
+            ffi::PyModule_New(name.as_ptr())
+                .assume_owned_or_err(py)?
+                .downcast_into_unchecked::<PyModule>()
+        };
+
+        let dict = module.dict();
+        dict.set_item(intern!(py, "__file__"), file_name)?;
+        dict.set_item(
+            intern!(py, "__builtins__"),
+            py.import_bound(intern!(py, "builtins"))?,
+        )?;
+        if options.package {
+            let path = PyList::empty_bound(py);
+            match std::path::Path::new(file_name).parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => path.append(parent)?,
+                _ => {}
             }
+            dict.set_item(intern!(py, "__path__"), path)?;
+            dict.set_item(intern!(py, "__package__"), module_name)?;
+        } else {
+            let package = module_name
+                .rsplit_once('.')
+                .map_or("", |(parent, _)| parent);
+            dict.set_item(intern!(py, "__package__"), package)?;
+        }
 
-            <&PyModule as FromPyObject>::extract(py.from_owned_ptr_or_err(mptr)?)
+        let modules = if options.register {
+            let modules = unsafe {
+                ffi::PyImport_GetModuleDict()
+                    .assume_borrowed_or_err(py)?
+                    .to_owned()
+                    .downcast_into_unchecked::<PyDict>()
+            };
+            modules.set_item(module_name, &module)?;
+            Some(modules)
+        } else {
+            None
+        };
+
+        let result = unsafe {
+            ffi::PyEval_EvalCode(code.as_ptr(), dict.as_ptr(), dict.as_ptr())
+                .assume_owned_or_err(py)
+        };
+        if let Err(err) = result {
+            if let Some(modules) = modules {
+                // like the import system, don't leave a half-initialized module behind
+                let _ = modules.del_item(module_name);
+            }
+            return Err(err);
         }
+        Ok(module)
     }
 
     /// Returns the module's `__dict__` attribute, which contains the module's symbol table.
@@ -215,6 +351,36 @@ impl PyModule {
         self.as_borrowed().add(name, value)
     }
 
+    /// Adds an attribute to the module, without listing it in the module's `__all__`.
+    ///
+    /// All of the other `add*` methods append the name of the added object to `__all__`, so that
+    /// it is imported by <span style="white-space: pre">`from my_module import *`</span> and
+    /// picked up by documentation tools. Use this method for objects which should be accessible
+    /// as attributes of the module but are not part of its public interface.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// #[pyfunction]
+    /// fn helper() {}
+    ///
+    /// #[pymodule]
+    /// fn my_module(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    ///     module.add_unexported("helper", wrap_pyfunction!(helper, module)?)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Functions added with `#[pyfn(module, unexported)]` are added the same way.
+    pub fn add_unexported<V>(&self, name: &str, value: V) -> PyResult<()>
+    where
+        V: IntoPy<PyObject>,
+    {
+        self.as_borrowed().add_unexported(name, value)
+    }
+
     /// Adds a new class to the module.
     ///
     /// Notice that this method does not take an argument.
@@ -257,7 +423,59 @@ impl PyModule {
     where
         T: PyClass,
     {
-        self.as_borrowed().add_class::<T>()
+        self.as_borrowed().add_class::<T>()?;
+        Ok(())
+    }
+
+    /// Adds a type to the module, and makes it appear to be defined there by setting its
+    /// `__module__` attribute to the name of the module.
+    ///
+    /// Unlike [`add_class`][PyModule::add_class], any type implementing [`PyTypeInfo`] can be added, including
+    /// exceptions defined with [`create_exception!`][crate::create_exception]. This is intended for
+    /// extensions which inject their types into an existing Python package, for example a
+    /// pure-Python facade over a Rust extension module: types added this way are displayed,
+    /// documented and pickled as members of the package.
+    ///
+    /// A type object is shared by every module it is added to, so only one module can be its
+    /// `__module__`. [`add_class`][PyModule::add_class] leaves `__module__` unchanged for this reason.
+    ///
+    /// The type object is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::PyException;
+    ///
+    /// pyo3::create_exception!(my_extension, MyError, PyException);
+    ///
+    /// #[pyclass]
+    /// struct Foo { /* fields omitted */ }
+    ///
+    /// #[pymodule]
+    /// fn my_extension(py: Python<'_>, _module: &PyModule) -> PyResult<()> {
+    ///     let package = PyModule::new(py, "my_package")?;
+    ///     package.add_type::<Foo>()?;
+    ///     package.add_type::<MyError>()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn add_type<T>(&self) -> PyResult<&PyType>
+    where
+        T: PyTypeInfo,
+    {
+        self.as_borrowed().add_type::<T>().map(Bound::into_gil_ref)
+    }
+
+    /// Adds a new class to the module, creating its type object only when it is first accessed.
+    ///
+    /// See [`PyModuleMethods::add_class_lazy`].
+    #[cfg(feature = "macros")]
+    pub fn add_class_lazy<T>(&self) -> PyResult<()>
+    where
+        T: PyClass,
+    {
+        self.as_borrowed().add_class_lazy::<T>()
     }
 
     /// Adds a function or a (sub)module to a module, using the functions name as name.
@@ -267,7 +485,8 @@ impl PyModule {
     where
         T: IntoPyCallbackOutput<PyObject>,
     {
-        self.as_borrowed().add_wrapped(wrapper)
+        self.as_borrowed().add_wrapped(wrapper)?;
+        Ok(())
     }
 
     /// Adds a submodule to a module.
@@ -349,7 +568,50 @@ impl PyModule {
     /// [1]: crate::prelude::pyfunction
     /// [2]: crate::wrap_pyfunction
     pub fn add_function<'a>(&'a self, fun: &'a PyCFunction) -> PyResult<()> {
-        self.as_borrowed().add_function(&fun.as_borrowed())
+        self.as_borrowed().add_function(&fun.as_borrowed())?;
+        Ok(())
+    }
+}
+
+/// Options for [`PyModule::from_code_with_options`].
+///
+/// By default, the module is added to `sys.modules` and is not a package.
+#[derive(Clone, Copy, Debug)]
+pub struct FromCodeOptions {
+    register: bool,
+    package: bool,
+}
+
+impl FromCodeOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self {
+            register: true,
+            package: false,
+        }
+    }
+
+    /// Whether to add the module to `sys.modules` before executing its code, so that it can be
+    /// imported by other modules, including by the code of the module itself.
+    pub fn register(mut self, register: bool) -> Self {
+        self.register = register;
+        self
+    }
+
+    /// Whether the module is a package.
+    ///
+    /// A package has a `__path__` attribute, which contains the directory of its file name if it
+    /// has one, so that submodules can be imported from it, and its `__package__` is its own name
+    /// for relative imports.
+    pub fn package(mut self, package: bool) -> Self {
+        self.package = package;
+        self
+    }
+}
+
+impl Default for FromCodeOptions {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -415,6 +677,34 @@ pub trait PyModuleMethods<'py> {
         N: IntoPy<Py<PyString>>,
         V: IntoPy<PyObject>;
 
+    /// Adds an attribute to the module, without listing it in the module's `__all__`.
+    ///
+    /// All of the other `add*` methods append the name of the added object to `__all__`, so that
+    /// it is imported by <span style="white-space: pre">`from my_module import *`</span> and
+    /// picked up by documentation tools. Use this method for objects which should be accessible
+    /// as attributes of the module but are not part of its public interface.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// #[pyfunction]
+    /// fn helper() {}
+    ///
+    /// #[pymodule]
+    /// fn my_module(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    ///     module.add_unexported("helper", wrap_pyfunction!(helper, module)?)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Functions added with `#[pyfn(module, unexported)]` are added the same way.
+    fn add_unexported<N, V>(&self, name: N, value: V) -> PyResult<()>
+    where
+        N: IntoPy<Py<PyString>>,
+        V: IntoPy<PyObject>;
+
     /// Adds a new class to the module.
     ///
     /// Notice that this method does not take an argument.
@@ -452,15 +742,90 @@ pub trait PyModuleMethods<'py> {
     /// make an *instance* of `Foo` (or *get* one for that matter, as we haven't exported
     /// anything that can return instances of `Foo`).
     ///
+    /// The type object of the class is returned, so that it can be registered elsewhere, e.g.
+    /// with an abstract base class, without looking it up again.
+    ///
     /// [1]: https://pyo3.rs/latest/class.html#constructor
-    fn add_class<T>(&self) -> PyResult<()>
+    fn add_class<T>(&self) -> PyResult<Bound<'py, PyType>>
+    where
+        T: PyClass;
+
+    /// Adds a type to the module, and makes it appear to be defined there by setting its
+    /// `__module__` attribute to the name of the module.
+    ///
+    /// Unlike [`add_class`][PyModuleMethods::add_class], any type implementing [`PyTypeInfo`] can be added, including
+    /// exceptions defined with [`create_exception!`][crate::create_exception]. This is intended for
+    /// extensions which inject their types into an existing Python package, for example a
+    /// pure-Python facade over a Rust extension module: types added this way are displayed,
+    /// documented and pickled as members of the package.
+    ///
+    /// A type object is shared by every module it is added to, so only one module can be its
+    /// `__module__`. [`add_class`][PyModuleMethods::add_class] leaves `__module__` unchanged for this reason.
+    ///
+    /// The type object is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::PyException;
+    ///
+    /// pyo3::create_exception!(my_extension, MyError, PyException);
+    ///
+    /// #[pyclass]
+    /// struct Foo { /* fields omitted */ }
+    ///
+    /// #[pymodule]
+    /// fn my_extension(py: Python<'_>, _module: &PyModule) -> PyResult<()> {
+    ///     let package = PyModule::new(py, "my_package")?;
+    ///     package.add_type::<Foo>()?;
+    ///     package.add_type::<MyError>()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    fn add_type<T>(&self) -> PyResult<Bound<'py, PyType>>
+    where
+        T: PyTypeInfo;
+
+    /// Adds a new class to the module, creating its type object only when it is first accessed.
+    ///
+    /// [`add_class`][PyModuleMethods::add_class] creates the type object immediately, which
+    /// makes importing modules defining hundreds of classes noticeably slow. With this method,
+    /// the class is instead looked up by a module `__getattr__` function ([PEP 562]), which
+    /// creates the type object the first time the class is accessed from Python, e.g. with
+    /// `from my_module import Foo`, and stores it in the module. The class is still listed in
+    /// `__all__` and by `dir()`. Type objects are also created when the class is used from Rust,
+    /// for example to create an instance.
+    ///
+    /// If the module already defines a `__getattr__` function, it is called for all other names.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// #[pyclass]
+    /// struct Foo { /* fields omitted */ }
+    ///
+    /// #[pymodule]
+    /// fn my_module(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    ///     module.add_class_lazy::<Foo>()?;
+    ///     Ok(())
+    /// }
+    ///  ```
+    ///
+    /// [PEP 562]: https://peps.python.org/pep-0562/
+    #[cfg(feature = "macros")]
+    fn add_class_lazy<T>(&self) -> PyResult<()>
     where
         T: PyClass;
 
     /// Adds a function or a (sub)module to a module, using the functions name as name.
     ///
     /// Prefer to use [`PyModule::add_function`] and/or [`PyModule::add_submodule`] instead.
-    fn add_wrapped<T>(&self, wrapper: &impl Fn(Python<'py>) -> T) -> PyResult<()>
+    ///
+    /// Returns the object which was added.
+    fn add_wrapped<T>(&self, wrapper: &impl Fn(Python<'py>) -> T) -> PyResult<Bound<'py, PyAny>>
     where
         T: IntoPyCallbackOutput<PyObject>;
 
@@ -538,9 +903,12 @@ pub trait PyModuleMethods<'py> {
     /// Hello world!
     /// ```
     ///
+    /// The function object is returned, so that it can be used further without looking it up
+    /// on the module again.
+    ///
     /// [1]: crate::prelude::pyfunction
     /// [2]: crate::wrap_pyfunction
-    fn add_function(&self, fun: &Bound<'_, PyCFunction>) -> PyResult<()>;
+    fn add_function(&self, fun: &Bound<'_, PyCFunction>) -> PyResult<Bound<'py, PyCFunction>>;
 }
 
 impl<'py> PyModuleMethods<'py> for Bound<'py, PyModule> {
@@ -628,21 +996,62 @@ impl<'py> PyModuleMethods<'py> for Bound<'py, PyModule> {
         )
     }
 
-    fn add_class<T>(&self) -> PyResult<()>
+    fn add_unexported<N, V>(&self, name: N, value: V) -> PyResult<()>
+    where
+        N: IntoPy<Py<PyString>>,
+        V: IntoPy<PyObject>,
+    {
+        let py = self.py();
+        self.setattr(name.into_py(py).into_bound(py), value.into_py(py))
+    }
+
+    fn add_class<T>(&self) -> PyResult<Bound<'py, PyType>>
     where
         T: PyClass,
     {
         let py = self.py();
-        self.add(T::NAME, T::lazy_type_object().get_or_try_init(py)?)
+        let ty = T::lazy_type_object()
+            .get_or_try_init(py)?
+            .as_borrowed()
+            .to_owned();
+        self.add(T::NAME, &ty)?;
+        Ok(ty)
+    }
+
+    fn add_type<T>(&self) -> PyResult<Bound<'py, PyType>>
+    where
+        T: PyTypeInfo,
+    {
+        let ty = T::type_object_bound(self.py());
+        ty.setattr(intern!(self.py(), "__module__"), self.name()?)?;
+        self.add(T::NAME, &ty)?;
+        Ok(ty)
     }
 
-    fn add_wrapped<T>(&self, wrapper: &impl Fn(Python<'py>) -> T) -> PyResult<()>
+    #[cfg(feature = "macros")]
+    fn add_class_lazy<T>(&self) -> PyResult<()>
+    where
+        T: PyClass,
+    {
+        let registry = crate::impl_::pymodule::LazyClasses::get_or_install(self)?;
+        registry.borrow_mut().insert::<T>();
+        self.index()?
+            .append(T::NAME)
+            .expect("could not append __name__ to __all__");
+        Ok(())
+    }
+
+    fn add_wrapped<T>(&self, wrapper: &impl Fn(Python<'py>) -> T) -> PyResult<Bound<'py, PyAny>>
     where
         T: IntoPyCallbackOutput<PyObject>,
     {
-        fn inner(module: &Bound<'_, PyModule>, object: Bound<'_, PyAny>) -> PyResult<()> {
+        fn inner<'py>(
+            module: &Bound<'py, PyModule>,
+            object: Bound<'py, PyAny>,
+        ) -> PyResult<Bound<'py, PyAny>> {
             let name = object.getattr(__name__(module.py()))?;
-            module.add(name.downcast_into::<PyString>()?, object)
+            module.add(name.downcast_into::<PyString>()?, &object)?;
+            Ok(object)
         }
 
         let py = self.py();
@@ -654,9 +1063,10 @@ impl<'py> PyModuleMethods<'py> for Bound<'py, PyModule> {
         self.add(name, module)
     }
 
-    fn add_function(&self, fun: &Bound<'_, PyCFunction>) -> PyResult<()> {
+    fn add_function(&self, fun: &Bound<'_, PyCFunction>) -> PyResult<Bound<'py, PyCFunction>> {
         let name = fun.getattr(__name__(self.py()))?;
-        self.add(name.downcast_into::<PyString>()?, fun)
+        self.add(name.downcast_into::<PyString>()?, fun)?;
+        Ok(fun.clone().unbind().into_bound(self.py()))
     }
 }
 
@@ -670,7 +1080,9 @@ fn __name__(py: Python<'_>) -> &Bound<'_, PyString> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{types::PyModule, Python};
+    use super::FromCodeOptions;
+    use crate::types::{any::PyAnyMethods, module::PyModuleMethods, string::PyStringMethods};
+    use crate::{exceptions::PyZeroDivisionError, types::PyModule, Python};
 
     #[test]
     fn module_import_and_name() {
@@ -688,4 +1100,94 @@ mod tests {
             assert!(site.filename().unwrap().ends_with("site.py"));
         })
     }
+
+    #[test]
+    fn module_from_code_bound() {
+        Python::with_gil(|py| {
+            let module =
+                PyModule::from_code_bound(py, "X = __name__", "x.py", "pyo3_test_from_code")
+                    .unwrap();
+            assert_eq!(
+                module.name().unwrap().to_cow().unwrap(),
+                "pyo3_test_from_code"
+            );
+            assert_eq!(module.filename().unwrap().to_cow().unwrap(), "x.py");
+            assert_eq!(
+                module.getattr("X").unwrap().extract::<String>().unwrap(),
+                "pyo3_test_from_code"
+            );
+            let imported = py.import_bound("pyo3_test_from_code").unwrap();
+            assert!(imported.is(&module));
+        })
+    }
+
+    #[test]
+    fn module_from_code_with_options() {
+        Python::with_gil(|py| {
+            let package = PyModule::from_code_with_options(
+                py,
+                "NAME = __name__",
+                "pkg/__init__.py",
+                "pyo3_test_pkg",
+                FromCodeOptions::new().package(true),
+            )
+            .unwrap();
+            assert_eq!(
+                package
+                    .getattr("__path__")
+                    .unwrap()
+                    .extract::<Vec<String>>()
+                    .unwrap(),
+                ["pkg"]
+            );
+            assert_eq!(
+                package
+                    .getattr("__package__")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "pyo3_test_pkg"
+            );
+
+            let module = PyModule::from_code_with_options(
+                py,
+                "from . import NAME",
+                "pkg/sub.py",
+                "pyo3_test_pkg.sub",
+                FromCodeOptions::new(),
+            )
+            .unwrap();
+            assert_eq!(
+                module.getattr("NAME").unwrap().extract::<String>().unwrap(),
+                "pyo3_test_pkg"
+            );
+            assert!(py.import_bound("pyo3_test_pkg.sub").unwrap().is(&module));
+
+            let unregistered = PyModule::from_code_with_options(
+                py,
+                "X = 1",
+                "unregistered.py",
+                "pyo3_test_unregistered",
+                FromCodeOptions::new().register(false),
+            )
+            .unwrap();
+            assert_eq!(
+                unregistered.filename().unwrap().to_cow().unwrap(),
+                "unregistered.py"
+            );
+            assert!(py.import_bound("pyo3_test_unregistered").is_err());
+
+            let err = PyModule::from_code_with_options(
+                py,
+                "1 / 0",
+                "failing.py",
+                "pyo3_test_failing",
+                FromCodeOptions::new(),
+            )
+            .unwrap_err();
+            assert!(err.is_instance_of::<PyZeroDivisionError>(py));
+            let modules = py.import_bound("sys").unwrap().getattr("modules").unwrap();
+            assert!(!modules.contains("pyo3_test_failing").unwrap());
+        })
+    }
 }
@@ -68,6 +68,8 @@ impl IntoPy<PyObject> for () {
     }
 }
 
+into_pyobject_via_into_py!(());
+
 #[cfg(test)]
 mod tests {
     use crate::types::any::PyAnyMethods;
@@ -8,7 +8,10 @@ use crate::internal_tricks::get_ssize_index;
 use crate::py_result_ext::PyResultExt;
 use crate::sync::GILOnceCell;
 use crate::type_object::PyTypeInfo;
-use crate::types::{any::PyAnyMethods, PyAny, PyList, PyString, PyTuple, PyType};
+use crate::types::{
+    any::PyAnyMethods, list::PyListMethods, tuple::PyTupleMethods, PyAny, PyList, PyString,
+    PyTuple, PyType,
+};
 use crate::{ffi, FromPyObject, Py, PyNativeType, PyTypeCheck, Python, ToPyObject};
 
 /// Represents a reference to a Python object supporting the sequence protocol.
@@ -498,6 +501,15 @@ fn extract_sequence<'py, T>(obj: &Bound<'py, PyAny>) -> PyResult<Vec<T>>
 where
     T: FromPyObject<'py>,
 {
+    // Exact lists and tuples are indexed directly rather than through the iterator protocol,
+    // which is noticeably faster for the common case of extracting a homogeneous list.
+    if let Ok(list) = obj.downcast_exact::<PyList>() {
+        return extract_list(list);
+    }
+    if let Ok(tuple) = obj.downcast_exact::<PyTuple>() {
+        return extract_tuple(tuple);
+    }
+
     // Types that pass `PySequence_Check` usually implement enough of the sequence protocol
     // to support this function and if not, we will only fail extraction safely.
     let seq = unsafe {
@@ -515,6 +527,46 @@ where
     Ok(v)
 }
 
+fn extract_list<'py, T>(list: &Bound<'py, PyList>) -> PyResult<Vec<T>>
+where
+    T: FromPyObject<'py>,
+{
+    let mut v = Vec::with_capacity(list.len());
+    // The length is re-read on every step, as extracting an item may run arbitrary Python
+    // code which modifies the list.
+    let mut index = 0;
+    while index < list.len() {
+        // With the GIL held, the list cannot change between the bounds check and the read, so
+        // the unchecked access is sound. Each item is still made owned, as the extraction of
+        // the item may remove it from the list (see #890).
+        #[cfg(not(any(Py_LIMITED_API, py_sys_config = "Py_GIL_DISABLED")))]
+        let item = unsafe { list.get_item_unchecked(index) };
+        #[cfg(any(Py_LIMITED_API, py_sys_config = "Py_GIL_DISABLED"))]
+        let item = list.get_item(index)?;
+        v.push(item.extract::<T>()?);
+        index += 1;
+    }
+    Ok(v)
+}
+
+fn extract_tuple<'py, T>(tuple: &Bound<'py, PyTuple>) -> PyResult<Vec<T>>
+where
+    T: FromPyObject<'py>,
+{
+    let mut v = Vec::with_capacity(tuple.len());
+    // Tuples are immutable, so their items can be read in place without touching the
+    // reference counts.
+    #[cfg(not(Py_LIMITED_API))]
+    for item in tuple.as_slice() {
+        v.push(item.extract::<T>()?);
+    }
+    #[cfg(Py_LIMITED_API)]
+    for item in tuple.iter() {
+        v.push(item.extract::<T>()?);
+    }
+    Ok(v)
+}
+
 fn get_sequence_abc(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
     static SEQUENCE_ABC: GILOnceCell<Py<PyType>> = GILOnceCell::new();
 
@@ -568,7 +620,7 @@ impl<'v> crate::PyTryFrom<'v> for PySequence {
 #[cfg(test)]
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
-    use crate::types::{PyList, PySequence, PyTuple};
+    use crate::types::{PyDict, PyList, PySequence, PyTuple};
     use crate::{PyObject, Python, ToPyObject};
 
     fn get_object() -> PyObject {
@@ -1036,6 +1088,36 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_extract_list_subclass_to_vec() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            py.run(
+                "class MyList(list):\n    pass\nobj = MyList([1, 2, 3])",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+            let v: Vec<i32> = locals.get_item("obj").unwrap().unwrap().extract().unwrap();
+            assert!(v == [1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn test_extract_list_modified_during_extraction() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            py.run(
+                "class Clear:\n    def __index__(self):\n        obj.clear()\n        return 1\nobj = [Clear(), 2, 3]",
+                Some(locals),
+                Some(locals),
+            )
+            .unwrap();
+            let v: Vec<i32> = locals.get_item("obj").unwrap().unwrap().extract().unwrap();
+            assert!(v == [1]);
+        });
+    }
+
     #[test]
     fn test_extract_range_to_vec() {
         Python::with_gil(|py| {
@@ -58,6 +58,29 @@ impl PySet {
         new_from_iter(py, elements)
     }
 
+    /// Creates a new set, adding each element as the iterator produces it.
+    ///
+    /// Unlike [`PySet::new_bound`], the iterator may yield owned values, so elements do not have
+    /// to be collected first. Returns an error if some element is not hashable.
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PySet;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let squares = PySet::new_from_iter(py, (1..=4).map(|i| i * i)).unwrap();
+    ///     assert_eq!(squares.len(), 4);
+    ///     assert!(squares.contains(16).unwrap());
+    /// });
+    /// ```
+    #[inline]
+    pub fn new_from_iter<T: ToPyObject>(
+        py: Python<'_>,
+        elements: impl IntoIterator<Item = T>,
+    ) -> PyResult<Bound<'_, PySet>> {
+        new_from_iter(py, elements)
+    }
+
     /// Deprecated form of [`PySet::empty_bound`].
     pub fn empty(py: Python<'_>) -> PyResult<&'_ PySet> {
         Self::empty_bound(py).map(Bound::into_gil_ref)
@@ -124,6 +147,25 @@ impl PySet {
         self.as_borrowed().pop().map(Bound::unbind)
     }
 
+    /// Returns a new set with the elements of this set and of `other`, which may be any iterable.
+    ///
+    /// This is equivalent to the Python expression `self.union(other)`.
+    pub fn union(&self, other: &PyAny) -> PyResult<&PySet> {
+        self.as_borrowed()
+            .union(&other.as_borrowed())
+            .map(Bound::into_gil_ref)
+    }
+
+    /// Returns a new set with the elements common to this set and `other`, which may be any
+    /// iterable.
+    ///
+    /// This is equivalent to the Python expression `self.intersection(other)`.
+    pub fn intersection(&self, other: &PyAny) -> PyResult<&PySet> {
+        self.as_borrowed()
+            .intersection(&other.as_borrowed())
+            .map(Bound::into_gil_ref)
+    }
+
     /// Returns an iterator of values in this set.
     ///
     /// # Panics
@@ -176,6 +218,17 @@ pub trait PySetMethods<'py> {
     /// Removes and returns an arbitrary element from the set.
     fn pop(&self) -> Option<Bound<'py, PyAny>>;
 
+    /// Returns a new set with the elements of this set and of `other`, which may be any iterable.
+    ///
+    /// This is equivalent to the Python expression `self.union(other)`.
+    fn union(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>>;
+
+    /// Returns a new set with the elements common to this set and `other`, which may be any
+    /// iterable.
+    ///
+    /// This is equivalent to the Python expression `self.intersection(other)`.
+    fn intersection(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>>;
+
     /// Returns an iterator of values in this set.
     ///
     /// # Panics
@@ -251,6 +304,19 @@ impl<'py> PySetMethods<'py> for Bound<'py, PySet> {
         }
     }
 
+    fn union(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>> {
+        // A subclass may override `union`, so the result has to be checked
+        self.call_method1(intern!(self.py(), "union"), (other,))?
+            .downcast_into()
+            .map_err(Into::into)
+    }
+
+    fn intersection(&self, other: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PySet>> {
+        self.call_method1(intern!(self.py(), "intersection"), (other,))?
+            .downcast_into()
+            .map_err(Into::into)
+    }
+
     fn iter(&self) -> BoundSetIterator<'py> {
         BoundSetIterator::new(self.clone())
     }
@@ -407,33 +473,37 @@ pub(crate) fn new_from_iter<T: ToPyObject>(
     py: Python<'_>,
     elements: impl IntoIterator<Item = T>,
 ) -> PyResult<Bound<'_, PySet>> {
-    fn inner<'py>(
-        py: Python<'py>,
-        elements: &mut dyn Iterator<Item = PyObject>,
-    ) -> PyResult<Bound<'py, PySet>> {
-        let set = unsafe {
-            // We create the  `Py` pointer because its Drop cleans up the set if user code panics.
-            ffi::PySet_New(std::ptr::null_mut())
-                .assume_owned_or_err(py)?
-                .downcast_into_unchecked()
-        };
-        let ptr = set.as_ptr();
+    let mut iter = elements
+        .into_iter()
+        .map(|e| Ok(e.to_object(py).into_bound(py)));
+    try_new_from_iter(py, &mut iter)
+}
 
-        for obj in elements {
-            err::error_on_minusone(py, unsafe { ffi::PySet_Add(ptr, obj.as_ptr()) })?;
-        }
+/// Like [`new_from_iter`], for elements whose conversion can fail; the first error is returned.
+pub(crate) fn try_new_from_iter<'py>(
+    py: Python<'py>,
+    elements: &mut dyn Iterator<Item = PyResult<Bound<'py, PyAny>>>,
+) -> PyResult<Bound<'py, PySet>> {
+    let set = unsafe {
+        // We create the  `Py` pointer because its Drop cleans up the set if user code panics.
+        ffi::PySet_New(std::ptr::null_mut())
+            .assume_owned_or_err(py)?
+            .downcast_into_unchecked()
+    };
+    let ptr = set.as_ptr();
 
-        Ok(set)
+    for obj in elements {
+        err::error_on_minusone(py, unsafe { ffi::PySet_Add(ptr, obj?.as_ptr()) })?;
     }
 
-    let mut iter = elements.into_iter().map(|e| e.to_object(py));
-    inner(py, &mut iter)
+    Ok(set)
 }
 
 #[cfg(test)]
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
-    use super::PySet;
+    use super::{PySet, PySetMethods};
+    use crate::types::any::PyAnyMethods;
     use crate::{Python, ToPyObject};
     use std::collections::HashSet;
 
@@ -527,6 +597,44 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_set_new_from_iter() {
+        Python::with_gil(|py| {
+            let set = PySet::new_from_iter(py, (0..5).map(|i| i % 3)).unwrap();
+            assert_eq!(set.len(), 3);
+            for i in 0..3 {
+                assert!(set.contains(i).unwrap());
+            }
+
+            let unhashable = vec![vec![1]];
+            assert!(PySet::new_from_iter(py, unhashable).is_err());
+        });
+    }
+
+    #[test]
+    fn test_set_union_intersection() {
+        Python::with_gil(|py| {
+            let set = PySet::new_bound(py, &[1, 2, 3]).unwrap();
+            let other = vec![2, 3, 4].to_object(py).into_bound(py);
+
+            let union = set.union(&other).unwrap();
+            assert_eq!(
+                union.extract::<HashSet<i32>>().unwrap(),
+                [1, 2, 3, 4].into()
+            );
+            let intersection = set.intersection(&other).unwrap();
+            assert_eq!(
+                intersection.extract::<HashSet<i32>>().unwrap(),
+                [2, 3].into()
+            );
+            // the original set is unchanged
+            assert_eq!(set.len(), 3);
+
+            let not_iterable = 5.to_object(py).into_bound(py);
+            assert!(set.union(&not_iterable).is_err());
+        });
+    }
+
     #[test]
     fn test_set_iter() {
         Python::with_gil(|py| {
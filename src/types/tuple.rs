@@ -9,8 +9,8 @@ use crate::instance::Borrowed;
 use crate::internal_tricks::get_ssize_index;
 use crate::types::{any::PyAnyMethods, sequence::PySequenceMethods, PyList, PySequence};
 use crate::{
-    exceptions, Bound, FromPyObject, IntoPy, Py, PyAny, PyErr, PyNativeType, PyObject, PyResult,
-    Python, ToPyObject,
+    exceptions, Bound, FromPyObject, IntoPy, IntoPyObject, Py, PyAny, PyErr, PyNativeType, PyObject,
+    PyResult, Python, ToPyObject,
 };
 
 #[inline]
@@ -655,6 +655,17 @@ fn type_output() -> TypeInfo {
         }
     }
 
+    impl <'py, $($T: IntoPyObject<'py>),+> IntoPyObject<'py> for ($($T,)+) {
+        fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            Ok(array_into_tuple(py, [$(self.$n.into_pyobject(py)?.unbind()),+]).into_bound(py).into_any())
+        }
+
+        #[cfg(feature = "experimental-inspect")]
+        fn type_output() -> TypeInfo {
+            TypeInfo::Tuple(Some(vec![$( $T::type_output() ),+]))
+        }
+    }
+
     impl <$($T: IntoPy<PyObject>),+> IntoPy<Py<PyTuple>> for ($($T,)+) {
         fn into_py(self, py: Python<'_>) -> Py<PyTuple> {
             array_into_tuple(py, [$(self.$n.into_py(py)),+])
@@ -1,5 +1,7 @@
 use crate::err::{self, PyResult};
-use crate::{ffi, PyAny, PyTypeInfo, Python};
+use crate::types::any::PyAnyMethods;
+use crate::types::{PyDict, PyString, PyTuple};
+use crate::{ffi, Bound, PyAny, PyTypeInfo, Python};
 use std::borrow::Cow;
 #[cfg(not(any(Py_LIMITED_API, PyPy)))]
 use std::ffi::CStr;
@@ -17,6 +19,53 @@ impl PyType {
         T::type_object_bound(py).into_gil_ref()
     }
 
+    /// Creates a new Python class at runtime.
+    ///
+    /// Equivalent to the Python expression `type(name, bases, dict)`. If any of `bases` has a
+    /// custom metaclass, that metaclass is used to create the class.
+    ///
+    /// Rust closures created with [`PyCFunction::new_closure_bound`] can be placed in `dict`,
+    /// however like all builtin functions they do not bind to instances of the class. Wrap them in
+    /// `functools.partialmethod` to receive the instance as the first argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::{PyCFunction, PyDict, PyTuple, PyType};
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let greet = PyCFunction::new_closure_bound(py, None, None, |args, _kwargs| -> PyResult<String> {
+    ///         let name: String = args.get_item(0)?.getattr("name")?.extract()?;
+    ///         Ok(format!("Hello, {}!", name))
+    ///     })?;
+    ///     let partialmethod = py.import_bound("functools")?.getattr("partialmethod")?;
+    ///
+    ///     let dict = PyDict::new_bound(py);
+    ///     dict.set_item("name", "world")?;
+    ///     dict.set_item("greet", partialmethod.call1((greet,))?)?;
+    ///
+    ///     let cls = PyType::new_heap_type(py, "Greeter", &PyTuple::empty_bound(py), &dict)?;
+    ///     let greeting: String = cls.call0()?.call_method0("greet")?.extract()?;
+    ///     assert_eq!(greeting, "Hello, world!");
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    ///
+    /// [`PyCFunction::new_closure_bound`]: crate::types::PyCFunction::new_closure_bound
+    pub fn new_heap_type<'py>(
+        py: Python<'py>,
+        name: &str,
+        bases: &Bound<'py, PyTuple>,
+        dict: &Bound<'py, PyDict>,
+    ) -> PyResult<Bound<'py, PyType>> {
+        PyType::type_object_bound(py)
+            .call1((name, bases, dict))?
+            .downcast_into()
+            .map_err(Into::into)
+    }
+
     /// Retrieves the underlying FFI pointer associated with this Python object.
     #[inline]
     pub fn as_type_ptr(&self) -> *mut ffi::PyTypeObject {
@@ -87,6 +136,25 @@ impl PyType {
         }
     }
 
+    /// Gets the name of the module defining the `PyType`.
+    ///
+    /// Equivalent to the Python expression `self.__module__`.
+    pub fn module(&self) -> PyResult<&PyString> {
+        self.getattr(intern!(self.py(), "__module__"))?
+            .downcast()
+            .map_err(Into::into)
+    }
+
+    /// Gets the [method resolution order](https://docs.python.org/3/glossary.html#term-method-resolution-order)
+    /// of the `PyType`, starting with the type itself.
+    ///
+    /// Equivalent to the Python expression `self.__mro__`.
+    pub fn mro(&self) -> PyResult<&PyTuple> {
+        self.getattr(intern!(self.py(), "__mro__"))?
+            .downcast()
+            .map_err(Into::into)
+    }
+
     /// Checks whether `self` is a subclass of `other`.
     ///
     /// Equivalent to the Python expression `issubclass(self, other)`.
@@ -110,8 +178,9 @@ impl PyType {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{PyBool, PyLong};
-    use crate::Python;
+    use crate::types::any::PyAnyMethods;
+    use crate::types::{PyBool, PyDict, PyLong, PyTuple, PyType};
+    use crate::{PyAny, Python};
 
     #[test]
     fn test_type_is_subclass() {
@@ -128,4 +197,55 @@ mod tests {
             assert!(py.get_type::<PyBool>().is_subclass_of::<PyLong>().unwrap());
         });
     }
+
+    #[test]
+    fn test_new_heap_type() {
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("answer", 42).unwrap();
+            let bases = PyTuple::new_bound(py, [py.get_type::<PyLong>()]);
+            let cls = PyType::new_heap_type(py, "MyInt", &bases, &dict).unwrap();
+            assert_eq!(cls.getattr("__name__").unwrap().to_string(), "MyInt");
+            assert!(cls.as_gil_ref().is_subclass_of::<PyLong>().unwrap());
+            let instance = cls.call1((5,)).unwrap();
+            assert_eq!(instance.extract::<i32>().unwrap(), 5);
+            assert_eq!(
+                instance
+                    .getattr("answer")
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap(),
+                42
+            );
+        });
+    }
+
+    #[test]
+    fn test_new_heap_type_bad_bases() {
+        Python::with_gil(|py| {
+            let bases = PyTuple::new_bound(py, [py.get_type::<PyBool>()]);
+            let err = PyType::new_heap_type(py, "Bad", &bases, &PyDict::new_bound(py)).unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_type_module() {
+        Python::with_gil(|py| {
+            let module = py.get_type::<PyBool>().module().unwrap();
+            assert_eq!(module.to_str().unwrap(), "builtins");
+        });
+    }
+
+    #[test]
+    fn test_type_mro() {
+        Python::with_gil(|py| {
+            let bool_type = py.get_type::<PyBool>();
+            let mro = bool_type.mro().unwrap();
+            assert_eq!(mro.len(), 3);
+            assert!(mro.get_item(0).unwrap().is(bool_type));
+            assert!(mro.get_item(1).unwrap().is(py.get_type::<PyLong>()));
+            assert!(mro.get_item(2).unwrap().is(py.get_type::<PyAny>()));
+        });
+    }
 }
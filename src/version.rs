@@ -72,6 +72,46 @@ impl<'py> PythonVersionInfo<'py> {
     }
 }
 
+/// The implementation of the running Python interpreter, as named by `sys.implementation.name`.
+///
+/// This struct is usually created with [`Python::implementation`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use pyo3::{Python, PythonImplementation};
+/// Python::with_gil(|py| {
+///     if py.implementation() == PythonImplementation::PyPy {
+///         // work around PyPy-specific behaviour
+///     }
+/// });
+/// ```
+///
+/// [`Python::implementation`]: crate::marker::Python::implementation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PythonImplementation {
+    /// The reference implementation, CPython.
+    CPython,
+    /// PyPy.
+    PyPy,
+    /// GraalPy.
+    GraalPy,
+    /// Any other implementation, such as a future one which PyO3 does not know about.
+    Other,
+}
+
+impl PythonImplementation {
+    pub(crate) fn from_name(name: &str) -> Self {
+        match name {
+            "cpython" => PythonImplementation::CPython,
+            "pypy" => PythonImplementation::PyPy,
+            "graalpy" => PythonImplementation::GraalPy,
+            _ => PythonImplementation::Other,
+        }
+    }
+}
+
 impl PartialEq<(u8, u8)> for PythonVersionInfo<'_> {
     fn eq(&self, other: &(u8, u8)) -> bool {
         self.major == other.0 && self.minor == other.1
@@ -99,6 +139,7 @@ impl PartialOrd<(u8, u8, u8)> for PythonVersionInfo<'_> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::types::any::PyAnyMethods;
     use crate::Python;
     #[test]
     fn test_python_version_info() {
@@ -127,6 +168,34 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_python_implementation() {
+        Python::with_gil(|py| {
+            let implementation = py.implementation();
+            #[cfg(PyPy)]
+            assert_eq!(implementation, PythonImplementation::PyPy);
+            #[cfg(not(PyPy))]
+            assert_eq!(implementation, PythonImplementation::CPython);
+            assert_eq!(
+                PythonImplementation::from_name("graalpy"),
+                PythonImplementation::GraalPy
+            );
+            assert_eq!(
+                PythonImplementation::from_name("ironpython"),
+                PythonImplementation::Other
+            );
+
+            let gil_disabled: Option<i32> = py
+                .import_bound("sysconfig")
+                .unwrap()
+                .call_method1("get_config_var", ("Py_GIL_DISABLED",))
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(py.is_free_threaded(), gil_disabled == Some(1));
+        });
+    }
+
     #[test]
     fn test_python_version_info_parse() {
         assert!(PythonVersionInfo::from_str("3.5.0a1").unwrap() >= (3, 5, 0));
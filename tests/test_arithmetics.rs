@@ -541,6 +541,35 @@ fn rich_comparisons_python_3_type_error() {
     });
 }
 
+#[pyclass(ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct OrderedNumber(i32);
+
+#[test]
+fn rich_comparisons_from_ord() {
+    Python::with_gil(|py| {
+        let one = PyCell::new(py, OrderedNumber(1)).unwrap();
+        let two = PyCell::new(py, OrderedNumber(2)).unwrap();
+        py_run!(py, one two, "assert one < two");
+        py_run!(py, one two, "assert one <= two");
+        py_run!(py, one two, "assert not one == two");
+        py_run!(py, one two, "assert one != two");
+        py_run!(py, one two, "assert two > one");
+        py_run!(py, one two, "assert two >= one");
+        py_run!(py, one, "assert one == one");
+        py_run!(py, one, "assert sorted([one, one]) == [one, one]");
+
+        // incompatible types return NotImplemented rather than raising
+        py_run!(py, one, "assert (one == 1) == False");
+        py_run!(py, one, "assert (one != 1) == True");
+        py_expect_exception!(py, one, "one < 1", PyTypeError);
+
+        // a failure to borrow the other operand is raised
+        let _guard = two.borrow_mut();
+        py_expect_exception!(py, one two, "one < two", PyRuntimeError);
+    });
+}
+
 // Checks that binary operations for which the arguments don't match the
 // required type, return NotImplemented.
 mod return_not_implemented {
@@ -96,6 +96,41 @@ fn recursive_class_attributes() {
     });
 }
 
+#[pyclass]
+struct Point {
+    #[pyo3(get)]
+    x: i32,
+    #[pyo3(get)]
+    y: i32,
+}
+
+#[pymethods]
+impl Point {
+    #[classattr]
+    const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    #[classattr]
+    const AXES: (&'static str, &'static str) = ("x", "y");
+
+    #[classattr]
+    const LIMITS: [i32; 2] = [i32::MIN, i32::MAX];
+
+    #[classattr]
+    const UNIT: Option<i32> = None;
+}
+
+#[test]
+fn class_attribute_consts() {
+    Python::with_gil(|py| {
+        let point = py.get_type::<Point>();
+        py_assert!(py, point, "isinstance(point.ORIGIN, point)");
+        py_assert!(py, point, "(point.ORIGIN.x, point.ORIGIN.y) == (0, 0)");
+        py_assert!(py, point, "point.AXES == ('x', 'y')");
+        py_assert!(py, point, "point.LIMITS == [-2**31, 2**31 - 1]");
+        py_assert!(py, point, "point.UNIT is None");
+    });
+}
+
 #[test]
 fn test_fallible_class_attribute() {
     use pyo3::{exceptions::PyValueError, types::PyString};
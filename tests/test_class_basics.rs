@@ -1,5 +1,6 @@
 #![cfg(feature = "macros")]
 
+use pyo3::exceptions::{PyIndexError, PyKeyError};
 use pyo3::prelude::*;
 use pyo3::types::PyType;
 use pyo3::{py_run, PyClass};
@@ -21,6 +22,68 @@ fn empty_class() {
     });
 }
 
+struct Wrapper<T> {
+    inner: T,
+}
+
+/// Wraps a float.
+#[pyclass]
+type WrapperF64 = Wrapper<f64>;
+
+#[pyclass(name = "WrapperStr", module = "wrappers")]
+type WrapperString = Wrapper<String>;
+
+#[pymethods]
+impl WrapperF64 {
+    #[new]
+    fn new(inner: f64) -> Self {
+        Wrapper { inner }
+    }
+
+    #[getter]
+    fn inner(&self) -> f64 {
+        self.inner
+    }
+}
+
+#[pymethods]
+impl WrapperString {
+    #[new]
+    fn new(inner: String) -> Self {
+        Wrapper { inner }
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+#[test]
+fn generic_class_instantiations() {
+    Python::with_gil(|py| {
+        let float = py.get_type::<WrapperF64>();
+        let string = py.get_type::<WrapperString>();
+        py_run!(
+            py,
+            float string,
+            r#"
+assert float.__name__ == "WrapperF64"
+assert float.__doc__ == "Wraps a float."
+assert float(1.5).inner == 1.5
+assert string.__name__ == "WrapperStr"
+assert string.__module__ == "wrappers"
+assert len(string("abc")) == 3
+assert float is not string
+"#
+        );
+
+        let obj = Bound::new(py, Wrapper { inner: 2.0 }).unwrap();
+        assert!(obj.as_any().is_instance_of::<WrapperF64>());
+        assert!(!obj.as_any().is_instance_of::<WrapperString>());
+        assert_eq!(obj.borrow().inner, 2.0);
+    });
+}
+
 #[pyclass]
 struct UnitClass;
 
@@ -59,11 +122,26 @@ struct ClassWithDocs {
 fn class_with_docstr() {
     Python::with_gil(|py| {
         let typeobj = py.get_type::<ClassWithDocs>();
+        #[cfg(not(feature = "experimental-inspect"))]
         py_run!(
             py,
             typeobj,
             "assert typeobj.__doc__ == 'Line1\\nLine2\\n Line3'"
         );
+        #[cfg(feature = "experimental-inspect")]
+        py_run!(
+            py,
+            typeobj,
+            r#"
+assert typeobj.__doc__ == """Line1
+Line2
+ Line3
+
+Attributes:
+    value (int): Property field
+    readonly (int): Read-only property field"""
+"#
+        );
         py_run!(
             py,
             typeobj,
@@ -603,3 +681,359 @@ fn drop_unsendable_elsewhere() {
         capture.borrow_mut(py).uninstall(py);
     });
 }
+
+#[pyclass(name = "type", module = "naming.ünïcode")]
+struct KeywordName {
+    #[pyo3(get, name = "match")]
+    value: i32,
+}
+
+#[pymethods]
+impl KeywordName {
+    #[pyo3(name = "async")]
+    fn async_(&self) -> i32 {
+        self.value
+    }
+}
+
+#[pyclass(name = "Ünïcode")]
+struct UnicodeName;
+
+#[test]
+fn class_names_keywords_and_unicode() {
+    Python::with_gil(|py| {
+        let keyword = Py::new(py, KeywordName { value: 5 }).unwrap();
+        let unicode = py.get_type::<UnicodeName>();
+        py_run!(
+            py,
+            keyword unicode,
+            r#"
+assert type(keyword).__name__ == "type"
+assert type(keyword).__module__ == "naming.ünïcode"
+assert getattr(keyword, "match") == 5
+assert getattr(keyword, "async")() == 5
+assert unicode.__name__ == "Ünïcode"
+"#
+        );
+    });
+}
+
+#[pyclass(dataclass, get_all)]
+struct Point {
+    x: i32,
+    #[pyo3(name = "y_coord")]
+    y: i32,
+}
+
+#[pyclass(dataclass, rename_all = "camelCase")]
+struct Labelled {
+    #[pyo3(get)]
+    label_text: String,
+    #[allow(dead_code)]
+    hidden: i32,
+}
+
+#[test]
+fn dataclass_fields_and_asdict() {
+    Python::with_gil(|py| {
+        let point = Py::new(py, Point { x: 1, y: 2 }).unwrap();
+        let labelled = Py::new(
+            py,
+            Labelled {
+                label_text: "a".into(),
+                hidden: 0,
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            point labelled,
+            r#"
+import dataclasses
+assert type(point).__match_args__ == ("x", "y_coord")
+assert dataclasses.is_dataclass(point)
+assert [f.name for f in dataclasses.fields(point)] == ["x", "y_coord"]
+assert dataclasses.asdict(point) == {"x": 1, "y_coord": 2}
+assert dataclasses.astuple(point) == (1, 2)
+assert type(labelled).__match_args__ == ("labelText",)
+assert dataclasses.asdict(labelled) == {"labelText": "a"}
+"#
+        );
+    });
+}
+
+#[pyclass(dataclass)]
+struct CfgField {
+    #[pyo3(get)]
+    present: i32,
+    #[cfg(any())]
+    #[pyo3(get)]
+    absent: String,
+}
+
+#[test]
+fn dataclass_cfg_field() {
+    Python::with_gil(|py| {
+        let cfg_field = Py::new(py, CfgField { present: 1 }).unwrap();
+        py_run!(
+            py,
+            cfg_field,
+            r#"
+import dataclasses
+assert type(cfg_field).__match_args__ == ("present",)
+assert dataclasses.asdict(cfg_field) == {"present": 1}
+"#
+        );
+    });
+}
+
+#[test]
+#[cfg(not(feature = "experimental-inspect"))]
+fn getter_field_annotations_without_inspect() {
+    Python::with_gil(|py| {
+        let point = py.get_type::<Point>();
+        py_assert!(py, point, "'__annotations__' not in point.__dict__");
+        py_assert!(py, point, "point.__doc__ is None");
+    });
+}
+
+#[test]
+#[cfg(feature = "experimental-inspect")]
+fn getter_field_annotations() {
+    Python::with_gil(|py| {
+        let point = py.get_type::<Point>();
+        let labelled = py.get_type::<Labelled>();
+        let cfg_field = py.get_type::<CfgField>();
+        py_run!(
+            py,
+            point labelled cfg_field,
+            r#"
+import typing
+assert point.__annotations__ == {"x": int, "y_coord": int}
+assert typing.get_type_hints(point) == {"x": int, "y_coord": int}
+assert labelled.__annotations__ == {"labelText": str}
+assert cfg_field.__annotations__ == {"present": int}
+assert point.__doc__ == "Attributes:\n    x (int)\n    y_coord (int)"
+"#
+        );
+    });
+}
+
+#[test]
+#[cfg(Py_3_10)]
+#[allow(non_snake_case)]
+fn dataclass_match_statement() {
+    Python::with_gil(|py| {
+        let point = Py::new(py, Point { x: 0, y: 5 }).unwrap();
+        let Point = py.get_type::<Point>();
+        py_run!(
+            py,
+            point Point,
+            r#"
+match point:
+    case Point(0, y):
+        assert y == 5
+    case _:
+        assert False
+"#
+        );
+    });
+}
+
+#[pyclass(get_all, match_args = ("x", "y"))]
+struct Vector {
+    x: i32,
+    y: i32,
+}
+
+#[pyclass(sequence)]
+struct Pair {
+    items: [i32; 2],
+}
+
+#[pymethods]
+impl Pair {
+    fn __len__(&self) -> usize {
+        self.items.len()
+    }
+
+    fn __getitem__(&self, idx: isize) -> PyResult<i32> {
+        usize::try_from(idx)
+            .ok()
+            .and_then(|idx| self.items.get(idx).copied())
+            .ok_or_else(|| PyIndexError::new_err("index out of range"))
+    }
+}
+
+#[pyclass(mapping)]
+struct SingleEntry {
+    key: String,
+    value: i32,
+}
+
+#[pymethods]
+impl SingleEntry {
+    fn __len__(&self) -> usize {
+        1
+    }
+
+    fn __getitem__(&self, key: &str) -> PyResult<i32> {
+        if key == self.key {
+            Ok(self.value)
+        } else {
+            Err(PyKeyError::new_err(key.to_owned()))
+        }
+    }
+
+    #[pyo3(signature = (key, default = None))]
+    fn get(&self, py: Python<'_>, key: &str, default: Option<PyObject>) -> PyObject {
+        if key == self.key {
+            self.value.into_py(py)
+        } else {
+            default.unwrap_or_else(|| py.None())
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
+
+#[test]
+#[cfg(Py_3_10)]
+#[allow(non_snake_case)]
+fn match_statement_patterns() {
+    Python::with_gil(|py| {
+        let vector = Py::new(py, Vector { x: 1, y: 2 }).unwrap();
+        let Vector = py.get_type::<Vector>();
+        let pair = Py::new(py, Pair { items: [3, 4] }).unwrap();
+        let entry = Py::new(
+            py,
+            SingleEntry {
+                key: "k".into(),
+                value: 5,
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            vector Vector pair entry,
+            r#"
+assert Vector.__match_args__ == ("x", "y")
+match vector:
+    case Vector(1, y):
+        assert y == 2
+    case _:
+        assert False
+
+match pair:
+    case {}:
+        assert False
+    case [a, b]:
+        assert (a, b) == (3, 4)
+    case _:
+        assert False
+
+match entry:
+    case [*_]:
+        assert False
+    case {"k": v}:
+        assert v == 5
+    case _:
+        assert False
+"#
+        );
+    });
+}
+
+// `immortal` is a compile error where it is not supported
+#[cfg(all(
+    Py_3_12,
+    not(any(Py_LIMITED_API, PyPy, py_sys_config = "Py_GIL_DISABLED"))
+))]
+mod immutable_immortal {
+    use super::*;
+
+    #[pyclass(immutable_type, immortal)]
+    struct ImmutableImmortal {
+        #[pyo3(get)]
+        value: i32,
+    }
+
+    #[pymethods]
+    impl ImmutableImmortal {
+        #[classattr]
+        const ANSWER: i32 = 42;
+
+        fn method(&self) -> i32 {
+            1
+        }
+    }
+
+    #[test]
+    fn immutable_immortal_type() {
+        Python::with_gil(|py| {
+            let ty = py.get_type::<ImmutableImmortal>();
+            py_expect_exception!(py, ty, "ty.method = lambda self: 2", PyTypeError);
+            assert_ne!(unsafe { pyo3::ffi::_Py_IsImmortal(ty.as_ptr()) }, 0);
+            #[cfg(feature = "experimental-inspect")]
+            py_assert!(py, ty, "'value' in ty.__annotations__");
+
+            let obj = Py::new(py, ImmutableImmortal { value: 5 }).unwrap();
+            py_assert!(py, obj, "obj.method() == 1");
+            py_assert!(py, obj, "obj.ANSWER == 42");
+            py_assert!(py, obj, "obj.value == 5");
+        });
+    }
+}
+
+#[pyclass]
+struct CachedLookups {}
+
+#[pymethods]
+impl CachedLookups {
+    fn method(&self) -> i32 {
+        1
+    }
+}
+
+#[test]
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+fn type_attribute_cache_version_tag() {
+    Python::with_gil(|py| {
+        let ty = py.get_type::<CachedLookups>();
+        let version_tag = || unsafe { (*ty.as_type_ptr()).tp_version_tag };
+
+        // Looking up an attribute assigns a version tag, which enables the type attribute cache.
+        py_run!(py, ty, "ty.method");
+        let tag = version_tag();
+        assert_ne!(tag, 0);
+
+        // Modifying the class invalidates the cached lookups.
+        py_run!(py, ty, "ty.method = lambda self: 2");
+        assert_ne!(version_tag(), tag);
+        py_assert!(py, ty, "ty.method(None) == 2");
+        assert_ne!(version_tag(), 0);
+    });
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", ignore)]
+fn unsendable_raises_from_python_on_other_thread() {
+    let obj = Python::with_gil(|py| Py::new(py, UnsendableChild::new(5)).unwrap());
+
+    std::thread::spawn(move || {
+        Python::with_gil(|py| {
+            let err = obj.getattr(py, "value").unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyRuntimeError>(py));
+            assert_eq!(
+                err.value(py).to_string(),
+                "test_class_basics::UnsendableBase is unsendable, but sent to another thread"
+            );
+            // Leak the object, as it cannot be dropped on this thread.
+            std::mem::forget(obj);
+        });
+    })
+    .join()
+    .unwrap();
+}
@@ -1,6 +1,7 @@
 #![cfg(feature = "macros")]
 
 use pyo3::prelude::*;
+use pyo3::PyTypeInfo;
 use pyo3::ToPyObject;
 
 #[macro_use]
@@ -158,3 +159,65 @@ fn test_pycell_deref() {
         );
     });
 }
+
+/// Classes published under the same shared name by two modules. The definitions are not identical,
+/// as each module has its own Rust type, so only the first one to be created can be published.
+mod first_module {
+    use pyo3::prelude::*;
+
+    #[pyclass(shared = "test_class_conversion.SharedCounter")]
+    pub struct SharedCounter {
+        pub count: i64,
+    }
+}
+
+mod second_module {
+    use pyo3::prelude::*;
+
+    #[pyclass(shared = "test_class_conversion.SharedCounter")]
+    pub struct SharedCounter {
+        pub count: i64,
+    }
+
+    #[pyclass(shared = "test_class_conversion.SharedCounter")]
+    pub struct IncompatibleCounter {
+        pub count: i64,
+        pub limit: i64,
+    }
+}
+
+#[test]
+fn test_shared_pyclass_published() {
+    Python::with_gil(|py| {
+        let counter = first_module::SharedCounter::type_object_bound(py);
+        let registry = py
+            .import_bound("sys")
+            .unwrap()
+            .getattr("_pyo3_shared_types")
+            .unwrap();
+        py_assert!(
+            py,
+            registry,
+            "type(registry['test_class_conversion.SharedCounter']).__name__ == 'PyCapsule'"
+        );
+        assert!(first_module::SharedCounter::type_object_bound(py).is(&counter));
+    });
+}
+
+#[test]
+fn test_shared_pyclass_different_definition() {
+    Python::with_gil(|py| {
+        // Ensure the first class is published first.
+        first_module::SharedCounter::type_object_bound(py);
+        // Same layout, but a different Rust type
+        let result = std::panic::catch_unwind(|| {
+            second_module::SharedCounter::type_object_bound(py);
+        });
+        assert!(result.is_err());
+        // Different layout
+        let result = std::panic::catch_unwind(|| {
+            second_module::IncompatibleCounter::type_object_bound(py);
+        });
+        assert!(result.is_err());
+    });
+}
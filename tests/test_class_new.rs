@@ -3,7 +3,7 @@
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::sync::GILOnceCell;
-use pyo3::types::IntoPyDict;
+use pyo3::types::{IntoPyDict, PyList};
 
 #[pyclass]
 struct EmptyClassWithNew {}
@@ -270,3 +270,115 @@ fn test_new_existing() {
         assert!(!obj5.is(obj6));
     });
 }
+
+#[pyclass]
+struct SelfRegistering {
+    #[pyo3(get)]
+    value: i32,
+    #[pyo3(get)]
+    index: usize,
+}
+
+#[pymethods]
+impl SelfRegistering {
+    #[new]
+    fn new(value: i32, registry: &PyList) -> Self {
+        SelfRegistering {
+            value,
+            index: registry.len(),
+        }
+    }
+
+    #[init]
+    fn init(slf: &PyCell<Self>, value: i32, registry: &PyList) -> PyResult<()> {
+        if value < 0 {
+            return Err(PyValueError::new_err("value must not be negative"));
+        }
+        registry.append(slf)
+    }
+}
+
+#[test]
+fn test_init_receives_object_and_arguments() {
+    Python::with_gil(|py| {
+        let typeobj = py.get_type::<SelfRegistering>();
+        pyo3::py_run!(
+            py,
+            typeobj,
+            r#"
+registry = []
+obj = typeobj(1, registry)
+assert registry == [obj] and registry[0] is obj
+obj = typeobj(value=2, registry=registry)
+assert [(o.value, o.index) for o in registry] == [(1, 0), (2, 1)]
+
+try:
+    typeobj(-1, registry)
+except ValueError as e:
+    assert str(e) == "value must not be negative"
+else:
+    assert False, "expected ValueError"
+assert len(registry) == 2
+"#
+        );
+    });
+}
+
+#[pyclass]
+struct InitWithoutArguments {
+    #[pyo3(get)]
+    sum: i32,
+    #[pyo3(get)]
+    initialized: bool,
+}
+
+#[pymethods]
+impl InitWithoutArguments {
+    #[new]
+    fn new(a: i32, b: i32) -> Self {
+        InitWithoutArguments {
+            sum: a + b,
+            initialized: false,
+        }
+    }
+
+    #[init]
+    fn init(&mut self) {
+        self.initialized = true;
+    }
+}
+
+#[test]
+fn test_init_without_arguments_ignores_constructor_arguments() {
+    Python::with_gil(|py| {
+        let typeobj = py.get_type::<InitWithoutArguments>();
+        pyo3::py_run!(
+            py,
+            typeobj,
+            r#"
+obj = typeobj(1, b=2)
+assert obj.sum == 3 and obj.initialized
+"#
+        );
+    });
+}
+
+#[test]
+fn create_class_object_releases_reference_on_drop() {
+    Python::with_gil(|py| {
+        let obj = PyClassInitializer::from(EmptyClassWithNew {})
+            .create_class_object(py)
+            .unwrap();
+        assert_eq!(obj.as_any().get_refcnt(), 1);
+
+        // The reference is owned by `obj` alone, rather than also by the GIL pool
+        let other = obj.clone();
+        assert_eq!(obj.as_any().get_refcnt(), 2);
+        drop(other);
+        assert_eq!(obj.as_any().get_refcnt(), 1);
+
+        let typeobj = py.get_type::<EmptyClassWithNew>();
+        let created = typeobj.call0().unwrap();
+        assert!(created.is_instance_of::<EmptyClassWithNew>());
+    });
+}
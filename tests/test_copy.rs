@@ -0,0 +1,192 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+#[path = "../src/tests/common.rs"]
+mod common;
+
+#[pyclass(copy)]
+#[derive(Clone)]
+struct Counter {
+    #[pyo3(get, set)]
+    count: u32,
+    #[pyo3(get)]
+    history: Vec<u32>,
+    #[pyo3(get, set)]
+    payload: PyObject,
+}
+
+#[test]
+fn test_copy() {
+    Python::with_gil(|py| {
+        let counter = Py::new(
+            py,
+            Counter {
+                count: 1,
+                history: vec![0],
+                payload: py.None(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            counter,
+            r#"
+import copy
+c = copy.copy(counter)
+assert c is not counter
+assert c.count == 1
+c.count = 2
+assert counter.count == 1
+assert c.history == [0]
+"#
+        );
+    });
+}
+
+#[test]
+fn test_deepcopy_copies_python_fields() {
+    Python::with_gil(|py| {
+        let payload = pyo3::types::PyList::empty_bound(py);
+        let counter = Py::new(
+            py,
+            Counter {
+                count: 1,
+                history: vec![0, 1],
+                payload: payload.into_any().unbind(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            counter,
+            r#"
+import copy
+pair = [counter, counter]
+c = copy.deepcopy(pair)
+assert c[0] is c[1]
+assert c[0] is not counter
+assert c[0].history == [0, 1]
+assert c[0].payload == counter.payload
+assert c[0].payload is not counter.payload
+assert copy.copy(counter).payload is counter.payload
+"#
+        );
+    });
+}
+
+#[test]
+fn test_deepcopy_reference_cycle() {
+    Python::with_gil(|py| {
+        let counter = Py::new(
+            py,
+            Counter {
+                count: 1,
+                history: vec![],
+                payload: py.None(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            counter,
+            r#"
+import copy
+counter.payload = [counter]
+c = copy.deepcopy(counter)
+assert c is not counter
+assert c.payload is not counter.payload
+assert c.payload[0] is c
+"#
+        );
+    });
+}
+
+#[pyclass(copy, frozen)]
+#[derive(Clone)]
+struct Frozen {
+    #[pyo3(get)]
+    inner: Option<Py<Counter>>,
+}
+
+#[test]
+fn test_deepcopy_frozen() {
+    Python::with_gil(|py| {
+        let inner = Py::new(
+            py,
+            Counter {
+                count: 3,
+                history: vec![],
+                payload: py.None(),
+            },
+        )
+        .unwrap();
+        let frozen = Py::new(py, Frozen { inner: Some(inner) }).unwrap();
+        py_run!(
+            py,
+            frozen,
+            r#"
+import copy
+c = copy.deepcopy(frozen)
+assert c is not frozen
+assert c.inner is not frozen.inner
+assert c.inner.count == 3
+"#
+        );
+    });
+}
+
+#[test]
+fn test_deepcopy_requires_memo() {
+    Python::with_gil(|py| {
+        let counter = Py::new(
+            py,
+            Counter {
+                count: 0,
+                history: vec![],
+                payload: py.None(),
+            },
+        )
+        .unwrap();
+        py_expect_exception!(py, counter, "counter.__deepcopy__()", PyTypeError);
+    });
+}
+
+#[pyclass(copy, subclass)]
+#[derive(Clone)]
+struct Basket {
+    #[pyo3(get)]
+    items: Vec<PyObject>,
+}
+
+#[pymethods]
+impl Basket {
+    #[new]
+    fn new(items: Vec<PyObject>) -> Self {
+        Self { items }
+    }
+}
+
+#[test]
+fn test_deepcopy_nested_python_objects_and_subclass() {
+    Python::with_gil(|py| {
+        let basket = py.get_type::<Basket>();
+        py_run!(
+            py,
+            basket,
+            r#"
+import copy
+class SubBasket(basket):
+    pass
+b = SubBasket([[1], [2]])
+c = copy.deepcopy(b)
+assert type(c) is SubBasket
+assert c.items == [[1], [2]]
+assert c.items[0] is not b.items[0]
+assert type(copy.copy(b)) is SubBasket
+assert copy.copy(b).items[0] is b.items[0]
+"#
+        );
+    });
+}
@@ -4,7 +4,10 @@ use std::{ops::Deref, task::Poll, thread, time::Duration};
 
 use futures::{channel::oneshot, future::poll_fn, FutureExt};
 use pyo3::{
-    coroutine::CancelHandle,
+    coroutine::{
+        asyncio::{future_into_py, get_current_loop, into_future, scope},
+        CancelHandle, Coroutine,
+    },
     prelude::*,
     py_run,
     types::{IntoPyDict, PyType},
@@ -290,3 +293,90 @@ fn test_async_method_receiver() {
         py_run!(gil, *locals, test);
     })
 }
+
+#[test]
+fn asyncio_future_into_py() {
+    #[pyfunction]
+    fn rust_sleep(py: Python<'_>, seconds: f64) -> PyResult<Bound<'_, Coroutine>> {
+        future_into_py(py, async move { Ok::<_, PyErr>(sleep(seconds).await) })
+    }
+    Python::with_gil(|gil| {
+        let rust_sleep = wrap_pyfunction!(rust_sleep, gil).unwrap();
+        let test = r#"import asyncio; assert asyncio.run(rust_sleep(0.01)) == 42"#;
+        py_run!(gil, rust_sleep, &handle_windows(test));
+    })
+}
+
+#[test]
+fn asyncio_into_future() {
+    #[pyfunction]
+    async fn await_py(awaitable: PyObject) -> PyResult<PyObject> {
+        let future = Python::with_gil(|py| into_future(awaitable.bind(py)))?;
+        future.await
+    }
+    Python::with_gil(|gil| {
+        let await_py = wrap_pyfunction!(await_py, gil).unwrap();
+        let test = r#"
+        import asyncio
+        async def fail():
+            raise ValueError("failed")
+        async def main():
+            assert await await_py(asyncio.sleep(0.01, 42)) == 42
+            try:
+                await await_py(fail())
+            except ValueError as err:
+                assert str(err) == "failed"
+            else:
+                assert False
+            try:
+                await await_py(42)
+            except TypeError:
+                pass
+            else:
+                assert False
+        asyncio.run(main())
+        "#;
+        let globals = gil.import_bound("__main__").unwrap().dict();
+        globals.set_item("await_py", await_py).unwrap();
+        gil.run_bound(
+            &pyo3::unindent::unindent(&handle_windows(test)),
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    })
+}
+
+#[test]
+fn asyncio_scope_on_rust_thread() {
+    #[pyfunction]
+    fn on_rust_thread(py: Python<'_>, awaitable: PyObject) -> PyResult<Bound<'_, Coroutine>> {
+        let event_loop = get_current_loop(py)?.unbind();
+        let future = scope(event_loop, async move {
+            let future = Python::with_gil(|py| into_future(awaitable.bind(py)))?;
+            future.await
+        });
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            tx.send(futures::executor::block_on(future)).unwrap();
+        });
+        future_into_py(py, async move { rx.await.unwrap() })
+    }
+    Python::with_gil(|gil| {
+        let on_rust_thread = wrap_pyfunction!(on_rust_thread, gil).unwrap();
+        let test = r#"
+        import asyncio
+        async def main():
+            return await on_rust_thread(asyncio.sleep(0.01, 42))
+        assert asyncio.run(main()) == 42
+        "#;
+        let globals = gil.import_bound("__main__").unwrap().dict();
+        globals.set_item("on_rust_thread", on_rust_thread).unwrap();
+        gil.run_bound(
+            &pyo3::unindent::unindent(&handle_windows(test)),
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    })
+}
@@ -0,0 +1,21 @@
+#![cfg(not(PyPy))]
+
+use pyo3::prelude::*;
+
+// This must be the only test in this file, as it initializes and finalizes the interpreter.
+#[test]
+fn test_embedded_interpreter_with_signal_handlers() {
+    let installed = unsafe {
+        pyo3::with_embedded_python_interpreter_and_signal_handlers(|py| {
+            py.eval_bound(
+                "__import__('signal').getsignal(2) is __import__('signal').default_int_handler",
+                None,
+                None,
+            )
+            .and_then(|installed| installed.extract::<bool>())
+            .map_err(|e| e.print(py))
+            .unwrap()
+        })
+    };
+    assert!(installed);
+}
@@ -94,6 +94,26 @@ fn test_custom_discriminant() {
     })
 }
 
+#[pyclass(ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum OrderedEnum {
+    Low,
+    High,
+}
+
+#[test]
+fn test_enum_ord() {
+    Python::with_gil(|py| {
+        let low = Py::new(py, OrderedEnum::Low).unwrap();
+        let high = Py::new(py, OrderedEnum::High).unwrap();
+        py_assert!(py, low high, "low < high");
+        py_assert!(py, low high, "high >= low");
+        py_assert!(py, low high, "low != high");
+        py_assert!(py, low, "low == 0");
+        py_expect_exception!(py, low, "low < 'foo'", PyTypeError);
+    })
+}
+
 #[test]
 fn test_enum_to_int() {
     Python::with_gil(|py| {
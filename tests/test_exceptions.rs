@@ -23,15 +23,19 @@ fn fail_to_open_file() -> PyResult<()> {
 fn test_filenotfounderror() {
     Python::with_gil(|py| {
         let fail_to_open_file = wrap_pyfunction!(fail_to_open_file)(py).unwrap();
+        #[cfg(not(feature = "io-error-errno"))]
+        let message = "No such file or directory (os error 2)";
+        #[cfg(feature = "io-error-errno")]
+        let message = "[Errno 2] No such file or directory";
 
         py_run!(
             py,
-            fail_to_open_file,
+            fail_to_open_file message,
             r#"
         try:
             fail_to_open_file()
         except FileNotFoundError as e:
-            assert str(e) == "No such file or directory (os error 2)"
+            assert str(e) == message
         "#
         );
     });
@@ -83,6 +87,80 @@ fn test_custom_error() {
     });
 }
 
+#[cfg(not(Py_LIMITED_API))]
+#[pyclass(extends = exceptions::PyException)]
+struct CodedError {
+    #[pyo3(get)]
+    code: u32,
+    #[pyo3(get)]
+    source: Option<PyObject>,
+}
+
+#[cfg(not(Py_LIMITED_API))]
+#[pymethods]
+impl CodedError {
+    #[new]
+    #[pyo3(signature = (_message, code, source = None))]
+    fn new(_message: &Bound<'_, PyAny>, code: u32, source: Option<PyObject>) -> Self {
+        CodedError { code, source }
+    }
+
+    fn __str__(&self) -> String {
+        format!("error code {}", self.code)
+    }
+}
+
+#[cfg(not(Py_LIMITED_API))]
+#[pyfunction]
+fn raise_coded_error(py: Python<'_>, code: u32) -> PyResult<()> {
+    let source = py.eval_bound("KeyError('missing')", None, None)?.unbind();
+    let value = Bound::new(
+        py,
+        CodedError {
+            code,
+            source: Some(source),
+        },
+    )?;
+    Err(PyErr::from_value_bound(value.into_any()))
+}
+
+#[test]
+#[cfg(not(Py_LIMITED_API))]
+fn test_pyclass_exception_with_rust_fields() {
+    Python::with_gil(|py| {
+        let cls = py.get_type::<CodedError>();
+        let raise_coded_error = wrap_pyfunction!(raise_coded_error)(py).unwrap();
+
+        py_run!(
+            py,
+            cls raise_coded_error,
+            r#"
+        try:
+            raise_coded_error(7)
+        except cls as e:
+            assert isinstance(e, Exception)
+            assert e.code == 7
+            assert isinstance(e.source, KeyError)
+            assert str(e) == "error code 7"
+        else:
+            assert False, "expected CodedError"
+
+        try:
+            raise cls("from python", 3)
+        except Exception as e:
+            assert e.code == 3
+            assert e.source is None
+            assert e.args == ("from python", 3)
+        "#
+        );
+
+        let err = raise_coded_error.call1((11,)).unwrap_err();
+        assert!(err.is_instance_of::<CodedError>(py));
+        let value = err.value(py).downcast::<PyCell<CodedError>>().unwrap();
+        assert_eq!(value.borrow().code, 11);
+    });
+}
+
 #[test]
 fn test_exception_nosegfault() {
     use std::net::TcpListener;
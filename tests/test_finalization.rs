@@ -0,0 +1,41 @@
+#![cfg(all(feature = "macros", not(PyPy)))]
+
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+#[pyclass]
+struct DropCounter;
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// This must be the only test in this file, as it initializes and finalizes the interpreter.
+#[test]
+fn test_drop_without_gil_around_finalization() {
+    let outlived = unsafe {
+        pyo3::with_embedded_python_interpreter(|py| {
+            // Dropped by a thread which does not hold the GIL, so the release is deferred until
+            // the `atexit` hook runs at finalization.
+            let deferred = Py::new(py, DropCounter).unwrap();
+            std::thread::spawn(move || drop(deferred)).join().unwrap();
+            assert_eq!(DROPPED.load(Ordering::SeqCst), 0);
+
+            Py::new(py, DropCounter).unwrap()
+        })
+    };
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+
+    // The interpreter is finalized, so dropping the reference must not touch the object, even
+    // once a new interpreter is running.
+    drop(outlived);
+    unsafe {
+        pyo3::with_embedded_python_interpreter(|py| {
+            py.run_bound("import gc; gc.collect()", None, None).unwrap();
+        })
+    };
+}
@@ -2,7 +2,8 @@
 
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList, PyString, PyTuple};
+use pyo3::types::{IntoPyDict, PyDict, PyList, PyString, PyTuple};
+use pyo3::IntoPyTuple;
 
 #[macro_use]
 #[path = "../src/tests/common.rs"]
@@ -248,10 +249,14 @@ fn test_struct_nested_type_errors_with_generics() {
 
         let test = pybaz.extract::<Baz<usize, usize>>(py);
         assert!(test.is_err());
+        // Both failing fields are reported together
         assert_eq!(
             extract_traceback(py, test.unwrap_err()),
-            "TypeError: failed to extract field Baz.e: TypeError: failed to extract field E.test: \
-         TypeError: \'str\' object cannot be interpreted as an integer",
+            "TypeError: failed to extract Baz\n\
+            - TypeError: failed to extract field Baz.e, caused by TypeError: failed to extract field E.test, \
+            caused by TypeError: \'str\' object cannot be interpreted as an integer\n\
+            - TypeError: failed to extract field Baz.tup, caused by TypeError: failed to extract field Tuple.1, \
+            caused by TypeError: \'str\' object cannot be interpreted as an integer",
         );
     });
 }
@@ -525,10 +530,7 @@ fn test_from_py_with() {
 }
 
 #[derive(Debug, FromPyObject)]
-pub struct ZapTuple(
-    String,
-    #[pyo3(from_py_with = "Bound::<'_, PyAny>::len")] usize,
-);
+pub struct ZapTuple(String, #[pyo3(from_py_with = "str_len")] usize);
 
 #[test]
 fn test_from_py_with_tuple_struct() {
@@ -563,11 +565,8 @@ fn test_from_py_with_tuple_struct_error() {
 
 #[derive(Debug, FromPyObject, PartialEq, Eq)]
 pub enum ZapEnum {
-    Zip(#[pyo3(from_py_with = "Bound::<'_, PyAny>::len")] usize),
-    Zap(
-        String,
-        #[pyo3(from_py_with = "Bound::<'_, PyAny>::len")] usize,
-    ),
+    Zip(#[pyo3(from_py_with = "str_len")] usize),
+    Zap(String, #[pyo3(from_py_with = "str_len")] usize),
 }
 
 #[test]
@@ -587,7 +586,7 @@ fn test_from_py_with_enum() {
 #[derive(Debug, FromPyObject, PartialEq, Eq)]
 #[pyo3(transparent)]
 pub struct TransparentFromPyWith {
-    #[pyo3(from_py_with = "Bound::<'_, PyAny>::len")]
+    #[pyo3(from_py_with = "str_len")]
     len: usize,
 }
 
@@ -602,3 +601,283 @@ fn test_transparent_from_py_with() {
         assert_eq!(result, expected);
     });
 }
+
+#[derive(Debug, FromPyObject, PartialEq, Eq)]
+#[pyo3(from_item_all, deny_unknown_fields)]
+pub struct Config {
+    name: String,
+    #[pyo3(default)]
+    retries: usize,
+    #[pyo3(default = 30)]
+    timeout: u32,
+    #[pyo3(item("log-level"), default = String::from("info"))]
+    log_level: String,
+}
+
+#[test]
+fn test_config_defaults() {
+    Python::with_gil(|py| {
+        let dict = [("name", "server")].into_py_dict_bound(py);
+        assert_eq!(
+            dict.extract::<Config>().unwrap(),
+            Config {
+                name: "server".into(),
+                retries: 0,
+                timeout: 30,
+                log_level: "info".into(),
+            }
+        );
+
+        let dict = py
+            .eval_bound(
+                r#"{"name": "server", "retries": 3, "timeout": 5, "log-level": "debug"}"#,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            dict.extract::<Config>().unwrap(),
+            Config {
+                name: "server".into(),
+                retries: 3,
+                timeout: 5,
+                log_level: "debug".into(),
+            }
+        );
+    });
+}
+
+#[derive(Debug, FromPyObject, PartialEq, Eq)]
+pub struct Settings {
+    #[pyo3(default = 1)]
+    level: u32,
+}
+
+#[test]
+fn test_default_propagates_lookup_errors() {
+    Python::with_gil(|py| {
+        let locals = PyDict::new_bound(py);
+        py.run_bound(
+            r#"
+class Missing:
+    pass
+
+class Broken:
+    def __getattr__(self, name):
+        raise RuntimeError("lookup failed inside __getattr__")
+
+class BrokenItems(dict):
+    def __getitem__(self, key):
+        raise KeyError("lookup failed inside __getitem__")
+"#,
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+        let missing = py.eval_bound("Missing()", None, Some(&locals)).unwrap();
+        assert_eq!(
+            missing.extract::<Settings>().unwrap(),
+            Settings { level: 1 }
+        );
+
+        let broken = py.eval_bound("Broken()", None, Some(&locals)).unwrap();
+        assert_eq!(
+            broken.extract::<Settings>().unwrap_err().to_string(),
+            "RuntimeError: lookup failed inside __getattr__"
+        );
+
+        let broken_items = py
+            .eval_bound(
+                r#"BrokenItems({"name": "server", "retries": 1})"#,
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        assert_eq!(
+            broken_items.extract::<Config>().unwrap_err().to_string(),
+            "TypeError: failed to extract Config\n\
+            - KeyError: 'lookup failed inside __getitem__'\n\
+            - KeyError: 'lookup failed inside __getitem__'"
+        );
+    });
+}
+
+#[test]
+fn test_config_unknown_keys() {
+    Python::with_gil(|py| {
+        let dict = py
+            .eval_bound(r#"{"name": "server", "retry": 3, 1: 2}"#, None, None)
+            .unwrap();
+        assert_eq!(
+            dict.extract::<Config>().unwrap_err().to_string(),
+            "TypeError: Config got unexpected keys 'retry', 1"
+        );
+    });
+}
+
+#[test]
+fn test_config_aggregated_errors() {
+    Python::with_gil(|py| {
+        let dict = py
+            .eval_bound(r#"{"timeout": "never", "colour": True}"#, None, None)
+            .unwrap();
+        assert_eq!(
+            dict.extract::<Config>().unwrap_err().to_string(),
+            "TypeError: failed to extract Config\n\
+            - KeyError: 'name'\n\
+            - TypeError: failed to extract field Config.timeout, caused by TypeError: \
+            'str' object cannot be interpreted as an integer\n\
+            - TypeError: Config got unexpected key 'colour'"
+        );
+    });
+}
+
+fn str_len(obj: &Bound<'_, PyAny>) -> PyResult<usize> {
+    obj.len()
+}
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyTuple)]
+#[pyo3(from_tuple)]
+pub struct Point {
+    x: i32,
+    y: i32,
+    #[pyo3(from_py_with = "str_len")]
+    label_len: usize,
+}
+
+#[test]
+fn test_struct_from_tuple() {
+    Python::with_gil(|py| {
+        let tup = PyTuple::new_bound(py, [1.to_object(py), 2.to_object(py), "abc".to_object(py)]);
+        assert_eq!(
+            tup.extract::<Point>().unwrap(),
+            Point {
+                x: 1,
+                y: 2,
+                label_len: 3
+            }
+        );
+
+        let err = (1, "two", "abc")
+            .to_object(py)
+            .extract::<Point>(py)
+            .unwrap_err();
+        assert_eq!(
+            extract_traceback(py, err),
+            "TypeError: failed to extract field Point.y: \
+            TypeError: 'str' object cannot be interpreted as an integer"
+        );
+
+        let err = (1, 2).to_object(py).extract::<Point>(py).unwrap_err();
+        assert!(err.is_instance_of::<PyValueError>(py), "{}", err);
+    });
+}
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyTuple)]
+#[pyo3(from_tuple)]
+pub struct Single(String);
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyTuple)]
+#[pyo3(from_tuple)]
+pub struct Twelve<T> {
+    a: T,
+    b: T,
+    c: T,
+    d: T,
+    e: T,
+    f: T,
+    g: T,
+    h: T,
+    i: T,
+    j: T,
+    k: T,
+    l: T,
+}
+
+#[test]
+fn test_struct_tuple_round_trip() {
+    Python::with_gil(|py| {
+        let single: PyObject = Single("a".into()).into_py(py);
+        assert!(single.bind(py).is_exact_instance_of::<PyTuple>());
+        assert_eq!(single.extract::<(String,)>(py).unwrap(), ("a".to_owned(),));
+        assert_eq!(single.extract::<Single>(py).unwrap(), Single("a".into()));
+
+        let twelve = Twelve {
+            a: 0,
+            b: 1,
+            c: 2,
+            d: 3,
+            e: 4,
+            f: 5,
+            g: 6,
+            h: 7,
+            i: 8,
+            j: 9,
+            k: 10,
+            l: 11,
+        };
+        let obj = twelve.to_object(py);
+        assert_eq!(
+            obj.extract::<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>(py)
+                .unwrap(),
+            (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11)
+        );
+        assert_eq!(obj.extract::<Twelve<u8>>(py).unwrap(), twelve);
+
+        let args: Py<PyTuple> = twelve.into_py(py);
+        assert_eq!(args.bind(py).len(), 12);
+    });
+}
+
+#[derive(Debug, PartialEq, FromPyObject)]
+#[pyo3(rename_all = "camelCase")]
+pub struct CamelCaseFields {
+    #[pyo3(item)]
+    first_name: String,
+    #[pyo3(item)]
+    last_name: String,
+    #[pyo3(item("AGE"))]
+    age: u8,
+}
+
+#[derive(Debug, PartialEq, FromPyObject)]
+#[pyo3(rename_all = "camelCase")]
+pub enum CamelCaseEnum {
+    Point { x_pos: i32, y_pos: i32 },
+    Name(String),
+}
+
+#[test]
+fn test_rename_all() {
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("firstName", "Ada").unwrap();
+        dict.set_item("lastName", "Lovelace").unwrap();
+        dict.set_item("AGE", 36).unwrap();
+        assert_eq!(
+            dict.extract::<CamelCaseFields>().unwrap(),
+            CamelCaseFields {
+                first_name: "Ada".into(),
+                last_name: "Lovelace".into(),
+                age: 36
+            }
+        );
+
+        let locals = PyDict::new_bound(py);
+        py.run_bound(
+            "class Point:\n    xPos = 1\n    yPos = 2\npoint = Point()",
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+        let point = locals.get_item("point").unwrap().unwrap();
+        assert_eq!(
+            point.extract::<CamelCaseEnum>().unwrap(),
+            CamelCaseEnum::Point { x_pos: 1, y_pos: 2 }
+        );
+        assert_eq!(
+            "abc".to_object(py).extract::<CamelCaseEnum>(py).unwrap(),
+            CamelCaseEnum::Name("abc".into())
+        );
+    });
+}
@@ -557,6 +557,141 @@ fn unsendable_are_not_traversed_on_foreign_thread() {
     });
 }
 
+#[cfg(not(Py_LIMITED_API))]
+#[pyclass]
+struct Finalizable {
+    on_del: PyObject,
+    #[allow(dead_code)]
+    drop_check: TestDropCall,
+}
+
+#[cfg(not(Py_LIMITED_API))]
+#[pymethods]
+impl Finalizable {
+    fn __del__(&self, py: Python<'_>) -> PyResult<()> {
+        self.on_del.call0(py)?;
+        Ok(())
+    }
+}
+
+#[test]
+#[cfg(not(Py_LIMITED_API))]
+fn finalizer_runs_before_drop() {
+    Python::with_gil(|py| {
+        let drop_called = Arc::new(AtomicBool::new(false));
+        let calls = pyo3::types::PyList::empty_bound(py);
+        let on_del = {
+            let drop_called = Arc::clone(&drop_called);
+            let calls = calls.clone().unbind();
+            pyo3::types::PyCFunction::new_closure_bound(py, None, None, move |args, _kwargs| {
+                // The Rust value must still be alive when `__del__` runs
+                assert!(!drop_called.load(Ordering::Relaxed));
+                calls.bind(args.py()).append("__del__")
+            })
+            .unwrap()
+        };
+        let inst = Py::new(
+            py,
+            Finalizable {
+                on_del: on_del.into(),
+                drop_check: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+
+        // A pending exception is preserved while the finalizer runs
+        pyo3::exceptions::PyValueError::new_err("pending").restore(py);
+        drop(inst);
+        let pending = PyErr::take(py).unwrap();
+        assert!(pending.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+
+        assert_eq!(calls.len(), 1);
+        assert!(drop_called.load(Ordering::Relaxed));
+    });
+}
+
+#[test]
+#[cfg(all(Py_3_8, not(Py_LIMITED_API)))] // sys.unraisablehook not available until Python 3.8
+fn finalizer_error_is_unraisable() {
+    use common::UnraisableCapture;
+
+    Python::with_gil(|py| {
+        let capture = UnraisableCapture::install(py);
+        let drop_called = Arc::new(AtomicBool::new(false));
+        let inst = Py::new(
+            py,
+            Finalizable {
+                // not callable, so `__del__` fails
+                on_del: py.None(),
+                drop_check: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+        drop(inst);
+
+        let (err, object) = capture.borrow_mut(py).capture.take().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: 'NoneType' object is not callable"
+        );
+        assert!(object.is(py.get_type::<Finalizable>()));
+        assert!(drop_called.load(Ordering::Relaxed));
+
+        capture.borrow_mut(py).uninstall(py);
+    });
+}
+
+#[cfg(not(Py_LIMITED_API))]
+#[pyclass]
+struct Resurrecting {
+    graveyard: Option<Py<pyo3::types::PyList>>,
+    #[allow(dead_code)]
+    drop_check: TestDropCall,
+}
+
+#[cfg(not(Py_LIMITED_API))]
+#[pymethods]
+impl Resurrecting {
+    fn __del__(slf: &PyCell<Self>) -> PyResult<()> {
+        let graveyard = slf.borrow_mut().graveyard.take();
+        if let Some(graveyard) = graveyard {
+            graveyard.as_ref(slf.py()).append(slf)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+#[cfg(not(Py_LIMITED_API))]
+fn finalizer_can_resurrect() {
+    Python::with_gil(|py| {
+        let drop_called = Arc::new(AtomicBool::new(false));
+        let graveyard = pyo3::types::PyList::empty_bound(py);
+        let inst = Py::new(
+            py,
+            Resurrecting {
+                graveyard: Some(graveyard.clone().unbind()),
+                drop_check: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+        drop(inst);
+
+        // The object is alive again, and was not dropped
+        assert_eq!(graveyard.len(), 1);
+        assert!(!drop_called.load(Ordering::Relaxed));
+
+        graveyard.del_item(0).unwrap();
+        assert!(drop_called.load(Ordering::Relaxed));
+    });
+}
+
 // Manual traversal utilities
 
 unsafe fn get_type_traverse(tp: *mut pyo3::ffi::PyTypeObject) -> Option<pyo3::ffi::traverseproc> {
@@ -0,0 +1,142 @@
+#![cfg(feature = "macros")]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::generator::Generator;
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+#[pyfunction]
+fn squares(n: u64) -> Generator {
+    Generator::from_iterator((0..n).map(|i| i * i))
+}
+
+#[test]
+fn generator_from_iterator() {
+    Python::with_gil(|py| {
+        let squares = wrap_pyfunction!(squares, py).unwrap();
+        py_run!(
+            py,
+            squares,
+            r#"
+            import collections.abc
+            gen = squares(4)
+            assert isinstance(gen, collections.abc.Generator)
+            assert iter(gen) is gen
+            assert next(gen) == 0
+            assert list(gen) == [1, 4, 9]
+            assert list(gen) == []
+            "#
+        );
+    });
+}
+
+#[test]
+fn generator_is_lazy() {
+    #[pyfunction]
+    fn naturals() -> Generator {
+        Generator::from_iterator(0u64..)
+    }
+
+    Python::with_gil(|py| {
+        let naturals = wrap_pyfunction!(naturals, py).unwrap();
+        py_run!(
+            py,
+            naturals,
+            r#"
+            import itertools
+            assert list(itertools.islice(naturals(), 3)) == [0, 1, 2]
+            "#
+        );
+    });
+}
+
+#[test]
+fn generator_try_from_iterator() {
+    #[pyfunction]
+    fn parse(values: Vec<String>) -> Generator {
+        Generator::try_from_iterator(values.into_iter().map(|value| {
+            value
+                .parse::<i64>()
+                .map_err(|_| PyValueError::new_err(format!("invalid number: {}", value)))
+        }))
+    }
+
+    Python::with_gil(|py| {
+        let parse = wrap_pyfunction!(parse, py).unwrap();
+        py_run!(
+            py,
+            parse,
+            r#"
+            gen = parse(["1", "x", "3"])
+            assert next(gen) == 1
+            try:
+                next(gen)
+            except ValueError as err:
+                assert str(err) == "invalid number: x"
+            else:
+                assert False
+            assert list(gen) == []
+            "#
+        );
+    });
+}
+
+#[test]
+fn generator_send() {
+    #[pyfunction]
+    fn accumulate() -> Generator {
+        let mut total = 0;
+        Generator::from_fn(move |py, value| {
+            if !value.is_none(py) {
+                total += value.extract::<i64>(py)?;
+            }
+            Ok(Some(total))
+        })
+    }
+
+    Python::with_gil(|py| {
+        let accumulate = wrap_pyfunction!(accumulate, py).unwrap();
+        py_run!(
+            py,
+            accumulate,
+            r#"
+            gen = accumulate()
+            assert next(gen) == 0
+            assert gen.send(2) == 2
+            assert gen.send(3) == 5
+            assert next(gen) == 5
+            "#
+        );
+    });
+}
+
+#[test]
+fn generator_throw_and_close() {
+    Python::with_gil(|py| {
+        let squares = wrap_pyfunction!(squares, py).unwrap();
+        py_run!(
+            py,
+            squares,
+            r#"
+            gen = squares(4)
+            assert next(gen) == 0
+            try:
+                gen.throw(KeyError("stop"))
+            except KeyError as err:
+                assert err.args == ("stop",)
+            else:
+                assert False
+            assert list(gen) == []
+
+            gen = squares(4)
+            gen.close()
+            try:
+                gen.send(None)
+            except StopIteration:
+                pass
+            else:
+                assert False
+            "#
+        );
+    });
+}
@@ -237,3 +237,161 @@ fn borrowed_value_with_lifetime_of_self() {
         py_run!(py, inst, "assert inst.value == 'value'");
     });
 }
+
+#[pyclass]
+struct RenamedField {
+    /// the renamed field
+    #[pyo3(get, set, name = "x")]
+    value: i32,
+}
+
+#[test]
+fn renamed_field_getter_setter() {
+    Python::with_gil(|py| {
+        let inst = Py::new(py, RenamedField { value: 1 }).unwrap();
+
+        py_run!(
+            py,
+            inst,
+            "assert inst.x == 1; inst.x = 2; assert inst.x == 2"
+        );
+        py_run!(py, inst, "assert not hasattr(inst, 'value')");
+
+        let d = [("C", py.get_type::<RenamedField>())].into_py_dict_bound(py);
+        py_assert!(py, *d, "C.x.__doc__ == 'the renamed field'");
+    });
+}
+
+#[pyclass]
+struct SetterDocumented {
+    num: i32,
+}
+
+#[pymethods]
+impl SetterDocumented {
+    #[getter]
+    fn get_num(&self) -> i32 {
+        self.num
+    }
+
+    /// documented on the setter
+    #[setter]
+    fn set_num(&mut self, value: i32) {
+        self.num = value;
+    }
+}
+
+#[test]
+fn property_doc_from_setter() {
+    Python::with_gil(|py| {
+        let d = [("C", py.get_type::<SetterDocumented>())].into_py_dict_bound(py);
+        py_assert!(py, *d, "C.num.__doc__ == 'documented on the setter'");
+    });
+}
+
+#[pyclass]
+struct ClassWithDeleter {
+    value: Option<i32>,
+}
+
+#[pymethods]
+impl ClassWithDeleter {
+    #[getter]
+    fn get_value(&self) -> Option<i32> {
+        self.value
+    }
+
+    /// the value, if any
+    #[setter]
+    fn set_value(&mut self, value: i32) {
+        self.value = Some(value);
+    }
+
+    #[deleter]
+    fn del_value(&mut self) {
+        self.value = None;
+    }
+
+    #[deleter(cleared)]
+    fn clear(&mut self, _py: Python<'_>) -> PyResult<()> {
+        match self.value.take() {
+            Some(_) => Ok(()),
+            None => Err(pyo3::exceptions::PyAttributeError::new_err(
+                "already cleared",
+            )),
+        }
+    }
+}
+
+#[test]
+fn class_with_deleter() {
+    Python::with_gil(|py| {
+        let inst = Py::new(py, ClassWithDeleter { value: Some(1) }).unwrap();
+
+        py_run!(py, inst, "assert inst.value == 1");
+        py_run!(py, inst, "del inst.value; assert inst.value is None");
+        py_run!(py, inst, "inst.value = 2; assert inst.value == 2");
+
+        py_run!(py, inst, "del inst.cleared; assert inst.value is None");
+        py_expect_exception!(py, inst, "del inst.cleared", PyAttributeError);
+        py_expect_exception!(py, inst, "inst.cleared", PyAttributeError);
+        py_expect_exception!(py, inst, "inst.cleared = 1", PyAttributeError);
+
+        let d = [("C", py.get_type::<ClassWithDeleter>())].into_py_dict_bound(py);
+        py_assert!(py, *d, "C.value.__doc__ == 'the value, if any'");
+    });
+}
+
+#[pyclass]
+#[derive(Clone)]
+struct Inner {
+    #[pyo3(get, set)]
+    value: i32,
+}
+
+#[pyclass]
+struct Outer {
+    #[pyo3(get)]
+    shared: Py<Inner>,
+    #[pyo3(get)]
+    maybe_shared: Option<Py<Inner>>,
+    #[pyo3(get)]
+    inline: Inner,
+}
+
+#[test]
+fn nested_pyclass_getters() {
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            Outer {
+                shared: Py::new(py, Inner { value: 1 }).unwrap(),
+                maybe_shared: Some(Py::new(py, Inner { value: 2 }).unwrap()),
+                inline: Inner { value: 3 },
+            },
+        )
+        .unwrap();
+
+        // `Py<T>` fields return the same object, so changes through it are kept
+        py_run!(py, inst, "assert inst.shared is inst.shared");
+        py_run!(
+            py,
+            inst,
+            "inst.shared.value = 10; assert inst.shared.value == 10"
+        );
+        py_run!(py, inst, "assert inst.maybe_shared is inst.maybe_shared");
+        py_run!(
+            py,
+            inst,
+            "inst.maybe_shared.value = 20; assert inst.maybe_shared.value == 20"
+        );
+
+        // inline fields are copied each time
+        py_run!(py, inst, "assert inst.inline is not inst.inline");
+        py_run!(
+            py,
+            inst,
+            "inst.inline.value = 30; assert inst.inline.value == 3"
+        );
+    });
+}
@@ -3,7 +3,7 @@
 use pyo3::prelude::*;
 use pyo3::py_run;
 
-use pyo3::types::IntoPyDict;
+use pyo3::types::{IntoPyDict, PyType};
 
 #[path = "../src/tests/common.rs"]
 mod common;
@@ -109,6 +109,52 @@ fn mutation_fails() {
     });
 }
 
+#[pyclass(subclass)]
+struct Template {
+    name: String,
+}
+
+#[pymethods]
+impl Template {
+    #[new]
+    fn new(name: String) -> Self {
+        Template { name }
+    }
+
+    fn greeting(&self) -> &'static str {
+        "Hello"
+    }
+
+    fn render_static(&self) -> String {
+        format!("{}, {}!", self.greeting(), self.name)
+    }
+
+    fn render(slf: PyRef<'_, Self>) -> PyResult<String> {
+        let greeting: String = slf.call_method_on_self("greeting", ())?.extract()?;
+        Ok(format!("{}, {}!", greeting, slf.name))
+    }
+}
+
+#[test]
+fn call_method_on_self_honours_python_overrides() {
+    Python::with_gil(|py| {
+        let cls = py.get_type::<Template>();
+        py_run!(
+            py,
+            cls,
+            r#"
+class Casual(cls):
+    def greeting(self):
+        return "Hi"
+
+assert cls("world").render() == "Hello, world!"
+assert Casual("world").render() == "Hi, world!"
+assert Casual("world").render_static() == "Hello, world!"
+"#
+        );
+    });
+}
+
 #[test]
 fn is_subclass_and_is_instance() {
     Python::with_gil(|py| {
@@ -367,3 +413,346 @@ fn module_add_class_inherit_bool_fails() {
         );
     })
 }
+
+#[cfg(any(not(Py_LIMITED_API), Py_3_12))]
+mod metaclass {
+    use super::*;
+    use pyo3::sync::GILOnceCell;
+    use pyo3::types::{PyDict, PyType};
+
+    #[repr(transparent)]
+    struct Meta(PyAny);
+
+    pyo3::pyobject_native_type_core!(Meta, Meta::type_object_raw, #module=None);
+
+    impl Meta {
+        fn type_object_raw(py: Python<'_>) -> *mut pyo3::ffi::PyTypeObject {
+            static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+            TYPE_OBJECT
+                .get_or_init(py, || {
+                    let locals = PyDict::new_bound(py);
+                    py.run_bound(
+                        r#"
+class Meta(type):
+    def __getitem__(cls, item):
+        return f"{cls.__name__}[{item}]"
+"#,
+                        None,
+                        Some(&locals),
+                    )
+                    .unwrap();
+                    locals
+                        .get_item("Meta")
+                        .unwrap()
+                        .unwrap()
+                        .downcast_into::<PyType>()
+                        .unwrap()
+                        .unbind()
+                })
+                .as_ptr()
+                .cast()
+        }
+    }
+
+    #[pyclass(metaclass = Meta)]
+    struct WithMetaclass {
+        #[pyo3(get)]
+        value: i32,
+    }
+
+    #[pymethods]
+    impl WithMetaclass {
+        #[new]
+        fn new(value: i32) -> Self {
+            WithMetaclass { value }
+        }
+    }
+
+    #[test]
+    fn pyclass_with_metaclass() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<WithMetaclass>();
+            let meta = py.get_type::<Meta>();
+            py_run!(
+                py,
+                cls meta,
+                r#"
+assert type(cls) is meta
+assert cls["int"] == "WithMetaclass[int]"
+assert cls(5).value == 5
+assert isinstance(cls(5), cls)
+"#
+            );
+        });
+    }
+
+    #[repr(transparent)]
+    struct MetaWithInit(PyAny);
+
+    pyo3::pyobject_native_type_core!(MetaWithInit, MetaWithInit::type_object_raw, #module=None);
+
+    impl MetaWithInit {
+        fn type_object_raw(py: Python<'_>) -> *mut pyo3::ffi::PyTypeObject {
+            static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+            TYPE_OBJECT
+                .get_or_init(py, || {
+                    py.eval_bound(
+                        "type('MetaWithInit', (type,), {'__init__': lambda cls, *args: None})",
+                        None,
+                        None,
+                    )
+                    .unwrap()
+                    .downcast_into::<PyType>()
+                    .unwrap()
+                    .unbind()
+                })
+                .as_ptr()
+                .cast()
+        }
+    }
+
+    #[test]
+    fn pyclass_with_metaclass_overriding_init() {
+        #[pyclass(metaclass = MetaWithInit)]
+        struct WithInit;
+
+        Python::with_gil(|py| {
+            let m = PyModule::new(py, "test_module").unwrap();
+            let err = m.add_class::<WithInit>().unwrap_err();
+            assert_eq!(
+                err.cause(py).unwrap().to_string(),
+                "TypeError: metaclass of `#[pyclass]` WithInit must not override `__new__` or `__init__`"
+            );
+        });
+    }
+
+    #[cfg(not(Py_LIMITED_API))]
+    #[test]
+    fn pyclass_with_invalid_metaclass() {
+        #[pyclass(metaclass = PyDict)]
+        struct NotAType;
+
+        Python::with_gil(|py| {
+            let m = PyModule::new(py, "test_module").unwrap();
+            let err = m.add_class::<NotAType>().unwrap_err();
+            assert_eq!(
+                err.cause(py).unwrap().to_string(),
+                "TypeError: metaclass of `#[pyclass]` NotAType must be a subclass of `type`"
+            );
+        });
+    }
+}
+
+#[pyclass(abc = "collections.abc.Sized")]
+struct RegisteredSized;
+
+#[pymethods]
+impl RegisteredSized {
+    #[new]
+    fn new() -> Self {
+        RegisteredSized
+    }
+
+    fn __len__(&self) -> usize {
+        0
+    }
+}
+
+#[pyclass(abc = "collections.abc.Sequence")]
+struct RegisteredSequence;
+
+#[test]
+fn pyclass_registered_with_abc() {
+    Python::with_gil(|py| {
+        let sized = py.get_type::<RegisteredSized>();
+        let sequence = py.get_type::<RegisteredSequence>();
+        py_run!(
+            py,
+            sized sequence,
+            r#"
+import collections.abc
+assert issubclass(sized, collections.abc.Sized)
+assert isinstance(sized(), collections.abc.Sized)
+assert issubclass(sequence, collections.abc.Sequence)
+assert not issubclass(sized, collections.abc.Sequence)
+"#
+        );
+    });
+}
+
+#[pyclass(abc = "collections.abc.DoesNotExist")]
+struct RegisteredWithMissingAbc;
+
+#[test]
+fn pyclass_registered_with_missing_abc() {
+    Python::with_gil(|py| {
+        let m = PyModule::new(py, "test_module").unwrap();
+        let err = m.add_class::<RegisteredWithMissingAbc>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "RuntimeError: An error occurred while initializing class RegisteredWithMissingAbc"
+        );
+        assert!(err
+            .cause(py)
+            .unwrap()
+            .is_instance_of::<pyo3::exceptions::PyAttributeError>(py));
+    });
+}
+
+#[pyclass(subclass)]
+struct Quacks;
+
+#[pymethods]
+impl Quacks {
+    #[classmethod]
+    fn __subclasshook__(_cls: &Bound<'_, PyType>, other: &Bound<'_, PyType>) -> PyResult<PyObject> {
+        let py = other.py();
+        if other.hasattr("quack")? {
+            Ok(true.into_py(py))
+        } else {
+            Ok(py.NotImplemented())
+        }
+    }
+}
+
+#[test]
+fn pyclass_subclasshook_used_by_abc_subclass() {
+    Python::with_gil(|py| {
+        let quacks = py.get_type::<Quacks>();
+        py_run!(
+            py,
+            quacks,
+            r#"
+import abc
+class Duck(quacks, metaclass=abc.ABCMeta):
+    pass
+class Mallard:
+    def quack(self):
+        pass
+assert issubclass(Mallard, Duck)
+assert isinstance(Mallard(), Duck)
+assert not issubclass(int, Duck)
+"#
+        );
+    });
+}
+
+#[pyclass(abc = "pyo3_slow_abc.SlowAbc")]
+struct RegisteredWithSlowAbc;
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))] // We are building wasm Python with pthreads disabled
+fn type_object_created_once_across_threads() {
+    Python::with_gil(|py| {
+        // Registration releases the GIL, which gives other threads the chance to start creating
+        // the type object at the same time.
+        py.run_bound(
+            r#"
+import sys, time, types
+module = types.ModuleType("pyo3_slow_abc")
+class SlowAbc:
+    registered = []
+    @classmethod
+    def register(cls, subclass):
+        time.sleep(0.05)
+        cls.registered.append(subclass)
+module.SlowAbc = SlowAbc
+sys.modules["pyo3_slow_abc"] = module
+"#,
+            None,
+            None,
+        )
+        .unwrap();
+    });
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                Python::with_gil(|py| py.get_type::<RegisteredWithSlowAbc>().as_ptr() as usize)
+            })
+        })
+        .collect();
+    let type_objects: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert!(type_objects.iter().all(|&t| t == type_objects[0]));
+
+    Python::with_gil(|py| {
+        let cls = py.get_type::<RegisteredWithSlowAbc>();
+        py_run!(
+            py,
+            cls,
+            "import pyo3_slow_abc; assert pyo3_slow_abc.SlowAbc.registered == [cls]"
+        );
+    });
+}
+
+#[pyclass(abc = "pyo3_cyclic_abc.FirstAbc")]
+struct RegisteredWithFirstAbc;
+
+#[pyclass(abc = "pyo3_cyclic_abc.SecondAbc")]
+struct RegisteredWithSecondAbc;
+
+#[pyfunction]
+fn first_type(py: Python<'_>) -> Py<pyo3::types::PyType> {
+    py.get_type::<RegisteredWithFirstAbc>().into()
+}
+
+#[pyfunction]
+fn second_type(py: Python<'_>) -> Py<pyo3::types::PyType> {
+    py.get_type::<RegisteredWithSecondAbc>().into()
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))] // We are building wasm Python with pthreads disabled
+fn type_objects_needing_each_other_created_across_threads() {
+    Python::with_gil(|py| {
+        // Registering each class creates the other one, after releasing the GIL so that both
+        // threads below are creating their type object at the same time.
+        let globals = [
+            ("first_type", wrap_pyfunction!(first_type, py).unwrap()),
+            ("second_type", wrap_pyfunction!(second_type, py).unwrap()),
+        ]
+        .into_py_dict_bound(py);
+        py.run_bound(
+            r#"
+import sys, time, types
+module = types.ModuleType("pyo3_cyclic_abc")
+def make_abc(create_other):
+    class Abc:
+        registered = []
+        @classmethod
+        def register(cls, subclass):
+            if not cls.registered:
+                cls.registered.append(subclass)
+                time.sleep(0.05)
+                create_other()
+    return Abc
+module.FirstAbc = make_abc(second_type)
+module.SecondAbc = make_abc(first_type)
+sys.modules["pyo3_cyclic_abc"] = module
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+
+    let first = std::thread::spawn(|| {
+        Python::with_gil(|py| py.get_type::<RegisteredWithFirstAbc>().as_ptr() as usize)
+    });
+    let second = std::thread::spawn(|| {
+        Python::with_gil(|py| py.get_type::<RegisteredWithSecondAbc>().as_ptr() as usize)
+    });
+    let first = first.join().unwrap();
+    let second = second.join().unwrap();
+
+    Python::with_gil(|py| {
+        assert_eq!(
+            py.get_type::<RegisteredWithFirstAbc>().as_ptr() as usize,
+            first
+        );
+        assert_eq!(
+            py.get_type::<RegisteredWithSecondAbc>().as_ptr() as usize,
+            second
+        );
+    });
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "macros")]
+
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::{create_exception, py_run, IntoPyErr};
+use std::fmt;
+
+create_exception!(test_intopyerr, StorageError, pyo3::exceptions::PyException);
+
+#[derive(Debug)]
+struct ParseFailure(String);
+
+impl fmt::Display for ParseFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse {:?}", self.0)
+    }
+}
+
+impl From<ParseFailure> for PyErr {
+    fn from(err: ParseFailure) -> PyErr {
+        PyValueError::new_err(format!("parse failure: {}", err.0))
+    }
+}
+
+#[derive(Debug, IntoPyErr)]
+#[pyerr(exception = PyRuntimeError)]
+enum LibraryError {
+    #[pyerr(exception = PyValueError)]
+    InvalidValue(i32),
+    #[pyerr(exception = PyKeyError)]
+    Missing {
+        key: String,
+    },
+    #[pyerr(exception = StorageError)]
+    Storage,
+    #[pyerr(transparent)]
+    Parse(ParseFailure),
+    #[pyerr(transparent)]
+    Io {
+        source: std::io::Error,
+    },
+    Other,
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibraryError::InvalidValue(value) => write!(f, "invalid value: {}", value),
+            LibraryError::Missing { key } => write!(f, "missing key: {}", key),
+            LibraryError::Storage => write!(f, "storage unavailable"),
+            LibraryError::Parse(err) => err.fmt(f),
+            LibraryError::Io { source } => source.fmt(f),
+            LibraryError::Other => write!(f, "something else"),
+        }
+    }
+}
+
+#[test]
+fn test_enum_variants() {
+    Python::with_gil(|py| {
+        let err = PyErr::from(LibraryError::InvalidValue(-1));
+        assert!(err.is_instance_of::<PyValueError>(py));
+        assert_eq!(err.to_string(), "ValueError: invalid value: -1");
+
+        let err = PyErr::from(LibraryError::Missing {
+            key: "name".to_owned(),
+        });
+        assert!(err.is_instance_of::<PyKeyError>(py));
+        assert_eq!(err.to_string(), "KeyError: 'missing key: name'");
+
+        let err = PyErr::from(LibraryError::Storage);
+        assert!(err.is_instance_of::<StorageError>(py));
+        assert_eq!(err.to_string(), "StorageError: storage unavailable");
+
+        let err = PyErr::from(LibraryError::Other);
+        assert!(err.is_instance_of::<PyRuntimeError>(py));
+        assert_eq!(err.to_string(), "RuntimeError: something else");
+    });
+}
+
+#[test]
+fn test_enum_transparent_variants() {
+    Python::with_gil(|py| {
+        let err = PyErr::from(LibraryError::Parse(ParseFailure("x".to_owned())));
+        assert!(err.is_instance_of::<PyValueError>(py));
+        assert_eq!(err.to_string(), "ValueError: parse failure: x");
+
+        let err = PyErr::from(LibraryError::Io {
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "gone"),
+        });
+        assert!(err.is_instance_of::<pyo3::exceptions::PyFileNotFoundError>(py));
+    });
+}
+
+#[derive(Debug, IntoPyErr)]
+#[pyerr(exception = PyValueError)]
+struct OutOfRange<T: fmt::Display> {
+    value: T,
+}
+
+impl<T: fmt::Display> fmt::Display for OutOfRange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is out of range", self.value)
+    }
+}
+
+#[derive(Debug, IntoPyErr)]
+#[pyerr(transparent)]
+struct Wrapped(LibraryError);
+
+#[test]
+fn test_structs() {
+    Python::with_gil(|py| {
+        let err = PyErr::from(OutOfRange { value: 1.5 });
+        assert!(err.is_instance_of::<PyValueError>(py));
+        assert_eq!(err.to_string(), "ValueError: 1.5 is out of range");
+
+        let err = PyErr::from(Wrapped(LibraryError::Storage));
+        assert!(err.is_instance_of::<StorageError>(py));
+    });
+}
+
+fn lookup(key: &str) -> Result<i32, LibraryError> {
+    match key {
+        "answer" => Ok(42),
+        "negative" => Err(LibraryError::InvalidValue(-42)),
+        _ => Err(LibraryError::Missing {
+            key: key.to_owned(),
+        }),
+    }
+}
+
+#[pyfunction]
+fn lookup_value(key: &str) -> PyResult<i32> {
+    Ok(lookup(key)?)
+}
+
+#[test]
+fn test_question_mark_in_pyfunction() {
+    Python::with_gil(|py| {
+        let lookup_value = wrap_pyfunction!(lookup_value, py).unwrap();
+        py_run!(
+            py,
+            lookup_value,
+            r#"
+            assert lookup_value("answer") == 42
+
+            try:
+                lookup_value("negative")
+            except ValueError as e:
+                assert str(e) == "invalid value: -42"
+            else:
+                assert False
+
+            try:
+                lookup_value("unknown")
+            except KeyError as e:
+                assert e.args == ("missing key: unknown",)
+            else:
+                assert False
+            "#
+        );
+    });
+}
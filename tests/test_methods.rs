@@ -135,6 +135,46 @@ fn class_method_with_args() {
     });
 }
 
+#[pyclass]
+struct ClassGetItem {}
+
+#[pymethods]
+impl ClassGetItem {
+    fn __class_getitem__(cls: &Bound<'_, PyType>, key: &Bound<'_, PyAny>) -> PyResult<String> {
+        Ok(format!("{}[{}]", cls.as_gil_ref().qualname()?, key.str()?))
+    }
+}
+
+#[test]
+fn class_getitem_is_implicit_classmethod() {
+    Python::with_gil(|py| {
+        let d = [("C", py.get_type::<ClassGetItem>())].into_py_dict_bound(py);
+        py_assert!(py, *d, "C[int] == \"ClassGetItem[<class 'int'>]\"");
+    });
+}
+
+#[pyclass(generic)]
+struct GenericClass {}
+
+#[test]
+#[cfg(Py_3_9)]
+fn class_generic() {
+    Python::with_gil(|py| {
+        let d = [("C", py.get_type::<GenericClass>())].into_py_dict_bound(py);
+        py_run!(
+            py,
+            *d,
+            r#"
+import types
+alias = C[int]
+assert isinstance(alias, types.GenericAlias)
+assert alias.__origin__ is C
+assert alias.__args__ == (int,)
+"#
+        );
+    });
+}
+
 #[pyclass]
 struct StaticMethod {}
 
@@ -1152,3 +1192,202 @@ fn test_issue_2988() {
     ) {
     }
 }
+
+trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+    fn kind() -> &'static str;
+}
+
+#[pyclass]
+struct Square {
+    side: f64,
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.side *= factor;
+    }
+
+    fn kind() -> &'static str {
+        "square"
+    }
+}
+
+#[pyclass]
+struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.width *= factor;
+        self.height *= factor;
+    }
+
+    fn kind() -> &'static str {
+        "rectangle"
+    }
+}
+
+#[pymethods(from_trait = Shape)]
+impl Square {
+    #[new]
+    fn new(side: f64) -> Self {
+        Square { side }
+    }
+
+    fn area(&self) -> f64;
+    #[pyo3(signature = (factor = 2.0))]
+    fn scale(&mut self, factor: f64);
+    #[staticmethod]
+    fn kind() -> &'static str;
+}
+
+#[pymethods(from_trait = Shape)]
+impl Rectangle {
+    #[new]
+    fn new(width: f64, height: f64) -> Self {
+        Rectangle { width, height }
+    }
+
+    #[getter]
+    fn area(&self) -> f64;
+}
+
+#[test]
+fn test_methods_from_trait() {
+    Python::with_gil(|py| {
+        let square = py.get_type::<Square>();
+        let rectangle = py.get_type::<Rectangle>();
+        py_run!(
+            py,
+            square rectangle,
+            r#"
+s = square(3)
+assert s.area() == 9
+s.scale()
+assert s.area() == 36
+assert square.kind() == "square"
+assert rectangle(2, 5).area == 10
+"#
+        );
+    });
+}
+
+#[pyclass(unsendable)]
+struct SharedData {
+    items: Vec<i32>,
+    label: std::rc::Rc<str>,
+    tags: std::sync::Arc<Vec<String>>,
+}
+
+#[pymethods]
+impl SharedData {
+    fn items(&self) -> &Vec<i32> {
+        &self.items
+    }
+
+    fn label(&self) -> std::rc::Rc<str> {
+        self.label.clone()
+    }
+
+    fn tags(&self) -> std::sync::Arc<Vec<String>> {
+        self.tags.clone()
+    }
+
+    fn boxed_total(&self) -> Box<i32> {
+        Box::new(self.items.iter().sum())
+    }
+}
+
+#[test]
+fn test_return_references_and_smart_pointers() {
+    Python::with_gil(|py| {
+        let data = Py::new(
+            py,
+            SharedData {
+                items: vec![1, 2, 3],
+                label: "numbers".into(),
+                tags: std::sync::Arc::new(vec!["a".to_owned(), "b".to_owned()]),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            data,
+            r#"
+assert data.items() == [1, 2, 3]
+assert data.label() == "numbers"
+assert data.tags() == ["a", "b"]
+assert data.boxed_total() == 6
+"#
+        );
+    });
+}
+
+#[pyclass]
+struct CamelCaseMethods {
+    total_count: i32,
+}
+
+#[pymethods(rename_all = "camelCase")]
+impl CamelCaseMethods {
+    #[new]
+    fn new() -> Self {
+        CamelCaseMethods { total_count: 0 }
+    }
+
+    fn add_items(&mut self, item_count: i32) {
+        self.total_count += item_count;
+    }
+
+    #[getter]
+    fn get_total_count(&self) -> i32 {
+        self.total_count
+    }
+
+    #[setter]
+    fn set_total_count(&mut self, value: i32) {
+        self.total_count = value;
+    }
+
+    #[pyo3(name = "reset_all")]
+    fn reset(&mut self) {
+        self.total_count = 0;
+    }
+
+    fn __len__(&self) -> usize {
+        self.total_count as usize
+    }
+}
+
+#[test]
+fn test_methods_rename_all() {
+    Python::with_gil(|py| {
+        let cls = py.get_type::<CamelCaseMethods>();
+        py_run!(
+            py,
+            cls,
+            r#"
+obj = cls()
+obj.addItems(3)
+assert obj.totalCount == 3
+obj.totalCount = 5
+assert len(obj) == 5
+obj.reset_all()
+assert obj.totalCount == 0
+assert not hasattr(obj, "add_items")
+"#
+        );
+    });
+}
@@ -134,6 +134,23 @@ fn test_module_renaming() {
     });
 }
 
+#[pymodule]
+#[pyo3(gil_used = false)]
+fn gil_not_used(_: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("answer", 42)?;
+    Ok(())
+}
+
+#[test]
+fn test_module_gil_not_used() {
+    use pyo3::wrap_pymodule;
+
+    Python::attach(|py| {
+        let module = wrap_pymodule!(gil_not_used)(py);
+        py_assert!(py, module, "module.answer == 42");
+    });
+}
+
 #[test]
 fn test_module_from_code() {
     Python::with_gil(|py| {
@@ -234,6 +251,45 @@ fn test_module_dunder_all() {
     });
 }
 
+#[test]
+fn test_module_unexported_items() {
+    Python::with_gil(|py| {
+        #[pymodule]
+        fn unexported_items(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+            #[pyfn(m)]
+            fn exported() -> i32 {
+                1
+            }
+
+            #[pyfn(m, unexported)]
+            fn hidden() -> i32 {
+                2
+            }
+
+            #[pyfn(m, name = "also_hidden")]
+            #[pyo3(unexported)]
+            fn renamed_hidden() -> i32 {
+                3
+            }
+
+            m.add("exported_value", 4)?;
+            m.add_unexported("hidden_value", 5)?;
+            Ok(())
+        }
+
+        let module = pyo3::wrap_pymodule!(unexported_items)(py);
+
+        py_assert!(
+            py,
+            module,
+            "module.__all__ == ['exported', 'exported_value']"
+        );
+        py_assert!(py, module, "module.hidden() == 2");
+        py_assert!(py, module, "module.also_hidden() == 3");
+        py_assert!(py, module, "module.hidden_value == 5");
+    });
+}
+
 #[pyfunction]
 fn subfunction() -> String {
     "Subfunction".to_string()
@@ -292,6 +348,32 @@ fn test_module_nesting() {
     });
 }
 
+#[pyclass]
+struct RegisteredClass {}
+
+#[test]
+fn test_add_class_and_add_function_return_handles() {
+    Python::with_gil(|py| {
+        let m = PyModule::new(py, "handles").unwrap().as_borrowed().to_owned();
+
+        let ty = m.add_class::<RegisteredClass>().unwrap();
+        assert!(ty.is(&m.getattr("RegisteredClass").unwrap()));
+        let base = py
+            .eval_bound("__import__('abc').ABCMeta('Base', (), {})", None, None)
+            .unwrap();
+        base.call_method1("register", (&ty,)).unwrap();
+        assert!(ty.as_gil_ref().is_subclass(base.as_gil_ref()).unwrap());
+
+        let fun = wrap_pyfunction!(double, m.as_gil_ref()).unwrap();
+        let fun = m.add_function(&fun.as_borrowed()).unwrap();
+        assert!(fun.is(&m.getattr("double").unwrap()));
+        assert_eq!(fun.call1((2,)).unwrap().extract::<usize>().unwrap(), 4);
+
+        let wrapped = m.add_wrapped(wrap_pyfunction!(superfunction)).unwrap();
+        assert!(wrapped.is(&m.getattr("superfunction").unwrap()));
+    });
+}
+
 // Test that argument parsing specification works for pyfunctions
 
 #[pyfunction(signature = (a=5, *args))]
@@ -484,3 +566,136 @@ fn test_module_doc_hidden() {
         py_assert!(py, m, "m.__doc__ == ''");
     })
 }
+
+#[pyclass]
+struct LazyClass {}
+
+#[pyclass]
+struct OtherLazyClass {}
+
+#[pyclass]
+struct NoFallbackLazyClass {}
+
+#[pyfunction]
+fn module_getattr(name: &str) -> String {
+    format!("fallback {}", name)
+}
+
+#[pymodule]
+fn lazy_module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(module_getattr, m)?)?;
+    m.setattr("__getattr__", m.getattr("module_getattr")?)?;
+    m.add_class_lazy::<LazyClass>()?;
+    m.add_class_lazy::<OtherLazyClass>()?;
+    Ok(())
+}
+
+#[test]
+fn test_module_add_class_lazy() {
+    use pyo3::pyclass::set_type_init_hook;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static LAZY_CLASS_CREATED: AtomicBool = AtomicBool::new(false);
+    static OTHER_LAZY_CLASS_CREATED: AtomicBool = AtomicBool::new(false);
+
+    let previous = set_type_init_hook(Some(|_py, name, _elapsed| match name {
+        "LazyClass" => LAZY_CLASS_CREATED.store(true, Ordering::SeqCst),
+        "OtherLazyClass" => OTHER_LAZY_CLASS_CREATED.store(true, Ordering::SeqCst),
+        _ => {}
+    }));
+    let created = |name: &str| match name {
+        "LazyClass" => LAZY_CLASS_CREATED.load(Ordering::SeqCst),
+        _ => OTHER_LAZY_CLASS_CREATED.load(Ordering::SeqCst),
+    };
+
+    Python::with_gil(|py| {
+        let m = pyo3::wrap_pymodule!(lazy_module)(py);
+        assert!(!created("LazyClass"));
+        assert!(!created("OtherLazyClass"));
+
+        py_assert!(py, m, "'LazyClass' in m.__all__");
+        py_assert!(
+            py,
+            m,
+            "{'LazyClass', 'OtherLazyClass', 'module_getattr'} <= set(dir(m))"
+        );
+        py_assert!(py, m, "'LazyClass' not in m.__dict__");
+        assert!(!created("LazyClass"));
+
+        py_assert!(py, m, "m.LazyClass.__name__ == 'LazyClass'");
+        assert!(created("LazyClass"));
+        assert!(!created("OtherLazyClass"));
+        // the created class is stored in the module
+        py_assert!(py, m, "m.__dict__['LazyClass'] is m.LazyClass");
+
+        m.as_ref(py).getattr("OtherLazyClass").unwrap();
+        assert!(created("OtherLazyClass"));
+
+        // other names are forwarded to the `__getattr__` defined by the module
+        py_assert!(py, m, "m.missing == 'fallback missing'");
+    });
+
+    set_type_init_hook(previous);
+}
+
+#[test]
+fn test_module_add_class_lazy_missing_attribute() {
+    #[pymodule]
+    fn lazy_module_no_fallback(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+        m.add_class_lazy::<NoFallbackLazyClass>()
+    }
+
+    Python::with_gil(|py| {
+        let m = pyo3::wrap_pymodule!(lazy_module_no_fallback)(py);
+        py_expect_exception!(py, m, "m.missing", PyAttributeError);
+        py_assert!(py, m, "not hasattr(m, 'missing')");
+        py_assert!(py, m, "hasattr(m, 'NoFallbackLazyClass')");
+    });
+}
+
+pyo3::create_exception!(rust_extension, FacadeError, pyo3::exceptions::PyException);
+
+#[pyclass]
+struct FacadeClass {}
+
+#[test]
+fn test_module_add_type_to_existing_package() {
+    Python::with_gil(|py| {
+        let facade = PyModule::from_code(
+            py,
+            "__all__ = ['helper']\ndef helper(): pass",
+            "facade.py",
+            "facade",
+        )
+        .unwrap();
+        py.import_bound("sys")
+            .unwrap()
+            .getattr("modules")
+            .unwrap()
+            .set_item("facade", facade)
+            .unwrap();
+
+        let facade = PyModule::import(py, "facade").unwrap();
+        let ty = facade.add_type::<FacadeClass>().unwrap();
+        assert!(ty.is(facade.getattr("FacadeClass").unwrap()));
+        facade.add_type::<FacadeError>().unwrap();
+
+        py_assert!(
+            py,
+            facade,
+            "facade.__all__ == ['helper', 'FacadeClass', 'FacadeError']"
+        );
+        py_assert!(py, facade, "facade.FacadeClass.__module__ == 'facade'");
+        py_assert!(py, facade, "facade.FacadeError.__module__ == 'facade'");
+        py_run!(
+            py,
+            facade,
+            r#"
+import pickle
+err = pickle.loads(pickle.dumps(facade.FacadeError("oops")))
+assert type(err) is facade.FacadeError
+assert err.args == ("oops",)
+"#
+        );
+    });
+}
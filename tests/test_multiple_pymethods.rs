@@ -62,6 +62,31 @@ impl PyClassWithMultiplePyMethods {
     const CLASS_ATTRIBUTE: &'static str = "CLASS_ATTRIBUTE";
 }
 
+mod other_module {
+    use super::PyClassWithMultiplePyMethods;
+    use pyo3::prelude::*;
+
+    #[pymethods]
+    impl PyClassWithMultiplePyMethods {
+        fn in_other_module(&self) -> &'static str {
+            "in_other_module"
+        }
+    }
+}
+
+macro_rules! generated_method {
+    ($name:ident) => {
+        #[pymethods]
+        impl PyClassWithMultiplePyMethods {
+            fn $name(&self) -> &'static str {
+                stringify!($name)
+            }
+        }
+    };
+}
+
+generated_method!(macro_generated);
+
 #[test]
 fn test_class_with_multiple_pymethods() {
     Python::with_gil(|py| {
@@ -72,5 +97,7 @@ fn test_class_with_multiple_pymethods() {
         py_assert!(py, cls, "cls.staticmethod() == 'staticmethod'");
         py_assert!(py, cls, "cls.class_attribute == 'class_attribute'");
         py_assert!(py, cls, "cls.CLASS_ATTRIBUTE == 'CLASS_ATTRIBUTE'");
+        py_assert!(py, cls, "cls().in_other_module() == 'in_other_module'");
+        py_assert!(py, cls, "cls().macro_generated() == 'macro_generated'");
     })
 }
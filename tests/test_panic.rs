@@ -0,0 +1,93 @@
+#![cfg(feature = "macros")]
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::panic::{install_panic_hook, set_panic_translator, PanicDetails};
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+#[pyfunction]
+fn panics() {
+    panic!("oh no")
+}
+
+#[pyfunction]
+fn panics_with_payload() {
+    std::panic::panic_any(42_i32)
+}
+
+fn translate(_py: Python<'_>, details: &PanicDetails<'_>) -> Option<PyErr> {
+    let message = details.message()?;
+    let location = details.location().unwrap_or("unknown location");
+    Some(PyRuntimeError::new_err(format!(
+        "{} (at {})",
+        message, location
+    )))
+}
+
+// The panic hook and translator are global, so they are tested together in one test.
+#[test]
+fn test_panic_details_and_translator() {
+    Python::with_gil(|py| {
+        let panics = wrap_pyfunction!(panics, py).unwrap();
+        let panics_with_payload = wrap_pyfunction!(panics_with_payload, py).unwrap();
+
+        // without the hook, no location is known
+        py_run!(
+            py,
+            panics,
+            r#"
+try:
+    panics()
+except BaseException as e:
+    assert type(e).__name__ == "PanicException"
+    assert str(e) == "oh no"
+    assert e.location is None
+else:
+    assert False
+"#
+        );
+
+        install_panic_hook();
+        py_run!(
+            py,
+            panics,
+            r#"
+try:
+    panics()
+except BaseException as e:
+    assert e.location.startswith("tests/test_panic.rs:"), e.location
+else:
+    assert False
+"#
+        );
+
+        assert!(set_panic_translator(Some(translate)).is_none());
+        py_run!(
+            py,
+            panics,
+            r#"
+try:
+    panics()
+except RuntimeError as e:
+    assert str(e).startswith("oh no (at tests/test_panic.rs:"), str(e)
+else:
+    assert False
+"#
+        );
+        // the translator falls back to `PanicException` for non-string payloads
+        py_run!(
+            py,
+            panics_with_payload,
+            r#"
+try:
+    panics_with_payload()
+except BaseException as e:
+    assert type(e).__name__ == "PanicException"
+    assert str(e) == "panic from Rust code"
+else:
+    assert False
+"#
+        );
+        set_panic_translator(None);
+    });
+}
@@ -0,0 +1,187 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::types::{PyDict, PyModule};
+
+#[path = "../src/tests/common.rs"]
+mod common;
+
+#[pyclass(pickle, module = "test_pickle")]
+struct Point {
+    #[pyo3(get)]
+    x: i32,
+    #[pyo3(get)]
+    y: i32,
+    label: String,
+}
+
+#[pyclass(pickle, frozen, module = "test_pickle")]
+struct Pair(i32, #[cfg(any())] f64, String);
+
+#[pyclass(pickle, module = "test_pickle")]
+struct Unit;
+
+#[pyclass(pickle, subclass, module = "test_pickle")]
+struct Base {
+    #[pyo3(get)]
+    value: i32,
+}
+
+#[pymethods]
+impl Base {
+    #[new]
+    fn new(value: i32) -> Self {
+        Base { value }
+    }
+}
+
+/// Registers the classes in an importable module so that `pickle` can find them.
+fn register_module(py: Python<'_>) {
+    let module = PyModule::new(py, "test_pickle").unwrap();
+    module.add_class::<Point>().unwrap();
+    module.add_class::<Pair>().unwrap();
+    module.add_class::<Unit>().unwrap();
+    module.add_class::<Base>().unwrap();
+    py.import_bound("sys")
+        .unwrap()
+        .getattr("modules")
+        .unwrap()
+        .set_item("test_pickle", module)
+        .unwrap();
+}
+
+#[test]
+fn test_pickle_roundtrip() {
+    Python::with_gil(|py| {
+        register_module(py);
+        let point = Py::new(
+            py,
+            Point {
+                x: 1,
+                y: 2,
+                label: "origin".into(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            point,
+            r#"
+            import pickle
+            copy = pickle.loads(pickle.dumps(point))
+            assert type(copy) is type(point)
+            assert copy is not point
+            assert (copy.x, copy.y) == (1, 2)
+            assert copy.__getstate__() == {"x": 1, "y": 2, "label": "origin"}
+            "#
+        );
+
+        let copy: Py<Point> = py
+            .import_bound("pickle")
+            .unwrap()
+            .call_method1(
+                "loads",
+                (py.import_bound("pickle")
+                    .unwrap()
+                    .call_method1("dumps", (&point,))
+                    .unwrap(),),
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(copy.borrow(py).label, "origin");
+    });
+}
+
+#[test]
+fn test_pickle_tuple_and_unit_structs() {
+    Python::with_gil(|py| {
+        register_module(py);
+        let pair = Py::new(py, Pair(3, "three".into())).unwrap();
+        let unit = Py::new(py, Unit).unwrap();
+        py_run!(
+            py,
+            pair unit,
+            r#"
+            import pickle
+            assert pair.__getstate__() == (3, "three")
+            assert pickle.loads(pickle.dumps(pair)).__getstate__() == (3, "three")
+            assert type(pickle.loads(pickle.dumps(unit))) is type(unit)
+            assert pair.__reduce_ex__(2) == (type(pair).__pyo3_unpickle__, ((3, "three"),))
+            "#
+        );
+    });
+}
+
+#[test]
+fn test_pickle_python_subclass() {
+    Python::with_gil(|py| {
+        register_module(py);
+        py_run!(
+            py,
+            *py.import_bound("test_pickle").unwrap().dict(),
+            r#"
+            import pickle
+
+            class Sub(Base):
+                pass
+
+            Sub.__module__ = "test_pickle"
+            Sub.__qualname__ = "Sub"
+            globals()["Sub"] = Sub
+            __import__("sys").modules["test_pickle"].Sub = Sub
+
+            copy = pickle.loads(pickle.dumps(Sub(5)))
+            assert type(copy) is Sub
+            assert copy.value == 5
+            assert copy.__dict__ == {}
+
+            original = Sub(6)
+            original.extra = [1, 2]
+            copy = pickle.loads(pickle.dumps(original))
+            assert type(copy) is Sub
+            assert copy.value == 6
+            assert copy.extra == [1, 2]
+            "#
+        );
+    });
+}
+
+#[test]
+fn test_setstate() {
+    Python::with_gil(|py| {
+        let point = Py::new(
+            py,
+            Point {
+                x: 1,
+                y: 2,
+                label: "origin".into(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            point,
+            r#"
+            point.__setstate__({"x": 3, "y": 4, "label": "moved"})
+            assert point.__getstate__() == {"x": 3, "y": 4, "label": "moved"}
+            "#
+        );
+        let pair = Py::new(py, Pair(3, "three".into())).unwrap();
+        py_assert!(py, pair, "not hasattr(pair, '__setstate__')");
+    });
+}
+
+#[test]
+fn test_unpickle_invalid_state() {
+    Python::with_gil(|py| {
+        let cls = py.get_type::<Point>();
+        let state = PyDict::new_bound(py);
+        state.set_item("x", 1).unwrap();
+        py_expect_exception!(py, cls state, "cls.__pyo3_unpickle__(state)", PyKeyError);
+        py_expect_exception!(py, cls, "cls.__pyo3_unpickle__(1)", PyTypeError);
+        let cls = py.get_type::<Pair>();
+        py_expect_exception!(py, cls, "cls.__pyo3_unpickle__((1,))", PyValueError);
+    });
+}
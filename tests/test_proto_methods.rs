@@ -419,6 +419,62 @@ fn callable() {
     });
 }
 
+#[pyclass(vectorcall, subclass)]
+struct VectorcallCallable;
+
+#[pymethods]
+impl VectorcallCallable {
+    #[new]
+    fn new() -> Self {
+        VectorcallCallable
+    }
+
+    #[pyo3(signature = (arg, *, scale = 6))]
+    fn __call__(&self, arg: i32, scale: i32) -> i32 {
+        arg * scale
+    }
+}
+
+#[pyclass(extends = VectorcallCallable)]
+struct VectorcallCallableChild {
+    #[pyo3(get)]
+    value: i32,
+}
+
+#[test]
+fn callable_vectorcall() {
+    Python::with_gil(|py| {
+        let c = Py::new(py, VectorcallCallable).unwrap();
+        #[cfg(all(Py_3_8, not(Py_LIMITED_API), not(PyPy)))]
+        assert!(unsafe { pyo3::ffi::PyVectorcall_Function(c.as_ptr()) }.is_some());
+        py_assert!(py, c, "callable(c)");
+        py_assert!(py, c, "c(7) == 42");
+        py_assert!(py, c, "c(7, scale=2) == 14");
+        py_expect_exception!(py, c, "c()", PyTypeError);
+        py_expect_exception!(py, c, "c(1, 2)", PyTypeError);
+
+        let child = Py::new(
+            py,
+            PyClassInitializer::from(VectorcallCallable)
+                .add_subclass(VectorcallCallableChild { value: 5 }),
+        )
+        .unwrap();
+        py_assert!(py, child, "child.value == 5 and child(7) == 42");
+
+        let cls = py.get_type::<VectorcallCallable>();
+        py_run!(
+            py,
+            cls,
+            r#"
+            class PySubclass(cls):
+                pass
+
+            assert PySubclass()(7, scale=3) == 21
+            "#
+        );
+    });
+}
+
 #[pyclass]
 #[derive(Debug)]
 struct SetItem {
@@ -898,3 +954,87 @@ fn test_contains_opt_out() {
         py_expect_exception!(py, no_contains, "'a' in no_contains", PyTypeError);
     })
 }
+
+#[pyclass(len)]
+struct Bag {
+    items: Vec<u32>,
+}
+
+impl pyo3::pyclass::PyLen for Bag {
+    fn py_len(&self) -> usize {
+        pyo3::pyclass::PyLen::py_len(&self.items)
+    }
+}
+
+#[pyclass(len)]
+struct InfiniteRange;
+
+impl pyo3::pyclass::PyLen for InfiniteRange {
+    fn py_len(&self) -> usize {
+        usize::MAX
+    }
+}
+
+#[test]
+fn test_pyclass_len() {
+    Python::with_gil(|py| {
+        let empty = Py::new(py, Bag { items: vec![] }).unwrap();
+        let full = Py::new(py, Bag { items: vec![1, 2] }).unwrap();
+        let infinite = Py::new(py, InfiniteRange).unwrap();
+        py_run!(
+            py,
+            empty full infinite,
+            r#"
+import operator, sys
+assert len(empty) == 0 and not empty
+assert len(full) == 2 and full
+assert operator.length_hint(full) == 2
+assert infinite
+assert infinite.__length_hint__() == sys.maxsize
+try:
+    len(infinite)
+except OverflowError:
+    pass
+else:
+    assert False
+"#
+        );
+    });
+}
+
+#[pyclass(array, frozen)]
+struct Samples {
+    values: Box<[f64]>,
+}
+
+unsafe impl pyo3::pyclass::PyArrayData for Samples {
+    type Element = f64;
+
+    fn array_data(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+#[test]
+fn test_pyclass_array() {
+    Python::with_gil(|py| {
+        let values = vec![0.5, 1.5, 2.5].into_boxed_slice();
+        let samples = Py::new(py, Samples { values }).unwrap();
+        py_run!(
+            py,
+            samples,
+            r#"
+import ctypes, sys
+interface = samples.__array_interface__
+assert interface["version"] == 3
+assert interface["typestr"] == ("<f8" if sys.byteorder == "little" else ">f8")
+assert interface["shape"] == (3,)
+address, readonly = interface["data"]
+assert readonly
+values = ctypes.cast(address, ctypes.POINTER(ctypes.c_double))
+assert [values[i] for i in range(3)] == [0.5, 1.5, 2.5]
+assert callable(samples.__array__)
+"#
+        );
+    });
+}
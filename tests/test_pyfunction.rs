@@ -204,6 +204,85 @@ fn test_from_py_with_defaults() {
     });
 }
 
+#[test]
+fn test_default_factory_and_default_once() {
+    #[allow(clippy::unnecessary_wraps)]
+    fn new_list(py: Python<'_>) -> PyResult<Bound<'_, types::PyList>> {
+        Ok(types::PyList::empty_bound(py))
+    }
+
+    #[pyfunction]
+    fn append_to_new(
+        value: i32,
+        #[pyo3(default_factory = "new_list")] items: Bound<'_, types::PyList>,
+    ) -> PyResult<Bound<'_, types::PyList>> {
+        items.append(value)?;
+        Ok(items)
+    }
+
+    #[pyfunction]
+    fn append_to_shared(
+        value: i32,
+        #[pyo3(default_once = "new_list")] items: Bound<'_, types::PyList>,
+    ) -> PyResult<Bound<'_, types::PyList>> {
+        items.append(value)?;
+        Ok(items)
+    }
+
+    #[pyfunction(signature = (*, items))]
+    fn keyword_only(
+        #[pyo3(default_factory = "new_list")] items: Bound<'_, types::PyList>,
+    ) -> usize {
+        items.len()
+    }
+
+    #[pyclass]
+    struct Collector;
+
+    #[pymethods]
+    impl Collector {
+        fn collect<'py>(
+            &self,
+            value: i32,
+            #[pyo3(default_once = "new_list")] items: Bound<'py, types::PyList>,
+        ) -> PyResult<Bound<'py, types::PyList>> {
+            items.append(value)?;
+            Ok(items)
+        }
+    }
+
+    Python::with_gil(|py| {
+        let collector = Py::new(py, Collector).unwrap();
+        pyo3::py_run!(
+            py,
+            collector,
+            "assert collector.collect(1) == [1]; assert collector.collect(2) == [1, 2]"
+        );
+
+        let append_to_new = wrap_pyfunction!(append_to_new)(py).unwrap();
+        let append_to_shared = wrap_pyfunction!(append_to_shared)(py).unwrap();
+        let keyword_only = wrap_pyfunction!(keyword_only)(py).unwrap();
+        pyo3::py_run!(
+            py,
+            append_to_new append_to_shared keyword_only,
+            r#"
+            assert append_to_new(1) == [1]
+            assert append_to_new(2) == [2]
+            assert append_to_new(3, [0]) == [0, 3]
+            assert append_to_new.__text_signature__ == "(value, items=...)"
+
+            assert append_to_shared(1) == [1]
+            assert append_to_shared(2) == [1, 2]
+            assert append_to_shared(3, []) == [3]
+            assert append_to_shared(4) == [1, 2, 4]
+
+            assert keyword_only() == 0
+            assert keyword_only(items=[1]) == 1
+            "#
+        );
+    });
+}
+
 #[pyclass]
 #[derive(Debug, FromPyObject)]
 struct ValueClass {
@@ -293,7 +372,7 @@ conversion_error('string1', -100, ('string2', 10.), None, ValueClass(-5))",
         assert_eq!(
             extract_traceback(py, exception),
             "TypeError: argument 'struct_arg': failed to \
-    extract field ValueClass.value: OverflowError: can't convert negative int to unsigned"
+    extract field ValueClass.value: OverflowError: Python int -5 is out of range for usize"
         );
     });
 }
@@ -546,3 +625,448 @@ fn test_reference_to_bound_arguments() {
         py_assert!(py, function, "function(1, 2) == 3");
     })
 }
+
+#[test]
+fn test_wide_keyword_signature() {
+    #[pyfunction(signature = (a, b, /, c, d = 4, *, e, f = 6, g = 7, h = 8, i = 9, j = 10))]
+    #[allow(clippy::too_many_arguments)]
+    fn wide(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8, i: u8, j: u8) -> [u8; 10] {
+        [a, b, c, d, e, f, g, h, i, j]
+    }
+
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction!(wide, py).unwrap();
+        py_assert!(py, f, "f(1, 2, 3, e=5) == [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]");
+        py_assert!(
+            py,
+            f,
+            "f(1, 2, j=0, c=3, i=1, e=5, d=2) == [1, 2, 3, 2, 5, 6, 7, 8, 1, 0]"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 2, 3, e=5, k=1)",
+            PyTypeError,
+            "wide() got an unexpected keyword argument 'k'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 2, c=3, e=5, b=2)",
+            PyTypeError,
+            "wide() got some positional-only arguments passed as keyword arguments: 'b'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 2, 3, c=3, e=5)",
+            PyTypeError,
+            "wide() got multiple values for argument 'c'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, c=3, e=5)",
+            PyTypeError,
+            "wide() missing 1 required positional argument: 'b'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 2, 3)",
+            PyTypeError,
+            "wide() missing 1 required keyword argument: 'e'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 2, 3, 4, 5, e=5)",
+            PyTypeError,
+            "wide() takes from 3 to 4 positional arguments but 5 were given"
+        );
+    })
+}
+
+#[test]
+fn test_wide_keyword_signature_with_varargs() {
+    #[pyfunction(signature = (z, y, *args, x, w = 0, v = 0, u = 0, t = 0, s = 0, r = 0, q = 0, p = 0, **kwargs))]
+    #[allow(clippy::too_many_arguments)]
+    fn wide(
+        z: u8,
+        y: u8,
+        args: &types::PyTuple,
+        x: u8,
+        w: u8,
+        v: u8,
+        u: u8,
+        t: u8,
+        s: u8,
+        r: u8,
+        q: u8,
+        p: u8,
+        kwargs: Option<&types::PyDict>,
+    ) -> (Vec<u8>, usize, usize) {
+        (
+            vec![z, y, x, w, v, u, t, s, r, q, p],
+            args.len(),
+            kwargs.map_or(0, types::PyDict::len),
+        )
+    }
+
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction!(wide, py).unwrap();
+        py_assert!(
+            py,
+            f,
+            "f(1, 2, x=3) == ([1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 0], 0, 0)"
+        );
+        py_assert!(
+            py,
+            f,
+            "f(p=9, y=2, q=8, z=1, x=3, extra=0) == ([1, 2, 3, 0, 0, 0, 0, 0, 0, 8, 9], 0, 1)"
+        );
+        py_assert!(
+            py,
+            f,
+            "f(1, 2, 3, 4, x=3, s=5) == ([1, 2, 3, 0, 0, 0, 0, 5, 0, 0, 0], 2, 0)"
+        );
+        py_expect_exception!(py, f, "f(1, 2, z=1, x=3)", PyTypeError);
+        py_expect_exception!(py, f, "f(1, 2)", PyTypeError);
+    })
+}
+
+#[test]
+fn test_injected_arguments() {
+    struct Counter(usize);
+
+    impl<'py> pyo3::conversion::InjectedArgument<'py> for Counter {
+        fn inject(_context: &pyo3::conversion::CallContext<'_, 'py>) -> PyResult<Self> {
+            Ok(Counter(42))
+        }
+    }
+
+    #[pyfunction]
+    fn no_python_args(#[pyo3(inject)] counter: Counter) -> usize {
+        counter.0
+    }
+
+    #[pyfunction(signature = (a, *, b = 2))]
+    fn with_signature(
+        a: usize,
+        #[pyo3(inject)] counter: Counter,
+        #[pyo3(inject)] py: Python<'_>,
+        b: usize,
+    ) -> usize {
+        assert!(py.version_info() >= (3, 7));
+        a + b + counter.0
+    }
+
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction!(no_python_args, py).unwrap();
+        py_assert!(py, f, "f() == 42");
+        py_expect_exception!(py, f, "f(1)", PyTypeError);
+
+        let f = wrap_pyfunction!(with_signature, py).unwrap();
+        py_assert!(py, f, "f(1) == 45");
+        py_assert!(py, f, "f(1, b=0) == 43");
+        py_expect_exception!(py, f, "f(1, counter=1)", PyTypeError);
+    })
+}
+
+#[test]
+fn test_injected_call_context() {
+    /// Describes the call which created it.
+    struct CallInfo(String);
+
+    impl<'py> pyo3::conversion::InjectedArgument<'py> for CallInfo {
+        fn inject(context: &pyo3::conversion::CallContext<'_, 'py>) -> PyResult<Self> {
+            let module = match context.module() {
+                Some(module) => module.name()?.to_string(),
+                None => "-".to_owned(),
+            };
+            let slf = match context.slf() {
+                Some(slf) => slf.get_type().name()?.into_owned(),
+                None => "-".to_owned(),
+            };
+            let kwargs = match context.kwargs()? {
+                Some(kwargs) => kwargs.to_string(),
+                None => "-".to_owned(),
+            };
+            Ok(CallInfo(format!(
+                "{} {} {} {}",
+                module,
+                slf,
+                context.args(),
+                kwargs
+            )))
+        }
+    }
+
+    #[pyfunction(signature = (a, b = 0))]
+    fn function(a: i32, b: i32, #[pyo3(inject)] info: CallInfo) -> String {
+        let _ = (a, b);
+        info.0
+    }
+
+    #[pyclass]
+    struct Target;
+
+    #[pymethods]
+    impl Target {
+        #[pyo3(signature = (*args, **kwargs))]
+        fn method(
+            &self,
+            args: &PyAny,
+            kwargs: Option<&PyAny>,
+            #[pyo3(inject)] info: CallInfo,
+        ) -> String {
+            let _ = (args, kwargs);
+            info.0
+        }
+
+        fn __len__(&self, #[pyo3(inject)] info: CallInfo) -> usize {
+            info.0.len()
+        }
+    }
+
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "context_module").unwrap();
+        module.add_function(wrap_pyfunction!(function, module).unwrap()).unwrap();
+        let target = Py::new(py, Target).unwrap();
+        pyo3::py_run!(
+            py,
+            module target,
+            r#"
+assert module.function(1) == "context_module module (1,) -"
+assert module.function(1, b=2) == "context_module module (1,) {'b': 2}"
+assert target.method(1, 2, c=3) == "- builtins.Target (1, 2) {'c': 3}"
+assert len(target) == len("- builtins.Target () -")
+"#
+        );
+    })
+}
+
+/// Only implements the fallible `IntoPyObject`, not `IntoPy`.
+#[derive(Clone)]
+struct Even(i64);
+
+impl<'py> IntoPyObject<'py> for Even {
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        if self.0 % 2 != 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} is not even",
+                self.0
+            )));
+        }
+        Ok(self.0.into_py(py).into_bound(py))
+    }
+}
+
+#[pyfunction]
+fn double(value: i64) -> Even {
+    Even(value * 2)
+}
+
+#[pyfunction]
+fn maybe_even(value: &str) -> PyResult<Even> {
+    Ok(Even(value.parse()?))
+}
+
+#[pyclass]
+struct EvenHolder {
+    #[pyo3(get)]
+    value: Even,
+}
+
+#[pymethods]
+impl EvenHolder {
+    fn get(&self) -> Even {
+        self.value.clone()
+    }
+
+    fn __getitem__(&self, index: i64) -> Even {
+        Even(self.value.0 + index)
+    }
+}
+
+#[test]
+fn test_fallible_return_conversion() {
+    Python::with_gil(|py| {
+        let double = wrap_pyfunction!(double, py).unwrap();
+        let maybe_even = wrap_pyfunction!(maybe_even, py).unwrap();
+        let holder = Py::new(py, EvenHolder { value: Even(3) }).unwrap();
+        pyo3::py_run!(
+            py,
+            double maybe_even holder,
+            r#"
+            assert double(21) == 42
+            assert maybe_even("4") == 4
+
+            try:
+                maybe_even("5")
+            except ValueError as e:
+                assert str(e) == "5 is not even"
+            else:
+                assert False
+
+            import operator
+            getters = [
+                operator.attrgetter("value"),
+                operator.methodcaller("get"),
+                operator.itemgetter(0),
+            ]
+            for get in getters:
+                try:
+                    get(holder)
+                except ValueError as e:
+                    assert str(e) == "3 is not even"
+                else:
+                    assert False
+
+            assert holder[1] == 4
+            "#
+        );
+    });
+}
+
+#[pyfunction]
+fn halves(values: Vec<i64>) -> (Vec<Even>, HashMap<String, Even>) {
+    let halves: Vec<Even> = values.into_iter().map(|value| Even(value / 2)).collect();
+    let by_name = halves
+        .iter()
+        .map(|half| (half.0.to_string(), half.clone()))
+        .collect();
+    (halves, by_name)
+}
+
+#[test]
+fn test_fallible_return_conversion_in_containers() {
+    Python::with_gil(|py| {
+        let halves = wrap_pyfunction!(halves, py).unwrap();
+        py_assert!(py, halves, "halves([4, 8]) == ([2, 4], {'2': 2, '4': 4})");
+        py_expect_exception!(py, halves, "halves([4, 6])", PyValueError, "3 is not even");
+    });
+}
+
+/// Only implements the infallible `IntoPy`, not `IntoPyObject`.
+#[derive(Clone)]
+struct Legacy(i64);
+
+impl IntoPy<PyObject> for Legacy {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        (self.0 + 1).into_py(py)
+    }
+}
+
+#[pyfunction]
+fn legacy(value: i64) -> Legacy {
+    Legacy(value)
+}
+
+#[pyfunction]
+fn maybe_legacy(value: &str) -> PyResult<Legacy> {
+    Ok(Legacy(value.parse()?))
+}
+
+#[pyclass]
+struct LegacyHolder {
+    #[pyo3(get)]
+    value: Legacy,
+}
+
+#[test]
+fn test_into_py_return_conversion() {
+    Python::with_gil(|py| {
+        let legacy = wrap_pyfunction!(legacy, py).unwrap();
+        let maybe_legacy = wrap_pyfunction!(maybe_legacy, py).unwrap();
+        let holder = Py::new(py, LegacyHolder { value: Legacy(1) }).unwrap();
+        py_assert!(py, legacy, "legacy(1) == 2");
+        py_assert!(py, maybe_legacy, "maybe_legacy('2') == 3");
+        py_expect_exception!(py, maybe_legacy, "maybe_legacy('x')", PyValueError);
+        py_assert!(py, holder, "holder.value == 2");
+    });
+}
+
+#[pyclass]
+struct Counter {
+    count: i64,
+}
+
+#[pyclass]
+#[derive(Default)]
+struct Registry {
+    counters: Vec<Py<Counter>>,
+}
+
+#[pymethods]
+impl Registry {
+    fn register(&mut self, counter: Py<Counter>) {
+        self.counters.push(counter);
+    }
+
+    fn total(&self, py: Python<'_>) -> i64 {
+        self.counters
+            .iter()
+            .map(|counter| counter.borrow(py).count)
+            .sum()
+    }
+}
+
+#[pyfunction]
+fn increment_cell(counter: &PyCell<Counter>) {
+    counter.borrow_mut().count += 1;
+}
+
+#[pyfunction]
+fn increment_ref_mut(mut counter: PyRefMut<'_, Counter>) {
+    counter.count += 10;
+}
+
+#[pyfunction]
+fn read_count(counter: PyRef<'_, Counter>) -> i64 {
+    counter.count
+}
+
+#[pyfunction]
+fn transfer(mut from: PyRefMut<'_, Counter>, mut to: PyRefMut<'_, Counter>) {
+    to.count += from.count;
+    from.count = 0;
+}
+
+#[test]
+fn test_pyclass_receivers_do_not_copy() {
+    Python::with_gil(|py| {
+        let registry = Py::new(py, Registry::default()).unwrap();
+        let counter = Py::new(py, Counter { count: 0 }).unwrap();
+        let other = Py::new(py, Counter { count: 5 }).unwrap();
+        let increment_cell = wrap_pyfunction!(increment_cell, py).unwrap();
+        let increment_ref_mut = wrap_pyfunction!(increment_ref_mut, py).unwrap();
+        let read_count = wrap_pyfunction!(read_count, py).unwrap();
+        let transfer = wrap_pyfunction!(transfer, py).unwrap();
+        pyo3::py_run!(
+            py,
+            registry counter other increment_cell increment_ref_mut read_count transfer,
+            r#"
+            registry.register(counter)
+            increment_cell(counter)
+            increment_ref_mut(counter)
+            assert read_count(counter) == 11
+            assert registry.total() == 11
+
+            transfer(counter, other)
+            assert read_count(counter) == 0
+            assert read_count(other) == 16
+            assert registry.total() == 0
+
+            try:
+                transfer(other, other)
+            except RuntimeError as e:
+                assert str(e) == "Already borrowed"
+            else:
+                assert False
+            "#
+        );
+        // The registry holds the very same object that was mutated from Python.
+        assert!(registry.borrow(py).counters[0].is(&counter));
+    });
+}
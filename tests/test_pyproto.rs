@@ -0,0 +1,202 @@
+#![cfg(feature = "macros")]
+
+//! Tests for `#[pymethods]` on impls of the legacy `#[pyproto]` protocol traits.
+
+use pyo3::class::basic::CompareOp;
+use pyo3::class::{
+    PyContextProtocol, PyIterProtocol, PyMappingProtocol, PyNumberProtocol, PyObjectProtocol,
+};
+use pyo3::exceptions::PyKeyError;
+use pyo3::prelude::*;
+
+#[path = "../src/tests/common.rs"]
+mod common;
+
+#[pyclass]
+struct Vector {
+    #[pyo3(get)]
+    items: Vec<i64>,
+}
+
+#[pymethods]
+impl Vector {
+    #[new]
+    fn new(items: Vec<i64>) -> Self {
+        Vector { items }
+    }
+
+    fn sum(&self) -> i64 {
+        self.items.iter().sum()
+    }
+}
+
+#[pymethods]
+impl PyObjectProtocol for Vector {
+    fn __repr__(&self) -> String {
+        format!("Vector({:?})", self.items)
+    }
+
+    fn __richcmp__(&self, other: PyRef<'_, Vector>, op: CompareOp) -> PyObject {
+        let py = other.py();
+        match op {
+            CompareOp::Eq => (self.items == other.items).into_py(py),
+            CompareOp::Ne => (self.items != other.items).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+}
+
+#[pymethods]
+impl<'p> PyNumberProtocol<'p> for Vector {
+    fn __add__(&self, other: PyRef<'p, Vector>) -> Vector {
+        let items = self
+            .items
+            .iter()
+            .zip(&other.items)
+            .map(|(a, b)| a + b)
+            .collect();
+        Vector { items }
+    }
+
+    fn __neg__(&self) -> Vector {
+        Vector {
+            items: self.items.iter().map(|a| -a).collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyMappingProtocol for Vector {
+    fn __len__(&self) -> usize {
+        self.items.len()
+    }
+
+    fn __getitem__(&self, index: usize) -> PyResult<i64> {
+        self.items
+            .get(index)
+            .copied()
+            .ok_or_else(|| PyKeyError::new_err(index))
+    }
+}
+
+#[test]
+fn test_pyproto_and_pymethods_on_one_class() {
+    Python::with_gil(|py| {
+        let cls = py.get_type::<Vector>();
+        pyo3::py_run!(
+            py,
+            cls,
+            r#"
+v = cls([1, 2, 3])
+assert v.sum() == 6
+assert repr(v) == "Vector([1, 2, 3])"
+assert v == cls([1, 2, 3]) and v != cls([3, 2, 1])
+assert (v + cls([1, 1, 1])).items == [2, 3, 4]
+assert (-v).items == [-1, -2, -3]
+assert len(v) == 3 and v[1] == 2
+"#
+        );
+        py_expect_exception!(py, cls, "cls([1])[5]", PyKeyError);
+    });
+}
+
+#[pyclass]
+struct Countdown {
+    remaining: u32,
+    entered: bool,
+}
+
+#[pymethods]
+impl PyIterProtocol for Countdown {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<u32> {
+        if slf.remaining == 0 {
+            None
+        } else {
+            slf.remaining -= 1;
+            Some(slf.remaining)
+        }
+    }
+}
+
+#[pymethods]
+impl PyContextProtocol for Countdown {
+    fn __enter__(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.entered = true;
+        slf
+    }
+
+    fn __exit__(&mut self, _ty: &PyAny, _value: &PyAny, _traceback: &PyAny) -> bool {
+        self.entered = false;
+        false
+    }
+}
+
+#[test]
+fn test_pyproto_without_pymethods() {
+    Python::with_gil(|py| {
+        let countdown = Py::new(
+            py,
+            Countdown {
+                remaining: 3,
+                entered: false,
+            },
+        )
+        .unwrap();
+        py_assert!(py, countdown, "list(countdown) == [2, 1, 0]");
+        pyo3::py_run!(py, countdown, "with countdown as c: assert c is countdown");
+        assert!(!countdown.borrow(py).entered);
+    });
+}
+
+#[cfg(feature = "pyproto")]
+#[allow(deprecated)]
+mod pyproto_attribute {
+    use pyo3::class::{PyObjectProtocol, PySequenceProtocol};
+    use pyo3::prelude::*;
+
+    #[pyclass]
+    struct Word {
+        text: String,
+    }
+
+    #[pymethods]
+    impl Word {
+        fn upper(&self) -> String {
+            self.text.to_uppercase()
+        }
+    }
+
+    #[pyproto]
+    impl PyObjectProtocol for Word {
+        fn __str__(&self) -> String {
+            self.text.clone()
+        }
+    }
+
+    #[pyproto]
+    impl PySequenceProtocol for Word {
+        fn __len__(&self) -> usize {
+            self.text.len()
+        }
+    }
+
+    #[test]
+    fn test_pyproto_attribute() {
+        Python::with_gil(|py| {
+            let word = Py::new(
+                py,
+                Word {
+                    text: "pyo3".to_owned(),
+                },
+            )
+            .unwrap();
+            crate::py_assert!(py, word, "str(word) == 'pyo3'");
+            crate::py_assert!(py, word, "len(word) == 4");
+            crate::py_assert!(py, word, "word.upper() == 'PYO3'");
+        });
+    }
+}
@@ -122,3 +122,74 @@ fn test_nested_iter_reset() {
         assert!(reader_ref.inner.is_empty());
     });
 }
+
+/// A builder whose methods return `self` so that calls can be chained from Python.
+#[pyclass(subclass)]
+#[derive(Default)]
+struct Builder {
+    parts: Vec<String>,
+}
+
+#[pymethods]
+impl Builder {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(mut slf: PyRefMut<'_, Self>, part: String) -> PyRefMut<'_, Self> {
+        slf.parts.push(part);
+        slf
+    }
+
+    fn add_twice(slf: &PyCell<Self>, part: String) -> PyResult<&PyCell<Self>> {
+        slf.borrow_mut().parts.push(part.clone());
+        slf.call_method1("add", (part,))?;
+        Ok(slf)
+    }
+
+    fn owned(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn separator(&self) -> &'static str {
+        ","
+    }
+
+    fn build(slf: PyRef<'_, Self>) -> PyResult<String> {
+        // Calls through the Python object so that subclasses can override `separator`
+        let separator: String = slf.as_pyobject().call_method0("separator")?.extract()?;
+        Ok(slf.parts.join(&separator))
+    }
+}
+
+#[test]
+fn test_self_receivers_chaining() {
+    Python::with_gil(|py| {
+        let builder = py.get_type::<Builder>();
+        pyo3::py_run!(
+            py,
+            builder,
+            r#"
+b = builder()
+assert b.add("a").add_twice("b").owned() is b
+assert b.build() == "a,b,b"
+
+class Spaced(builder):
+    def separator(self):
+        return " "
+
+assert Spaced().add("x").add("y").build() == "x y"
+"#
+        );
+    });
+}
+
+#[test]
+fn test_as_pyobject() {
+    Python::with_gil(|py| {
+        let cell = PyCell::new(py, Builder::default()).unwrap();
+        assert!(cell.borrow().as_pyobject().is(cell));
+        assert!(cell.borrow_mut().as_pyobject().is(cell));
+    });
+}
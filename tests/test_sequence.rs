@@ -215,7 +215,7 @@ fn test_inplace_concat() {
         py_run!(
             py,
             *d,
-            "s += ByteSequence([4, 5]); assert list(s) == [1, 2, 3, 4, 5]"
+            "t = s; s += ByteSequence([4, 5]); assert list(s) == [1, 2, 3, 4, 5]; assert s is t"
         );
         py_expect_exception!(py, *d, "s += 'hello'", PyTypeError);
     });
@@ -239,7 +239,7 @@ fn test_inplace_repeat() {
         py_run!(
             py,
             *d,
-            "s = ByteSequence([1, 2]); s *= 3; assert list(s) == [1, 2, 1, 2, 1, 2]"
+            "s = t = ByteSequence([1, 2]); s *= 3; assert list(s) == [1, 2, 1, 2, 1, 2]; assert s is t"
         );
         py_expect_exception!(py, *d, "s = ByteSequence([1, 2]); s *= -1", PyValueError);
     });
@@ -0,0 +1,16 @@
+#![deny(deprecated)]
+
+use pyo3::class::PyObjectProtocol;
+use pyo3::prelude::*;
+
+#[pyclass]
+struct MyClass;
+
+#[pyproto]
+impl PyObjectProtocol for MyClass {
+    fn __str__(&self) -> &'static str {
+        "MyClass"
+    }
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+use pyo3::IntoPyErr;
+
+#[derive(IntoPyErr)]
+enum EmptyEnum {}
+
+#[derive(IntoPyErr)]
+enum MissingException {
+    #[pyerr(exception = pyo3::exceptions::PyValueError)]
+    Valid,
+    Missing,
+}
+
+#[derive(IntoPyErr)]
+struct MissingStructException;
+
+#[derive(IntoPyErr)]
+#[pyerr(transparent)]
+enum TransparentEnum {
+    A(String),
+}
+
+#[derive(IntoPyErr)]
+enum TransparentTwoFields {
+    #[pyerr(transparent)]
+    A(String, String),
+}
+
+#[derive(IntoPyErr)]
+enum ExceptionAndTransparent {
+    #[pyerr(exception = pyo3::exceptions::PyValueError, transparent)]
+    A(String),
+}
+
+#[derive(IntoPyErr)]
+#[pyerr(exception = pyo3::exceptions::PyValueError, exception = pyo3::exceptions::PyValueError)]
+struct DuplicateException;
+
+#[derive(IntoPyErr)]
+#[pyerr(message = "foo")]
+struct UnknownAttribute;
+
+#[derive(IntoPyErr)]
+enum VariantCrate {
+    #[pyerr(crate = "pyo3", exception = pyo3::exceptions::PyValueError)]
+    A,
+}
+
+#[derive(IntoPyErr)]
+union Union {
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pyo3::prelude::*;
+
+#[derive(Clone)]
+struct NotPython;
+
+#[pyclass(copy)]
+#[derive(Clone)]
+struct Holder {
+    inner: NotPython,
+}
+
+fn main() {}
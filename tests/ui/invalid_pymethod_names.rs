@@ -38,5 +38,12 @@ impl TestClass {
     fn get_two(&self) -> Self { Self { num: 0 } }
 }
 
+#[pymethods]
+impl TestClass {
+    #[pyo3(name = "setup")]
+    #[init]
+    fn init(&self) {}
+}
+
 
 fn main() {}
@@ -0,0 +1,70 @@
+//! Check that methods which would overwrite each other's type slot are rejected.
+
+use pyo3::class::{PyNumberProtocol, PyObjectProtocol};
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+
+#[pyclass]
+struct RichcmpAndEq {}
+
+#[pymethods]
+impl RichcmpAndEq {
+    fn __richcmp__(&self, _other: &PyAny, _op: CompareOp) -> bool {
+        true
+    }
+
+    fn __eq__(&self, _other: &PyAny) -> bool {
+        true
+    }
+}
+
+#[pyclass]
+struct DuplicateRepr {}
+
+#[pymethods]
+impl DuplicateRepr {
+    fn __repr__(&self) -> &'static str {
+        "a"
+    }
+
+    #[pyo3(name = "__repr__")]
+    fn other_repr(&self) -> &'static str {
+        "b"
+    }
+}
+
+#[pyclass]
+struct AddInBoth {}
+
+#[pymethods]
+impl AddInBoth {
+    fn __add__(&self, _other: &PyAny) -> i32 {
+        1
+    }
+}
+
+#[pymethods]
+impl PyNumberProtocol for AddInBoth {
+    fn __add__(&self, _other: &PyAny) -> i32 {
+        2
+    }
+}
+
+#[pyclass]
+struct ComparisonInBoth {}
+
+#[pymethods]
+impl ComparisonInBoth {
+    fn __lt__(&self, _other: &PyAny) -> bool {
+        true
+    }
+}
+
+#[pymethods]
+impl PyObjectProtocol for ComparisonInBoth {
+    fn __richcmp__(&self, _other: &PyAny, _op: CompareOp) -> bool {
+        true
+    }
+}
+
+fn main() {}
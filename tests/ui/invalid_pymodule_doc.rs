@@ -0,0 +1,9 @@
+use pyo3::prelude::*;
+
+#[pymodule]
+#[doc = "nul \0 byte"]
+fn module(_py: Python<'_>, _m: &PyModule) -> PyResult<()> {
+    Ok(())
+}
+
+fn main() {}